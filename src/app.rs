@@ -0,0 +1,5006 @@
+//! # Interactive Application
+//!
+//! `App` holds the repositories, command history, and session state of the
+//! interactive menus, and implements every menu flow. It lives in the
+//! library so tests can run whole sessions in-process: prompts read from and
+//! output goes to the console installed with `App::with_console` (see
+//! `ui::console`), which defaults to the terminal.
+
+// src/app.rs
+
+// Core logic from the yada-core library crate
+use yada_core::{models, repositories, commands, strategies, factories};
+
+// Standard library imports for I/O operations and data structures
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::collections::HashSet;
+use std::cell::OnceCell;
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveTime, Timelike, Weekday}; // Date/time handling
+
+// Import core models for the application
+use models::command_manager::{CommandManager, UndoPolicies, UndoPolicy, UNDO_POLICY_ENV_VAR};
+use models::command::{Command, CommandType};
+use models::profile::{Gender, ActivityLevel, UserProfile, DailyProfile};
+use models::validation;
+use models::food::{Food, Macros};
+use models::log::{self as food_log, CostSummary, DailyLog, DayNote};
+use models::search::{self, MatchQuality, SavedSearch, SearchMode, SUGGESTION_COUNT};
+use models::nutrient_filter::{self, Comparison, Nutrient, NutrientFilter};
+use models::category;
+use models::duplicates;
+use models::usage::{self, LoggedFood, ARCHIVE_AFTER_DAYS, QUICK_LOG_COUNT};
+use models::serving::{self as serving_units, ServingSize, ServingUnit};
+use models::quantity::Quantity;
+use models::exercise::{ExerciseEntry, ACTIVITIES};
+use models::meal::{self as meals, MealPlan, MealSlot};
+use models::meal_template::MealTemplate;
+use models::batch_log::{self, ResolvedItem};
+use models::food_phrase;
+use models::calorie_sources::{CalorieSources, TOP_FOOD_COUNT};
+use models::week::{self, WeekStart};
+use models::weekday_target::TargetOverride;
+use models::diet_preset::DietPreset;
+use models::life_stage::LifeStage;
+use models::macro_target::{MacroRatios, MacroTargets};
+use models::dietary::{self, Conflict, DietaryRestriction};
+use models::intake_limits::IntakeLimits;
+use models::reminder;
+use models::preferences::{ConfirmPolicy, DateFormat, UnitSystem};
+
+// Import repository pattern implementations for data persistence
+use repositories::food_repository::{FoodRepository, FoodSort};
+use repositories::log_repository::LogRepository;
+use repositories::profile_repository::ProfileRepository;
+use repositories::exercise_repository::ExerciseRepository;
+use repositories::meal_plan_repository::MealPlanRepository;
+use repositories::meal_template_repository::MealTemplateRepository;
+use repositories::settings_repository::SettingsRepository;
+use repositories::autosave::{Autosaver, AutosavePolicy, AutosaveTrigger, AUTOSAVE_ENV_VAR};
+use repositories::load_report::LoadReport;
+use repositories::lock::DataLock;
+use repositories::audit_log::AuditLog;
+use repositories::bundle;
+use repositories::encryption;
+
+// Import command pattern implementations for undo functionality
+use commands::food_commands::{AddFoodCommand, ArchiveFoodsCommand, EditKeywordsCommand, MergeFoodsCommand, UpdateFoodCommand};
+use commands::log_commands::{AddLogEntryCommand, RemoveLogEntryCommand, SetDayNoteCommand, UpdateLogEntryCommand};
+use commands::profile_commands::{UpdateUserProfileCommand, UpdateDailyProfileCommand};
+use commands::exercise_commands::{AddExerciseCommand, RemoveExerciseCommand};
+use commands::meal_plan_commands::SetMealPlanCommand;
+use commands::macro_command::MacroCommand;
+
+// Import strategy pattern for calorie calculations
+use strategies::calorie_calculator::{CalorieCalculatorFactory, ADAPTIVE_METHOD};
+use strategies::health_metrics::HealthMetrics;
+use strategies::target_modifier::TargetPipeline;
+
+// Import factory pattern for extensible food sources and other apps' exports
+use factories::async_food_source::LookupOptions;
+use factories::food_source_factory::{FoodSourceFactory, LOCAL_SOURCE};
+use yada_core::importers::ImporterFactory;
+
+// Import the prompt library used by every interactive flow
+use crate::ui::prompt::{self, PromptError};
+use crate::ui::console::{self, outln, InputProvider, OutputSink};
+use crate::ui::interrupt;
+use crate::ui::style::{self, Style};
+use crate::ui::i18n::Message;
+use crate::ui::comparison;
+use crate::ui::table::{Table, NAME_COLUMN_WIDTH};
+use crate::ui::food_list::{self, CategoryFilter, PageAction};
+use crate::ui::food_picker::{self, Pick};
+use crate::ui::highlight::DensityThresholds;
+use crate::ui::date_stack::DateStack;
+use crate::ui::charts::{self as text_charts, BarRow};
+use crate::ui::recovery;
+use crate::ui::reminder_checker::ReminderChecker;
+
+// Import the startup timings and the reports shown in the menus
+use crate::cli::status::DailyStatus;
+use crate::cli::import;
+use crate::cli::profiling::StartupProfile;
+use crate::reports::daily_summary::{DailySummary, JournalSetting};
+
+// Import configuration for data file locations
+use crate::config::{self, AppConfig, DataDirSource};
+use crate::ui::users;
+
+// Enumeration representing all possible menu options in the application
+// This provides a type-safe way to handle user menu selections
+enum MenuOption {
+    ManageFood,   // Add or create new foods (basic/composite)
+    ViewFood,     // Display all foods in the database
+    LogFood,      // Record food consumption for the current date
+    ViewLog,      // View and manage food consumption logs (with delete functionality)
+    Exercise,     // Log exercise and view calories burned for the current date
+    ManageProfile, // Update user profile information
+    ViewStats,    // Display nutritional statistics and calorie calculations
+    ChangeDate,   // Change the current working date for the application
+    SaveData,     // Manually save all data to persistent storage
+    Undo,         // Undo the last executed command
+    Preferences,  // Units, autosave, search mode, date format, and data directory
+    Exit,         // Exit the application with automatic data saving
+}
+
+// Main application structure containing all repositories and managers
+// This implements the Facade pattern by providing a unified interface to the complex subsystem
+pub struct App {
+    // Repository pattern implementations for data persistence
+    food_repo: FoodRepository,           // Manages the food database
+    log_repo: LogRepository,             // Manages daily food consumption logs
+    profile_repo: ProfileRepository,     // Manages user profile data
+    exercise_repo: ExerciseRepository,   // Manages exercise entries by date
+    meal_plan_repo: MealPlanRepository,  // Manages planned foods per meal slot by date
+    meal_template_repo: MealTemplateRepository, // Manages named lists of foods logged together
+    settings_repo: SettingsRepository,   // Manages app-wide settings such as reminders
+    
+    // Command pattern for undo functionality
+    command_manager: CommandManager,     // Tracks and manages command history
+    autosaver: Autosaver,                // Decides when dirty repositories are persisted
+    
+    // Factory patterns for extensible architecture
+    calculator_factory: CalorieCalculatorFactory, // Creates calorie calculation strategies
+    food_source_factory: OnceCell<FoodSourceFactory>, // Food source implementations, created on first use
+    
+    // Application state
+    current_date: NaiveDate,            // Current working date for logging operations
+    date_stack: DateStack,              // Bookmarked working dates for this session
+    config: AppConfig,                  // Resolved data directory and settings
+    load_reports: Vec<(String, LoadReport)>, // Data files that did not load exactly as written
+    reminder_checker: Option<ReminderChecker>, // Prints due reminders while a prompt waits (terminals only)
+    _data_lock: DataLock,               // Keeps other instances out of the data directory until dropped
+}
+
+impl App {
+    /// Creates the application with its own input and output instead of the terminal
+    /// Installs them as the current thread's console (see `ui::console`), so every
+    /// prompt of the session, including load recovery at startup, reads from `input`
+    /// and everything printed goes to `output`
+    /// Parameters: config - resolved configuration with the data file locations
+    ///             profile - receives the time spent on each startup phase
+    ///             input - source of the lines the user types
+    ///             output - destination of the menus
+    /// Returns: Result containing the App instance or an IO error
+    pub fn with_console(
+        config: AppConfig,
+        profile: &mut StartupProfile,
+        input: impl InputProvider + 'static,
+        output: impl OutputSink + 'static,
+    ) -> Result<Self, io::Error> {
+        console::install(input, output);
+        Self::new(config, profile)
+    }
+
+    /// Creates a new instance of the YADA application
+    /// Initializes all repositories, managers, and factories
+    /// Seeds the food database with initial foods if empty
+    /// Optional subsystems (external food sources) are created on first use instead
+    /// Fails when another instance holds the data directory's lock file
+    /// Parameters: config - resolved configuration with the data file locations
+    ///             profile - receives the time spent on each startup phase
+    /// Returns: Result containing the App instance or an IO error
+    pub fn new(config: AppConfig, profile: &mut StartupProfile) -> Result<Self, io::Error> {
+        // Initialize repositories for data persistence inside the data directory
+        config.ensure_data_dir()?;
+        // Another instance saving the same files would overwrite this one's changes
+        let data_lock = DataLock::acquire(&config.data_dir)?;
+        // A file that fails to load opens the recovery menu (interactive terminals only)
+        let food_repo = Self::open_repository(&config.foods_path(), "Load foods", profile, FoodRepository::new)?;
+        profile.annotate_last(&format!("{} foods", food_repo.get_all_foods().len()));
+        let log_repo = Self::open_repository(&config.logs_path(), "Load logs", profile, LogRepository::new)?;
+        let days = log_repo.get_logs().len();
+        profile.annotate_last(&format!("{} {}", days, if days == 1 { "day" } else { "days" }));
+        let profile_repo = Self::open_repository(&config.profile_path(), "Load profile", profile, ProfileRepository::new)?;
+        let exercise_repo = Self::open_repository(&config.exercise_path(), "Load exercise", profile, ExerciseRepository::new)?;
+        let meal_plan_repo = Self::open_repository(&config.meal_plans_path(), "Load meal plans", profile, MealPlanRepository::new)?;
+        let meal_template_repo = Self::open_repository(&config.meal_templates_path(), "Load meal templates", profile, MealTemplateRepository::new)?;
+        let settings_repo = Self::open_repository(&config.settings_path(), "Load settings", profile, SettingsRepository::new)?;
+        
+        // Skipped lines and defaulted values are listed at startup, or stop it in strict mode
+        let load_reports = Self::check_load_reports(&config, vec![
+            (config.foods_path(), food_repo.load_report()),
+            (config.logs_path(), log_repo.load_report()),
+            (config.profile_path(), profile_repo.load_report()),
+            (config.exercise_path(), exercise_repo.load_report()),
+            (config.meal_plans_path(), meal_plan_repo.load_report()),
+            (config.meal_templates_path(), meal_template_repo.load_report()),
+            (config.settings_path(), settings_repo.load_report()),
+        ])?;
+        
+        // Initialize command manager with a capacity of 100 commands for undo functionality;
+        // per-type undo policies come from YADA_UNDO_POLICY
+        let command_manager = Self::command_manager(&config, UndoPolicies::from_env());
+        
+        // Autosave policy comes from YADA_AUTOSAVE or else the autosave preference
+        let autosaver = Autosaver::new(AutosavePolicy::from_env_or(settings_repo.preferences().autosave.clone()));
+        
+        // Initialize factory patterns for extensible architecture; the adaptive
+        // method's logged intake is added below (see `refresh_intake`)
+        let calculator_factory = CalorieCalculatorFactory::new();
+        
+        // Set current date as the working date for the application
+        let current_date = Local::now().date_naive();
+        
+        let mut app = App {
+            food_repo,
+            log_repo,
+            profile_repo,
+            exercise_repo,
+            meal_plan_repo,
+            meal_template_repo,
+            settings_repo,
+            command_manager,
+            autosaver,
+            calculator_factory,
+            food_source_factory: OnceCell::new(),
+            current_date,
+            date_stack: DateStack::new(),
+            config,
+            load_reports,
+            reminder_checker: None,
+            _data_lock: data_lock,
+        };
+        
+        // Seed the database with initial foods if it's empty (first-time setup)
+        if app.food_repo.get_all_foods().is_empty() {
+            profile.time("Seed foods", || app.seed_initial_foods());
+        }
+        
+        // Entries logged by older versions keep the calories their foods have now
+        app.log_repo.backfill_calories(app.food_repo.get_foods());
+        
+        // Pick up uses logged by older versions (and stamp foods never seen before)
+        profile.time("Sync food usage", || {
+            let found = usage::collect_usage(app.log_repo.get_logs(), app.food_repo.get_foods(), current_date);
+            app.food_repo.sync_usage(&found, current_date);
+        });
+        app.refresh_intake();
+        
+        Ok(app)
+    }
+
+    /// Opens one repository, timing the load and recovering from failures
+    /// When loading fails and the input is interactive, the recovery menu offers to restore
+    /// a backup or skip the file; scripted runs fail with the original error instead
+    /// Parameters: path - data file to load
+    ///             label - phase name for the startup profile
+    ///             profile - receives the load time (recovery time is not counted)
+    ///             open - repository constructor, e.g. FoodRepository::new
+    /// Returns: Result containing the repository or the load error
+    fn open_repository<R>(
+        path: &str,
+        label: &str,
+        profile: &mut StartupProfile,
+        open: impl Fn(&str) -> io::Result<R>,
+    ) -> Result<R, io::Error> {
+        match profile.time(label, || open(path)) {
+            Ok(repo) => Ok(repo),
+            Err(e) if console::is_interactive() => recovery::recover(path, e, open),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Keeps the load reports of files that did not load exactly as written
+    /// In strict mode the first such file stops loading, with its problems as the error
+    /// Parameters: config - configuration with the load mode
+    ///             reports - path and load report of each data file
+    /// Returns: Result containing the reports worth showing or the strict-mode error
+    fn check_load_reports(config: &AppConfig, reports: Vec<(String, &LoadReport)>) -> Result<Vec<(String, LoadReport)>, io::Error> {
+        let mut damaged = Vec::new();
+        for (path, report) in reports.into_iter().filter(|(_, report)| !report.is_clean()) {
+            config.load_mode.check(&path, report)?;
+            damaged.push((path, report.clone()));
+        }
+        Ok(damaged)
+    }
+
+    /// Lists what was skipped or defaulted while loading the data files
+    fn print_load_reports(&self) {
+        for (path, report) in &self.load_reports {
+            outln!("\n{}", Message::LoadProblems.format(&[path as &dyn std::fmt::Display, &report.summary()]));
+            for line in report.describe() {
+                outln!("  {}", line);
+            }
+            outln!("{}", Message::LoadProblemsHint);
+        }
+    }
+
+    /// Returns the food source factory, creating it on first use
+    /// Sources may load large datasets, so this keeps them off the startup path
+    /// Plugins in the data directory's `plugins` folder are registered here
+    fn food_sources(&self) -> &FoodSourceFactory {
+        self.food_source_factory.get_or_init(|| {
+            let mut factory = FoodSourceFactory::new();
+            for problem in factory.register_plugins(&self.config.plugins_dir(), &self.config.food_cache_dir(), LookupOptions::from_env()) {
+                outln!("{}", Message::SkippedPlugin.format(&[&problem]));
+            }
+            factory
+        })
+    }
+
+      /// Seeds the food database with a comprehensive set of basic and composite foods
+    /// This method is called during first-time application setup when the food database is empty
+    /// Creates 24 basic foods across different categories and 2 composite foods as examples
+    fn seed_initial_foods(&mut self) {
+        outln!("{}", Message::SeedingFoods);
+        
+        // Helper closure for adding basic foods with error handling
+        // Parameters: id, name, keywords (comma-separated), calories per serving,
+        // protein/carbs/fat/fiber in grams per serving, and serving weight in grams
+        let mut add_basic_food = |id: &str, name: &str, keywords: &str, calories: f64, macros: (f64, f64, f64, f64), grams: f64| {
+            let kw_set: HashSet<String> = keywords.split(',')
+                .map(|s| s.trim().to_lowercase().to_string())
+                .collect();
+            let (protein, carbs, fat, fiber) = macros;
+            let food_category = category::infer(name, &kw_set).map(str::to_string);
+            let food = Food::new_basic(id.to_string(), name.to_string(), kw_set, calories)
+                .with_macros(Macros::new(protein, carbs, fat).with_fiber(fiber))
+                .with_serving_details(Some(grams), None)
+                .with_metadata(food_category, HashSet::new());
+            self.food_repo.add_food(food).ok(); // Ignore errors during seeding
+        };
+        
+        // === DAIRY PRODUCTS ===
+        add_basic_food("milk_whole", "Whole Milk (1 cup)", "milk,dairy,drink", 150.0, (8.0, 12.0, 8.0, 0.0), 244.0);
+        add_basic_food("milk_skim", "Skim Milk (1 cup)", "milk,dairy,drink,skim", 90.0, (8.0, 12.0, 0.2, 0.0), 245.0);
+        add_basic_food("cheese_cheddar", "Cheddar Cheese (1 oz)", "cheese,dairy,cheddar", 110.0, (7.0, 0.4, 9.0, 0.0), 28.0);
+        add_basic_food("yogurt_plain", "Plain Yogurt (1 cup)", "yogurt,dairy", 120.0, (9.0, 12.0, 4.0, 0.0), 245.0);
+        
+        // === MEAT & PROTEIN ===
+        add_basic_food("chicken_breast", "Chicken Breast (4 oz)", "chicken,meat,protein", 170.0, (35.0, 0.0, 3.5, 0.0), 113.0);
+        add_basic_food("beef_ground", "Ground Beef 85% (4 oz)", "beef,meat,protein", 240.0, (21.0, 0.0, 17.0, 0.0), 113.0);
+        add_basic_food("eggs", "Eggs (1 large)", "eggs,protein", 70.0, (6.0, 0.6, 5.0, 0.0), 50.0);
+        add_basic_food("tuna", "Tuna (1 can)", "tuna,fish,protein", 180.0, (40.0, 0.0, 1.5, 0.0), 165.0);
+        
+        // === FRUITS ===
+        add_basic_food("apple", "Apple (medium)", "apple,fruit", 95.0, (0.5, 25.0, 0.3, 4.4), 182.0);
+        add_basic_food("banana", "Banana (medium)", "banana,fruit", 105.0, (1.3, 27.0, 0.4, 3.1), 118.0);
+        add_basic_food("orange", "Orange (medium)", "orange,fruit,citrus", 65.0, (1.3, 16.0, 0.2, 3.4), 131.0);
+        add_basic_food("strawberries", "Strawberries (1 cup)", "strawberry,fruit,berries", 50.0, (1.0, 12.0, 0.5, 3.0), 152.0);
+        
+        // === VEGETABLES ===
+        add_basic_food("broccoli", "Broccoli (1 cup)", "broccoli,vegetable,veggie", 55.0, (3.7, 11.0, 0.6, 5.1), 156.0);
+        add_basic_food("carrot", "Carrot (medium)", "carrot,vegetable,veggie", 25.0, (0.6, 6.0, 0.1, 1.7), 61.0);
+        add_basic_food("spinach", "Spinach (1 cup)", "spinach,vegetable,veggie,leafy", 7.0, (0.9, 1.1, 0.1, 0.7), 30.0);
+        add_basic_food("potato", "Potato (medium)", "potato,vegetable,starchy", 110.0, (3.0, 26.0, 0.1, 3.8), 173.0);
+        
+        // === GRAINS & STARCHES ===
+        add_basic_food("bread_wheat", "Wheat Bread (1 slice)", "bread,grain,wheat", 80.0, (4.0, 14.0, 1.0, 1.9), 32.0);
+        add_basic_food("rice_white", "White Rice (1 cup cooked)", "rice,grain,white", 200.0, (4.2, 45.0, 0.4, 0.6), 158.0);
+        add_basic_food("pasta", "Pasta (1 cup cooked)", "pasta,grain", 220.0, (8.0, 43.0, 1.3, 2.5), 140.0);
+        add_basic_food("oatmeal", "Oatmeal (1 cup cooked)", "oatmeal,grain,breakfast", 160.0, (6.0, 28.0, 3.0, 4.0), 234.0);
+        
+        // === OTHER FOODS ===
+        add_basic_food("peanut_butter", "Peanut Butter (2 tbsp)", "peanut,butter,spread", 190.0, (7.0, 7.0, 16.0, 1.6), 32.0);
+        add_basic_food("jelly", "Grape Jelly (1 tbsp)", "jelly,grape,spread", 50.0, (0.0, 13.0, 0.0, 0.2), 20.0);
+        add_basic_food("olive_oil", "Olive Oil (1 tbsp)", "oil,fat", 120.0, (0.0, 0.0, 14.0, 0.0), 14.0);
+        add_basic_food("soda", "Soda (12 oz can)", "soda,drink,sugar", 150.0, (0.0, 39.0, 0.0, 0.0), 370.0);        
+        // === COMPOSITE FOODS DEMONSTRATION ===
+        // Create example composite foods to show the Composite pattern implementation
+        
+        // First composite food: Peanut Butter Sandwich (bread + peanut butter)
+        let mut pb_sandwich = Food::new_composite(
+            "pb_sandwich".to_string(),
+            "Peanut Butter Sandwich".to_string(),
+            ["sandwich", "peanut butter", "lunch"].iter().map(|s| s.to_string()).collect(),
+            vec![("bread_wheat".to_string(), 2.0), ("peanut_butter".to_string(), 1.0)]
+        );
+        
+        // Calculate total calories and macros by summing component values * servings
+        pb_sandwich.calculate_from_components(self.food_repo.get_foods());
+        let food_category = category::infer(&pb_sandwich.name, &pb_sandwich.keywords).map(str::to_string);
+        pb_sandwich.category = food_category;
+        self.food_repo.add_food(pb_sandwich).ok();
+        
+        // Second composite food: PB&J Sandwich (extends pb_sandwich with jelly)
+        // This demonstrates composites can contain other composites
+        let mut pbj_sandwich = Food::new_composite(
+            "pbj_sandwich".to_string(),
+            "PB&J Sandwich".to_string(),
+            ["sandwich", "peanut butter", "jelly", "lunch"].iter().map(|s| s.to_string()).collect(),
+            vec![("pb_sandwich".to_string(), 1.0), ("jelly".to_string(), 1.0)]
+        );
+        
+        // Calculate calories and macros for this composite food
+        pbj_sandwich.calculate_from_components(self.food_repo.get_foods());
+        let food_category = category::infer(&pbj_sandwich.name, &pbj_sandwich.keywords).map(str::to_string);
+        pbj_sandwich.category = food_category;
+        self.food_repo.add_food(pbj_sandwich).ok();
+        
+        outln!("{}", Message::FoodsSeeded.format(&[&24, &2]));
+        
+        // Persist the seeded database to the file system
+        if let Err(e) = self.food_repo.save() {
+            outln!("{}", Message::ErrorSavingSeededFoods.format(&[&e]));
+        }
+    }
+      /// Main application loop that handles user interaction and menu navigation
+    /// 
+    /// This method implements the main event loop of the application:
+    /// 1. Welcomes the user and ensures a profile exists (creates one if needed)
+    /// 2. Displays the main menu and processes user choices
+    /// 3. Delegates to appropriate handler methods based on user selection
+    /// 4. Automatically saves data before exiting
+    /// 
+    /// The loop continues until the user chooses to exit, ensuring persistent
+    /// application state and clean shutdown with data preservation.
+    pub fn run(&mut self) {
+        outln!("{}", Message::Welcome);
+        outln!("{}", Message::DataDirectory.format(&[&self.config.data_dir.display()]));
+        if self.config.user.is_some() {
+            outln!("{}", Message::CurrentUser.format(&[&self.config.user_name()]));
+        }
+        if self.config.data_dir_source == DataDirSource::Legacy {
+            outln!("{}", Message::LegacyDataDir);
+        }
+        self.print_load_reports();
+        
+        // Check if we have a user profile - required for calorie calculations
+        if self.profile_repo.get_profile().is_none() {
+            outln!("{}", Message::NoProfileFound);
+            self.create_initial_profile();
+        }
+        
+        // A new day starts with yesterday's weight and activity level
+        self.carry_forward_daily_profile();
+        
+        // Reminders coming due while a prompt waits are printed in the background
+        if console::is_interactive() && !self.settings_repo.reminders().is_empty() {
+            self.reminder_checker = Some(ReminderChecker::start(Vec::new()));
+        }
+        
+        // Main application event loop - continues until user exits
+        loop {
+            self.show_due_reminders();
+            
+            // Give interval-based autosave a chance to run between actions
+            self.autosave(AutosaveTrigger::Tick);
+
+            // Input ended inside a flow (end of file or Ctrl-C): save and exit without the menu
+            let option = if console::input_closed() { MenuOption::Exit } else { self.show_main_menu() };
+            match option {
+                MenuOption::ManageFood => self.manage_foods(),        // Add/create foods
+                MenuOption::ViewFood => self.view_foods(),            // Display food database
+                MenuOption::LogFood => self.log_food(),               // Record consumption
+                MenuOption::ViewLog => self.view_log(),               // View/manage logs
+                MenuOption::Exercise => self.exercise_log(),          // Log/view exercise
+                MenuOption::ManageProfile => self.manage_profile(),   // Update user profile
+                MenuOption::ViewStats => self.view_stats(),           // Show statistics
+                MenuOption::ChangeDate => self.change_date(),         // Change working date
+                MenuOption::SaveData => {                             // Manual data save
+                    self.save_data();
+                }
+                MenuOption::Undo => self.undo_last_command(),         // Undo last action
+                MenuOption::Preferences => self.preferences_menu(),   // App-wide preferences
+                MenuOption::Exit => {
+                    if interrupt::interrupted() {
+                        outln!("\n{}", Message::Interrupted);
+                    }
+                    self.save_data();  // Automatic save on exit
+                    self.finish_day();
+                    outln!("{}", Message::Goodbye);
+                    break;
+                }
+            }
+        }
+    }
+    /// Prints the summary of the working day and writes it to the journal
+    ///
+    /// Nothing is printed without a profile. The journal is only written
+    /// when `YADA_JOURNAL` enables it (see `reports::daily_summary`).
+    fn finish_day(&self) {
+        let Ok(status) = DailyStatus::compute(&self.food_repo, &self.log_repo, &self.profile_repo, &self.exercise_repo, self.current_date) else {
+            return;
+        };
+        let summary = DailySummary::new(status, self.profile_repo.get_profile());
+        outln!("\n{}", summary.text());
+
+        if let Some(path) = JournalSetting::from_env().path(&self.config.reports_dir()) {
+            match summary.write_to_journal(&path) {
+                Ok(()) => outln!("{}", Message::SummarySaved.format(&[&path.display()])),
+                Err(e) => outln!("{}", Message::ErrorWritingJournal.format(&[&path.display() as &dyn std::fmt::Display, &e])),
+            }
+        }
+    }
+
+      /// Displays the main menu and captures user input for menu selection
+    /// 
+    /// This method provides the primary user interface for the application:
+    /// 1. Shows the current working date for context
+    /// 2. Lists all available menu options with numbered choices
+    /// 3. Validates user input and returns the corresponding MenuOption
+    /// 4. Loops until a valid choice is entered
+    /// 
+    /// The menu includes options for food management, logging, profile management,
+    /// statistics viewing, date changes, data persistence, and undo functionality.
+    /// 
+    /// Returns: MenuOption enum representing the user's choice
+    fn show_main_menu(&self) -> MenuOption {
+        outln!("\n{}", style::heading(Message::MainMenu));
+        outln!("{}", Message::CurrentDate.format(&[&self.show_date(self.current_date)]));
+        let entries = [
+            Message::ManageFoods,
+            Message::ViewFoods,
+            Message::LogFood,
+            Message::ViewLog,
+            Message::ExerciseLog,
+            Message::ManageProfile,
+            Message::ViewStats,
+            Message::ChangeDate,
+            Message::SaveData,
+            Message::UndoLastAction,
+            Message::Preferences,
+            Message::Exit,
+        ];
+        for (number, entry) in entries.iter().enumerate() {
+            outln!("{}. {}", number + 1, entry);
+        }
+        outln!("----------------------------");
+
+        // Input validation loop - continues until valid choice is entered
+        loop {
+            match prompt::menu(12).ask() {
+                Ok(1) => return MenuOption::ManageFood,
+                Ok(2) => return MenuOption::ViewFood,
+                Ok(3) => return MenuOption::LogFood,
+                Ok(4) => return MenuOption::ViewLog,
+                Ok(5) => return MenuOption::Exercise,
+                Ok(6) => return MenuOption::ManageProfile,
+                Ok(7) => return MenuOption::ViewStats,
+                Ok(8) => return MenuOption::ChangeDate, // Added new option
+                Ok(9) => return MenuOption::SaveData,
+                Ok(10) => return MenuOption::Undo,
+                Ok(11) => return MenuOption::Preferences,
+                Ok(12) => return MenuOption::Exit,
+                // Out-of-range numbers are rejected by the prompt itself
+                Ok(_) => continue,
+                // Nothing to cancel at the top level, just ask again
+                Err(PromptError::Cancelled) => continue,
+                // Input is gone (EOF), exit cleanly so data still gets saved
+                Err(PromptError::InputClosed) => return MenuOption::Exit,
+            }
+        }
+    }
+      /// Allows the user to change the current working date for the application
+    /// 
+    /// This method provides date management functionality:
+    /// 1. Shows the current working date and any bookmarked dates
+    /// 2. Accepts either a specific date (YYYY-MM-DD) or 'today' for current date
+    /// 3. Bookmarks the current date, or returns to the most recent bookmark
+    ///    (see `ui::date_stack`) so several days can be edited without retyping
+    /// 4. Validates date format and updates the application state
+    /// 
+    /// The working date affects all date-sensitive operations including:
+    /// - Food logging (entries are recorded for the current date)
+    /// - Log viewing (shows entries for the current date)
+    /// - Statistics (calculates metrics for the current date)
+    /// - Profile data (uses current date for age calculations and daily profiles)
+    fn change_date(&mut self) {
+        outln!("\n{}", style::heading(Message::ChangeDate));
+        outln!("{}", Message::CurrentDate.format(&[&self.show_date(self.current_date)]));
+        if !self.date_stack.is_empty() {
+            let bookmarks: Vec<String> = self.date_stack.dates().map(|date| self.show_date(*date)).collect();
+            outln!("{}", Message::Bookmarked.format(&[&bookmarks.join(", ")]));
+        }
+
+        let previous = self.date_stack.peek(self.current_date);
+        let bookmark_label = Message::BookmarkThisDate.format(&[&self.show_date(self.current_date)]);
+        let return_label = match previous {
+            Some(date) => Message::ReturnToPreviousDate.format(&[&self.show_date(date)]),
+            None => Message::ReturnToPreviousDateNone.to_string(),
+        };
+        let Ok(choice) = prompt::choice(&[Message::EnterNewDate.text(), &bookmark_label, &return_label]).default(1).ask() else {
+            return;
+        };
+
+        match choice {
+            2 => {
+                self.date_stack.push(self.current_date);
+                outln!("{}", Message::DateBookmarked.format(&[&self.show_date(self.current_date)]));
+            }
+            3 => match self.date_stack.swap(self.current_date) {
+                Some(date) => self.set_current_date(date),
+                None => outln!("{}", Message::NoOtherBookmark),
+            },
+            _ => {
+                // The date prompt retries until a valid date (or 'today') is entered
+                if let Ok(date) = prompt::date_or_today(Message::EnterNewDatePrompt.text()).ask() {
+                    self.set_current_date(date);
+                }
+            }
+        }
+    }
+
+    /// Switches the working date and fills in its daily profile if needed
+    fn set_current_date(&mut self, date: NaiveDate) {
+        self.current_date = date;
+        outln!("{}", Message::DateChanged.format(&[&self.show_date(self.current_date)]));
+        self.carry_forward_daily_profile();
+    }
+
+    /// Fills in the current date's weight and activity level from the latest earlier day
+    /// 
+    /// Without a daily profile the calorie target for a day is zero, so a day
+    /// opened without one inherits the most recent values. The copy stays
+    /// marked as carried forward until the user confirms it (or edits it
+    /// through Manage Profile > Update Today's Data).
+    fn carry_forward_daily_profile(&mut self) {
+        let date = self.current_date;
+        let Some(carried) = self.profile_repo.get_profile_mut()
+            .and_then(|profile| profile.carry_forward(date))
+            .cloned() else {
+            return;
+        };
+        
+        outln!("\n{}", Message::CarriedForward.format(&[
+            &self.show_date(date) as &dyn std::fmt::Display,
+            &self.show_date(carried.carried_from.unwrap_or(date)),
+            &self.units().format_weight(carried.weight),
+            &Message::from(&carried.activity_level),
+        ]));
+        let choice = prompt::choice(&[
+            Message::ConfirmValues.text(),
+            Message::EditTodaysValues.text(),
+            Message::DecideLater.text(),
+        ]).default(3).ask();
+        
+        match choice {
+            Ok(1) => self.confirm_daily_profile(),
+            Ok(2) => self.update_daily_profile(),
+            _ => {}
+        }
+        
+        // Not routed through a command, so trigger autosave explicitly
+        self.autosave(AutosaveTrigger::Command);
+    }
+    
+    /// Clears the carried-forward mark on the current date's daily profile
+    fn confirm_daily_profile(&mut self) {
+        let date = self.current_date;
+        if let Some(daily) = self.profile_repo.get_profile_mut()
+            .and_then(|profile| profile.daily_profiles.iter_mut().find(|daily| daily.date == date)) {
+            daily.carried_from = None;
+            outln!("{}", Message::DailyProfileConfirmed);
+        }
+    }
+
+    /// Units of weights and heights chosen in Preferences
+    fn units(&self) -> UnitSystem {
+        self.settings_repo.preferences().units
+    }
+
+    /// Formats a date as chosen in Preferences
+    fn show_date(&self, date: NaiveDate) -> String {
+        self.settings_repo.preferences().date_format.format(date)
+    }
+
+    /// Prints the reminders that came due and records that they were shown
+    ///
+    /// Checks look at today's log and weight, not the working date's. The
+    /// background checker gets the reminders whose check is done marked as
+    /// shown, so it does not print them either.
+    fn show_due_reminders(&mut self) {
+        let now = Local::now().naive_local();
+        let today = now.date();
+        if let Some(checker) = &self.reminder_checker {
+            for id in checker.take_announced() {
+                self.settings_repo.mark_shown(id, today);
+            }
+        }
+
+        let log = self.log_repo.get_log(today);
+        let profile = self.profile_repo.get_profile();
+        let due: Vec<(u32, NaiveTime, String)> = reminder::due_reminders(self.settings_repo.reminders(), now, log, profile)
+            .into_iter()
+            .map(|due| (due.id, due.time, due.message.clone()))
+            .collect();
+        if !due.is_empty() {
+            outln!();
+        }
+        for (id, time, message) in due {
+            outln!("{}", Message::ReminderDue.format(&[&time.format("%H:%M"), &message]));
+            self.settings_repo.mark_shown(id, today);
+        }
+
+        if let Some(checker) = &self.reminder_checker {
+            let watched = self.settings_repo.reminders().iter()
+                .cloned()
+                .map(|mut watched| {
+                    if watched.check.is_some_and(|check| check.is_done(today, log, profile)) {
+                        watched.last_shown = Some(today);
+                    }
+                    watched
+                })
+                .collect();
+            checker.set_reminders(watched);
+        }
+    }
+      /// Searches the food database based on user-provided keywords
+    /// 
+    /// This method implements flexible food search functionality:
+    /// 1. Prompts user for comma-separated search keywords
+    /// 2. Offers choice between AND search (all keywords must match) and OR search (any keyword matches)
+    /// 3. Filters the food database based on the selected criteria
+    /// 4. Returns a vector of food references that match the search
+    /// 
+    /// The search is case-insensitive and matches against the keywords stored
+    /// with each food item. This enables users to quickly find foods without
+    /// browsing the entire database.
+    /// 
+    /// Returns: Food references matching the search criteria, and the search terms
+    fn search_foods(&self) -> (Vec<&Food>, HashSet<String>) {
+        outln!("\n{}", style::heading(Message::SearchFoods));
+        
+        // Saved searches can be picked by number or name instead of typing keywords
+        let saved = self.settings_repo.saved_searches();
+        let message = if saved.is_empty() {
+            Message::SearchKeywordsPrompt.text()
+        } else {
+            outln!("{}", Message::SavedSearchesList);
+            for (index, search) in saved.iter().enumerate() {
+                outln!("  #{} {}", index + 1, search);
+            }
+            Message::SearchKeywordsOrSavedPrompt.text()
+        };
+
+        // Get search keywords from user input (normalized to lowercase, empty entries removed)
+        let Ok(mut keywords) = prompt::keywords(message).ask() else {
+            return (Vec::new(), HashSet::new());
+        };
+        let saved_search = self.find_saved_search(&keywords);
+        if let Some(search) = saved_search {
+            outln!("{}", Message::UsingSavedSearch.format(&[&search.name]));
+            keywords = search.terms.iter().cloned().collect();
+        }
+
+        // "category:dairy" terms restrict the results instead of being searched for
+        let categories: Vec<String> = keywords.iter()
+            .filter_map(|term| term.strip_prefix(category::SEARCH_PREFIX))
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .collect();
+        keywords.retain(|term| !term.starts_with(category::SEARCH_PREFIX));
+        let in_category = |food: &Food| categories.is_empty() || categories.iter().any(|name| category::matches(food, name));
+
+        // Handle case where no valid keywords were entered
+        if keywords.is_empty() {
+            if !categories.is_empty() {
+                let results: Vec<&Food> = self.food_repo.get_foods_sorted(FoodSort::Name)
+                    .into_iter()
+                    .filter(|food| in_category(food))
+                    .collect();
+                outln!("{}", Message::FoundFoodsInCategories.format(&[&results.len(), &categories.join(Message::Or.text())]));
+                return (results, keywords);
+            }
+            outln!("{}", Message::NoKeywordsAllFoods);
+            return (self.food_repo.get_active_foods(), keywords);
+        }
+
+        // Determine search mode (AND vs OR); a saved search brings its own, and
+        // otherwise empty input uses the search mode preference
+        let match_all = match saved_search {
+            Some(search) => search.mode.match_all(),
+            None => {
+                let default_mode = match self.settings_repo.preferences().search_mode {
+                    SearchMode::Any => 1,
+                    SearchMode::All => 2,
+                };
+                outln!("{}", Message::MatchAllOrAny);
+                let Ok(mode) = prompt::choice(&[
+                    Message::MatchAny.text(),
+                    Message::MatchAll.text(),
+                ]).default(default_mode).ask() else {
+                    return (Vec::new(), HashSet::new());
+                };
+                mode == 2
+            }
+        };
+
+        // Rank by match quality, allowing partial words and typos (archived foods stay hidden)
+        let mut hits = self.food_repo.search_ranked(&keywords, match_all);
+        hits.retain(|hit| !hit.food.archived && in_category(hit.food));
+        let results: Vec<&Food> = hits.iter().map(|hit| hit.food).collect();
+
+        let approximate = hits.iter().filter(|hit| hit.best != MatchQuality::Exact).count();
+        outln!("{}", Message::FoundMatchingFoods.format(&[&results.len()]));
+        if approximate > 0 {
+            outln!("{}", Message::ApproximateMatches.format(&[&approximate]));
+        }
+        
+        (results, keywords)
+    }
+
+    /// Finds the saved search the search prompt input refers to
+    ///
+    /// # Arguments
+    /// * `keywords` - Input of the search prompt; a single `#N` or saved
+    ///   search name picks that search
+    ///
+    /// # Returns
+    /// The saved search, or `None` if the input is ordinary keywords
+    fn find_saved_search(&self, keywords: &HashSet<String>) -> Option<&SavedSearch> {
+        if keywords.len() != 1 {
+            return None;
+        }
+        let input = keywords.iter().next()?;
+        match input.strip_prefix('#').map(|number| number.trim().parse::<usize>()) {
+            Some(Ok(number)) => self.settings_repo.saved_searches().get(number.checked_sub(1)?),
+            _ => self.settings_repo.find_search(input),
+        }
+    }
+
+    /// Finds foods by keywords and nutrient limits per serving
+    ///
+    /// Filters such as `calories < 200, protein > 10 g` are combined with
+    /// optional keywords (see `models::nutrient_filter`). Pressing Enter at
+    /// the filter prompt limits calories to what is left of today's target.
+    ///
+    /// # Returns
+    /// * `(Vec<&Food>, HashSet<String>)` - Matching foods sorted by calories,
+    ///   and the keywords searched for
+    fn advanced_search(&self) -> (Vec<&Food>, HashSet<String>) {
+        outln!("\n{}", style::heading(Message::AdvancedSearch));
+        let remaining = self.remaining_calories();
+        if let Some(remaining) = remaining {
+            outln!("{}", Message::CaloriesLeftToday.format(&[&remaining.max(0.0)]));
+        }
+
+        let Ok(keywords) = prompt::keywords(Message::AdvancedKeywordsPrompt.text()).ask() else {
+            return (Vec::new(), HashSet::new());
+        };
+        let match_all = if keywords.len() > 1 {
+            let Ok(mode) = prompt::choice(&[
+                Message::MatchAny.text(),
+                Message::MatchAll.text(),
+            ]).default(if self.settings_repo.preferences().search_mode.match_all() { 2 } else { 1 }).ask() else {
+                return (Vec::new(), HashSet::new());
+            };
+            mode == 2
+        } else {
+            true
+        };
+
+        let nutrients: Vec<&str> = Nutrient::ALL.iter().map(|nutrient| nutrient.key()).collect();
+        outln!("{}", Message::NutrientList.format(&[&nutrients.join(", ")]));
+        let message = match remaining {
+            Some(remaining) => Message::NutrientFiltersPromptRemaining.format(&[&remaining.max(0.0)]),
+            None => Message::NutrientFiltersPrompt.to_string(),
+        };
+        let Ok(mut filters) = prompt::Prompt::new(&message, nutrient_filter::parse_filters).ask() else {
+            return (Vec::new(), HashSet::new());
+        };
+        if let (true, Some(remaining)) = (filters.is_empty(), remaining) {
+            filters.push(NutrientFilter::new(Nutrient::Calories, Comparison::AtMost, remaining.max(0.0).round()));
+        }
+
+        let mut results: Vec<&Food> = self.food_repo.search_foods(&keywords, match_all, &filters)
+            .into_iter()
+            .filter(|food| !food.archived)
+            .collect();
+        results.sort_by(|a, b| a.calories_per_serving.total_cmp(&b.calories_per_serving).then_with(|| a.name.cmp(&b.name)));
+
+        let conditions: Vec<String> = filters.iter().map(ToString::to_string).collect();
+        if conditions.is_empty() {
+            outln!("{}", Message::FoundFoodsByCalories.format(&[&results.len()]));
+        } else {
+            outln!("{}", Message::FoundFoodsWithFilters.format(&[&results.len(), &conditions.join(", ")]));
+        }
+        (results, keywords)
+    }
+
+    /// Target modifiers of the menus: the standard ones and the exercise
+    /// credit, when enabled
+    fn target_pipeline(&self) -> TargetPipeline<'_> {
+        TargetPipeline::standard().with_exercise_credit(&self.exercise_repo)
+    }
+
+    /// Returns the calories left of today's target, counting the exercise
+    /// credit, or `None` without a profile
+    fn remaining_calories(&self) -> Option<f64> {
+        let profile = self.profile_repo.get_profile()?;
+        let calculator = self.calculator_factory.calculator_for(profile, self.current_date)?;
+        let target = calculator.calculate_target_with(profile, self.current_date, &self.target_pipeline()).target;
+        let eaten = self.log_repo.get_log(self.current_date)
+            .map_or(0.0, |log| log.total_calories(self.food_repo.get_foods()));
+        Some(target - eaten)
+    }
+      /// Creates an initial user profile for new users
+    /// 
+    /// This method guides new users through the profile creation process:
+    /// 1. Collects basic biographical information (gender, height, birth date)
+    /// 2. Records current weight and activity level for the current date
+    /// 3. Creates both a UserProfile and initial DailyProfile
+    /// 4. Stores the profile in the repository for future use
+    /// 
+    /// The profile information is essential for:
+    /// - Calorie calculation strategies (BMR/TDEE calculations)
+    /// - Age-based nutritional recommendations
+    /// - Activity level adjustments for calorie targets
+    /// - Weight tracking over time
+    /// 
+    /// Input validation ensures all data is within reasonable ranges
+    /// and properly formatted before creating the profile.
+    fn create_initial_profile(&mut self) {
+        outln!("\n{}", style::heading(Message::CreateUserProfile));
+        
+        // Collect gender information for BMR calculations
+        outln!("{}", Message::SelectGender);
+        let Ok(gender) = Self::prompt_gender(None) else {
+            outln!("{}", Message::ProfileCreationCancelled);
+            return;
+        };
+
+        // Collect height (required for BMR calculations), stored in centimeters
+        let units = self.units();
+        let Ok(height) = prompt::height_cm(&Message::EnterHeight.format(&[&units.height_unit_name()]), units).ask() else {
+            outln!("{}", Message::ProfileCreationCancelled);
+            return;
+        };
+
+        // Collect birth date (for age calculation)
+        let Ok(birth_date) = prompt::birth_date(Message::EnterBirthDate.text()).ask() else {
+            outln!("{}", Message::ProfileCreationCancelled);
+            return;
+        };
+
+        // Create the basic user profile with biographical data
+        let mut profile = UserProfile::new(gender, height, birth_date);
+
+        // Collect current day's variable data (weight and activity level), stored in kilograms
+        let Ok(weight) = prompt::weight_kg(&Message::EnterCurrentWeight.format(&[&units.weight_unit_name()]), units).ask() else {
+            outln!("{}", Message::ProfileCreationCancelled);
+            return;
+        };
+
+        // Activity level affects TDEE calculations
+        outln!("{}", Message::SelectActivityLevel);
+        let Ok(activity_level) = Self::prompt_activity_level() else {
+            outln!("{}", Message::ProfileCreationCancelled);
+            return;
+        };
+
+        // Create daily profile for the current date
+        let daily_profile = DailyProfile {
+            date: self.current_date,
+            weight,
+            activity_level,
+            carried_from: None,
+        };
+        
+        // Add the daily profile to the user profile
+        profile.add_or_update_daily_profile(daily_profile);
+        
+        // Store the completed profile in the repository
+        self.profile_repo.set_profile(profile);
+        outln!("{}", Message::ProfileCreated);
+
+        // Not routed through a command, so trigger autosave explicitly
+        self.autosave(AutosaveTrigger::Command);
+    }
+
+    /// Asks the user to pick a gender from the numbered list
+    ///
+    /// When `current` is provided, an extra "Keep current" option is offered
+    /// and returns the existing value unchanged.
+    fn prompt_gender(current: Option<&Gender>) -> prompt::PromptResult<Gender> {
+        let mut options = vec![Message::Male.text(), Message::Female.text(), Message::OtherGender.text()];
+        if current.is_some() {
+            options.push(Message::KeepCurrent.text());
+        }
+
+        match prompt::choice(&options).ask()? {
+            1 => Ok(Gender::Male),
+            2 => Ok(Gender::Female),
+            3 => Ok(Gender::Other),
+            _ => Ok(current.cloned().unwrap_or(Gender::Other)),
+        }
+    }
+
+    /// Asks the user to pick one of the five activity levels
+    fn prompt_activity_level() -> prompt::PromptResult<ActivityLevel> {
+        let choice = prompt::choice(&[
+            Message::Sedentary.text(),
+            Message::LightlyActive.text(),
+            Message::ModeratelyActive.text(),
+            Message::VeryActive.text(),
+            Message::ExtremelyActive.text(),
+        ]).ask()?;
+
+        Ok(match choice {
+            1 => ActivityLevel::Sedentary,
+            2 => ActivityLevel::LightlyActive,
+            3 => ActivityLevel::ModeratelyActive,
+            4 => ActivityLevel::VeryActive,
+            _ => ActivityLevel::ExtremelyActive,
+        })
+    }
+      /// Provides a sub-menu for food management operations
+    /// 
+    /// This method creates a dedicated interface for food-related operations:
+    /// 1. Add Basic Food - Create simple food items with direct calorie values
+    /// 2. Create Composite Food - Build complex foods from existing components
+    /// 3. Compare Foods - Side-by-side nutrition comparison
+    /// 4. Archive Unused Foods - Maintenance for foods unused for a year
+    /// 5. Restore Archived Food - Show an archived food in pickers again
+    /// 6. Return to Main Menu - Exit the food management interface
+    /// 
+    /// The method implements a loop that continues until the user chooses
+    /// to return to the main menu, allowing multiple food operations in sequence.
+    /// This design follows the single responsibility principle by grouping
+    /// related food management functionality.
+    fn manage_foods(&mut self) {
+        loop {
+            outln!("\n{}", style::heading(Message::ManageFoods));
+
+            let choice = prompt::choice(&[
+                Message::AddBasicFood.text(),
+                Message::CreateCompositeFood.text(),
+                Message::CompareFoods.text(),
+                Message::ArchiveUnusedFoods.text(),
+                Message::RestoreArchivedFood.text(),
+                Message::MergeDuplicateFoods.text(),
+                Message::ImportFoodFromSources.text(),
+                Message::EditKeywords.text(),
+                Message::SetDefaultServings.text(),
+                Message::BackToMainMenu.text(),
+            ]).ask();
+
+            match choice {
+                Ok(1) => self.add_basic_food(),      // Delegate to basic food creation
+                Ok(2) => self.create_composite_food(), // Delegate to composite food creation
+                Ok(3) => self.compare_foods(),        // Side-by-side nutrition comparison
+                Ok(4) => self.archive_unused_foods(), // Maintenance: hide long-unused foods
+                Ok(5) => self.restore_archived_food(), // Bring an archived food back
+                Ok(6) => self.merge_duplicate_foods(), // Maintenance: combine near-duplicates
+                Ok(7) => self.import_food_from_sources(), // Search every food source at once
+                Ok(8) => self.edit_keywords(),        // Add or remove keywords on existing foods
+                Ok(9) => self.set_default_servings(), // Servings offered when logging a food
+                _ => break,                           // Exit food management menu
+            }
+        }
+    }
+      /// Creates and adds a basic food item to the database using the Command pattern
+    /// 
+    /// This method handles the creation of simple food items with the following process:
+    /// 1. Collects food identification information (ID and name)
+    /// 2. Validates that the food ID is unique in the database
+    /// 3. Gathers search keywords for easy food discovery
+    /// 4. Records the calorie content per serving
+    /// 5. Creates the food object and uses Command pattern for undo support
+    /// 
+    /// Input validation ensures:
+    /// - Food ID uniqueness to prevent duplicates
+    /// - Non-negative calorie values for nutritional accuracy
+    /// - Proper keyword formatting for search functionality
+    /// 
+    /// Uses the Command pattern to enable undo functionality for food additions.
+    fn add_basic_food(&mut self) {
+        outln!("\n{}", style::heading(Message::AddBasicFood));
+        
+        // Collect unique food identifier (rejects duplicates before asking anything else)
+        let Ok(id) = self.prompt_new_food_id().ask() else {
+            return;
+        };
+
+        // Collect human-readable food name
+        let Ok(name) = prompt::text(Message::EnterFoodName.text()).ask() else {
+            return;
+        };
+
+        // Collect search keywords for food discovery (normalized for consistent searching)
+        let Ok(keywords) = self.prompt_keywords(&name) else {
+            return;
+        };
+
+        // Nutrition labels often list values per 100 g; such foods are logged by weight
+        outln!("{}", Message::NutritionValuesGiven);
+        let Ok(basis) = prompt::choice(&[Message::PerServing.text(), Message::Per100g.text()]).default(1).ask() else {
+            return;
+        };
+        let per_100g = basis == 2;
+        let basis_label = if per_100g { "100 g" } else { Message::ServingBasis.text() };
+
+        // Collect nutritional information with validation
+        let Ok(calories) = prompt::non_negative_f64(
+            &Message::EnterCaloriesPer.format(&[&basis_label]),
+            Message::InvalidCalories.text(),
+        ).ask() else {
+            return;
+        };
+
+        // Macronutrients are optional; empty input records them as unknown (0)
+        outln!("{}", Message::MacronutrientsPer.format(&[&basis_label]));
+        let mut grams = [0.0; 4];
+        for (value, nutrient) in grams.iter_mut().zip([Message::Protein.text(), Message::Carbs.text(), Message::Fat.text(), Message::Fiber.text()]) {
+            match prompt::non_negative_f64(
+                &format!("  {}: ", nutrient),
+                Message::InvalidAmountNonNegative.text(),
+            ).optional().ask() {
+                Ok(amount) => *value = amount.unwrap_or(0.0),
+                Err(_) => return,
+            }
+        }
+        let [protein, carbs, fat, fiber] = grams;
+
+        // Alcohol and caffeine are optional too; they count toward the daily limits
+        outln!("{}", Message::AlcoholCaffeinePer.format(&[&basis_label]));
+        let mut amounts = [0.0; 2];
+        for (value, substance) in amounts.iter_mut().zip([Message::AlcoholGrams.text(), Message::CaffeineMilligrams.text()]) {
+            match prompt::non_negative_f64(
+                &format!("  {}: ", substance),
+                Message::InvalidAmountNonNegative.text(),
+            ).optional().ask() {
+                Ok(amount) => *value = amount.unwrap_or(0.0),
+                Err(_) => return,
+            }
+        }
+        let [alcohol, caffeine] = amounts;
+
+        // Serving size lets the food be logged in grams, ml, cups, pieces, or ounces;
+        // foods described per 100 g always have a 100 g serving
+        let serving_size = if per_100g {
+            Some(ServingSize::new(100.0, ServingUnit::Gram))
+        } else {
+            match Self::prompt_serving_size().optional().ask() {
+                Ok(size) => size,
+                Err(_) => return,
+            }
+        };
+
+        // Serving weight and cost are optional; they enable per-100 g and cost comparisons.
+        // A serving size given as a mass already defines the weight.
+        let serving_grams = match serving_size.and_then(|size| size.grams()) {
+            Some(grams) => Some(grams),
+            None => match prompt::positive_f64(
+                Message::ServingWeightPrompt.text(),
+                Message::InvalidWeightPositive.text(),
+            ).optional().ask() {
+                Ok(grams) => grams,
+                Err(_) => return,
+            },
+        };
+        let Ok(cost) = prompt::non_negative_f64(
+            &Message::CostPerPrompt.format(&[&basis_label]),
+            Message::InvalidCost.text(),
+        ).optional().ask() else {
+            return;
+        };
+        // Foods usually eaten several at a time (eggs, slices) can offer that amount when logged
+        let Ok(default_servings) = prompt::positive_f64(
+            Message::DefaultServingsPrompt.text(),
+            Message::InvalidServingsPositive.text(),
+        ).optional().ask() else {
+            return;
+        };
+
+        let Ok((category, tags)) = Self::prompt_food_metadata() else {
+            return;
+        };
+
+        // Create food object and add using Command pattern for undo support
+        let food = if per_100g {
+            Food::new_basic_per_100g(id, name, keywords, calories)
+        } else {
+            Food::new_basic(id, name, keywords, calories).with_serving_size(serving_size)
+        };
+        let food = food
+            .with_macros(Macros::new(protein, carbs, fat).with_fiber(fiber).with_alcohol_and_caffeine(alcohol, caffeine))
+            .with_serving_details(serving_grams, cost)
+            .with_default_servings(default_servings)
+            .with_metadata(category, tags);
+        if !self.confirm_food_values(&food) {
+            outln!("{}", Message::FoodNotAdded);
+            return;
+        }
+        let command = Box::new(AddFoodCommand::new(&mut self.food_repo, food));
+        
+        match self.execute_command(command) {
+            Ok(_) => outln!("{}", Message::FoodAdded),
+            Err(e) => outln!("{}", Message::ErrorAddingFood.format(&[&e])),
+        }
+    }
+      /// Creates a composite food item built from existing food components (Composite Pattern)
+    /// 
+    /// This method implements the Composite Pattern for complex food creation:
+    /// 1. Collects basic food information (ID, name, keywords)
+    /// 2. Allows user to specify multiple component foods with servings
+    /// 3. Validates that all component foods exist in the database
+    /// 4. Creates a composite food whose calories are calculated from components
+    /// 5. Uses Command pattern for undo support
+    /// 
+    /// Composite foods enable modeling of:
+    /// - Recipes (e.g., sandwich made from bread, meat, cheese)
+    /// - Meals (e.g., breakfast combining multiple food items)
+    /// - Complex dishes with multiple ingredients
+    /// 
+    /// The calorie content is automatically calculated by summing the calories
+    /// of all components multiplied by their respective serving amounts.
+    fn create_composite_food(&mut self) {
+        outln!("\n{}", style::heading(Message::CreateCompositeFood));
+        
+        // Collect basic food identification (same as basic foods, unique across all food types)
+        let Ok(id) = self.prompt_new_food_id().ask() else {
+            return;
+        };
+
+        let Ok(name) = prompt::text(Message::EnterFoodName.text()).ask() else {
+            return;
+        };
+
+        let Ok(keywords) = prompt::keywords(Message::EnterKeywords.text()).ask() else {
+            return;
+        };
+
+        // Collect component foods and their quantities
+        let mut components: Vec<(String, f64)> = Vec::new();
+
+        outln!("{}", Message::AddComponents);
+        let foods = self.food_repo.get_active_foods();
+        loop {
+            // Empty input signals completion of component entry
+            let comp_id = match self.pick_food(Message::EnterComponent.text(), &foods, false, true) {
+                Ok(Some(comp_id)) => comp_id,
+                Ok(None) => break,
+                Err(_) => return,
+            };
+
+            // Get the quantity of this component (servings or an amount with a unit)
+            let Some(component) = self.food_repo.get_food(&comp_id) else {
+                continue;
+            };
+            let Ok((_, servings)) = Self::prompt_amount(component).ask() else {
+                return;
+            };
+
+            // Add the validated component to the list
+            components.push((comp_id, servings));
+        }
+        
+        // Ensure at least one component was added
+        if components.is_empty() {
+            outln!("{}", Message::NoComponentsAdded);
+            return;
+        }
+
+        let Ok((category, tags)) = Self::prompt_food_metadata() else {
+            return;
+        };
+        
+        // Create composite food using the Composite Pattern; calories and
+        // macros are derived from the components
+        let mut food = Food::new_composite(id, name, keywords, components)
+            .with_metadata(category, tags);
+        food.calculate_from_components(self.food_repo.get_foods());
+        let command = Box::new(AddFoodCommand::new(&mut self.food_repo, food));
+        
+        match self.execute_command(command) {
+            Ok(_) => outln!("{}", Message::CompositeFoodAdded),
+            Err(e) => outln!("{}", Message::ErrorAddingCompositeFood.format(&[&e])),
+        }
+    }
+
+    /// Shows two or more foods side by side for meal planning
+    ///
+    /// Asks for food IDs until an empty line is entered, then prints calories,
+    /// macros, and cost per serving and per 100 g (see `ui::comparison`).
+    fn compare_foods(&self) {
+        outln!("\n{}", style::heading(Message::CompareFoods));
+        outln!("{}", Message::EnterFoodsToCompare);
+
+        let mut ids: Vec<String> = Vec::new();
+        loop {
+            match self.prompt_existing_food_id(Message::EnterFoodId.text()).optional().ask() {
+                Ok(Some(id)) if ids.contains(&id) => outln!("{}", Message::AlreadyCompared.format(&[&id])),
+                Ok(Some(id)) => ids.push(id),
+                Ok(None) => break,
+                Err(_) => return,
+            }
+        }
+
+        if ids.len() < 2 {
+            outln!("{}", Message::ChooseTwoFoods);
+            return;
+        }
+
+        let foods: Vec<&Food> = ids.iter()
+            .filter_map(|id| self.food_repo.get_food(id))
+            .collect();
+        outln!("\n{}", comparison::comparison_table(&foods));
+    }
+
+    /// Suggests archiving foods that have not been logged for a year
+    ///
+    /// Lists the candidates from `usage::archive_candidates` with their last
+    /// use and archives all of them after confirmation. The archive is a
+    /// single command, so one undo brings every food back.
+    fn archive_unused_foods(&mut self) {
+        outln!("\n{}", style::heading(Message::ArchiveUnusedFoods));
+
+        let today = Local::now().date_naive();
+        let candidates = usage::archive_candidates(self.food_repo.get_foods(), today, ARCHIVE_AFTER_DAYS);
+        if candidates.is_empty() {
+            outln!("{}", Message::NothingToArchive.format(&[&ARCHIVE_AFTER_DAYS]));
+            return;
+        }
+
+        outln!("{}", Message::UnusedFoods.format(&[&ARCHIVE_AFTER_DAYS]));
+        let mut table = Self::last_used_table();
+        for food in candidates.iter().filter_map(|id| self.food_repo.get_food(id)) {
+            let last_used = food.last_used.map_or("-".to_string(), |date| date.to_string());
+            table.row([food.id.as_str(), food.name.as_str(), &last_used]);
+        }
+        outln!("{}", table);
+        outln!("\n{}", Message::ArchiveExplanation);
+        outln!("{}", Message::ArchiveExplanationContinued);
+
+        if !self.confirm(Message::ConfirmArchive.text(), Some(CommandType::ArchiveFoods)) {
+            outln!("{}", Message::NothingArchived);
+            return;
+        }
+
+        let count = candidates.len();
+        let command = Box::new(ArchiveFoodsCommand::new(&mut self.food_repo, candidates, true));
+        match self.execute_command(command) {
+            Ok(_) => outln!("{}", Message::ArchivedFoods.format(&[&count])),
+            Err(e) => outln!("{}", Message::ErrorArchivingFoods.format(&[&e])),
+        }
+    }
+
+    /// Creates the table of foods and the day each was last logged
+    fn last_used_table() -> Table {
+        Table::new()
+            .column("ID").min_width(10)
+            .column(Message::Name.text()).min_width(20).max_width(NAME_COLUMN_WIDTH)
+            .column(Message::LastUsed.text()).min_width(12)
+    }
+
+    /// Shows an archived food in food lists again
+    fn restore_archived_food(&mut self) {
+        outln!("\n{}", style::heading(Message::RestoreArchivedFood));
+
+        let mut archived: Vec<&Food> = self.food_repo.get_all_foods()
+            .into_iter()
+            .filter(|food| food.archived)
+            .collect();
+        if archived.is_empty() {
+            outln!("{}", Message::NoArchivedFoods);
+            return;
+        }
+        archived.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let mut table = Self::last_used_table();
+        for food in &archived {
+            let last_used = food.last_used.map_or("-".to_string(), |date| date.to_string());
+            table.row([food.id.as_str(), food.name.as_str(), &last_used]);
+        }
+        outln!("{}", table);
+
+        let archived_ids: HashSet<String> = archived.iter().map(|food| food.id.clone()).collect();
+        let Ok(food_id) = prompt::text(&format!("\n{}", Message::EnterFoodIdToRestore))
+            .validate(move |id| {
+                if archived_ids.contains(id) {
+                    Ok(())
+                } else {
+                    Err(Message::NotArchivedFood.format(&[&id]))
+                }
+            })
+            .ask()
+        else {
+            return;
+        };
+
+        let command = Box::new(ArchiveFoodsCommand::new(&mut self.food_repo, vec![food_id], false));
+        match self.execute_command(command) {
+            Ok(_) => outln!("{}", Message::FoodRestored),
+            Err(e) => outln!("{}", Message::ErrorRestoringFood.format(&[&e])),
+        }
+    }
+
+    /// Adds keywords to, or removes them from, one or more existing foods
+    ///
+    /// The foods are named by ID, so a keyword such as `vegan` can be added
+    /// to many foods at once without editing each. The whole change is one
+    /// command, so a single undo reverses it.
+    fn edit_keywords(&mut self) {
+        outln!("\n{}", style::heading(Message::EditKeywords));
+
+        let known: HashSet<String> = self.food_repo.get_foods().keys().cloned().collect();
+        let Ok(food_ids) = prompt::text(Message::EnterFoodIds.text())
+            .validate(prompt::non_empty(Message::EnterAtLeastOneFoodId.text()))
+            .validate(move |ids| match ids.split(',').map(str::trim).find(|id| !id.is_empty() && !known.contains(*id)) {
+                Some(id) => Err(Message::FoodIdMissing.format(&[&id])),
+                None => Ok(()),
+            })
+            .ask() else {
+            return;
+        };
+        let mut ids: Vec<String> = Vec::new();
+        for id in food_ids.split(',').map(str::trim).filter(|id| !id.is_empty()) {
+            if !ids.iter().any(|known| known == id) {
+                ids.push(id.to_string());
+            }
+        }
+
+        let mut table = Table::new()
+            .column("ID").min_width(10)
+            .column(Message::Name.text()).min_width(20).max_width(NAME_COLUMN_WIDTH)
+            .column(Message::KeywordsColumn.text());
+        for id in &ids {
+            let Some(food) = self.food_repo.get_food(id) else {
+                continue;
+            };
+            let mut keywords: Vec<&str> = food.keywords.iter().map(String::as_str).collect();
+            keywords.sort();
+            table.row([food.id.as_str(), food.name.as_str(), &keywords.join(", ")]);
+        }
+        outln!("{}", table);
+
+        let Ok(action) = prompt::choice(&[Message::AddKeywords.text(), Message::RemoveKeywords.text(), Message::Back.text()]).ask() else {
+            return;
+        };
+        if action == 3 {
+            return;
+        }
+        let add = action == 1;
+        let Ok(keywords) = prompt::keywords(Message::EnterKeywords.text())
+            .validate(|keywords| if keywords.is_empty() { Err(Message::EnterAtLeastOneKeyword.text().to_string()) } else { Ok(()) })
+            .ask() else {
+            return;
+        };
+        let mut keywords: Vec<String> = keywords.into_iter().collect();
+        keywords.sort();
+
+        // Count the foods that will change, since the command is handed over
+        let changing = ids.iter()
+            .filter_map(|id| self.food_repo.get_food(id))
+            .filter(|food| keywords.iter().any(|keyword| food.keywords.contains(keyword) != add))
+            .count();
+        if changing == 0 {
+            outln!("{}", Message::NothingToChange);
+            return;
+        }
+
+        let joined = keywords.join(", ");
+        let command = Box::new(EditKeywordsCommand::new(&mut self.food_repo, ids, keywords, add));
+        match self.execute_command(command) {
+            Ok(_) if add => outln!("{}", Message::AddedKeywords.format(&[&joined, &changing])),
+            Ok(_) => outln!("{}", Message::RemovedKeywords.format(&[&joined, &changing])),
+            Err(e) => outln!("{}", Message::ErrorEditingKeywords.format(&[&e])),
+        }
+    }
+
+    /// Sets the servings a food offers when it is logged
+    ///
+    /// The amount prompt of Log Food then accepts Enter for that many
+    /// servings, e.g. 2 for eggs. The change is a command, so it can be undone.
+    fn set_default_servings(&mut self) {
+        outln!("\n{}", style::heading(Message::SetDefaultServings));
+
+        let Ok(food_id) = self.prompt_existing_food_id(Message::EnterFoodId.text()).ask() else {
+            return;
+        };
+        let Some(food) = self.food_repo.get_food(&food_id) else {
+            return;
+        };
+        let current = food.servings_to_log();
+        let Ok(servings) = prompt::positive_f64(
+            &Message::DefaultServingsForPrompt.format(&[&food.name, &current]),
+            Message::InvalidServingsPositive.text(),
+        ).default(current).ask() else {
+            return;
+        };
+
+        let updated = food.clone().with_default_servings(Some(servings));
+        if updated.default_servings == food.default_servings {
+            outln!("{}", Message::NothingToChange);
+            return;
+        }
+        let command = Box::new(UpdateFoodCommand::new(&mut self.food_repo, updated));
+        match self.execute_command(command) {
+            Ok(_) => outln!("{}", Message::DefaultServingsSet.format(&[&food_id, &servings])),
+            Err(e) => outln!("{}", Message::ErrorUpdatingFood.format(&[&e])),
+        }
+    }
+
+    /// Finds near-duplicate foods and merges the pairs the user picks
+    ///
+    /// Each candidate pair from `duplicates::find_duplicates` is shown with
+    /// how often each food is used; the user keeps one of the two, skips the
+    /// pair, or stops. Every merge is its own command, so undo reverses the
+    /// most recent merge only.
+    fn merge_duplicate_foods(&mut self) {
+        outln!("\n{}", style::heading(Message::MergeDuplicateFoods));
+
+        let pairs = duplicates::find_duplicates(self.food_repo.get_foods());
+        if pairs.is_empty() {
+            outln!("{}", Message::NoDuplicates);
+            return;
+        }
+        outln!("{}", Message::FoundDuplicates.format(&[&pairs.len()]));
+        outln!("{}", Message::FoundDuplicatesContinued);
+
+        let mut merged = 0;
+        for (number, pair) in pairs.iter().enumerate() {
+            // A food merged away by an earlier pair is gone
+            let first = self.food_repo.get_food(&pair.first);
+            let second = self.food_repo.get_food(&pair.second);
+            let (Some(first), Some(second)) = (first, second) else {
+                continue;
+            };
+
+            outln!("\n{}", Message::PairOf.format(&[&(number + 1), &pairs.len()]));
+            outln!("{}", food_list::header());
+            for food in [first, second] {
+                outln!("{}", food_list::row(food, &format!("  {}", self.usage_summary(&food.id))));
+            }
+
+            let keep_first = Message::KeepAndMerge.format(&[&first.id, &second.id]);
+            let keep_second = Message::KeepAndMerge.format(&[&second.id, &first.id]);
+            let (keep, remove) = match prompt::choice(&[&keep_first, &keep_second, Message::SkipPair.text(), Message::Stop.text()]).ask() {
+                Ok(1) => (pair.first.clone(), pair.second.clone()),
+                Ok(2) => (pair.second.clone(), pair.first.clone()),
+                Ok(3) => continue,
+                _ => break,
+            };
+
+            let command = Box::new(MergeFoodsCommand::new(
+                &mut self.food_repo,
+                &mut self.log_repo,
+                &mut self.meal_plan_repo,
+                keep.clone(),
+                remove.clone(),
+            ));
+            match self.execute_command(command) {
+                Ok(_) => {
+                    outln!("{}", Message::MergedInto.format(&[&remove, &keep]));
+                    merged += 1;
+                }
+                Err(e) => outln!("{}", Message::ErrorMergingFoods.format(&[&e])),
+            }
+        }
+        outln!("\n{}", Message::MergedPairs.format(&[&merged]));
+    }
+
+    /// Prompts for a new food's keywords, suggesting some from its name
+    ///
+    /// Suggestions come from the keywords already in the database (see
+    /// `models::keyword_suggestion`). Pressing Enter accepts them, a list
+    /// replaces them, and a list starting with `+` adds to them.
+    ///
+    /// Parameters: name - name of the new food
+    fn prompt_keywords(&self, name: &str) -> prompt::PromptResult<HashSet<String>> {
+        let suggested = self.food_repo.suggest_keywords(name);
+        if suggested.is_empty() {
+            return prompt::keywords(Message::EnterKeywords.text()).ask();
+        }
+
+        outln!("{}", Message::SuggestedKeywords.format(&[&suggested.join(", ")]));
+        let input = prompt::text(Message::EnterKeywordsWithSuggestions.text())
+            .default(String::new())
+            .ask()?;
+        let typed = input.trim();
+        let (extra, mut keywords) = match typed.strip_prefix('+') {
+            Some(extra) => (extra, suggested.into_iter().collect()),
+            None if typed.is_empty() => ("", suggested.into_iter().collect()),
+            None => (typed, HashSet::new()),
+        };
+        keywords.extend(extra.split(',').map(|s| s.trim().to_lowercase()).filter(|s| !s.is_empty()));
+        Ok(keywords)
+    }
+
+    /// Searches every registered food source and adds a chosen result to the database
+    fn import_food_from_sources(&mut self) {
+        outln!("\n{}", style::heading(Message::ImportFoodFromSources));
+
+        let Ok(query) = prompt::text(Message::SearchFoodSourcesFor.text())
+            .validate(prompt::non_empty(Message::EnterSearchText.text()))
+            .ask() else {
+            return;
+        };
+        self.import_from_sources(&query);
+    }
+
+    /// Searches every registered food source for a query and adds the chosen result
+    ///
+    /// All sources are asked at once (see `AggregateFoodSource`); sources that
+    /// time out are named and skipped, so a slow database never freezes the
+    /// menu. Each result shows which sources found it. A result whose ID is
+    /// already taken locally is added under a new ID.
+    ///
+    /// Returns: ID of the added food, or None if nothing was added
+    fn import_from_sources(&mut self, query: &str) -> Option<String> {
+        let sources = self.food_sources().aggregate(LookupOptions::from_env());
+        if console::is_interactive() {
+            outln!("{}", Message::SearchingSourcesCancellable.format(&[&sources.source_names().join(", ")]));
+        } else {
+            outln!("{}", Message::SearchingSources.format(&[&sources.source_names().join(", ")]));
+        }
+        let cancel = interrupt::cancel_token().child();
+        let results = {
+            let _watch = interrupt::cancel_on_enter(&cancel);
+            sources.search_cancellable(query, &cancel)
+        };
+        if cancel.is_cancelled() {
+            outln!("{}", Message::SearchCancelled);
+            return None;
+        }
+        for (source, error) in &results.failed {
+            outln!("{}", Message::SkippedSource.format(&[&source, &error]));
+        }
+        if results.foods.is_empty() {
+            outln!("{}", Message::NoFoodsFound);
+            return None;
+        }
+
+        let shown = &results.foods[..results.foods.len().min(food_list::PAGE_SIZE)];
+        if shown.len() < results.foods.len() {
+            outln!("{}", Message::ShowingBestResults.format(&[&shown.len(), &results.foods.len()]));
+        }
+        let labels: Vec<String> = shown.iter().map(|result| result.to_string()).collect();
+        let mut options: Vec<&str> = labels.iter().map(String::as_str).collect();
+        options.push(Message::Back.text());
+        let chosen = prompt::choice(&options).ask().ok().and_then(|number| shown.get(number - 1))?;
+
+        let mut food = chosen.food.clone();
+        if self.food_repo.get_food(&food.id).is_some() {
+            outln!("{}", Message::FoodIdExists.format(&[&food.id]));
+            food.id = self.prompt_new_food_id().ask().ok()?;
+        }
+        if !self.confirm_food_values(&food) {
+            outln!("{}", Message::FoodNotAdded);
+            return None;
+        }
+        let (id, name) = (food.id.clone(), food.name.clone());
+        let command = Box::new(AddFoodCommand::new(&mut self.food_repo, food));
+        match self.execute_command(command) {
+            Ok(_) => {
+                outln!("{}", Message::AddedFoodAs.format(&[&name, &id]));
+                Some(id)
+            }
+            Err(e) => {
+                outln!("{}", Message::ErrorAddingFood.format(&[&e]));
+                None
+            }
+        }
+    }
+
+    /// Offers similar foods, and a search of the food sources, after a search found nothing
+    ///
+    /// Similar foods are those whose names resemble the search terms (see
+    /// `search::similar`). The food sources are offered when any source
+    /// besides the built-in local one is registered (such as a plugin).
+    ///
+    /// Parameters: terms - search terms that found nothing
+    /// Returns: ID of the food to log, or None to give up
+    fn suggest_foods(&mut self, terms: &HashSet<String>) -> Option<String> {
+        let similar: Vec<(String, String)> = search::similar(self.food_repo.get_active_foods(), terms, SUGGESTION_COUNT)
+            .into_iter()
+            .map(|food| (food.id.clone(), format!("{} ({})", food.name, food.id)))
+            .collect();
+        let has_sources = self.food_sources().get_all_sources().iter().any(|name| *name != LOCAL_SOURCE);
+        if similar.is_empty() && !has_sources {
+            return None;
+        }
+
+        let mut terms: Vec<&str> = terms.iter().map(String::as_str).collect();
+        terms.sort();
+        let query = terms.join(" ");
+        if !similar.is_empty() {
+            outln!("{}", Message::DidYouMean);
+        }
+        let search_sources = Message::SearchSourcesFor.format(&[&query]);
+        let mut options: Vec<&str> = similar.iter().map(|(_, label)| label.as_str()).collect();
+        if has_sources {
+            options.push(&search_sources);
+        }
+        options.push(Message::Back.text());
+
+        let number = prompt::choice(&options).ask().ok()?;
+        match similar.get(number - 1) {
+            Some((id, _)) => Some(id.clone()),
+            None if has_sources && number == similar.len() + 1 => self.import_from_sources(&query),
+            None => None,
+        }
+    }
+
+    /// Describes how often a food is used in logs and recipes, e.g. `3 log entries, 1 recipe`
+    fn usage_summary(&self, food_id: &str) -> String {
+        let entries = self.log_repo.entries_for_food(food_id).len();
+        let recipes = self.food_repo.get_foods()
+            .values()
+            .filter(|food| food.components.iter().any(|(id, _)| id == food_id))
+            .count();
+        let count = |count: usize, one: &str, many: &str| format!("{} {}", count, if count == 1 { one } else { many });
+        format!(
+            "{}, {}",
+            count(entries, Message::LogEntryOne.text(), Message::LogEntryMany.text()),
+            count(recipes, Message::RecipeOne.text(), Message::RecipeMany.text())
+        )
+    }
+
+    /// Builds a prompt for the size of one serving, such as `1 cup` or `30 g`
+    fn prompt_serving_size() -> prompt::Prompt<ServingSize> {
+        prompt::Prompt::new(
+            Message::ServingSizePrompt.text(),
+            |input| match serving_units::parse_quantity(input)? {
+                (amount, Some(unit)) => Ok(ServingSize::new(amount, unit)),
+                (_, None) => Err(Message::IncludeUnit.text().to_string()),
+            },
+        )
+    }
+
+    /// Builds a prompt for an amount of a food
+    ///
+    /// The user may type a plain number of servings or an amount with a
+    /// unit (`85 g`, `1.5 cups`); amounts the food cannot be measured in
+    /// are rejected with the reason (see `Food::servings_for`).
+    ///
+    /// # Returns
+    /// A prompt producing the quantity as entered and its value in servings
+    fn prompt_amount(food: &Food) -> prompt::Prompt<(Quantity, f64)> {
+        Self::amount_prompt(food, None)
+    }
+
+    /// Builds a prompt for the amount of a food being logged
+    ///
+    /// Like `prompt_amount`, but pressing Enter logs the food's default
+    /// servings (see `Food::servings_to_log`), so foods usually eaten in the
+    /// same amount need no typing.
+    fn prompt_amount_to_log(food: &Food) -> prompt::Prompt<(Quantity, f64)> {
+        let servings = food.servings_to_log();
+        Self::amount_prompt(food, Some(servings)).default((Quantity::servings(servings), servings))
+    }
+
+    /// Builds the amount prompt, showing the servings Enter gives if any
+    fn amount_prompt(food: &Food, offered: Option<f64>) -> prompt::Prompt<(Quantity, f64)> {
+        let offered = offered.map_or(String::new(), |servings| format!(" [{}]", servings));
+        let message = match food.serving_size {
+            Some(size) => Message::EnterAmountWithSize.format(&[&size, &offered]),
+            None => Message::EnterAmount.format(&[&offered]),
+        };
+        let food = food.clone();
+        prompt::Prompt::new(&message, move |input| {
+            let quantity = Quantity::parse(input)?;
+            Ok((quantity, quantity.to_servings(&food)?))
+        })
+    }
+
+    /// Asks for an optional category and tags for a new food
+    ///
+    /// Both are searchable: the food repository derives keywords from them
+    /// (see `models::derived_keywords`), so they need not be repeated as keywords.
+    fn prompt_food_metadata() -> prompt::PromptResult<(Option<String>, HashSet<String>)> {
+        let category = prompt::text(Message::CategoryPrompt.text())
+            .optional()
+            .ask()?;
+        let tags = prompt::keywords(Message::TagsPrompt.text())
+            .ask()?;
+        Ok((category, tags))
+    }
+
+    /// Points out values of a new food that look like typos and asks whether to keep them
+    ///
+    /// # Returns
+    /// true if nothing looks wrong or the user keeps the values anyway
+    fn confirm_food_values(&self, food: &Food) -> bool {
+        let warnings = validation::food_warnings(food);
+        if warnings.is_empty() {
+            return true;
+        }
+        outln!("{}", Message::CheckFoodValues.format(&[&food.name]));
+        for warning in &warnings {
+            outln!("  {}", warning);
+        }
+        self.confirm(Message::ConfirmAddFoodAnyway.text(), Some(CommandType::AddFood))
+    }
+
+    /// Formats a nutrient-density annotation as a suffix for a food list row
+    ///
+    /// # Returns
+    /// ` * high protein` and the like, or an empty string for foods that are not highlighted
+    fn highlight_suffix(annotation: &str) -> String {
+        if annotation.is_empty() {
+            String::new()
+        } else {
+            format!(" * {}", annotation)
+        }
+    }
+
+    /// Formats a day's food cost, noting entries whose food has no cost
+    ///
+    /// # Returns
+    /// `4.20` or `4.20 (2 entries without a cost)`
+    fn describe_cost(summary: &CostSummary) -> String {
+        match summary.unpriced_entries {
+            0 => format!("{:.2}", summary.cost),
+            1 => Message::CostOneUnpriced.format(&[&summary.cost]),
+            n => Message::CostUnpriced.format(&[&summary.cost, &n]),
+        }
+    }
+
+    /// Asks which meal a log entry belongs to
+    ///
+    /// The meal matching the time the food was eaten is the default (see
+    /// `MealSlot::for_time`); the last option leaves the entry unassigned.
+    fn prompt_meal_slot(time: NaiveTime) -> prompt::PromptResult<Option<MealSlot>> {
+        Self::prompt_meal_slot_default(MealSlot::for_time(time))
+    }
+
+    /// Asks which meal an entry belongs to, suggesting `default`
+    fn prompt_meal_slot_default(default: MealSlot) -> prompt::PromptResult<Option<MealSlot>> {
+        outln!("{}", Message::MealLabel);
+        let default_index = MealSlot::ALL.iter().position(|slot| *slot == default).unwrap_or(0) + 1;
+        let choice = prompt::choice(&[
+            Message::Breakfast.text(),
+            Message::Lunch.text(),
+            Message::Dinner.text(),
+            Message::Snack.text(),
+            Message::NoMeal.text(),
+        ])
+            .default(default_index)
+            .ask()?;
+        Ok(MealSlot::ALL.get(choice - 1).copied())
+    }
+
+    /// Builds a prompt for a time of day on a date
+    ///
+    /// Empty input gives `default`. Times skipped by a clock change are rejected.
+    fn prompt_time_on(date: NaiveDate, message: &str, default: NaiveTime) -> prompt::Prompt<NaiveTime> {
+        prompt::time(message)
+            .validate(move |time| match food_log::local_timestamp(date, *time) {
+                Some(_) => Ok(()),
+                None => Err(Message::TimeSkippedByClockChange.format(&[&time.format("%H:%M"), &date])),
+            })
+            .default(default)
+    }
+
+    /// Asks when a food was eaten, if it is being logged for another day
+    ///
+    /// Entries for today are stamped with the current time without asking.
+    /// For other days the current time of day is the default.
+    ///
+    /// # Returns
+    /// * `Ok(None)` - Logging for today: use the current time
+    /// * `Ok(Some(DateTime))` - The time the food was eaten
+    fn prompt_log_time(&self) -> prompt::PromptResult<Option<DateTime<Local>>> {
+        let now = Local::now();
+        if self.current_date == now.date_naive() {
+            return Ok(None);
+        }
+        let default = NaiveTime::from_hms_opt(now.hour(), now.minute(), 0).unwrap_or_default();
+        let message = Message::TimeEatenPrompt.format(&[&default.format("%H:%M")]);
+        let time = Self::prompt_time_on(self.current_date, &message, default).ask()?;
+        Ok(food_log::local_timestamp(self.current_date, time))
+    }
+
+    /// Builds a prompt for a new, unique food ID
+    ///
+    /// Rejects empty IDs, IDs containing spaces, and IDs already present
+    /// in the food database, asking again until a usable ID is entered.
+    fn prompt_new_food_id(&self) -> prompt::Prompt<String> {
+        let existing: HashSet<String> = self.food_repo.get_foods().keys().cloned().collect();
+
+        prompt::text(Message::EnterNewFoodId.text())
+            .validate(prompt::non_empty(Message::FoodIdEmpty.text()))
+            .validate(prompt::no_whitespace(Message::FoodIdSpaces.text()))
+            .validate(move |id| {
+                if existing.contains(id) {
+                    Err(Message::FoodIdExists.format(&[&id]))
+                } else {
+                    Ok(())
+                }
+            })
+    }
+
+    /// Prints foods as a numbered table, marking nutrient-dense ones (see `ui::highlight`)
+    fn print_food_choices(foods: &[&Food]) {
+        let thresholds = DensityThresholds::from_env();
+        let mut table = Table::new()
+            .column("#").min_width(4)
+            .column("ID").min_width(10)
+            .column(Message::Name.text()).min_width(20).max_width(NAME_COLUMN_WIDTH)
+            .column(Message::CaloriesColumn.text()).min_width(10);
+
+        let mut highlighted = false;
+        for (i, food) in foods.iter().enumerate() {
+            let annotation = thresholds.annotation(food);
+            highlighted |= !annotation.is_empty();
+            table.row_with_note([
+                (i + 1).to_string(), food.id.clone(), food.name.clone(), format!("{:.1}", food.calories_per_serving),
+            ], &Self::highlight_suffix(&annotation));
+        }
+        outln!("{}", table);
+        if highlighted {
+            outln!("{}", thresholds.legend());
+        }
+    }
+
+    /// Asks for a food by its number in a list, its ID, or a filter (see `ui::food_picker`)
+    ///
+    /// Each filter narrows the list last shown and prints it again, numbered
+    /// anew, until the user picks a food.
+    ///
+    /// # Arguments
+    /// * `message` - Question printed before each input attempt
+    /// * `foods` - Foods the numbers and filters refer to
+    /// * `shown` - Whether `foods` has already been printed with `print_food_choices`
+    /// * `optional` - Whether empty input finishes without a food
+    ///
+    /// # Returns
+    /// * `Ok(Some(String))` - The ID of the chosen food
+    /// * `Ok(None)` - If the input was empty and `optional` is set
+    /// * `Err(PromptError)` - If the user cancelled or the input ended
+    fn pick_food(&self, message: &str, foods: &[&Food], shown: bool, optional: bool) -> prompt::PromptResult<Option<String>> {
+        let known: HashSet<String> = self.food_repo.get_foods().keys().cloned().collect();
+        let mut listed: Vec<&Food> = if shown { foods.to_vec() } else { Vec::new() };
+
+        loop {
+            let ids: Vec<String> = listed.iter().map(|food| food.id.clone()).collect();
+            let known = known.clone();
+            let pick = prompt::Prompt::new(message, move |input| food_picker::parse(input, &ids, &known))
+                .optional()
+                .ask()?;
+            match pick {
+                Some(Pick::Food(id)) => return Ok(Some(id)),
+                None if optional => return Ok(None),
+                None => outln!("{}", Message::PickFoodHint),
+                Some(Pick::Filter(text)) => {
+                    // Filters narrow the list shown; an empty one starts over with every food
+                    let matching = if text.is_empty() {
+                        foods.to_vec()
+                    } else if listed.is_empty() {
+                        food_picker::filter(foods, &text)
+                    } else {
+                        food_picker::filter(&listed, &text)
+                    };
+                    if matching.is_empty() {
+                        outln!("{}", Message::NoFoodsMatch.format(&[&text]));
+                    } else {
+                        listed = matching;
+                        Self::print_food_choices(&listed);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Builds a prompt for the ID of a food that already exists in the database
+    fn prompt_existing_food_id(&self, message: &str) -> prompt::Prompt<String> {
+        let known: HashSet<String> = self.food_repo.get_foods().keys().cloned().collect();
+
+        prompt::text(message).validate(move |id| {
+            if known.contains(id) {
+                Ok(())
+            } else {
+                Err(Message::FoodIdMissing.format(&[&id]))
+            }
+        })
+    }
+      /// Displays all foods in the database in a formatted table
+    /// 
+    /// This method provides a comprehensive view of the food database:
+    /// 1. Retrieves the active foods from the repository, sorted by name
+    /// 2. Displays them in a formatted table with columns for ID, Name, Keywords, Category, and Calories,
+    ///    cutting long text short so the columns line up (see `ui::food_list`)
+    /// 3. Handles empty database gracefully with appropriate messaging
+    /// 4. Splits long lists into pages the user can move through and re-sort
+    ///    by name, calories, or ID
+    /// 
+    /// The tabular format makes it easy for users to:
+    /// - Browse available foods before logging consumption
+    /// - See nutritional information at a glance
+    /// - Identify foods by their keywords for search purposes
+    /// - Copy food IDs for use in logging or composite food creation
+    fn view_foods(&self) {
+        outln!("\n{}", style::heading(Message::ViewFoods));
+        
+        let archived_count = self.food_repo.get_all_foods().len() - self.food_repo.get_active_foods().len();
+        
+        // Handle empty database case
+        if self.food_repo.get_active_foods().is_empty() {
+            outln!("{}", Message::NoFoodsInDatabase);
+            return;
+        }
+        
+        // Nutrient-dense foods are marked (see ui::highlight)
+        let thresholds = DensityThresholds::from_env();
+        let categories = self.food_repo.categories();
+        let mut sort = FoodSort::default();
+        let mut filter = CategoryFilter::default();
+        let mut page = 0;
+        loop {
+            let foods: Vec<&Food> = self.food_repo.get_foods_sorted(sort)
+                .into_iter()
+                .filter(|food| filter.allows(food))
+                .collect();
+            let pages = food_list::page_count(foods.len());
+            
+            if pages > 1 || filter != CategoryFilter::All {
+                outln!("\n{}", Message::FoodsPageOf.format(&[&(page + 1), &pages, &foods.len(), &filter, &Message::from(sort)]));
+            }
+            outln!("{}", food_list::header());
+            let mut highlighted = false;
+            for food in food_list::page(&foods, page) {
+                let annotation = thresholds.annotation(food);
+                highlighted |= !annotation.is_empty();
+                outln!("{}", food_list::row(food, &Self::highlight_suffix(&annotation)));
+            }
+            if highlighted {
+                outln!("{}", thresholds.legend());
+            }
+            
+            // A short list without categories needs no navigation
+            if pages == 1 && categories.is_empty() {
+                break;
+            }
+            let action = prompt::text(Message::FoodsPageActions.text())
+                .validate(move |input: &String| food_list::parse_action(input, page, pages).map(|_| ()))
+                .ask();
+            let Ok(input) = action else {
+                break;
+            };
+            match food_list::parse_action(&input, page, pages) {
+                Ok(PageAction::Go(index)) => page = index,
+                Ok(PageAction::Sort) => {
+                    let Ok(choice) = prompt::choice(&[Message::Name.text(), Message::SortByCalories.text(), "ID"]).ask() else {
+                        break;
+                    };
+                    sort = [FoodSort::Name, FoodSort::Calories, FoodSort::Id][choice - 1];
+                    page = 0;
+                }
+                Ok(PageAction::Category) => {
+                    let Ok(chosen) = self.prompt_category_filter(&categories) else {
+                        break;
+                    };
+                    filter = chosen;
+                    page = 0;
+                }
+                Ok(PageAction::Done) | Err(_) => break,
+            }
+        }
+        
+        if archived_count > 0 {
+            outln!("{}", Message::ArchivedNotShown.format(&[&archived_count]));
+        }
+    }
+
+    /// Asks which category View Foods should show
+    ///
+    /// # Arguments
+    /// * `categories` - Categories of the active foods with their food counts
+    fn prompt_category_filter(&self, categories: &[(String, usize)]) -> prompt::PromptResult<CategoryFilter> {
+        let uncategorized = self.food_repo.get_active_foods().iter().filter(|food| food.category.is_none()).count();
+        let mut options = vec![Message::AllCategories.text().to_string()];
+        options.extend(categories.iter().map(|(name, count)| format!("{} ({})", name, count)));
+        if uncategorized > 0 {
+            options.push(format!("{} ({})", category::UNCATEGORIZED, uncategorized));
+        }
+        let labels: Vec<&str> = options.iter().map(String::as_str).collect();
+        let choice = prompt::choice(&labels).default(1).ask()?;
+        Ok(match choice {
+            1 => CategoryFilter::All,
+            n if n - 2 < categories.len() => CategoryFilter::Named(categories[n - 2].0.clone()),
+            _ => CategoryFilter::Uncategorized,
+        })
+    }
+      /// Records food consumption for the current date using the Command pattern
+    /// 
+    /// This method handles food logging with the following workflow:
+    /// 1. Offers choice between viewing all foods, searching by keywords, or
+    ///    quick logging a frequent or recent food (see `quick_log`)
+    /// 2. Displays available foods in a formatted table for easy selection; when
+    ///    a search finds nothing, suggests similar foods or a search of the
+    ///    food sources instead (see `suggest_foods`)
+    /// 3. Validates that the selected food exists in the database
+    /// 4. Records the number of servings consumed
+    /// 5. Uses Command pattern to enable undo functionality
+    /// 
+    /// The search integration allows users to quickly find foods without
+    /// browsing the entire database. All logged entries are associated with
+    /// the current working date, enabling day-specific tracking.
+    /// 
+    /// Uses AddLogEntryCommand for undo support and consistent data management.
+    fn log_food(&mut self) {
+        outln!("\n{}", style::heading(Message::LogFood));
+        
+        // Ensure food database is not empty
+        let foods = self.food_repo.get_all_foods();
+        if foods.is_empty() {
+            outln!("{}", Message::NoFoodsAddFirst);
+            return;
+        }
+        
+        // Offer food selection methods (empty input shows all foods)
+        let Ok(method) = prompt::choice(&[
+            Message::ShowAllFoods.text(),
+            Message::SearchByKeyword.text(),
+            Message::QuickLogOption.text(),
+            Message::AdvancedSearchOption.text(),
+            Message::MealTemplatesOption.text(),
+            Message::BatchLogOption.text(),
+            Message::DescribeOption.text(),
+        ]).default(1).ask() else {
+            return;
+        };
+        if method == 3 {
+            self.quick_log();
+            return;
+        }
+        if method == 5 {
+            self.meal_templates();
+            return;
+        }
+        if method == 6 {
+            self.batch_log();
+            return;
+        }
+        if method == 7 {
+            self.log_described_foods();
+            return;
+        }
+
+        // Get foods based on user's selection method
+        let (mut selected_foods, terms) = match method {
+            2 => self.search_foods(),                                 // Use search functionality
+            4 => self.advanced_search(),                              // Keywords with nutrient filters
+            _ => (self.food_repo.get_active_foods(), HashSet::new()), // Show all foods that are not archived
+        };
+
+        // Search results stay in match order; the full list shows the most popular first,
+        // with older uses counting less (see models::usage)
+        if method == 1 {
+            let found = usage::collect_usage(self.log_repo.get_logs(), self.food_repo.get_foods(), Local::now().date_naive());
+            usage::sort_by_popularity(&mut selected_foods, &found);
+        }
+
+        let food_id = if selected_foods.is_empty() {
+            // Offer similar foods or the food sources instead of giving up
+            outln!("{}", Message::NoFoodsFound);
+            if terms.is_empty() {
+                return;
+            }
+            let Some(food_id) = self.suggest_foods(&terms) else {
+                return;
+            };
+            food_id
+        } else {
+            outln!("\n{}", Message::AvailableFoods);
+            Self::print_food_choices(&selected_foods);
+
+            // Get user's food selection by number, ID, or filter (see ui::food_picker)
+            let Ok(Some(food_id)) = self.pick_food(&format!("\n{}", Message::EnterNumberOrFoodId), &selected_foods, true, false) else {
+                return;
+            };
+            food_id
+        };
+
+        // Get the amount consumed, in servings or any unit the food can be measured in
+        let Some(food) = self.food_repo.get_food(&food_id) else {
+            return;
+        };
+        self.warn_dietary_conflicts(food);
+        let Ok((quantity, servings)) = Self::prompt_amount_to_log(food).ask() else {
+            return;
+        };
+
+        // Ask for the time only when logging for another day
+        let Ok(timestamp) = self.prompt_log_time() else {
+            return;
+        };
+
+        // Assign the entry to a meal (defaults to the meal matching the time eaten)
+        let time = timestamp.unwrap_or_else(Local::now).time();
+        let Ok(meal) = Self::prompt_meal_slot(time) else {
+            return;
+        };
+
+        self.add_log_entry(&food_id, quantity, servings, meal, timestamp);
+    }
+
+    /// Logs a food from the most used and most recently logged foods
+    ///
+    /// Foods are numbered across both lists, and recent foods already shown
+    /// as most used are skipped. Choosing a number logs the food again with
+    /// the amount of its latest entry, assigned to the meal matching the
+    /// time of day, so a repeat takes only the number and Enter.
+    fn quick_log(&mut self) {
+        let foods = self.food_repo.get_foods();
+        let most_used: Vec<LoggedFood> = self.log_repo.most_used_foods(usize::MAX)
+            .into_iter()
+            .filter(|logged| foods.contains_key(&logged.food_id))
+            .take(QUICK_LOG_COUNT)
+            .collect();
+        let recent: Vec<LoggedFood> = self.log_repo.recent_foods(usize::MAX)
+            .into_iter()
+            .filter(|logged| foods.contains_key(&logged.food_id))
+            .filter(|logged| !most_used.iter().any(|used| used.food_id == logged.food_id))
+            .take(QUICK_LOG_COUNT)
+            .collect();
+
+        if most_used.is_empty() {
+            outln!("{}", Message::NothingLoggedYet);
+            return;
+        }
+
+        let print_section = |title: &str, logged_foods: &[LoggedFood], first: usize| {
+            outln!("\n{}:", title);
+            let mut table = Table::new()
+                .column("#").min_width(4)
+                .column("ID").min_width(10)
+                .column(Message::Name.text()).min_width(20).max_width(NAME_COLUMN_WIDTH)
+                .column(Message::UsesColumn.text()).min_width(6)
+                .column(Message::LastAmountColumn.text()).min_width(14)
+                .column(Message::LastLoggedColumn.text());
+            for (offset, logged) in logged_foods.iter().enumerate() {
+                let name = foods.get(&logged.food_id).map_or("", |food| food.name.as_str());
+                table.row([
+                    (first + offset).to_string(), logged.food_id.clone(), name.to_string(), logged.uses.to_string(),
+                    logged.last_quantity.to_string(), logged.last_date.to_string(),
+                ]);
+            }
+            outln!("{}", table);
+        };
+        print_section(Message::MostUsed.text(), &most_used, 1);
+        if !recent.is_empty() {
+            print_section(Message::Recent.text(), &recent, most_used.len() + 1);
+        }
+
+        let choices: Vec<LoggedFood> = most_used.into_iter().chain(recent).collect();
+        let error = Message::NumberBetween.format(&[&choices.len()]);
+        let Ok(number) = prompt::number::<usize>(&format!("\n{}", Message::LogWhichFood), &error)
+            .validate(prompt::in_range(1, choices.len(), &error))
+            .ask() else {
+            return;
+        };
+        let logged = &choices[number - 1];
+
+        // The food may no longer be measurable in the unit of its latest entry
+        let Some(food) = self.food_repo.get_food(&logged.food_id) else {
+            return;
+        };
+        let quantity = logged.last_quantity;
+        let servings = match quantity.to_servings(food) {
+            Ok(servings) => servings,
+            Err(e) => {
+                outln!("{}", Message::CannotRepeatAmount.format(&[&logged.food_id, &e]));
+                return;
+            }
+        };
+        self.warn_dietary_conflicts(food);
+        let food_id = logged.food_id.clone();
+        let Ok(timestamp) = self.prompt_log_time() else {
+            return;
+        };
+        let meal = Some(MealSlot::for_time(timestamp.unwrap_or_else(Local::now).time()));
+        self.add_log_entry(&food_id, quantity, servings, meal, timestamp);
+    }
+
+    /// Lists, logs, creates, and deletes meal templates
+    ///
+    /// Logging a template adds one entry per food as a single command, so
+    /// one undo removes them all (see `commands::macro_command`).
+    fn meal_templates(&mut self) {
+        loop {
+            outln!("\n{}", style::heading(Message::MealTemplates.text()));
+            let templates = self.meal_template_repo.templates();
+            if templates.is_empty() {
+                outln!("{}", Message::NoMealTemplates);
+            }
+            for (index, template) in templates.iter().enumerate() {
+                let meal = template.meal.map_or(String::new(), |slot| format!(" ({})", Message::from(slot)));
+                outln!("{:<3} {}{}: {} ({:.0} kcal)",
+                        index + 1, template.name, meal, template.describe_items(),
+                        template.total_calories(self.food_repo.get_foods()));
+            }
+
+            let Ok(choice) = prompt::choice(&[
+                Message::LogTemplate.text(),
+                Message::CreateTemplate.text(),
+                Message::DeleteTemplate.text(),
+                Message::Back.text(),
+            ]).ask() else {
+                return;
+            };
+            match choice {
+                1 | 3 if self.meal_template_repo.templates().is_empty() => outln!("{}", Message::CreateTemplateFirst),
+                1 => {
+                    let Ok(index) = self.prompt_template_number(Message::LogWhichTemplate.text()) else {
+                        continue;
+                    };
+                    let template = self.meal_template_repo.templates()[index].clone();
+                    self.log_meal_template(&template);
+                    return;
+                }
+                2 => self.create_meal_template(),
+                3 => {
+                    let Ok(index) = self.prompt_template_number(Message::DeleteWhichTemplate.text()) else {
+                        continue;
+                    };
+                    let name = self.meal_template_repo.templates()[index].name.clone();
+                    if !self.confirm(&Message::ConfirmDeleteTemplate.format(&[&name]), None) {
+                        outln!("{}", Message::DeleteCancelled);
+                        continue;
+                    }
+                    if self.meal_template_repo.remove_template(&name).is_some() {
+                        outln!("{}", Message::DeletedTemplate.format(&[&name]));
+                        // Not routed through a command, so trigger autosave explicitly
+                        self.autosave(AutosaveTrigger::Command);
+                    }
+                }
+                _ => return,
+            }
+        }
+    }
+
+    /// Asks for the number of a meal template as listed
+    ///
+    /// # Returns
+    /// The index of the template in `MealTemplateRepository::templates`
+    fn prompt_template_number(&self, message: &str) -> prompt::PromptResult<usize> {
+        let count = self.meal_template_repo.templates().len();
+        let error = Message::NumberBetween.format(&[&count]);
+        prompt::number::<usize>(message, &error)
+            .validate(prompt::in_range(1, count, &error))
+            .ask()
+            .map(|number| number - 1)
+    }
+
+    /// Logs every food of a meal template on the current date as one command
+    ///
+    /// The servings of each food can be changed first, and 0 leaves the food
+    /// out. Foods no longer in the database are skipped.
+    fn log_meal_template(&mut self, template: &MealTemplate) {
+        let mut items: Vec<ResolvedItem> = Vec::new();
+        for item in &template.items {
+            let Some(food) = self.food_repo.get_food(&item.food_id) else {
+                outln!("{}", Message::SkippingMissingFood.format(&[&item.food_id]));
+                continue;
+            };
+            self.warn_dietary_conflicts(food);
+            let Ok(servings) = prompt::non_negative_f64(
+                &Message::TemplateServingsPrompt.format(&[&food.name, &food.id, &item.servings]),
+                Message::EnterServingsOrZero.text(),
+            ).default(item.servings).ask() else {
+                return;
+            };
+            if servings > 0.0 {
+                items.push(ResolvedItem { food_id: item.food_id.clone(), quantity: Quantity::servings(servings), servings });
+            }
+        }
+        if items.is_empty() {
+            outln!("{}", Message::NothingToLog);
+            return;
+        }
+
+        let Ok(timestamp) = self.prompt_log_time() else {
+            return;
+        };
+        let time = timestamp.unwrap_or_else(Local::now).time();
+        let Ok(meal) = Self::prompt_meal_slot_default(template.meal.unwrap_or_else(|| MealSlot::for_time(time))) else {
+            return;
+        };
+
+        let description = format!("Log meal template '{}': {} foods on {}", template.name, items.len(), self.current_date.format("%Y-%m-%d"));
+        match self.log_several(description, &items, meal, timestamp) {
+            Ok(total_calories) => outln!("{}", Message::LoggedTemplate.format(&[&template.name, &items.len(), &total_calories])),
+            Err(e) => outln!("{}", Message::ErrorLoggingFood.format(&[&e])),
+        }
+    }
+
+    /// Logs several foods typed on one line, such as `apple:1, milk:0.5`
+    ///
+    /// The line is parsed and every food checked before anything is logged
+    /// (see `models::batch_log`); a line with problems is asked for again.
+    /// All foods get the same time and meal and are logged as one command,
+    /// so a single undo removes them all.
+    fn batch_log(&mut self) {
+        outln!("\n{}", Message::BatchLogHint);
+        outln!("{}", Message::BatchLogHintContinued);
+        let foods = self.food_repo.get_foods().clone();
+        let Ok(items) = prompt::Prompt::new(Message::FoodsPrompt.text(), move |input| {
+            let items = batch_log::parse_batch(input)?;
+            batch_log::resolve(&items, &foods).map_err(|problems| problems.join("\n"))
+        }).ask() else {
+            return;
+        };
+
+        let mut table = Table::new()
+            .column("ID").min_width(10)
+            .column(Message::Name.text()).min_width(20).max_width(NAME_COLUMN_WIDTH)
+            .column(Message::AmountColumn.text()).min_width(14)
+            .column(Message::CaloriesColumn.text());
+        let mut total_calories = 0.0;
+        for item in &items {
+            let Some(food) = self.food_repo.get_food(&item.food_id) else {
+                continue;
+            };
+            let calories = food.calories_per_serving * item.servings;
+            total_calories += calories;
+            table.row([food.id.clone(), food.name.clone(), item.quantity.to_string(), format!("{:.1}", calories)]);
+        }
+        table.footer(["", Message::Total.text(), "", &format!("{:.1}", total_calories)]);
+        outln!("{}", table);
+        for item in &items {
+            if let Some(food) = self.food_repo.get_food(&item.food_id) {
+                self.warn_dietary_conflicts(food);
+            }
+        }
+
+        let Ok(timestamp) = self.prompt_log_time() else {
+            return;
+        };
+        let time = timestamp.unwrap_or_else(Local::now).time();
+        let Ok(meal) = Self::prompt_meal_slot(time) else {
+            return;
+        };
+
+        let description = format!("Batch log: {} foods on {}", items.len(), self.current_date.format("%Y-%m-%d"));
+        match self.log_several(description, &items, meal, timestamp) {
+            Ok(_) => outln!("{}", Message::LoggedFoods.format(&[&items.len(), &total_calories])),
+            Err(e) => outln!("{}", Message::ErrorLoggingFood.format(&[&e])),
+        }
+    }
+
+    /// Logs foods described in plain words, such as `2 eggs and a slice of wheat bread`
+    ///
+    /// Each phrase is matched to the best food of a ranked search on its
+    /// words (see `models::food_phrase`). The interpretation is shown and
+    /// must be confirmed; the foods are then logged as one command, so a
+    /// single undo removes them all.
+    fn log_described_foods(&mut self) {
+        let Ok(phrases) = prompt::Prompt::new(Message::WhatDidYouEat.text(), food_phrase::parse).ask() else {
+            return;
+        };
+
+        let mut items: Vec<ResolvedItem> = Vec::new();
+        let mut total_calories = 0.0;
+        let mut table = Table::new()
+            .column(Message::YouWroteColumn.text()).min_width(28)
+            .column(Message::UnderstoodAsColumn.text());
+        for phrase in &phrases {
+            let hits = self.food_repo.search_ranked(&phrase.terms, true);
+            let Some(hit) = hits.first() else {
+                table.row([phrase.text.as_str(), Message::NoMatchingFood.text()]);
+                continue;
+            };
+            let food = hit.food;
+            match phrase.to_servings(food) {
+                Ok((quantity, servings)) => {
+                    let calories = food.calories_per_serving * servings;
+                    total_calories += calories;
+                    table.row([phrase.text.clone(), Message::UnderstoodFood.format(&[&quantity, &food.name, &food.id, &calories])]);
+                    items.push(ResolvedItem { food_id: food.id.clone(), quantity, servings });
+                }
+                Err(e) => table.row([phrase.text.clone(), format!("{}: {}", food.id, e)]),
+            }
+        }
+        outln!("\n{}", table);
+        if items.len() < phrases.len() {
+            outln!("{}", Message::NothingLoggedDescribed);
+            return;
+        }
+        outln!("{}", Message::TotalKcal.format(&[&total_calories]));
+        if !self.confirm(Message::ConfirmLogFoods.text(), Some(CommandType::AddLog)) {
+            outln!("{}", Message::NothingLogged);
+            return;
+        }
+
+        for item in &items {
+            if let Some(food) = self.food_repo.get_food(&item.food_id) {
+                self.warn_dietary_conflicts(food);
+            }
+        }
+        let Ok(timestamp) = self.prompt_log_time() else {
+            return;
+        };
+        let time = timestamp.unwrap_or_else(Local::now).time();
+        let Ok(meal) = Self::prompt_meal_slot(time) else {
+            return;
+        };
+
+        let description = format!("Log described foods: {} foods on {}", items.len(), self.current_date.format("%Y-%m-%d"));
+        match self.log_several(description, &items, meal, timestamp) {
+            Ok(_) => outln!("{}", Message::LoggedFoods.format(&[&items.len(), &total_calories])),
+            Err(e) => outln!("{}", Message::ErrorLoggingFood.format(&[&e])),
+        }
+    }
+
+    /// Logs several foods as one undoable command
+    ///
+    /// # Arguments
+    /// * `description` - Description shown in the command history
+    /// * `items` - Foods with their amounts, all known to the food database
+    /// * `meal` - Meal every entry is assigned to, if any
+    /// * `timestamp` - Time every entry was eaten; `None` means now
+    ///
+    /// # Returns
+    /// * `Ok(f64)` - Calories logged in total
+    /// * `Err(String)` - If an entry could not be logged; none of them are then
+    fn log_several(
+        &mut self,
+        description: String,
+        items: &[ResolvedItem],
+        meal: Option<MealSlot>,
+        timestamp: Option<DateTime<Local>>,
+    ) -> Result<f64, String> {
+        let mut command = MacroCommand::new(CommandType::AddLog, description);
+        let mut total_calories = 0.0;
+        for item in items {
+            let calories = self.food_repo.get_food(&item.food_id).map_or(0.0, |food| food.calories_per_serving * item.servings);
+            total_calories += calories;
+            let mut entry = AddLogEntryCommand::new(&mut self.log_repo, self.current_date, item.food_id.clone(), item.servings)
+                .with_quantity(item.quantity)
+                .with_calories(calories);
+            if let Some(meal) = meal {
+                entry = entry.with_meal(meal);
+            }
+            if let Some(timestamp) = timestamp {
+                entry = entry.with_timestamp(timestamp);
+            }
+            command = command.with_command(Box::new(entry));
+        }
+
+        self.execute_command(Box::new(command))?;
+        for item in items {
+            self.food_repo.record_usage(&item.food_id, self.current_date);
+        }
+        Ok(total_calories)
+    }
+
+    /// Asks for a new meal template and stores it, replacing one with the same name
+    fn create_meal_template(&mut self) {
+        let Ok(name) = prompt::text(Message::TemplateNamePrompt.text())
+            .validate(prompt::non_empty(Message::EnterName.text()))
+            .ask() else {
+            return;
+        };
+        if self.meal_template_repo.get_template(&name).is_some() {
+            outln!("{}", Message::TemplateReplaced.format(&[&name]));
+        }
+        outln!("{}", Message::UsualMeal);
+        let Ok(meal) = prompt::choice(&[
+            Message::Breakfast.text(),
+            Message::Lunch.text(),
+            Message::Dinner.text(),
+            Message::Snack.text(),
+            Message::NoMeal.text(),
+        ]).default(MealSlot::ALL.len() + 1).ask() else {
+            return;
+        };
+
+        let mut template = MealTemplate::new(name, MealSlot::ALL.get(meal - 1).copied());
+        loop {
+            let Ok(food_id) = self.prompt_existing_food_id(Message::FoodIdToAdd.text()).optional().ask() else {
+                return;
+            };
+            let Some(food_id) = food_id else {
+                break;
+            };
+            let Ok(servings) = prompt::positive_f64(Message::ServingsDefaultOne.text(), Message::EnterPositiveServings.text())
+                .default(1.0)
+                .ask() else {
+                return;
+            };
+            template.add_item(food_id, servings);
+        }
+
+        let (name, count) = (template.name.clone(), template.items.len());
+        match self.meal_template_repo.save_template(template) {
+            Ok(_) => {
+                outln!("{}", Message::SavedTemplate.format(&[&name.trim(), &count]));
+                // Not routed through a command, so trigger autosave explicitly
+                self.autosave(AutosaveTrigger::Command);
+            }
+            Err(e) => outln!("{}.", e),
+        }
+    }
+
+    /// Lists the foods, among a food and its components, that conflict with
+    /// the dietary restrictions declared in the profile (see `models::dietary`)
+    fn dietary_conflicts(&self, food: &Food) -> Vec<Conflict> {
+        let restrictions = self.profile_repo.get_profile()
+            .map_or(&[][..], |profile| profile.restrictions.as_slice());
+        dietary::conflicts(food, self.food_repo.get_foods(), restrictions)
+    }
+
+    /// Prints a warning for each conflict between a food about to be logged
+    /// and the declared dietary restrictions
+    fn warn_dietary_conflicts(&self, food: &Food) {
+        for conflict in self.dietary_conflicts(food) {
+            outln!("{}", Message::WarningLine.format(&[&conflict]));
+        }
+    }
+
+    /// Logs an amount of a food for the current date through `AddLogEntryCommand`
+    ///
+    /// Reports the outcome, including the restore of an archived food.
+    /// Without a timestamp the entry is stamped with the current time.
+    fn add_log_entry(
+        &mut self,
+        food_id: &str,
+        quantity: Quantity,
+        servings: f64,
+        meal: Option<MealSlot>,
+        timestamp: Option<DateTime<Local>>,
+    ) {
+        // Create and execute log entry command for undo support
+        let food = self.food_repo.get_food(food_id);
+        let was_archived = food.is_some_and(|food| food.archived);
+        let calories = food.map(|food| food.calories_per_serving * servings);
+        let mut command = AddLogEntryCommand::new(
+            &mut self.log_repo,
+            self.current_date,
+            food_id.to_string(),
+            servings
+        ).with_quantity(quantity);
+        if let Some(calories) = calories {
+            command = command.with_calories(calories);
+        }
+        if let Some(meal) = meal {
+            command = command.with_meal(meal);
+        }
+        if let Some(timestamp) = timestamp {
+            command = command.with_timestamp(timestamp);
+        }
+        
+        match self.execute_command(Box::new(command)) {
+            Ok(_) => {
+                self.food_repo.record_usage(food_id, self.current_date);
+                if was_archived {
+                    outln!("{}", Message::ArchivedFoodRestored.format(&[&food_id]));
+                }
+                outln!("{}", Message::FoodLogged);
+            }
+            Err(e) => outln!("{}", Message::ErrorLoggingFood.format(&[&e])),
+        }
+    }
+
+    /// Logs a food without any prompts (used by the `yada log` subcommand)
+    ///
+    /// The entry goes through the same command as the interactive flow and the
+    /// log is saved immediately, regardless of the autosave policy.
+    ///
+    /// # Arguments
+    /// * `food_id` - ID of an existing food
+    /// * `quantity` - Amount in servings or a unit the food can be measured in
+    /// * `date` - Date to log for; defaults to the current date
+    /// * `meal` - Meal slot to assign the entry to, if any
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Confirmation message
+    /// * `Err(String)` - Validation, command, or save error
+    pub fn log_food_direct(&mut self, food_id: &str, quantity: Quantity, date: Option<NaiveDate>, meal: Option<MealSlot>) -> Result<String, String> {
+        let Some(food) = self.food_repo.get_food(food_id) else {
+            return Err(Message::FoodIdMissing.format(&[&food_id]));
+        };
+        if quantity.amount <= 0.0 {
+            return Err(Message::InvalidAmountPositive.text().to_string());
+        }
+        let servings = quantity.to_servings(food)?;
+
+        let mut message = Message::LoggedDirect.format(&[&quantity, &food.name, &(food.calories_per_serving * servings)]);
+        for conflict in self.dietary_conflicts(food) {
+            message.push_str(&format!("\n{}", Message::WarningLine.format(&[&conflict])));
+        }
+        let date = date.unwrap_or(self.current_date);
+        let calories = food.calories_per_serving * servings;
+
+        let mut command = AddLogEntryCommand::new(
+            &mut self.log_repo,
+            date,
+            food_id.to_string(),
+            servings
+        ).with_quantity(quantity).with_calories(calories);
+        if let Some(meal) = meal {
+            command = command.with_meal(meal);
+        }
+        self.execute_command(Box::new(command))?;
+        self.food_repo.record_usage(food_id, date);
+
+        if self.log_repo.is_dirty() {
+            self.log_repo.save().map_err(|e| Message::ErrorSavingLogData.format(&[&e]))?;
+        }
+        if self.food_repo.is_dirty() {
+            self.food_repo.save().map_err(|e| Message::ErrorSavingFoodData.format(&[&e]))?;
+        }
+
+        Ok(message)
+    }
+    /// Displays the food log for the current date with interactive management options
+    /// 
+    /// This method provides a comprehensive view of daily food consumption with:
+    /// 1. Formatted display of all logged food entries for the current date
+    /// 2. Calculation of total calories consumed vs target calories
+    /// 3. Interactive menu for deleting entries (edit functionality)
+    /// 4. Real-time display updates after modifications
+    /// 
+    /// Display includes:
+    /// - Food ID, name, servings, and calories for each entry
+    /// - Total calories consumed for the day, and their cost when foods have one
+    /// - Target calories based on user profile and calculation method
+    /// - Calorie difference (surplus/deficit) for diet tracking
+    /// 
+    /// The method integrates with the Repository pattern to access food and log data,
+    /// and the Strategy pattern for calorie calculations based on user preferences.
+    fn view_log(&mut self) {
+        loop {
+            outln!("\n{}", style::heading(Message::ViewLog));
+            
+            // Get log for current date
+            let log = self.log_repo.get_log(self.current_date).filter(|log| !log.entries.is_empty());
+            let has_entries = log.is_some();
+            
+            if let Some(log) = log {
+                outln!("{}", Message::FoodLogFor.format(&[&self.show_date(self.current_date)]));
+                let total_calories = self.print_log_entries(log);
+                outln!("{}", Message::TotalCalories.format(&[&total_calories]));
+                let cost = log.total_cost(self.food_repo.get_foods());
+                if cost.priced_entries > 0 {
+                    outln!("{}", Message::TotalCost.format(&[&Self::describe_cost(&cost)]));
+                }
+                
+                // If we have a profile, show target calories
+                if let Some(profile) = self.profile_repo.get_profile() {
+                    let calculator = self.calculator_factory.calculator_for(profile, self.current_date)
+                        .expect("the fallback calculator is always registered");
+                    
+                    let target_calories = calculator.calculate_target_with(profile, self.current_date, &self.target_pipeline()).target;
+                    
+                    outln!("{}", Message::TargetCalories.format(&[&target_calories]));
+                    if let Some(target) = profile.weekday_targets.get(self.current_date.weekday()) {
+                        outln!("{}", Message::WeekdayOverride.format(&[&Message::from(self.current_date.weekday()), &target]));
+                    }
+                    outln!("{}", Message::Difference.format(&[&(style::against_target(
+                        format!("{:.1}", total_calories - target_calories), total_calories, target_calories))]));
+                    if let Some(notice) = profile.body_metrics(self.current_date).estimate_notice() {
+                        outln!("{}", notice);
+                    }
+                }
+            } else {
+                outln!("{}", Message::NoFoodEntriesFor.format(&[&self.show_date(self.current_date)]));
+            }
+            
+            // With a meal plan, compare planned and logged calories per meal
+            if let Some(plan) = self.meal_plan_repo.get_plan(self.current_date) {
+                self.print_meal_compliance(plan, log);
+            }
+            
+            if let Some(log) = self.log_repo.get_log(self.current_date) {
+                Self::print_day_note(&log.note);
+            }
+            
+            // Show menu options
+            outln!("\n{}", Message::Options);
+            let options: &[&str] = if has_entries {
+                &[Message::DeleteFoodEntry.text(), Message::EditFoodEntry.text(), Message::PlanMeals.text(),
+                  Message::EditDayNote.text(), Message::ViewDateRange.text(), Message::CalorieSources.text(),
+                  Message::BackToMainMenu.text()]
+            } else {
+                &[Message::PlanMeals.text(), Message::EditDayNote.text(), Message::ViewDateRange.text(),
+                  Message::CalorieSources.text(), Message::BackToMainMenu.text()]
+            };
+            let choice = prompt::choice(options).ask();
+
+            match (has_entries, choice) {
+                (true, Ok(1)) => {
+                    self.delete_log_entry();
+                    // Continue the loop to refresh the display
+                },
+                (true, Ok(2)) => self.edit_log_entry(),
+                (true, Ok(3)) | (false, Ok(1)) => self.plan_meals(),
+                (true, Ok(4)) | (false, Ok(2)) => self.edit_day_note(),
+                (true, Ok(5)) | (false, Ok(3)) => self.view_log_range(),
+                (true, Ok(6)) | (false, Ok(4)) => self.view_calorie_sources(),
+                _ => break,
+            }
+        }
+    }
+
+    /// Prints a day's entries as a numbered table
+    ///
+    /// # Returns
+    /// The day's total calories
+    fn print_log_entries(&self, log: &DailyLog) -> f64 {
+        let mut table = Table::new()
+            .column("#").min_width(5)
+            .column(Message::TimeColumn.text()).min_width(6)
+            .column(Message::FoodIdColumn.text()).min_width(10)
+            .column(Message::Name.text()).min_width(20).max_width(NAME_COLUMN_WIDTH)
+            .column(Message::AmountColumn.text()).min_width(14)
+            .column(Message::MealColumn.text()).min_width(10)
+            .column(Message::CaloriesColumn.text()).min_width(10);
+
+        let mut total_calories = 0.0;
+        let mut changed = false;
+        for (i, entry) in log.entries.iter().enumerate() {
+            let food = self.food_repo.get_food(&entry.food_id);
+            let food_name = food.map_or(Message::Unknown.text().to_string(), |f| f.name.clone());
+
+            // Calories as logged, even for deleted foods; entries without a snapshot use the food's current values
+            let calories = entry.logged_calories(food);
+            let meal = entry.meal.map_or("-".to_string(), |slot| Message::from(slot).to_string());
+            let marker = if entry.calories_changed(food) { " *" } else { "" };
+            changed |= !marker.is_empty();
+
+            table.row_with_note([
+                (i + 1).to_string(), entry.timestamp.format("%H:%M").to_string(), entry.food_id.clone(), food_name,
+                entry.quantity.to_string(), meal, format!("{:.1}", calories),
+            ], marker);
+
+            total_calories += calories;
+        }
+
+        outln!("{}", table);
+        outln!("{}", table.rule());
+        if changed {
+            outln!("{}", Message::CaloriesAsLoggedNote);
+        }
+        total_calories
+    }
+
+    /// Shows the calories of every day in a date range, with a drill-down into one day
+    ///
+    /// The range is picked with `prompt_period`. Each day is listed with its
+    /// entries, calories, and the difference from its target (see
+    /// `LogRepository::get_logs_between`); picking a day's number shows its entries.
+    fn view_log_range(&mut self) {
+        outln!("\n{}", style::heading(Message::ViewDateRange));
+        let Some((start, end)) = self.prompt_period() else {
+            return;
+        };
+
+        let foods = self.food_repo.get_foods();
+        let logs = self.log_repo.get_logs_between(start, end);
+        let days: Vec<NaiveDate> = start.iter_days().take_while(|date| *date <= end).collect();
+        let pipeline = self.target_pipeline();
+        outln!("\n{}", Message::FoodLogRange.format(&[&self.show_date(start), &self.show_date(end)]));
+        let mut table = Table::new()
+            .column("#").min_width(4)
+            .column(Message::DateColumn.text()).min_width(15)
+            .column(Message::EntriesColumn.text()).min_width(8)
+            .column(Message::CaloriesColumn.text()).min_width(10)
+            .column(Message::TargetColumn.text()).min_width(10)
+            .column(Message::DifferenceColumn.text()).min_width(10);
+        for (number, date) in days.iter().enumerate() {
+            let log = logs.iter().find(|log| log.date == *date).filter(|log| !log.entries.is_empty());
+            let calories = log.map_or(0.0, |log| log.total_calories(foods));
+            let target = self.profile_repo.get_profile()
+                .filter(|profile| profile.latest_daily_profile(*date).is_some())
+                .and_then(|profile| self.calculator_factory.calculator_for(profile, *date)
+                    .map(|calculator| calculator.calculate_target_with(profile, *date, &pipeline).target));
+            let label = format!("{} {}", Message::from(date.weekday()), self.show_date(*date));
+            let (entries, calories, target, difference) = match (log, target) {
+                (Some(log), Some(target)) => (log.entries.len(), format!("{:.1}", calories), format!("{:.1}", target),
+                    style::against_target(format!("{:+.1}", calories - target), calories, target)),
+                (Some(log), None) => (log.entries.len(), format!("{:.1}", calories), "-".to_string(), "-".to_string()),
+                (None, _) => (0, "-".to_string(), "-".to_string(), "-".to_string()),
+            };
+            table.row([(number + 1).to_string(), label, entries.to_string(), calories, target, difference]);
+        }
+        outln!("{}", table);
+        outln!("{}", table.rule());
+        let days_logged = self.log_repo.days_logged_in(start, end).len();
+        let total_calories = self.log_repo.total_calories_between(start, end, foods);
+        if days_logged == 0 {
+            outln!("{}", Message::NoEntriesInRange);
+            return;
+        }
+        outln!("{}", Message::RangeTotal.format(&[&total_calories, &days_logged, &(total_calories / days_logged as f64)]));
+
+        // Drill down into single days until the user goes back
+        let count = days.len();
+        loop {
+            let Ok(Some(number)) = prompt::number::<usize>(
+                &Message::EnterDayNumber.format(&[&count]),
+                Message::EnterDayFromList.text(),
+            ).validate(prompt::in_range(1, count, Message::EnterDayFromList.text())).optional().ask() else {
+                return;
+            };
+            let date = days[number - 1];
+            match self.log_repo.get_log(date).filter(|log| !log.entries.is_empty()) {
+                Some(log) => {
+                    outln!("\n{}", Message::FoodLogFor.format(&[&self.show_date(date)]));
+                    let calories = self.print_log_entries(log);
+                    outln!("{}", Message::TotalCalories.format(&[&calories]));
+                }
+                None => outln!("{}", Message::NoFoodEntriesFor.format(&[&self.show_date(date)])),
+            }
+        }
+    }
+
+    /// Asks for a period ending at the working date or a custom date range
+    ///
+    /// # Returns
+    /// * `Some((start, end))` - The first and last day, both included
+    /// * `None` - If the user went back
+    fn prompt_period(&self) -> Option<(NaiveDate, NaiveDate)> {
+        let choice = prompt::choice(&[Message::Last7Days.text(), Message::Last30Days.text(), Message::CustomRange.text(), Message::Back.text()])
+            .default(1)
+            .ask()
+            .ok()?;
+        match choice {
+            1 => Some((self.current_date - Duration::days(6), self.current_date)),
+            2 => Some((self.current_date - Duration::days(29), self.current_date)),
+            3 => {
+                let start = prompt::date_or_today(Message::StartDatePrompt.text()).ask().ok()?;
+                let end = prompt::date_or_today(Message::EndDatePrompt.text())
+                    .validate(move |end| if *end < start { Err(Message::EndBeforeStart.text().to_string()) } else { Ok(()) })
+                    .default(self.current_date.max(start))
+                    .ask()
+                    .ok()?;
+                Some((start, end))
+            }
+            _ => None,
+        }
+    }
+
+    /// Shows the foods and categories that drive intake over a period
+    ///
+    /// Lists the foods logged most often and those contributing the most
+    /// calories, then each category's share of the calories (see
+    /// `models::calorie_sources`).
+    fn view_calorie_sources(&mut self) {
+        outln!("\n{}", style::heading(Message::CalorieSources));
+        let Some((start, end)) = self.prompt_period() else {
+            return;
+        };
+
+        let sources = CalorieSources::summarize(self.log_repo.get_logs_between(start, end), self.food_repo.get_foods());
+        outln!("\n{}", Message::CalorieSourcesRange.format(&[&self.show_date(start), &self.show_date(end)]));
+        if sources.is_empty() {
+            outln!("{}", Message::NoEntriesInRange);
+            return;
+        }
+        outln!("{}", Message::EntriesAndCalories.format(&[&sources.total_entries, &sources.total_calories]));
+
+        outln!("\n{}", Message::MostFrequentFoods);
+        let mut table = Self::calorie_sources_table();
+        for (number, food) in sources.top_by_frequency(TOP_FOOD_COUNT).into_iter().enumerate() {
+            table.row([
+                (number + 1).to_string(), food.name.clone(), food.entries.to_string(),
+                format!("{:.1}", food.calories), format!("{:.1}%", sources.percent_of_calories(food)),
+            ]);
+        }
+        outln!("{}", table);
+
+        outln!("\n{}", Message::MostCalories);
+        let mut table = Self::calorie_sources_table();
+        for (number, food) in sources.top_by_calories(TOP_FOOD_COUNT).into_iter().enumerate() {
+            table.row([
+                (number + 1).to_string(), food.name.clone(), food.entries.to_string(),
+                format!("{:.1}", food.calories), format!("{:.1}%", sources.percent_of_calories(food)),
+            ]);
+        }
+        outln!("{}", table);
+
+        outln!("\n{}", Message::CaloriesByCategory);
+        for share in &sources.categories {
+            outln!("{:<16} {} {:>6.1}% ({:.0} kcal)", share.category,
+                text_charts::progress_bar(share.percent, 100.0, text_charts::PROGRESS_WIDTH), share.percent, share.calories);
+        }
+    }
+
+    /// Creates the table of foods ranked by entries or calories
+    fn calorie_sources_table() -> Table {
+        Table::new()
+            .column("#").min_width(4)
+            .column(Message::FoodColumn.text()).min_width(24).max_width(NAME_COLUMN_WIDTH)
+            .column(Message::EntriesColumn.text()).min_width(8)
+            .column(Message::CaloriesColumn.text()).min_width(10)
+            .column(Message::ShareColumn.text())
+    }
+
+    /// Prints a day's note and ratings, if any were written
+    fn print_day_note(note: &DayNote) {
+        if note.is_empty() {
+            return;
+        }
+        outln!("");
+        let rating = |rating: Option<u8>| rating.map_or("-".to_string(), |rating| format!("{}/{}", rating, food_log::RATING_MAX));
+        if note.mood.is_some() || note.energy.is_some() {
+            outln!("{}", Message::MoodAndEnergy.format(&[&rating(note.mood), &rating(note.energy)]));
+        }
+        if let Some(text) = &note.text {
+            outln!("{}", Message::NoteLine.format(&[&text]));
+        }
+    }
+
+    /// Writes or changes the note, mood, and energy of the current date
+    ///
+    /// Enter keeps a value and `none` clears it. The change goes through
+    /// `SetDayNoteCommand`, so it can be undone from the main menu.
+    fn edit_day_note(&mut self) {
+        outln!("\n{}", style::heading(Message::EditDayNote));
+        let current = self.log_repo.get_log(self.current_date)
+            .map(|log| log.note.clone())
+            .unwrap_or_default();
+
+        let kept = current.text.clone();
+        let text = prompt::Prompt::new(&Self::keep_or_clear(Message::NoteLabel.text(), current.text.as_deref()), move |input| {
+            Ok(match input {
+                "" => kept.clone(),
+                _ if input.eq_ignore_ascii_case("none") => None,
+                _ => Some(input.to_string()),
+            })
+        }).ask();
+        let Ok(text) = text else { return };
+
+        let rating_prompt = |label: &str, value: Option<u8>| {
+            let shown = value.map(|value| value.to_string());
+            let label = Message::RatingLabel.format(&[&label, &food_log::RATING_MIN, &food_log::RATING_MAX]);
+            prompt::Prompt::new(&Self::keep_or_clear(&label, shown.as_deref()), move |input| match input {
+                "" => Ok(value),
+                _ if input.eq_ignore_ascii_case("none") => Ok(None),
+                _ => food_log::parse_rating(input).map(Some),
+            }).ask()
+        };
+        let Ok(mood) = rating_prompt(Message::MoodLabel.text(), current.mood) else { return };
+        let Ok(energy) = rating_prompt(Message::EnergyLabel.text(), current.energy) else { return };
+
+        let note = DayNote { text, mood, energy };
+        if note == current {
+            outln!("{}", Message::NoteUnchanged);
+            return;
+        }
+        let command = SetDayNoteCommand::new(&mut self.log_repo, self.current_date, note);
+        match self.execute_command(Box::new(command)) {
+            Ok(_) => outln!("{}", Message::NoteSaved),
+            Err(e) => outln!("{}", Message::ErrorSavingNote.format(&[&e])),
+        }
+    }
+
+    /// Builds a prompt for a value that Enter keeps and `none` clears
+    fn keep_or_clear(label: &str, current: Option<&str>) -> String {
+        match current {
+            Some(current) => Message::KeepOrClearPrompt.format(&[&label, &current]),
+            None => Message::OptionalPrompt.format(&[&label]),
+        }
+    }
+
+    /// Prints planned versus logged calories for each meal of a day
+    ///
+    /// Meals that went over their planned calories are flagged, and the one
+    /// furthest over is named so it is clear which meal blew the budget.
+    /// Entries logged without a meal get their own row (see `models::meal`).
+    ///
+    /// # Arguments
+    /// * `plan` - The meal plan for the current date
+    /// * `log` - The food log for the same date, if anything was logged
+    fn print_meal_compliance(&self, plan: &MealPlan, log: Option<&DailyLog>) {
+        let rows = meals::meal_compliance(plan, log, self.food_repo.get_foods());
+
+        outln!("\n{}", Message::MealPlanVsLog);
+        let mut table = Table::new()
+            .column(Message::MealColumn.text()).min_width(12)
+            .numeric(Message::PlannedColumn.text()).min_width(10)
+            .numeric(Message::LoggedColumn.text()).min_width(10)
+            .numeric(Message::DifferenceColumn.text()).min_width(11);
+        for row in &rows {
+            match row.slot {
+                Some(slot) => {
+                    let flag = if row.difference() > 0.0 { Message::OverPlan.text() } else { "" };
+                    table.row_with_note([
+                        Message::from(slot).to_string(), format!("{:.1}", row.planned), format!("{:.1}", row.logged),
+                        format!("{:+.1}", row.difference()),
+                    ], flag);
+                }
+                None => table.row([Message::Unassigned.text().to_string(), "-".to_string(), format!("{:.1}", row.logged), "-".to_string()]),
+            }
+        }
+
+        let planned: f64 = rows.iter().map(|row| row.planned).sum();
+        let logged: f64 = rows.iter().map(|row| row.logged).sum();
+        table.footer([
+            Message::Total.text().to_string(), format!("{:.1}", planned), format!("{:.1}", logged), format!("{:+.1}", logged - planned),
+        ]);
+        outln!("{}", table);
+
+        let worst = rows.iter()
+            .filter(|row| row.slot.is_some() && row.difference() > 0.0)
+            .max_by(|a, b| a.difference().total_cmp(&b.difference()));
+        if let Some(row) = worst.and_then(|row| row.slot.map(|slot| (slot, row.difference()))) {
+            outln!("{}", Message::MostOverPlan.format(&[&Message::from(row.0), &row.1]));
+        }
+    }
+
+    /// Shows the meal plan for the current date and offers to change it
+    ///
+    /// Every change replaces the day's plan through `SetMealPlanCommand`,
+    /// so it can be undone from the main menu like any other change.
+    fn plan_meals(&mut self) {
+        loop {
+            outln!("\n{}", style::heading(Message::MealPlanFor.format(&[&self.show_date(self.current_date)])));
+
+            let plan = self.meal_plan_repo.get_plan(self.current_date).cloned()
+                .unwrap_or_else(|| MealPlan::new(self.current_date));
+            if plan.items.is_empty() {
+                outln!("{}", Message::NothingPlanned);
+            } else {
+                let mut table = Table::new()
+                    .column("#").min_width(5)
+                    .column(Message::MealColumn.text()).min_width(10)
+                    .column(Message::FoodIdColumn.text()).min_width(10)
+                    .column(Message::Name.text()).min_width(20).max_width(NAME_COLUMN_WIDTH)
+                    .column(Message::ServingsColumn.text()).min_width(10)
+                    .column(Message::CaloriesColumn.text()).min_width(10);
+                for (i, item) in plan.items.iter().enumerate() {
+                    let food = self.food_repo.get_food(&item.food_id);
+                    table.row([
+                        (i + 1).to_string(), Message::from(item.slot).to_string(), item.food_id.clone(),
+                        food.map_or(Message::Unknown.text().to_string(), |f| f.name.clone()),
+                        item.servings.to_string(),
+                        format!("{:.1}", food.map_or(0.0, |f| f.calories_per_serving * item.servings)),
+                    ]);
+                }
+                outln!("{}", table);
+            }
+
+            outln!("\n{}", Message::Options);
+            let choice = prompt::choice(&[
+                Message::AddPlannedFood.text(),
+                Message::RemovePlannedFood.text(),
+                Message::ClearPlan.text(),
+                Message::Back.text(),
+            ]).ask();
+
+            let new_plan = match choice {
+                Ok(1) => match self.prompt_planned_item(plan) {
+                    Ok(plan) => Some(plan),
+                    Err(_) => continue,
+                },
+                Ok(2) | Ok(3) if plan.items.is_empty() => {
+                    outln!("{}", Message::NothingPlannedForDay);
+                    continue;
+                }
+                Ok(2) => {
+                    let count = plan.items.len();
+                    let error = Message::InvalidItemNumber.format(&[&count]);
+                    let Ok(number) = prompt::number::<usize>(
+                        &Message::ItemToRemovePrompt.format(&[&count]),
+                        &error,
+                    ).validate(prompt::in_range(1, count, &error)).ask() else {
+                        continue;
+                    };
+                    let mut plan = plan;
+                    plan.items.remove(number - 1);
+                    Some(plan)
+                }
+                Ok(3) => None,
+                _ => break,
+            };
+
+            let command = Box::new(SetMealPlanCommand::new(&mut self.meal_plan_repo, self.current_date, new_plan));
+            if let Err(e) = self.execute_command(command) {
+                outln!("{}", Message::ErrorUpdatingMealPlan.format(&[&e]));
+            }
+        }
+    }
+
+    /// Asks for a meal, food, and servings and adds them to a plan
+    ///
+    /// # Returns
+    /// The plan with the new item, or the prompt error if input was cancelled
+    fn prompt_planned_item(&self, mut plan: MealPlan) -> prompt::PromptResult<MealPlan> {
+        outln!("{}", Message::MealLabel);
+        let slot = prompt::choice(&[Message::Breakfast.text(), Message::Lunch.text(), Message::Dinner.text(), Message::Snack.text()]).ask()?;
+        let food_id = self.prompt_existing_food_id(Message::EnterFoodId.text()).ask()?;
+        let servings = prompt::positive_f64(Message::EnterServings.text(), Message::InvalidServingsPositive.text()).ask()?;
+        plan.add_item(MealSlot::ALL[slot - 1], food_id, servings);
+        Ok(plan)
+    }
+    
+    /// Shows the exercise entries for the current date and offers to add or delete one
+    /// 
+    /// Calories burned are estimated from each activity's MET value and the
+    /// weight in the profile (see `models::exercise`). Both adding and deleting
+    /// go through commands, so they can be undone from the main menu.
+    fn exercise_log(&mut self) {
+        loop {
+            outln!("\n{}", style::heading(Message::ExerciseLog));
+            outln!("{}", Message::ExerciseFor.format(&[&self.show_date(self.current_date)]));
+            
+            let entries = self.exercise_repo.get_entries(self.current_date);
+            if entries.is_empty() {
+                outln!("{}", Message::NoExerciseLogged);
+            } else {
+                let mut table = Table::new()
+                    .column("#").min_width(5)
+                    .column(Message::ActivityColumn.text()).min_width(20).max_width(NAME_COLUMN_WIDTH)
+                    .column(Message::MinutesColumn.text()).min_width(10)
+                    .column("MET").min_width(6)
+                    .column(Message::CaloriesColumn.text()).min_width(10);
+                for (i, entry) in entries.iter().enumerate() {
+                    table.row([
+                        (i + 1).to_string(), entry.activity.clone(), entry.minutes.to_string(),
+                        entry.met.to_string(), format!("{:.1}", entry.calories_burned),
+                    ]);
+                }
+                outln!("{}", table);
+                outln!("{}", table.rule());
+                outln!("{}", Message::TotalBurned.format(&[&self.exercise_repo.calories_burned(self.current_date)]));
+            }
+            
+            outln!("\n{}", Message::Options);
+            let choice = prompt::choice(&[
+                Message::LogExercise.text(),
+                Message::DeleteExerciseEntry.text(),
+                Message::BackToMainMenu.text(),
+            ]).ask();
+            
+            match choice {
+                Ok(1) => self.add_exercise(),
+                Ok(2) => self.delete_exercise_entry(),
+                _ => break,
+            }
+        }
+    }
+    
+    /// Asks for an activity and duration and logs it for the current date
+    /// 
+    /// The body weight for the estimate is the latest one recorded on or before
+    /// the current date; without one the user is asked for it.
+    fn add_exercise(&mut self) {
+        let mut options: Vec<String> = ACTIVITIES.iter()
+            .map(|activity| format!("{} (MET {})", activity.name, activity.met))
+            .collect();
+        options.push(Message::OtherActivity.text().to_string());
+        let labels: Vec<&str> = options.iter().map(|option| option.as_str()).collect();
+        
+        outln!("\n{}", Message::ActivityLabel);
+        let Ok(choice) = prompt::choice(&labels).ask() else {
+            return;
+        };
+        let (name, met) = match ACTIVITIES.get(choice - 1) {
+            Some(activity) => (activity.name.to_string(), activity.met),
+            None => {
+                let Ok(name) = prompt::text(Message::ActivityNamePrompt.text())
+                    .validate(prompt::non_empty(Message::ActivityNameEmpty.text()))
+                    .ask() else {
+                    return;
+                };
+                let Ok(met) = prompt::positive_f64(
+                    Message::MetValuePrompt.text(),
+                    Message::EnterPositiveNumber.text(),
+                ).ask() else {
+                    return;
+                };
+                (name, met)
+            }
+        };
+        
+        let Ok(minutes) = prompt::positive_f64(Message::DurationPrompt.text(), Message::EnterPositiveMinutes.text()).ask() else {
+            return;
+        };
+        
+        let known_weight = self.profile_repo.get_profile()
+            .and_then(|profile| profile.latest_daily_profile(self.current_date))
+            .map(|daily| daily.weight)
+            .filter(|weight| validation::weight_kg(*weight).is_ok());
+        let weight = match known_weight {
+            Some(weight) => weight,
+            None => {
+                let units = self.units();
+                let message = Message::WeightForEstimatePrompt.format(&[&units.weight_unit()]);
+                let Ok(weight) = prompt::weight_kg(&message, units).ask() else {
+                    return;
+                };
+                weight
+            }
+        };
+        
+        let entry = ExerciseEntry::new(name, met, minutes, weight);
+        let burned = entry.calories_burned;
+        let command = Box::new(AddExerciseCommand::new(&mut self.exercise_repo, self.current_date, entry));
+        match self.execute_command(command) {
+            Ok(_) => outln!("{}", Message::ExerciseLogged.format(&[&burned])),
+            Err(e) => outln!("{}", Message::ErrorLoggingExercise.format(&[&e])),
+        }
+    }
+    
+    /// Deletes one exercise entry for the current date after confirmation
+    fn delete_exercise_entry(&mut self) {
+        let entry_count = self.exercise_repo.get_entries(self.current_date).len();
+        if entry_count == 0 {
+            outln!("{}", Message::NoExerciseToDelete);
+            return;
+        }
+        
+        let error = Message::InvalidEntryNumber.format(&[&entry_count]);
+        let Ok(entry_number) = prompt::number::<usize>(
+            &Message::EntryToDeletePrompt.format(&[&entry_count]),
+            &error,
+        ).validate(prompt::in_range(1, entry_count, &error)).ask() else {
+            return;
+        };
+        
+        let entry = &self.exercise_repo.get_entries(self.current_date)[entry_number - 1];
+        outln!("{}", Message::ExerciseEntryLine.format(&[&entry_number, &entry.minutes, &entry.activity]));
+        if !self.confirm(Message::ConfirmDeleteEntry.text(), Some(CommandType::RemoveExercise)) {
+            outln!("{}", Message::DeleteCancelled);
+            return;
+        }
+        
+        let command = Box::new(RemoveExerciseCommand::new(&mut self.exercise_repo, self.current_date, entry_number - 1));
+        match self.execute_command(command) {
+            Ok(_) => outln!("{}", Message::ExerciseEntryDeleted),
+            Err(e) => outln!("{}", Message::ErrorDeletingExerciseEntry.format(&[&e])),
+        }
+    }
+    
+    /// Provides a comprehensive interface for user profile management
+    /// 
+    /// This method creates a centralized profile management hub that:
+    /// 1. Displays current profile information in a formatted view
+    /// 2. Shows both basic profile data (gender, height, birth date, age)
+    /// 3. Displays current daily data (weight, activity level) for the active date
+    /// 4. Shows the current calorie calculation method in use
+    /// 5. Provides navigation to specific profile update operations
+    /// 
+    /// Profile management options:
+    /// - Update Basic Profile: Modify static information (gender, height, birth date)
+    /// - Update Today's Data: Modify current weight and activity level
+    /// - Change Calculation Method: Switch between different TDEE calculation strategies
+    /// 
+    /// The method integrates with the Repository pattern for profile data access
+    /// and provides a user-friendly interface for profile modifications while
+    /// maintaining separation of concerns for different types of profile updates.
+    fn manage_profile(&mut self) {
+        loop {
+            outln!("\n{}", style::heading(Message::ManageProfile));
+            
+            if let Some(profile) = self.profile_repo.get_profile() {
+                let units = self.units();
+                outln!("{}", Message::CurrentProfile);
+                outln!("{}", Message::GenderLine.format(&[&Message::from(&profile.gender)]));
+                match profile.height {
+                    Some(height) => outln!("{}", Message::HeightLine.format(&[&units.format_height(height)])),
+                    None => outln!("{}", Message::HeightUnknown),
+                }
+                match profile.birth_date {
+                    Some(birth_date) => outln!("{}", Message::BirthDateLine.format(&[&self.show_date(birth_date)])),
+                    None => outln!("{}", Message::BirthDateUnknown),
+                }
+                if let Some(age) = profile.age(self.current_date) {
+                    outln!("{}", Message::AgeLine.format(&[&age]));
+                }
+                if let Some(notice) = profile.body_metrics(self.current_date).estimate_notice() {
+                    outln!("{}", notice);
+                }
+                
+                if let Some(daily) = profile.get_daily_profile(self.current_date) {
+                    outln!("{}", Message::CurrentWeightLine.format(&[&units.format_weight(daily.weight)]));
+                    outln!("{}", Message::ActivityLevelLine.format(&[&Message::from(&daily.activity_level)]));
+                    if let Some(from) = daily.carried_from {
+                        outln!("{}", Message::CarriedFromLine.format(&[&self.show_date(from)]));
+                    }
+                }
+                
+                outln!("{}", Message::CalculationMethodLine.format(&[&profile.calculation_method]));
+                if !profile.weekday_targets.is_empty() {
+                    let overrides: Vec<String> = profile.weekday_targets.iter()
+                        .map(|(weekday, target)| format!("{} {}", weekday, target))
+                        .collect();
+                    outln!("{}", Message::WeekdayTargetsLine.format(&[&overrides.join(", ")]));
+                }
+                if let Some(preset) = profile.diet_preset {
+                    outln!("{}", Message::DietPresetLine.format(&[&preset, &preset.ratios(), &preset.calorie_adjustment()]));
+                }
+                if let Some(stage) = profile.life_stage {
+                    outln!("{}", Message::LifeStageLine.format(&[&stage]));
+                }
+                if let Some(targets) = profile.macro_targets {
+                    outln!("{}", Message::MacroTargetsLine.format(&[&targets]));
+                }
+                if !profile.restrictions.is_empty() {
+                    outln!("{}", Message::DietaryRestrictionsLine.format(&[&dietary::list(&profile.restrictions)]));
+                }
+                if !profile.history.is_empty() {
+                    outln!("{}", Message::ProfileHistory);
+                    for version in &profile.history {
+                        let height = version.height
+                            .map_or(Message::HeightUnknownShort.text().to_string(), |height| units.format_height(height));
+                        outln!("{}", Message::HistoryLine.format(&[
+                            &self.show_date(version.until), &Message::from(&version.gender), &height, &version.calculation_method,
+                        ]));
+                    }
+                }
+            } else {
+                outln!("{}", Message::NoProfile);
+            }
+            
+            // Carried-forward data can be confirmed as is
+            let carried = self.profile_repo.get_profile()
+                .and_then(|profile| profile.get_daily_profile(self.current_date))
+                .is_some_and(|daily| daily.carried_from.is_some());
+            let mut options = vec![
+                Message::UpdateBasicProfile.text(),
+                Message::UpdateTodaysData.text(),
+                Message::ChangeCalculationMethod.text(),
+                Message::SetWeekdayTargets.text(),
+                Message::ChooseDietPreset.text(),
+                Message::SetMacroTargets.text(),
+                Message::SetDietaryRestrictions.text(),
+                Message::SwitchUser.text(),
+                Message::ExportEverything.text(),
+                Message::ImportDataBundle.text(),
+                Message::ImportFromApp.text(),
+                Message::SetLifeStage.text(),
+            ];
+            if carried {
+                options.push(Message::ConfirmCarriedForward.text());
+            }
+            options.push(Message::BackToMainMenu.text());
+            
+            outln!();
+            let choice = prompt::choice(&options).ask();
+
+            match choice {
+                Ok(1) => self.update_basic_profile(),
+                Ok(2) => self.update_daily_profile(),
+                Ok(3) => self.change_calculation_method(),
+                Ok(4) => self.set_weekday_target(),
+                Ok(5) => self.choose_diet_preset(),
+                Ok(6) => self.set_macro_targets(),
+                Ok(7) => self.set_dietary_restrictions(),
+                Ok(8) => self.switch_user(),
+                Ok(9) => self.export_everything(),
+                Ok(10) => self.import_data_bundle(),
+                Ok(11) => self.import_from_app(),
+                Ok(12) => self.set_life_stage(),
+                Ok(13) if carried => {
+                    self.confirm_daily_profile();
+                    self.autosave(AutosaveTrigger::Command);
+                }
+                _ => break,
+            }
+        }
+    }
+    
+    /// Updates the static components of a user profile (gender, height, birth date)
+    /// 
+    /// This method handles modification of user profile information that typically
+    /// remains constant over time:
+    /// 1. Gender selection with current value display and keep-current option
+    /// 2. Height modification with validation for reasonable values (>0)
+    /// 3. Birth date updates with proper date parsing and validation
+    /// 4. Command pattern integration for undo functionality
+    /// 
+    /// User experience features:
+    /// - Shows current values for all fields before changes
+    /// - Provides "keep current" options to avoid accidental modifications
+    /// - Input validation prevents invalid data entry
+    /// - Clear feedback on successful updates
+    /// 
+    /// Uses UpdateBasicProfileCommand to maintain consistency with the
+    /// application's command-based architecture, enabling undo functionality
+    /// for profile modifications while preserving data integrity.
+    fn update_basic_profile(&mut self) {
+        outln!("\n{}", style::heading(Message::UpdateBasicProfile));
+        
+        let current_profile = match self.profile_repo.get_profile() {
+            Some(p) => p.clone(),
+            None => {
+                outln!("{}", Message::NoProfileCreatingOne);
+                self.create_initial_profile();
+                return;
+            }
+        };
+        
+        // Gender
+        outln!("{}", Message::SelectGenderCurrent.format(&[&Message::from(&current_profile.gender)]));
+        let Ok(gender) = Self::prompt_gender(Some(&current_profile.gender)) else {
+            return;
+        };
+
+        // Height in the preferred unit (blank input keeps the current value; an unknown height has none)
+        let units = self.units();
+        let height = match current_profile.height {
+            Some(height) => {
+                outln!("{}", Message::CurrentHeight.format(&[&units.format_height(height)]));
+                prompt::height_cm(
+                    &Message::EnterHeightKeep.format(&[&units.height_unit_name()]),
+                    units,
+                ).default(height).ask()
+            }
+            None => prompt::height_cm(&Message::EnterHeight.format(&[&units.height_unit_name()]), units).ask(),
+        };
+        let Ok(height) = height else {
+            return;
+        };
+
+        // Birth date (blank input keeps the current value; an unknown date has none)
+        let birth_date_prompt = match current_profile.birth_date {
+            Some(birth_date) => {
+                outln!("{}", Message::CurrentBirthDate.format(&[&self.show_date(birth_date)]));
+                prompt::birth_date(Message::EnterBirthDateKeep.text())
+                    .default(birth_date)
+            }
+            None => prompt::birth_date(Message::EnterBirthDate.text()),
+        };
+        let Ok(birth_date) = birth_date_prompt.ask() else {
+            return;
+        };
+        
+        // Changing values the profile already has overwrites them
+        let changed = gender != current_profile.gender
+            || Some(height) != current_profile.height
+            || Some(birth_date) != current_profile.birth_date;
+        if changed && !self.confirm(Message::ConfirmOverwriteBasic.text(), Some(CommandType::UpdateProfile)) {
+            outln!("{}", Message::ProfileNotChanged);
+            return;
+        }
+        
+        // Only the basic values change; daily data, targets, and settings are kept
+        let mut new_profile = current_profile.clone();
+        new_profile.gender = gender;
+        new_profile.height = Some(height);
+        new_profile.birth_date = Some(birth_date);
+        
+        // Earlier days keep the values that applied to them
+        new_profile.record_basic_change(&current_profile, self.current_date);
+        
+        // Update using command pattern
+        let command = Box::new(UpdateUserProfileCommand::new(
+            &mut self.profile_repo,
+            new_profile
+        ));
+        
+        match self.execute_command(command) {
+            Ok(_) => outln!("{}", Message::ProfileUpdated),
+            Err(e) => outln!("{}", Message::ErrorUpdatingProfile.format(&[&e])),
+        }
+    }
+    
+    /// Updates daily profile information (weight and activity level) for the current date
+    /// 
+    /// This method manages date-specific profile data that can vary day by day:
+    /// 1. Current weight input with validation for positive values
+    /// 2. Activity level selection from predefined categories
+    /// 3. Creates or updates daily profile for the current application date
+    /// 4. Command pattern integration for undo functionality
+    /// 
+    /// Daily profile categories:
+    /// - Weight: Allows tracking of weight changes over time
+    /// - Activity Level: Sedentary, Lightly Active, Moderately Active, Very Active, Extremely Active
+    /// 
+    /// This enables accurate TDEE calculations that account for daily variations
+    /// in weight and activity, providing more precise calorie targets for
+    /// effective diet management. Uses UpdateDailyProfileCommand to maintain
+    /// consistency with the application's command-based architecture.
+    fn update_daily_profile(&mut self) {
+        outln!("\n{}", style::heading(Message::UpdateTodaysData));
+        
+        if self.profile_repo.get_profile().is_none() {
+            outln!("{}", Message::NoProfileCreateFirst);
+            return;
+        }
+        
+        // Get current daily profile if it exists
+        let current_daily = self.profile_repo
+            .get_profile()
+            .and_then(|p| p.get_daily_profile(self.current_date).cloned());
+        
+        // Weight
+        let current_weight = current_daily.as_ref().map_or(0.0, |d| d.weight);
+        let units = self.units();
+        outln!("{}", Message::CurrentWeight.format(&[&units.format_weight(current_weight)]));
+        
+        let Ok(weight) = prompt::weight_kg(&Message::EnterWeight.format(&[&units.weight_unit_name()]), units).ask() else {
+            return;
+        };
+
+        // Activity level
+        outln!("{}", Message::SelectActivityLevel);
+        let Ok(activity_level) = Self::prompt_activity_level() else {
+            return;
+        };
+        
+        // Replacing a weigh-in of the day overwrites it (a carried weight was never entered)
+        let overwrites = current_daily.as_ref().is_some_and(|daily| {
+            daily.carried_from.is_none() && (daily.weight != weight || daily.activity_level != activity_level)
+        });
+        if overwrites && !self.confirm(
+            &Message::ConfirmReplaceDaily.format(&[&self.show_date(self.current_date)]),
+            Some(CommandType::UpdateProfile),
+        ) {
+            outln!("{}", Message::ProfileNotChanged);
+            return;
+        }
+        
+        // Create daily profile
+        let daily_profile = DailyProfile {
+            date: self.current_date,
+            weight,
+            activity_level,
+            carried_from: None,
+        };
+        
+        // Update using command pattern
+        let command = Box::new(UpdateDailyProfileCommand::new(
+            &mut self.profile_repo,
+            daily_profile
+        ));
+        
+        match self.execute_command(command) {
+            Ok(_) => outln!("{}", Message::DailyProfileUpdated),
+            Err(e) => outln!("{}", Message::ErrorUpdatingDailyProfile.format(&[&e])),
+        }
+    }
+    
+    /// Changes the calorie calculation method used for TDEE computations (Strategy Pattern)
+    /// 
+    /// This method implements the Strategy Pattern by allowing users to switch between
+    /// different Total Daily Energy Expenditure (TDEE) calculation algorithms:
+    /// 1. Harris-Benedict Formula: Traditional BMR calculation method
+    /// 2. Mifflin-St Jeor Formula: More modern and often more accurate
+    /// 3. Future extensibility for additional calculation strategies
+    /// 
+    /// Strategy Pattern implementation:
+    /// - Factory creates appropriate calculator instances
+    /// - User can switch strategies at runtime
+    /// - Calculations adapt automatically to selected method
+    /// - Consistent interface regardless of underlying algorithm
+    /// 
+    /// This flexibility allows users to choose the calculation method that works
+    /// best for their body type and goals, improving the accuracy of calorie
+    /// targets and overall diet management effectiveness.
+    /// 
+    /// The new method applies from the current date; earlier days keep the
+    /// method of the time (see `UserProfile::record_basic_change`). The change
+    /// goes through UpdateUserProfileCommand so it can be undone.
+    fn change_calculation_method(&mut self) {
+        outln!("\n{}", style::heading(Message::ChangeCalculationMethod));
+        
+        let Some(profile) = self.profile_repo.get_profile().cloned() else {
+            outln!("{}", Message::NoProfileCreateFirst);
+            return;
+        };
+
+        let methods = self.calculator_factory.get_all_calculators();
+
+        outln!("{}", Message::AvailableMethods);
+        for (i, method) in methods.iter().enumerate() {
+            let calculator = self.calculator_factory.get_calculator(method).unwrap();
+            outln!("{}. {} - {}", i+1, calculator.name(), calculator.description());
+        }
+
+        outln!("{}", Message::CurrentMethod.format(&[&profile.calculation_method]));
+
+        let Ok(choice) = prompt::menu(methods.len()).ask() else {
+            return;
+        };
+
+        let method = methods[choice - 1].to_string();
+        let mut new_profile = profile.clone();
+        new_profile.calculation_method = method.clone();
+        new_profile.record_basic_change(&profile, self.current_date);
+        let command = Box::new(UpdateUserProfileCommand::new(&mut self.profile_repo, new_profile));
+        match self.execute_command(command) {
+            Ok(_) => outln!("{}", Message::MethodChanged.format(&[&method])),
+            Err(e) => outln!("{}", Message::ErrorUpdatingProfile.format(&[&e])),
+        }
+    }
+    
+    /// Sets or clears the calorie target override of one weekday
+    /// 
+    /// The override is either a fixed target (`2500`) or an adjustment of
+    /// the calculated target (`+300`, `-200`); see `models::weekday_target`.
+    /// Blank input clears it. The change goes through UpdateUserProfileCommand
+    /// so it can be undone.
+    fn set_weekday_target(&mut self) {
+        outln!("\n{}", style::heading(Message::SetWeekdayTargets));
+        
+        let Some(current_profile) = self.profile_repo.get_profile().cloned() else {
+            outln!("{}", Message::NoProfileCreateFirst);
+            return;
+        };
+        
+        let weekdays: Vec<Weekday> = (0..7u8).filter_map(|index| Weekday::try_from(index).ok()).collect();
+        let labels: Vec<String> = weekdays.iter().map(|weekday| {
+            match current_profile.weekday_targets.get(*weekday) {
+                Some(target) => format!("{} ({} kcal)", Message::from(*weekday), target),
+                None => Message::WeekdayCalculated.format(&[&Message::from(*weekday)]),
+            }
+        }).collect();
+        let labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+        
+        outln!("{}", Message::ChooseDay);
+        let Ok(choice) = prompt::choice(&labels).ask() else {
+            return;
+        };
+        let weekday = weekdays[choice - 1];
+        
+        let Ok(target) = prompt::Prompt::new(
+            &Message::WeekdayTargetPrompt.format(&[&Message::from(weekday)]),
+            TargetOverride::parse,
+        ).optional().ask() else {
+            return;
+        };
+        
+        let mut new_profile = current_profile;
+        new_profile.weekday_targets.set(weekday, target);
+        let command = Box::new(UpdateUserProfileCommand::new(&mut self.profile_repo, new_profile));
+        match (self.execute_command(command), target) {
+            (Ok(_), Some(target)) => outln!("{}", Message::WeekdayTargetSet.format(&[&Message::from(weekday), &target])),
+            (Ok(_), None) => outln!("{}", Message::WeekdayTargetCleared.format(&[&Message::from(weekday)])),
+            (Err(e), _) => outln!("{}", Message::ErrorUpdatingProfile.format(&[&e])),
+        }
+    }
+    
+    /// Switches the diet preset, or turns presets off
+    /// 
+    /// A preset sets macro ratio targets and a calorie adjustment in one
+    /// step (see `models::diet_preset`). The change goes through
+    /// UpdateUserProfileCommand so it can be undone.
+    fn choose_diet_preset(&mut self) {
+        outln!("\n{}", style::heading(Message::ChooseDietPreset));
+        
+        let Some(current_profile) = self.profile_repo.get_profile().cloned() else {
+            outln!("{}", Message::NoProfileCreateFirst);
+            return;
+        };
+        
+        match current_profile.diet_preset {
+            Some(preset) => outln!("{}", Message::CurrentPreset.format(&[&preset])),
+            None => outln!("{}", Message::CurrentPresetNone),
+        }
+        let mut labels: Vec<String> = DietPreset::ALL.iter()
+            .map(|preset| format!("{} ({}, {:+} kcal)", preset, preset.ratios(), preset.calorie_adjustment()))
+            .collect();
+        labels.push(Message::NoPreset.text().to_string());
+        let labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+        
+        let Ok(choice) = prompt::choice(&labels).ask() else {
+            return;
+        };
+        let preset = DietPreset::ALL.get(choice - 1).copied();
+        
+        let mut new_profile = current_profile;
+        new_profile.diet_preset = preset;
+        let command = Box::new(UpdateUserProfileCommand::new(&mut self.profile_repo, new_profile));
+        match (self.execute_command(command), preset) {
+            (Ok(_), Some(preset)) => outln!("{}", Message::PresetSet.format(&[&preset])),
+            (Ok(_), None) => outln!("{}", Message::PresetOff),
+            (Err(e), _) => outln!("{}", Message::ErrorUpdatingProfile.format(&[&e])),
+        }
+    }
+    
+    /// Sets or clears pregnancy or breastfeeding
+    /// 
+    /// The formulas estimate what a non-pregnant adult burns; the life stage
+    /// adds the extra calories on top (see `models::life_stage`). The change
+    /// goes through UpdateUserProfileCommand so it can be undone.
+    fn set_life_stage(&mut self) {
+        outln!("\n{}", style::heading(Message::SetLifeStage));
+        
+        let Some(current_profile) = self.profile_repo.get_profile().cloned() else {
+            outln!("{}", Message::NoProfileCreateFirst);
+            return;
+        };
+        
+        match current_profile.life_stage {
+            Some(stage) => outln!("{}", Message::CurrentLifeStage.format(&[&stage])),
+            None => outln!("{}", Message::CurrentLifeStageNone),
+        }
+        let Ok(choice) = prompt::choice(&[Message::Pregnant.text(), Message::Breastfeeding.text(), Message::Neither.text()]).ask() else {
+            return;
+        };
+        let stage = match choice {
+            1 => match prompt::date(Message::DueDate.text()).ask() {
+                Ok(due_date) => Some(LifeStage::Pregnant { due_date }),
+                Err(_) => return,
+            },
+            2 => match prompt::date_or_today(Message::BabyBirthDate.text()).ask() {
+                Ok(since) => Some(LifeStage::Breastfeeding { since }),
+                Err(_) => return,
+            },
+            _ => None,
+        };
+        
+        let mut new_profile = current_profile;
+        new_profile.life_stage = stage;
+        let command = Box::new(UpdateUserProfileCommand::new(&mut self.profile_repo, new_profile));
+        match (self.execute_command(command), stage) {
+            (Ok(_), Some(stage)) => outln!("{}", Message::LifeStageSet.format(&[&stage])),
+            (Ok(_), None) => outln!("{}", Message::LifeStageCleared),
+            (Err(e), _) => outln!("{}", Message::ErrorUpdatingProfile.format(&[&e])),
+        }
+    }
+    
+    /// Sets protein, carbohydrate, and fat targets as percentages or grams
+    /// 
+    /// The user's own targets take precedence over the diet preset's ratios;
+    /// clearing them goes back to the preset (see `models::macro_target`).
+    /// The change goes through UpdateUserProfileCommand so it can be undone.
+    fn set_macro_targets(&mut self) {
+        outln!("\n{}", style::heading(Message::SetMacroTargets));
+        
+        let Some(current_profile) = self.profile_repo.get_profile().cloned() else {
+            outln!("{}", Message::NoProfileCreateFirst);
+            return;
+        };
+        
+        match current_profile.active_macro_targets() {
+            Some(targets) => outln!("{}", Message::CurrentTargets.format(&[&targets])),
+            None => outln!("{}", Message::CurrentTargetsNone),
+        }
+        let Ok(choice) = prompt::choice(&[
+            Message::PercentOfCalories.text(),
+            Message::GramsPerDay.text(),
+            Message::ClearMacroTargets.text(),
+        ]).ask() else {
+            return;
+        };
+        
+        let targets = match choice {
+            1 => {
+                let percent = |name: &str| {
+                    prompt::non_negative_f64(&Message::PercentFromPrompt.format(&[&name]), Message::EnterPercentage.text())
+                        .validate(prompt::in_range(0.0, 100.0, Message::EnterPercentage100.text()))
+                        .ask()
+                };
+                let Ok(protein) = percent(Message::ProteinWord.text()) else { return; };
+                let Ok(carbs) = percent(Message::CarbsWord.text()) else { return; };
+                let Ok(fat) = percent(Message::FatWord.text()) else { return; };
+                match MacroRatios::new(protein, carbs, fat) {
+                    Ok(ratios) => Some(MacroTargets::Ratios(ratios)),
+                    Err(e) => {
+                        outln!("{}", e);
+                        return;
+                    }
+                }
+            }
+            2 => {
+                let grams = |name: &str| {
+                    prompt::non_negative_f64(&Message::GramsOfPrompt.format(&[&name]), Message::EnterNonNegative.text())
+                        .ask()
+                };
+                let Ok(protein) = grams(Message::ProteinWord.text()) else { return; };
+                let Ok(carbs) = grams(Message::CarbsWord.text()) else { return; };
+                let Ok(fat) = grams(Message::FatWord.text()) else { return; };
+                Some(MacroTargets::Grams(Macros::new(protein, carbs, fat)))
+            }
+            _ => None,
+        };
+        
+        let mut new_profile = current_profile;
+        new_profile.macro_targets = targets;
+        let command = Box::new(UpdateUserProfileCommand::new(&mut self.profile_repo, new_profile));
+        match (self.execute_command(command), targets) {
+            (Ok(_), Some(targets)) => outln!("{}", Message::MacroTargetsSet.format(&[&targets])),
+            (Ok(_), None) => outln!("{}", Message::MacroTargetsCleared),
+            (Err(e), _) => outln!("{}", Message::ErrorUpdatingProfile.format(&[&e])),
+        }
+    }
+    
+    /// Declares the dietary restrictions that logged foods are checked against
+    /// 
+    /// Foods tagged with an ingredient a restriction rules out, or made with
+    /// such a food, are warned about when logged (see `models::dietary`).
+    /// The change goes through UpdateUserProfileCommand so it can be undone.
+    fn set_dietary_restrictions(&mut self) {
+        outln!("\n{}", style::heading(Message::SetDietaryRestrictions));
+        
+        let Some(current_profile) = self.profile_repo.get_profile().cloned() else {
+            outln!("{}", Message::NoProfileCreateFirst);
+            return;
+        };
+        
+        if current_profile.restrictions.is_empty() {
+            outln!("{}", Message::CurrentRestrictionsNone);
+        } else {
+            outln!("{}", Message::CurrentRestrictions.format(&[&dietary::list(&current_profile.restrictions)]));
+        }
+        outln!("{}", Message::AvailableRestrictions.format(&[&dietary::list(&DietaryRestriction::ALL)]));
+        let Ok(restrictions) = prompt::Prompt::new(
+            Message::EnterRestrictions.text(),
+            |input| match input {
+                "" => Ok(None),
+                _ if input.eq_ignore_ascii_case("none") => Ok(Some(Vec::new())),
+                _ => dietary::parse_restrictions(input).map(Some),
+            },
+        ).ask() else {
+            return;
+        };
+        let Some(restrictions) = restrictions.filter(|restrictions| *restrictions != current_profile.restrictions) else {
+            outln!("{}", Message::RestrictionsUnchanged);
+            return;
+        };
+        
+        let mut new_profile = current_profile;
+        new_profile.restrictions = restrictions;
+        let restrictions = new_profile.restrictions.clone();
+        let command = Box::new(UpdateUserProfileCommand::new(&mut self.profile_repo, new_profile));
+        match self.execute_command(command) {
+            Ok(_) if restrictions.is_empty() => outln!("{}", Message::RestrictionsCleared),
+            Ok(_) => outln!("{}", Message::RestrictionsSet.format(&[&dietary::list(&restrictions)])),
+            Err(e) => outln!("{}", Message::ErrorUpdatingProfile.format(&[&e])),
+        }
+    }
+    
+    /// Switches the session to another user, or to a new one
+    /// 
+    /// The current user's data is saved first. The shared food database
+    /// stays loaded; the new user's profile, logs, exercise, and meal plans
+    /// are loaded from their directory (see `config.rs`). Undo history is
+    /// cleared, since it belongs to the previous user's data. A new user
+    /// goes through the same first-run profile setup as at startup.
+    fn switch_user(&mut self) {
+        outln!("\n{}", style::heading(Message::SwitchUser));
+        
+        let Ok(name) = users::choose_user(&self.config, Some(self.config.user_name())) else {
+            return;
+        };
+        if name == self.config.user_name() {
+            outln!("{}", Message::AlreadyUsing.format(&[&name]));
+            return;
+        }
+        
+        // Loading the other user's data would drop changes that were not saved
+        if !self.save_data() {
+            outln!("{}", Message::SwitchNotSaved.format(&[&name]));
+            return;
+        }
+        
+        let mut config = self.config.clone();
+        if let Err(e) = config.select_user(&name) {
+            outln!("{}", e);
+            return;
+        }
+        if let Err(e) = self.load_user_data(&config) {
+            outln!("{}", Message::ErrorLoadingUserData.format(&[&name as &dyn std::fmt::Display, &e]));
+            return;
+        }
+        self.config = config;
+        let policies = self.command_manager.policies().clone();
+        self.command_manager = Self::command_manager(&self.config, policies);
+        outln!("{}", Message::SwitchedUser.format(&[&self.config.user_name()]));
+        self.print_load_reports();
+        
+        if self.profile_repo.get_profile().is_none() {
+            outln!("{}", Message::NoProfileFound);
+            self.create_initial_profile();
+        }
+        self.carry_forward_daily_profile();
+    }
+    
+    /// Writes the food database and the current user's data into one zip bundle
+    /// Unsaved changes are saved first so the bundle matches what the menus show;
+    /// the bundle goes into the reports directory unless another path is entered
+    fn export_everything(&mut self) {
+        outln!("\n{}", style::heading(Message::ExportEverything));
+        
+        let file_name = format!("yada-export-{}.zip", Local::now().format("%Y-%m-%d"));
+        let default_path = self.config.reports_dir().join(file_name).to_string_lossy().into_owned();
+        let Ok(path) = prompt::text(&Message::BundleFileDefault.format(&[&default_path]))
+            .default(default_path)
+            .ask()
+        else {
+            return;
+        };
+        
+        if !self.save_data() {
+            outln!("{}", Message::ExportNotSaved);
+            return;
+        }
+        if let Some(parent) = Path::new(&path).parent().filter(|parent| !parent.as_os_str().is_empty())
+            && let Err(e) = fs::create_dir_all(parent)
+        {
+            outln!("{}", Message::ErrorCreating.format(&[&parent.display(), &e]));
+            return;
+        }
+        match bundle::export_bundle(&path, &self.config.data_files()) {
+            Ok(files) => {
+                outln!("{}", Message::ExportedTo.format(&[&files.join(", "), &path]));
+                if encryption::encrypts_saves() {
+                    outln!("{}", Message::BundleNotEncrypted);
+                }
+            }
+            Err(e) => outln!("{}", e),
+        }
+    }
+    
+    /// Replaces the food database and the current user's data with a bundle's files
+    /// Shows what the bundle holds and asks first; the replaced files are backed up,
+    /// and the undo history is cleared since it refers to the replaced data
+    fn import_data_bundle(&mut self) {
+        outln!("\n{}", style::heading(Message::ImportDataBundle));
+        
+        let Ok(path) = prompt::text(Message::BundleFile.text())
+            .validate(prompt::non_empty(Message::EnterBundlePath.text()))
+            .ask()
+        else {
+            return;
+        };
+        let bundle = match bundle::read_bundle(&path) {
+            Ok(bundle) => bundle,
+            Err(e) => {
+                outln!("{}", e);
+                return;
+            }
+        };
+        let created = bundle.created
+            .map_or(Message::AtUnknownTime.text().to_string(), |time| time.with_timezone(&Local).format(Message::OnDateTime.text()).to_string());
+        outln!("{}", Message::ExportedWhen.format(&[&created, &bundle.names().join(", ")]));
+        outln!("{}", Message::BundleReplaces.format(&[&self.config.user_name()]));
+        if !self.confirm(Message::ConfirmImport.text(), None) {
+            return;
+        }
+        
+        // Pending changes are kept in the backups the import makes
+        if !self.save_data() {
+            outln!("{}", Message::ImportNotSaved);
+            return;
+        }
+        let restored = match bundle::restore_bundle(&bundle, &self.config.data_files()) {
+            Ok(restored) => restored,
+            Err(e) => {
+                outln!("{}", e);
+                return;
+            }
+        };
+        
+        let config = self.config.clone();
+        let reloaded = FoodRepository::new(&config.foods_path())
+            .and_then(|food_repo| {
+                self.food_repo = food_repo;
+                self.settings_repo = SettingsRepository::new(&config.settings_path())?;
+                self.load_user_data(&config)
+            });
+        if let Err(e) = reloaded {
+            outln!("{}", Message::ErrorLoadingImported.format(&[&e]));
+            outln!("{}", Message::PreviousFilesKept.format(&[&config.data_dir.display()]));
+            return;
+        }
+        let policies = self.command_manager.policies().clone();
+        self.command_manager = Self::command_manager(&self.config, policies);
+        outln!("{}", Message::ImportedFiles.format(&[&restored.join(", ")]));
+        self.print_load_reports();
+        self.carry_forward_daily_profile();
+    }
+    
+    /// Imports a diary exported from another app into the food database and log
+    /// The formats come from the importer factory; the import is previewed on
+    /// freshly loaded copies of the saved data and only kept after confirmation.
+    /// Both files are saved or neither is, and the undo history is cleared,
+    /// since its commands refer to the data replaced
+    fn import_from_app(&mut self) {
+        outln!("\n{}", style::heading(Message::ImportFromApp));
+        
+        let factory = ImporterFactory::new();
+        let importers = factory.get_all_importers();
+        let options: Vec<String> = importers.iter()
+            .map(|importer| format!("{} ({})", importer.app_name(), importer.description()))
+            .collect();
+        let options: Vec<&str> = options.iter().map(String::as_str).collect();
+        let Ok(choice) = prompt::choice(&options).ask() else {
+            return;
+        };
+        let importer = importers[choice - 1];
+        
+        let Ok(path) = prompt::text(Message::ExportFilePrompt.text())
+            .validate(prompt::non_empty(Message::EnterExportPath.text()))
+            .ask()
+        else {
+            return;
+        };
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) => {
+                outln!("{}", Message::ErrorReading.format(&[&path, &e]));
+                return;
+            }
+        };
+        
+        // Pending changes are saved so the preview starts from what the menus show
+        if !self.save_data() {
+            outln!("{}", Message::ImportNotSaved);
+            return;
+        }
+        let loaded = FoodRepository::new(&self.config.foods_path())
+            .and_then(|food_repo| Ok((food_repo, LogRepository::new(&self.config.logs_path())?)));
+        let (mut food_repo, mut log_repo) = match loaded {
+            Ok(repos) => repos,
+            Err(e) => {
+                outln!("{}", Message::ErrorLoadingData.format(&[&e]));
+                return;
+            }
+        };
+        let report = match importer.import(&mut food_repo, &mut log_repo, &text) {
+            Ok(report) => report,
+            Err(e) => {
+                outln!("{}", e);
+                return;
+            }
+        };
+        outln!("{}", import::describe(importer, &report, true));
+        if report.new_foods.is_empty() && report.entries.added == 0 {
+            outln!("{}", Message::NothingNewToImport);
+            return;
+        }
+        if !self.confirm(Message::ConfirmImport.text(), None) {
+            return;
+        }
+        
+        if let Err(e) = food_repo.save() {
+            outln!("{}", Message::ErrorSavingImported.format(&[&e]));
+            return;
+        }
+        if let Err(e) = log_repo.save() {
+            outln!("{}", Message::ErrorSavingImported.format(&[&e]));
+            // Writes the previous foods back, so the files never hold half an import
+            if let Err(e) = self.food_repo.save() {
+                outln!("{}", Message::ErrorRestoring.format(&[&self.config.foods_path(), &e]));
+                outln!("{}", Message::PreviousFileKept.format(&[&self.config.data_dir.display()]));
+            }
+            return;
+        }
+        let found = usage::collect_usage(log_repo.get_logs(), food_repo.get_foods(), self.current_date);
+        food_repo.sync_usage(&found, self.current_date);
+        self.food_repo = food_repo;
+        self.log_repo = log_repo;
+        let policies = self.command_manager.policies().clone();
+        self.command_manager = Self::command_manager(&self.config, policies);
+        self.refresh_intake();
+        outln!("{}", Message::ImportedFoodsAndEntries.format(&[&report.new_foods.len(), &report.entries.added]));
+    }
+    
+    /// Loads one user's profile, logs, exercise, meal plans, and meal templates
+    /// 
+    /// Nothing is replaced unless every file loads.
+    fn load_user_data(&mut self, config: &AppConfig) -> Result<(), io::Error> {
+        config.ensure_data_dir()?;
+        let mut timings = StartupProfile::new();
+        let mut log_repo = Self::open_repository(&config.logs_path(), "Load logs", &mut timings, LogRepository::new)?;
+        let profile_repo = Self::open_repository(&config.profile_path(), "Load profile", &mut timings, ProfileRepository::new)?;
+        let exercise_repo = Self::open_repository(&config.exercise_path(), "Load exercise", &mut timings, ExerciseRepository::new)?;
+        let meal_plan_repo = Self::open_repository(&config.meal_plans_path(), "Load meal plans", &mut timings, MealPlanRepository::new)?;
+        let meal_template_repo = Self::open_repository(&config.meal_templates_path(), "Load meal templates", &mut timings, MealTemplateRepository::new)?;
+        let load_reports = Self::check_load_reports(config, vec![
+            (config.logs_path(), log_repo.load_report()),
+            (config.profile_path(), profile_repo.load_report()),
+            (config.exercise_path(), exercise_repo.load_report()),
+            (config.meal_plans_path(), meal_plan_repo.load_report()),
+            (config.meal_templates_path(), meal_template_repo.load_report()),
+        ])?;
+        
+        log_repo.backfill_calories(self.food_repo.get_foods());
+        let found = usage::collect_usage(log_repo.get_logs(), self.food_repo.get_foods(), self.current_date);
+        self.food_repo.sync_usage(&found, self.current_date);
+        
+        self.log_repo = log_repo;
+        self.profile_repo = profile_repo;
+        self.exercise_repo = exercise_repo;
+        self.meal_plan_repo = meal_plan_repo;
+        self.meal_template_repo = meal_template_repo;
+        self.load_reports = load_reports;
+        self.refresh_intake();
+        Ok(())
+    }
+    
+    /// Displays comprehensive diet and profile statistics for the current date
+    /// 
+    /// This method provides a detailed statistical overview combining:
+    /// 1. Current user profile information (age, gender, height, weight, activity)
+    /// 2. Calorie calculation method and target calories for the current date
+    /// 3. Food consumption summary with total calories consumed, their calorie
+    ///    density, and what the food cost (for foods with a cost per serving)
+    /// 4. Alcohol and caffeine consumed, with a warning for each daily limit
+    ///    exceeded (see `models::intake_limits`)
+    /// 5. Diet progress analysis (surplus/deficit, percentage of target achieved)
+    /// 
+    /// Statistical insights include:
+    /// - BMI (Body Mass Index) with its weight category
+    /// - BMR (Basal Metabolic Rate) calculation
+    /// - TDEE (Total Daily Energy Expenditure) based on activity level
+    /// - Current calorie consumption vs target comparison
+    /// - Diet goal progress indicators
+    /// 
+    /// Integrates multiple design patterns:
+    /// - Repository Pattern: Access to profile and log data
+    /// - Strategy Pattern: Flexible calorie calculation methods
+    /// - Factory Pattern: Creation of appropriate calculator instances
+    fn view_stats(&self) {
+        outln!("\n{}", style::heading(Message::ViewStats));
+        
+        let profile = match self.profile_repo.get_profile() {
+            Some(p) => p,
+            None => {
+                outln!("{}", Message::NoProfileCreateFirst);
+                return;
+            }
+        };
+        
+        // Get calculator
+        let calculator = self.calculator_factory.calculator_for(profile, self.current_date)
+            .expect("the fallback calculator is always registered");
+        
+        // Calculate target calories, exercise credit included
+        let calculation = calculator.calculate_target_with(profile, self.current_date, &self.target_pipeline());
+        let target_calories = calculation.target;
+        
+        outln!("{}", Message::StatisticsFor.format(&[&self.show_date(self.current_date)]));
+        // Without a weigh-in the target uses the latest earlier one
+        let carried_from = profile.latest_daily_profile(self.current_date).and_then(|daily| {
+            daily.carried_from.or((daily.date != self.current_date).then_some(daily.date))
+        });
+        if let Some(from) = carried_from {
+            outln!("{}", Message::TargetUsesCarried.format(&[&self.show_date(from)]));
+        }
+        if let Some(notice) = profile.body_metrics(self.current_date).estimate_notice() {
+            outln!("{}", notice);
+        }
+        
+        // How the target is derived, step by step
+        if let Some(metrics) = HealthMetrics::calculate(profile, calculator, self.current_date) {
+            if let (Some(bmi), Some(category)) = (metrics.bmi, metrics.bmi_category()) {
+                outln!("{}", Message::BmiLine.format(&[&bmi, &category]));
+            }
+            outln!("{}", Message::BmrLine.format(&[&calculation.bmr, &calculator.name()]));
+            outln!("{}", Message::ActivityMultiplierLine.format(&[&calculation.activity_multiplier, &Message::from(&metrics.activity_level)]));
+            outln!("{}", Message::TdeeLine.format(&[&calculation.tdee]));
+            outln!("({})", calculation.explanation);
+        }
+        for adjustment in &calculation.adjustments {
+            outln!("{}: {:+.0}", adjustment.label, adjustment.change);
+        }
+        outln!("{}", Message::TargetCaloriesStat.format(&[&target_calories]));
+        let burned = self.exercise_repo.calories_burned(self.current_date);
+        if burned > 0.0 {
+            outln!("{}", Message::CaloriesBurnedStat.format(&[&burned]));
+        }
+        
+        // Get log for current date
+        if let Some(log) = self.log_repo.get_log(self.current_date) {
+            let total_calories = log.total_calories(self.food_repo.get_foods());
+            
+            let macros = log.total_macros(self.food_repo.get_foods());
+            
+            outln!("{}", Message::CaloriesConsumedStat.format(&[&total_calories]));
+            outln!("{}", Message::Difference.format(&[&(style::against_target(
+                format!("{:.1}", total_calories - target_calories), total_calories, target_calories))]));
+            outln!("{}", Message::MacrosLine.format(&[&macros.protein, &macros.carbs, &macros.fat]));
+            if macros.alcohol > 0.0 || macros.caffeine > 0.0 {
+                outln!("{}", Message::AlcoholCaffeineLine.format(&[&macros.alcohol, &macros.caffeine]));
+            }
+            for warning in IntakeLimits::from_env().exceeded(&macros) {
+                outln!("{}", style::paint(Style::Yellow, Message::WarningLine.format(&[&warning])));
+            }
+            if let Some(density) = log.calorie_density(self.food_repo.get_foods()) {
+                outln!("{}", Message::CalorieDensityLine.format(&[&density]));
+            }
+
+            // Spending, for foods with a cost per serving
+            let cost = log.total_cost(self.food_repo.get_foods());
+            if cost.priced_entries > 0 {
+                outln!("{}", Message::FoodCostLine.format(&[&Self::describe_cost(&cost)]));
+                if let (Some(calories), Some(protein)) = (cost.calories_per_unit_cost(), cost.protein_per_unit_cost()) {
+                    outln!("{}", Message::PerUnitCostLine.format(&[&calories, &protein]));
+                }
+            }
+        } else {
+            outln!("{}", Message::NoFoodLoggedToday);
+            outln!("{}", Message::CaloriesConsumedZero);
+            outln!("{}", Message::Difference.format(&[&style::against_target(format!("{:.1}", -target_calories), 0.0, target_calories)]));
+        }
+        
+        // Consumed against the macro targets (the user's own, or the preset's)
+        if let Some(target) = profile.active_macro_targets().and_then(|targets| targets.grams(target_calories)) {
+            let consumed = self.log_repo.get_log(self.current_date)
+                .map_or(Macros::default(), |log| log.total_macros(self.food_repo.get_foods()));
+            match (profile.macro_targets, profile.diet_preset) {
+                (None, Some(preset)) => outln!("\n{}", style::paint(Style::Bold, Message::MacroTargetsPreset.format(&[&preset]))),
+                _ => outln!("\n{}", style::paint(Style::Bold, Message::MacroTargetsHeading.text())),
+            }
+            for (name, consumed, target) in [
+                (Message::Protein.text(), consumed.protein, target.protein),
+                (Message::Carbs.text(), consumed.carbs, target.carbs),
+                (Message::Fat.text(), consumed.fat, target.fat),
+            ] {
+                let percent = if target > 0.0 { format!(" ({:.0}%)", consumed / target * 100.0) } else { String::new() };
+                outln!("{:<8} {} {:.0}/{:.0} g{}", name,
+                         text_charts::progress_bar(consumed, target, text_charts::PROGRESS_WIDTH), consumed, target, percent);
+            }
+        }
+
+        // Totals for the week containing the current date (see models::week)
+        let week_start = WeekStart::from_env();
+        let first_day = week_start.week_of(self.current_date);
+        let week_logs = self.log_repo.get_logs_between(first_day, first_day + Duration::days(6));
+        if let Some(totals) = week::weekly_totals(week_logs, self.food_repo.get_foods(), week_start).first() {
+            outln!("\n{}", style::paint(Style::Bold, Message::ThisWeek.format(&[
+                &Message::from(totals.start.weekday()),
+                &self.show_date(totals.start),
+                &Message::from(totals.end().weekday()),
+                &self.show_date(totals.end()),
+            ])));
+            outln!("{}", Message::WeekCaloriesConsumed.format(&[&totals.calories, &totals.days_logged]));
+            outln!("{}", Message::AveragePerLoggedDay.format(&[&totals.average_calories()]));
+            if totals.cost > 0.0 {
+                outln!("{}", Message::WeekFoodCost.format(&[&totals.cost, &(totals.cost / totals.days_logged as f64)]));
+            }
+        }
+        
+        // Calories of the last two weeks against each day's target
+        let pipeline = self.target_pipeline();
+        let rows: Vec<BarRow> = (0..text_charts::CHART_DAYS).rev().map(|offset| {
+            let date = self.current_date - Duration::days(offset);
+            let calories = self.log_repo.get_log(date)
+                .filter(|log| !log.entries.is_empty())
+                .map(|log| log.total_calories(self.food_repo.get_foods()));
+            let target = self.calculator_factory.calculator_for(profile, date)
+                .map(|calculator| calculator.calculate_target_with(profile, date, &pipeline).target)
+                .filter(|target| *target > 0.0);
+            BarRow { label: format!("{} {}", Message::from(date.weekday()), date.format("%m-%d")), value: calories, target }
+        }).collect();
+        if rows.iter().any(|row| row.value.is_some()) {
+            outln!("\n{}", style::paint(Style::Bold, Message::LastDaysChart.format(&[&text_charts::CHART_DAYS])));
+            for line in text_charts::bar_chart(&rows, text_charts::BAR_WIDTH) {
+                outln!("{}", line);
+            }
+        }
+
+        // Trend of the most recent weigh-ins (carried-forward weights are not measurements)
+        let mut weigh_ins: Vec<&DailyProfile> = profile.daily_profiles.iter()
+            .filter(|daily| daily.carried_from.is_none() && daily.date <= self.current_date)
+            .collect();
+        weigh_ins.sort_by_key(|daily| daily.date);
+        let weigh_ins = &weigh_ins[weigh_ins.len().saturating_sub(text_charts::SPARKLINE_WEIGH_INS)..];
+        if let [first, .., last] = weigh_ins {
+            let weights: Vec<f64> = weigh_ins.iter().map(|daily| daily.weight).collect();
+            outln!("\n{}", style::paint(Style::Bold, Message::WeightTrend.format(&[
+                &weigh_ins.len(),
+                &self.show_date(first.date),
+                &self.show_date(last.date),
+            ])));
+            let units = self.units();
+            let change = units.weight_from_kg(last.weight) - units.weight_from_kg(first.weight);
+            outln!("{}  {} -> {} ({:+.1} {})",
+                     text_charts::sparkline(&weights), units.format_weight(first.weight), units.format_weight(last.weight),
+                     change, units.weight_unit());
+        }
+        
+        // Show weight history if available
+        if !profile.daily_profiles.is_empty() {
+            outln!("\n{}", style::paint(Style::Bold, Message::WeightHistory.text()));
+            
+            // Sort by date
+            let mut profiles = profile.daily_profiles.clone();
+            profiles.sort_by_key(|p| p.date);
+            
+            for daily in profiles {
+                outln!("{}: {}", self.show_date(daily.date), self.units().format_weight(daily.weight));
+            }
+        }
+    }
+    
+    /// Shows and changes the app-wide preferences kept in the settings file
+    ///
+    /// Preferences are settings rather than data, so changes are saved
+    /// right away instead of going through a command, and cannot be undone.
+    /// The data directory is kept in the config file instead, since the
+    /// settings file lives inside it.
+    fn preferences_menu(&mut self) {
+        loop {
+            outln!("\n{}", style::heading(Message::Preferences));
+            let preferences = self.settings_repo.preferences().clone();
+            let data_dir_source = match self.config.data_dir_source {
+                DataDirSource::CommandLine => Message::FromDataDirFlag.text(),
+                DataDirSource::Environment => Message::FromDataDirEnv.text(),
+                DataDirSource::ConfigFile => Message::FromConfigFile.text(),
+                DataDirSource::Legacy => Message::CurrentDirectory.text(),
+                DataDirSource::PlatformDefault => Message::DefaultSource.text(),
+            };
+            let options = [
+                Message::UnitsOption.format(&[&preferences.units]),
+                Message::AutosaveOption.format(&[&self.autosaver.policy()]),
+                Message::DefaultSearchOption.format(&[&preferences.search_mode]),
+                Message::DateFormatOption.format(&[&preferences.date_format]),
+                Message::ConfirmationsOption.format(&[&preferences.confirm]),
+                Message::SavedSearchesOption.format(&[&self.settings_repo.saved_searches().len()]),
+                Message::DataDirectoryOption.format(&[&self.config.data_dir.display(), &data_dir_source]),
+                Message::BackToMainMenu.to_string(),
+            ];
+            let options: Vec<&str> = options.iter().map(String::as_str).collect();
+
+            let mut changed = preferences.clone();
+            match prompt::choice(&options).ask() {
+                Ok(1) => {
+                    let labels: Vec<String> = UnitSystem::ALL.iter().map(ToString::to_string).collect();
+                    let Ok(choice) = prompt::choice(&labels.iter().map(String::as_str).collect::<Vec<_>>()).ask() else {
+                        continue;
+                    };
+                    changed.units = UnitSystem::ALL[choice - 1];
+                }
+                Ok(2) => {
+                    let Ok(policy) = prompt::Prompt::new(
+                        Message::AutosavePrompt.text(),
+                        |input| AutosavePolicy::parse(input).ok_or_else(|| Message::AutosaveHint.text().to_string()),
+                    ).ask() else {
+                        continue;
+                    };
+                    changed.autosave = policy.clone();
+                    match AutosavePolicy::env_override() {
+                        Some(current) => outln!("{}", Message::AutosaveEnvOverride.format(&[&AUTOSAVE_ENV_VAR, &current])),
+                        None => self.autosaver.set_policy(policy),
+                    }
+                }
+                Ok(3) => {
+                    let Ok(choice) = prompt::choice(&[Message::MatchAny.text(), Message::MatchAll.text()]).ask() else {
+                        continue;
+                    };
+                    changed.search_mode = if choice == 2 { SearchMode::All } else { SearchMode::Any };
+                }
+                Ok(4) => {
+                    let labels: Vec<String> = DateFormat::ALL.iter()
+                        .map(|format| Message::DateFormatExample.format(&[&format, &format.format(self.current_date)]))
+                        .collect();
+                    let Ok(choice) = prompt::choice(&labels.iter().map(String::as_str).collect::<Vec<_>>()).ask() else {
+                        continue;
+                    };
+                    changed.date_format = DateFormat::ALL[choice - 1];
+                }
+                Ok(5) => {
+                    let labels: Vec<String> = ConfirmPolicy::ALL.iter().map(ToString::to_string).collect();
+                    let Ok(choice) = prompt::choice(&labels.iter().map(String::as_str).collect::<Vec<_>>()).ask() else {
+                        continue;
+                    };
+                    changed.confirm = ConfirmPolicy::ALL[choice - 1];
+                }
+                Ok(6) => {
+                    self.saved_searches_menu();
+                    continue;
+                }
+                Ok(7) => {
+                    self.change_data_dir();
+                    continue;
+                }
+                _ => break,
+            }
+
+            if changed != preferences {
+                self.settings_repo.set_preferences(changed);
+                outln!("{}", Message::PreferencesUpdated);
+                // Not routed through a command, so trigger autosave explicitly
+                self.autosave(AutosaveTrigger::Command);
+            }
+        }
+    }
+
+    /// Lists, adds, and deletes the saved food searches
+    ///
+    /// Saved searches are picked at the search prompt by number or name.
+    fn saved_searches_menu(&mut self) {
+        loop {
+            outln!("\n{}", style::heading(Message::SavedSearches.text()));
+            let saved = self.settings_repo.saved_searches();
+            if saved.is_empty() {
+                outln!("{}", Message::NoSavedSearches);
+            }
+            for (index, search) in saved.iter().enumerate() {
+                outln!("  #{} {}", index + 1, search);
+            }
+
+            match prompt::choice(&[Message::AddSavedSearch.text(), Message::DeleteSavedSearch.text(), Message::Back.text()]).ask() {
+                Ok(1) => {
+                    let Ok(name) = prompt::Prompt::new(Message::SearchNamePrompt.text(), |input| {
+                        if input.is_empty() {
+                            Err(Message::EnterName.text().to_string())
+                        } else {
+                            Ok(input.to_string())
+                        }
+                    }).ask() else {
+                        continue;
+                    };
+                    let Ok(terms) = prompt::keywords(Message::SavedSearchKeywordsPrompt.text()).ask() else {
+                        continue;
+                    };
+                    let Ok(mode) = prompt::choice(&[Message::MatchAny.text(), Message::MatchAll.text()]).ask() else {
+                        continue;
+                    };
+                    let mode = if mode == 2 { SearchMode::All } else { SearchMode::Any };
+                    let terms: Vec<String> = terms.into_iter().collect();
+                    match self.settings_repo.save_search(&name, &terms, mode) {
+                        Ok(replaced) => {
+                            let message = if replaced { Message::ReplacedSavedSearch } else { Message::AddedSavedSearch };
+                            outln!("{}", message.format(&[&name]));
+                            // Not routed through a command, so trigger autosave explicitly
+                            self.autosave(AutosaveTrigger::Command);
+                        }
+                        Err(e) => outln!("{}.", e),
+                    }
+                }
+                Ok(2) => {
+                    if saved.is_empty() {
+                        continue;
+                    }
+                    let count = saved.len();
+                    let Ok(number) = prompt::Prompt::new(Message::SearchToDeletePrompt.text(), move |input| {
+                        input.trim_start_matches('#').parse::<usize>().ok()
+                            .filter(|number| (1..=count).contains(number))
+                            .ok_or_else(|| Message::NumberFromTo.format(&[&count]))
+                    }).ask() else {
+                        continue;
+                    };
+                    let name = self.settings_repo.saved_searches()[number - 1].name.clone();
+                    if !self.confirm(&Message::ConfirmDeleteSearch.format(&[&name]), None) {
+                        outln!("{}", Message::DeleteCancelled);
+                        continue;
+                    }
+                    if let Some(removed) = self.settings_repo.remove_search(&name) {
+                        outln!("{}", Message::DeletedSavedSearch.format(&[&removed.name]));
+                        self.autosave(AutosaveTrigger::Command);
+                    }
+                }
+                _ => break,
+            }
+        }
+    }
+
+    /// Stores a new data directory in the config file for the next start
+    ///
+    /// The data files are not moved; `--data-dir` and `YADA_DATA_DIR` still
+    /// take precedence over the config file.
+    fn change_data_dir(&mut self) {
+        outln!("{}", Message::DataDirReadAtStartup);
+        outln!("{}", Message::FilesNotMoved);
+        let Ok(dir) = prompt::Prompt::new(Message::NewDataDirPrompt.text(), |input| {
+            if input.is_empty() {
+                Err(Message::EnterDirectory.text().to_string())
+            } else {
+                Ok(input.to_string())
+            }
+        }).ask() else {
+            return;
+        };
+
+        match config::write_config_value("data_dir", &dir) {
+            Ok(path) => {
+                outln!("{}", Message::SavedTo.format(&[&path.display()]));
+                if matches!(self.config.data_dir_source, DataDirSource::CommandLine | DataDirSource::Environment) {
+                    outln!("{}", Message::DataDirPrecedence);
+                }
+            }
+            Err(e) => outln!("{}", e),
+        }
+    }
+
+    /// Persists all application data to disk using the Repository Pattern
+    /// 
+    /// This method coordinates data persistence across all repositories:
+    /// 1. Food database persistence (foods.txt) - maintains food definitions
+    /// 2. Food logs persistence (logs.txt) - saves daily consumption records
+    /// 3. User profile persistence (profile.txt) - stores user information
+    ///
+    /// All files live in the configured data directory (see `config.rs`).
+    /// 
+    /// Data persistence features:
+    /// - Atomic operations to prevent data corruption
+    /// - Error handling with user feedback for failed saves
+    /// - Repository Pattern abstraction for consistent data access
+    /// - File-based storage for simplicity and portability
+    /// 
+    /// This method ensures data durability and enables application state
+    /// to be maintained across sessions. The Repository Pattern provides
+    /// a clean separation between data access logic and business logic,
+    /// making the system maintainable and testable.
+    ///
+    /// # Returns
+    /// True if every file that needed saving was saved
+    fn save_data(&mut self) -> bool {
+        outln!("{}", Message::SavingData);
+        let mut saved = true;
+        
+        match self.food_repo.save() {
+            Ok(_) => outln!("{}", Message::FoodDataSaved),
+            Err(e) => {
+                saved = false;
+                outln!("{}", Message::ErrorSavingFoodData.format(&[&e]));
+            }
+        }
+        
+        match self.log_repo.save() {
+            Ok(_) => outln!("{}", Message::LogDataSaved),
+            Err(e) => {
+                saved = false;
+                outln!("{}", Message::ErrorSavingLogData.format(&[&e]));
+            }
+        }
+        
+        match self.profile_repo.save() {
+            Ok(_) => outln!("{}", Message::ProfileDataSaved),
+            Err(e) => {
+                saved = false;
+                outln!("{}", Message::ErrorSavingProfileData.format(&[&e]));
+            }
+        }
+        
+        // The exercise file is only created once something has been logged
+        if self.exercise_repo.is_dirty() {
+            match self.exercise_repo.save() {
+                Ok(_) => outln!("{}", Message::ExerciseDataSaved),
+                Err(e) => {
+                    saved = false;
+                    outln!("{}", Message::ErrorSavingExerciseData.format(&[&e]));
+                }
+            }
+        }
+
+        // Likewise for meal plans
+        if self.meal_plan_repo.is_dirty() {
+            match self.meal_plan_repo.save() {
+                Ok(_) => outln!("{}", Message::MealPlansSaved),
+                Err(e) => {
+                    saved = false;
+                    outln!("{}", Message::ErrorSavingMealPlans.format(&[&e]));
+                }
+            }
+        }
+
+        // And for meal templates
+        if self.meal_template_repo.is_dirty() {
+            match self.meal_template_repo.save() {
+                Ok(_) => outln!("{}", Message::MealTemplatesSaved),
+                Err(e) => {
+                    saved = false;
+                    outln!("{}", Message::ErrorSavingMealTemplates.format(&[&e]));
+                }
+            }
+        }
+
+        // And for settings
+        if self.settings_repo.is_dirty() {
+            match self.settings_repo.save() {
+                Ok(_) => outln!("{}", Message::SettingsSaved),
+                Err(e) => {
+                    saved = false;
+                    outln!("{}", Message::ErrorSavingSettings.format(&[&e]));
+                }
+            }
+        }
+
+        if saved {
+            self.autosaver.record_save();
+        }
+        saved
+    }
+
+    /// Executes a command through the command manager and then autosaves
+    ///
+    /// All menu flows go through this wrapper instead of calling the command
+    /// manager directly, so every undoable change is persisted according to
+    /// the configured autosave policy. Commands whose undo policy is `Skip`
+    /// print a note that they cannot be undone.
+    ///
+    /// # Arguments
+    /// * `command` - The command to execute
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the command executed successfully
+    /// * `Err(String)` - Error message from the command
+    fn execute_command(&mut self, command: Box<dyn Command>) -> Result<(), String> {
+        let policy = self.command_manager.policies().policy_for(&command.get_type());
+        let result = self.command_manager.execute_command(command);
+        self.report_audit_error();
+        if result.is_ok() {
+            if policy == UndoPolicy::Skip {
+                outln!("{}", Message::CannotBeUndone.format(&[&UNDO_POLICY_ENV_VAR]));
+            }
+            self.autosave(AutosaveTrigger::Command);
+            self.refresh_intake();
+        }
+        result
+    }
+
+    /// Creates the command manager of a user, recording commands in the user's audit log
+    fn command_manager(config: &AppConfig, policies: UndoPolicies) -> CommandManager {
+        CommandManager::new(100)
+            .with_policies(policies)
+            .with_audit_sink(AuditLog::new(config.audit_log_path()))
+    }
+
+    /// Reports a failure to write the audit log; the change itself was made
+    fn report_audit_error(&mut self) {
+        if let Some(e) = self.command_manager.take_audit_error() {
+            outln!("{}", Message::AuditLogError.format(&[&e]));
+        }
+    }
+
+    /// Asks the user to type `yes` before a change, if the confirmation preference requires it
+    ///
+    /// Every confirmation of the menus goes through here. In expert mode,
+    /// changes that Undo can reverse go ahead without asking (see
+    /// `ConfirmPolicy`).
+    ///
+    /// # Arguments
+    /// * `message` - The prompt
+    /// * `kind` - Type of the command making the change, or `None` for changes that are not commands
+    ///
+    /// # Returns
+    /// true if the change goes ahead
+    fn confirm(&self, message: &str, kind: Option<CommandType>) -> bool {
+        let undoable = kind.is_some_and(|kind| self.command_manager.policies().policy_for(&kind) != UndoPolicy::Skip);
+        if !self.settings_repo.preferences().confirm.requires_confirmation(undoable) {
+            return true;
+        }
+        prompt::confirm(message).ask() == Ok(true)
+    }
+
+    /// Hands the current logged intake to the adaptive calorie method
+    ///
+    /// Called after every change to the log (commands, undos, imports, and
+    /// loading a user's data), so targets follow new entries and edits.
+    /// Adding up the whole log takes a while with years of entries, so it is
+    /// skipped unless the profile uses the adaptive method on some day.
+    fn refresh_intake(&mut self) {
+        if self.profile_repo.get_profile().is_some_and(|profile| profile.uses_method(ADAPTIVE_METHOD)) {
+            self.calculator_factory = CalorieCalculatorFactory::from_repositories(&self.food_repo, &self.log_repo);
+        }
+    }
+
+    /// Silently saves every dirty repository when the autosave policy says so
+    ///
+    /// Clean repositories are skipped to avoid needless disk writes. Failures
+    /// are reported but do not interrupt the current flow; the data stays
+    /// dirty and will be retried on the next trigger or on exit.
+    ///
+    /// # Arguments
+    /// * `trigger` - The event giving the autosaver a chance to run
+    fn autosave(&mut self, trigger: AutosaveTrigger) {
+        if !self.autosaver.is_due(trigger) {
+            return;
+        }
+
+        let mut failed = false;
+
+        if self.food_repo.is_dirty() && let Err(e) = self.food_repo.save() {
+            outln!("{}", Message::AutosaveFailedFoods.format(&[&e]));
+            failed = true;
+        }
+
+        if self.log_repo.is_dirty() && let Err(e) = self.log_repo.save() {
+            outln!("{}", Message::AutosaveFailedLog.format(&[&e]));
+            failed = true;
+        }
+
+        if self.profile_repo.is_dirty() && let Err(e) = self.profile_repo.save() {
+            outln!("{}", Message::AutosaveFailedProfile.format(&[&e]));
+            failed = true;
+        }
+
+        if self.exercise_repo.is_dirty() && let Err(e) = self.exercise_repo.save() {
+            outln!("{}", Message::AutosaveFailedExercise.format(&[&e]));
+            failed = true;
+        }
+
+        if self.meal_plan_repo.is_dirty() && let Err(e) = self.meal_plan_repo.save() {
+            outln!("{}", Message::AutosaveFailedMealPlans.format(&[&e]));
+            failed = true;
+        }
+
+        if self.meal_template_repo.is_dirty() && let Err(e) = self.meal_template_repo.save() {
+            outln!("{}", Message::AutosaveFailedTemplates.format(&[&e]));
+            failed = true;
+        }
+
+        if self.settings_repo.is_dirty() && let Err(e) = self.settings_repo.save() {
+            outln!("{}", Message::AutosaveFailedSettings.format(&[&e]));
+            failed = true;
+        }
+
+        if !failed {
+            self.autosaver.record_save();
+        }
+    }
+    /// Undoes the last executed command using the Command Pattern
+    /// 
+    /// This method implements the undo functionality of the Command Pattern:
+    /// 1. Checks if there are any commands available to undo
+    /// 2. Displays the description of the command being undone
+    /// 3. Executes the undo operation through the command manager
+    /// 4. Provides feedback on the success or failure of the undo operation
+    /// 
+    /// Command Pattern benefits:
+    /// - Encapsulates operations as objects for easy undo/redo
+    /// - Maintains command history for multiple undo levels
+    /// - Decouples command execution from command creation
+    /// - Enables macro recording and replay capabilities
+    /// 
+    /// Supported undoable operations include:
+    /// - Food additions (basic and composite)
+    /// - Food log entries and deletions
+    /// - Profile modifications (basic and daily updates)
+    /// - Calculation method changes
+    fn undo_last_command(&mut self) {
+        if !self.command_manager.has_commands_to_undo() {
+            outln!("{}", Message::NoCommandsToUndo);
+            return;
+        }
+        
+        let description = self.command_manager.get_command_history().last().cloned().unwrap_or_else(|| Message::Unknown.text().to_string());
+        outln!("{}", Message::UndoingCommand.format(&[&description]));
+        
+        // Some kinds of changes (profile updates by default) are only undone after confirmation
+        if self.command_manager.next_undo_policy() == Some(UndoPolicy::Confirm)
+            && !self.confirm(Message::ConfirmUndo.text(), None)
+        {
+            outln!("{}", Message::NothingUndone);
+            return;
+        }
+        
+        let result = self.command_manager.undo_last_command();
+        self.report_audit_error();
+        match result {
+            Ok(_) => {
+                outln!("{}", Message::CommandUndone);
+                self.autosave(AutosaveTrigger::Command);
+                self.refresh_intake();
+            }
+            Err(e) => outln!("{}", Message::ErrorUndoing.format(&[&e])),
+        }
+    }
+    
+    /// Deletes a specific food log entry for the current date with user confirmation
+    /// 
+    /// This method implements safe deletion of food log entries with:
+    /// 1. Validation that log entries exist for the current date
+    /// 2. User selection of specific entry by number (1-based indexing)
+    /// 3. Input validation for entry number bounds checking
+    /// 4. Confirmation dialog showing entry details before deletion
+    /// 5. Command pattern integration for undo functionality
+    /// 
+    /// Safety features:
+    /// - Bounds checking prevents array index errors
+    /// - Confirmation dialog prevents accidental deletions
+    /// - User-friendly display shows food name and servings
+    /// - Clear feedback on success or failure
+    /// 
+    /// Uses the Command pattern (RemoveLogEntryCommand) to enable undoing
+    /// of deletion operations, maintaining consistency with the application's
+    /// command-based architecture for all data modifications.
+    fn delete_log_entry(&mut self) {
+        outln!("\n{}", style::heading(Message::DeleteLogEntry));
+        
+        // Get log for current date
+        let log = match self.log_repo.get_log(self.current_date) {
+            Some(log) => log,
+            None => {
+                outln!("{}", Message::NoFoodEntriesFor.format(&[&self.show_date(self.current_date)]));
+                return;
+            }
+        };
+        
+        if log.entries.is_empty() {
+            outln!("{}", Message::NoEntriesToDelete);
+            return;
+        }
+        
+        let entry_count = log.entries.len();
+        let error = Message::InvalidEntryNumber.format(&[&entry_count]);
+        let Ok(entry_number) = prompt::number::<usize>(
+            &Message::EntryToDeletePrompt.format(&[&entry_count]),
+            &error,
+        ).validate(prompt::in_range(1, entry_count, &error)).ask() else {
+            return;
+        };
+        
+        // Convert to 0-based index
+        let index = entry_number - 1;
+        
+        // Get the entry details for confirmation
+        let entry = &log.entries[index];
+        let entry_id = entry.entry_id;
+        let food_name = self.food_repo.get_food(&entry.food_id)
+            .map_or(Message::Unknown.text().to_string(), |f| f.name.clone());
+        
+        outln!("{}", Message::LogEntryLine.format(&[&entry_number, &entry.quantity, &food_name, &entry.food_id]));
+        if !self.confirm(Message::ConfirmDeleteEntry.text(), Some(CommandType::DeleteLog)) {
+            outln!("{}", Message::DeleteCancelled);
+            return;
+        }
+        
+        // Create and execute the remove command
+        let command = Box::new(RemoveLogEntryCommand::new(
+            &mut self.log_repo,
+            self.current_date,
+            entry_id
+        ));
+        
+        match self.execute_command(command) {
+            Ok(_) => outln!("{}", Message::FoodEntryDeleted),
+            Err(e) => outln!("{}", Message::ErrorDeletingFoodEntry.format(&[&e])),
+        }
+    }
+
+    /// Changes the food or amount of a food log entry for the current date
+    ///
+    /// Pressing Enter at the food or time prompt keeps the entry's food or
+    /// time. An entry given a new time moves to its place in the day; it
+    /// keeps its meal. The change goes through `UpdateLogEntryCommand` so it
+    /// can be undone.
+    fn edit_log_entry(&mut self) {
+        outln!("\n{}", style::heading(Message::EditLogEntry));
+
+        let Some(log) = self.log_repo.get_log(self.current_date).filter(|log| !log.entries.is_empty()) else {
+            outln!("{}", Message::NoEntriesToEdit);
+            return;
+        };
+
+        let entry_count = log.entries.len();
+        let error = Message::InvalidEntryNumber.format(&[&entry_count]);
+        let Ok(entry_number) = prompt::number::<usize>(
+            &Message::EntryToEditPrompt.format(&[&entry_count]),
+            &error,
+        ).validate(prompt::in_range(1, entry_count, &error)).ask() else {
+            return;
+        };
+        let index = entry_number - 1;
+        let entry = log.entries[index].clone();
+
+        let current_food = self.food_repo.get_food(&entry.food_id);
+        outln!("{}", Message::LogEntryLine.format(&[
+            &entry_number,
+            &entry.quantity,
+            &(current_food.map_or(Message::Unknown.text(), |f| f.name.as_str())),
+            &entry.food_id,
+        ]));
+
+        // Entries whose food no longer exists must be given another food
+        let food_prompt = match current_food {
+            Some(_) => self.prompt_existing_food_id(&Message::EnterFoodIdKeep.format(&[&entry.food_id]))
+                .default(entry.food_id.clone()),
+            None => self.prompt_existing_food_id(Message::EnterFoodId.text()),
+        };
+        let Ok(food_id) = food_prompt.ask() else {
+            return;
+        };
+        let Some(food) = self.food_repo.get_food(&food_id) else {
+            return;
+        };
+        if food_id != entry.food_id {
+            self.warn_dietary_conflicts(food);
+        }
+        let Ok((quantity, servings)) = Self::prompt_amount(food).ask() else {
+            return;
+        };
+        // An unchanged food and amount keep the calories they were logged with
+        let calories = match entry.calories {
+            Some(calories) if food_id == entry.food_id && quantity == entry.quantity => calories,
+            _ => food.calories_per_serving * servings,
+        };
+
+        // The time only changes when a different minute is entered
+        let current_time = entry.timestamp.time();
+        let message = Message::TimeEatenKeepPrompt.format(&[&current_time.format("%H:%M")]);
+        let Ok(time) = Self::prompt_time_on(self.current_date, &message, current_time).ask() else {
+            return;
+        };
+
+        let mut command = UpdateLogEntryCommand::new(
+            &mut self.log_repo,
+            self.current_date,
+            entry.entry_id,
+            food_id.clone(),
+            quantity,
+            servings,
+        ).with_calories(calories);
+        if (time.hour(), time.minute()) != (current_time.hour(), current_time.minute())
+            && let Some(timestamp) = food_log::local_timestamp(self.current_date, time) {
+            command = command.with_timestamp(timestamp);
+        }
+        let command = Box::new(command);
+
+        match self.execute_command(command) {
+            Ok(_) => {
+                if food_id != entry.food_id {
+                    self.food_repo.record_usage(&food_id, self.current_date);
+                }
+                outln!("{}", Message::FoodEntryUpdated);
+            }
+            Err(e) => outln!("{}", Message::ErrorUpdatingFoodEntry.format(&[&e])),
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! # Shell Completion Scripts
+//!
+//! This module generates completion scripts for bash, zsh, and fish. The
+//! static part (subcommands, flags, shell names) is produced by
+//! `clap_complete` from the `Cli` definition, so it never drifts from the
+//! actual arguments.
+//!
+//! ## Dynamic Food-ID Completion
+//!
+//! Food IDs live in the food database and change at runtime, so they cannot
+//! be baked into a static script. Each generated script is extended with a
+//! small shell-specific hook that runs `yada --list-food-ids` when the cursor
+//! is on a food-ID argument (`yada log <FOOD_ID>`, `yada compare <FOOD_ID>...`).
+//!
+//! ## Installation
+//!
+//! - **bash**: `yada completions bash > ~/.local/share/bash-completion/completions/yada`
+//! - **zsh**: `yada completions zsh > "${fpath[1]}/_yada"`
+//! - **fish**: `yada completions fish > ~/.config/fish/completions/yada.fish`
+
+// src/cli/completions.rs
+use std::io::{self, Write};
+use clap::{CommandFactory, ValueEnum};
+use clap_complete::{generate, Shell};
+
+use super::Cli;
+
+/// Binary name used in the generated scripts
+const BIN_NAME: &str = "yada";
+
+/// Hidden flag that prints food IDs for the completion hooks
+pub const LIST_FOOD_IDS_FLAG: &str = "list-food-ids";
+
+/// Shells for which completion scripts can be generated
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum CompletionShell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl CompletionShell {
+    /// Maps to the corresponding `clap_complete` generator
+    fn generator(self) -> Shell {
+        match self {
+            CompletionShell::Bash => Shell::Bash,
+            CompletionShell::Zsh => Shell::Zsh,
+            CompletionShell::Fish => Shell::Fish,
+        }
+    }
+}
+
+/// Builds the complete script for a shell, including the food-ID hook
+///
+/// # Arguments
+/// * `shell` - Target shell
+///
+/// # Returns
+/// The completion script as a string
+pub fn completion_script(shell: CompletionShell) -> String {
+    let mut command = Cli::command();
+    let mut buffer = Vec::new();
+    generate(shell.generator(), &mut command, BIN_NAME, &mut buffer);
+    let script = String::from_utf8_lossy(&buffer).into_owned();
+
+    match shell {
+        CompletionShell::Bash => bash_with_food_ids(script),
+        CompletionShell::Zsh => zsh_with_food_ids(script),
+        CompletionShell::Fish => fish_with_food_ids(script),
+    }
+}
+
+/// Writes the completion script for a shell to standard output
+pub fn print_completions(shell: CompletionShell) -> io::Result<()> {
+    let mut stdout = io::stdout().lock();
+    stdout.write_all(completion_script(shell).as_bytes())?;
+    stdout.flush()
+}
+
+/// Wraps the generated `_yada` function so food-ID arguments offer food IDs
+fn bash_with_food_ids(mut script: String) -> String {
+    script.push_str(&format!(r#"
+# Dynamic food-ID completion for `{bin} log` and `{bin} compare`
+_{bin}_food_ids() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}"
+    local prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+    if [[ "${{COMP_WORDS[1]}}" == "compare" && "${{cur}}" != -* ]]; then
+        COMPREPLY=( $(compgen -W "$({bin} --{flag} 2>/dev/null)" -- "${{cur}}") )
+        return 0
+    fi
+    if [[ "${{COMP_WORDS[1]}}" == "log" && "${{cur}}" != -* && "${{prev}}" != "--date" ]]; then
+        local i positional=0
+        for (( i = 2; i < COMP_CWORD; i++ )); do
+            case "${{COMP_WORDS[i]}}" in
+                --date) (( i++ )) ;;
+                -*) ;;
+                *) (( positional++ )) ;;
+            esac
+        done
+        if [[ ${{positional}} -eq 0 ]]; then
+            COMPREPLY=( $(compgen -W "$({bin} --{flag} 2>/dev/null)" -- "${{cur}}") )
+            return 0
+        fi
+    fi
+    _{bin} "$@"
+}}
+complete -F _{bin}_food_ids -o bashdefault -o default {bin}
+"#, bin = BIN_NAME, flag = LIST_FOOD_IDS_FLAG));
+    script
+}
+
+/// Points the food-ID argument specs at a helper that lists food IDs
+fn zsh_with_food_ids(script: String) -> String {
+    let helper = format!(r#"(( $+functions[_{bin}_food_ids] )) ||
+_{bin}_food_ids() {{
+    local -a food_ids
+    food_ids=(${{(f)"$({bin} --{flag} 2>/dev/null)"}})
+    _describe -t food-ids 'food id' food_ids
+}}
+
+"#, bin = BIN_NAME, flag = LIST_FOOD_IDS_FLAG);
+
+    let mut output = String::new();
+    for line in script.lines() {
+        if line.starts_with("':food_id -- ") || line.starts_with("'*::food_ids -- ") {
+            output.push_str(&line.replace(":_default'", &format!(":_{}_food_ids'", BIN_NAME)));
+        } else {
+            if line.starts_with(&format!("if [ \"$funcstack[1]\" = \"_{}\" ]", BIN_NAME)) {
+                output.push_str(&helper);
+            }
+            output.push_str(line);
+        }
+        output.push('\n');
+    }
+    output
+}
+
+/// Adds `complete` rules offering food IDs after `yada log` and `yada compare`
+fn fish_with_food_ids(mut script: String) -> String {
+    script.push_str(&format!(
+        "\n# Dynamic food-ID completion for `{bin} log` and `{bin} compare`\n\
+         complete -c {bin} -n \"__fish_{bin}_using_subcommand log; and test (count (commandline -opc)) -eq 2\" -f -a \"({bin} --{flag} 2>/dev/null)\" -d 'Food ID'\n\
+         complete -c {bin} -n \"__fish_{bin}_using_subcommand compare\" -f -a \"({bin} --{flag} 2>/dev/null)\" -d 'Food ID'\n",
+        bin = BIN_NAME,
+        flag = LIST_FOOD_IDS_FLAG,
+    ));
+    script
+}
@@ -0,0 +1,85 @@
+//! # Data Integrity Doctor
+//!
+//! This module holds the terminal side of `yada doctor`, which checks that
+//! log entries and recipes only use foods that exist and that daily
+//! profiles have possible weights. The checks and repairs themselves live in
+//! `yada_core::repositories::integrity`.
+//!
+//! ## Modes
+//!
+//! - **Report** (default): List the problems; nothing is changed
+//! - **--repair**: Ask about each problem: replace the missing food with an
+//!   existing one, correct the weight, remove the broken record, or leave it
+//! - **--prune**: Remove every broken record without asking
+
+// src/cli/doctor.rs
+use std::collections::HashSet;
+
+use yada_core::models::validation;
+use yada_core::repositories::integrity::{Problem, Repair};
+
+use crate::ui::console::outln;
+use crate::ui::i18n::Message;
+use crate::ui::prompt::{self, PromptResult};
+
+/// Formats the problems found as a report
+pub fn describe(problems: &[Problem]) -> String {
+    if problems.is_empty() {
+        return Message::NoProblemsFound.text().to_string();
+    }
+    let mut lines = vec![Message::FoundProblems.format(&[&problems.len()])];
+    lines.extend(problems.iter().map(|problem| format!("  {}", problem)));
+    lines.join("\n")
+}
+
+/// Asks the user how to repair one problem
+///
+/// # Arguments
+/// * `problem` - The problem to repair
+/// * `food_ids` - IDs of the existing foods, offered as replacements
+///
+/// # Returns
+/// * `Ok(Some(Repair))` - The chosen repair
+/// * `Ok(None)` - If the problem should be left as it is
+/// * `Err(PromptError)` - If the user cancelled or input ended
+pub fn ask_repair(problem: &Problem, food_ids: &HashSet<String>) -> PromptResult<Option<Repair>> {
+    outln!("\n{}", problem);
+    match problem {
+        Problem::ImpossibleWeight { .. } => {
+            match prompt::choice(&[Message::CorrectWeight.text(), Message::RemoveDailyProfile.text(), Message::LeaveIt.text()]).ask()? {
+                1 => {
+                    let weight = prompt::number(Message::EnterWeightKg.text(), Message::WeightInKg.text())
+                        .validate(|weight| validation::weight_kg(*weight).map(|_| ()))
+                        .ask()?;
+                    Ok(Some(Repair::SetWeight(weight)))
+                }
+                2 => Ok(Some(Repair::Remove)),
+                _ => Ok(None),
+            }
+        }
+        Problem::MissingLoggedFood { .. } | Problem::MissingComponent { .. } => {
+            let remove = if matches!(problem, Problem::MissingLoggedFood { .. }) {
+                Message::RemoveLogEntry.text()
+            } else {
+                Message::RemoveComponent.text()
+            };
+            match prompt::choice(&[Message::UseAnotherFood.text(), remove, Message::LeaveIt.text()]).ask()? {
+                1 => {
+                    let known = food_ids.clone();
+                    let id = prompt::text(Message::EnterFoodId.text())
+                        .validate(move |id| {
+                            if known.contains(id) {
+                                Ok(())
+                            } else {
+                                Err(Message::FoodIdNotFound.format(&[&id]))
+                            }
+                        })
+                        .ask()?;
+                    Ok(Some(Repair::Replace(id)))
+                }
+                2 => Ok(Some(Repair::Remove)),
+                _ => Ok(None),
+            }
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! # Importing From Other Apps
+//!
+//! This module supports `yada import` and the Import From Another App menu,
+//! which bring food diaries kept in other apps into YADA. The importers
+//! themselves live in `yada_core::importers` and are listed through its
+//! `ImporterFactory`.
+//!
+//! ## Output
+//!
+//! A preview of the foods created (the first few by name), the entries added
+//! and their date range, entries skipped because an earlier import already
+//! added them, rows that could not be imported, and created foods whose
+//! values look like typos (see `models::validation`). For a dry run the
+//! same preview is worded as what would happen.
+
+// src/cli/import.rs
+use yada_core::importers::diary::ImportReport;
+use yada_core::importers::{Importer, ImporterFactory};
+
+use crate::ui::i18n::Message;
+
+/// Food names listed in the preview before the rest are counted
+const LISTED_FOODS: usize = 10;
+
+/// Parses an importer name given on the command line
+pub fn parse_importer(value: &str) -> Result<String, String> {
+    let factory = ImporterFactory::new();
+    match factory.get_importer(value) {
+        Some(importer) => Ok(importer.name().to_string()),
+        None => {
+            let names: Vec<&str> = factory.get_all_importers().iter().map(|importer| importer.name()).collect();
+            Err(format!("unknown importer '{}', expected {}", value, names.join(", ")))
+        }
+    }
+}
+
+/// Formats an import report for the terminal
+///
+/// # Arguments
+/// * `importer` - Importer that read the export
+/// * `report` - Result of `Importer::import`
+/// * `dry_run` - Whether nothing is saved
+///
+/// # Returns
+/// The report as multiple lines of text
+pub fn describe(importer: &dyn Importer, report: &ImportReport, dry_run: bool) -> String {
+    let (create, add) = if dry_run {
+        (Message::WouldCreateFoods, Message::WouldAddEntries)
+    } else {
+        (Message::CreatedFoods, Message::AddedEntries)
+    };
+    let mut lines = vec![Message::ReadRows.format(&[&report.rows, &importer.app_name()])];
+
+    let listed = if report.new_foods.is_empty() { "." } else { ":" };
+    lines.push(create.format(&[&report.new_foods.len(), &listed]));
+    for name in report.new_foods.iter().take(LISTED_FOODS) {
+        lines.push(format!("  {}", name));
+    }
+    if report.new_foods.len() > LISTED_FOODS {
+        lines.push(format!("  {}", Message::AndMore.format(&[&(report.new_foods.len() - LISTED_FOODS)])));
+    }
+
+    let range = match report.dates {
+        Some((first, last)) if first == last => Message::OnDateSuffix.format(&[&first.format("%Y-%m-%d")]),
+        Some((first, last)) => Message::FromToSuffix.format(&[&first.format("%Y-%m-%d"), &last.format("%Y-%m-%d")]),
+        None => String::new(),
+    };
+    lines.push(add.format(&[&report.entries.added, &range]));
+    if report.entries.skipped > 0 {
+        lines.push(Message::SkippedImported.format(&[&report.entries.skipped]));
+    }
+
+    for error in report.foods.errors.iter().chain(&report.entries.errors) {
+        lines.push(format!("  {}", Message::NotImported.format(&[&error.message])));
+    }
+    for warning in &report.foods.warnings {
+        lines.push(format!("  {}", Message::CheckValue.format(&[&warning.message])));
+    }
+    lines.join("\n")
+}
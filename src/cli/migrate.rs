@@ -0,0 +1,84 @@
+//! # Data File Migration Report
+//!
+//! This module formats the result of `yada migrate`, which rewrites legacy
+//! data files in the canonical format (versioned header, sorted records,
+//! escaped delimiters) after backing up the originals. The migration itself
+//! lives in `yada_core::repositories::migration`.
+//!
+//! ## Output
+//!
+//! One line per data file with its outcome, followed by the format upgrade
+//! steps applied to its records and indented warnings
+//! about problems the migration found but cannot fix (e.g. log entries for
+//! foods that no longer exist).
+
+// src/cli/migrate.rs
+use std::path::Path;
+
+use yada_core::repositories::format::FORMAT_VERSION;
+use yada_core::repositories::migration::{FileMigration, MigrationReport, MigrationStatus};
+
+use crate::ui::i18n::Message;
+
+/// Formats a migration report for the terminal
+///
+/// # Arguments
+/// * `report` - Result of `migrate_data_files`
+///
+/// # Returns
+/// The report as multiple lines of text
+pub fn describe(report: &MigrationReport) -> String {
+    let mut lines: Vec<String> = report.files.iter().flat_map(describe_file).collect();
+
+    if !report.changed_anything() {
+        lines.push(Message::AllUpToDate.format(&[&FORMAT_VERSION]));
+    }
+    lines.join("\n")
+}
+
+/// Returns true if a file was left alone because a newer version wrote it
+pub fn has_unsupported(report: &MigrationReport) -> bool {
+    report.files.iter().any(|file| matches!(file.status, MigrationStatus::Unsupported(_)))
+}
+
+/// Formats the outcome and warnings for one data file
+fn describe_file(file: &FileMigration) -> Vec<String> {
+    let name = Path::new(&file.path)
+        .file_name()
+        .map_or(file.path.clone(), |name| name.to_string_lossy().into_owned());
+    let found = match file.found_version {
+        Some(version) => Message::FormatV.format(&[&version]),
+        None => Message::LegacyFormat.text().to_string(),
+    };
+    let action = if file.found_version == Some(FORMAT_VERSION) {
+        Message::RewrittenIn.format(&[&FORMAT_VERSION])
+    } else {
+        Message::MigratedFrom.format(&[&found, &FORMAT_VERSION])
+    };
+
+    let outcome = match &file.status {
+        MigrationStatus::Missing => Message::NothingToMigrate.text().to_string(),
+        MigrationStatus::UpToDate => Message::AlreadyFormat.format(&[&FORMAT_VERSION, &file.records]),
+        MigrationStatus::Migrated => {
+            let backup = file.backup_path.as_ref().map_or(String::new(), |backup| Message::OriginalSavedAs.format(&[backup]));
+            Message::MigratedRecords.format(&[&action, &file.records, &backup])
+        }
+        MigrationStatus::WouldMigrate => Message::WouldBeMigrated.format(&[&action, &file.records]),
+        MigrationStatus::Unsupported(version) => Message::NewerFormat.format(&[&version]),
+    };
+
+    let mut lines = vec![format!("{}: {}", name, outcome)];
+    if file.skipped_lines > 0 {
+        let fate = match file.status {
+            MigrationStatus::Migrated => Message::LinesDropped,
+            MigrationStatus::WouldMigrate => Message::LinesWouldDrop,
+            _ => Message::LinesIgnored,
+        };
+        lines.push(format!("  {}", Message::SkippedLines.format(&[&file.skipped_lines, &fate])));
+    }
+    if matches!(file.status, MigrationStatus::Migrated | MigrationStatus::WouldMigrate) {
+        lines.extend(file.upgrades.iter().map(|upgrade| format!("  {}", Message::UpgradeLine.format(&[upgrade]))));
+    }
+    lines.extend(file.warnings.iter().map(|warning| format!("  {}", Message::WarningLine.format(&[warning]))));
+    lines
+}
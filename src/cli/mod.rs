@@ -0,0 +1,325 @@
+//! # Command-Line Interface Module
+//!
+//! This module defines the non-interactive command-line surface of YADA using
+//! `clap`. Running `yada` without a subcommand starts the interactive menu as
+//! before; subcommands expose individual operations for shell usage and
+//! scripting.
+//!
+//! ## Subcommands
+//!
+//! - **log**: Record servings (or an amount such as `85g`) of a food without opening the menu,
+//!   optionally for a meal (`--meal lunch`)
+//! - **status**: Show consumed versus target calories for a day
+//! - **compare**: Show two or more foods side by side (per serving and per 100 g)
+//! - **charts**: Render monthly weight and calorie-intake PNG charts into the reports directory
+//! - **review**: Write a year-in-review report (Markdown or HTML) into the reports directory
+//! - **weekly-report**: Write a report of one week (calories per day, weights, top foods)
+//!   as Markdown or HTML into the reports directory
+//! - **energy-balance**: Write weekly intake against weekly weight change, with the
+//!   adaptive TDEE next to the formula-based one, as Markdown or HTML
+//! - **health-export**: Write weights and nutrition for Apple Health (XML) or
+//!   Google Fit (CSV) into the reports directory
+//! - **migrate**: Rewrite legacy data files in the canonical format (originals are backed up)
+//! - **doctor**: Find log entries and recipes using missing foods and impossible daily
+//!   profiles, and optionally repair (`--repair`) or remove (`--prune`) them
+//! - **import**: Create foods and log entries from a MyFitnessPal (`mfp`),
+//!   Cronometer, or Lose It! (`loseit`) export, previewing them without
+//!   saving with `--dry-run`
+//! - **pack**: Install a food pack (`.yadapack`) of branded foods, list the
+//!   installed packs, or uninstall one, keeping its foods that are still used
+//! - **remind**: Print the reminders that are due (for cron), or add, list,
+//!   or remove reminders
+//! - **sync**: Copy the data files to and from the folder, WebDAV server, or S3
+//!   bucket named by `YADA_SYNC`, reporting files changed on both sides
+//! - **completions**: Print a bash, zsh, or fish completion script
+//!
+//! ## Scripting Support
+//!
+//! - **--data-dir**: Uses a specific data directory (see `config.rs`)
+//! - **--quiet**: Suppresses normal output; errors are still written to stderr
+//! - **--profile-startup**: Reports the time spent loading each data file to
+//!   stderr (interactive menu and `log` only)
+//! - **--strict**: Stops with an error instead of skipping damaged lines in
+//!   the data files (interactive menu and `log` only)
+//! - **--no-color**: Prints without ANSI colors or bold text; `NO_COLOR` does
+//!   the same, and output that is not a terminal is never colored (see `ui::style`)
+//! - **Exit codes**: `0` on success, `1` when `status --check` finds the day
+//!   over budget, `doctor` leaves problems unrepaired, or `sync` leaves
+//!   conflicts, `2` on errors
+//!   (unknown food, missing profile, I/O failures)
+//!
+//! ## Hidden Flags
+//!
+//! - **--list-food-ids**: Print every food ID, one per line. Completion scripts
+//!   call this at completion time so food IDs always reflect the current
+//!   food database.
+//!
+//! ## Module Organization
+//!
+//! - `completions`: Completion script generation with dynamic food-ID completion
+//! - `status`: Daily calorie status used by the `status` subcommand
+//! - `migrate`: Report formatting for the `migrate` subcommand
+//! - `doctor`: Problem report and repair prompts for the `doctor` subcommand
+//! - `profiling`: Startup phase timings for `--profile-startup`
+//! - `sync`: File list and report formatting for the `sync` subcommand
+//! - `import`: Importer names and the preview for the `import` subcommand
+//! - `pack`: Actions and reports of the `pack` subcommand
+//! - `remind`: Actions and reports of the `remind` subcommand
+
+// src/cli/mod.rs
+pub mod completions;
+pub mod status;
+pub mod migrate;
+pub mod doctor;
+pub mod profiling;
+pub mod sync;
+pub mod import;
+pub mod pack;
+pub mod remind;
+
+use std::path::PathBuf;
+use clap::{Parser, Subcommand};
+use chrono::NaiveDate;
+use yada_core::models::meal::MealSlot;
+use yada_core::models::quantity::Quantity;
+use yada_core::sync::Side;
+
+use completions::CompletionShell;
+use pack::PackAction;
+use remind::RemindAction;
+use crate::reports::document::ReportFormat;
+use crate::reports::energy_balance::DEFAULT_WEEKS;
+use crate::reports::health_export::HealthFormat;
+
+/// Exit code for successful runs (and `status --check` within budget)
+pub const EXIT_OK: u8 = 0;
+
+/// Exit code for `status --check` when the day is over the calorie budget
+pub const EXIT_OVER_BUDGET: u8 = 1;
+
+/// Exit code for `doctor` when problems remain after the run
+pub const EXIT_PROBLEMS_FOUND: u8 = 1;
+
+/// Exit code for `sync` when files changed on both sides were left unchanged
+pub const EXIT_SYNC_CONFLICT: u8 = 1;
+
+/// Exit code for errors such as unknown foods, missing profiles, or I/O failures
+pub const EXIT_ERROR: u8 = 2;
+
+/// Top-level command-line arguments
+#[derive(Parser, Debug)]
+#[command(name = "yada", version, about = "YADA - Yet Another Diet Assistant")]
+pub struct Cli {
+    /// Subcommand to run; starts the interactive menu when omitted
+    #[command(subcommand)]
+    pub command: Option<CliCommand>,
+
+    /// Directory containing the data files (overrides YADA_DATA_DIR and the config file)
+    #[arg(long, global = true, value_name = "DIR", value_hint = clap::ValueHint::DirPath)]
+    pub data_dir: Option<PathBuf>,
+
+    /// User whose profile and logs to use (overrides YADA_USER; "default" for the original data)
+    #[arg(long, global = true, value_name = "NAME")]
+    pub user: Option<String>,
+
+    /// Suppress normal output (errors are still printed to stderr)
+    #[arg(short, long, global = true)]
+    pub quiet: bool,
+
+    /// Report the time spent on each startup phase (repository loads, seeding) to stderr
+    #[arg(long, global = true)]
+    pub profile_startup: bool,
+
+    /// Print without colors or bold text (as does setting NO_COLOR)
+    #[arg(long, global = true)]
+    pub no_color: bool,
+
+    /// Refuse to start when a data file has lines that cannot be read (see YADA_STRICT_LOAD)
+    #[arg(long, global = true)]
+    pub strict: bool,
+
+    /// Print all food IDs, one per line (used by completion scripts)
+    #[arg(long = completions::LIST_FOOD_IDS_FLAG, hide = true)]
+    pub list_food_ids: bool,
+}
+
+/// Subcommands available from the shell
+#[derive(Subcommand, Debug)]
+pub enum CliCommand {
+    /// Log servings of a food (defaults to today)
+    Log {
+        /// ID of the food to log
+        food_id: String,
+
+        /// Servings consumed, or an amount with a unit such as 85g or "250 ml"
+        #[arg(default_value = "1", value_parser = Quantity::parse)]
+        amount: Quantity,
+
+        /// Date to log for (YYYY-MM-DD)
+        #[arg(long)]
+        date: Option<NaiveDate>,
+
+        /// Meal the entry belongs to (breakfast, lunch, dinner, or snack)
+        #[arg(long, value_parser = parse_meal)]
+        meal: Option<MealSlot>,
+    },
+
+    /// Show consumed versus target calories for a day (defaults to today)
+    Status {
+        /// Date to report on (YYYY-MM-DD)
+        #[arg(long)]
+        date: Option<NaiveDate>,
+
+        /// Exit with code 1 when over the calorie budget
+        #[arg(long)]
+        check: bool,
+
+        /// Print a single line for status bars (e.g. "1450/2100 kcal, 92 g protein")
+        #[arg(long)]
+        short: bool,
+    },
+
+    /// Compare two or more foods side by side (per serving and per 100 g)
+    Compare {
+        /// IDs of the foods to compare
+        #[arg(required = true, num_args = 2..)]
+        food_ids: Vec<String>,
+    },
+
+    /// Render weight and calorie-intake charts for a month as PNG images
+    Charts {
+        /// Month to chart (YYYY-MM); defaults to the current month
+        #[arg(long, value_parser = parse_month)]
+        month: Option<NaiveDate>,
+    },
+
+    /// Write a year-in-review report (days logged, weight change, streaks, adherence)
+    Review {
+        /// Year to summarize; defaults to the current year
+        #[arg(long)]
+        year: Option<i32>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "markdown")]
+        format: ReportFormat,
+    },
+
+    /// Write a weekly report (calories per day, weights, top foods by calories)
+    WeeklyReport {
+        /// Any day of the week to report on (YYYY-MM-DD); defaults to the current week
+        #[arg(long)]
+        week: Option<NaiveDate>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "markdown")]
+        format: ReportFormat,
+    },
+
+    /// Write weekly intake against weight change, with the adaptive TDEE next to the formula-based one
+    EnergyBalance {
+        /// Number of weeks to cover, counting the week of --to
+        #[arg(long, default_value_t = DEFAULT_WEEKS, value_parser = clap::value_parser!(u32).range(1..))]
+        weeks: u32,
+
+        /// Last day to cover (YYYY-MM-DD); defaults to today
+        #[arg(long)]
+        to: Option<NaiveDate>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "markdown")]
+        format: ReportFormat,
+    },
+
+    /// Export weights and nutrition for Apple Health (XML) or Google Fit (CSV)
+    HealthExport {
+        /// Health app format to write
+        #[arg(long, value_enum)]
+        format: HealthFormat,
+
+        /// First day to export (YYYY-MM-DD); defaults to the first day with data
+        #[arg(long)]
+        from: Option<NaiveDate>,
+
+        /// Last day to export (YYYY-MM-DD); defaults to the last day with data
+        #[arg(long)]
+        to: Option<NaiveDate>,
+    },
+
+    /// Validate data files and rewrite them in the current format, backing up the originals
+    Migrate {
+        /// Only report what would change; write nothing
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Also rewrite files that already use the current format
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Check that logs and recipes only use existing foods and that daily profiles are possible
+    Doctor {
+        /// Ask how to repair each problem
+        #[arg(long)]
+        repair: bool,
+
+        /// Remove every broken log entry, component, and daily profile without asking
+        #[arg(long, conflicts_with = "repair")]
+        prune: bool,
+    },
+
+    /// Import foods and log entries from another app's export
+    Import {
+        /// Importer for the app the export comes from (mfp, cronometer, or loseit)
+        #[arg(value_parser = import::parse_importer)]
+        importer: String,
+
+        /// CSV file exported from the app
+        #[arg(value_hint = clap::ValueHint::FilePath)]
+        file: PathBuf,
+
+        /// Only show what would be imported; write nothing
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Install, list, or uninstall food packs of branded foods such as restaurant menus
+    Pack {
+        #[command(subcommand)]
+        action: PackAction,
+    },
+
+    /// Print the reminders that are due (for cron), or add, list, or remove reminders
+    Remind {
+        #[command(subcommand)]
+        action: Option<RemindAction>,
+    },
+
+    /// Synchronize the data files with the backend named by YADA_SYNC (folder:, webdav:, or s3:)
+    Sync {
+        /// Copy to use for files changed on both sides (local or remote)
+        #[arg(long, value_parser = sync::parse_side)]
+        prefer: Option<Side>,
+
+        /// Only report what would be copied; write nothing
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Print a shell completion script to standard output
+    Completions {
+        /// Shell to generate the script for
+        shell: CompletionShell,
+    },
+}
+
+/// Parses a meal slot name such as `lunch`
+fn parse_meal(value: &str) -> Result<MealSlot, String> {
+    MealSlot::parse(value)
+        .ok_or_else(|| format!("invalid meal '{}', expected breakfast, lunch, dinner, or snack", value))
+}
+
+/// Parses a `YYYY-MM` month into its first day
+fn parse_month(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(&format!("{}-01", value.trim()), "%Y-%m-%d")
+        .map_err(|_| format!("invalid month '{}', expected YYYY-MM", value))
+}
@@ -0,0 +1,86 @@
+//! # Food Pack Commands
+//!
+//! This module supports `yada pack`, which installs, lists, and uninstalls
+//! food packs: curated files of branded foods such as a restaurant's menu
+//! (see `yada_core::repositories::food_pack`).
+//!
+//! ## Output
+//!
+//! - **install**: How many foods were added and updated, and the IDs that
+//!   were skipped because another food already uses them
+//! - **list**: One line per installed pack with its number of foods
+//! - **uninstall**: How many foods were removed, and the foods kept because
+//!   they are still logged, planned, or part of a recipe
+
+// src/cli/pack.rs
+use std::path::PathBuf;
+
+use clap::Subcommand;
+use yada_core::repositories::food_pack::{FoodPack, InstallReport, UninstallReport};
+
+use crate::ui::i18n::Message;
+
+/// Food IDs listed in a report before the rest are counted
+const LISTED_FOODS: usize = 10;
+
+/// What to do with food packs
+#[derive(Subcommand, Debug)]
+pub enum PackAction {
+    /// Install a food pack file (.yadapack), or update an installed pack
+    Install {
+        /// Pack file to install
+        #[arg(value_hint = clap::ValueHint::FilePath)]
+        file: PathBuf,
+    },
+
+    /// List the installed food packs
+    List,
+
+    /// Remove an installed pack's foods, keeping those still in use
+    Uninstall {
+        /// Name of the pack (as shown by `yada pack list`)
+        name: String,
+    },
+}
+
+/// Formats the result of installing a pack
+pub fn describe_install(pack: &FoodPack, report: &InstallReport) -> String {
+    let version = pack.version.as_deref().map(|version| format!(" {}", version)).unwrap_or_default();
+    let mut lines = vec![Message::PackInstalled.format(&[&pack.title, &version, &pack.name, &report.added, &report.updated])];
+    if !report.conflicts.is_empty() {
+        lines.push(Message::PackConflicts.format(&[&report.conflicts.len()]));
+        lines.extend(list(&report.conflicts));
+    }
+    lines.join("\n")
+}
+
+/// Formats the installed packs
+pub fn describe_installed(packs: &[(String, usize)]) -> String {
+    if packs.is_empty() {
+        return Message::NoPacks.text().to_string();
+    }
+    packs.iter()
+        .map(|(name, count)| format!("{:<16} {}", name, Message::FoodCount.format(&[count])))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Formats the result of uninstalling a pack
+pub fn describe_uninstall(name: &str, report: &UninstallReport) -> String {
+    let mut lines = vec![Message::PackRemoved.format(&[&report.removed.len(), &name])];
+    if !report.kept.is_empty() {
+        lines.push(Message::PackKept.format(&[&report.kept.len()]));
+        lines.extend(list(&report.kept));
+        lines.push(Message::PackUninstallAgain.text().to_string());
+    }
+    lines.join("\n")
+}
+
+/// Indented food IDs, the first `LISTED_FOODS` of them, then how many more
+fn list(ids: &[String]) -> Vec<String> {
+    let mut lines: Vec<String> = ids.iter().take(LISTED_FOODS).map(|id| format!("  {}", id)).collect();
+    if ids.len() > LISTED_FOODS {
+        lines.push(format!("  {}", Message::AndMore.format(&[&(ids.len() - LISTED_FOODS)])));
+    }
+    lines
+}
@@ -0,0 +1,79 @@
+//! # Startup Profiling
+//!
+//! Backs the global `--profile-startup` flag. Each phase of application
+//! startup (loading a data file, seeding, usage sync) is timed and, when the
+//! flag is given, a table of the timings is written to stderr so it never
+//! mixes with scripted output on stdout.
+//!
+//! ## What Is Measured
+//!
+//! Only work done before the first prompt (or before `yada log` records its
+//! entry) is listed. Optional subsystems such as the external food sources
+//! are created lazily on first use and therefore do not appear here.
+
+// src/cli/profiling.rs
+use std::time::{Duration, Instant};
+
+/// Timings for the phases of application startup
+#[derive(Debug, Default)]
+pub struct StartupProfile {
+    /// Phase labels and durations, in the order they ran
+    phases: Vec<(String, Duration)>,
+}
+
+impl StartupProfile {
+    /// Creates an empty profile
+    pub fn new() -> Self {
+        StartupProfile::default()
+    }
+
+    /// Runs a phase of startup and records how long it took
+    ///
+    /// # Arguments
+    /// * `label` - Name of the phase, e.g. `"foods.txt"`
+    /// * `phase` - The work to time
+    ///
+    /// # Returns
+    /// * Whatever `phase` returns
+    pub fn time<T>(&mut self, label: &str, phase: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = phase();
+        self.phases.push((label.to_string(), start.elapsed()));
+        result
+    }
+
+    /// Appends detail to the most recently recorded phase, e.g. a record count
+    pub fn annotate_last(&mut self, detail: &str) {
+        if let Some((label, _)) = self.phases.last_mut() {
+            label.push_str(&format!(" ({})", detail));
+        }
+    }
+
+    /// Returns the total time across all phases
+    pub fn total(&self) -> Duration {
+        self.phases.iter().map(|(_, duration)| *duration).sum()
+    }
+
+    /// Formats the timings as a table with each phase's share of the total
+    pub fn report(&self) -> String {
+        let total = self.total();
+        let width = self.phases.iter().map(|(label, _)| label.len()).max().unwrap_or(0).max(5);
+
+        let mut lines = vec!["Startup profile:".to_string()];
+        for (label, duration) in &self.phases {
+            let share = if total.is_zero() {
+                0.0
+            } else {
+                duration.as_secs_f64() / total.as_secs_f64() * 100.0
+            };
+            lines.push(format!("  {:<width$}  {:>9.3} ms  {:>5.1}%", label, millis(*duration), share, width = width));
+        }
+        lines.push(format!("  {:<width$}  {:>9.3} ms", "Total", millis(total), width = width));
+        lines.join("\n")
+    }
+}
+
+/// Converts a duration into fractional milliseconds
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
@@ -0,0 +1,87 @@
+//! # Reminder Commands
+//!
+//! This module supports `yada remind`, which adds, lists, and removes
+//! reminders (see `yada_core::models::reminder`) and, without an action,
+//! prints the reminders that are due. Running it from cron every few
+//! minutes shows reminders without the menu open (cron mails the output):
+//!
+//! ```text
+//! */10 * * * * yada remind
+//! ```
+//!
+//! ## Output
+//!
+//! - **(no action)**: One line per due reminder, or nothing, so cron only
+//!   mails when there is something to say. Each reminder is printed once a
+//!   day; while the menu runs it shows them itself and this prints nothing
+//! - **add**: The new reminder with its ID
+//! - **list**: One line per reminder with its ID, schedule, and check
+//! - **remove**: The removed reminder
+
+// src/cli/remind.rs
+use chrono::NaiveTime;
+use clap::Subcommand;
+use yada_core::models::reminder::{self, Reminder, ReminderCheck};
+
+use crate::ui::i18n::Message;
+
+/// What to do with reminders
+#[derive(Subcommand, Debug)]
+pub enum RemindAction {
+    /// Add a reminder shown at a time of day
+    Add {
+        /// Time of day the reminder comes due (HH:MM)
+        #[arg(value_parser = reminder::parse_time)]
+        time: NaiveTime,
+
+        /// Text shown to the user
+        message: String,
+
+        /// Days it applies to: daily, weekdays, weekends, or names such as mon,wed,fri
+        #[arg(long, default_value = "daily")]
+        days: String,
+
+        /// Skip the reminder once it is done for the day: a meal (lunch, ...) or weigh-in
+        #[arg(long, value_parser = parse_check)]
+        check: Option<ReminderCheck>,
+    },
+
+    /// List the reminders
+    List,
+
+    /// Remove a reminder
+    Remove {
+        /// ID of the reminder (as shown by `yada remind list`)
+        id: u32,
+    },
+}
+
+/// Parses the value of `--check`
+fn parse_check(value: &str) -> Result<ReminderCheck, String> {
+    ReminderCheck::parse(value)
+        .ok_or_else(|| format!("invalid check '{}', expected breakfast, lunch, dinner, snack, or weigh-in", value))
+}
+
+/// Formats the due reminders, one per line
+pub fn describe_due(due: &[Reminder]) -> String {
+    due.iter()
+        .map(|reminder| Message::ReminderDue.format(&[&reminder.time.format("%H:%M"), &reminder.message]))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Formats one reminder with its ID and check
+fn describe_reminder(reminder: &Reminder) -> String {
+    match reminder.check {
+        Some(check) => format!("{:>3}  {}", reminder.id, Message::ReminderUntil.format(&[reminder, &check.key()])),
+        None => format!("{:>3}  {}", reminder.id, reminder),
+    }
+}
+
+/// Formats all reminders
+pub fn describe_reminders(reminders: &[Reminder]) -> String {
+    if reminders.is_empty() {
+        return Message::NoReminders.text().to_string();
+    }
+    reminders.iter().map(describe_reminder).collect::<Vec<_>>().join("\n")
+}
@@ -0,0 +1,150 @@
+//! # Daily Status
+//!
+//! This module computes the calorie status for a single day (consumed versus
+//! target) for the `yada status` subcommand. It reads the repositories
+//! directly and never prompts, so it is safe to call from shell scripts.
+//!
+//! ## Output Formats
+//!
+//! - **Report** (default): A few labelled lines with consumed, target, and remaining calories
+//!   (green when within the budget and red when over it, on a terminal; see `ui::style`)
+//! - **Short** (`--short`): One line for tmux/i3 status bars, e.g. `1450/2100 kcal, 92 g protein`
+//!
+//! When the profile lacks a height or birth date, the target is computed from
+//! population defaults; the report says so, and the short line marks the
+//! target with `~` (e.g. `1450/~2100 kcal`).
+//!
+//! Only the three data files are read; no seeding, command history, or
+//! interactive state is set up, so the command stays fast enough to run from
+//! a status bar every few seconds.
+//!
+//! ## Exit Codes
+//!
+//! With `--check`, the status is also reported through the process exit code:
+//! - `0`: Consumption is within the calorie budget
+//! - `1`: Consumption is over the calorie budget
+//! - `2`: The status could not be computed (missing profile, unreadable data)
+
+// src/cli/status.rs
+use chrono::NaiveDate;
+
+use yada_core::models::food::Macros;
+use yada_core::repositories::food_repository::FoodRepository;
+use yada_core::repositories::log_repository::LogRepository;
+use yada_core::repositories::exercise_repository::ExerciseRepository;
+use yada_core::repositories::profile_repository::ProfileRepository;
+use yada_core::strategies::calorie_calculator::CalorieCalculatorFactory;
+use yada_core::strategies::target_modifier::TargetPipeline;
+
+use crate::ui::i18n::Message;
+use crate::ui::style;
+
+/// Calorie status for one day
+#[derive(Debug, Clone, PartialEq)]
+pub struct DailyStatus {
+    /// The day this status describes
+    pub date: NaiveDate,
+
+    /// Calories consumed according to the food log
+    pub consumed: f64,
+
+    /// Target calories according to the profile's calculation method, with the
+    /// exercise credit when it is enabled
+    pub target: f64,
+
+    /// Macronutrients consumed according to the food log
+    pub macros: Macros,
+
+    /// "Estimate only" notice when the target uses population defaults
+    pub estimate_notice: Option<String>,
+}
+
+impl DailyStatus {
+    /// Computes the status for a date from the repositories
+    ///
+    /// # Arguments
+    /// * `food_repo` - Food database used to resolve logged food IDs
+    /// * `log_repo` - Food consumption logs
+    /// * `profile_repo` - User profile with daily weight and activity level
+    /// * `exercise_repo` - Exercise log, for the exercise credit of the target
+    /// * `date` - The day to compute the status for
+    ///
+    /// # Returns
+    /// * `Ok(DailyStatus)` - Consumed and target calories for the day
+    /// * `Err(String)` - If there is no profile or no daily profile on or before the date
+    pub fn compute(
+        food_repo: &FoodRepository,
+        log_repo: &LogRepository,
+        profile_repo: &ProfileRepository,
+        exercise_repo: &ExerciseRepository,
+        date: NaiveDate,
+    ) -> Result<Self, String> {
+        let mut profile = profile_repo.get_profile()
+            .ok_or(Message::NoProfileExists.text())?
+            .clone();
+
+        // Like the interactive menu, a day without data uses the latest earlier values
+        // (only in memory; the interactive menu asks the user to confirm them)
+        profile.carry_forward(date);
+        if profile.get_daily_profile(date).is_none() {
+            return Err(Message::NoDailyProfileBefore.format(&[&date.format("%Y-%m-%d")]));
+        }
+        let profile = &profile;
+
+        let factory = CalorieCalculatorFactory::from_repositories(food_repo, log_repo);
+        let calculator = factory.calculator_for(profile, date)
+            .ok_or(Message::NoCalculator.text())?;
+
+        let foods = food_repo.get_foods();
+        let (consumed, macros) = log_repo.get_log(date)
+            .map_or((0.0, Macros::default()), |log| (log.total_calories(foods), log.total_macros(foods)));
+
+        Ok(DailyStatus {
+            date,
+            consumed,
+            macros,
+            target: calculator.calculate_target_with(profile, date, &TargetPipeline::standard().with_exercise_credit(exercise_repo)).target,
+            estimate_notice: profile.body_metrics(date).estimate_notice(),
+        })
+    }
+
+    /// Returns true when more calories were consumed than the target allows
+    pub fn is_over_budget(&self) -> bool {
+        self.consumed > self.target
+    }
+
+    /// Formats the status as a single line for terminal status bars
+    ///
+    /// # Examples
+    /// ```ignore
+    /// assert_eq!(status.short_line(), "1450/2100 kcal, 92 g protein");
+    /// ```
+    pub fn short_line(&self) -> String {
+        let marker = if self.estimate_notice.is_some() { "~" } else { "" };
+        Message::StatusShortLine.format(&[&self.consumed, &marker, &self.target, &self.macros.protein])
+    }
+
+    /// Formats the status as a short multi-line report
+    pub fn report(&self) -> String {
+        let difference = self.consumed - self.target;
+        let balance = format!(
+            "{}: {:.1} kcal",
+            if difference > 0.0 { Message::OverBudgetBy.text() } else { Message::RemainingLabel.text() },
+            difference.abs()
+        );
+        let mut report = Message::StatusReport.format(&[
+            &self.date.format("%Y-%m-%d"),
+            &self.consumed,
+            &self.target,
+            &style::against_target(balance, self.consumed, self.target),
+            &self.macros.protein,
+            &self.macros.carbs,
+            &self.macros.fat,
+        ]);
+        if let Some(notice) = &self.estimate_notice {
+            report.push('\n');
+            report.push_str(notice);
+        }
+        report
+    }
+}
@@ -0,0 +1,66 @@
+//! # Data File Sync
+//!
+//! This module supports `yada sync`, which copies the data files to and from
+//! the backend named by `YADA_SYNC` (see `yada_core::sync`): it lists the
+//! files to synchronize and formats the result.
+//!
+//! ## Output
+//!
+//! One line per data file with what happened to it, followed by a hint on
+//! resolving conflicts with `--prefer` when there are any.
+
+// src/cli/sync.rs
+use std::path::Path;
+
+use yada_core::sync::{Side, SyncAction, SyncReport};
+
+use crate::config::AppConfig;
+use crate::ui::i18n::Message;
+
+/// Names of the data files (see `AppConfig::data_files`), relative to the data directory
+pub fn data_file_names(config: &AppConfig) -> Vec<String> {
+    config.data_files()
+        .iter()
+        .filter_map(|file| Path::new(&file.path).strip_prefix(&config.data_dir).ok())
+        .map(|relative| {
+            relative.components().map(|part| part.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/")
+        })
+        .collect()
+}
+
+/// Parses the value of `--prefer`
+pub fn parse_side(value: &str) -> Result<Side, String> {
+    match value.trim().to_lowercase().as_str() {
+        "local" => Ok(Side::Local),
+        "remote" => Ok(Side::Remote),
+        _ => Err(format!("invalid side '{}', expected local or remote", value)),
+    }
+}
+
+/// Formats a sync report for the terminal
+///
+/// # Arguments
+/// * `report` - Result of `sync_files`
+///
+/// # Returns
+/// The report as multiple lines of text
+pub fn describe(report: &SyncReport) -> String {
+    let heading = if report.dry_run { Message::SyncDryRun } else { Message::Synced };
+    let mut lines = vec![Message::SyncedWith.format(&[&heading, &report.backend])];
+    for file in &report.files {
+        let outcome = match (file.action, report.dry_run) {
+            (SyncAction::UpToDate, _) => Message::UpToDate.text(),
+            (SyncAction::Uploaded, false) => Message::Uploaded.text(),
+            (SyncAction::Uploaded, true) => Message::WouldUpload.text(),
+            (SyncAction::Downloaded, false) => Message::Downloaded.text(),
+            (SyncAction::Downloaded, true) => Message::WouldDownload.text(),
+            (SyncAction::Conflict, _) => Message::SyncConflict.text(),
+            (SyncAction::Missing, _) => Message::NotOnEitherSide.text(),
+        };
+        lines.push(format!("  {}: {}", file.name, outcome));
+    }
+    if !report.conflicts().is_empty() {
+        lines.push(Message::SyncConflictHint.to_string());
+    }
+    lines.join("\n")
+}
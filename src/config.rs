@@ -0,0 +1,432 @@
+//! # Application Configuration
+//!
+//! This module decides where YADA keeps its data files. Instead of reading
+//! and writing `foods.txt`, `logs.txt`, and `profile.txt` in whatever
+//! directory the program was started from, all repositories are created from
+//! the paths in an `AppConfig`.
+//!
+//! ## Data Directory Resolution
+//!
+//! The first source that provides a value wins:
+//! 1. **Command line**: `--data-dir <DIR>`
+//! 2. **Environment**: `YADA_DATA_DIR`
+//! 3. **Config file**: `data_dir = <DIR>` in the config file (see below)
+//! 4. **Legacy location**: The current directory, if it already contains YADA
+//!    data files and the platform directory does not exist yet
+//! 5. **Platform default**:
+//!    - Linux/BSD: `$XDG_DATA_HOME/yada` or `~/.local/share/yada`
+//!    - macOS: `~/Library/Application Support/yada`
+//!    - Windows: `%APPDATA%\yada`
+//!
+//! ## Config File
+//!
+//! The config file lives at `$XDG_CONFIG_HOME/yada/config` (or
+//! `~/.config/yada/config`; `%APPDATA%\yada\config` on Windows) and uses
+//! simple `key = value` lines. Blank lines and lines starting with `#` are
+//! ignored, and a leading `~/` in paths is expanded to the home directory.
+//!
+//! ```text
+//! # ~/.config/yada/config
+//! data_dir = ~/Documents/yada
+//! ```
+//!
+//! ## Users
+//!
+//! Several people can share one data directory. The food database and
+//! settings are shared, but each user has their own profile, logs,
+//! exercise, meal plans, meal templates, and reports:
+//! - **Default user**: Files directly in the data directory, as before
+//!   users existed
+//! - **Named users**: Files in `users/<name>/` inside the data directory
+//!
+//! The user is chosen with `--user <NAME>` or `YADA_USER` (`default` selects
+//! the default user); otherwise the interactive menu asks at startup once
+//! named users exist.
+
+// src/config.rs
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use yada_core::repositories::bundle::BundleEntry;
+use yada_core::repositories::load_report::LoadMode;
+
+use crate::ui::i18n::Message;
+
+/// Environment variable overriding the data directory
+pub const DATA_DIR_ENV_VAR: &str = "YADA_DATA_DIR";
+
+/// Environment variable choosing the user
+pub const USER_ENV_VAR: &str = "YADA_USER";
+
+/// Name of the user whose files are directly in the data directory
+pub const DEFAULT_USER: &str = "default";
+
+/// Longest accepted user name
+const MAX_USER_NAME_LEN: usize = 32;
+
+/// Application directory name under the platform data/config directories
+const APP_DIR_NAME: &str = "yada";
+
+/// File names of the data files inside the data directory
+const FOODS_FILE: &str = "foods.txt";
+const LOGS_FILE: &str = "logs.txt";
+const PROFILE_FILE: &str = "profile.txt";
+const EXERCISE_FILE: &str = "exercise.txt";
+const MEAL_PLANS_FILE: &str = "meal_plans.txt";
+const MEAL_TEMPLATES_FILE: &str = "meal_templates.txt";
+const SETTINGS_FILE: &str = "settings.txt";
+
+/// File name of the append-only record of executed and undone commands
+const AUDIT_LOG_FILE: &str = "audit.log";
+
+/// Directory inside the data directory that receives generated reports
+const REPORTS_DIR: &str = "reports";
+
+/// Directories inside the data directory with food source plugins and the foods they returned
+const PLUGINS_DIR: &str = "plugins";
+const FOOD_CACHE_DIR: &str = "food_cache";
+
+/// Directory inside the data directory holding one directory per named user
+const USERS_DIR: &str = "users";
+
+/// Where the data directory setting came from
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataDirSource {
+    /// `--data-dir` command-line flag
+    CommandLine,
+
+    /// `YADA_DATA_DIR` environment variable
+    Environment,
+
+    /// `data_dir` entry in the config file
+    ConfigFile,
+
+    /// Existing data files in the current directory (pre-configuration layout)
+    Legacy,
+
+    /// Platform-specific default location
+    PlatformDefault,
+}
+
+/// Resolved application configuration
+#[derive(Debug, Clone)]
+pub struct AppConfig {
+    /// Directory containing the data files
+    pub data_dir: PathBuf,
+
+    /// Which source provided `data_dir`
+    pub data_dir_source: DataDirSource,
+
+    /// Selected named user, or `None` for the default user
+    pub user: Option<String>,
+
+    /// Whether damaged data files stop the interactive menu from starting
+    /// (`YADA_STRICT_LOAD` or `--strict`)
+    pub load_mode: LoadMode,
+}
+
+impl AppConfig {
+    /// Resolves the configuration from all sources in precedence order
+    ///
+    /// # Arguments
+    /// * `cli_data_dir` - Value of the `--data-dir` flag, if given
+    ///
+    /// # Returns
+    /// * `Ok(AppConfig)` - The resolved configuration
+    /// * `Err(String)` - If the config file is unreadable or no data directory can be determined
+    pub fn resolve(cli_data_dir: Option<PathBuf>) -> Result<Self, String> {
+        if let Some(dir) = cli_data_dir {
+            return Ok(Self::with_data_dir(dir, DataDirSource::CommandLine));
+        }
+
+        if let Some(dir) = env::var_os(DATA_DIR_ENV_VAR).filter(|v| !v.is_empty()) {
+            return Ok(Self::with_data_dir(PathBuf::from(dir), DataDirSource::Environment));
+        }
+
+        let file_settings = match config_file_path() {
+            Some(path) if path.exists() => read_config_file(&path)?,
+            _ => HashMap::new(),
+        };
+        if let Some(dir) = file_settings.get("data_dir") {
+            return Ok(Self::with_data_dir(expand_home(dir), DataDirSource::ConfigFile));
+        }
+
+        let default_dir = platform_data_dir()
+            .ok_or(Message::NoDataDir.text())?;
+
+        // Keep using data files created before the data directory existed
+        if !default_dir.exists() && has_data_files(Path::new(".")) {
+            return Ok(Self::with_data_dir(PathBuf::from("."), DataDirSource::Legacy));
+        }
+
+        Ok(Self::with_data_dir(default_dir, DataDirSource::PlatformDefault))
+    }
+
+    /// Creates a configuration for an explicit data directory
+    pub fn with_data_dir(data_dir: PathBuf, source: DataDirSource) -> Self {
+        AppConfig {
+            data_dir,
+            data_dir_source: source,
+            user: None,
+            load_mode: LoadMode::from_env(),
+        }
+    }
+
+    /// Selects the user whose files are used
+    ///
+    /// # Arguments
+    /// * `user` - A user name; `default` (any case) selects the default user
+    ///
+    /// # Returns
+    /// * `Ok(())` - The user was selected (the directory is created by `ensure_data_dir`)
+    /// * `Err(String)` - If the name is not a valid user name
+    pub fn select_user(&mut self, user: &str) -> Result<(), String> {
+        let user = user.trim();
+        if user.eq_ignore_ascii_case(DEFAULT_USER) {
+            self.user = None;
+            return Ok(());
+        }
+        validate_user_name(user)?;
+        self.user = Some(user.to_string());
+        Ok(())
+    }
+
+    /// Name of the selected user (`default` for the default user)
+    pub fn user_name(&self) -> &str {
+        self.user.as_deref().unwrap_or(DEFAULT_USER)
+    }
+
+    /// Returns the named users that have a directory, sorted by name
+    pub fn users(&self) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(self.data_dir.join(USERS_DIR)) else {
+            return Vec::new();
+        };
+        let mut users: Vec<String> = entries
+            .filter_map(Result::ok)
+            .filter(|entry| entry.path().is_dir())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter(|name| validate_user_name(name).is_ok())
+            .collect();
+        users.sort();
+        users
+    }
+
+    /// Creates the data directory and the selected user's directory if needed
+    pub fn ensure_data_dir(&self) -> Result<(), io::Error> {
+        fs::create_dir_all(self.user_dir())
+    }
+
+    /// Directory with the selected user's files
+    pub fn user_dir(&self) -> PathBuf {
+        match &self.user {
+            Some(user) => self.data_dir.join(USERS_DIR).join(user),
+            None => self.data_dir.clone(),
+        }
+    }
+
+    /// Path of the food database file (shared by all users)
+    pub fn foods_path(&self) -> String {
+        path_string(self.data_dir.join(FOODS_FILE))
+    }
+
+    /// Path of the settings file with reminders (shared by all users)
+    pub fn settings_path(&self) -> String {
+        path_string(self.data_dir.join(SETTINGS_FILE))
+    }
+
+    /// Path of the selected user's food log file
+    pub fn logs_path(&self) -> String {
+        self.user_file_path(LOGS_FILE)
+    }
+
+    /// Path of the selected user's profile file
+    pub fn profile_path(&self) -> String {
+        self.user_file_path(PROFILE_FILE)
+    }
+
+    /// Path of the selected user's exercise log file
+    pub fn exercise_path(&self) -> String {
+        self.user_file_path(EXERCISE_FILE)
+    }
+
+    /// Path of the selected user's meal plan file
+    pub fn meal_plans_path(&self) -> String {
+        self.user_file_path(MEAL_PLANS_FILE)
+    }
+
+    /// Path of the selected user's meal template file
+    pub fn meal_templates_path(&self) -> String {
+        self.user_file_path(MEAL_TEMPLATES_FILE)
+    }
+
+    /// Path of the selected user's audit log (see `repositories::audit_log`)
+    pub fn audit_log_path(&self) -> PathBuf {
+        self.user_dir().join(AUDIT_LOG_FILE)
+    }
+
+    /// Directory for the selected user's generated reports such as chart images
+    pub fn reports_dir(&self) -> PathBuf {
+        self.user_dir().join(REPORTS_DIR)
+    }
+
+    /// Directory with food source plugins (shared by all users; see `factories::plugin_food_source`)
+    pub fn plugins_dir(&self) -> PathBuf {
+        self.data_dir.join(PLUGINS_DIR)
+    }
+
+    /// Directory where foods found by plugins are cached (shared by all users)
+    pub fn food_cache_dir(&self) -> PathBuf {
+        self.data_dir.join(FOOD_CACHE_DIR)
+    }
+
+    /// The shared data files and the selected user's, as bundled by "Export Everything" and synced by `yada sync`
+    ///
+    /// Bundles use the plain file names, so they can be restored for another user.
+    pub fn data_files(&self) -> Vec<BundleEntry> {
+        vec![
+            BundleEntry::new(FOODS_FILE, "foods", &self.foods_path()),
+            BundleEntry::new(SETTINGS_FILE, "settings", &self.settings_path()),
+            BundleEntry::new(LOGS_FILE, "logs", &self.logs_path()),
+            BundleEntry::new(PROFILE_FILE, "profile", &self.profile_path()),
+            BundleEntry::new(EXERCISE_FILE, "exercise", &self.exercise_path()),
+            BundleEntry::new(MEAL_PLANS_FILE, "meal_plans", &self.meal_plans_path()),
+            BundleEntry::new(MEAL_TEMPLATES_FILE, "meal_templates", &self.meal_templates_path()),
+        ]
+    }
+
+    /// Joins a file name onto the selected user's directory
+    fn user_file_path(&self, file_name: &str) -> String {
+        path_string(self.user_dir().join(file_name))
+    }
+}
+
+/// Reads the user chosen with `YADA_USER`, if set
+pub fn user_from_env() -> Option<String> {
+    env::var(USER_ENV_VAR).ok().filter(|user| !user.trim().is_empty())
+}
+
+/// Checks that a name can be used for a new user
+///
+/// Names are 1 to 32 letters, digits, `-`, or `_`, and cannot be `default`,
+/// which always means the user whose files are directly in the data directory.
+pub fn validate_user_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || name.len() > MAX_USER_NAME_LEN {
+        return Err(Message::UserNameLength.format(&[&MAX_USER_NAME_LEN]));
+    }
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(Message::UserNameCharacters.text().to_string());
+    }
+    if name.eq_ignore_ascii_case(DEFAULT_USER) {
+        return Err(Message::UserNameDefault.format(&[&DEFAULT_USER]));
+    }
+    Ok(())
+}
+
+/// Converts a data file path to the string form repositories take
+fn path_string(path: PathBuf) -> String {
+    path.to_string_lossy().into_owned()
+}
+
+/// Returns true if a directory contains any of the YADA data files
+fn has_data_files(dir: &Path) -> bool {
+    [FOODS_FILE, LOGS_FILE, PROFILE_FILE]
+        .iter()
+        .any(|file| dir.join(file).exists())
+}
+
+/// Returns the user's home directory from the environment
+fn home_dir() -> Option<PathBuf> {
+    let var = if cfg!(windows) { "USERPROFILE" } else { "HOME" };
+    env::var_os(var).filter(|v| !v.is_empty()).map(PathBuf::from)
+}
+
+/// Expands a leading `~/` to the home directory
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Reads an absolute directory from an environment variable
+fn env_dir(var: &str) -> Option<PathBuf> {
+    env::var_os(var)
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+}
+
+/// Returns the platform-specific data directory for YADA
+fn platform_data_dir() -> Option<PathBuf> {
+    let base = if cfg!(windows) {
+        env_dir("APPDATA")?
+    } else if cfg!(target_os = "macos") {
+        home_dir()?.join("Library").join("Application Support")
+    } else {
+        env_dir("XDG_DATA_HOME").or_else(|| Some(home_dir()?.join(".local").join("share")))?
+    };
+    Some(base.join(APP_DIR_NAME))
+}
+
+/// Returns the location of the config file
+pub fn config_file_path() -> Option<PathBuf> {
+    let base = if cfg!(windows) {
+        env_dir("APPDATA")?
+    } else {
+        env_dir("XDG_CONFIG_HOME").or_else(|| Some(home_dir()?.join(".config")))?
+    };
+    Some(base.join(APP_DIR_NAME).join("config"))
+}
+
+/// Sets a `key = value` entry in the config file, keeping its other lines
+///
+/// # Arguments
+/// * `key` - Setting to change, such as `data_dir`
+/// * `value` - New value
+///
+/// # Returns
+/// * `Ok(PathBuf)` - The config file that was written
+/// * `Err(String)` - If the config file location is unknown or it cannot be written
+pub fn write_config_value(key: &str, value: &str) -> Result<PathBuf, String> {
+    let path = config_file_path().ok_or(Message::NoConfigFile.text())?;
+    let contents = if path.exists() {
+        fs::read_to_string(&path).map_err(|e| Message::ErrorReadingConfig.format(&[&path.display(), &e]))?
+    } else {
+        String::new()
+    };
+
+    let entry = format!("{} = {}", key, value);
+    let is_entry = |line: &str| {
+        let line = line.trim();
+        !line.starts_with('#') && line.split_once('=').is_some_and(|(name, _)| name.trim().eq_ignore_ascii_case(key))
+    };
+    let mut lines: Vec<String> = contents.lines().filter(|line| !is_entry(line)).map(str::to_string).collect();
+    lines.push(entry);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| Message::ErrorCreating.format(&[&parent.display(), &e]))?;
+    }
+    fs::write(&path, lines.join("\n") + "\n")
+        .map_err(|e| Message::ErrorWritingConfig.format(&[&path.display(), &e]))?;
+    Ok(path)
+}
+
+/// Parses a `key = value` config file
+///
+/// # Returns
+/// * `Ok(HashMap)` - Settings by key (keys are trimmed and lowercased)
+/// * `Err(String)` - If the file cannot be read
+fn read_config_file(path: &Path) -> Result<HashMap<String, String>, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| Message::ErrorReadingConfig.format(&[&path.display(), &e]))?;
+
+    Ok(contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim().to_lowercase(), value.trim().to_string()))
+        .collect())
+}
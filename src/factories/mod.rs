@@ -1,2 +0,0 @@
-// src/factories/mod.rs
-pub mod food_source_factory;
\ No newline at end of file
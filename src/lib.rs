@@ -0,0 +1,21 @@
+//! # YADA Command-Line Frontend
+//!
+//! The interactive menus and command-line subcommands of YADA, built on the
+//! `yada-core` library. The `yada` binary (`main.rs`) only parses arguments
+//! and dispatches to this library, so tests can drive a whole menu session
+//! in-process (see `app::App::with_console`).
+//!
+//! ## Module Organization
+//!
+//! - `app`: The interactive application and every menu flow
+//! - `ui`: Reusable interactive prompts, console, and presentation helpers
+//! - `cli`: Command-line arguments and non-interactive subcommands
+//! - `config`: Data directory and configuration file resolution
+//! - `reports`: Generated report files (chart images, reviews)
+
+// src/lib.rs
+pub mod app;
+pub mod ui;
+pub mod cli;
+pub mod config;
+pub mod reports;
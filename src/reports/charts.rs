@@ -0,0 +1,385 @@
+//! # Monthly Chart Images
+//!
+//! This module renders PNG charts for one calendar month with `plotters`:
+//!
+//! - **Weight trend**: Weights recorded in daily profiles, one point per day
+//! - **Calorie intake**: Calories consumed per day as bars, with the daily
+//!   target as a line from the first daily profile on
+//!
+//! Images are written to the reports directory as `weight-YYYY-MM.png` and
+//! `intake-YYYY-MM.png`, together with a minimal `charts-YYYY-MM.html` page
+//! showing them. `ChartImage::html_img` returns an `<img>` tag with a path
+//! relative to that directory, so any HTML report saved next to the images
+//! can embed them the same way.
+//!
+//! ## Fonts
+//!
+//! Axis labels and titles need a TrueType font. The font named by
+//! `YADA_CHART_FONT` is used if set; otherwise a few common system font
+//! locations are tried. Without any font the charts are still drawn, just
+//! without text.
+
+// src/reports/charts.rs
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use chrono::{Datelike, Duration, NaiveDate};
+use plotters::prelude::*;
+use plotters::coord::types::RangedCoordf64;
+use plotters::style::{FontStyle, register_font};
+
+use yada_core::repositories::food_repository::FoodRepository;
+use yada_core::repositories::log_repository::LogRepository;
+use yada_core::repositories::exercise_repository::ExerciseRepository;
+use yada_core::repositories::profile_repository::ProfileRepository;
+use yada_core::strategies::calorie_calculator::CalorieCalculatorFactory;
+use yada_core::strategies::target_modifier::TargetPipeline;
+
+use crate::ui::i18n::{self, Message};
+
+/// Environment variable naming a TrueType font file for chart text
+pub const CHART_FONT_ENV_VAR: &str = "YADA_CHART_FONT";
+
+/// Font files tried when `YADA_CHART_FONT` is not set
+const FONT_CANDIDATES: &[&str] = &[
+    "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
+    "/usr/share/fonts/dejavu/DejaVuSans.ttf",
+    "/usr/share/fonts/TTF/DejaVuSans.ttf",
+    "/usr/share/fonts/truetype/liberation/LiberationSans-Regular.ttf",
+    "/Library/Fonts/Arial.ttf",
+    "/System/Library/Fonts/Supplemental/Arial.ttf",
+    "C:\\Windows\\Fonts\\arial.ttf",
+];
+
+/// Size of each chart image in pixels
+const CHART_SIZE: (u32, u32) = (900, 500);
+
+/// Values for one day of the month
+#[derive(Debug, Clone)]
+pub struct DayValues {
+    /// The day these values describe
+    pub date: NaiveDate,
+
+    /// Weight recorded in the daily profile, if any
+    pub weight: Option<f64>,
+
+    /// Calories consumed according to the food log
+    pub intake: f64,
+
+    /// Target calories, if a daily profile exists for the day or an earlier one
+    pub target: Option<f64>,
+}
+
+/// Daily values for a whole calendar month
+#[derive(Debug, Clone)]
+pub struct MonthlySeries {
+    /// First day of the month
+    pub month: NaiveDate,
+
+    /// One entry per day of the month, in order
+    pub days: Vec<DayValues>,
+}
+
+impl MonthlySeries {
+    /// Collects weights, intake, and targets for every day of a month
+    ///
+    /// # Arguments
+    /// * `food_repo` - Food database used to resolve logged food IDs
+    /// * `log_repo` - Food consumption logs
+    /// * `profile_repo` - User profile with daily weights
+    /// * `exercise_repo` - Exercise log, for the exercise credit of the targets
+    /// * `month` - Any day in the month to collect
+    pub fn collect(
+        food_repo: &FoodRepository,
+        log_repo: &LogRepository,
+        profile_repo: &ProfileRepository,
+        exercise_repo: &ExerciseRepository,
+        month: NaiveDate,
+    ) -> Self {
+        let first = month.with_day(1).unwrap_or(month);
+        let factory = CalorieCalculatorFactory::from_repositories(food_repo, log_repo);
+        let pipeline = TargetPipeline::standard().with_exercise_credit(exercise_repo);
+        let profile = profile_repo.get_profile();
+        let foods = food_repo.get_foods();
+
+        let days = (0..days_in_month(first))
+            .map(|offset| first + Duration::days(offset as i64))
+            .map(|date| {
+                let daily = profile.and_then(|p| p.get_daily_profile(date));
+                DayValues {
+                    date,
+                    weight: daily.map(|d| d.weight),
+                    intake: log_repo.get_log(date).map_or(0.0, |log| log.total_calories(foods)),
+                    target: match (profile, profile.and_then(|p| factory.calculator_for(p, date))) {
+                        (Some(p), Some(c)) if p.latest_daily_profile(date).is_some() => {
+                            Some(c.calculate_target_with(p, date, &pipeline).target)
+                        }
+                        _ => None,
+                    },
+                }
+            })
+            .collect();
+
+        MonthlySeries { month: first, days }
+    }
+
+    /// Returns true if at least one weight was recorded in the month
+    pub fn has_weights(&self) -> bool {
+        self.days.iter().any(|day| day.weight.is_some())
+    }
+
+    /// Returns true if any food was logged in the month
+    pub fn has_intake(&self) -> bool {
+        self.days.iter().any(|day| day.intake > 0.0)
+    }
+
+    /// Month label used in titles, e.g. "March 2024"
+    fn title(&self) -> String {
+        format!("{} {}", i18n::month_name(self.month), self.month.format("%Y"))
+    }
+
+    /// Month label used in file names, e.g. "2024-03"
+    fn file_stem(&self) -> String {
+        self.month.format("%Y-%m").to_string()
+    }
+}
+
+/// A rendered chart image
+#[derive(Debug, Clone)]
+pub struct ChartImage {
+    /// Location of the PNG file
+    pub path: PathBuf,
+
+    /// Human-readable chart title
+    pub title: String,
+}
+
+impl ChartImage {
+    /// Returns an `<img>` tag for an HTML report stored in the same directory
+    pub fn html_img(&self) -> String {
+        let file_name = self.path
+            .file_name()
+            .map_or(String::new(), |name| name.to_string_lossy().into_owned());
+        format!("<img src=\"{}\" alt=\"{}\">", file_name, self.title)
+    }
+}
+
+/// Renders the weight and intake charts for a month
+///
+/// The weight chart is skipped when no weight was recorded in the month.
+///
+/// # Arguments
+/// * `series` - Daily values for the month
+/// * `output_dir` - Directory to write the images to (created if missing)
+///
+/// # Returns
+/// * `Ok(Vec<ChartImage>)` - The images written
+/// * `Err(String)` - If the month has no data or an image cannot be written
+pub fn render_monthly_charts(series: &MonthlySeries, output_dir: &Path) -> Result<Vec<ChartImage>, String> {
+    if !series.has_weights() && !series.has_intake() {
+        return Err(Message::NoDataInMonth.format(&[&series.title()]));
+    }
+
+    fs::create_dir_all(output_dir)
+        .map_err(|e| Message::ErrorCreating.format(&[&output_dir.display(), &e]))?;
+
+    let mut images = Vec::new();
+    if series.has_weights() {
+        let image = ChartImage {
+            path: output_dir.join(format!("weight-{}.png", series.file_stem())),
+            title: Message::WeightTrendChart.format(&[&series.title()]),
+        };
+        render_weight_chart(series, &image)?;
+        images.push(image);
+    }
+
+    let image = ChartImage {
+        path: output_dir.join(format!("intake-{}.png", series.file_stem())),
+        title: Message::CalorieIntakeChart.format(&[&series.title()]),
+    };
+    render_intake_chart(series, &image)?;
+    images.push(image);
+
+    Ok(images)
+}
+
+/// Writes an HTML page embedding the chart images of a month
+///
+/// # Arguments
+/// * `series` - The month the images belong to
+/// * `images` - Images written by `render_monthly_charts`
+/// * `output_dir` - Directory containing the images
+///
+/// # Returns
+/// * `Ok(PathBuf)` - Location of the HTML page
+/// * `Err(String)` - If the page cannot be written
+pub fn write_html_page(series: &MonthlySeries, images: &[ChartImage], output_dir: &Path) -> Result<PathBuf, String> {
+    let title = Message::ChartsPageTitle.format(&[&series.title()]);
+    let figures: String = images
+        .iter()
+        .map(|image| format!("<figure>{}<figcaption>{}</figcaption></figure>\n", image.html_img(), image.title))
+        .collect();
+    let page = format!(
+        "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>{0}</title></head>\n<body>\n<h1>{0}</h1>\n{1}</body>\n</html>\n",
+        title, figures
+    );
+
+    let path = output_dir.join(format!("charts-{}.html", series.file_stem()));
+    fs::write(&path, page).map_err(|e| Message::ErrorWriting.format(&[&path.display(), &e]))?;
+    Ok(path)
+}
+
+/// Draws recorded weights as a line with a point per measurement
+fn render_weight_chart(series: &MonthlySeries, image: &ChartImage) -> Result<(), String> {
+    let points: Vec<(f64, f64)> = series.days
+        .iter()
+        .filter_map(|day| day.weight.map(|weight| (day.date.day() as f64, weight)))
+        .collect();
+    let (min, max) = points.iter().fold((f64::MAX, f64::MIN), |(lo, hi), &(_, w)| (lo.min(w), hi.max(w)));
+    let y_range = (min - 1.0).max(0.0)..(max + 1.0);
+
+    draw(image, |root, with_text| {
+        let mut chart = chart_builder(root, &image.title, with_text)
+            .build_cartesian_2d(day_range(series), y_range)?;
+        configure_mesh(&mut chart, Message::WeightKg.text(), 1, with_text)?;
+
+        chart.draw_series(LineSeries::new(points.iter().copied(), BLUE.stroke_width(2)))?;
+        chart.draw_series(points.iter().map(|&point| Circle::new(point, 4, BLUE.filled())))?;
+        Ok(())
+    })
+}
+
+/// Draws daily calorie intake as bars and the target as a line
+fn render_intake_chart(series: &MonthlySeries, image: &ChartImage) -> Result<(), String> {
+    let highest = series.days
+        .iter()
+        .map(|day| day.intake.max(day.target.unwrap_or(0.0)))
+        .fold(0.0, f64::max);
+    let y_max = (highest * 1.1).max(100.0);
+
+    draw(image, |root, with_text| {
+        let mut chart = chart_builder(root, &image.title, with_text)
+            .build_cartesian_2d(day_range(series), 0.0..y_max)?;
+        configure_mesh(&mut chart, Message::CaloriesKcal.text(), 0, with_text)?;
+
+        chart.draw_series(series.days.iter().map(|day| {
+            let x = day.date.day() as f64;
+            let color = match day.target {
+                Some(target) if day.intake > target => RED.mix(0.7),
+                _ => GREEN.mix(0.7),
+            };
+            Rectangle::new([(x - 0.4, 0.0), (x + 0.4, day.intake)], color.filled())
+        }))?;
+
+        let targets: Vec<(f64, f64)> = series.days
+            .iter()
+            .filter_map(|day| day.target.map(|target| (day.date.day() as f64, target)))
+            .collect();
+        chart.draw_series(LineSeries::new(targets, BLACK.stroke_width(2)))?;
+        Ok(())
+    })
+}
+
+/// Error type returned by the plotters drawing closures
+type DrawResult = Result<(), Box<dyn std::error::Error>>;
+
+/// Drawing area of a bitmap chart
+type Canvas<'a> = DrawingArea<BitMapBackend<'a>, plotters::coord::Shift>;
+
+/// Sets up the PNG backend, runs a drawing closure, and writes the file
+fn draw<F>(image: &ChartImage, paint: F) -> Result<(), String>
+where
+    F: FnOnce(&Canvas, bool) -> DrawResult,
+{
+    let with_text = chart_font_available();
+    let root = BitMapBackend::new(&image.path, CHART_SIZE).into_drawing_area();
+
+    root.fill(&WHITE)
+        .map_err(|e| e.to_string())
+        .and_then(|_| paint(&root, with_text).map_err(|e| e.to_string()))
+        .and_then(|_| root.present().map_err(|e| e.to_string()))
+        .map_err(|e| Message::ErrorRendering.format(&[&image.path.display(), &e]))
+}
+
+/// Creates a chart builder with a caption and label areas when text is available
+fn chart_builder<'a, 'b>(root: &'a Canvas<'b>, title: &str, with_text: bool) -> ChartBuilder<'a, 'b, BitMapBackend<'b>> {
+    let mut builder = ChartBuilder::on(root);
+    builder.margin(15);
+    if with_text {
+        builder
+            .caption(title, ("sans-serif", 24))
+            .x_label_area_size(40)
+            .y_label_area_size(60);
+    }
+    builder
+}
+
+/// Horizontal axis covering every day of the month with half a day of padding
+fn day_range(series: &MonthlySeries) -> std::ops::Range<f64> {
+    0.5..series.days.len() as f64 + 0.5
+}
+
+/// Draws grid lines, and axis labels when a font is available
+///
+/// # Arguments
+/// * `chart` - Chart with days on the x axis
+/// * `y_desc` - Label of the y axis
+/// * `y_decimals` - Decimals shown in y axis labels
+/// * `with_text` - Whether a font is available for labels
+fn configure_mesh(
+    chart: &mut ChartContext<BitMapBackend, Cartesian2d<RangedCoordf64, RangedCoordf64>>,
+    y_desc: &str,
+    y_decimals: usize,
+    with_text: bool,
+) -> DrawResult {
+    let day_label = |day: &f64| format!("{:.0}", day);
+    let y_label = |value: &f64| format!("{:.*}", y_decimals, value);
+
+    let mut mesh = chart.configure_mesh();
+    mesh.light_line_style(WHITE.mix(0.0));
+    if with_text {
+        mesh.x_desc(Message::DayOfMonth.text())
+            .y_desc(y_desc)
+            .x_labels(16)
+            .x_label_formatter(&day_label)
+            .y_label_formatter(&y_label);
+    } else {
+        mesh.x_labels(0).y_labels(0);
+    }
+    mesh.draw()?;
+    Ok(())
+}
+
+/// Registers a chart font once and reports whether text can be drawn
+fn chart_font_available() -> bool {
+    static FONT_LOADED: OnceLock<bool> = OnceLock::new();
+
+    *FONT_LOADED.get_or_init(|| {
+        let configured = env::var_os(CHART_FONT_ENV_VAR).map(PathBuf::from);
+        let candidates = configured.into_iter().chain(FONT_CANDIDATES.iter().map(PathBuf::from));
+
+        for path in candidates {
+            let Ok(bytes) = fs::read(&path) else {
+                continue;
+            };
+            // plotters keeps a reference to the font for the rest of the process
+            let bytes: &'static [u8] = Box::leak(bytes.into_boxed_slice());
+            if register_font("sans-serif", FontStyle::Normal, bytes).is_ok() {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+/// Returns the number of days in the month starting at `first`
+fn days_in_month(first: NaiveDate) -> u32 {
+    let next = if first.month() == 12 {
+        NaiveDate::from_ymd_opt(first.year() + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(first.year(), first.month() + 1, 1)
+    };
+    next.map_or(31, |next| (next - first).num_days() as u32)
+}
@@ -0,0 +1,227 @@
+//! # Daily Summary
+//!
+//! When the interactive session ends, YADA prints a short summary of the
+//! working day: calories consumed against the target, how the calories split
+//! between protein, carbohydrates, and fat, and the weight if one was entered
+//! for that day. The same summary can be kept as a diary in a Markdown
+//! journal, so the day can be looked back on without opening the app.
+//!
+//! ## Journal
+//!
+//! The journal is configured with the `YADA_JOURNAL` environment variable:
+//! - `off` (default): Only print the summary
+//! - `on`: Also write it to `summaries.md` in the reports directory
+//! - Any other value: Also write it to that file
+//!
+//! Each day is one `## YYYY-MM-DD` section. Exiting again on the same day
+//! replaces that day's section instead of adding a second one, and sections
+//! of other days are left exactly as they are.
+
+// src/reports/daily_summary.rs
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDate;
+
+use yada_core::models::macro_target::CALORIES_PER_GRAM;
+use yada_core::models::profile::UserProfile;
+
+use crate::cli::status::DailyStatus;
+use crate::ui::i18n::Message;
+
+/// Environment variable used to configure the journal
+pub const JOURNAL_ENV_VAR: &str = "YADA_JOURNAL";
+
+/// File name of the journal in the reports directory
+pub const JOURNAL_FILE: &str = "summaries.md";
+
+/// Where, if anywhere, daily summaries are written
+#[derive(Debug, Clone, PartialEq)]
+pub enum JournalSetting {
+    /// Summaries are only printed
+    Off,
+
+    /// Summaries go to `summaries.md` in the reports directory
+    ReportsDir,
+
+    /// Summaries go to this file
+    File(PathBuf),
+}
+
+impl JournalSetting {
+    /// Parses the textual form of the setting (`off`, `on`, or a file path)
+    pub fn parse(value: &str) -> Self {
+        let value = value.trim();
+        match value.to_lowercase().as_str() {
+            "" | "off" => JournalSetting::Off,
+            "on" => JournalSetting::ReportsDir,
+            _ => JournalSetting::File(PathBuf::from(value)),
+        }
+    }
+
+    /// Reads the setting from `YADA_JOURNAL` (off when unset)
+    pub fn from_env() -> Self {
+        env::var(JOURNAL_ENV_VAR).map_or(JournalSetting::Off, |value| Self::parse(&value))
+    }
+
+    /// Returns the journal file, if summaries should be written
+    ///
+    /// # Arguments
+    /// * `reports_dir` - Directory used by the `on` setting
+    pub fn path(&self, reports_dir: &Path) -> Option<PathBuf> {
+        match self {
+            JournalSetting::Off => None,
+            JournalSetting::ReportsDir => Some(reports_dir.join(JOURNAL_FILE)),
+            JournalSetting::File(path) => Some(path.clone()),
+        }
+    }
+}
+
+/// Summary of one day
+#[derive(Debug, Clone, PartialEq)]
+pub struct DailySummary {
+    /// Calories and macros consumed, and the target
+    pub status: DailyStatus,
+
+    /// Weight entered for the day in kilograms (not a carried-forward one)
+    pub weight: Option<f64>,
+}
+
+impl DailySummary {
+    /// Builds the summary from a day's status and the profile
+    pub fn new(status: DailyStatus, profile: Option<&UserProfile>) -> Self {
+        let weight = profile
+            .and_then(|profile| profile.get_daily_profile(status.date))
+            .filter(|daily| daily.carried_from.is_none())
+            .map(|daily| daily.weight);
+        DailySummary { status, weight }
+    }
+
+    /// Returns the share of macronutrient calories from protein, carbs, and fat (percent)
+    ///
+    /// # Returns
+    /// * `Some([f64; 3])` - Percentages adding up to 100
+    /// * `None` - If nothing with macros was logged
+    pub fn macro_split(&self) -> Option<[f64; 3]> {
+        let macros = &self.status.macros;
+        let calories = [
+            macros.protein * CALORIES_PER_GRAM[0],
+            macros.carbs * CALORIES_PER_GRAM[1],
+            macros.fat * CALORIES_PER_GRAM[2],
+        ];
+        let total: f64 = calories.iter().sum();
+        (total > 0.0).then(|| calories.map(|part| part / total * 100.0))
+    }
+
+    /// Returns the summary lines, without the date
+    fn lines(&self) -> Vec<String> {
+        let status = &self.status;
+        let difference = status.consumed - status.target;
+        let marker = if status.estimate_notice.is_some() { "~" } else { "" };
+        let balance = if difference > 0.0 { Message::OverWord } else { Message::RemainingWord };
+        let mut lines = vec![Message::SummaryCaloriesLine.format(&[
+            &status.consumed,
+            &marker,
+            &status.target,
+            &difference.abs(),
+            &balance,
+        ])];
+        match self.macro_split() {
+            Some([protein, carbs, fat]) => lines.push(Message::SummaryMacrosLine.format(&[
+                &protein,
+                &carbs,
+                &fat,
+                &status.macros.protein,
+                &status.macros.carbs,
+                &status.macros.fat,
+            ])),
+            None => lines.push(Message::SummaryMacrosNone.text().to_string()),
+        }
+        if let Some(weight) = self.weight {
+            lines.push(Message::SummaryWeightLine.format(&[&weight]));
+        }
+        lines
+    }
+
+    /// Formats the summary for the terminal
+    pub fn text(&self) -> String {
+        let mut text = Message::SummaryFor.format(&[&self.status.date.format("%Y-%m-%d")]);
+        for line in self.lines() {
+            text.push_str("\n  ");
+            text.push_str(&line);
+        }
+        text
+    }
+
+    /// Formats the summary as a journal section
+    pub fn markdown(&self) -> String {
+        let mut section = format!("## {}\n\n", self.status.date.format("%Y-%m-%d"));
+        for line in self.lines() {
+            section.push_str(&format!("- {}\n", line));
+        }
+        section
+    }
+
+    /// Writes the summary into a journal file, replacing the day's earlier section
+    ///
+    /// The file and its directory are created if needed.
+    ///
+    /// # Arguments
+    /// * `path` - The journal file
+    pub fn write_to_journal(&self, path: &Path) -> Result<(), io::Error> {
+        let existing = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, merge_section(&existing, self.status.date, &self.markdown()))
+    }
+}
+
+/// Puts a day's section into a journal's text
+///
+/// # Arguments
+/// * `journal` - Current journal text (empty for a new journal)
+/// * `date` - Day of the section
+/// * `section` - The section, starting with its `## YYYY-MM-DD` heading
+///
+/// # Returns
+/// The journal with the day's section replaced, or appended if it had none
+pub fn merge_section(journal: &str, date: NaiveDate, section: &str) -> String {
+    let heading = format!("## {}", date.format("%Y-%m-%d"));
+    let mut before = String::new();
+    let mut after = String::new();
+    let mut found = false;
+    let mut in_section = false;
+
+    for line in journal.lines() {
+        if line.starts_with("## ") {
+            in_section = line.trim_end() == heading;
+            found |= in_section;
+        }
+        if in_section {
+            continue;
+        }
+        let part = if found { &mut after } else { &mut before };
+        part.push_str(line);
+        part.push('\n');
+    }
+
+    if journal.is_empty() {
+        before.push_str("# YADA Journal\n");
+    }
+    if !before.is_empty() && !before.ends_with("\n\n") {
+        before.push('\n');
+    }
+    before.push_str(section);
+    if !after.is_empty() {
+        before.push('\n');
+        before.push_str(after.trim_start_matches('\n'));
+    }
+    before
+}
@@ -0,0 +1,188 @@
+//! # Report Documents
+//!
+//! Text reports are built once as a `Document` (headings, paragraphs,
+//! bullet lists, and tables) and then rendered as Markdown or as a
+//! standalone HTML page, so both formats always contain the same content.
+//!
+//! ## Escaping
+//!
+//! Text is escaped for the target format when rendering: `&`, `<`, `>`, and
+//! `"` in HTML, and `|` inside Markdown table cells. Callers pass plain text.
+
+// src/reports/document.rs
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+
+use crate::ui::i18n::Message;
+
+/// File formats a document can be exported to
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+}
+
+impl ReportFormat {
+    /// File extension without the dot
+    pub fn extension(self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "md",
+            ReportFormat::Html => "html",
+        }
+    }
+}
+
+/// One part of a document
+#[derive(Debug, Clone, PartialEq)]
+pub enum Block {
+    /// Section heading
+    Heading(String),
+
+    /// Paragraph of text
+    Paragraph(String),
+
+    /// Bullet list, one item per entry
+    List(Vec<String>),
+
+    /// Table with a header row
+    Table {
+        headers: Vec<String>,
+        rows: Vec<Vec<String>>,
+    },
+}
+
+/// A report made of blocks under a title
+#[derive(Debug, Clone, PartialEq)]
+pub struct Document {
+    /// Title shown at the top (and as the HTML page title)
+    pub title: String,
+
+    /// Content, in order
+    pub blocks: Vec<Block>,
+}
+
+impl Document {
+    /// Creates an empty document
+    pub fn new(title: &str) -> Self {
+        Document { title: title.to_string(), blocks: Vec::new() }
+    }
+
+    /// Appends a section heading
+    pub fn heading(&mut self, text: &str) {
+        self.blocks.push(Block::Heading(text.to_string()));
+    }
+
+    /// Appends a paragraph
+    pub fn paragraph(&mut self, text: &str) {
+        self.blocks.push(Block::Paragraph(text.to_string()));
+    }
+
+    /// Appends a bullet list
+    pub fn list(&mut self, items: Vec<String>) {
+        self.blocks.push(Block::List(items));
+    }
+
+    /// Appends a table
+    pub fn table(&mut self, headers: &[&str], rows: Vec<Vec<String>>) {
+        self.blocks.push(Block::Table {
+            headers: headers.iter().map(|header| header.to_string()).collect(),
+            rows,
+        });
+    }
+
+    /// Renders the document in a format
+    pub fn render(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Markdown => self.to_markdown(),
+            ReportFormat::Html => self.to_html(),
+        }
+    }
+
+    /// Renders the document as Markdown
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# {}\n", self.title);
+        for block in &self.blocks {
+            out.push('\n');
+            match block {
+                Block::Heading(text) => out.push_str(&format!("## {}\n", text)),
+                Block::Paragraph(text) => out.push_str(&format!("{}\n", text)),
+                Block::List(items) => {
+                    for item in items {
+                        out.push_str(&format!("- {}\n", item));
+                    }
+                }
+                Block::Table { headers, rows } => {
+                    let line = |cells: &[String]| {
+                        let cells: Vec<String> = cells.iter().map(|cell| cell.replace('|', "\\|")).collect();
+                        format!("| {} |\n", cells.join(" | "))
+                    };
+                    out.push_str(&line(headers));
+                    out.push_str(&format!("|{}\n", " --- |".repeat(headers.len())));
+                    for row in rows {
+                        out.push_str(&line(row));
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    /// Renders the document as a standalone HTML page
+    pub fn to_html(&self) -> String {
+        let mut body = format!("<h1>{}</h1>\n", escape_html(&self.title));
+        for block in &self.blocks {
+            match block {
+                Block::Heading(text) => body.push_str(&format!("<h2>{}</h2>\n", escape_html(text))),
+                Block::Paragraph(text) => body.push_str(&format!("<p>{}</p>\n", escape_html(text))),
+                Block::List(items) => {
+                    body.push_str("<ul>\n");
+                    for item in items {
+                        body.push_str(&format!("<li>{}</li>\n", escape_html(item)));
+                    }
+                    body.push_str("</ul>\n");
+                }
+                Block::Table { headers, rows } => {
+                    let cells = |tag: &str, cells: &[String]| -> String {
+                        cells.iter().map(|cell| format!("<{0}>{1}</{0}>", tag, escape_html(cell))).collect()
+                    };
+                    body.push_str("<table>\n");
+                    body.push_str(&format!("<tr>{}</tr>\n", cells("th", headers)));
+                    for row in rows {
+                        body.push_str(&format!("<tr>{}</tr>\n", cells("td", row)));
+                    }
+                    body.push_str("</table>\n");
+                }
+            }
+        }
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>{}</title>\n\
+             <style>table {{ border-collapse: collapse; }} th, td {{ border: 1px solid #ccc; padding: 4px 8px; }}</style>\n\
+             </head>\n<body>\n{}</body>\n</html>\n",
+            escape_html(&self.title),
+            body
+        )
+    }
+
+    /// Writes the document to `<output_dir>/<stem>.<extension>`
+    ///
+    /// # Returns
+    /// * `Ok(PathBuf)` - Location of the written file
+    /// * `Err(String)` - If the directory or file cannot be written
+    pub fn write(&self, format: ReportFormat, output_dir: &Path, stem: &str) -> Result<PathBuf, String> {
+        fs::create_dir_all(output_dir)
+            .map_err(|e| Message::ErrorCreating.format(&[&output_dir.display(), &e]))?;
+        let path = output_dir.join(format!("{}.{}", stem, format.extension()));
+        fs::write(&path, self.render(format)).map_err(|e| Message::ErrorWriting.format(&[&path.display(), &e]))?;
+        Ok(path)
+    }
+}
+
+/// Escapes text for use in HTML content and attribute values
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,168 @@
+//! # Energy Balance Report
+//!
+//! This module pairs weekly calorie intake with weekly weight change to show
+//! whether the scale follows what was eaten, and estimates the calories
+//! actually burned per day from that data (see `models::energy_balance`):
+//!
+//! - **Weekly intake and weight**: Days logged, average calories per logged
+//!   day, last weigh-in, and weight change for each week
+//! - **Adaptive TDEE**: The estimate from intake and weight trend next to
+//!   the formula-based TDEE of the profile's calculation method on the last
+//!   day, with the difference between them (Mifflin-St Jeor when the
+//!   profile uses the adaptive method itself)
+//!
+//! Weights carried forward from an earlier day are not measurements and are
+//! left out, as in the other reports. The report is exported as Markdown or
+//! HTML through `reports::document`.
+
+// src/reports/energy_balance.rs
+use chrono::{Duration, NaiveDate};
+
+use yada_core::models::energy_balance::{self, AdaptiveEstimate, WeekBalance, KCAL_PER_KG, MIN_LOGGED_DAYS, MIN_WEIGHT_SPAN_DAYS};
+use yada_core::models::week::WeekStart;
+use yada_core::repositories::food_repository::FoodRepository;
+use yada_core::repositories::log_repository::LogRepository;
+use yada_core::repositories::profile_repository::ProfileRepository;
+use yada_core::strategies::calorie_calculator::{CalorieCalculatorFactory, ADAPTIVE_FALLBACK_METHOD, ADAPTIVE_METHOD};
+use yada_core::strategies::health_metrics::HealthMetrics;
+
+use super::document::Document;
+use crate::ui::i18n::Message;
+
+/// Weeks covered unless another number is asked for
+pub const DEFAULT_WEEKS: u32 = 8;
+
+/// Formula-based TDEE of the last day
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormulaTdee {
+    /// Description of the calculation method
+    pub method: String,
+
+    /// TDEE from the method's BMR and the day's activity level
+    pub tdee: f64,
+}
+
+/// Intake against weight change over several weeks
+#[derive(Debug, Clone)]
+pub struct EnergyBalanceReport {
+    /// First day of the first week
+    pub start: NaiveDate,
+
+    /// Last day covered
+    pub end: NaiveDate,
+
+    /// Each week, earliest first
+    pub weeks: Vec<WeekBalance>,
+
+    /// Estimate from intake and weight trend, if there is enough data
+    pub adaptive: Option<AdaptiveEstimate>,
+
+    /// Estimate from the profile's formula, if the profile has a weight by the last day
+    pub formula: Option<FormulaTdee>,
+
+    /// "Estimate only" notice when the formula uses population defaults
+    pub estimate_notice: Option<String>,
+}
+
+impl EnergyBalanceReport {
+    /// Collects the report for the weeks up to a date
+    ///
+    /// # Arguments
+    /// * `food_repo` - Food database used to resolve logged food IDs
+    /// * `log_repo` - Food consumption logs
+    /// * `profile_repo` - User profile with daily weights
+    /// * `end` - Last day covered
+    /// * `weeks` - Number of weeks covered, counting the week containing `end`
+    /// * `week_start` - First day of the week
+    ///
+    /// # Returns
+    /// * `Ok(EnergyBalanceReport)` - The report
+    /// * `Err(String)` - If there is no profile, or the weeks have no logs and no weights
+    pub fn collect(
+        food_repo: &FoodRepository,
+        log_repo: &LogRepository,
+        profile_repo: &ProfileRepository,
+        end: NaiveDate,
+        weeks: u32,
+        week_start: WeekStart,
+    ) -> Result<Self, String> {
+        let profile = profile_repo.get_profile()
+            .ok_or_else(|| Message::NoProfileForReport.text().to_string())?;
+        let start = week_start.week_of(end) - Duration::weeks(i64::from(weeks.max(1)) - 1);
+
+        let calories = energy_balance::daily_calories(log_repo.get_logs_between(start, end), food_repo.get_foods());
+        let weights = energy_balance::measured_weights(profile);
+        let weeks = energy_balance::weekly_balance(&calories, &weights, start, end, week_start);
+        if weeks.iter().all(|week| week.days_logged == 0 && week.weight.is_none()) {
+            return Err(Message::NoDataBetween.format(&[&start.format("%Y-%m-%d"), &end.format("%Y-%m-%d")]));
+        }
+
+        // With the adaptive method selected, compare against the formula it falls back to
+        let factory = CalorieCalculatorFactory::new();
+        let calculator = factory.calculator_for(profile, end)
+            .filter(|calculator| calculator.name() != ADAPTIVE_METHOD)
+            .or_else(|| factory.get_calculator(ADAPTIVE_FALLBACK_METHOD));
+        let formula = calculator.and_then(|calculator| {
+            HealthMetrics::calculate(profile, calculator, end)
+                .map(|metrics| FormulaTdee { method: calculator.description().to_string(), tdee: metrics.tdee })
+        });
+
+        Ok(EnergyBalanceReport {
+            start,
+            end,
+            weeks,
+            adaptive: energy_balance::estimate(&calories, &weights, start, end),
+            formula,
+            estimate_notice: profile.body_metrics(end).estimate_notice(),
+        })
+    }
+
+    /// Builds the exportable document
+    pub fn to_document(&self) -> Document {
+        let mut doc = Document::new(&Message::EnergyBalanceTitle.format(&[&self.start.format("%Y-%m-%d"), &self.end.format("%Y-%m-%d")]));
+
+        doc.heading(Message::WeeklyIntakeHeading.text());
+        let rows = self.weeks.iter().map(|week| vec![
+            week.start.format("%Y-%m-%d").to_string(),
+            week.days_logged.to_string(),
+            week.average_calories.map_or("-".to_string(), |calories| format!("{:.0}", calories)),
+            week.weight.map_or("-".to_string(), |weight| format!("{:.1}", weight)),
+            week.weight_change.map_or("-".to_string(), |change| format!("{:+.1}", change)),
+        ]).collect();
+        doc.table(&[
+            Message::WeekOfColumn.text(),
+            Message::DaysLoggedColumn.text(),
+            Message::AverageCaloriesColumn.text(),
+            Message::WeightKg.text(),
+            Message::ChangeKg.text(),
+        ], rows);
+
+        doc.heading(Message::AdaptiveTdeeHeading.text());
+        let mut items = Vec::new();
+        match &self.adaptive {
+            Some(adaptive) => {
+                items.push(Message::AverageIntakeLine.format(&[&adaptive.average_calories, &adaptive.days_logged]));
+                items.push(Message::WeightTrendLine.format(&[&adaptive.weight_trend_per_week]));
+                items.push(Message::AdaptiveTdeeLine.format(&[&adaptive.tdee]));
+            }
+            None => doc.paragraph(&Message::NotEnoughForAdaptive.format(&[&MIN_LOGGED_DAYS, &MIN_WEIGHT_SPAN_DAYS])),
+        }
+        if let Some(formula) = &self.formula {
+            items.push(Message::FormulaTdeeLine.format(&[&formula.tdee, &formula.method]));
+        }
+        if let (Some(adaptive), Some(formula)) = (&self.adaptive, &self.formula) {
+            items.push(Message::DifferenceLine.format(&[&(adaptive.tdee - formula.tdee)]));
+        }
+        if !items.is_empty() {
+            doc.list(items);
+        }
+        if self.adaptive.is_some() {
+            doc.paragraph(&Message::AdaptiveTdeeExplained.format(&[&KCAL_PER_KG]));
+        }
+        if let Some(notice) = &self.estimate_notice {
+            doc.paragraph(notice);
+        }
+
+        doc
+    }
+}
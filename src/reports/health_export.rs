@@ -0,0 +1,307 @@
+//! # Health App Export
+//!
+//! This module exports weights and nutrition in formats other health apps
+//! and their import tools understand, so the data is not locked into YADA:
+//!
+//! - **Apple Health** (`.xml`): `Record` elements as in the `export.xml` of
+//!   Apple Health itself, with `HKQuantityTypeIdentifier` types (body mass,
+//!   dietary energy, protein, carbohydrates, fat, and fiber)
+//! - **Google Fit** (`.csv`): One row per data point of the
+//!   `com.google.weight` and `com.google.nutrition` data types, with the
+//!   fields and nutrient keys of their schema
+//!
+//! ## Samples
+//!
+//! Every log entry becomes one nutrition sample at the time it was logged,
+//! named after its food. Nutrients a food has no value for (zero) are left
+//! out rather than exported as zero. Weights are exported for the days they
+//! were entered (not carried forward), at noon, since the profile does not
+//! record the time.
+
+// src/reports/health_export.rs
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, TimeZone};
+use clap::ValueEnum;
+
+use yada_core::models::food::Macros;
+use yada_core::models::meal::MealSlot;
+use yada_core::repositories::food_repository::FoodRepository;
+use yada_core::repositories::log_repository::LogRepository;
+use yada_core::repositories::profile_repository::ProfileRepository;
+
+use crate::ui::i18n::Message;
+
+/// Source name recorded in the exported samples
+const SOURCE_NAME: &str = "YADA";
+
+/// Formats of health apps that can be exported to
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum HealthFormat {
+    /// Apple Health export XML
+    AppleHealth,
+
+    /// Google Fit data points as CSV
+    GoogleFit,
+}
+
+impl HealthFormat {
+    /// File extension without the dot
+    pub fn extension(self) -> &'static str {
+        match self {
+            HealthFormat::AppleHealth => "xml",
+            HealthFormat::GoogleFit => "csv",
+        }
+    }
+
+    /// Prefix of exported file names
+    fn file_prefix(self) -> &'static str {
+        match self {
+            HealthFormat::AppleHealth => "apple-health",
+            HealthFormat::GoogleFit => "google-fit",
+        }
+    }
+}
+
+/// Nutrition of one log entry
+#[derive(Debug, Clone, PartialEq)]
+pub struct NutritionSample {
+    /// When the entry was logged
+    pub time: DateTime<Local>,
+
+    /// Name of the food
+    pub food: String,
+
+    /// Meal the entry belongs to, if any
+    pub meal: Option<MealSlot>,
+
+    /// Calories consumed
+    pub calories: f64,
+
+    /// Macronutrients consumed
+    pub macros: Macros,
+}
+
+/// A weight entered for a day
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeightSample {
+    /// Noon of the day the weight was entered
+    pub time: DateTime<Local>,
+
+    /// Weight in kilograms
+    pub weight: f64,
+}
+
+/// Weights and nutrition of a date range, ready to be exported
+#[derive(Debug, Clone)]
+pub struct HealthExport {
+    /// First day exported
+    pub from: NaiveDate,
+
+    /// Last day exported
+    pub to: NaiveDate,
+
+    /// Weights, earliest first
+    pub weights: Vec<WeightSample>,
+
+    /// Log entries, earliest first
+    pub nutrition: Vec<NutritionSample>,
+}
+
+impl HealthExport {
+    /// Collects the samples of a date range
+    ///
+    /// # Arguments
+    /// * `food_repo` - Food database used to resolve logged food IDs
+    /// * `log_repo` - Food consumption logs
+    /// * `profile_repo` - User profile with daily weights
+    /// * `from` - First day to export; defaults to the first day with data
+    /// * `to` - Last day to export; defaults to the last day with data
+    ///
+    /// # Returns
+    /// * `Ok(HealthExport)` - The samples
+    /// * `Err(String)` - If there are no weights or log entries in the range
+    pub fn collect(
+        food_repo: &FoodRepository,
+        log_repo: &LogRepository,
+        profile_repo: &ProfileRepository,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    ) -> Result<Self, String> {
+        let in_range = |date: NaiveDate| from.is_none_or(|from| date >= from) && to.is_none_or(|to| date <= to);
+        let foods = food_repo.get_foods();
+
+        let mut nutrition: Vec<NutritionSample> = log_repo
+            .get_logs()
+            .into_iter()
+            .filter(|log| in_range(log.date))
+            .flat_map(|log| &log.entries)
+            .filter_map(|entry| {
+                let food = foods.get(&entry.food_id)?;
+                let servings = entry.servings_of(food);
+                Some(NutritionSample {
+                    time: entry.timestamp,
+                    food: food.name.clone(),
+                    meal: entry.meal,
+                    calories: entry.calories_of(food),
+                    macros: food.macros.scaled(servings),
+                })
+            })
+            .collect();
+        nutrition.sort_by_key(|sample| sample.time);
+
+        let mut weights: Vec<WeightSample> = profile_repo
+            .get_profile()
+            .map(|profile| {
+                profile
+                    .daily_profiles
+                    .iter()
+                    .filter(|daily| daily.carried_from.is_none() && in_range(daily.date))
+                    .filter_map(|daily| Some(WeightSample { time: noon(daily.date)?, weight: daily.weight }))
+                    .collect()
+            })
+            .unwrap_or_default();
+        weights.sort_by_key(|sample| sample.time);
+
+        let dates = nutrition.iter().map(|sample| sample.time.date_naive())
+            .chain(weights.iter().map(|sample| sample.time.date_naive()));
+        let (Some(first), Some(last)) = (dates.clone().min(), dates.max()) else {
+            return Err(Message::NothingToExport.text().to_string());
+        };
+        Ok(HealthExport { from: from.unwrap_or(first), to: to.unwrap_or(last), weights, nutrition })
+    }
+
+    /// Renders the samples in a health app's format
+    pub fn render(&self, format: HealthFormat) -> String {
+        match format {
+            HealthFormat::AppleHealth => self.to_apple_health(),
+            HealthFormat::GoogleFit => self.to_google_fit(),
+        }
+    }
+
+    /// Writes the export into a directory
+    ///
+    /// # Returns
+    /// * `Ok(PathBuf)` - Path of the written file, e.g. `apple-health-2024-03-01-to-2024-03-31.xml`
+    /// * `Err(String)` - If the directory or file cannot be written
+    pub fn write(&self, format: HealthFormat, output_dir: &Path) -> Result<PathBuf, String> {
+        fs::create_dir_all(output_dir).map_err(|e| Message::ErrorCreating.format(&[&output_dir.display(), &e]))?;
+        let path = output_dir.join(format!(
+            "{}-{}-to-{}.{}",
+            format.file_prefix(),
+            self.from.format("%Y-%m-%d"),
+            self.to.format("%Y-%m-%d"),
+            format.extension()
+        ));
+        fs::write(&path, self.render(format)).map_err(|e| Message::ErrorWriting.format(&[&path.display(), &e]))?;
+        Ok(path)
+    }
+
+    /// Apple Health `export.xml` records
+    fn to_apple_health(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<HealthData locale=\"en_US\">\n");
+        xml.push_str(&format!(" <ExportDate value=\"{}\"/>\n", apple_time(Local::now())));
+
+        for sample in &self.weights {
+            xml.push_str(&apple_record("HKQuantityTypeIdentifierBodyMass", "kg", sample.time, sample.weight, None));
+        }
+        for sample in &self.nutrition {
+            let records = [
+                ("HKQuantityTypeIdentifierDietaryEnergyConsumed", "kcal", sample.calories),
+                ("HKQuantityTypeIdentifierDietaryProtein", "g", sample.macros.protein),
+                ("HKQuantityTypeIdentifierDietaryCarbohydrates", "g", sample.macros.carbs),
+                ("HKQuantityTypeIdentifierDietaryFatTotal", "g", sample.macros.fat),
+                ("HKQuantityTypeIdentifierDietaryFiber", "g", sample.macros.fiber),
+            ];
+            for (kind, unit, value) in records.into_iter().filter(|(_, _, value)| *value > 0.0) {
+                xml.push_str(&apple_record(kind, unit, sample.time, value, Some(&sample.food)));
+            }
+        }
+        xml.push_str("</HealthData>\n");
+        xml
+    }
+
+    /// Google Fit data points, one CSV row each
+    fn to_google_fit(&self) -> String {
+        let mut csv = String::from(
+            "data_type,start_time,end_time,weight,calories,protein,carbs.total,fat.total,dietary_fiber,meal_type,food_item\n",
+        );
+        for sample in &self.weights {
+            let time = sample.time.to_rfc3339();
+            csv.push_str(&format!("com.google.weight,{},{},{:.2},,,,,,,\n", time, time, sample.weight));
+        }
+        for sample in &self.nutrition {
+            let time = sample.time.to_rfc3339();
+            let nutrients: Vec<String> = [sample.calories, sample.macros.protein, sample.macros.carbs, sample.macros.fat, sample.macros.fiber]
+                .iter()
+                .map(|value| if *value > 0.0 { format!("{:.2}", value) } else { String::new() })
+                .collect();
+            csv.push_str(&format!(
+                "com.google.nutrition,{},{},,{},{},{}\n",
+                time,
+                time,
+                nutrients.join(","),
+                fit_meal_type(sample.meal),
+                csv_field(&sample.food)
+            ));
+        }
+        csv
+    }
+}
+
+/// Noon of a day in local time
+fn noon(date: NaiveDate) -> Option<DateTime<Local>> {
+    Local.from_local_datetime(&date.and_time(NaiveTime::from_hms_opt(12, 0, 0)?)).earliest()
+}
+
+/// Time in the format of Apple Health exports, e.g. `2024-03-05 08:00:00 +0100`
+fn apple_time(time: DateTime<Local>) -> String {
+    time.format("%Y-%m-%d %H:%M:%S %z").to_string()
+}
+
+/// One Apple Health `Record` element
+fn apple_record(kind: &str, unit: &str, time: DateTime<Local>, value: f64, food: Option<&str>) -> String {
+    let time = apple_time(time);
+    let attributes = format!(
+        "type=\"{}\" sourceName=\"{}\" unit=\"{}\" creationDate=\"{}\" startDate=\"{}\" endDate=\"{}\" value=\"{:.2}\"",
+        kind, SOURCE_NAME, unit, time, time, time, value
+    );
+    match food {
+        Some(food) => format!(
+            " <Record {}>\n  <MetadataEntry key=\"HKFoodType\" value=\"{}\"/>\n </Record>\n",
+            attributes,
+            escape_xml(food)
+        ),
+        None => format!(" <Record {}/>\n", attributes),
+    }
+}
+
+/// Google Fit `meal_type` value: 1 unknown, 2 breakfast, 3 lunch, 4 dinner, 5 snack
+fn fit_meal_type(meal: Option<MealSlot>) -> u8 {
+    match meal {
+        None => 1,
+        Some(MealSlot::Breakfast) => 2,
+        Some(MealSlot::Lunch) => 3,
+        Some(MealSlot::Dinner) => 4,
+        Some(MealSlot::Snack) => 5,
+    }
+}
+
+/// Escapes text for XML attribute values
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Quotes a CSV field when it contains a comma, quote, or line break
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
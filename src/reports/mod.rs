@@ -0,0 +1,24 @@
+//! # Reports Module
+//!
+//! This module produces files meant to be kept or shared outside the
+//! interactive session. They are written to the `reports` directory inside
+//! the data directory (see `AppConfig::reports_dir`).
+//!
+//! ## Module Organization
+//!
+//! - `charts`: Monthly weight-trend and calorie-intake PNG charts
+//! - `daily_summary`: Summary of the day printed on exit, optionally kept in a journal
+//! - `document`: Text reports rendered as Markdown or HTML
+//! - `energy_balance`: Weekly intake against weight change and the adaptive TDEE
+//! - `health_export`: Weights and nutrition for Apple Health (XML) and Google Fit (CSV)
+//! - `weekly_report`: Weekly calories, weights, and top foods for sharing
+//! - `year_review`: Annual summary of logging, weight, streaks, and adherence
+
+// src/reports/mod.rs
+pub mod charts;
+pub mod daily_summary;
+pub mod document;
+pub mod energy_balance;
+pub mod health_export;
+pub mod weekly_report;
+pub mod year_review;
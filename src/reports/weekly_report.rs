@@ -0,0 +1,304 @@
+//! # Weekly Report
+//!
+//! This module summarizes one week of food logs and weights in a form that
+//! can be shared, for example with a coach:
+//!
+//! - **Overview**: Days logged, average calories per logged day, and days
+//!   within the calorie target
+//! - **Calories per day**: Calories, target, difference, and macronutrients
+//!   for each day of the week
+//! - **Weight**: Every weight entered during the week, with the change since
+//!   the first one, ready to be charted
+//! - **Top foods**: The foods that contributed the most calories
+//! - **Notes and mood**: Each day's note and mood and energy ratings next to
+//!   its calories, to see how eating and feeling go together
+//!
+//! Weeks start on the configured first day of the week (see `models::week`).
+//! Like the year in review, weights carried forward from an earlier day are
+//! not measurements and are left out, and a day is within target when food
+//! was logged and the calories consumed do not exceed its target.
+//!
+//! The report is exported as Markdown or HTML through `reports::document`.
+
+// src/reports/weekly_report.rs
+use std::collections::HashMap;
+
+use chrono::{Datelike, Duration, NaiveDate};
+
+use yada_core::models::food::Macros;
+use yada_core::models::log::{DayNote, RATING_MAX};
+use yada_core::models::week::WeekStart;
+use yada_core::repositories::food_repository::FoodRepository;
+use yada_core::repositories::log_repository::LogRepository;
+use yada_core::repositories::exercise_repository::ExerciseRepository;
+use yada_core::repositories::profile_repository::ProfileRepository;
+use yada_core::strategies::calorie_calculator::CalorieCalculatorFactory;
+use yada_core::strategies::target_modifier::TargetPipeline;
+
+use super::document::Document;
+use super::year_review::{FoodCount, WeightPoint};
+use crate::ui::i18n::Message;
+
+/// Number of foods listed as top foods
+const TOP_FOOD_COUNT: usize = 5;
+
+/// Calories and target for one day of the week
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayRow {
+    /// The day
+    pub date: NaiveDate,
+
+    /// Calories consumed, or `None` if nothing was logged
+    pub calories: Option<f64>,
+
+    /// Macronutrients consumed
+    pub macros: Macros,
+
+    /// Calorie target, if the profile has values for the day
+    pub target: Option<f64>,
+
+    /// Note, mood, and energy written for the day
+    pub note: DayNote,
+}
+
+impl DayRow {
+    /// Returns true if food was logged and stayed within the target
+    pub fn within_target(&self) -> bool {
+        matches!((self.calories, self.target), (Some(calories), Some(target)) if calories <= target)
+    }
+}
+
+/// Summary of one week
+#[derive(Debug, Clone)]
+pub struct WeeklyReport {
+    /// First day of the week
+    pub start: NaiveDate,
+
+    /// First day of the week setting used
+    pub week_start: WeekStart,
+
+    /// Each day of the week, in order
+    pub days: Vec<DayRow>,
+
+    /// Weights entered during the week, earliest first
+    pub weights: Vec<WeightPoint>,
+
+    /// Foods with the most calories, most first
+    pub top_foods: Vec<FoodCount>,
+
+    /// "Estimate only" notice when targets use population defaults
+    pub estimate_notice: Option<String>,
+}
+
+impl WeeklyReport {
+    /// Collects the report for the week containing a date
+    ///
+    /// # Arguments
+    /// * `food_repo` - Food database used to resolve logged food IDs
+    /// * `log_repo` - Food consumption logs
+    /// * `profile_repo` - User profile with daily weights
+    /// * `exercise_repo` - Exercise log, for the exercise credit of the targets
+    /// * `date` - Any day of the week to report on
+    /// * `week_start` - First day of the week
+    ///
+    /// # Returns
+    /// * `Ok(WeeklyReport)` - The summary
+    /// * `Err(String)` - If the week has no logs and no weights
+    pub fn collect(
+        food_repo: &FoodRepository,
+        log_repo: &LogRepository,
+        profile_repo: &ProfileRepository,
+        exercise_repo: &ExerciseRepository,
+        date: NaiveDate,
+        week_start: WeekStart,
+    ) -> Result<Self, String> {
+        let start = week_start.week_of(date);
+        let end = start + Duration::days(6);
+
+        let factory = CalorieCalculatorFactory::from_repositories(food_repo, log_repo);
+        let pipeline = TargetPipeline::standard().with_exercise_credit(exercise_repo);
+        let profile = profile_repo.get_profile();
+        let foods = food_repo.get_foods();
+
+        let mut counts: HashMap<&str, (usize, f64)> = HashMap::new();
+        let days: Vec<DayRow> = (0..7).map(|offset| {
+            let date = start + Duration::days(offset);
+            let note = log_repo.get_log(date).map(|log| log.note.clone()).unwrap_or_default();
+            let log = log_repo.get_log(date).filter(|log| !log.entries.is_empty());
+            for entry in log.iter().flat_map(|log| &log.entries) {
+                let count = counts.entry(entry.food_id.as_str()).or_insert((0, 0.0));
+                count.0 += 1;
+                count.1 += entry.logged_calories(foods.get(&entry.food_id));
+            }
+            let target = match (profile, profile.and_then(|p| factory.calculator_for(p, date))) {
+                (Some(p), Some(c)) if p.latest_daily_profile(date).is_some() => {
+                    Some(c.calculate_target_with(p, date, &pipeline).target).filter(|target| *target > 0.0)
+                }
+                _ => None,
+            };
+            DayRow {
+                date,
+                calories: log.map(|log| log.total_calories(foods)),
+                macros: log.map_or(Macros::default(), |log| log.total_macros(foods)),
+                target,
+                note,
+            }
+        }).collect();
+
+        let mut weights: Vec<WeightPoint> = profile
+            .map(|p| p.daily_profiles.iter()
+                .filter(|d| d.carried_from.is_none() && d.date >= start && d.date <= end)
+                .map(|d| WeightPoint { date: d.date, weight: d.weight })
+                .collect())
+            .unwrap_or_default();
+        weights.sort_by_key(|point| point.date);
+
+        if days.iter().all(|day| day.calories.is_none() && day.note.is_empty()) && weights.is_empty() {
+            return Err(Message::NoDataInWeek.format(&[&start.format("%Y-%m-%d")]));
+        }
+
+        let mut top_foods: Vec<FoodCount> = counts
+            .into_iter()
+            .map(|(id, (entries, calories))| FoodCount {
+                name: foods.get(id).map_or(id.to_string(), |food| food.name.clone()),
+                entries,
+                calories,
+            })
+            .collect();
+        top_foods.sort_by(|a, b| b.calories.total_cmp(&a.calories).then_with(|| a.name.cmp(&b.name)));
+        top_foods.truncate(TOP_FOOD_COUNT);
+
+        Ok(WeeklyReport {
+            start,
+            week_start,
+            days,
+            weights,
+            top_foods,
+            estimate_notice: profile.and_then(|p| p.body_metrics(end).estimate_notice()),
+        })
+    }
+
+    /// Last day of the week
+    pub fn end(&self) -> NaiveDate {
+        self.start + Duration::days(6)
+    }
+
+    /// Builds the exportable document
+    pub fn to_document(&self) -> Document {
+        let mut doc = Document::new(&Message::WeeklyReportTitle.format(&[&self.start.format("%Y-%m-%d"), &self.end().format("%Y-%m-%d")]));
+
+        let logged: Vec<f64> = self.days.iter().filter_map(|day| day.calories).collect();
+        let with_target = self.days.iter().filter(|day| day.calories.is_some() && day.target.is_some()).count();
+        let within = self.days.iter().filter(|day| day.within_target()).count();
+
+        doc.heading(Message::OverviewHeading.text());
+        let mut overview = vec![Message::DaysLoggedOfWeek.format(&[&logged.len()])];
+        if !logged.is_empty() {
+            overview.push(Message::AverageCaloriesLine.format(&[&(logged.iter().sum::<f64>() / logged.len() as f64)]));
+        }
+        if with_target > 0 {
+            overview.push(Message::DaysWithinTargetLine.format(&[&within, &with_target]));
+        }
+        overview.push(Message::WeeksStartOn.format(&[&Message::from(self.week_start)]));
+        doc.list(overview);
+        if let Some(notice) = &self.estimate_notice {
+            doc.paragraph(notice);
+        }
+
+        doc.heading(Message::CaloriesPerDayHeading.text());
+        let rows = self.days.iter().map(|day| {
+            let mut row = vec![format!("{} {}", Message::from(day.date.weekday()), day.date.format("%Y-%m-%d"))];
+            match day.calories {
+                Some(calories) => row.push(format!("{:.0}", calories)),
+                None => row.push("-".to_string()),
+            }
+            row.push(day.target.map_or("-".to_string(), |target| format!("{:.0}", target)));
+            row.push(match (day.calories, day.target) {
+                (Some(calories), Some(target)) => format!("{:+.0}", calories - target),
+                _ => "-".to_string(),
+            });
+            if day.calories.is_some() {
+                row.extend([
+                    format!("{:.1}", day.macros.protein),
+                    format!("{:.1}", day.macros.carbs),
+                    format!("{:.1}", day.macros.fat),
+                ]);
+            } else {
+                row.extend(std::iter::repeat_n("-".to_string(), 3));
+            }
+            row
+        }).collect();
+        doc.table(&[
+            Message::DayColumn.text(),
+            Message::CaloriesColumn.text(),
+            Message::TargetColumn.text(),
+            Message::DifferenceColumn.text(),
+            Message::ProteinGrams.text(),
+            Message::CarbsGrams.text(),
+            Message::FatGrams.text(),
+        ], rows);
+
+        doc.heading(Message::NotesAndMoodHeading.text());
+        let noted: Vec<&DayRow> = self.days.iter().filter(|day| !day.note.is_empty()).collect();
+        if noted.is_empty() {
+            doc.paragraph(Message::NoNotesThisWeek.text());
+        } else {
+            let mut averages = Vec::new();
+            for (label, ratings) in [
+                (Message::AverageMood.text(), noted.iter().filter_map(|day| day.note.mood).collect::<Vec<u8>>()),
+                (Message::AverageEnergy.text(), noted.iter().filter_map(|day| day.note.energy).collect()),
+            ] {
+                if !ratings.is_empty() {
+                    let average = ratings.iter().map(|rating| f64::from(*rating)).sum::<f64>() / ratings.len() as f64;
+                    averages.push(Message::RatingAverageLine.format(&[&label, &average, &RATING_MAX, &ratings.len()]));
+                }
+            }
+            if !averages.is_empty() {
+                doc.list(averages);
+            }
+            let rating = |rating: Option<u8>| rating.map_or("-".to_string(), |rating| format!("{}/{}", rating, RATING_MAX));
+            let rows = noted.iter().map(|day| vec![
+                format!("{} {}", Message::from(day.date.weekday()), day.date.format("%Y-%m-%d")),
+                rating(day.note.mood),
+                rating(day.note.energy),
+                day.calories.map_or("-".to_string(), |calories| format!("{:.0}", calories)),
+                day.note.text.clone().unwrap_or_default(),
+            ]).collect();
+            doc.table(&[
+                Message::DayColumn.text(),
+                Message::MoodLabel.text(),
+                Message::EnergyLabel.text(),
+                Message::CaloriesColumn.text(),
+                Message::NoteLabel.text(),
+            ], rows);
+        }
+
+        doc.heading(Message::WeightHeading.text());
+        match self.weights.first() {
+            Some(first) => {
+                let rows = self.weights.iter().map(|point| vec![
+                    point.date.format("%Y-%m-%d").to_string(),
+                    format!("{:.1}", point.weight),
+                    format!("{:+.1}", point.weight - first.weight),
+                ]).collect();
+                doc.table(&[Message::DateColumn.text(), Message::WeightKg.text(), Message::ChangeKg.text()], rows);
+            }
+            None => doc.paragraph(Message::NoWeighInsThisWeek.text()),
+        }
+
+        doc.heading(Message::TopFoodsHeading.text());
+        if self.top_foods.is_empty() {
+            doc.paragraph(Message::NoFoodsLogged.text());
+        } else {
+            let rows = self.top_foods.iter().enumerate().map(|(index, food)| vec![
+                (index + 1).to_string(),
+                food.name.clone(),
+                format!("{:.0}", food.calories),
+                food.entries.to_string(),
+            ]).collect();
+            doc.table(&["#", Message::FoodColumn.text(), Message::CaloriesColumn.text(), Message::TimesLoggedColumn.text()], rows);
+        }
+
+        doc
+    }
+}
@@ -0,0 +1,514 @@
+//! # Year in Review
+//!
+//! This module summarizes one calendar year of food logs and weights:
+//!
+//! - **Overview**: Days logged and the weight change over the year
+//! - **Quarters**: Average calories and macronutrients per logged day
+//! - **Most-logged foods**: The foods logged most often, with their calories
+//! - **Streaks**: Longest run of logged days, of days within the calorie
+//!   target, and of weeks with at least one logged day
+//! - **Adherence**: Share of days within target for each month, with the best
+//!   and worst month
+//!
+//! A day counts toward adherence when food was logged and a daily profile
+//! (and therefore a target) exists for it; it is within target when the
+//! calories consumed do not exceed the target. Weights carried forward from
+//! an earlier day (see `DailyProfile::carried_from`) are not measurements
+//! and are ignored. For the current year, the review covers January 1 up
+//! to today. Weeks start on the configured first day of the week (see
+//! `models::week`), so the first and last week may reach into the
+//! neighbouring years; only days of the covered year are counted.
+//!
+//! The review is exported as Markdown or HTML through `reports::document`.
+
+// src/reports/year_review.rs
+use std::collections::HashMap;
+
+use chrono::{Datelike, Duration, NaiveDate};
+
+use yada_core::models::food::Macros;
+use yada_core::models::week::{self, WeekStart};
+use yada_core::repositories::food_repository::FoodRepository;
+use yada_core::repositories::log_repository::LogRepository;
+use yada_core::repositories::exercise_repository::ExerciseRepository;
+use yada_core::repositories::profile_repository::ProfileRepository;
+use yada_core::strategies::calorie_calculator::CalorieCalculatorFactory;
+use yada_core::strategies::target_modifier::TargetPipeline;
+
+use super::document::Document;
+use crate::ui::i18n::{self, Message};
+
+/// Number of foods listed as most logged
+const TOP_FOOD_COUNT: usize = 10;
+
+/// A weight recorded on a day
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeightPoint {
+    /// Day the weight was recorded
+    pub date: NaiveDate,
+
+    /// Weight in kilograms
+    pub weight: f64,
+}
+
+/// Averages for the logged days of one quarter
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct QuarterSummary {
+    /// Days with at least one log entry
+    pub days_logged: usize,
+
+    /// Calories consumed on those days
+    pub calories: f64,
+
+    /// Macronutrients consumed on those days
+    pub macros: Macros,
+}
+
+impl QuarterSummary {
+    /// Returns the average calories and macros per logged day, if any day was logged
+    pub fn averages(&self) -> Option<(f64, Macros)> {
+        if self.days_logged == 0 {
+            return None;
+        }
+        let days = self.days_logged as f64;
+        Some((self.calories / days, self.macros.scaled(1.0 / days)))
+    }
+}
+
+/// How often a food was logged
+#[derive(Debug, Clone, PartialEq)]
+pub struct FoodCount {
+    /// Food name (the ID for foods no longer in the database)
+    pub name: String,
+
+    /// Number of log entries
+    pub entries: usize,
+
+    /// Calories from those entries
+    pub calories: f64,
+}
+
+/// A run of consecutive days
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Streak {
+    /// First day of the run
+    pub start: NaiveDate,
+
+    /// Number of days in the run
+    pub days: usize,
+}
+
+impl Streak {
+    /// Last day of the run
+    pub fn end(&self) -> NaiveDate {
+        self.start + Duration::days(self.days as i64 - 1)
+    }
+}
+
+/// A run of consecutive weeks
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeekStreak {
+    /// First day of the first week
+    pub start: NaiveDate,
+
+    /// Number of weeks in the run
+    pub weeks: usize,
+}
+
+impl WeekStreak {
+    /// First day of the last week of the run
+    pub fn last_week(&self) -> NaiveDate {
+        self.start + Duration::weeks(self.weeks as i64 - 1)
+    }
+}
+
+/// Days within target for one month
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MonthAdherence {
+    /// First day of the month
+    pub month: NaiveDate,
+
+    /// Logged days with a target
+    pub evaluated: usize,
+
+    /// Of those, days that stayed within the target
+    pub on_target: usize,
+}
+
+impl MonthAdherence {
+    /// Share of evaluated days within target, from 0 to 1
+    pub fn rate(&self) -> f64 {
+        if self.evaluated == 0 {
+            0.0
+        } else {
+            self.on_target as f64 / self.evaluated as f64
+        }
+    }
+}
+
+/// Summary of one calendar year
+#[derive(Debug, Clone)]
+pub struct YearInReview {
+    /// The year summarized
+    pub year: i32,
+
+    /// First day covered (January 1)
+    pub first: NaiveDate,
+
+    /// Last day covered (December 31, or today for the current year)
+    pub last: NaiveDate,
+
+    /// Days with at least one log entry
+    pub days_logged: usize,
+
+    /// First and last weight recorded in the year
+    pub weights: Option<(WeightPoint, WeightPoint)>,
+
+    /// Averages for each quarter, January–March first
+    pub quarters: [QuarterSummary; 4],
+
+    /// Most-logged foods, most entries first
+    pub top_foods: Vec<FoodCount>,
+
+    /// Longest run of logged days
+    pub logging_streak: Option<Streak>,
+
+    /// Longest run of days within the calorie target
+    pub target_streak: Option<Streak>,
+
+    /// First day of the week used for weekly streaks
+    pub week_start: WeekStart,
+
+    /// Longest run of weeks with at least one logged day
+    pub weekly_streak: Option<WeekStreak>,
+
+    /// Adherence for each month that has at least one evaluated day
+    pub months: Vec<MonthAdherence>,
+
+    /// "Estimate only" notice when targets use population defaults
+    pub estimate_notice: Option<String>,
+}
+
+impl YearInReview {
+    /// Collects the review for a year
+    ///
+    /// # Arguments
+    /// * `food_repo` - Food database used to resolve logged food IDs
+    /// * `log_repo` - Food consumption logs
+    /// * `profile_repo` - User profile with daily weights
+    /// * `exercise_repo` - Exercise log, for the exercise credit of the targets
+    /// * `year` - Calendar year to summarize
+    /// * `today` - Current date; later days of the current year are not covered
+    /// * `week_start` - First day of the week for weekly streaks
+    ///
+    /// # Returns
+    /// * `Ok(YearInReview)` - The summary
+    /// * `Err(String)` - If the year lies in the future or has no logs or weights
+    pub fn collect(
+        food_repo: &FoodRepository,
+        log_repo: &LogRepository,
+        profile_repo: &ProfileRepository,
+        exercise_repo: &ExerciseRepository,
+        year: i32,
+        today: NaiveDate,
+        week_start: WeekStart,
+    ) -> Result<Self, String> {
+        let first = NaiveDate::from_ymd_opt(year, 1, 1).ok_or_else(|| Message::InvalidYear.format(&[&year]))?;
+        let last = NaiveDate::from_ymd_opt(year, 12, 31).unwrap_or(first).min(today);
+        if last < first {
+            return Err(Message::YearNotStarted.format(&[&year]));
+        }
+
+        let factory = CalorieCalculatorFactory::from_repositories(food_repo, log_repo);
+        let pipeline = TargetPipeline::standard().with_exercise_credit(exercise_repo);
+        let profile = profile_repo.get_profile();
+        let foods = food_repo.get_foods();
+
+        let mut review = YearInReview {
+            year,
+            first,
+            last,
+            days_logged: 0,
+            weights: None,
+            quarters: [QuarterSummary::default(); 4],
+            top_foods: Vec::new(),
+            logging_streak: None,
+            target_streak: None,
+            week_start,
+            weekly_streak: None,
+            months: Vec::new(),
+            estimate_notice: profile.and_then(|p| p.body_metrics(last).estimate_notice()),
+        };
+        let mut counts: HashMap<&str, (usize, f64)> = HashMap::new();
+        let mut months: Vec<MonthAdherence> = Vec::new();
+        let mut logging_run = StreakTracker::default();
+        let mut target_run = StreakTracker::default();
+
+        let mut date = first;
+        while date <= last {
+            let log = log_repo.get_log(date).filter(|log| !log.entries.is_empty());
+            let intake = log.map_or(0.0, |log| log.total_calories(foods));
+            let target = match (profile, profile.and_then(|p| factory.calculator_for(p, date))) {
+                (Some(p), Some(c)) if p.latest_daily_profile(date).is_some() => {
+                    Some(c.calculate_target_with(p, date, &pipeline).target).filter(|target| *target > 0.0)
+                }
+                _ => None,
+            };
+            let on_target = log.is_some() && target.is_some_and(|target| intake <= target);
+
+            logging_run.record(date, log.is_some());
+            target_run.record(date, on_target);
+
+            if let Some(log) = log {
+                review.days_logged += 1;
+                let quarter = &mut review.quarters[date.month0() as usize / 3];
+                quarter.days_logged += 1;
+                quarter.calories += intake;
+                quarter.macros += log.total_macros(foods);
+
+                for entry in &log.entries {
+                    let count = counts.entry(entry.food_id.as_str()).or_insert((0, 0.0));
+                    count.0 += 1;
+                    count.1 += entry.logged_calories(foods.get(&entry.food_id));
+                }
+
+                if target.is_some() {
+                    let month = date.with_day(1).unwrap_or(date);
+                    if months.last().is_none_or(|m| m.month != month) {
+                        months.push(MonthAdherence { month, evaluated: 0, on_target: 0 });
+                    }
+                    if let Some(adherence) = months.last_mut() {
+                        adherence.evaluated += 1;
+                        adherence.on_target += on_target as usize;
+                    }
+                }
+            }
+            date += Duration::days(1);
+        }
+
+        let mut measured: Vec<WeightPoint> = profile
+            .map(|p| p.daily_profiles.iter()
+                .filter(|d| d.carried_from.is_none() && d.date >= first && d.date <= last)
+                .map(|d| WeightPoint { date: d.date, weight: d.weight })
+                .collect())
+            .unwrap_or_default();
+        measured.sort_by_key(|point| point.date);
+        review.weights = measured.first().copied().zip(measured.last().copied());
+
+        if review.days_logged == 0 && review.weights.is_none() {
+            return Err(Message::NoDataInYear.format(&[&year]));
+        }
+
+        let mut top_foods: Vec<FoodCount> = counts
+            .into_iter()
+            .map(|(id, (entries, calories))| FoodCount {
+                name: foods.get(id).map_or(id.to_string(), |food| food.name.clone()),
+                entries,
+                calories,
+            })
+            .collect();
+        top_foods.sort_by(|a, b| b.entries.cmp(&a.entries).then_with(|| a.name.cmp(&b.name)));
+        top_foods.truncate(TOP_FOOD_COUNT);
+
+        review.top_foods = top_foods;
+        review.logging_streak = logging_run.longest;
+        review.target_streak = target_run.longest;
+        review.weekly_streak = longest_weekly_streak(log_repo, food_repo, first, last, week_start);
+        review.months = months;
+        Ok(review)
+    }
+
+    /// Month with the highest adherence (the earliest one on ties)
+    pub fn best_month(&self) -> Option<&MonthAdherence> {
+        self.months.iter().rev().max_by(|a, b| a.rate().total_cmp(&b.rate()))
+    }
+
+    /// Month with the lowest adherence (the earliest one on ties)
+    pub fn worst_month(&self) -> Option<&MonthAdherence> {
+        self.months.iter().min_by(|a, b| a.rate().total_cmp(&b.rate()))
+    }
+
+    /// Builds the exportable document
+    pub fn to_document(&self) -> Document {
+        let mut doc = Document::new(&Message::YearReviewTitle.format(&[&self.year]));
+        let covered = (self.last - self.first).num_days() as usize + 1;
+
+        doc.heading(Message::OverviewHeading.text());
+        let mut overview = vec![
+            Message::PeriodLine.format(&[&self.first.format("%Y-%m-%d"), &self.last.format("%Y-%m-%d")]),
+            Message::DaysLoggedOfYear.format(&[&self.days_logged, &covered, &(self.days_logged as f64 * 100.0 / covered as f64)]),
+        ];
+        match self.weights {
+            Some((start, end)) if start.date != end.date => overview.push(Message::WeightChangeLine.format(&[
+                &start.weight,
+                &short_date(start.date),
+                &end.weight,
+                &short_date(end.date),
+                &(end.weight - start.weight),
+            ])),
+            Some((point, _)) => overview.push(Message::WeightOnceLine.format(&[&point.weight, &short_date(point.date)])),
+            None => overview.push(Message::WeightNoneLine.text().to_string()),
+        }
+        doc.list(overview);
+
+        doc.heading(Message::QuarterHeading.text());
+        let rows = self.quarters.iter().enumerate().map(|(index, quarter)| {
+            let mut row = vec![format!("Q{}", index + 1), quarter.days_logged.to_string()];
+            match quarter.averages() {
+                Some((calories, macros)) => row.extend([
+                    format!("{:.0}", calories),
+                    format!("{:.1}", macros.protein),
+                    format!("{:.1}", macros.carbs),
+                    format!("{:.1}", macros.fat),
+                ]),
+                None => row.extend(std::iter::repeat_n("-".to_string(), 4)),
+            }
+            row
+        }).collect();
+        doc.table(&[
+            Message::QuarterColumn.text(),
+            Message::DaysLoggedColumn.text(),
+            Message::CaloriesColumn.text(),
+            Message::ProteinGrams.text(),
+            Message::CarbsGrams.text(),
+            Message::FatGrams.text(),
+        ], rows);
+
+        doc.heading(Message::MostLoggedHeading.text());
+        if self.top_foods.is_empty() {
+            doc.paragraph(Message::NoFoodsLogged.text());
+        } else {
+            let rows = self.top_foods.iter().enumerate().map(|(index, food)| vec![
+                (index + 1).to_string(),
+                food.name.clone(),
+                food.entries.to_string(),
+                format!("{:.0}", food.calories),
+            ]).collect();
+            doc.table(&["#", Message::FoodColumn.text(), Message::TimesLoggedColumn.text(), Message::CaloriesColumn.text()], rows);
+        }
+
+        doc.heading(Message::StreaksHeading.text());
+        doc.list(vec![
+            describe_streak(Message::LongestLoggingStreak.text(), self.logging_streak),
+            describe_streak(Message::LongestTargetStreak.text(), self.target_streak),
+            describe_weekly_streak(self.weekly_streak, self.week_start),
+        ]);
+
+        doc.heading(Message::AdherenceHeading.text());
+        if let Some(notice) = &self.estimate_notice {
+            doc.paragraph(notice);
+        }
+        match (self.best_month(), self.worst_month()) {
+            (Some(best), Some(worst)) => {
+                let mut summary = vec![Message::BestMonth.format(&[&describe_month(best)])];
+                if worst.month != best.month {
+                    summary.push(Message::WorstMonth.format(&[&describe_month(worst)]));
+                }
+                doc.list(summary);
+                let rows = self.months.iter().map(|month| vec![
+                    i18n::month_name(month.month).to_string(),
+                    month.on_target.to_string(),
+                    month.evaluated.to_string(),
+                    format!("{:.0}%", month.rate() * 100.0),
+                ]).collect();
+                doc.table(&[
+                    Message::MonthColumn.text(),
+                    Message::DaysWithinTargetColumn.text(),
+                    Message::DaysWithTargetColumn.text(),
+                    Message::AdherenceColumn.text(),
+                ], rows);
+            }
+            _ => doc.paragraph(Message::NoTargetDays.text()),
+        }
+
+        doc
+    }
+}
+
+/// Tracks the current and longest run of days meeting a condition
+#[derive(Default)]
+struct StreakTracker {
+    /// Run that includes the most recent day, if it met the condition
+    current: Option<Streak>,
+    /// Longest run so far
+    longest: Option<Streak>,
+}
+
+impl StreakTracker {
+    /// Records whether the next day met the condition
+    fn record(&mut self, date: NaiveDate, met: bool) {
+        if !met {
+            self.current = None;
+            return;
+        }
+        let streak = match self.current {
+            Some(streak) => Streak { days: streak.days + 1, ..streak },
+            None => Streak { start: date, days: 1 },
+        };
+        self.current = Some(streak);
+        if self.longest.is_none_or(|longest| streak.days > longest.days) {
+            self.longest = Some(streak);
+        }
+    }
+}
+
+/// Finds the longest run of consecutive weeks with a logged day in a period
+fn longest_weekly_streak(
+    log_repo: &LogRepository,
+    food_repo: &FoodRepository,
+    first: NaiveDate,
+    last: NaiveDate,
+    week_start: WeekStart,
+) -> Option<WeekStreak> {
+    let logs = log_repo.get_logs_between(first, last);
+    let mut current: Option<WeekStreak> = None;
+    let mut longest: Option<WeekStreak> = None;
+    for totals in week::weekly_totals(logs, food_repo.get_foods(), week_start) {
+        let streak = match current {
+            Some(streak) if streak.last_week() + Duration::weeks(1) == totals.start => {
+                WeekStreak { weeks: streak.weeks + 1, ..streak }
+            }
+            _ => WeekStreak { start: totals.start, weeks: 1 },
+        };
+        current = Some(streak);
+        if longest.is_none_or(|longest| streak.weeks > longest.weeks) {
+            longest = Some(streak);
+        }
+    }
+    longest
+}
+
+/// Describes the weekly streak, e.g. "Longest weekly streak: 8 weeks (weeks of Mar 4 to Apr 22, starting Monday)"
+fn describe_weekly_streak(streak: Option<WeekStreak>, week_start: WeekStart) -> String {
+    let label = Message::LongestWeeklyStreak.text();
+    match streak {
+        Some(streak) if streak.weeks == 1 => Message::WeeklyStreakOne.format(&[&label, &short_date(streak.start), &Message::from(week_start)]),
+        Some(streak) => Message::WeeklyStreakMany.format(&[
+            &label,
+            &streak.weeks,
+            &short_date(streak.start),
+            &short_date(streak.last_week()),
+            &Message::from(week_start),
+        ]),
+        None => Message::StreakNone.format(&[&label]),
+    }
+}
+
+/// Formats a date as "Mar 5"
+fn short_date(date: NaiveDate) -> String {
+    date.format(Message::ShortDateFormat.text()).to_string()
+}
+
+/// Describes a streak, e.g. "Longest logging streak: 12 days (Mar 1 to Mar 12)"
+fn describe_streak(label: &str, streak: Option<Streak>) -> String {
+    match streak {
+        Some(streak) if streak.days == 1 => Message::StreakOneDay.format(&[&label, &short_date(streak.start)]),
+        Some(streak) => Message::StreakManyDays.format(&[&label, &streak.days, &short_date(streak.start), &short_date(streak.end())]),
+        None => Message::StreakNone.format(&[&label]),
+    }
+}
+
+/// Describes a month's adherence, e.g. "March (18 of 25 days within target, 72%)"
+fn describe_month(month: &MonthAdherence) -> String {
+    Message::MonthAdherenceLine.format(&[&i18n::month_name(month.month), &month.on_target, &month.evaluated, &(month.rate() * 100.0)])
+}
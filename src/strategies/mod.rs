@@ -1,2 +0,0 @@
-// src/strategies/mod.rs
-pub mod calorie_calculator;
\ No newline at end of file
@@ -0,0 +1,135 @@
+//! # Terminal Charts
+//!
+//! This module draws small text charts for the statistics screen, so trends
+//! can be seen at a glance without exporting a report:
+//!
+//! - **Bar chart**: One horizontal bar per row, scaled to the largest value
+//!   or target. A `|` marks the row's target, and the part of a bar beyond
+//!   its target is drawn with `▒` instead of `█`
+//! - **Sparkline**: A single line of block characters (`▁` to `█`) showing
+//!   how a series rises and falls between its lowest and highest value
+//! - **Progress bar**: How much of a target has been reached, with `░` for
+//!   the part still to go; a target passed fills the bar
+//!
+//! The charts only format values; collecting them is left to the caller.
+
+// src/ui/charts.rs
+
+/// Width of a full bar in characters
+pub const BAR_WIDTH: usize = 40;
+
+/// Width of a progress bar in characters
+pub const PROGRESS_WIDTH: usize = 20;
+
+/// Number of days in the statistics calorie chart
+pub const CHART_DAYS: i64 = 14;
+
+/// Number of most recent weigh-ins in the statistics weight sparkline
+pub const SPARKLINE_WEIGH_INS: usize = 30;
+
+/// Block characters used by sparklines, lowest first
+const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// One row of a bar chart
+#[derive(Debug, Clone, PartialEq)]
+pub struct BarRow {
+    /// Label printed before the bar
+    pub label: String,
+
+    /// Value of the bar, or `None` if there is nothing to draw
+    pub value: Option<f64>,
+
+    /// Target marked on the row, if any
+    pub target: Option<f64>,
+}
+
+/// Draws a horizontal bar chart
+///
+/// Labels are padded to the longest one, and each bar is followed by its
+/// value (`-` when missing) and, when over target, by how much.
+///
+/// # Arguments
+/// * `rows` - The rows, in display order
+/// * `width` - Width of the longest bar in characters
+///
+/// # Returns
+/// One line per row
+pub fn bar_chart(rows: &[BarRow], width: usize) -> Vec<String> {
+    let label_width = rows.iter().map(|row| row.label.chars().count()).max().unwrap_or(0);
+    let scale = rows.iter()
+        .flat_map(|row| [row.value, row.target])
+        .flatten()
+        .filter(|value| value.is_finite())
+        .fold(0.0, f64::max);
+    let columns = |value: f64| -> usize {
+        if scale > 0.0 {
+            ((value.max(0.0) / scale) * width as f64).round() as usize
+        } else {
+            0
+        }
+    };
+
+    rows.iter().map(|row| {
+        let filled = row.value.map_or(0, columns);
+        let target = row.target.map(columns);
+        let bar: String = (0..=width).map(|column| {
+            if column < filled {
+                if target.is_some_and(|target| column >= target) { '▒' } else { '█' }
+            } else if target == Some(column) {
+                '|'
+            } else {
+                ' '
+            }
+        }).collect();
+
+        let mut line = format!("{:<label_width$} {}", row.label, bar);
+        match row.value {
+            Some(value) => line.push_str(&format!(" {:>6.0}", value)),
+            None => line.push_str(&format!(" {:>6}", "-")),
+        }
+        if let (Some(value), Some(target)) = (row.value, row.target)
+            && value > target
+        {
+            line.push_str(&format!(" (+{:.0})", value - target));
+        }
+        line.trim_end().to_string()
+    }).collect()
+}
+
+/// Draws a sparkline of a series
+///
+/// A flat series is drawn at mid height.
+///
+/// # Arguments
+/// * `values` - The series, in order
+///
+/// # Returns
+/// One character per value (empty for an empty series)
+pub fn sparkline(values: &[f64]) -> String {
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let top = SPARK_LEVELS.len() - 1;
+    values.iter().map(|value| {
+        let level = if max - min > f64::EPSILON {
+            (((value - min) / (max - min)) * top as f64).round() as usize
+        } else {
+            top / 2
+        };
+        SPARK_LEVELS[level.min(top)]
+    }).collect()
+}
+
+/// Draws how much of a target has been reached
+///
+/// # Arguments
+/// * `value` - Amount reached
+/// * `target` - Amount aimed for (a bar of 0 or less is drawn full)
+/// * `width` - Width of the bar in characters
+///
+/// # Returns
+/// The bar, `width` characters long
+pub fn progress_bar(value: f64, target: f64, width: usize) -> String {
+    let share = if target > 0.0 { (value / target).clamp(0.0, 1.0) } else { 1.0 };
+    let filled = (share * width as f64).round() as usize;
+    format!("{}{}", "█".repeat(filled), "░".repeat(width - filled))
+}
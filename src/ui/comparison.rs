@@ -0,0 +1,85 @@
+//! # Food Comparison Table
+//!
+//! This module renders two or more foods side by side so alternatives can be
+//! weighed while meal planning. Each food gets a column; each row is one
+//! metric, first per serving and then normalized per 100 g.
+//!
+//! ## Metrics
+//!
+//! - **Per serving**: Serving weight, calories, protein, carbs, fat, cost
+//! - **Per 100 g**: Calories (the calorie density), protein, carbs, fat, cost
+//! - **Per unit of cost**: Calories and protein one unit of money buys
+//!
+//! Values that cannot be computed (unknown serving weight or cost) are shown
+//! as `-`. Per-100 g rows require the food's serving weight in grams, and
+//! per-cost rows its cost.
+
+// src/ui/comparison.rs
+use yada_core::models::food::Food;
+
+use crate::ui::i18n::Message;
+use crate::ui::style::{self, Style};
+use crate::ui::table::{Align, pad, truncate};
+
+/// Width of each food column in terminal columns
+const COLUMN_WIDTH: usize = 16;
+
+/// Width of the metric label column in terminal columns
+const LABEL_WIDTH: usize = 20;
+
+/// Builds a side-by-side comparison table for the given foods
+///
+/// # Arguments
+/// * `foods` - Foods to compare, in column order
+///
+/// # Returns
+/// The formatted table, one line per metric
+pub fn comparison_table(foods: &[&Food]) -> String {
+    let lines = vec![
+        style::paint(Style::Bold, row("", foods.iter().map(|f| truncate(&f.name, COLUMN_WIDTH)))),
+        row("ID", foods.iter().map(|f| truncate(&f.id, COLUMN_WIDTH))),
+        "-".repeat(LABEL_WIDTH + foods.len() * (COLUMN_WIDTH + 1)),
+        style::paint(Style::Bold, Message::PerServing.text()),
+        metric_row(&format!("  {}", Message::WeightGrams), foods, |f| f.grams_per_serving, 0),
+        metric_row(&format!("  {}", Message::CaloriesColumn), foods, |f| Some(f.calories_per_serving), 1),
+        metric_row(&format!("  {}", Message::ProteinGrams), foods, |f| Some(f.macros.protein), 1),
+        metric_row(&format!("  {}", Message::CarbsGrams), foods, |f| Some(f.macros.carbs), 1),
+        metric_row(&format!("  {}", Message::FatGrams), foods, |f| Some(f.macros.fat), 1),
+        metric_row(&format!("  {}", Message::Cost), foods, |f| f.cost_per_serving, 2),
+        style::paint(Style::Bold, Message::Per100g.text()),
+        metric_row(&format!("  {}", Message::CaloriesColumn), foods, |f| f.calorie_density(), 1),
+        metric_row(&format!("  {}", Message::ProteinGrams), foods, |f| f.per_100g(f.macros.protein), 1),
+        metric_row(&format!("  {}", Message::CarbsGrams), foods, |f| f.per_100g(f.macros.carbs), 1),
+        metric_row(&format!("  {}", Message::FatGrams), foods, |f| f.per_100g(f.macros.fat), 1),
+        metric_row(&format!("  {}", Message::Cost), foods, |f| f.cost_per_serving.and_then(|c| f.per_100g(c)), 2),
+        style::paint(Style::Bold, Message::PerUnitOfCost.text()),
+        metric_row(&format!("  {}", Message::CaloriesColumn), foods, |f| f.per_unit_cost(f.calories_per_serving), 0),
+        metric_row(&format!("  {}", Message::ProteinGrams), foods, |f| f.per_unit_cost(f.macros.protein), 1),
+    ];
+
+    lines.join("\n")
+}
+
+/// Formats one metric row, showing `-` for unknown values
+fn metric_row<F>(label: &str, foods: &[&Food], value: F, decimals: usize) -> String
+where
+    F: Fn(&Food) -> Option<f64>,
+{
+    row(label, foods.iter().map(|food| match value(food) {
+        Some(v) => format!("{:.*}", decimals, v),
+        None => "-".to_string(),
+    }))
+}
+
+/// Formats a label followed by right-aligned cells
+fn row<I>(label: &str, cells: I) -> String
+where
+    I: Iterator<Item = String>,
+{
+    let mut line = pad(label, LABEL_WIDTH, Align::Left);
+    for cell in cells {
+        line.push(' ');
+        line.push_str(&pad(&cell, COLUMN_WIDTH, Align::Right));
+    }
+    line
+}
@@ -0,0 +1,258 @@
+//! # Console
+//!
+//! Where the interactive menus read their input and write their output.
+//! Every prompt reads through an `InputProvider` and every menu line is
+//! written to an `OutputSink`, so a whole session can be scripted without a
+//! terminal:
+//!
+//! - **Terminal** (the default): `StdinInput` and `StdoutSink`
+//! - **Scripted**: `ScriptedInput` replays lines given up front, and
+//!   `CapturedOutput` keeps everything written for the caller to inspect
+//!
+//! The console is installed per thread with `install` (see
+//! `App::with_console`), so sessions running on different threads, such as
+//! parallel tests, do not share input or output. Menu code writes with the
+//! `out!` and `outln!` macros, which work like `print!` and `println!`.
+//!
+//! ```ignore
+//! let output = CapturedOutput::new();
+//! console::install(ScriptedInput::new(["7", "11"]), output.clone());
+//! // ... run the menus ...
+//! assert!(output.contents().contains("------ View Statistics ------"));
+//! ```
+
+// src/ui/console.rs
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::rc::Rc;
+
+/// Source of the lines a user types
+pub trait InputProvider {
+    /// Reads the next line
+    ///
+    /// # Returns
+    /// * `Some(String)` - The line, possibly with its line ending
+    /// * `None` - At the end of input
+    fn read_line(&mut self) -> Option<String>;
+
+    /// Reads the next line without showing it, for passwords
+    fn read_secret(&mut self) -> Option<String> {
+        self.read_line()
+    }
+
+    /// Whether a person is typing, so menus that only make sense when
+    /// someone can answer them (such as load recovery) may be shown
+    fn is_interactive(&self) -> bool {
+        false
+    }
+}
+
+/// Destination of the menus' output
+pub trait OutputSink {
+    /// Writes text exactly as given (no line ending is added)
+    fn write(&mut self, text: &str);
+
+    /// Makes text written so far visible, e.g. before waiting for input
+    fn flush(&mut self) {}
+}
+
+/// Reads lines from standard input
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdinInput;
+
+impl InputProvider for StdinInput {
+    fn read_line(&mut self) -> Option<String> {
+        // Bytes that are not UTF-8 are replaced rather than ending the session
+        let mut line = Vec::new();
+        match io::stdin().lock().read_until(b'\n', &mut line) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(String::from_utf8_lossy(&line).into_owned()),
+        }
+    }
+
+    fn read_secret(&mut self) -> Option<String> {
+        if io::stdin().is_terminal() {
+            rpassword::read_password().ok()
+        } else {
+            self.read_line()
+        }
+    }
+
+    fn is_interactive(&self) -> bool {
+        io::stdin().is_terminal()
+    }
+}
+
+/// Writes to standard output
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdoutSink;
+
+impl OutputSink for StdoutSink {
+    fn write(&mut self, text: &str) {
+        let _ = io::stdout().write_all(text.as_bytes());
+    }
+
+    fn flush(&mut self) {
+        let _ = io::stdout().flush();
+    }
+}
+
+/// Replays a fixed list of lines, then reports the end of input
+#[derive(Debug, Clone, Default)]
+pub struct ScriptedInput {
+    lines: VecDeque<String>,
+}
+
+impl ScriptedInput {
+    /// Creates input that answers prompts with `lines`, in order
+    pub fn new<I, S>(lines: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        ScriptedInput { lines: lines.into_iter().map(Into::into).collect() }
+    }
+
+    /// Number of lines not read yet
+    pub fn remaining(&self) -> usize {
+        self.lines.len()
+    }
+}
+
+impl InputProvider for ScriptedInput {
+    fn read_line(&mut self) -> Option<String> {
+        self.lines.pop_front()
+    }
+}
+
+/// Keeps everything written, readable through any of its clones
+#[derive(Debug, Clone, Default)]
+pub struct CapturedOutput {
+    text: Rc<RefCell<String>>,
+}
+
+impl CapturedOutput {
+    /// Creates an empty capture
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Everything written so far
+    pub fn contents(&self) -> String {
+        self.text.borrow().clone()
+    }
+}
+
+impl OutputSink for CapturedOutput {
+    fn write(&mut self, text: &str) {
+        self.text.borrow_mut().push_str(text);
+    }
+}
+
+/// The input and output of the current thread's session
+struct Console {
+    input: Box<dyn InputProvider>,
+    output: Box<dyn OutputSink>,
+
+    /// Whether the input has ended (it stays ended once it has)
+    closed: bool,
+}
+
+thread_local! {
+    static CONSOLE: RefCell<Console> = RefCell::new(Console {
+        input: Box::new(StdinInput),
+        output: Box::new(StdoutSink),
+        closed: false,
+    });
+}
+
+/// Makes the current thread read from `input` and write to `output`
+///
+/// # Arguments
+/// * `input` - Source of the lines the prompts read
+/// * `output` - Destination of everything the menus print
+pub fn install(input: impl InputProvider + 'static, output: impl OutputSink + 'static) {
+    CONSOLE.with(|console| {
+        *console.borrow_mut() = Console { input: Box::new(input), output: Box::new(output), closed: false };
+    });
+}
+
+/// Reads the next input line, flushing pending output first
+///
+/// # Returns
+/// The line, or `None` at the end of input
+pub fn read_line() -> Option<String> {
+    CONSOLE.with(|console| {
+        let mut console = console.borrow_mut();
+        console.output.flush();
+        if console.closed {
+            return None;
+        }
+        let line = console.input.read_line();
+        console.closed = line.is_none();
+        line
+    })
+}
+
+/// Whether the input has ended, by end of file or an interrupt (see `ui::interrupt`)
+///
+/// Once ended, every later read returns `None` without asking the input
+/// again, so a flow that ignores one failed prompt cannot wait on another.
+pub fn input_closed() -> bool {
+    CONSOLE.with(|console| console.borrow().closed)
+}
+
+/// Reads the next input line without echoing it on a terminal
+///
+/// # Returns
+/// The line, or `None` at the end of input
+pub fn read_secret() -> Option<String> {
+    CONSOLE.with(|console| {
+        let mut console = console.borrow_mut();
+        console.output.flush();
+        if console.closed {
+            return None;
+        }
+        let line = console.input.read_secret();
+        console.closed = line.is_none();
+        line
+    })
+}
+
+/// Whether a person is typing the input (see `InputProvider::is_interactive`)
+pub fn is_interactive() -> bool {
+    CONSOLE.with(|console| console.borrow().input.is_interactive())
+}
+
+/// Writes formatted text; used by the `out!` and `outln!` macros
+pub fn write_fmt(args: fmt::Arguments) {
+    let text = args.to_string();
+    CONSOLE.with(|console| console.borrow_mut().output.write(&text));
+}
+
+/// Makes output written so far visible
+pub fn flush() {
+    CONSOLE.with(|console| console.borrow_mut().output.flush());
+}
+
+/// Writes to the console like `print!`
+macro_rules! out {
+    ($($arg:tt)*) => {
+        $crate::ui::console::write_fmt(format_args!($($arg)*))
+    };
+}
+
+/// Writes a line to the console like `println!`
+macro_rules! outln {
+    () => {
+        $crate::ui::console::write_fmt(format_args!("\n"))
+    };
+    ($($arg:tt)*) => {
+        $crate::ui::console::write_fmt(format_args!("{}\n", format_args!($($arg)*)))
+    };
+}
+
+pub(crate) use out;
+pub(crate) use outln;
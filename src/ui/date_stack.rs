@@ -0,0 +1,71 @@
+//! # Date Bookmarks
+//!
+//! When editing several historical days, retyping dates is tedious. The
+//! date stack remembers bookmarked working dates for the current session so
+//! the user can hop back to them.
+//!
+//! ## Behavior
+//!
+//! - **Bookmark**: Pushes a date. A date already on the stack moves to the
+//!   top instead of appearing twice. When the stack is full, the oldest
+//!   bookmark is dropped.
+//! - **Return**: Takes the most recent bookmark and bookmarks the date being
+//!   left, so returning twice comes back to where you started.
+//!
+//! Bookmarks are not saved; they end with the session.
+
+// src/ui/date_stack.rs
+use chrono::NaiveDate;
+
+/// Maximum number of bookmarked dates
+pub const DATE_STACK_CAPACITY: usize = 10;
+
+/// Bookmarked working dates, most recent last
+#[derive(Debug, Clone, Default)]
+pub struct DateStack {
+    /// Bookmarks in the order they were made
+    dates: Vec<NaiveDate>,
+}
+
+impl DateStack {
+    /// Creates an empty stack
+    pub fn new() -> Self {
+        DateStack::default()
+    }
+
+    /// Bookmarks a date
+    pub fn push(&mut self, date: NaiveDate) {
+        self.dates.retain(|bookmarked| *bookmarked != date);
+        if self.dates.len() == DATE_STACK_CAPACITY {
+            self.dates.remove(0);
+        }
+        self.dates.push(date);
+    }
+
+    /// Returns the most recent bookmark other than `current`
+    pub fn peek(&self, current: NaiveDate) -> Option<NaiveDate> {
+        self.dates.iter().rev().copied().find(|date| *date != current)
+    }
+
+    /// Takes the most recent bookmark other than `current` and bookmarks `current`
+    ///
+    /// # Returns
+    /// * `Some(NaiveDate)` - The date to return to
+    /// * `None` - If there is no other bookmarked date
+    pub fn swap(&mut self, current: NaiveDate) -> Option<NaiveDate> {
+        let previous = self.peek(current)?;
+        self.dates.retain(|date| *date != previous);
+        self.push(current);
+        Some(previous)
+    }
+
+    /// Returns the bookmarks, most recent first
+    pub fn dates(&self) -> impl Iterator<Item = &NaiveDate> {
+        self.dates.iter().rev()
+    }
+
+    /// Returns true if nothing is bookmarked
+    pub fn is_empty(&self) -> bool {
+        self.dates.is_empty()
+    }
+}
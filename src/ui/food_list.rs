@@ -0,0 +1,164 @@
+//! # Food List
+//!
+//! This module renders the food database as a paged table for View Foods,
+//! so that browsing an imported database of thousands of foods shows one
+//! screenful at a time instead of scrolling everything past.
+//!
+//! ## Layout
+//!
+//! Every column has a fixed width, so pages line up with each other. Text
+//! longer than its column (long names, many keywords) is cut short and ends
+//! in `…` (see `ui::table`). The full details of a food are available
+//! through its ID.
+//!
+//! ## Navigation
+//!
+//! Lists of up to `PAGE_SIZE` foods are printed in one go. Longer lists are
+//! split into pages; after each page the user can type:
+//! - `n` / `p`: Next or previous page
+//! - A page number: Jump to that page
+//! - `s`: Change the order (name, calories, or ID; see `FoodSort`)
+//! - `c`: Show only one category (see `models::category`), or all again
+//! - Nothing: Return to the menu
+//!
+//! A list that fits on one page still offers these choices when some foods
+//! have a category, so small databases can be browsed by category too.
+
+// src/ui/food_list.rs
+use std::fmt;
+
+use yada_core::models::category::{self, UNCATEGORIZED};
+use yada_core::models::food::Food;
+
+use crate::ui::i18n::Message;
+use crate::ui::table::Table;
+
+/// Foods shown per page
+pub const PAGE_SIZE: usize = 20;
+
+/// Column widths in terminal columns: ID, name, keywords, category, calories
+const WIDTHS: [usize; 5] = [12, 28, 26, 14, 9];
+
+/// What the user asked for after a page
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageAction {
+    /// Show the page with this index (0-based)
+    Go(usize),
+
+    /// Choose a different order
+    Sort,
+
+    /// Choose a category to show
+    Category,
+
+    /// Leave the list
+    Done,
+}
+
+/// Which foods the list shows
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum CategoryFilter {
+    /// Every food
+    #[default]
+    All,
+
+    /// Foods without a category
+    Uncategorized,
+
+    /// Foods in one category (ignoring case)
+    Named(String),
+}
+
+impl CategoryFilter {
+    /// Returns true if the filter lets a food through
+    pub fn allows(&self, food: &Food) -> bool {
+        match self {
+            CategoryFilter::All => true,
+            CategoryFilter::Uncategorized => food.category.is_none(),
+            CategoryFilter::Named(name) => category::matches(food, name),
+        }
+    }
+}
+
+impl fmt::Display for CategoryFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CategoryFilter::All => write!(f, "{}", Message::AllCategoriesInText),
+            CategoryFilter::Uncategorized => write!(f, "{}", UNCATEGORIZED),
+            CategoryFilter::Named(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// Returns the number of pages needed for `count` foods (at least one)
+pub fn page_count(count: usize) -> usize {
+    count.div_ceil(PAGE_SIZE).max(1)
+}
+
+/// Returns the foods on a page (0-based), or nothing past the last page
+pub fn page<'a, 'f>(foods: &'a [&'f Food], index: usize) -> &'a [&'f Food] {
+    let start = (index * PAGE_SIZE).min(foods.len());
+    let end = (start + PAGE_SIZE).min(foods.len());
+    &foods[start..end]
+}
+
+/// Interprets the input typed after a page
+///
+/// # Arguments
+/// * `input` - Trimmed user input
+/// * `current` - Index of the page being shown (0-based)
+/// * `pages` - Total number of pages
+///
+/// # Returns
+/// * `Ok(PageAction)` - The requested action
+/// * `Err(String)` - Message explaining why the input was not understood
+pub fn parse_action(input: &str, current: usize, pages: usize) -> Result<PageAction, String> {
+    match input.to_lowercase().as_str() {
+        "" | "q" => Ok(PageAction::Done),
+        "n" if current + 1 < pages => Ok(PageAction::Go(current + 1)),
+        "n" => Err(Message::AlreadyLastPage.text().to_string()),
+        "p" if current > 0 => Ok(PageAction::Go(current - 1)),
+        "p" => Err(Message::AlreadyFirstPage.text().to_string()),
+        "s" => Ok(PageAction::Sort),
+        "c" => Ok(PageAction::Category),
+        number => match number.parse::<usize>() {
+            Ok(page) if (1..=pages).contains(&page) => Ok(PageAction::Go(page - 1)),
+            _ => Err(Message::PageActionHint.format(&[&pages])),
+        },
+    }
+}
+
+/// Creates an empty table with the list's fixed columns
+fn table() -> Table {
+    let [id, name, keywords, category, calories] = WIDTHS;
+    Table::new()
+        .column("ID").min_width(id).max_width(id)
+        .column(Message::Name.text()).min_width(name).max_width(name)
+        .column(Message::KeywordsColumn.text()).min_width(keywords).max_width(keywords)
+        .column(Message::CategoryColumn.text()).min_width(category).max_width(category)
+        .numeric(Message::CaloriesColumn.text()).min_width(calories).max_width(calories)
+}
+
+/// Formats the table header and its underline
+pub fn header() -> String {
+    table().header()
+}
+
+/// Formats one food as a table row
+///
+/// # Arguments
+/// * `food` - Food to show
+/// * `suffix` - Text appended after the last column (e.g. a highlight)
+pub fn row(food: &Food, suffix: &str) -> String {
+    let mut keywords: Vec<&str> = food.keywords.iter().map(String::as_str).collect();
+    keywords.sort_unstable();
+    let mut table = table();
+    table.row_with_note([
+        food.id.as_str(),
+        food.name.as_str(),
+        &keywords.join(", "),
+        food.category.as_deref().unwrap_or("-"),
+        &format!("{:.1}", food.calories_per_serving),
+    ], suffix);
+    table.lines().concat()
+}
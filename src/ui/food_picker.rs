@@ -0,0 +1,73 @@
+//! # Food Picker
+//!
+//! Choosing a food without retyping its exact ID. Where a flow asks for a
+//! food (logging a food, adding a component to a composite food), the foods
+//! on offer are listed with row numbers and the user can type:
+//! - A row number: The food in that row of the list last shown
+//! - A food ID: Any food in the database, as before
+//! - `/text`: Narrows the list to the foods whose ID, name, or keywords
+//!   contain the text (ignoring case) and shows it again, numbered anew;
+//!   filters add up, so `/chick` then `/breast` finds chicken breast
+//! - `/` alone: Shows the whole list again
+//!
+//! A number within the list always means a row, even if a food has that
+//! number as its ID; such a food can still be found with a filter.
+
+// src/ui/food_picker.rs
+use std::collections::HashSet;
+
+use yada_core::models::food::Food;
+
+use crate::ui::i18n::Message;
+
+/// Character starting a filter
+pub const FILTER_PREFIX: char = '/';
+
+/// What the user typed at the food picker
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Pick {
+    /// A food, by its ID
+    Food(String),
+
+    /// Narrow the list to foods matching the text (empty to show every food again)
+    Filter(String),
+}
+
+/// Interprets the input typed at the food picker
+///
+/// # Arguments
+/// * `input` - Trimmed user input
+/// * `shown` - IDs of the foods in the list last shown, in row order
+/// * `known` - IDs of every food in the database
+///
+/// # Returns
+/// * `Ok(Pick)` - The chosen food or the filter to apply
+/// * `Err(String)` - Message explaining why the input was not understood
+pub fn parse(input: &str, shown: &[String], known: &HashSet<String>) -> Result<Pick, String> {
+    if let Some(text) = input.strip_prefix(FILTER_PREFIX) {
+        return Ok(Pick::Filter(text.trim().to_string()));
+    }
+    if let Ok(number) = input.parse::<usize>() && (1..=shown.len()).contains(&number) {
+        return Ok(Pick::Food(shown[number - 1].clone()));
+    }
+    if known.contains(input) {
+        Ok(Pick::Food(input.to_string()))
+    } else if input.parse::<usize>().is_ok() && !shown.is_empty() {
+        Err(Message::PickFoodRange.format(&[&shown.len()]))
+    } else {
+        Err(Message::FoodIdMissing.format(&[&input]))
+    }
+}
+
+/// Returns the foods whose ID, name, or keywords contain the text, ignoring case
+pub fn filter<'f>(foods: &[&'f Food], text: &str) -> Vec<&'f Food> {
+    let text = text.to_lowercase();
+    foods.iter()
+        .copied()
+        .filter(|food| {
+            food.id.to_lowercase().contains(&text)
+                || food.name.to_lowercase().contains(&text)
+                || food.keywords.iter().any(|keyword| keyword.to_lowercase().contains(&text))
+        })
+        .collect()
+}
@@ -0,0 +1,100 @@
+//! # Nutrient-Density Highlighting
+//!
+//! This module marks nutrient-dense foods in food lists. A food is scored by
+//! how many grams of protein and fiber it provides per 100 calories; each
+//! ratio at or above its threshold adds an annotation such as
+//! `high protein`. The score is only used for display: it never changes
+//! the food data or the order of a list.
+//!
+//! ## Thresholds
+//!
+//! - **Protein**: `YADA_PROTEIN_DENSITY` grams per 100 kcal (default 10,
+//!   roughly lean meat, fish, and egg whites)
+//! - **Fiber**: `YADA_FIBER_DENSITY` grams per 100 kcal (default 3, roughly
+//!   most vegetables and berries)
+//!
+//! Invalid or negative values fall back to the default. Setting a threshold
+//! to `0` turns that highlight off. Foods without calories are never
+//! highlighted, since their ratios are undefined.
+
+// src/ui/highlight.rs
+use std::env;
+
+use yada_core::models::food::Food;
+
+use crate::ui::i18n::Message;
+
+/// Environment variable with the protein threshold in grams per 100 kcal
+pub const PROTEIN_DENSITY_ENV_VAR: &str = "YADA_PROTEIN_DENSITY";
+
+/// Environment variable with the fiber threshold in grams per 100 kcal
+pub const FIBER_DENSITY_ENV_VAR: &str = "YADA_FIBER_DENSITY";
+
+/// Default protein threshold in grams per 100 kcal
+const DEFAULT_PROTEIN_DENSITY: f64 = 10.0;
+
+/// Default fiber threshold in grams per 100 kcal
+const DEFAULT_FIBER_DENSITY: f64 = 3.0;
+
+/// Ratios at or above which a food is highlighted
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DensityThresholds {
+    /// Grams of protein per 100 kcal (0 disables the highlight)
+    pub protein: f64,
+
+    /// Grams of fiber per 100 kcal (0 disables the highlight)
+    pub fiber: f64,
+}
+
+impl DensityThresholds {
+    /// Reads the thresholds from `YADA_PROTEIN_DENSITY` and `YADA_FIBER_DENSITY`
+    pub fn from_env() -> Self {
+        let threshold = |var: &str, default: f64| {
+            env::var(var)
+                .ok()
+                .and_then(|value| value.trim().parse::<f64>().ok())
+                .filter(|value| value.is_finite() && *value >= 0.0)
+                .unwrap_or(default)
+        };
+        DensityThresholds {
+            protein: threshold(PROTEIN_DENSITY_ENV_VAR, DEFAULT_PROTEIN_DENSITY),
+            fiber: threshold(FIBER_DENSITY_ENV_VAR, DEFAULT_FIBER_DENSITY),
+        }
+    }
+
+    /// Returns the annotation for a food, or an empty string if it is not nutrient-dense
+    ///
+    /// # Arguments
+    /// * `food` - The food to score
+    ///
+    /// # Returns
+    /// `high protein`, `high fiber`, or both separated by a comma
+    pub fn annotation(&self, food: &Food) -> String {
+        let calories = food.calories_per_serving;
+        if calories <= 0.0 {
+            return String::new();
+        }
+
+        let dense = |grams: f64, threshold: f64| threshold > 0.0 && grams * 100.0 / calories >= threshold;
+        let mut labels = Vec::new();
+        if dense(food.macros.protein, self.protein) {
+            labels.push(Message::HighProtein.text());
+        }
+        if dense(food.macros.fiber, self.fiber) {
+            labels.push(Message::HighFiber.text());
+        }
+        labels.join(", ")
+    }
+
+    /// Describes the enabled thresholds, for a legend printed under highlighted lists
+    pub fn legend(&self) -> String {
+        let mut parts = Vec::new();
+        if self.protein > 0.0 {
+            parts.push(Message::GramsProtein.format(&[&self.protein]));
+        }
+        if self.fiber > 0.0 {
+            parts.push(Message::GramsFiber.format(&[&self.fiber]));
+        }
+        Message::NutrientDenseLegend.format(&[&(parts.join(Message::Or.text()))])
+    }
+}
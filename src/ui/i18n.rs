@@ -0,0 +1,1303 @@
+//! # Message Catalog
+//!
+//! Text shown to the user in every supported language, so the menus,
+//! prompts, error messages, subcommand output, and reports can be translated
+//! without touching the code that prints them. What is stored in the data
+//! files (food names, command descriptions) stays as written.
+//!
+//! ## Locales
+//!
+//! - **English** (`en`, the default)
+//! - **German** (`de`)
+//!
+//! The locale is read once from the `YADA_LANG` environment variable, which
+//! takes a language code such as `de` or a full locale name such as
+//! `de_DE.UTF-8`. Unknown languages fall back to English.
+//!
+//! ## Adding Messages
+//!
+//! Each message is one line of the `catalog!` table below with its English
+//! and German text, so a message cannot be added without a translation.
+//! Messages print through `Display`; ones with placeholders are filled in
+//! order with `Message::format`. A placeholder is `{}`, or for numbers
+//! `{:.1}` (one decimal) and `{:+.0}` (with a sign), as in `format!`.
+//! Typed answers such as `today`, `yes`, and `cancel` are not translated.
+
+// src/ui/i18n.rs
+use std::env;
+use std::fmt;
+use std::sync::OnceLock;
+
+use chrono::{Datelike, NaiveDate, Weekday};
+use yada_core::models::meal::MealSlot;
+use yada_core::models::profile::{ActivityLevel, Gender};
+use yada_core::models::week::WeekStart;
+use yada_core::repositories::food_repository::FoodSort;
+
+/// Environment variable choosing the language of the menus
+pub const LANG_ENV_VAR: &str = "YADA_LANG";
+
+/// Locale of the session, read on first use
+static LOCALE: OnceLock<Locale> = OnceLock::new();
+
+/// Language the menus are shown in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Locale {
+    /// English
+    #[default]
+    English,
+
+    /// German
+    German,
+}
+
+impl Locale {
+    /// Reads the locale from `YADA_LANG`, defaulting to English
+    pub fn from_env() -> Self {
+        env::var(LANG_ENV_VAR)
+            .ok()
+            .and_then(|value| Self::parse(&value))
+            .unwrap_or_default()
+    }
+
+    /// Parses a language code (`de`) or locale name (`de_DE.UTF-8`), case-insensitively
+    pub fn parse(value: &str) -> Option<Self> {
+        let language = value.trim().split(['_', '-', '.']).next().unwrap_or("");
+        match language.to_lowercase().as_str() {
+            "en" | "english" => Some(Locale::English),
+            "de" | "german" | "deutsch" => Some(Locale::German),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the locale of the session
+pub fn locale() -> Locale {
+    *LOCALE.get_or_init(Locale::from_env)
+}
+
+/// Defines `Message` with its text in each locale
+macro_rules! catalog {
+    ($($name:ident => $english:literal, $german:literal;)*) => {
+        /// A translatable message of the interactive menus
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum Message {
+            $($name,)*
+        }
+
+        impl Message {
+            /// Text of the message in a locale
+            pub fn text_in(self, locale: Locale) -> &'static str {
+                match locale {
+                    Locale::English => match self {
+                        $(Message::$name => $english,)*
+                    },
+                    Locale::German => match self {
+                        $(Message::$name => $german,)*
+                    },
+                }
+            }
+        }
+    };
+}
+
+catalog! {
+    // Startup and exit
+    Welcome => "Welcome to YADA (Yet Another Diet Assistant)!", "Willkommen bei YADA (Yet Another Diet Assistant)!";
+    DataDirectory => "Data directory: {}", "Datenverzeichnis: {}";
+    CurrentUser => "User: {}", "Benutzer: {}";
+    LegacyDataDir => "Note: using data files found in the current directory. Use --data-dir or YADA_DATA_DIR to choose a data directory.",
+        "Hinweis: Es werden die Datendateien im aktuellen Verzeichnis verwendet. Mit --data-dir oder YADA_DATA_DIR lässt sich ein Datenverzeichnis wählen.";
+    LoadProblems => "Warning: {} was not loaded exactly as written ({}):", "Warnung: {} wurde nicht genau wie gespeichert geladen ({}):";
+    LoadProblemsHint => "Skipped lines are dropped the next time the file is saved; start with --strict to stop instead.", "Übersprungene Zeilen fehlen nach dem nächsten Speichern; mit --strict starten, um stattdessen abzubrechen.";
+    NoProfileFound => "No user profile found. Let's create one!", "Kein Benutzerprofil gefunden. Legen wir eines an!";
+    Interrupted => "Interrupted. Saving your data before exiting (press Ctrl-C again to quit without saving).",
+        "Unterbrochen. Ihre Daten werden vor dem Beenden gespeichert (erneut Strg-C drücken, um ohne Speichern zu beenden).";
+    Goodbye => "Goodbye!", "Auf Wiedersehen!";
+
+    // Main menu (the entries double as screen titles)
+    MainMenu => "YADA Main Menu", "YADA-Hauptmenü";
+    CurrentDate => "Current date: {}", "Aktuelles Datum: {}";
+    ManageFoods => "Manage Foods", "Lebensmittel verwalten";
+    ViewFoods => "View Foods", "Lebensmittel anzeigen";
+    LogFood => "Log Food Consumption", "Verzehr eintragen";
+    ViewLog => "View Food Log", "Ernährungstagebuch anzeigen";
+    ExerciseLog => "Exercise Log", "Trainingstagebuch";
+    ManageProfile => "Manage Profile", "Profil verwalten";
+    ViewStats => "View Statistics", "Statistik anzeigen";
+    ChangeDate => "Change Current Date", "Datum ändern";
+    SaveData => "Save Data", "Daten speichern";
+    UndoLastAction => "Undo Last Action", "Letzte Aktion rückgängig machen";
+    Preferences => "Preferences", "Einstellungen";
+    Exit => "Exit", "Beenden";
+    BackToMainMenu => "Back to Main Menu", "Zurück zum Hauptmenü";
+    Back => "Back", "Zurück";
+    Options => "Options:", "Optionen:";
+
+    // Manage Foods
+    AddBasicFood => "Add Basic Food", "Einfaches Lebensmittel hinzufügen";
+    CreateCompositeFood => "Create Composite Food", "Zusammengesetztes Lebensmittel erstellen";
+    CompareFoods => "Compare Foods", "Lebensmittel vergleichen";
+    ArchiveUnusedFoods => "Archive Unused Foods", "Unbenutzte Lebensmittel archivieren";
+    RestoreArchivedFood => "Restore Archived Food", "Archiviertes Lebensmittel wiederherstellen";
+    MergeDuplicateFoods => "Merge Duplicate Foods", "Doppelte Lebensmittel zusammenführen";
+    ImportFoodFromSources => "Import Food From Sources", "Lebensmittel aus Quellen importieren";
+    EditKeywords => "Edit Keywords", "Schlagwörter bearbeiten";
+    SetDefaultServings => "Set Default Servings", "Standardportionen festlegen";
+    SearchFoods => "Search Foods", "Lebensmittel suchen";
+    ErrorAddingFood => "Error adding food: {}", "Fehler beim Hinzufügen des Lebensmittels: {}";
+    ErrorAddingCompositeFood => "Error adding composite food: {}", "Fehler beim Hinzufügen des zusammengesetzten Lebensmittels: {}";
+    ErrorArchivingFoods => "Error archiving foods: {}", "Fehler beim Archivieren der Lebensmittel: {}";
+    ErrorRestoringFood => "Error restoring food: {}", "Fehler beim Wiederherstellen des Lebensmittels: {}";
+    ErrorEditingKeywords => "Error editing keywords: {}", "Fehler beim Bearbeiten der Schlagwörter: {}";
+    ErrorUpdatingFood => "Error updating food: {}", "Fehler beim Aktualisieren des Lebensmittels: {}";
+    ErrorMergingFoods => "Error merging foods: {}", "Fehler beim Zusammenführen der Lebensmittel: {}";
+
+    // Food log and meal plans
+    DeleteFoodEntry => "Delete a food entry", "Einen Eintrag löschen";
+    EditFoodEntry => "Edit a food entry", "Einen Eintrag bearbeiten";
+    PlanMeals => "Plan meals for this day", "Mahlzeiten für diesen Tag planen";
+    EditDayNote => "Write a note and rate mood and energy", "Notiz schreiben und Stimmung und Energie bewerten";
+    ViewDateRange => "View a date range", "Zeitraum anzeigen";
+    CalorieSources => "Top foods and calorie sources", "Top-Lebensmittel und Kalorienquellen";
+    DeleteLogEntry => "Delete Food Log Entry", "Tagebucheintrag löschen";
+    EditLogEntry => "Edit Food Log Entry", "Tagebucheintrag bearbeiten";
+    MealPlanFor => "Meal Plan for {}", "Mahlzeitenplan für {}";
+    AddPlannedFood => "Add a planned food", "Geplantes Lebensmittel hinzufügen";
+    RemovePlannedFood => "Remove a planned food", "Geplantes Lebensmittel entfernen";
+    ClearPlan => "Clear the plan", "Plan leeren";
+    Breakfast => "Breakfast", "Frühstück";
+    Lunch => "Lunch", "Mittagessen";
+    Dinner => "Dinner", "Abendessen";
+    Snack => "Snack", "Snack";
+    NoMeal => "No meal", "Keine Mahlzeit";
+    ErrorLoggingFood => "Error logging food: {}", "Fehler beim Eintragen des Lebensmittels: {}";
+    ErrorDeletingFoodEntry => "Error deleting food entry: {}", "Fehler beim Löschen des Eintrags: {}";
+    ErrorUpdatingFoodEntry => "Error updating food entry: {}", "Fehler beim Bearbeiten des Eintrags: {}";
+    ErrorUpdatingMealPlan => "Error updating meal plan: {}", "Fehler beim Aktualisieren des Mahlzeitenplans: {}";
+
+    // Exercise
+    LogExercise => "Log exercise", "Training eintragen";
+    DeleteExerciseEntry => "Delete an exercise entry", "Trainingseintrag löschen";
+    ErrorLoggingExercise => "Error logging exercise: {}", "Fehler beim Eintragen des Trainings: {}";
+    ErrorDeletingExerciseEntry => "Error deleting exercise entry: {}", "Fehler beim Löschen des Trainingseintrags: {}";
+    ExerciseFor => "Exercise for {}", "Training am {}";
+    NoExerciseLogged => "No exercise logged.", "Kein Training eingetragen.";
+    ActivityColumn => "Activity", "Aktivität";
+    MinutesColumn => "Minutes", "Minuten";
+    TotalBurned => "Total burned: {:.1}", "Verbrannt gesamt: {:.1}";
+    OtherActivity => "Other activity (enter a MET value)", "Andere Aktivität (MET-Wert eingeben)";
+    ActivityLabel => "Activity:", "Aktivität:";
+    ActivityNamePrompt => "Activity name: ", "Name der Aktivität: ";
+    ActivityNameEmpty => "Activity name cannot be empty.", "Der Name der Aktivität darf nicht leer sein.";
+    MetValuePrompt => "MET value (e.g. 3.5 for walking, 8 for running): ", "MET-Wert (z. B. 3.5 für Gehen, 8 für Laufen): ";
+    EnterPositiveNumber => "Please enter a positive number.", "Bitte eine positive Zahl eingeben.";
+    DurationPrompt => "Duration in minutes: ", "Dauer in Minuten: ";
+    EnterPositiveMinutes => "Please enter a positive number of minutes.", "Bitte eine positive Anzahl Minuten eingeben.";
+    WeightForEstimatePrompt => "Your weight in {} (for the estimate): ", "Ihr Gewicht in {} (für die Schätzung): ";
+    ExerciseLogged => "Exercise logged: about {:.1} calories burned.", "Training eingetragen: etwa {:.1} Kalorien verbrannt.";
+    NoExerciseToDelete => "No exercise entries to delete.", "Keine Trainingseinträge zum Löschen.";
+    InvalidEntryNumber => "Invalid entry number. Please enter a number between 1 and {}.",
+        "Ungültige Eintragsnummer. Bitte eine Zahl zwischen 1 und {} eingeben.";
+    EntryToDeletePrompt => "Enter the entry number to delete (1-{}): ", "Nummer des zu löschenden Eintrags (1-{}): ";
+    ExerciseEntryLine => "Entry {}: {} min of {}", "Eintrag {}: {} min {}";
+    ConfirmDeleteEntry => "Delete this entry? Type 'yes' to confirm: ", "Diesen Eintrag löschen? Zur Bestätigung 'yes' eingeben: ";
+    ExerciseEntryDeleted => "Exercise entry deleted successfully!", "Trainingseintrag erfolgreich gelöscht!";
+
+    // Manage Profile
+    CreateUserProfile => "Create User Profile", "Benutzerprofil anlegen";
+    UpdateBasicProfile => "Update Basic Profile", "Grunddaten ändern";
+    UpdateTodaysData => "Update Today's Data", "Heutige Daten ändern";
+    ChangeCalculationMethod => "Change Calculation Method", "Berechnungsmethode ändern";
+    SetWeekdayTargets => "Set Weekday Targets", "Ziele pro Wochentag festlegen";
+    ChooseDietPreset => "Choose Diet Preset", "Ernährungsvorlage wählen";
+    SetMacroTargets => "Set Macro Targets", "Makroziele festlegen";
+    SetDietaryRestrictions => "Set Dietary Restrictions", "Ernährungseinschränkungen festlegen";
+    SwitchUser => "Switch User", "Benutzer wechseln";
+    ExportEverything => "Export Everything", "Alles exportieren";
+    ImportDataBundle => "Import Data Bundle", "Datenpaket importieren";
+    ImportFromApp => "Import From Another App", "Aus anderer App importieren";
+    SetLifeStage => "Set Pregnancy or Breastfeeding", "Schwangerschaft oder Stillzeit festlegen";
+    ConfirmCarriedForward => "Confirm Today's Carried-Forward Data", "Übernommene Daten von heute bestätigen";
+    SelectGender => "Select your gender:", "Geschlecht wählen:";
+    Male => "Male", "Männlich";
+    Female => "Female", "Weiblich";
+    OtherGender => "Other", "Divers";
+    KeepCurrent => "Keep current", "Aktuellen Wert behalten";
+    SelectActivityLevel => "Select your activity level:", "Aktivitätslevel wählen:";
+    Sedentary => "Sedentary (little or no exercise)", "Sitzend (wenig oder keine Bewegung)";
+    LightlyActive => "Lightly active (light exercise/sports 1-3 days/week)", "Leicht aktiv (leichter Sport an 1-3 Tagen pro Woche)";
+    ModeratelyActive => "Moderately active (moderate exercise/sports 3-5 days/week)", "Mäßig aktiv (moderater Sport an 3-5 Tagen pro Woche)";
+    VeryActive => "Very active (hard exercise/sports 6-7 days a week)", "Sehr aktiv (intensiver Sport an 6-7 Tagen pro Woche)";
+    ExtremelyActive => "Extremely active (very hard exercise & physical job or training twice a day)",
+        "Extrem aktiv (sehr intensiver Sport und körperliche Arbeit oder zweimal täglich Training)";
+    NoProfile => "No profile exists!", "Es gibt noch kein Profil!";
+    NoProfileCreateFirst => "No profile exists! Please create a profile first.", "Es gibt noch kein Profil! Bitte zuerst ein Profil anlegen.";
+    NoProfileCreatingOne => "No profile exists! Creating a new one.", "Es gibt noch kein Profil! Ein neues wird angelegt.";
+    ProfileCreationCancelled => "Profile creation cancelled.", "Profilerstellung abgebrochen.";
+    ProfileCreated => "Profile created successfully!", "Profil erfolgreich angelegt!";
+    ProfileUpdated => "Profile updated successfully!", "Profil erfolgreich aktualisiert!";
+    DailyProfileUpdated => "Daily profile updated successfully!", "Tagesdaten erfolgreich aktualisiert!";
+    DailyProfileConfirmed => "Daily profile confirmed.", "Tagesdaten bestätigt.";
+    ErrorUpdatingProfile => "Error updating profile: {}", "Fehler beim Aktualisieren des Profils: {}";
+    ErrorUpdatingDailyProfile => "Error updating daily profile: {}", "Fehler beim Aktualisieren der Tagesdaten: {}";
+    ErrorLoadingUserData => "Error loading data for {}: {}", "Fehler beim Laden der Daten von {}: {}";
+
+    // Carrying the daily profile forward
+    CarriedForward => "No weight recorded for {}; carried forward from {}: {}, {}.",
+        "Kein Gewicht für {} eingetragen; übernommen vom {}: {}, {}.";
+    ConfirmValues => "Confirm these values", "Diese Werte bestätigen";
+    EditTodaysValues => "Edit today's weight and activity level", "Heutiges Gewicht und Aktivitätslevel bearbeiten";
+    DecideLater => "Decide later (keep them marked as carried forward)", "Später entscheiden (als übernommen markiert lassen)";
+
+    // Change Current Date
+    Bookmarked => "Bookmarked: {}", "Gemerkt: {}";
+    EnterNewDate => "Enter a new date", "Neues Datum eingeben";
+    BookmarkThisDate => "Bookmark this date ({})", "Dieses Datum merken ({})";
+    ReturnToPreviousDate => "Return to previous date ({})", "Zum vorherigen Datum zurückkehren ({})";
+    ReturnToPreviousDateNone => "Return to previous date (none bookmarked)", "Zum vorherigen Datum zurückkehren (keines gemerkt)";
+    DateBookmarked => "Bookmarked {}.", "{} gemerkt.";
+    NoOtherBookmark => "No other date is bookmarked. Bookmark a date first.", "Es ist kein anderes Datum gemerkt. Bitte zuerst ein Datum merken.";
+    EnterNewDatePrompt => "Enter new date (YYYY-MM-DD) or 'today' for current date: ",
+        "Neues Datum (JJJJ-MM-TT) oder 'today' für heute eingeben: ";
+    DateChanged => "Date changed to: {}", "Datum geändert auf: {}";
+
+    // Saving and undo
+    SavingData => "Saving data...", "Daten werden gespeichert...";
+    FoodDataSaved => "Food data saved successfully.", "Lebensmitteldaten gespeichert.";
+    LogDataSaved => "Log data saved successfully.", "Tagebuchdaten gespeichert.";
+    ProfileDataSaved => "Profile data saved successfully.", "Profildaten gespeichert.";
+    ExerciseDataSaved => "Exercise data saved successfully.", "Trainingsdaten gespeichert.";
+    MealPlansSaved => "Meal plans saved successfully.", "Mahlzeitenpläne gespeichert.";
+    MealTemplatesSaved => "Meal templates saved successfully.", "Mahlzeitvorlagen gespeichert.";
+    SettingsSaved => "Settings saved successfully.", "Einstellungen gespeichert.";
+    ErrorSavingFoodData => "Error saving food data: {}", "Fehler beim Speichern der Lebensmitteldaten: {}";
+    ErrorSavingLogData => "Error saving log data: {}", "Fehler beim Speichern der Tagebuchdaten: {}";
+    ErrorSavingProfileData => "Error saving profile data: {}", "Fehler beim Speichern der Profildaten: {}";
+    ErrorSavingExerciseData => "Error saving exercise data: {}", "Fehler beim Speichern der Trainingsdaten: {}";
+    ErrorSavingMealPlans => "Error saving meal plans: {}", "Fehler beim Speichern der Mahlzeitenpläne: {}";
+    ErrorSavingMealTemplates => "Error saving meal templates: {}", "Fehler beim Speichern der Mahlzeitvorlagen: {}";
+    ErrorSavingSettings => "Error saving settings: {}", "Fehler beim Speichern der Einstellungen: {}";
+    ReminderDue => "Reminder ({}): {}", "Erinnerung ({}): {}";
+    NoCommandsToUndo => "No commands to undo.", "Nichts rückgängig zu machen.";
+    UndoingCommand => "Undoing last command: {}", "Letzte Aktion wird rückgängig gemacht: {}";
+    ConfirmUndo => "Type 'yes' to undo it: ", "Zum Rückgängigmachen 'yes' eingeben: ";
+    NothingUndone => "Nothing undone.", "Nichts rückgängig gemacht.";
+    CommandUndone => "Command undone successfully.", "Aktion rückgängig gemacht.";
+    ErrorUndoing => "Error undoing command: {}", "Fehler beim Rückgängigmachen: {}";
+    CannotBeUndone => "Note: this change cannot be undone (see {}).", "Hinweis: Diese Änderung kann nicht rückgängig gemacht werden (siehe {}).";
+    AuditLogError => "Could not write the audit log: {}", "Das Änderungsprotokoll konnte nicht geschrieben werden: {}";
+    AutosaveFailedFoods => "Autosave failed for food data: {}", "Automatisches Speichern der Lebensmitteldaten fehlgeschlagen: {}";
+    AutosaveFailedLog => "Autosave failed for log data: {}", "Automatisches Speichern des Ernährungstagebuchs fehlgeschlagen: {}";
+    AutosaveFailedProfile => "Autosave failed for profile data: {}", "Automatisches Speichern der Profildaten fehlgeschlagen: {}";
+    AutosaveFailedExercise => "Autosave failed for exercise data: {}", "Automatisches Speichern der Trainingsdaten fehlgeschlagen: {}";
+    AutosaveFailedMealPlans => "Autosave failed for meal plans: {}", "Automatisches Speichern der Essenspläne fehlgeschlagen: {}";
+    AutosaveFailedTemplates => "Autosave failed for meal templates: {}", "Automatisches Speichern der Mahlzeitvorlagen fehlgeschlagen: {}";
+    AutosaveFailedSettings => "Autosave failed for settings: {}", "Automatisches Speichern der Einstellungen fehlgeschlagen: {}";
+
+    // Users
+    WhoIsUsing => "Who is using YADA?", "Wer benutzt YADA?";
+    CurrentMarker => "{} (current)", "{} (aktuell)";
+    NewUser => "New user", "Neuer Benutzer";
+    NewUserName => "Name of the new user: ", "Name des neuen Benutzers: ";
+    UserExists => "A user named '{}' already exists.", "Es gibt bereits einen Benutzer namens '{}'.";
+
+    // Prompts
+    Cancelled => "Cancelled.", "Abgebrochen.";
+    EnterChoice => "Enter your choice (1-{}): ", "Ihre Wahl (1-{}): ";
+    InvalidChoice => "Invalid choice. Please enter a number between 1 and {}.", "Ungültige Auswahl. Bitte eine Zahl zwischen 1 und {} eingeben.";
+    InvalidDate => "Invalid date format. Please use YYYY-MM-DD.", "Ungültiges Datumsformat. Bitte JJJJ-MM-TT verwenden.";
+    InvalidTime => "Invalid time format. Please use HH:MM (24-hour).", "Ungültiges Zeitformat. Bitte HH:MM (24 Stunden) verwenden.";
+    InvalidHeight => "Invalid height. Please enter a number.", "Ungültige Größe. Bitte eine Zahl eingeben.";
+    HeightBetween => "Please enter a height between {} and {}.", "Bitte eine Größe zwischen {} und {} eingeben.";
+    InvalidWeight => "Invalid weight. Please enter a number.", "Ungültiges Gewicht. Bitte eine Zahl eingeben.";
+    WeightBetween => "Please enter a weight between {} and {}.", "Bitte ein Gewicht zwischen {} und {} eingeben.";
+
+    // Startup food sources and day summary
+    SkippedPlugin => "Skipped food source plugin {}", "Lebensmittelquellen-Plugin übersprungen: {}";
+    SeedingFoods => "Initializing food database with basic foods...", "Lebensmitteldatenbank wird mit Grundnahrungsmitteln angelegt...";
+    FoodsSeeded => "Food database initialized with {} basic foods and {} composite foods.",
+        "Lebensmitteldatenbank mit {} einfachen und {} zusammengesetzten Lebensmitteln angelegt.";
+    ErrorSavingSeededFoods => "Warning: Failed to save seeded food database: {}",
+        "Warnung: Die angelegte Lebensmitteldatenbank konnte nicht gespeichert werden: {}";
+    SummarySaved => "Summary saved to {}", "Zusammenfassung gespeichert in {}";
+    ErrorWritingJournal => "Error writing the journal {}: {}", "Fehler beim Schreiben des Journals {}: {}";
+
+    // Search
+    SearchKeywordsPrompt => "Enter search keywords (comma-separated; add category:NAME to filter): ",
+        "Suchbegriffe eingeben (durch Kommas getrennt; category:NAME filtert nach Kategorie): ";
+    SearchKeywordsOrSavedPrompt => "Enter search keywords (comma-separated; add category:NAME to filter), or #N or a name for a saved search: ",
+        "Suchbegriffe eingeben (durch Kommas getrennt; category:NAME filtert nach Kategorie), oder #N bzw. den Namen einer gespeicherten Suche: ";
+    SavedSearchesList => "Saved searches:", "Gespeicherte Suchen:";
+    UsingSavedSearch => "Using saved search '{}'.", "Gespeicherte Suche '{}' wird verwendet.";
+    FoundFoodsInCategories => "Found {} foods in {}.", "{} Lebensmittel in {} gefunden.";
+    Or => " or ", " oder ";
+    NoKeywordsAllFoods => "No valid keywords entered. Returning all foods.",
+        "Keine gültigen Suchbegriffe eingegeben. Alle Lebensmittel werden angezeigt.";
+    MatchAllOrAny => "Match all keywords or any keyword?", "Alle Suchbegriffe oder irgendeinen?";
+    MatchAny => "Match ANY keyword (OR search)", "IRGENDEIN Suchbegriff (ODER-Suche)";
+    MatchAll => "Match ALL keywords (AND search)", "ALLE Suchbegriffe (UND-Suche)";
+    FoundMatchingFoods => "Found {} foods matching your search criteria (best matches first).",
+        "{} passende Lebensmittel gefunden (beste Treffer zuerst).";
+    ApproximateMatches => "{} of them match only part of a word or a similar spelling.",
+        "{} davon passen nur zu einem Wortteil oder einer ähnlichen Schreibweise.";
+    AdvancedSearch => "Advanced Search", "Erweiterte Suche";
+    CaloriesLeftToday => "Calories left today: {:.0}", "Heute verbleibende Kalorien: {:.0}";
+    AdvancedKeywordsPrompt => "Keywords (comma-separated, or Enter for any food): ",
+        "Suchbegriffe (durch Kommas getrennt, oder Enter für alle Lebensmittel): ";
+    NutrientList => "Nutrients: {}; compare with <, <=, >, >=, or =.", "Nährstoffe: {}; Vergleich mit <, <=, >, >= oder =.";
+    NutrientFiltersPromptRemaining => "Nutrient filters (e.g. calories < 200, protein > 10 g; Enter for calories <= {:.0}): ",
+        "Nährstofffilter (z. B. calories < 200, protein > 10 g; Enter für calories <= {:.0}): ";
+    NutrientFiltersPrompt => "Nutrient filters (e.g. calories < 200, protein > 10 g; Enter for none): ",
+        "Nährstofffilter (z. B. calories < 200, protein > 10 g; Enter für keine): ";
+    FoundFoodsByCalories => "Found {} foods (lowest calories first).", "{} Lebensmittel gefunden (wenigste Kalorien zuerst).";
+    FoundFoodsWithFilters => "Found {} foods with {} (lowest calories first).", "{} Lebensmittel mit {} gefunden (wenigste Kalorien zuerst).";
+
+    // Adding and editing foods
+    EnterFoodName => "Enter food name: ", "Name des Lebensmittels eingeben: ";
+    NutritionValuesGiven => "Nutrition values are given:", "Die Nährwerte sind angegeben:";
+    PerServing => "Per serving", "Pro Portion";
+    Per100g => "Per 100 g", "Pro 100 g";
+    EnterCaloriesPer => "Enter calories per {}: ", "Kalorien pro {} eingeben: ";
+    InvalidCalories => "Invalid calories. Please enter a non-negative number.", "Ungültige Kalorien. Bitte eine nicht negative Zahl eingeben.";
+    MacronutrientsPer => "Macronutrients per {} in grams (press Enter to skip):", "Makronährstoffe pro {} in Gramm (Enter zum Überspringen):";
+    Protein => "Protein", "Eiweiß";
+    Carbs => "Carbs", "Kohlenhydrate";
+    Fat => "Fat", "Fett";
+    Fiber => "Fiber", "Ballaststoffe";
+    AlcoholCaffeinePer => "Alcohol and caffeine per {} (press Enter to skip):", "Alkohol und Koffein pro {} (Enter zum Überspringen):";
+    AlcoholGrams => "Alcohol (g)", "Alkohol (g)";
+    CaffeineMilligrams => "Caffeine (mg)", "Koffein (mg)";
+    ServingWeightPrompt => "Serving weight in grams (press Enter to skip): ", "Portionsgewicht in Gramm (Enter zum Überspringen): ";
+    InvalidWeightPositive => "Invalid weight. Please enter a positive number.", "Ungültiges Gewicht. Bitte eine positive Zahl eingeben.";
+    CostPerPrompt => "Cost per {} (press Enter to skip): ", "Kosten pro {} (Enter zum Überspringen): ";
+    InvalidCost => "Invalid cost. Please enter a non-negative number.", "Ungültige Kosten. Bitte eine nicht negative Zahl eingeben.";
+    DefaultServingsPrompt => "Servings usually logged at once (press Enter for 1): ", "Üblicherweise auf einmal erfasste Portionen (Enter für 1): ";
+    FoodNotAdded => "Food not added.", "Lebensmittel nicht hinzugefügt.";
+    FoodAdded => "Food added successfully!", "Lebensmittel erfolgreich hinzugefügt!";
+    EnterKeywords => "Enter keywords (comma-separated): ", "Schlagwörter eingeben (durch Kommas getrennt): ";
+    AddComponents => "Add components (enter empty food ID to finish, /text to list matching foods):",
+        "Zutaten hinzufügen (leere Lebensmittel-ID zum Beenden, /Text listet passende Lebensmittel):";
+    EnterComponent => "Enter component number or food ID: ", "Nummer der Zutat oder Lebensmittel-ID eingeben: ";
+    NoComponentsAdded => "No components added. Cannot create composite food.",
+        "Keine Zutaten hinzugefügt. Zusammengesetztes Lebensmittel kann nicht erstellt werden.";
+    CompositeFoodAdded => "Composite food added successfully!", "Zusammengesetztes Lebensmittel erfolgreich hinzugefügt!";
+    EnterFoodsToCompare => "Enter the foods to compare (empty food ID to finish):",
+        "Zu vergleichende Lebensmittel eingeben (leere Lebensmittel-ID zum Beenden):";
+    EnterFoodId => "Enter food ID: ", "Lebensmittel-ID eingeben: ";
+    AlreadyCompared => "'{}' is already in the comparison.", "'{}' ist bereits im Vergleich.";
+    ChooseTwoFoods => "Please choose at least two foods to compare.", "Bitte mindestens zwei Lebensmittel zum Vergleichen wählen.";
+    NothingToArchive => "Every food has been used within the last {} days. Nothing to archive.",
+        "Jedes Lebensmittel wurde in den letzten {} Tagen verwendet. Nichts zu archivieren.";
+    UnusedFoods => "These foods have not been logged for at least {} days:", "Diese Lebensmittel wurden seit mindestens {} Tagen nicht erfasst:";
+    ArchiveExplanation => "Archived foods are hidden from food lists; old logs and recipes using them",
+        "Archivierte Lebensmittel werden in Listen ausgeblendet; alte Einträge und Rezepte damit";
+    ArchiveExplanationContinued => "are not affected, and logging one by its ID restores it.",
+        "bleiben unberührt, und das Erfassen über die ID stellt es wieder her.";
+    ConfirmArchive => "Type 'yes' to archive all of them: ", "'yes' eingeben, um alle zu archivieren: ";
+    NothingArchived => "Nothing archived.", "Nichts archiviert.";
+    ArchivedFoods => "Archived {} food(s).", "{} Lebensmittel archiviert.";
+    Name => "Name", "Name";
+    LastUsed => "Last used", "Zuletzt verwendet";
+    NoArchivedFoods => "No foods are archived.", "Keine Lebensmittel archiviert.";
+    FoodRestored => "Food restored successfully!", "Lebensmittel erfolgreich wiederhergestellt!";
+    EnterFoodIds => "Enter food IDs (comma-separated): ", "Lebensmittel-IDs eingeben (durch Kommas getrennt): ";
+    EnterAtLeastOneFoodId => "Please enter at least one food ID.", "Bitte mindestens eine Lebensmittel-ID eingeben.";
+    KeywordsColumn => "Keywords", "Schlagwörter";
+    AddKeywords => "Add keywords", "Schlagwörter hinzufügen";
+    RemoveKeywords => "Remove keywords", "Schlagwörter entfernen";
+    EnterAtLeastOneKeyword => "Please enter at least one keyword.", "Bitte mindestens ein Schlagwort eingeben.";
+    NothingToChange => "Nothing to change.", "Nichts zu ändern.";
+    AddedKeywords => "Added {} to {} food(s).", "{} zu {} Lebensmittel(n) hinzugefügt.";
+    RemovedKeywords => "Removed {} from {} food(s).", "{} aus {} Lebensmittel(n) entfernt.";
+    DefaultServingsForPrompt => "Servings usually logged for {} (currently {}; press Enter to keep): ",
+        "Üblicherweise erfasste Portionen für {} (derzeit {}; Enter behält den Wert): ";
+    InvalidServingsPositive => "Invalid servings. Please enter a positive number.", "Ungültige Portionen. Bitte eine positive Zahl eingeben.";
+    DefaultServingsSet => "{} is now logged as {} serving(s) unless changed.", "{} wird nun mit {} Portion(en) erfasst, sofern nicht geändert.";
+    NoDuplicates => "No likely duplicate foods found.", "Keine wahrscheinlichen Duplikate gefunden.";
+    FoundDuplicates => "Found {} possible duplicate pair(s). Merging keeps one food and moves the",
+        "{} mögliche Duplikatpaare gefunden. Beim Zusammenführen bleibt ein Lebensmittel erhalten, und";
+    FoundDuplicatesContinued => "recipes, log entries, and meal plans that use the other one over to it.",
+        "Rezepte, Einträge und Essenspläne des anderen werden darauf übertragen.";
+    PairOf => "Pair {} of {}:", "Paar {} von {}:";
+    KeepAndMerge => "Keep {}, merge {} into it", "{} behalten, {} darin zusammenführen";
+    SkipPair => "Skip this pair", "Dieses Paar überspringen";
+    Stop => "Stop", "Beenden";
+    MergedInto => "Merged {} into {}.", "{} in {} zusammengeführt.";
+    MergedPairs => "Merged {} pair(s).", "{} Paar(e) zusammengeführt.";
+    SuggestedKeywords => "Suggested keywords: {}", "Vorgeschlagene Schlagwörter: {}";
+    EnterKeywordsWithSuggestions => "Enter keywords (comma-separated; Enter accepts the suggestions, +WORD adds to them): ",
+        "Schlagwörter eingeben (durch Kommas getrennt; Enter übernimmt die Vorschläge, +WORT ergänzt sie): ";
+    SearchFoodSourcesFor => "Search food sources for: ", "In Lebensmittelquellen suchen nach: ";
+    EnterSearchText => "Please enter something to search for.", "Bitte einen Suchbegriff eingeben.";
+    ServingBasis => "serving", "Portion";
+    InvalidAmountNonNegative => "Invalid amount. Please enter a non-negative number.", "Ungültige Menge. Bitte eine nicht negative Zahl eingeben.";
+    EnterFoodIdToRestore => "Enter food ID to restore: ", "Wiederherzustellende Lebensmittel-ID eingeben: ";
+    NotArchivedFood => "'{}' is not an archived food.", "'{}' ist kein archiviertes Lebensmittel.";
+
+    // Food sources and food prompts
+    SearchingSourcesCancellable => "Searching {}... (press Enter to cancel)", "Suche in {}... (Enter bricht ab)";
+    SearchingSources => "Searching {}...", "Suche in {}...";
+    SearchCancelled => "Search cancelled.", "Suche abgebrochen.";
+    SkippedSource => "  Skipped {}: {}", "  {} übersprungen: {}";
+    NoFoodsFound => "No foods found.", "Keine Lebensmittel gefunden.";
+    ShowingBestResults => "Showing the best {} of {} results.", "Die besten {} von {} Ergebnissen werden angezeigt.";
+    FoodIdExists => "A food with ID '{}' already exists.", "Ein Lebensmittel mit der ID '{}' existiert bereits.";
+    AddedFoodAs => "Added {} as {}.", "{} als {} hinzugefügt.";
+    DidYouMean => "Did you mean:", "Meinten Sie:";
+    SearchSourcesFor => "Search food sources for \"{}\"", "Lebensmittelquellen nach \"{}\" durchsuchen";
+    LogEntryOne => "log entry", "Eintrag";
+    LogEntryMany => "log entries", "Einträge";
+    RecipeOne => "recipe", "Rezept";
+    RecipeMany => "recipes", "Rezepte";
+    ServingSizePrompt => "Serving size, e.g. 1 cup, 240 ml, 30 g, 1 piece (press Enter to skip): ",
+        "Portionsgröße, z. B. 1 cup, 240 ml, 30 g, 1 piece (Enter zum Überspringen): ";
+    IncludeUnit => "Please include a unit (g, ml, cup, piece, oz).", "Bitte eine Einheit angeben (g, ml, cup, piece, oz).";
+    EnterAmountWithSize => "Enter amount (servings, or with a unit; 1 serving = {}){}: ",
+        "Menge eingeben (Portionen oder mit Einheit; 1 Portion = {}){}: ";
+    EnterAmount => "Enter amount (servings, or grams like 85 g if the serving weight is known){}: ",
+        "Menge eingeben (Portionen, oder Gramm wie 85 g, wenn das Portionsgewicht bekannt ist){}: ";
+    CategoryPrompt => "Category, e.g. Dairy or Fruit (press Enter to skip): ", "Kategorie, z. B. Dairy oder Fruit (Enter zum Überspringen): ";
+    TagsPrompt => "Tags, e.g. vegan, contains-gluten (comma-separated, Enter to skip): ",
+        "Tags, z. B. vegan, contains-gluten (durch Kommas getrennt, Enter zum Überspringen): ";
+    CheckFoodValues => "Please check the values of {}:", "Bitte die Werte von {} prüfen:";
+    ConfirmAddFoodAnyway => "Type 'yes' to add the food anyway: ", "'yes' eingeben, um das Lebensmittel trotzdem hinzuzufügen: ";
+    CostOneUnpriced => "{:.2} (1 entry without a cost)", "{:.2} (1 Eintrag ohne Kosten)";
+    CostUnpriced => "{:.2} ({} entries without a cost)", "{:.2} ({} Einträge ohne Kosten)";
+    MealLabel => "Meal:", "Mahlzeit:";
+    TimeSkippedByClockChange => "{} does not exist on {} because of a clock change.", "{} gibt es am {} wegen einer Zeitumstellung nicht.";
+    TimeEatenPrompt => "Time eaten (HH:MM, press Enter for {}): ", "Uhrzeit des Essens (HH:MM, Enter für {}): ";
+    EnterNewFoodId => "Enter food ID (no spaces): ", "Lebensmittel-ID eingeben (ohne Leerzeichen): ";
+    FoodIdEmpty => "Food ID cannot be empty.", "Die Lebensmittel-ID darf nicht leer sein.";
+    FoodIdSpaces => "Food ID cannot contain spaces.", "Die Lebensmittel-ID darf keine Leerzeichen enthalten.";
+    CaloriesColumn => "Calories", "Kalorien";
+    PickFoodHint => "Please enter a number, a food ID, or /text to filter the list.",
+        "Bitte eine Nummer, eine Lebensmittel-ID oder /Text zum Filtern der Liste eingeben.";
+    NoFoodsMatch => "No foods match '{}'.", "Keine Lebensmittel passen zu '{}'.";
+    FoodIdMissing => "Food with ID '{}' doesn't exist.", "Lebensmittel mit der ID '{}' existiert nicht.";
+    NoFoodsInDatabase => "No foods in database.", "Keine Lebensmittel in der Datenbank.";
+    FoodsPageOf => "Page {} of {} ({} foods in {}, sorted by {})", "Seite {} von {} ({} Lebensmittel in {}, sortiert nach {})";
+    FoodsPageActions => "[n]ext, [p]revious, page number, [s]ort, [c]ategory, or Enter to return: ",
+        "[n] nächste, [p] vorherige, Seitenzahl, [s] sortieren, [c] Kategorie oder Enter zum Zurückkehren: ";
+    SortByCalories => "Calories (lowest first)", "Kalorien (niedrigste zuerst)";
+    ArchivedNotShown => "({} archived food(s) not shown; see Manage Foods > Restore Archived Food)",
+        "({} archivierte(s) Lebensmittel nicht angezeigt; siehe Lebensmittel verwalten > Archiviertes Lebensmittel wiederherstellen)";
+    AllCategories => "All categories", "Alle Kategorien";
+    NoFoodsAddFirst => "No foods in database. Please add foods first.", "Keine Lebensmittel in der Datenbank. Bitte zuerst Lebensmittel hinzufügen.";
+
+    // Logging food
+    ShowAllFoods => "Show all foods", "Alle Lebensmittel anzeigen";
+    SearchByKeyword => "Search foods by keyword", "Lebensmittel nach Schlagwort suchen";
+    QuickLogOption => "Quick log (most used and recent foods)", "Schnell eintragen (häufigste und letzte Lebensmittel)";
+    AdvancedSearchOption => "Advanced search (keywords and nutrient limits)", "Erweiterte Suche (Schlagwörter und Nährstoffgrenzen)";
+    MealTemplatesOption => "Meal templates (log several foods at once)", "Mahlzeitvorlagen (mehrere Lebensmittel auf einmal eintragen)";
+    BatchLogOption => "Batch log (several foods typed on one line)", "Sammeleintrag (mehrere Lebensmittel in einer Zeile)";
+    DescribeOption => "Describe what you ate (e.g. 2 eggs and a slice of bread)",
+        "Beschreiben, was Sie gegessen haben (z. B. 2 eggs and a slice of bread)";
+    AvailableFoods => "Available foods:", "Verfügbare Lebensmittel:";
+    EnterNumberOrFoodId => "Enter a number or food ID (/text to filter): ", "Nummer oder Lebensmittel-ID eingeben (/Text zum Filtern): ";
+    NothingLoggedYet => "Nothing logged yet. Log a food by search or from the full list first.",
+        "Noch nichts eingetragen. Zuerst ein Lebensmittel über die Suche oder die vollständige Liste eintragen.";
+    UsesColumn => "Uses", "Anzahl";
+    LastAmountColumn => "Last amount", "Letzte Menge";
+    LastLoggedColumn => "Last logged", "Zuletzt eingetragen";
+    MostUsed => "Most used", "Am häufigsten";
+    Recent => "Recent", "Zuletzt";
+    NumberBetween => "Please enter a number between 1 and {}.", "Bitte eine Zahl zwischen 1 und {} eingeben.";
+    LogWhichFood => "Log which food? ", "Welches Lebensmittel eintragen? ";
+    CannotRepeatAmount => "Cannot repeat the last amount of '{}': {}", "Die letzte Menge von '{}' kann nicht wiederholt werden: {}";
+    MealTemplates => "Meal Templates", "Mahlzeitvorlagen";
+    NoMealTemplates => "No meal templates yet.", "Noch keine Mahlzeitvorlagen.";
+    LogTemplate => "Log a template", "Vorlage eintragen";
+    CreateTemplate => "Create a template", "Vorlage erstellen";
+    DeleteTemplate => "Delete a template", "Vorlage löschen";
+    CreateTemplateFirst => "Create a template first.", "Zuerst eine Vorlage erstellen.";
+    LogWhichTemplate => "Log which template? ", "Welche Vorlage eintragen? ";
+    DeleteWhichTemplate => "Delete which template? ", "Welche Vorlage löschen? ";
+    ConfirmDeleteTemplate => "Type 'yes' to delete the template '{}': ", "'yes' eingeben, um die Vorlage '{}' zu löschen: ";
+    DeleteCancelled => "Delete cancelled.", "Löschen abgebrochen.";
+    DeletedTemplate => "Deleted meal template '{}'.", "Mahlzeitvorlage '{}' gelöscht.";
+    SkippingMissingFood => "Skipping '{}': it is no longer in the food database.",
+        "'{}' wird übersprungen: Es ist nicht mehr in der Lebensmitteldatenbank.";
+    TemplateServingsPrompt => "Servings of {} ({}) [{}, 0 to leave out]: ", "Portionen von {} ({}) [{}, 0 zum Weglassen]: ";
+    EnterServingsOrZero => "Please enter a number of servings (0 or more).", "Bitte eine Anzahl Portionen eingeben (0 oder mehr).";
+    NothingToLog => "Nothing to log.", "Nichts einzutragen.";
+    LoggedTemplate => "Logged '{}': {} foods, {:.0} kcal.", "'{}' eingetragen: {} Lebensmittel, {:.0} kcal.";
+    BatchLogHint => "Enter foods as ID:amount separated by commas, e.g. apple:1, milk:0.5, chicken:150 g",
+        "Lebensmittel als ID:Menge durch Kommas getrennt eingeben, z. B. apple:1, milk:0.5, chicken:150 g";
+    BatchLogHintContinued => "(a food without an amount is logged with its default servings).",
+        "(ein Lebensmittel ohne Menge wird mit seinen üblichen Portionen eingetragen).";
+    FoodsPrompt => "Foods: ", "Lebensmittel: ";
+    AmountColumn => "Amount", "Menge";
+    Total => "Total", "Summe";
+    LoggedFoods => "Logged {} foods, {:.0} kcal.", "{} Lebensmittel eingetragen, {:.0} kcal.";
+    WhatDidYouEat => "What did you eat? ", "Was haben Sie gegessen? ";
+    YouWroteColumn => "You wrote", "Ihre Eingabe";
+    UnderstoodAsColumn => "Understood as", "Verstanden als";
+    NoMatchingFood => "no matching food", "kein passendes Lebensmittel";
+    UnderstoodFood => "{} of {} ({}), {:.0} kcal", "{} {} ({}), {:.0} kcal";
+    NothingLoggedDescribed => "Nothing logged. Add the missing foods or name them differently, or use Batch log with food IDs.",
+        "Nichts eingetragen. Fehlende Lebensmittel hinzufügen, anders benennen oder den Sammeleintrag mit Lebensmittel-IDs verwenden.";
+    TotalKcal => "Total: {:.0} kcal", "Summe: {:.0} kcal";
+    ConfirmLogFoods => "Type 'yes' to log these foods: ", "'yes' eingeben, um diese Lebensmittel einzutragen: ";
+    NothingLogged => "Nothing logged.", "Nichts eingetragen.";
+    TemplateNamePrompt => "Template name (e.g. Monday breakfast): ", "Name der Vorlage (z. B. Frühstück am Montag): ";
+    EnterName => "Please enter a name.", "Bitte einen Namen eingeben.";
+    TemplateReplaced => "The template '{}' will be replaced.", "Die Vorlage '{}' wird ersetzt.";
+    UsualMeal => "Usual meal:", "Übliche Mahlzeit:";
+    FoodIdToAdd => "Food ID to add (Enter to finish): ", "Hinzuzufügende Lebensmittel-ID (Enter zum Beenden): ";
+    ServingsDefaultOne => "Servings [1]: ", "Portionen [1]: ";
+    EnterPositiveServings => "Please enter a positive number of servings.", "Bitte eine positive Anzahl Portionen eingeben.";
+    SavedTemplate => "Saved meal template '{}' with {} foods.", "Mahlzeitvorlage '{}' mit {} Lebensmitteln gespeichert.";
+    WarningLine => "Warning: {}", "Warnung: {}";
+    ArchivedFoodRestored => "'{}' was archived and has been restored.", "'{}' war archiviert und wurde wiederhergestellt.";
+    FoodLogged => "Food logged successfully!", "Lebensmittel erfolgreich eingetragen!";
+    InvalidAmountPositive => "Invalid amount. Please enter a positive number.", "Ungültige Menge. Bitte eine positive Zahl eingeben.";
+    LoggedDirect => "Logged {} of {} ({:.1} calories).", "{} {} eingetragen ({:.1} Kalorien).";
+
+    // Names of profile values
+    LevelSedentary => "Sedentary", "Sitzend";
+    LevelLightlyActive => "LightlyActive", "Leicht aktiv";
+    LevelModeratelyActive => "ModeratelyActive", "Mäßig aktiv";
+    LevelVeryActive => "VeryActive", "Sehr aktiv";
+    LevelExtremelyActive => "ExtremelyActive", "Extrem aktiv";
+
+    // Names of weekdays
+    Mon => "Mon", "Mo";
+    Tue => "Tue", "Di";
+    Wed => "Wed", "Mi";
+    Thu => "Thu", "Do";
+    Fri => "Fri", "Fr";
+    Sat => "Sat", "Sa";
+    Sun => "Sun", "So";
+
+    // Food log
+    FoodLogFor => "Food log for {}", "Ernährungstagebuch für {}";
+    TotalCalories => "Total calories: {:.1}", "Kalorien gesamt: {:.1}";
+    TotalCost => "Total cost: {}", "Kosten gesamt: {}";
+    TargetCalories => "Target calories: {:.1}", "Kalorienziel: {:.1}";
+    WeekdayOverride => "({} target override: {} kcal)", "(Ziel für {} abweichend: {} kcal)";
+    Difference => "Difference: {}", "Differenz: {}";
+    NoFoodEntriesFor => "No food entries for {}", "Keine Einträge für {}";
+    TimeColumn => "Time", "Zeit";
+    FoodIdColumn => "Food ID", "Lebensmittel-ID";
+    MealColumn => "Meal", "Mahlzeit";
+    Unknown => "Unknown", "Unbekannt";
+    CaloriesAsLoggedNote => "* Calories as logged, before the food was changed or deleted; macros and cost use its current values.",
+        "* Kalorien wie eingetragen, bevor das Lebensmittel geändert oder gelöscht wurde; Makros und Kosten verwenden die aktuellen Werte.";
+    FoodLogRange => "Food log from {} to {}", "Ernährungstagebuch vom {} bis {}";
+    DateColumn => "Date", "Datum";
+    EntriesColumn => "Entries", "Einträge";
+    TargetColumn => "Target", "Ziel";
+    DifferenceColumn => "Difference", "Differenz";
+    NoEntriesInRange => "No food entries in this range.", "Keine Einträge in diesem Zeitraum.";
+    RangeTotal => "Total calories: {:.1} over {} logged day(s), {:.1} per logged day",
+        "Kalorien gesamt: {:.1} an {} eingetragenen Tag(en), {:.1} pro eingetragenem Tag";
+    EnterDayNumber => "Enter a day number to see its entries (1-{}, Enter to go back): ",
+        "Nummer eines Tages für seine Einträge eingeben (1-{}, Enter zum Zurückgehen): ";
+    EnterDayFromList => "Please enter a day number from the list.", "Bitte eine Tagesnummer aus der Liste eingeben.";
+    Last7Days => "Last 7 days", "Letzte 7 Tage";
+    Last30Days => "Last 30 days", "Letzte 30 Tage";
+    CustomRange => "Custom range", "Eigener Zeitraum";
+    StartDatePrompt => "Start date: ", "Startdatum: ";
+    EndDatePrompt => "End date (press Enter for the working date): ", "Enddatum (Enter für das Arbeitsdatum): ";
+    EndBeforeStart => "The end date cannot be before the start date.", "Das Enddatum darf nicht vor dem Startdatum liegen.";
+    CalorieSourcesRange => "Calorie sources from {} to {}", "Kalorienquellen vom {} bis {}";
+    EntriesAndCalories => "{} entries, {:.1} calories", "{} Einträge, {:.1} Kalorien";
+    MostFrequentFoods => "Most Frequent Foods:", "Häufigste Lebensmittel:";
+    MostCalories => "Most Calories:", "Meiste Kalorien:";
+    CaloriesByCategory => "Calories by Category:", "Kalorien nach Kategorie:";
+    FoodColumn => "Food", "Lebensmittel";
+    ShareColumn => "Share", "Anteil";
+    MoodAndEnergy => "Mood: {}  Energy: {}", "Stimmung: {}  Energie: {}";
+    NoteLine => "Note: {}", "Notiz: {}";
+
+    // Day notes and meal plans
+    MostOverPlan => "Most over plan: {} (+{:.1} calories)", "Am stärksten über Plan: {} (+{:.1} Kalorien)";
+    NoteLabel => "Note", "Notiz";
+    RatingLabel => "{} from {} to {}", "{} von {} bis {}";
+    MoodLabel => "Mood", "Stimmung";
+    EnergyLabel => "Energy", "Energie";
+    NoteUnchanged => "Note unchanged.", "Notiz unverändert.";
+    NoteSaved => "Note saved.", "Notiz gespeichert.";
+    ErrorSavingNote => "Error saving the note: {}", "Fehler beim Speichern der Notiz: {}";
+    KeepOrClearPrompt => "{} (press Enter to keep '{}', 'none' to clear): ", "{} (Enter behält '{}', 'none' löscht): ";
+    OptionalPrompt => "{} (optional): ", "{} (optional): ";
+    MealPlanVsLog => "Meal plan vs. log:", "Essensplan und Tagebuch im Vergleich:";
+    PlannedColumn => "Planned", "Geplant";
+    LoggedColumn => "Logged", "Eingetragen";
+    OverPlan => "  over plan", "  über Plan";
+    Unassigned => "Unassigned", "Ohne Mahlzeit";
+    NothingPlanned => "Nothing planned.", "Nichts geplant.";
+    ServingsColumn => "Servings", "Portionen";
+    NothingPlannedForDay => "Nothing planned for this day.", "Für diesen Tag ist nichts geplant.";
+    InvalidItemNumber => "Invalid item number. Please enter a number between 1 and {}.",
+        "Ungültige Nummer. Bitte eine Zahl zwischen 1 und {} eingeben.";
+    ItemToRemovePrompt => "Enter the item number to remove (1-{}): ", "Nummer des zu entfernenden Eintrags (1-{}): ";
+    EnterServings => "Enter servings: ", "Portionen eingeben: ";
+
+    // Profile
+    GenderLine => "Gender: {}", "Geschlecht: {}";
+    ActivityLevelLine => "Activity Level: {}", "Aktivitätsniveau: {}";
+    HistoryLine => "  Before {}: {}, {}, {}", "  Vor dem {}: {}, {}, {}";
+    SelectGenderCurrent => "Select your gender (current: {}):", "Geschlecht wählen (derzeit: {}):";
+    WeekdayCalculated => "{} (calculated)", "{} (berechnet)";
+    WeekdayTargetPrompt => "Target for {} (e.g. 2500, +300, -200; leave blank to use the calculated target): ",
+        "Ziel für {} (z. B. 2500, +300, -200; leer lassen für das berechnete Ziel): ";
+    WeekdayTargetSet => "{} target set to {} kcal.", "Ziel für {} auf {} kcal gesetzt.";
+    WeekdayTargetCleared => "{} uses the calculated target.", "{} verwendet das berechnete Ziel.";
+    EnterHeight => "Enter your height in {}: ", "Größe in {} eingeben: ";
+    EnterBirthDate => "Enter your birth date (YYYY-MM-DD): ", "Geburtsdatum eingeben (JJJJ-MM-TT): ";
+    EnterCurrentWeight => "Enter your current weight in {}: ", "Aktuelles Gewicht in {} eingeben: ";
+    CurrentProfile => "Current Profile:", "Aktuelles Profil:";
+    HeightLine => "Height: {}", "Größe: {}";
+    HeightUnknown => "Height: unknown", "Größe: unbekannt";
+    BirthDateLine => "Birth Date: {}", "Geburtsdatum: {}";
+    BirthDateUnknown => "Birth Date: unknown", "Geburtsdatum: unbekannt";
+    AgeLine => "Age: {} years", "Alter: {} Jahre";
+    CurrentWeightLine => "Current Weight: {}", "Aktuelles Gewicht: {}";
+    CarriedFromLine => "(Carried forward from {}; not confirmed yet)", "(Übernommen vom {}; noch nicht bestätigt)";
+    CalculationMethodLine => "Calculation Method: {}", "Berechnungsmethode: {}";
+    WeekdayTargetsLine => "Weekday Targets: {}", "Ziele je Wochentag: {}";
+    DietPresetLine => "Diet Preset: {} ({}, {:+} kcal)", "Ernährungsvorgabe: {} ({}, {:+} kcal)";
+    LifeStageLine => "Life Stage: {}", "Lebensphase: {}";
+    MacroTargetsLine => "Macro Targets: {}", "Makroziele: {}";
+    DietaryRestrictionsLine => "Dietary Restrictions: {}", "Ernährungseinschränkungen: {}";
+    ProfileHistory => "Profile History (values used for earlier days):", "Profilverlauf (Werte für frühere Tage):";
+    HeightUnknownShort => "height unknown", "Größe unbekannt";
+    CurrentHeight => "Current height: {}", "Aktuelle Größe: {}";
+    EnterHeightKeep => "Enter your height in {} (or leave blank to keep current): ", "Größe in {} eingeben (oder leer lassen, um sie zu behalten): ";
+    CurrentBirthDate => "Current birth date: {}", "Aktuelles Geburtsdatum: {}";
+    EnterBirthDateKeep => "Enter your birth date (YYYY-MM-DD) (or leave blank to keep current): ",
+        "Geburtsdatum eingeben (JJJJ-MM-TT) (oder leer lassen, um es zu behalten): ";
+    ConfirmOverwriteBasic => "Type 'yes' to overwrite your basic information: ", "'yes' eingeben, um Ihre Grunddaten zu überschreiben: ";
+    ProfileNotChanged => "Profile not changed.", "Profil nicht geändert.";
+    CurrentWeight => "Current weight: {}", "Aktuelles Gewicht: {}";
+    EnterWeight => "Enter your weight in {}: ", "Gewicht in {} eingeben: ";
+    ConfirmReplaceDaily => "Type 'yes' to replace the data recorded for {}: ", "'yes' eingeben, um die Daten für {} zu ersetzen: ";
+    AvailableMethods => "Available calculation methods:", "Verfügbare Berechnungsmethoden:";
+    CurrentMethod => "Current method: {}", "Aktuelle Methode: {}";
+    MethodChanged => "Calculation method changed to: {}", "Berechnungsmethode geändert zu: {}";
+    ChooseDay => "Choose a day:", "Tag wählen:";
+    CurrentPreset => "Current preset: {}", "Aktuelle Vorgabe: {}";
+    CurrentPresetNone => "Current preset: none", "Aktuelle Vorgabe: keine";
+    NoPreset => "No preset", "Keine Vorgabe";
+    PresetSet => "Diet preset set to {}.", "Ernährungsvorgabe auf {} gesetzt.";
+    PresetOff => "Diet preset turned off.", "Ernährungsvorgabe ausgeschaltet.";
+    CurrentLifeStage => "Current life stage: {}", "Aktuelle Lebensphase: {}";
+    CurrentLifeStageNone => "Current life stage: none", "Aktuelle Lebensphase: keine";
+    Pregnant => "Pregnant", "Schwanger";
+    Breastfeeding => "Breastfeeding", "Stillend";
+    Neither => "Neither", "Keines von beiden";
+    DueDate => "Due date", "Geburtstermin";
+    BabyBirthDate => "Baby's birth date", "Geburtsdatum des Babys";
+    LifeStageSet => "Life stage set to {}.", "Lebensphase auf {} gesetzt.";
+    LifeStageCleared => "Life stage cleared.", "Lebensphase entfernt.";
+    CurrentTargets => "Current targets: {}", "Aktuelle Ziele: {}";
+    CurrentTargetsNone => "Current targets: none", "Aktuelle Ziele: keine";
+    PercentOfCalories => "Percent of calories", "Prozent der Kalorien";
+    GramsPerDay => "Grams per day", "Gramm pro Tag";
+    ClearMacroTargets => "Clear (use the diet preset's ratios, if any)", "Entfernen (Verhältnisse der Ernährungsvorgabe verwenden, falls vorhanden)";
+    PercentFromPrompt => "Percent of calories from {}: ", "Prozent der Kalorien aus {}: ";
+    EnterPercentage => "Please enter a percentage.", "Bitte einen Prozentwert eingeben.";
+    EnterPercentage100 => "Please enter a percentage from 0 to 100.", "Bitte einen Prozentwert von 0 bis 100 eingeben.";
+    GramsOfPrompt => "Grams of {} per day: ", "Gramm {} pro Tag: ";
+    EnterNonNegative => "Please enter a non-negative number.", "Bitte eine nicht negative Zahl eingeben.";
+    MacroTargetsSet => "Macro targets set to {}.", "Makroziele auf {} gesetzt.";
+    MacroTargetsCleared => "Macro targets cleared.", "Makroziele entfernt.";
+    CurrentRestrictionsNone => "Current restrictions: none", "Aktuelle Einschränkungen: keine";
+    CurrentRestrictions => "Current restrictions: {}", "Aktuelle Einschränkungen: {}";
+    AvailableRestrictions => "Available: {}", "Verfügbar: {}";
+    EnterRestrictions => "Enter restrictions (comma-separated, 'none' to clear, Enter to keep): ",
+        "Einschränkungen eingeben (durch Kommas getrennt, 'none' entfernt, Enter behält): ";
+    RestrictionsUnchanged => "Dietary restrictions unchanged.", "Ernährungseinschränkungen unverändert.";
+    RestrictionsCleared => "Dietary restrictions cleared.", "Ernährungseinschränkungen entfernt.";
+    RestrictionsSet => "Dietary restrictions set to {}.", "Ernährungseinschränkungen auf {} gesetzt.";
+    ProteinWord => "protein", "Eiweiß";
+    CarbsWord => "carbs", "Kohlenhydrate";
+    FatWord => "fat", "Fett";
+
+    // Users and data bundles
+    AlreadyUsing => "Already using {}.", "{} ist bereits aktiv.";
+    SwitchNotSaved => "The current data could not be saved, so the switch to {} was cancelled.",
+        "Die aktuellen Daten konnten nicht gespeichert werden, daher wurde der Wechsel zu {} abgebrochen.";
+    ExportNotSaved => "The current data could not be saved, so nothing was exported.",
+        "Die aktuellen Daten konnten nicht gespeichert werden, daher wurde nichts exportiert.";
+    ImportNotSaved => "The current data could not be saved, so nothing was imported.",
+        "Die aktuellen Daten konnten nicht gespeichert werden, daher wurde nichts importiert.";
+    SwitchedUser => "Switched to user {}.", "Zu Benutzer {} gewechselt.";
+    BundleFileDefault => "Bundle file (Enter for {}): ", "Paketdatei (Enter für {}): ";
+    ErrorCreating => "Error creating {}: {}", "Fehler beim Anlegen von {}: {}";
+    ExportedTo => "Exported {} to {}", "{} nach {} exportiert";
+    BundleNotEncrypted => "Note: the bundle is not encrypted; keep it somewhere safe.",
+        "Hinweis: Das Paket ist nicht verschlüsselt; bewahren Sie es sicher auf.";
+    BundleFile => "Bundle file: ", "Paketdatei: ";
+    EnterBundlePath => "Enter the path of a bundle written by Export Everything.",
+        "Den Pfad eines mit „Alles exportieren“ geschriebenen Pakets eingeben.";
+    AtUnknownTime => "at an unknown time", "zu unbekannter Zeit";
+    ExportedWhen => "Exported {}: {}", "Exportiert {}: {}";
+    BundleReplaces => "These files replace the current ones for {} (the current versions are backed up).",
+        "Diese Dateien ersetzen die aktuellen von {} (die aktuellen Fassungen werden gesichert).";
+    ConfirmImport => "Type 'yes' to import: ", "'yes' eingeben, um zu importieren: ";
+    ErrorLoadingImported => "Error loading the imported data: {}", "Fehler beim Laden der importierten Daten: {}";
+    PreviousFilesKept => "The previous files are kept as .bak.1 backups in {}.", "Die vorherigen Dateien liegen als .bak.1-Sicherungen in {}.";
+    ImportedFiles => "Imported {}.", "{} importiert.";
+    OnDateTime => "on %Y-%m-%d %H:%M", "am %Y-%m-%d um %H:%M";
+
+    // Importing from other apps
+    ExportFilePrompt => "Export file: ", "Exportdatei: ";
+    EnterExportPath => "Enter the path of the file exported from the app.", "Den Pfad der aus der App exportierten Datei eingeben.";
+    ErrorReading => "Error reading {}: {}", "Fehler beim Lesen von {}: {}";
+    ErrorLoadingData => "Error loading data: {}", "Fehler beim Laden der Daten: {}";
+    NothingNewToImport => "Nothing new to import.", "Nichts Neues zu importieren.";
+    ErrorSavingImported => "Error saving the imported data: {}", "Fehler beim Speichern der importierten Daten: {}";
+    ErrorRestoring => "Error restoring {}: {}", "Fehler beim Wiederherstellen von {}: {}";
+    PreviousFileKept => "The previous file is kept as a .bak.1 backup in {}.", "Die vorherige Datei liegt als .bak.1-Sicherung in {}.";
+    ImportedFoodsAndEntries => "Imported {} food(s) and {} log entries.", "{} Lebensmittel und {} Einträge importiert.";
+    ReadRows => "Read {} row(s) from the {} export.", "{} Zeile(n) aus dem Export von {} gelesen.";
+    OnDateSuffix => " on {}", " am {}";
+    FromToSuffix => " from {} to {}", " vom {} bis {}";
+    SkippedImported => "Skipped {} entries already imported.", "{} bereits importierte Einträge übersprungen.";
+    CreatedFoods => "Created {} food(s){}", "{} Lebensmittel angelegt{}";
+    WouldCreateFoods => "Would create {} food(s){}", "Würde {} Lebensmittel anlegen{}";
+    AddedEntries => "Added {} log entries{}.", "{} Tagebucheinträge{} hinzugefügt.";
+    WouldAddEntries => "Would add {} log entries{}.", "Würde {} Tagebucheinträge{} hinzufügen.";
+    AndMore => "... and {} more", "... und {} weitere";
+    NotImported => "Not imported: {}", "Nicht importiert: {}";
+    CheckValue => "Check: {}", "Prüfen: {}";
+
+    // Statistics
+    ActivityMultiplierLine => "Activity Multiplier: x{} ({})", "Aktivitätsfaktor: x{} ({})";
+    StatisticsFor => "Statistics for {}", "Statistik für {}";
+    TargetUsesCarried => "(Target uses weight and activity level carried forward from {})",
+        "(Das Ziel verwendet Gewicht und Aktivitätsniveau vom {})";
+    BmiLine => "BMI: {:.1} ({})", "BMI: {:.1} ({})";
+    BmrLine => "BMR: {:.1} ({})", "Grundumsatz: {:.1} ({})";
+    TdeeLine => "TDEE: {:.1}", "Gesamtumsatz: {:.1}";
+    TargetCaloriesStat => "Target Calories: {:.1}", "Kalorienziel: {:.1}";
+    CaloriesBurnedStat => "Calories Burned (exercise): {:.1}", "Verbrannte Kalorien (Training): {:.1}";
+    CaloriesConsumedStat => "Total Calories Consumed: {:.1}", "Aufgenommene Kalorien gesamt: {:.1}";
+    MacrosLine => "Macros: {:.1} g protein, {:.1} g carbs, {:.1} g fat", "Makros: {:.1} g Eiweiß, {:.1} g Kohlenhydrate, {:.1} g Fett";
+    AlcoholCaffeineLine => "Alcohol: {:.0} g, Caffeine: {:.0} mg", "Alkohol: {:.0} g, Koffein: {:.0} mg";
+    CalorieDensityLine => "Calorie Density: {:.0} kcal/100 g", "Energiedichte: {:.0} kcal/100 g";
+    FoodCostLine => "Food Cost: {}", "Lebensmittelkosten: {}";
+    PerUnitCostLine => "Per Unit of Cost: {:.0} kcal, {:.1} g protein", "Pro Kosteneinheit: {:.0} kcal, {:.1} g Eiweiß";
+    NoFoodLoggedToday => "No food logged for today.", "Für heute ist nichts eingetragen.";
+    CaloriesConsumedZero => "Total Calories Consumed: 0.0", "Aufgenommene Kalorien gesamt: 0.0";
+    MacroTargetsPreset => "Macro Targets ({} preset):", "Makroziele (Vorgabe {}):";
+    MacroTargetsHeading => "Macro Targets:", "Makroziele:";
+    ThisWeek => "This Week ({} {} to {} {}):", "Diese Woche ({} {} bis {} {}):";
+    WeekCaloriesConsumed => "Total Calories Consumed: {:.1} over {} logged day(s)", "Aufgenommene Kalorien gesamt: {:.1} an {} eingetragenen Tag(en)";
+    AveragePerLoggedDay => "Average per Logged Day: {:.1}", "Durchschnitt pro eingetragenem Tag: {:.1}";
+    WeekFoodCost => "Food Cost: {:.2} ({:.2} per logged day)", "Lebensmittelkosten: {:.2} ({:.2} pro eingetragenem Tag)";
+    LastDaysChart => "Last {} Days (| marks the target, ▒ is over it):", "Letzte {} Tage (| markiert das Ziel, ▒ liegt darüber):";
+    WeightTrend => "Weight Trend ({} weigh-ins, {} to {}):", "Gewichtsverlauf ({} Wägungen, {} bis {}):";
+    WeightHistory => "Weight History:", "Gewichtshistorie:";
+
+    // Preferences and saved searches
+    ReplacedSavedSearch => "Replaced saved search '{}'.", "Gespeicherte Suche '{}' ersetzt.";
+    AddedSavedSearch => "Added saved search '{}'.", "Gespeicherte Suche '{}' hinzugefügt.";
+    FromDataDirFlag => "from --data-dir", "aus --data-dir";
+    FromDataDirEnv => "from YADA_DATA_DIR", "aus YADA_DATA_DIR";
+    FromConfigFile => "from the config file", "aus der Konfigurationsdatei";
+    CurrentDirectory => "current directory", "aktuelles Verzeichnis";
+    DefaultSource => "default", "Standard";
+    UnitsOption => "Units: {}", "Einheiten: {}";
+    AutosaveOption => "Autosave: {}", "Automatisch speichern: {}";
+    DefaultSearchOption => "Default search: {}", "Standardsuche: {}";
+    DateFormatOption => "Date format: {}", "Datumsformat: {}";
+    ConfirmationsOption => "Confirmations: {}", "Bestätigungen: {}";
+    SavedSearchesOption => "Saved searches: {}", "Gespeicherte Suchen: {}";
+    DataDirectoryOption => "Data directory: {} ({})", "Datenverzeichnis: {} ({})";
+    AutosavePrompt => "Autosave ('command' for after every change, 'off', or minutes between saves): ",
+        "Automatisch speichern ('command' nach jeder Änderung, 'off' oder Minuten zwischen den Speicherungen): ";
+    AutosaveHint => "Please enter 'command', 'off', or a number of minutes.", "Bitte 'command', 'off' oder eine Anzahl Minuten eingeben.";
+    AutosaveEnvOverride => "Saved, but {} is set, so autosave stays {} while it is.",
+        "Gespeichert, aber {} ist gesetzt, daher bleibt das automatische Speichern bei {}, solange es gesetzt ist.";
+    DateFormatExample => "{}, e.g. {}", "{}, z. B. {}";
+    PreferencesUpdated => "Preferences updated.", "Einstellungen aktualisiert.";
+    SavedSearches => "Saved Searches", "Gespeicherte Suchen";
+    NoSavedSearches => "No saved searches.", "Keine gespeicherten Suchen.";
+    AddSavedSearch => "Add a saved search", "Gespeicherte Suche hinzufügen";
+    DeleteSavedSearch => "Delete a saved search", "Gespeicherte Suche löschen";
+    SearchNamePrompt => "Name of the search: ", "Name der Suche: ";
+    SavedSearchKeywordsPrompt => "Search keywords (comma-separated; add category:NAME to filter): ",
+        "Suchbegriffe (durch Kommas getrennt; category:NAME filtert nach Kategorie): ";
+    SearchToDeletePrompt => "Number of the search to delete: ", "Nummer der zu löschenden Suche: ";
+    NumberFromTo => "Please enter a number from 1 to {}.", "Bitte eine Zahl von 1 bis {} eingeben.";
+    ConfirmDeleteSearch => "Type 'yes' to delete the saved search '{}': ", "'yes' eingeben, um die gespeicherte Suche '{}' zu löschen: ";
+    DeletedSavedSearch => "Deleted saved search '{}'.", "Gespeicherte Suche '{}' gelöscht.";
+    DataDirReadAtStartup => "The data directory is read at startup from the config file, so a new one is used from the next start.",
+        "Das Datenverzeichnis wird beim Start aus der Konfigurationsdatei gelesen; ein neues gilt daher ab dem nächsten Start.";
+    FilesNotMoved => "Files are not moved; copy them yourself to keep your data.",
+        "Dateien werden nicht verschoben; kopieren Sie sie selbst, um Ihre Daten zu behalten.";
+    NewDataDirPrompt => "New data directory: ", "Neues Datenverzeichnis: ";
+    EnterDirectory => "Please enter a directory.", "Bitte ein Verzeichnis eingeben.";
+    SavedTo => "Saved to {}.", "In {} gespeichert.";
+    DataDirPrecedence => "Note: --data-dir and YADA_DATA_DIR still take precedence over the config file.",
+        "Hinweis: --data-dir und YADA_DATA_DIR haben weiterhin Vorrang vor der Konfigurationsdatei.";
+
+    // Editing log entries
+    NoEntriesToDelete => "No food entries to delete.", "Keine Einträge zum Löschen.";
+    LogEntryLine => "Entry {}: {} of {} ({})", "Eintrag {}: {} {} ({})";
+    FoodEntryDeleted => "Food entry deleted successfully!", "Eintrag erfolgreich gelöscht!";
+    NoEntriesToEdit => "No food entries to edit.", "Keine Einträge zum Bearbeiten.";
+    EntryToEditPrompt => "Enter the entry number to edit (1-{}): ", "Nummer des zu bearbeitenden Eintrags (1-{}): ";
+    EnterFoodIdKeep => "Enter food ID (press Enter to keep '{}'): ", "Lebensmittel-ID eingeben (Enter behält '{}'): ";
+    TimeEatenKeepPrompt => "Time eaten (HH:MM, press Enter to keep {}): ", "Uhrzeit des Essens (HH:MM, Enter behält {}): ";
+    FoodEntryUpdated => "Food entry updated successfully!", "Eintrag erfolgreich aktualisiert!";
+
+    // Comparing foods
+    PerUnitOfCost => "Per unit of cost", "Pro Kosteneinheit";
+    WeightGrams => "Weight (g)", "Gewicht (g)";
+    ProteinGrams => "Protein (g)", "Eiweiß (g)";
+    CarbsGrams => "Carbs (g)", "Kohlenhydrate (g)";
+    FatGrams => "Fat (g)", "Fett (g)";
+    Cost => "Cost", "Kosten";
+
+    // Encrypted data files
+    PasswordDoesNotOpen => "The password in {} does not open {}.", "Das Passwort in {} öffnet {} nicht.";
+    FilesEncryptedSetPassword => "The data files are encrypted; set {} to open them.", "Die Datendateien sind verschlüsselt; zum Öffnen {} setzen.";
+    WillEncrypt => "Data files will be encrypted when they are next saved.", "Die Datendateien werden beim nächsten Speichern verschlüsselt.";
+    EncryptionOff => "Encryption is off ({}=off); data files will be saved unencrypted.",
+        "Die Verschlüsselung ist aus ({}=off); die Datendateien werden unverschlüsselt gespeichert.";
+    PasswordPrompt => "Password for the data files: ", "Passwort für die Datendateien: ";
+    NoPasswordGiven => "No password given.", "Kein Passwort angegeben.";
+    WrongPassword => "Wrong password.", "Falsches Passwort.";
+    WrongPasswordNotOpened => "Wrong password; the data files were not opened.", "Falsches Passwort; die Datendateien wurden nicht geöffnet.";
+    ChoosePasswordPrompt => "Choose a password for the data files: ", "Passwort für die Datendateien wählen: ";
+    PasswordEmpty => "The password cannot be empty.", "Das Passwort darf nicht leer sein.";
+    RepeatPasswordPrompt => "Repeat the password: ", "Passwort wiederholen: ";
+    PasswordsDiffer => "The passwords do not match.", "Die Passwörter stimmen nicht überein.";
+    CannotCheckPassword => "Cannot check the password: {}", "Das Passwort kann nicht geprüft werden: {}";
+
+    // Recovering data files
+    CouldNotLoad => "Could not load {}: {}", "{} konnte nicht geladen werden: {}";
+    RestoreFromBackup => "Restore from a backup", "Aus einer Sicherung wiederherstellen";
+    SkipFile => "Skip this file (it is kept as a .broken copy)", "Diese Datei überspringen (sie bleibt als .broken-Kopie erhalten)";
+    CheckFile => "Check the file for problems", "Die Datei auf Probleme prüfen";
+    Quit => "Quit", "Beenden";
+    RestoredFromBackup => "Restored {} from {} (broken file kept as {}).", "{} aus {} wiederhergestellt (beschädigte Datei als {} behalten).";
+    MovedAside => "Moved {} to {}; continuing without it.", "{} nach {} verschoben; es geht ohne sie weiter.";
+    RecoveryFailed => "Recovery failed: {}", "Wiederherstellung fehlgeschlagen: {}";
+    NoBackups => "No backups of {} were found.", "Keine Sicherungen von {} gefunden.";
+    BackupSaved => "{} (saved {})", "{} (gespeichert {})";
+    BackupsNewestFirst => "Backups, newest first:", "Sicherungen, neueste zuerst:";
+    CheckHeading => "=== Check: {} ===", "=== Prüfung: {} ===";
+    LinesCount => "Lines: {}", "Zeilen: {}";
+    FormatVersion => "Format version: v{}", "Formatversion: v{}";
+    FormatVersionNone => "Format version: none (legacy file or no header)", "Formatversion: keine (alte Datei oder kein Kopf)";
+    NoLoadProblems => "No problems found that would stop the file from loading.",
+        "Keine Probleme gefunden, die das Laden der Datei verhindern würden.";
+
+    // Food lists
+    AllCategoriesInText => "all categories", "allen Kategorien";
+    AlreadyLastPage => "Already on the last page.", "Bereits auf der letzten Seite.";
+    AlreadyFirstPage => "Already on the first page.", "Bereits auf der ersten Seite.";
+    PageActionHint => "Enter n, p, s, c, a page number between 1 and {}, or nothing to return.",
+        "n, p, s, c, eine Seitenzahl zwischen 1 und {} oder nichts zum Zurückkehren eingeben.";
+    CategoryColumn => "Category", "Kategorie";
+    PickFoodRange => "Please enter a number between 1 and {}, a food ID, or /text to filter the list.",
+        "Bitte eine Zahl zwischen 1 und {}, eine Lebensmittel-ID oder /Text zum Filtern der Liste eingeben.";
+    HighProtein => "high protein", "viel Eiweiß";
+    HighFiber => "high fiber", "viele Ballaststoffe";
+    GramsProtein => "{} g protein", "{} g Eiweiß";
+    GramsFiber => "{} g fiber", "{} g Ballaststoffe";
+    NutrientDenseLegend => "* Nutrient-dense: at least {} per 100 kcal", "* Nährstoffreich: mindestens {} pro 100 kcal";
+
+    // Names of food list orders
+    SortName => "name", "Name";
+    SortCalories => "calories", "Kalorien";
+    SortId => "ID", "ID";
+
+    // Names of months and first weekdays
+    January => "January", "Januar";
+    February => "February", "Februar";
+    March => "March", "März";
+    April => "April", "April";
+    May => "May", "Mai";
+    June => "June", "Juni";
+    July => "July", "Juli";
+    August => "August", "August";
+    September => "September", "September";
+    October => "October", "Oktober";
+    November => "November", "November";
+    December => "December", "Dezember";
+    Monday => "Monday", "Montag";
+    Sunday => "Sunday", "Sonntag";
+    ShortDateFormat => "%b %-d", "%-d.%-m.";
+
+    // Reports
+    NoDataInMonth => "No weights or food logs recorded in {}.", "Im {} wurden weder Gewichte noch Mahlzeiten erfasst.";
+    WeightTrendChart => "Weight trend, {}", "Gewichtsverlauf, {}";
+    CalorieIntakeChart => "Calorie intake, {}", "Kalorienaufnahme, {}";
+    ChartsPageTitle => "YADA charts, {}", "YADA-Diagramme, {}";
+    ErrorWriting => "Error writing {}: {}", "Fehler beim Schreiben von {}: {}";
+    WeightKg => "Weight (kg)", "Gewicht (kg)";
+    CaloriesKcal => "Calories (kcal)", "Kalorien (kcal)";
+    ErrorRendering => "Error rendering {}: {}", "Fehler beim Zeichnen von {}: {}";
+    DayOfMonth => "Day of month", "Tag des Monats";
+    NothingToExport => "No weights or food logs to export in the chosen dates.",
+        "In den gewählten Tagen gibt es keine Gewichte oder Mahlzeiten zum Exportieren.";
+    NoProfileForReport => "No user profile found. Create one before writing this report.",
+        "Kein Benutzerprofil gefunden. Vor diesem Bericht bitte eines anlegen.";
+    NoDataBetween => "No food logs or weights recorded from {} to {}.", "Vom {} bis {} wurden weder Mahlzeiten noch Gewichte erfasst.";
+    EnergyBalanceTitle => "Energy Balance: {} to {}", "Energiebilanz: {} bis {}";
+    WeeklyIntakeHeading => "Weekly Intake and Weight", "Wöchentliche Aufnahme und Gewicht";
+    WeekOfColumn => "Week of", "Woche ab";
+    DaysLoggedColumn => "Days logged", "Erfasste Tage";
+    AverageCaloriesColumn => "Average calories", "Durchschnittliche Kalorien";
+    ChangeKg => "Change (kg)", "Änderung (kg)";
+    AdaptiveTdeeHeading => "Adaptive TDEE", "Adaptiver Gesamtumsatz";
+    AverageIntakeLine => "Average intake: {:.0} kcal per logged day over {} day(s)",
+        "Durchschnittliche Aufnahme: {:.0} kcal pro erfasstem Tag über {} Tag(e)";
+    WeightTrendLine => "Weight trend: {:+.2} kg per week", "Gewichtstrend: {:+.2} kg pro Woche";
+    AdaptiveTdeeLine => "Adaptive TDEE: {:.0} kcal", "Adaptiver Gesamtumsatz: {:.0} kcal";
+    NotEnoughForAdaptive => "Not enough data for an adaptive estimate: it needs at least {} logged days and weigh-ins at least {} days apart.",
+        "Zu wenige Daten für eine adaptive Schätzung: Nötig sind mindestens {} erfasste Tage und Wiegungen mindestens {} Tage auseinander.";
+    FormulaTdeeLine => "Formula TDEE: {:.0} kcal ({})", "Gesamtumsatz nach Formel: {:.0} kcal ({})";
+    DifferenceLine => "Difference: {:+.0} kcal", "Differenz: {:+.0} kcal";
+    AdaptiveTdeeExplained => "The adaptive TDEE is the average intake minus the weight trend at {:.0} kcal per kg. It assumes days without food entries were like logged days.",
+        "Der adaptive Gesamtumsatz ist die durchschnittliche Aufnahme abzüglich des Gewichtstrends zu {:.0} kcal pro kg. Tage ohne Einträge gelten dabei wie erfasste Tage.";
+    NoDataInWeek => "No food logs, notes, or weights recorded in the week of {}.",
+        "In der Woche ab {} wurden weder Mahlzeiten, Notizen noch Gewichte erfasst.";
+    WeeklyReportTitle => "Weekly Report: {} to {}", "Wochenbericht: {} bis {}";
+    OverviewHeading => "Overview", "Überblick";
+    DaysLoggedOfWeek => "Days logged: {} of 7", "Erfasste Tage: {} von 7";
+    AverageCaloriesLine => "Average calories per logged day: {:.0}", "Durchschnittliche Kalorien pro erfasstem Tag: {:.0}";
+    DaysWithinTargetLine => "Days within target: {} of {}", "Tage im Zielbereich: {} von {}";
+    WeeksStartOn => "Weeks start on {}", "Wochen beginnen am {}";
+    CaloriesPerDayHeading => "Calories per Day", "Kalorien pro Tag";
+    DayColumn => "Day", "Tag";
+    NotesAndMoodHeading => "Notes and Mood", "Notizen und Stimmung";
+    NoNotesThisWeek => "No notes or ratings written this week.", "Diese Woche wurden keine Notizen oder Bewertungen geschrieben.";
+    AverageMood => "Average mood", "Durchschnittliche Stimmung";
+    AverageEnergy => "Average energy", "Durchschnittliche Energie";
+    RatingAverageLine => "{}: {:.1}/{} over {} rated day(s)", "{}: {:.1}/{} über {} bewertete(n) Tag(e)";
+    WeightHeading => "Weight", "Gewicht";
+    NoWeighInsThisWeek => "No weigh-ins recorded this week.", "Diese Woche wurde kein Gewicht erfasst.";
+    TopFoodsHeading => "Top Foods by Calories", "Lebensmittel mit den meisten Kalorien";
+    NoFoodsLogged => "No foods logged.", "Keine Lebensmittel erfasst.";
+    TimesLoggedColumn => "Times logged", "Anzahl Einträge";
+    InvalidYear => "Invalid year {}.", "Ungültiges Jahr {}.";
+    YearNotStarted => "{} has not started yet.", "{} hat noch nicht begonnen.";
+    NoDataInYear => "No food logs or weights recorded in {}.", "{} wurden weder Mahlzeiten noch Gewichte erfasst.";
+    YearReviewTitle => "Year in Review: {}", "Jahresrückblick: {}";
+    PeriodLine => "Period: {} to {}", "Zeitraum: {} bis {}";
+    DaysLoggedOfYear => "Days logged: {} of {} ({:.0}%)", "Erfasste Tage: {} von {} ({:.0} %)";
+    WeightChangeLine => "Weight: {:.1} kg on {} to {:.1} kg on {} ({:+.1} kg)", "Gewicht: {:.1} kg am {} bis {:.1} kg am {} ({:+.1} kg)";
+    WeightOnceLine => "Weight: {:.1} kg on {} (only one weigh-in)", "Gewicht: {:.1} kg am {} (nur eine Wiegung)";
+    WeightNoneLine => "Weight: no weigh-ins recorded", "Gewicht: keine Wiegungen erfasst";
+    QuarterHeading => "Average per Logged Day by Quarter", "Durchschnitt pro erfasstem Tag nach Quartal";
+    QuarterColumn => "Quarter", "Quartal";
+    MostLoggedHeading => "Most-Logged Foods", "Am häufigsten erfasste Lebensmittel";
+    StreaksHeading => "Streaks", "Serien";
+    LongestLoggingStreak => "Longest logging streak", "Längste Erfassungsserie";
+    LongestTargetStreak => "Longest within-target streak", "Längste Serie im Zielbereich";
+    AdherenceHeading => "Adherence by Month", "Zieltreue nach Monat";
+    BestMonth => "Best month: {}", "Bester Monat: {}";
+    WorstMonth => "Worst month: {}", "Schlechtester Monat: {}";
+    MonthColumn => "Month", "Monat";
+    DaysWithinTargetColumn => "Days within target", "Tage im Zielbereich";
+    DaysWithTargetColumn => "Days with a target", "Tage mit Ziel";
+    AdherenceColumn => "Adherence", "Zieltreue";
+    NoTargetDays => "No logged days with a calorie target (record your weight in Manage Profile).",
+        "Keine erfassten Tage mit Kalorienziel (Gewicht unter Profil verwalten eintragen).";
+    LongestWeeklyStreak => "Longest weekly streak", "Längste Wochenserie";
+    WeeklyStreakOne => "{}: 1 week (week of {}, starting {})", "{}: 1 Woche (Woche ab {}, Beginn {})";
+    WeeklyStreakMany => "{}: {} weeks (weeks of {} to {}, starting {})", "{}: {} Wochen (Wochen ab {} bis {}, Beginn {})";
+    StreakNone => "{}: none", "{}: keine";
+    StreakOneDay => "{}: 1 day ({})", "{}: 1 Tag ({})";
+    StreakManyDays => "{}: {} days ({} to {})", "{}: {} Tage ({} bis {})";
+    MonthAdherenceLine => "{} ({} of {} days within target, {:.0}%)", "{} ({} von {} Tagen im Zielbereich, {:.0} %)";
+    OverWord => "over", "darüber";
+    RemainingWord => "remaining", "übrig";
+    SummaryCaloriesLine => "Calories: {:.0} of {}{:.0} kcal ({:.0} {})", "Kalorien: {:.0} von {}{:.0} kcal ({:.0} {})";
+    SummaryMacrosLine => "Macros: {:.0}% protein, {:.0}% carbs, {:.0}% fat ({:.0} g / {:.0} g / {:.0} g)",
+        "Makros: {:.0} % Eiweiß, {:.0} % Kohlenhydrate, {:.0} % Fett ({:.0} g / {:.0} g / {:.0} g)";
+    SummaryMacrosNone => "Macros: nothing logged", "Makros: nichts erfasst";
+    SummaryWeightLine => "Weight: {:.1} kg", "Gewicht: {:.1} kg";
+    SummaryFor => "Summary for {}", "Zusammenfassung für {}";
+
+    // Commands
+    ErrorInitializingApp => "Error initializing app: {}", "Fehler beim Starten der App: {}";
+    ErrorWritingCompletions => "Error writing completion script: {}", "Fehler beim Schreiben des Vervollständigungsskripts: {}";
+    ErrorLoadingFoodData => "Error loading food data: {}", "Fehler beim Laden der Lebensmitteldaten: {}";
+    ErrorLoadingLogData => "Error loading log data: {}", "Fehler beim Laden der Tagebuchdaten: {}";
+    ErrorLoadingProfileData => "Error loading profile data: {}", "Fehler beim Laden der Profildaten: {}";
+    ErrorLoadingExerciseData => "Error loading exercise data: {}", "Fehler beim Laden der Trainingsdaten: {}";
+    HtmlPage => "HTML page: {}", "HTML-Seite: {}";
+    YearReviewWritten => "Year in review: {}", "Jahresrückblick: {}";
+    WeeklyReportWritten => "Weekly report: {}", "Wochenbericht: {}";
+    EnergyBalanceWritten => "Energy balance report: {}", "Energiebilanzbericht: {}";
+    HealthExported => "Exported {} weights and {} log entries: {}", "{} Gewichte und {} Tagebucheinträge exportiert: {}";
+    ErrorCreatingDataDir => "Error creating data directory: {}", "Fehler beim Anlegen des Datenverzeichnisses: {}";
+    UnknownImporter => "Unknown importer '{}'.", "Unbekannter Importer '{}'.";
+    ErrorImporting => "Error importing {}: {}", "Fehler beim Importieren von {}: {}";
+    DryRunNothingSaved => "Dry run: nothing was saved.", "Probelauf: nichts wurde gespeichert.";
+    ErrorLoadingSettings => "Error loading settings: {}", "Fehler beim Laden der Einstellungen: {}";
+    AddedReminder => "Added reminder {}: {}", "Erinnerung {} hinzugefügt: {}";
+    NoReminderWithId => "No reminder has ID {}.", "Keine Erinnerung hat die ID {}.";
+    RemovedReminder => "Removed reminder {}: {}", "Erinnerung {} entfernt: {}";
+    ErrorLoadingLogDataOf => "Error loading log data of {}: {}", "Fehler beim Laden der Tagebuchdaten von {}: {}";
+    ErrorLoadingMealPlansOf => "Error loading meal plans of {}: {}", "Fehler beim Laden der Mahlzeitenpläne von {}: {}";
+    ErrorLoadingMealTemplatesOf => "Error loading meal templates of {}: {}", "Fehler beim Laden der Mahlzeitvorlagen von {}: {}";
+    DoctorHint => "Run `yada doctor --repair` to fix them one by one, or `--prune` to remove them.",
+        "Mit `yada doctor --repair` einzeln beheben oder mit `--prune` entfernen.";
+    RepairedProblems => "Repaired {} problem(s); {} remaining.", "{} Problem(e) behoben; {} verbleibend.";
+    SetSyncTarget => "Set {} to where the data files are synced, e.g. folder:/mnt/share/yada, webdav:https://cloud.example.com/dav/yada, or s3:my-bucket/yada.",
+        "{} auf den Ort setzen, an den die Datendateien synchronisiert werden, z. B. folder:/mnt/share/yada, webdav:https://cloud.example.com/dav/yada oder s3:my-bucket/yada.";
+    FromAfterTo => "--from must not be after --to.", "--from darf nicht nach --to liegen.";
+
+    // Configuration
+    NoDataDir => "Could not determine a data directory; use --data-dir or YADA_DATA_DIR.",
+        "Kein Datenverzeichnis ermittelbar; --data-dir oder YADA_DATA_DIR verwenden.";
+    UserNameLength => "User names are 1 to {} characters long.", "Benutzernamen sind 1 bis {} Zeichen lang.";
+    UserNameCharacters => "User names may only contain letters, digits, '-', and '_'.",
+        "Benutzernamen dürfen nur Buchstaben, Ziffern, '-' und '_' enthalten.";
+    UserNameDefault => "'{}' is the name of the default user.", "'{}' ist der Name des Standardbenutzers.";
+    NoConfigFile => "Could not determine the config file location.", "Der Ort der Konfigurationsdatei lässt sich nicht ermitteln.";
+    ErrorReadingConfig => "Error reading config file {}: {}", "Fehler beim Lesen der Konfigurationsdatei {}: {}";
+    ErrorWritingConfig => "Error writing config file {}: {}", "Fehler beim Schreiben der Konfigurationsdatei {}: {}";
+
+    // Checking data files
+    NoProblemsFound => "No problems found.", "Keine Probleme gefunden.";
+    FoundProblems => "Found {} problem(s):", "{} Problem(e) gefunden:";
+    CorrectWeight => "Correct the weight", "Gewicht korrigieren";
+    RemoveDailyProfile => "Remove the daily profile", "Tagesprofil entfernen";
+    LeaveIt => "Leave it", "So lassen";
+    EnterWeightKg => "Enter weight (kg): ", "Gewicht eingeben (kg): ";
+    WeightInKg => "Please enter a weight in kg.", "Bitte ein Gewicht in kg eingeben.";
+    RemoveLogEntry => "Remove the log entry", "Tagebucheintrag entfernen";
+    RemoveComponent => "Remove the component", "Bestandteil entfernen";
+    UseAnotherFood => "Use another food instead", "Stattdessen ein anderes Lebensmittel verwenden";
+    FoodIdNotFound => "Food with ID '{}' not found.", "Lebensmittel mit der ID '{}' nicht gefunden.";
+
+    // Migrating data files
+    AllUpToDate => "All data files already use format version {}.", "Alle Datendateien verwenden bereits Formatversion {}.";
+    FormatV => "format v{}", "Format v{}";
+    LegacyFormat => "legacy format", "altes Format";
+    RewrittenIn => "rewritten in format v{}", "im Format v{} neu geschrieben";
+    MigratedFrom => "migrated from {} to v{}", "von {} auf v{} migriert";
+    NothingToMigrate => "not found, nothing to migrate", "nicht gefunden, nichts zu migrieren";
+    AlreadyFormat => "already format v{}, {} records", "bereits Format v{}, {} Datensätze";
+    MigratedRecords => "{}, {} records{}", "{}, {} Datensätze{}";
+    WouldBeMigrated => "would be {}, {} records", "würde {} werden, {} Datensätze";
+    NewerFormat => "written by a newer YADA (format v{}), left unchanged", "von einem neueren YADA geschrieben (Format v{}), unverändert gelassen";
+    LinesDropped => "dropped (still in the backup)", "verworfen (noch in der Sicherung)";
+    LinesWouldDrop => "would be dropped", "würden verworfen";
+    LinesIgnored => "ignored", "ignoriert";
+    OriginalSavedAs => ", original saved as {}", ", Original gespeichert als {}";
+    UpgradeLine => "Upgrade: {}", "Aktualisierung: {}";
+    SkippedLines => "{} unreadable or duplicate line(s) {}", "{} unlesbare oder doppelte Zeile(n) {}";
+
+    // Food packs
+    PackInstalled => "Installed {}{} ({}): added {} food(s), updated {}.", "{}{} ({}) installiert: {} Lebensmittel hinzugefügt, {} aktualisiert.";
+    PackConflicts => "Skipped {} food(s) whose ID is already used by another food:",
+        "{} Lebensmittel übersprungen, deren ID bereits ein anderes Lebensmittel verwendet:";
+    NoPacks => "No food packs are installed.", "Es sind keine Lebensmittelpakete installiert.";
+    PackRemoved => "Removed {} food(s) of the {} pack.", "{} Lebensmittel des Pakets {} entfernt.";
+    PackKept => "Kept {} food(s) that are still logged, planned, or used in a recipe:",
+        "{} Lebensmittel behalten, die noch erfasst, geplant oder in einem Rezept verwendet werden:";
+    PackUninstallAgain => "Uninstall the pack again once they are no longer used to remove them.",
+        "Das Paket erneut deinstallieren, sobald sie nicht mehr verwendet werden, um sie zu entfernen.";
+    FoodCount => "{} food(s)", "{} Lebensmittel";
+
+    // Reminders
+    NoReminders => "No reminders. Add one with `yada remind add 13:00 \"Log lunch\"`.",
+        "Keine Erinnerungen. Mit `yada remind add 13:00 \"Mittagessen erfassen\"` eine hinzufügen.";
+    ReminderUntil => "{} (until {} is done)", "{} (bis {} erledigt ist)";
+
+    // Status
+    NoProfileExists => "No profile exists! Run yada interactively to create one.",
+        "Es gibt kein Profil! yada interaktiv starten, um eines anzulegen.";
+    NoDailyProfileBefore => "No weight and activity level recorded on or before {}.",
+        "Am oder vor dem {} wurden weder Gewicht noch Aktivitätsniveau erfasst.";
+    NoCalculator => "No calorie calculator available.", "Kein Kalorienrechner verfügbar.";
+    StatusShortLine => "{:.0}/{}{:.0} kcal, {:.0} g protein", "{:.0}/{}{:.0} kcal, {:.0} g Eiweiß";
+    OverBudgetBy => "Over budget by", "Budget überschritten um";
+    RemainingLabel => "Remaining", "Verbleibend";
+    StatusReport => "Status for {}\nConsumed: {:.1} kcal\nTarget: {:.1} kcal\n{}\nMacros: {:.1} g protein, {:.1} g carbs, {:.1} g fat",
+        "Status für {}\nVerzehrt: {:.1} kcal\nZiel: {:.1} kcal\n{}\nMakros: {:.1} g Eiweiß, {:.1} g Kohlenhydrate, {:.1} g Fett";
+
+    // Syncing
+    SyncedWith => "{} with {}:", "{} mit {}:";
+    SyncDryRun => "Dry run of syncing", "Probelauf der Synchronisierung";
+    Synced => "Synced", "Synchronisiert";
+    UpToDate => "up to date", "aktuell";
+    Uploaded => "uploaded", "hochgeladen";
+    WouldUpload => "would be uploaded", "würde hochgeladen";
+    Downloaded => "downloaded (previous version backed up)", "heruntergeladen (vorherige Version gesichert)";
+    WouldDownload => "would be downloaded", "würde heruntergeladen";
+    SyncConflict => "CONFLICT: changed on both sides since the last sync", "KONFLIKT: seit der letzten Synchronisierung auf beiden Seiten geändert";
+    NotOnEitherSide => "not found on either side", "auf keiner Seite gefunden";
+    SyncConflictHint => "Conflicting files were left unchanged. Run `yada sync --prefer local` or `--prefer remote` to choose which copy to keep.",
+        "Dateien mit Konflikt wurden nicht verändert. Mit `yada sync --prefer local` oder `--prefer remote` wählen, welche Kopie behalten wird.";
+}
+
+impl Message {
+    /// Text of the message in the session's locale
+    pub fn text(self) -> &'static str {
+        self.text_in(locale())
+    }
+
+    /// Fills the placeholders of the message in order
+    ///
+    /// # Arguments
+    /// * `args` - One value per placeholder; missing ones leave the placeholder empty
+    pub fn format(self, args: &[&dyn fmt::Display]) -> String {
+        fill(self.text(), args)
+    }
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.text())
+    }
+}
+
+/// Order of a food list, as in "sorted by name"
+impl From<FoodSort> for Message {
+    fn from(sort: FoodSort) -> Self {
+        match sort {
+            FoodSort::Name => Message::SortName,
+            FoodSort::Calories => Message::SortCalories,
+            FoodSort::Id => Message::SortId,
+        }
+    }
+}
+
+/// Name of a meal as the menus show it
+impl From<MealSlot> for Message {
+    fn from(slot: MealSlot) -> Self {
+        match slot {
+            MealSlot::Breakfast => Message::Breakfast,
+            MealSlot::Lunch => Message::Lunch,
+            MealSlot::Dinner => Message::Dinner,
+            MealSlot::Snack => Message::Snack,
+        }
+    }
+}
+
+/// Short name of a weekday, such as `Mon`
+impl From<Weekday> for Message {
+    fn from(day: Weekday) -> Self {
+        match day {
+            Weekday::Mon => Message::Mon,
+            Weekday::Tue => Message::Tue,
+            Weekday::Wed => Message::Wed,
+            Weekday::Thu => Message::Thu,
+            Weekday::Fri => Message::Fri,
+            Weekday::Sat => Message::Sat,
+            Weekday::Sun => Message::Sun,
+        }
+    }
+}
+
+/// Full name of the first day of the week, such as `Monday`
+impl From<WeekStart> for Message {
+    fn from(start: WeekStart) -> Self {
+        match start {
+            WeekStart::Sunday => Message::Sunday,
+            WeekStart::Monday => Message::Monday,
+        }
+    }
+}
+
+/// Name of a gender as the menus show it
+impl From<&Gender> for Message {
+    fn from(gender: &Gender) -> Self {
+        match gender {
+            Gender::Male => Message::Male,
+            Gender::Female => Message::Female,
+            Gender::Other => Message::OtherGender,
+        }
+    }
+}
+
+/// Short name of an activity level, as in the profile summary
+impl From<&ActivityLevel> for Message {
+    fn from(level: &ActivityLevel) -> Self {
+        match level {
+            ActivityLevel::Sedentary => Message::LevelSedentary,
+            ActivityLevel::LightlyActive => Message::LevelLightlyActive,
+            ActivityLevel::ModeratelyActive => Message::LevelModeratelyActive,
+            ActivityLevel::VeryActive => Message::LevelVeryActive,
+            ActivityLevel::ExtremelyActive => Message::LevelExtremelyActive,
+        }
+    }
+}
+
+/// Name of the month a date falls in, such as `March`
+pub fn month_name(date: NaiveDate) -> Message {
+    match date.month() {
+        1 => Message::January,
+        2 => Message::February,
+        3 => Message::March,
+        4 => Message::April,
+        5 => Message::May,
+        6 => Message::June,
+        7 => Message::July,
+        8 => Message::August,
+        9 => Message::September,
+        10 => Message::October,
+        11 => Message::November,
+        _ => Message::December,
+    }
+}
+
+/// Replaces each placeholder of a template with the next argument
+///
+/// `{}` shows the argument as it is; `{:.N}` and `{:+.N}` show numbers with
+/// `N` decimals, the latter with a sign. Anything else in braces is kept.
+fn fill(template: &str, args: &[&dyn fmt::Display]) -> String {
+    let mut args = args.iter();
+    let mut text = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        text.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+            break;
+        };
+        let spec = &rest[start + 1..end];
+        match (parse_spec(spec), args.next()) {
+            (Some((sign, precision)), Some(arg)) => text.push_str(&format_arg(arg, sign, precision)),
+            (Some(_), None) => {}
+            (None, _) => text.push_str(&rest[start..=end]),
+        }
+        rest = &rest[end + 1..];
+    }
+    text.push_str(rest);
+    text
+}
+
+/// Reads a placeholder: `""`, `":.N"`, `":+.N"`, or `":+"`
+///
+/// # Returns
+/// Whether to show a sign and the number of decimals, or `None` if the
+/// braces hold something else
+fn parse_spec(spec: &str) -> Option<(bool, Option<usize>)> {
+    if spec.is_empty() {
+        return Some((false, None));
+    }
+    let spec = spec.strip_prefix(':')?;
+    let (sign, spec) = match spec.strip_prefix('+') {
+        Some(spec) => (true, spec),
+        None => (false, spec),
+    };
+    if spec.is_empty() {
+        return Some((sign, None));
+    }
+    let precision = spec.strip_prefix('.')?.parse().ok()?;
+    Some((sign, Some(precision)))
+}
+
+/// Formats one argument with an optional sign and number of decimals
+fn format_arg(arg: &dyn fmt::Display, sign: bool, precision: Option<usize>) -> String {
+    match (sign, precision) {
+        (false, None) => arg.to_string(),
+        (false, Some(precision)) => format!("{:.*}", precision, arg),
+        (true, None) => format!("{:+}", arg),
+        (true, Some(precision)) => format!("{:+.*}", precision, arg),
+    }
+}
@@ -0,0 +1,156 @@
+//! # Interrupts
+//!
+//! Ctrl-C (SIGINT) and SIGTERM end an interactive session the same way the
+//! end of input does, so unsaved changes are written instead of lost.
+//!
+//! ## How It Works
+//!
+//! A signal handler can do very little safely, so it does not save anything
+//! itself. Instead it records the interrupt and puts `/dev/null` in place of
+//! standard input. The prompt waiting for a line (or the next one asked)
+//! then sees the end of input, every flow returns as it does on end of input
+//! (see `PromptError::InputClosed`), and the main loop saves and exits as if
+//! Exit had been chosen.
+//!
+//! A second interrupt while that happens exits at once with status 130, for
+//! a save that hangs (on a stalled network drive, say).
+//!
+//! ## Cancelling Lookups
+//!
+//! A food source lookup does not read input, so it would not notice the end
+//! of input. The handler therefore also cancels the token returned by
+//! `cancel_token`; lookups waiting on it (or on one of its children) stop
+//! within a few milliseconds. While a lookup runs in a terminal,
+//! `cancel_on_enter` lets Enter cancel it too.
+//!
+//! Handlers exist only on Unix; elsewhere Ctrl-C keeps its default behavior
+//! and Enter does not cancel lookups.
+
+// src/ui/interrupt.rs
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::thread::{self, JoinHandle};
+
+use yada_core::factories::async_food_source::CancelToken;
+
+use crate::ui::console;
+
+/// Exit status after a second interrupt (128 + SIGINT, as shells report it)
+pub const FORCED_EXIT_STATUS: i32 = 130;
+
+/// Whether an interrupt has been received
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// Token cancelled by the first interrupt
+static CANCEL: OnceLock<CancelToken> = OnceLock::new();
+
+/// Installs the handlers for Ctrl-C and SIGTERM
+///
+/// Only interactive sessions should install them; commands that do not
+/// read input keep the default behavior of stopping at once.
+pub fn install() {
+    // Created before a signal can arrive, so the handler never allocates
+    CANCEL.get_or_init(CancelToken::new);
+    #[cfg(unix)]
+    unix::install();
+}
+
+/// Whether the session was interrupted
+pub fn interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+/// Token cancelled by the first interrupt; give lookups a `child` of it
+pub fn cancel_token() -> CancelToken {
+    CANCEL.get_or_init(CancelToken::new).clone()
+}
+
+/// Cancels `token` when Enter is pressed, until the returned watch is dropped
+///
+/// Only a terminal is watched: scripted input is read by the prompts alone.
+/// The line ending the wait is consumed and never reaches a prompt.
+pub fn cancel_on_enter(token: &CancelToken) -> EnterWatch {
+    let done = Arc::new(AtomicBool::new(false));
+    let thread = (cfg!(unix) && console::is_interactive()).then(|| {
+        let (token, done) = (token.clone(), done.clone());
+        thread::spawn(move || {
+            while !done.load(Ordering::SeqCst) && !token.is_cancelled() {
+                #[cfg(unix)]
+                if unix::stdin_ready(POLL_MILLISECONDS) {
+                    let mut line = String::new();
+                    let _ = std::io::stdin().read_line(&mut line);
+                    token.cancel();
+                }
+            }
+        })
+    });
+    EnterWatch { done, thread }
+}
+
+/// How long the Enter watch waits for input before checking whether to stop
+const POLL_MILLISECONDS: i32 = 20;
+
+/// Watches for Enter until dropped (see `cancel_on_enter`)
+pub struct EnterWatch {
+    done: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Drop for EnterWatch {
+    fn drop(&mut self) {
+        self.done.store(true, Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(unix)]
+mod unix {
+    use std::fs::File;
+    use std::os::fd::IntoRawFd;
+    use std::os::raw::c_int;
+    use std::sync::atomic::{AtomicI32, Ordering};
+
+    use super::{CANCEL, FORCED_EXIT_STATUS, INTERRUPTED};
+
+    /// Descriptor of `/dev/null`, opened before any signal arrives
+    static DEV_NULL: AtomicI32 = AtomicI32::new(-1);
+
+    pub(super) fn install() {
+        let Ok(dev_null) = File::open("/dev/null") else {
+            return;
+        };
+        DEV_NULL.store(dev_null.into_raw_fd(), Ordering::SeqCst);
+        let handler = handle as extern "C" fn(c_int) as libc::sighandler_t;
+        // SAFETY: `handle` only calls async-signal-safe functions
+        unsafe {
+            libc::signal(libc::SIGINT, handler);
+            libc::signal(libc::SIGTERM, handler);
+        }
+    }
+
+    /// Whether standard input has a line to read, waiting up to `timeout` milliseconds
+    pub(super) fn stdin_ready(timeout: c_int) -> bool {
+        let mut fd = libc::pollfd { fd: libc::STDIN_FILENO, events: libc::POLLIN, revents: 0 };
+        // SAFETY: `fd` is a single valid entry that outlives the call
+        unsafe { libc::poll(&mut fd, 1 as libc::nfds_t, timeout) > 0 }
+    }
+
+    /// Ends the input and cancels lookups on the first signal, and exits on the second
+    extern "C" fn handle(_signum: c_int) {
+        if INTERRUPTED.swap(true, Ordering::SeqCst) {
+            // SAFETY: `_exit` is async-signal-safe
+            unsafe { libc::_exit(FORCED_EXIT_STATUS) }
+        }
+        // Only an atomic store: the token was created in `install`
+        if let Some(token) = CANCEL.get() {
+            token.cancel();
+        }
+        // SAFETY: `dup2` is async-signal-safe; a read waiting on standard
+        // input is restarted on `/dev/null` and returns the end of input
+        unsafe {
+            libc::dup2(DEV_NULL.load(Ordering::SeqCst), libc::STDIN_FILENO);
+        }
+    }
+}
@@ -0,0 +1,44 @@
+//! # UI Module
+//!
+//! This module contains the reusable building blocks of the interactive
+//! command-line interface. Keeping them separate from `main.rs` lets every
+//! menu flow share the same input handling, validation, and messaging.
+//!
+//! ## Module Organization
+//!
+//! - `console`: Input and output of the menus, replaceable for scripted sessions
+//! - `interrupt`: Ctrl-C and SIGTERM handling that ends the input so data is saved
+//! - `style`: Optional ANSI colors and bold text (`--no-color`, `NO_COLOR`)
+//! - `prompt`: Typed prompts (text, numbers, dates, choices) with validation,
+//!   defaults, and cancel handling
+//! - `table`: Column layout measured in terminal columns, with truncation and
+//!   dynamic column widths, shared by the tabular views
+//! - `comparison`: Side-by-side nutrition and cost table for comparing foods
+//! - `highlight`: Nutrient-density annotations for food lists
+//! - `food_list`: Paged, column-truncated food table for View Foods
+//! - `food_picker`: Choosing a food by row number, ID, or an incremental filter
+//! - `date_stack`: Session bookmarks of working dates
+//! - `recovery`: Menu for restoring or skipping a data file that fails to load
+//! - `charts`: Text bar charts and sparklines for the statistics screen
+//! - `users`: Menu for choosing, switching, and creating users
+//! - `i18n`: Message catalog for menu text and locale selection (`YADA_LANG`)
+//! - `unlock`: Password for encrypted data files at startup
+//! - `reminder_checker`: Background thread printing reminders as they come due
+
+// User interface helpers for the interactive CLI
+pub mod console;
+pub mod interrupt;
+pub mod style;
+pub mod prompt;
+pub mod table;
+pub mod comparison;
+pub mod highlight;
+pub mod food_list;
+pub mod food_picker;
+pub mod date_stack;
+pub mod recovery;
+pub mod charts;
+pub mod users;
+pub mod i18n;
+pub mod unlock;
+pub mod reminder_checker;
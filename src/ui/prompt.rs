@@ -0,0 +1,369 @@
+//! # Prompt Library
+//!
+//! This module provides typed, validated prompts for the interactive CLI.
+//! Every question the application asks the user goes through a `Prompt<T>`,
+//! which keeps input handling consistent across all menu flows.
+//!
+//! ## Prompt Lifecycle
+//!
+//! 1. **Header**: Optional lines (e.g. numbered options) printed once
+//! 2. **Message**: The question itself, printed before every attempt
+//! 3. **Parsing**: Raw input is converted into the target type
+//! 4. **Validation**: Zero or more validators are applied in order
+//! 5. **Retry**: Invalid input prints the error and asks again
+//!
+//! ## Special Inputs
+//!
+//! - **Cancel**: Typing `cancel` at any prompt aborts the current flow
+//! - **Default**: Empty input returns the default value when one is configured
+//! - **End of Input**: A closed input stream is reported instead of panicking
+//!
+//! ## Validation Combinators
+//!
+//! Validators are plain closures, so they compose by chaining `validate()`
+//! calls. Common checks (`positive`, `non_negative`, `in_range`, `non_empty`,
+//! `no_whitespace`) are provided as constructors that take the error message
+//! to show on failure. Heights, weights, and birth dates have their own
+//! prompts that apply the ranges of `models::validation`.
+
+// src/ui/prompt.rs
+use std::collections::HashSet;
+use std::str::FromStr;
+use chrono::{Local, NaiveDate, NaiveTime};
+use yada_core::models::preferences::{self, UnitSystem};
+use yada_core::models::validation;
+
+use crate::ui::console::{self, out, outln};
+use crate::ui::i18n::Message;
+
+/// Keyword that aborts the current prompt when typed by the user
+pub const CANCEL_KEYWORD: &str = "cancel";
+
+/// Reasons a prompt can finish without producing a value
+#[derive(Debug, Clone, PartialEq)]
+pub enum PromptError {
+    /// The user typed the cancel keyword
+    Cancelled,
+
+    /// The input stream was closed (EOF) or could not be read
+    InputClosed,
+}
+
+/// Result type returned by all prompts
+pub type PromptResult<T> = Result<T, PromptError>;
+
+/// Converts trimmed user input into a typed value or an error message
+type Parser<T> = Box<dyn Fn(&str) -> Result<T, String>>;
+
+/// Checks a parsed value and returns an error message when it is rejected
+type Validator<T> = Box<dyn Fn(&T) -> Result<(), String>>;
+
+/// A typed prompt with parsing, validation, and an optional default value
+///
+/// Prompts are built with a constructor (`text`, `number`, `date`, ...),
+/// refined with builder methods (`validate`, `default`, `optional`),
+/// and finally executed with `ask()`.
+///
+/// # Examples
+/// ```ignore
+/// let height = prompt::positive_f64(
+///     "Enter your height in centimeters: ",
+///     "Invalid height. Please enter a positive number.",
+/// ).ask()?;
+/// ```
+pub struct Prompt<T> {
+    /// Question printed before each input attempt
+    message: String,
+
+    /// Lines printed once before the first attempt
+    header: Vec<String>,
+
+    /// Conversion from raw input into the target type
+    parser: Parser<T>,
+
+    /// Additional checks applied after parsing, in order
+    validators: Vec<Validator<T>>,
+
+    /// Value returned when the user submits empty input
+    default: Option<T>,
+}
+
+impl<T: Clone + 'static> Prompt<T> {
+    /// Creates a prompt with a custom parser
+    ///
+    /// # Arguments
+    /// * `message` - Question printed before each input attempt
+    /// * `parser` - Converts trimmed input into a value or an error message
+    pub fn new<F>(message: &str, parser: F) -> Self
+    where
+        F: Fn(&str) -> Result<T, String> + 'static,
+    {
+        Prompt {
+            message: message.to_string(),
+            header: Vec::new(),
+            parser: Box::new(parser),
+            validators: Vec::new(),
+            default: None,
+        }
+    }
+
+    /// Adds a line printed once before the first input attempt
+    pub fn header(mut self, line: &str) -> Self {
+        self.header.push(line.to_string());
+        self
+    }
+
+    /// Adds a validator; all validators must accept the value (AND semantics)
+    pub fn validate<F>(mut self, validator: F) -> Self
+    where
+        F: Fn(&T) -> Result<(), String> + 'static,
+    {
+        self.validators.push(Box::new(validator));
+        self
+    }
+
+    /// Sets the value returned when the user submits empty input
+    pub fn default(mut self, value: T) -> Self {
+        self.default = Some(value);
+        self
+    }
+
+    /// Turns the prompt into one where empty input means "no value"
+    ///
+    /// Non-empty input is still parsed and validated as before.
+    pub fn optional(self) -> Prompt<Option<T>> {
+        let Prompt { message, header, parser, validators, default } = self;
+
+        Prompt {
+            message,
+            header,
+            parser: Box::new(move |input| {
+                Self::check(&parser, &validators, input).map(Some)
+            }),
+            validators: Vec::new(),
+            default: Some(default),
+        }
+    }
+
+    /// Runs the prompt until valid input, cancellation, or end of input
+    ///
+    /// # Returns
+    /// * `Ok(T)` - Parsed and validated value (or the default)
+    /// * `Err(PromptError::Cancelled)` - The user typed the cancel keyword
+    /// * `Err(PromptError::InputClosed)` - No more input is available
+    pub fn ask(&self) -> PromptResult<T> {
+        for line in &self.header {
+            outln!("{}", line);
+        }
+
+        loop {
+            out!("{}", self.message);
+
+            let input = read_line()?;
+            let input = input.trim();
+
+            if input.eq_ignore_ascii_case(CANCEL_KEYWORD) {
+                outln!("{}", Message::Cancelled);
+                return Err(PromptError::Cancelled);
+            }
+
+            if input.is_empty() && let Some(default) = &self.default {
+                return Ok(default.clone());
+            }
+
+            match Self::check(&self.parser, &self.validators, input) {
+                Ok(value) => return Ok(value),
+                Err(message) => outln!("{}", message),
+            }
+        }
+    }
+
+    /// Parses input and applies every validator in order
+    fn check(parser: &Parser<T>, validators: &[Validator<T>], input: &str) -> Result<T, String> {
+        let value = parser(input)?;
+        for validator in validators {
+            validator(&value)?;
+        }
+        Ok(value)
+    }
+}
+
+/// Reads one line from the console's input
+///
+/// Returns `InputClosed` at the end of input so callers can shut down cleanly.
+fn read_line() -> PromptResult<String> {
+    console::read_line().ok_or(PromptError::InputClosed)
+}
+
+/// Password prompt; what is typed is not shown on a terminal
+///
+/// The input is returned as typed apart from the line ending. The cancel
+/// keyword is not recognized, since it may be someone's password.
+pub fn password(message: &str) -> PromptResult<String> {
+    out!("{}", message);
+    let input = console::read_secret().ok_or(PromptError::InputClosed)?;
+    Ok(input.trim_end_matches(['\n', '\r']).to_string())
+}
+
+/// Free-form text prompt (input is trimmed, may be empty)
+pub fn text(message: &str) -> Prompt<String> {
+    Prompt::new(message, |input| Ok(input.to_string()))
+}
+
+/// Numeric prompt for any type implementing `FromStr`
+///
+/// # Arguments
+/// * `message` - Question printed before each input attempt
+/// * `error` - Message shown when the input is not a valid number
+pub fn number<T>(message: &str, error: &str) -> Prompt<T>
+where
+    T: FromStr + Clone + 'static,
+{
+    let error = error.to_string();
+    Prompt::new(message, move |input| input.parse::<T>().map_err(|_| error.clone()))
+}
+
+/// Prompt for a strictly positive decimal number (weights, heights, servings)
+pub fn positive_f64(message: &str, error: &str) -> Prompt<f64> {
+    number(message, error).validate(positive(error))
+}
+
+/// Prompt for a decimal number that may be zero (calories)
+pub fn non_negative_f64(message: &str, error: &str) -> Prompt<f64> {
+    number(message, error).validate(non_negative(error))
+}
+
+/// Prompt for a date in YYYY-MM-DD format (or DD.MM.YYYY or MM/DD/YYYY; see `models::preferences`)
+pub fn date(message: &str) -> Prompt<NaiveDate> {
+    Prompt::new(message, |input| {
+        preferences::parse_date(input).ok_or_else(|| Message::InvalidDate.to_string())
+    })
+}
+
+/// Prompt for a height in the preferred unit, returned in centimeters
+///
+/// Heights outside the range of `models::validation` are asked again, with
+/// the range shown in the preferred unit.
+pub fn height_cm(message: &str, units: UnitSystem) -> Prompt<f64> {
+    Prompt::new(message, move |input| {
+        let height: f64 = input.parse().map_err(|_| Message::InvalidHeight.text().to_string())?;
+        validation::height_cm(units.height_to_cm(height)).map_err(|_| Message::HeightBetween.format(&[
+            &units.format_height(validation::MIN_HEIGHT_CM),
+            &units.format_height(validation::MAX_HEIGHT_CM),
+        ]))
+    })
+}
+
+/// Prompt for a weight in the preferred unit, returned in kilograms
+///
+/// Weights outside the range of `models::validation` are asked again, with
+/// the range shown in the preferred unit.
+pub fn weight_kg(message: &str, units: UnitSystem) -> Prompt<f64> {
+    Prompt::new(message, move |input| {
+        let weight: f64 = input.parse().map_err(|_| Message::InvalidWeight.text().to_string())?;
+        validation::weight_kg(units.weight_to_kg(weight)).map_err(|_| Message::WeightBetween.format(&[
+            &units.format_weight(validation::MIN_WEIGHT_KG),
+            &units.format_weight(validation::MAX_WEIGHT_KG),
+        ]))
+    })
+}
+
+/// Prompt for a birth date: a date like `date` that is not in the future
+/// and less than `validation::MAX_AGE_YEARS` ago
+pub fn birth_date(message: &str) -> Prompt<NaiveDate> {
+    date(message).validate(|birth_date| validation::birth_date(*birth_date, Local::now().date_naive()).map(|_| ()))
+}
+
+/// Prompt for a date like `date` that also accepts `today`
+pub fn date_or_today(message: &str) -> Prompt<NaiveDate> {
+    Prompt::new(message, |input| {
+        if input.eq_ignore_ascii_case("today") {
+            return Ok(Local::now().date_naive());
+        }
+        preferences::parse_date(input).ok_or_else(|| Message::InvalidDate.to_string())
+    })
+}
+
+/// Prompt for a time of day in 24-hour HH:MM format
+pub fn time(message: &str) -> Prompt<NaiveTime> {
+    Prompt::new(message, |input| {
+        NaiveTime::parse_from_str(input, "%H:%M")
+            .map_err(|_| Message::InvalidTime.to_string())
+    })
+}
+
+/// Menu selection prompt returning the chosen number (1-based)
+///
+/// The options themselves are expected to be printed by the caller.
+pub fn menu(count: usize) -> Prompt<usize> {
+    let error = Message::InvalidChoice.format(&[&count]);
+    number(&Message::EnterChoice.format(&[&count]), &error)
+        .validate(in_range(1, count, &error))
+}
+
+/// Menu selection prompt that prints numbered options before asking
+///
+/// # Returns
+/// The chosen option number (1-based), matching the printed numbering
+pub fn choice(options: &[&str]) -> Prompt<usize> {
+    let mut prompt = menu(options.len());
+    for (i, option) in options.iter().enumerate() {
+        prompt = prompt.header(&format!("{}. {}", i + 1, option));
+    }
+    prompt
+}
+
+/// Confirmation prompt that is true only when the user types `yes`
+pub fn confirm(message: &str) -> Prompt<bool> {
+    Prompt::new(message, |input| Ok(input.eq_ignore_ascii_case("yes")))
+}
+
+/// Prompt for comma-separated keywords, normalized to lowercase
+pub fn keywords(message: &str) -> Prompt<HashSet<String>> {
+    Prompt::new(message, |input| {
+        Ok(input
+            .split(',')
+            .map(|s| s.trim().to_lowercase())
+            .filter(|s| !s.is_empty())
+            .collect())
+    })
+}
+
+/// Validator accepting only finite values greater than zero
+pub fn positive(error: &str) -> impl Fn(&f64) -> Result<(), String> + 'static {
+    let error = error.to_string();
+    move |value| if value.is_finite() && *value > 0.0 { Ok(()) } else { Err(error.clone()) }
+}
+
+/// Validator accepting zero and finite positive values
+pub fn non_negative(error: &str) -> impl Fn(&f64) -> Result<(), String> + 'static {
+    let error = error.to_string();
+    move |value| if value.is_finite() && *value >= 0.0 { Ok(()) } else { Err(error.clone()) }
+}
+
+/// Validator accepting values within an inclusive range
+pub fn in_range<T>(min: T, max: T, error: &str) -> impl Fn(&T) -> Result<(), String> + 'static
+where
+    T: PartialOrd + 'static,
+{
+    let error = error.to_string();
+    move |value| if *value >= min && *value <= max { Ok(()) } else { Err(error.clone()) }
+}
+
+/// Validator rejecting empty strings
+pub fn non_empty(error: &str) -> impl Fn(&String) -> Result<(), String> + 'static {
+    let error = error.to_string();
+    move |value| if value.is_empty() { Err(error.clone()) } else { Ok(()) }
+}
+
+/// Validator rejecting strings that contain whitespace (identifiers)
+pub fn no_whitespace(error: &str) -> impl Fn(&String) -> Result<(), String> + 'static {
+    let error = error.to_string();
+    move |value| {
+        if value.chars().any(char::is_whitespace) {
+            Err(error.clone())
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,114 @@
+//! # Load Recovery Menu
+//!
+//! When a data file fails to load at startup, the application offers this
+//! menu instead of exiting. The user can restore one of the file's
+//! rotating backups, skip the file (it is moved aside and the application
+//! starts without it), or check the file to see what is wrong with it.
+//!
+//! The file operations themselves live in `yada_core::repositories::recovery`;
+//! this module only presents them. Nothing is deleted: the broken file is
+//! always kept as `<file>.broken`.
+
+// src/ui/recovery.rs
+use std::io;
+
+use chrono::{DateTime, Local};
+use yada_core::repositories::recovery::{self, BackupFile};
+
+use crate::ui::prompt;
+use crate::ui::console::outln;
+use crate::ui::i18n::Message;
+
+/// Offers to repair a data file that failed to load, then opens it again
+///
+/// # Arguments
+/// * `path` - Data file that failed to load
+/// * `error` - The error returned by the first attempt
+/// * `open` - Opens the repository for `path` (e.g. `FoodRepository::new`)
+///
+/// # Returns
+/// * `Ok(R)` - The repository, loaded after a restore or empty after a skip
+/// * `Err(io::Error)` - The user chose to quit (or cancelled); the original error
+pub fn recover<R>(path: &str, error: io::Error, open: impl Fn(&str) -> io::Result<R>) -> io::Result<R> {
+    let mut error = error;
+    loop {
+        outln!("\n{}", Message::CouldNotLoad.format(&[&path, &error]));
+        let Ok(choice) = prompt::choice(&[
+            Message::RestoreFromBackup.text(),
+            Message::SkipFile.text(),
+            Message::CheckFile.text(),
+            Message::Quit.text(),
+        ]).ask() else {
+            return Err(error);
+        };
+
+        let outcome = match choice {
+            1 => {
+                let Some(backup) = choose_backup(path) else { continue };
+                recovery::restore_backup(path, &backup).map(|broken| {
+                    outln!("{}", Message::RestoredFromBackup.format(&[&path, &backup.path, &broken]));
+                })
+            }
+            2 => recovery::set_aside(path).map(|broken| {
+                outln!("{}", Message::MovedAside.format(&[&path, &broken]));
+            }),
+            3 => {
+                print_check(path);
+                continue;
+            }
+            _ => return Err(error),
+        };
+
+        if let Err(e) = outcome {
+            outln!("{}", Message::RecoveryFailed.format(&[&e]));
+            continue;
+        }
+        match open(path) {
+            Ok(repo) => return Ok(repo),
+            Err(e) => error = e,
+        }
+    }
+}
+
+/// Lists the backups of a file and asks which one to restore
+///
+/// # Returns
+/// * `Some(BackupFile)` - The chosen backup
+/// * `None` - There are no backups or the user cancelled
+fn choose_backup(path: &str) -> Option<BackupFile> {
+    let mut backups = recovery::list_backups(path);
+    if backups.is_empty() {
+        outln!("{}", Message::NoBackups.format(&[&path]));
+        return None;
+    }
+
+    let labels: Vec<String> = backups.iter()
+        .map(|backup| match backup.modified {
+            Some(time) => Message::BackupSaved.format(&[&backup.path, &(DateTime::<Local>::from(time).format("%Y-%m-%d %H:%M"))]),
+            None => backup.path.clone(),
+        })
+        .collect();
+    let options: Vec<&str> = labels.iter().map(|label| label.as_str()).collect();
+
+    outln!("{}", Message::BackupsNewestFirst);
+    let index = prompt::choice(&options).ask().ok()?;
+    Some(backups.swap_remove(index - 1))
+}
+
+/// Prints the integrity report for a file
+fn print_check(path: &str) {
+    let report = recovery::check_file(path);
+    outln!("\n{}", Message::CheckHeading.format(&[&path]));
+    outln!("{}", Message::LinesCount.format(&[&report.lines]));
+    match report.version {
+        Some(version) => outln!("{}", Message::FormatVersion.format(&[&version])),
+        None => outln!("{}", Message::FormatVersionNone),
+    }
+    if report.problems.is_empty() {
+        outln!("{}", Message::NoLoadProblems);
+    } else {
+        for problem in &report.problems {
+            outln!("- {}", problem);
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! # Background Reminder Checker
+//!
+//! The main menu shows due reminders each time it comes up, but a session
+//! can sit at one prompt for hours. While the menus run in a terminal, a
+//! background thread looks at the reminders every half minute and prints
+//! the ones that come due in the meantime.
+//!
+//! The thread only has a copy of the reminders and cannot see the food log,
+//! so the menu refreshes the copy (`set_reminders`) with reminders whose
+//! check is done marked as shown, and takes the IDs the thread printed
+//! (`take_announced`) to record them in the settings file. The thread stops
+//! once the checker is dropped.
+
+// src/ui/reminder_checker.rs
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread;
+use std::time::Duration;
+
+use chrono::Local;
+
+use yada_core::models::reminder::Reminder;
+
+use crate::ui::i18n::Message;
+
+/// Time between two looks at the reminders
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// What the menu and the background thread share
+struct CheckerState {
+    /// Copy of the reminders, with `last_shown` kept up to date by the thread
+    reminders: Vec<Reminder>,
+
+    /// IDs of the reminders the thread printed since the last `take_announced`
+    announced: Vec<u32>,
+}
+
+/// Handle of the background thread printing due reminders
+pub struct ReminderChecker {
+    state: Arc<Mutex<CheckerState>>,
+}
+
+impl ReminderChecker {
+    /// Starts the background thread
+    ///
+    /// # Arguments
+    /// * `reminders` - The reminders to watch, with done ones marked as shown today
+    pub fn start(reminders: Vec<Reminder>) -> Self {
+        let state = Arc::new(Mutex::new(CheckerState { reminders, announced: Vec::new() }));
+        let shared = Arc::downgrade(&state);
+        thread::spawn(move || loop {
+            thread::sleep(CHECK_INTERVAL);
+            // The checker was dropped, so the session is over
+            let Some(state) = shared.upgrade() else { break };
+            let mut state = lock(&state);
+            let now = Local::now().naive_local();
+            let CheckerState { reminders, announced } = &mut *state;
+            for reminder in reminders.iter_mut().filter(|reminder| reminder.is_due(now)) {
+                println!("\n{}", Message::ReminderDue.format(&[&reminder.time.format("%H:%M"), &reminder.message]));
+                reminder.last_shown = Some(now.date());
+                announced.push(reminder.id);
+            }
+        });
+        ReminderChecker { state }
+    }
+
+    /// Replaces the watched reminders
+    pub fn set_reminders(&self, reminders: Vec<Reminder>) {
+        lock(&self.state).reminders = reminders;
+    }
+
+    /// Returns the IDs of the reminders printed since the last call
+    pub fn take_announced(&self) -> Vec<u32> {
+        std::mem::take(&mut lock(&self.state).announced)
+    }
+}
+
+/// Locks the shared state; a panic elsewhere cannot leave it half-updated
+fn lock(state: &Mutex<CheckerState>) -> MutexGuard<'_, CheckerState> {
+    state.lock().unwrap_or_else(PoisonError::into_inner)
+}
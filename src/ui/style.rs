@@ -0,0 +1,116 @@
+//! # Terminal Styles
+//!
+//! Optional ANSI colors and bold text for the menus, tables, and statistics:
+//!
+//! - **Headings**: Menu titles and table header rows are bold
+//! - **Against a target**: Calories under the target are green, over it red
+//! - **Warnings**: Intake limit and other warnings are yellow
+//!
+//! Colors never carry meaning on their own; the text still says "over" or
+//! shows the sign, so output without colors reads the same.
+//!
+//! ## When Colors Are Used
+//!
+//! Styles are off unless `init` turns them on, which it does only when all of
+//! these hold:
+//! - `--no-color` was not given
+//! - `NO_COLOR` is not set to a non-empty value (see <https://no-color.org>)
+//! - `TERM` is not `dumb`
+//! - Standard output is a terminal, so piped output and files stay plain
+//!
+//! Like the console, the setting belongs to the current thread (see
+//! `console::install`), so scripted sessions are never styled.
+
+// src/ui/style.rs
+use std::cell::Cell;
+use std::env;
+use std::fmt::Display;
+use std::io::{self, IsTerminal};
+
+/// Environment variable that turns colors off when set to anything
+pub const NO_COLOR_ENV_VAR: &str = "NO_COLOR";
+
+/// Escape sequence ending every style
+const RESET: &str = "\x1b[0m";
+
+thread_local! {
+    static ENABLED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// A way of showing text on a terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    /// Headings and table headers
+    Bold,
+
+    /// Within a target
+    Green,
+
+    /// Over a target
+    Red,
+
+    /// Warnings
+    Yellow,
+}
+
+impl Style {
+    /// ANSI escape sequence starting the style
+    fn code(self) -> &'static str {
+        match self {
+            Style::Bold => "\x1b[1m",
+            Style::Green => "\x1b[32m",
+            Style::Red => "\x1b[31m",
+            Style::Yellow => "\x1b[33m",
+        }
+    }
+}
+
+/// Turns styles on for the current thread if the terminal and environment allow them
+///
+/// # Arguments
+/// * `no_color` - Whether `--no-color` was given
+pub fn init(no_color: bool) {
+    let no_color_env = env::var_os(NO_COLOR_ENV_VAR).is_some_and(|value| !value.is_empty());
+    let dumb = env::var("TERM").is_ok_and(|term| term == "dumb");
+    set_enabled(!no_color && !no_color_env && !dumb && io::stdout().is_terminal());
+}
+
+/// Turns styles on or off for the current thread
+pub fn set_enabled(enabled: bool) {
+    ENABLED.with(|cell| cell.set(enabled));
+}
+
+/// Whether styles are on for the current thread
+pub fn enabled() -> bool {
+    ENABLED.with(Cell::get)
+}
+
+/// Shows text in a style, or as it is when styles are off
+pub fn paint(style: Style, text: impl Display) -> String {
+    if enabled() {
+        format!("{}{}{}", style.code(), text, RESET)
+    } else {
+        text.to_string()
+    }
+}
+
+/// Formats a menu title, such as `------ View Statistics ------`, in bold
+pub fn heading(title: impl Display) -> String {
+    paint(Style::Bold, format!("------ {} ------", title))
+}
+
+/// Shows text green when a value is within its target and red when over it
+///
+/// # Arguments
+/// * `text` - The text to show, usually the value or its difference from the target
+/// * `value` - The value compared, such as calories consumed
+/// * `target` - The target; text is left plain when it is 0 (no target)
+pub fn against_target(text: impl Display, value: f64, target: f64) -> String {
+    if target <= 0.0 {
+        text.to_string()
+    } else if value > target {
+        paint(Style::Red, text)
+    } else {
+        paint(Style::Green, text)
+    }
+}
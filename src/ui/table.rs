@@ -0,0 +1,265 @@
+//! # Tables
+//!
+//! Renders rows of text as aligned columns for the tabular views (food
+//! lists, food logs, date ranges, calorie sources, meal plans, and the
+//! like), so every table measures, pads, and cuts its cells the same way.
+//!
+//! ## Width
+//!
+//! Cells are measured in terminal columns rather than bytes or characters
+//! (see `display_width`): CJK and fullwidth characters and most emoji take
+//! two columns, combining accents and zero-width characters none. Names
+//! such as `Crème brûlée` or `豆腐` therefore line up with ASCII names.
+//!
+//! ## Column Sizing
+//!
+//! Each column is as wide as its widest cell, but at least `min_width`
+//! (never less than the title's width, so translated titles are not cut)
+//! and at most `max_width`. Longer cells are
+//! cut short and end in `…`. Giving both limits the same value fixes the
+//! width, which keeps paged lists from shifting between pages. Numeric
+//! columns are aligned on the right.
+//!
+//! A footer row (such as a total) is printed after a line of `-`.
+//! Cells may be styled (see `ui::style`); the escape sequences take no
+//! width. A row may end in a note (such as a highlight or usage count), which is
+//! printed after the last column without being aligned or cut.
+//!
+//! ```ignore
+//! let mut table = Table::new()
+//!     .column("Food ID").min_width(10)
+//!     .column("Name").min_width(20).max_width(30)
+//!     .numeric("Calories");
+//! table.row(["oats", "Rolled Oats", "150.0"]);
+//! outln!("{}", table);
+//! ```
+
+// src/ui/table.rs
+use std::fmt;
+
+use crate::ui::style::{self, Style};
+
+/// Widest a column of food names grows before the names are cut
+pub const NAME_COLUMN_WIDTH: usize = 30;
+
+/// Where text sits in a column wider than it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    /// Text first, then padding
+    Left,
+
+    /// Padding first, then text (numbers)
+    Right,
+}
+
+/// One column of a table
+#[derive(Debug, Clone)]
+struct Column {
+    title: String,
+    align: Align,
+    min_width: usize,
+    max_width: Option<usize>,
+}
+
+/// Rows of cells rendered as aligned columns
+#[derive(Debug, Clone, Default)]
+pub struct Table {
+    columns: Vec<Column>,
+    rows: Vec<(Vec<String>, String)>,
+    footer: Option<Vec<String>>,
+}
+
+impl Table {
+    /// Creates a table without columns or rows
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a left-aligned column
+    pub fn column(self, title: &str) -> Self {
+        self.with_column(title, Align::Left)
+    }
+
+    /// Adds a right-aligned column, for numbers
+    pub fn numeric(self, title: &str) -> Self {
+        self.with_column(title, Align::Right)
+    }
+
+    fn with_column(mut self, title: &str, align: Align) -> Self {
+        self.columns.push(Column { title: title.to_string(), align, min_width: display_width(title), max_width: None });
+        self
+    }
+
+    /// Sets the smallest width of the column added last, never less than its title's
+    pub fn min_width(mut self, width: usize) -> Self {
+        if let Some(column) = self.columns.last_mut() {
+            column.min_width = width.max(display_width(&column.title));
+        }
+        self
+    }
+
+    /// Sets the largest width of the column added last; longer cells are cut
+    pub fn max_width(mut self, width: usize) -> Self {
+        if let Some(column) = self.columns.last_mut() {
+            column.max_width = Some(width);
+        }
+        self
+    }
+
+    /// Adds a row; missing cells are left empty and extra cells are ignored
+    pub fn row<I, S>(&mut self, cells: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.row_with_note(cells, "");
+    }
+
+    /// Adds a row followed by a note after the last column
+    pub fn row_with_note<I, S>(&mut self, cells: I, note: &str)
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        let cells = cells.into_iter().map(|cell| cell.to_string()).collect();
+        self.rows.push((cells, note.to_string()));
+    }
+
+    /// Sets a row, such as a total, printed below the other rows after a line of `-`
+    pub fn footer<I, S>(&mut self, cells: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.footer = Some(cells.into_iter().map(|cell| cell.to_string()).collect());
+    }
+
+    /// Returns true if the table has no rows
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Width of each column, from its cells and limits
+    pub fn widths(&self) -> Vec<usize> {
+        self.columns.iter().enumerate().map(|(i, column)| {
+            let widest = self.rows.iter()
+                .map(|(cells, _)| cells)
+                .chain(&self.footer)
+                .filter_map(|cells| cells.get(i))
+                .map(|cell| display_width(cell))
+                .max()
+                .unwrap_or(0);
+            let width = widest.max(column.min_width);
+            column.max_width.map_or(width, |max| width.min(max))
+        }).collect()
+    }
+
+    /// Formats the bold title line and the line under it
+    pub fn header(&self) -> String {
+        let widths = self.widths();
+        let titles: Vec<&str> = self.columns.iter().map(|column| column.title.as_str()).collect();
+        let line = self.format_cells(&titles, &widths);
+        format!("{}\n{}", style::paint(Style::Bold, line), self.rule())
+    }
+
+    /// Formats a line of `-` as wide as the table
+    pub fn rule(&self) -> String {
+        let widths = self.widths();
+        "-".repeat(widths.iter().sum::<usize>() + widths.len().saturating_sub(1))
+    }
+
+    /// Formats the rows, one line each
+    pub fn lines(&self) -> Vec<String> {
+        let widths = self.widths();
+        self.rows.iter().map(|(cells, note)| {
+            let cells: Vec<&str> = cells.iter().map(String::as_str).collect();
+            let mut line = self.format_cells(&cells, &widths);
+            line.push_str(note);
+            line.trim_end().to_string()
+        }).collect()
+    }
+
+    /// Pads and cuts one line of cells to the column widths
+    fn format_cells(&self, cells: &[&str], widths: &[usize]) -> String {
+        let formatted: Vec<String> = self.columns.iter().zip(widths).enumerate()
+            .map(|(i, (column, width))| pad(&truncate(cells.get(i).copied().unwrap_or(""), *width), *width, column.align))
+            .collect();
+        formatted.join(" ").trim_end().to_string()
+    }
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.header())?;
+        for line in self.lines() {
+            write!(f, "\n{}", line)?;
+        }
+        if let Some(footer) = &self.footer {
+            let cells: Vec<&str> = footer.iter().map(String::as_str).collect();
+            write!(f, "\n{}\n{}", self.rule(), self.format_cells(&cells, &self.widths()))?;
+        }
+        Ok(())
+    }
+}
+
+/// Number of terminal columns a character takes
+fn char_width(c: char) -> usize {
+    match u32::from(c) {
+        // Control characters, combining marks, zero-width characters, and variation selectors
+        0x00..=0x1F | 0x7F..=0x9F => 0,
+        0x0300..=0x036F | 0x0483..=0x0489 | 0x0591..=0x05BD | 0x0610..=0x061A | 0x064B..=0x065F => 0,
+        0x1AB0..=0x1AFF | 0x1DC0..=0x1DFF | 0x200B..=0x200F | 0x20D0..=0x20FF => 0,
+        0xFE00..=0xFE0F | 0xFE20..=0xFE2F | 0xE0100..=0xE01EF => 0,
+        // Hangul, CJK, fullwidth forms, and emoji
+        0x1100..=0x115F | 0x2E80..=0x303E | 0x3041..=0x33FF | 0x3400..=0x4DBF | 0x4E00..=0x9FFF => 2,
+        0xA000..=0xA4CF | 0xAC00..=0xD7A3 | 0xF900..=0xFAFF | 0xFE30..=0xFE4F | 0xFF00..=0xFF60 | 0xFFE0..=0xFFE6 => 2,
+        0x1F300..=0x1F64F | 0x1F900..=0x1F9FF | 0x20000..=0x2FFFD | 0x30000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+/// Number of terminal columns text takes, not counting style escape sequences
+pub fn display_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Skips to the letter ending the sequence, such as the `m` of `\x1b[1m`
+            chars.by_ref().find(char::is_ascii_alphabetic);
+        } else {
+            width += char_width(c);
+        }
+    }
+    width
+}
+
+/// Shortens text to at most `width` terminal columns, ending in `…` when cut
+pub fn truncate(text: &str, width: usize) -> String {
+    if display_width(text) <= width {
+        return text.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+    let mut short = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let next = char_width(c);
+        if used + next > width - 1 {
+            break;
+        }
+        short.push(c);
+        used += next;
+    }
+    short.push('…');
+    short
+}
+
+/// Pads text with spaces to `width` terminal columns
+pub fn pad(text: &str, width: usize, align: Align) -> String {
+    let padding = " ".repeat(width.saturating_sub(display_width(text)));
+    match align {
+        Align::Left => format!("{}{}", text, padding),
+        Align::Right => format!("{}{}", padding, text),
+    }
+}
@@ -0,0 +1,111 @@
+//! # Unlocking Encrypted Data
+//!
+//! Decides at startup whether the data files are encrypted (see
+//! `yada_core::repositories::encryption`) and gets the password before any
+//! repository reads them:
+//!
+//! - **Encryption on** (`YADA_ENCRYPT=on`): Saves are encrypted. Without
+//!   encrypted files yet, a new password is asked for twice
+//! - **Encryption off** (`YADA_ENCRYPT=off`): Saves are plain text; the
+//!   password is still needed to read files that are encrypted
+//! - **Not set**: Encryption stays on exactly if the files are encrypted
+//!
+//! `YADA_PASSWORD` provides the password without asking, for scripts.
+
+// src/ui/unlock.rs
+use std::fs;
+
+use yada_core::repositories::encryption;
+
+use crate::config::AppConfig;
+use crate::ui::console::outln;
+use crate::ui::i18n::Message;
+use crate::ui::prompt;
+
+/// Wrong passwords accepted before giving up
+const PASSWORD_ATTEMPTS: usize = 3;
+
+/// Gets the password for the data files if they are (or will be) encrypted
+///
+/// # Arguments
+/// * `config` - Configuration with the data file locations
+/// * `ask` - Whether the password may be asked for; otherwise only
+///   `YADA_PASSWORD` is used
+///
+/// # Returns
+/// * `Ok(())` - The data files can be read and saved
+/// * `Err(String)` - The password is missing or wrong
+pub fn unlock_data(config: &AppConfig, ask: bool) -> Result<(), String> {
+    let encrypted = encrypted_file(config);
+    let encrypt_saves = encryption::setting_from_env().unwrap_or(encrypted.is_some());
+    if !encrypt_saves && encrypted.is_none() {
+        encryption::lock();
+        return Ok(());
+    }
+
+    let password = match (encryption::password_from_env(), &encrypted) {
+        (Some(password), Some((path, data))) => {
+            if !check(data, &password)? {
+                return Err(Message::PasswordDoesNotOpen.format(&[&encryption::PASSWORD_ENV_VAR, &path]));
+            }
+            password
+        }
+        (Some(password), None) => password,
+        (None, _) if !ask => {
+            return Err(Message::FilesEncryptedSetPassword.format(&[&encryption::PASSWORD_ENV_VAR]));
+        }
+        (None, Some((_, data))) => ask_password(data)?,
+        (None, None) => choose_password()?,
+    };
+
+    encryption::unlock(&password, encrypt_saves);
+    if ask && encrypt_saves && encrypted.is_none() {
+        outln!("{}", Message::WillEncrypt);
+    } else if ask && !encrypt_saves {
+        outln!("{}", Message::EncryptionOff.format(&[&encryption::ENCRYPTION_ENV_VAR]));
+    }
+    Ok(())
+}
+
+/// Returns the path and contents of the first encrypted data file
+fn encrypted_file(config: &AppConfig) -> Option<(String, Vec<u8>)> {
+    [config.foods_path(), config.logs_path(), config.profile_path(), config.exercise_path(), config.meal_plans_path(),
+     config.meal_templates_path()]
+        .into_iter()
+        .filter_map(|path| fs::read(&path).ok().map(|data| (path, data)))
+        .find(|(_, data)| encryption::is_encrypted(data))
+}
+
+/// Asks for the password of encrypted files until it opens them
+fn ask_password(data: &[u8]) -> Result<String, String> {
+    for _ in 0..PASSWORD_ATTEMPTS {
+        let password = prompt::password(Message::PasswordPrompt.text()).map_err(|_| Message::NoPasswordGiven.text().to_string())?;
+        if check(data, &password)? {
+            return Ok(password);
+        }
+        outln!("{}", Message::WrongPassword);
+    }
+    Err(Message::WrongPasswordNotOpened.text().to_string())
+}
+
+/// Asks for a new password, twice, for files about to be encrypted
+fn choose_password() -> Result<String, String> {
+    loop {
+        let password = prompt::password(Message::ChoosePasswordPrompt.text())
+            .map_err(|_| Message::NoPasswordGiven.text().to_string())?;
+        if password.is_empty() {
+            outln!("{}", Message::PasswordEmpty);
+            continue;
+        }
+        let repeated = prompt::password(Message::RepeatPasswordPrompt.text()).map_err(|_| Message::NoPasswordGiven.text().to_string())?;
+        if repeated == password {
+            return Ok(password);
+        }
+        outln!("{}", Message::PasswordsDiffer);
+    }
+}
+
+/// Checks a password against an encrypted file
+fn check(data: &[u8], password: &str) -> Result<bool, String> {
+    encryption::check_password(data, password).map_err(|e| Message::CannotCheckPassword.format(&[&e]))
+}
@@ -0,0 +1,56 @@
+//! # User Selection
+//!
+//! Menu for choosing whose data the session uses, shown at startup once
+//! named users exist and from **Manage Profile > Switch User**. The users
+//! themselves are directories in the data directory (see `config.rs`); this
+//! module only presents them and asks for the name of a new one.
+
+// src/ui/users.rs
+use crate::config::{self, AppConfig, DEFAULT_USER};
+use crate::ui::console::outln;
+use crate::ui::i18n::Message;
+use crate::ui::prompt::{self, PromptResult};
+
+/// Asks which user to use, offering to create a new one
+///
+/// # Arguments
+/// * `config` - Configuration with the data directory
+/// * `current` - User of the session, marked in the list (`None` at startup)
+///
+/// # Returns
+/// * `Ok(String)` - Name of the chosen or new user (`default` for the default user)
+/// * `Err(PromptError)` - If the user cancelled or input ended
+pub fn choose_user(config: &AppConfig, current: Option<&str>) -> PromptResult<String> {
+    let mut names = vec![DEFAULT_USER.to_string()];
+    names.extend(config.users());
+
+    let mut labels: Vec<String> = names.iter()
+        .map(|name| if Some(name.as_str()) == current { Message::CurrentMarker.format(&[name]) } else { name.clone() })
+        .collect();
+    labels.push(Message::NewUser.to_string());
+    let labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+
+    outln!("{}", Message::WhoIsUsing);
+    let choice = prompt::choice(&labels).ask()?;
+    match names.get(choice - 1) {
+        Some(name) => Ok(name.clone()),
+        None => ask_new_user_name(names),
+    }
+}
+
+/// Asks for the name of a new user
+///
+/// # Arguments
+/// * `existing` - Names already in use
+fn ask_new_user_name(existing: Vec<String>) -> PromptResult<String> {
+    prompt::text(Message::NewUserName.text())
+        .validate(|name: &String| config::validate_user_name(name))
+        .validate(move |name| {
+            if existing.iter().any(|user| user.eq_ignore_ascii_case(name)) {
+                Err(Message::UserExists.format(&[name]))
+            } else {
+                Ok(())
+            }
+        })
+        .ask()
+}
@@ -0,0 +1,255 @@
+//! # Menu Flow Transcripts
+//!
+//! An expect-style harness for end-to-end tests of the interactive menus.
+//! A transcript lists the data files to start from, the lines the user
+//! types, and text the program must print. The harness runs the real
+//! `yada` binary in a fresh data directory, feeds it all the input, and
+//! checks the output in order.
+//!
+//! ## Transcript Format
+//!
+//! One directive per line; blank lines are ignored.
+//!
+//! ```text
+//! # Comment
+//! @env YADA_WEEK_START=sunday    Environment variable for the run
+//! @file profile.txt              Data file written before the run; its
+//! #yada:profile:v1               lines follow verbatim up to @end
+//! PROFILE|M|180|1990-01-01|mifflin_st_jeor
+//! @end
+//! > 2                            A line the user types ("> " alone is Enter)
+//! < ------ View Foods ------     Output that must appear after the previous match
+//! @saved logs.txt |oats|2|       Text a data file must contain after the run
+//! ```
+//!
+//! Expected text is matched as a substring anywhere after the previous
+//! match, so prompts that print without a newline are easy to expect.
+//! `{today}` is replaced with the current date (`YYYY-MM-DD`) in expected
+//! text, file contents, and input.
+//! Data files may be in subdirectories of the data directory (such as
+//! `users/alice/logs.txt`); the directories are created as needed.
+//!
+//! `DataDir` is also used by tests that run the menus in-process (see
+//! `tests/scripted_sessions.rs`).
+//!
+//! The program runs with every `YADA_*` variable of the test environment
+//! removed, so only `@env` lines affect it. It exits on its own when the
+//! input runs out, saving its data as if the user chose Exit.
+
+// tests/common/mod.rs
+#![allow(dead_code)]
+
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use chrono::Local;
+
+/// Directory holding the transcript files
+const TRANSCRIPT_DIR: &str = "tests/transcripts";
+
+/// Longest a transcript may run before it is considered hung
+const TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Counter making data directory names unique within a test binary
+static NEXT_DIR: AtomicUsize = AtomicUsize::new(0);
+
+/// One expectation checked against the program's output or data files
+#[derive(Debug, Clone, PartialEq)]
+enum Expectation {
+    /// Text printed after the previous printed match
+    Output { line: usize, text: String },
+
+    /// Text contained in a data file after the run
+    Saved { line: usize, file: String, text: String },
+}
+
+/// A parsed transcript
+#[derive(Debug, Clone, Default)]
+pub struct Transcript {
+    /// Name used in failure messages
+    name: String,
+
+    /// Environment variables for the run
+    env: Vec<(String, String)>,
+
+    /// Data files written before the run, with their contents
+    files: Vec<(String, String)>,
+
+    /// Input lines, in order
+    input: Vec<String>,
+
+    /// Checks, in order
+    expectations: Vec<Expectation>,
+}
+
+impl Transcript {
+    /// Parses a transcript
+    ///
+    /// # Arguments
+    /// * `name` - Name used in failure messages
+    /// * `source` - Transcript text
+    ///
+    /// # Returns
+    /// * `Ok(Transcript)` - The parsed transcript
+    /// * `Err(String)` - The first line that is not a valid directive
+    pub fn parse(name: &str, source: &str) -> Result<Self, String> {
+        let today = Local::now().date_naive().format("%Y-%m-%d").to_string();
+        let expand = |text: &str| text.replace("{today}", &today);
+        let mut transcript = Transcript { name: name.to_string(), ..Transcript::default() };
+        let mut lines = source.lines().enumerate().map(|(index, line)| (index + 1, line));
+
+        while let Some((number, line)) = lines.next() {
+            let error = |message: &str| format!("{}:{}: {}", name, number, message);
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(input) = line.strip_prefix('>') {
+                transcript.input.push(expand(input.strip_prefix(' ').unwrap_or(input)));
+            } else if let Some(text) = line.strip_prefix('<') {
+                let text = expand(text.strip_prefix(' ').unwrap_or(text));
+                if text.is_empty() {
+                    return Err(error("expected output is empty"));
+                }
+                transcript.expectations.push(Expectation::Output { line: number, text });
+            } else if let Some(setting) = line.strip_prefix("@env ") {
+                let (key, value) = setting.split_once('=').ok_or_else(|| error("expected @env NAME=value"))?;
+                transcript.env.push((key.trim().to_string(), value.trim().to_string()));
+            } else if let Some(file) = line.strip_prefix("@file ") {
+                let mut contents = String::new();
+                loop {
+                    match lines.next() {
+                        Some((_, "@end")) => break,
+                        Some((_, content)) => {
+                            contents.push_str(&expand(content));
+                            contents.push('\n');
+                        }
+                        None => return Err(error("@file is missing its @end")),
+                    }
+                }
+                transcript.files.push((file.trim().to_string(), contents));
+            } else if let Some(check) = line.strip_prefix("@saved ") {
+                let (file, text) = check.split_once(' ').ok_or_else(|| error("expected @saved FILE text"))?;
+                transcript.expectations.push(Expectation::Saved {
+                    line: number,
+                    file: file.to_string(),
+                    text: expand(text),
+                });
+            } else {
+                return Err(error(&format!("unknown directive '{}'", line)));
+            }
+        }
+        Ok(transcript)
+    }
+
+    /// Loads `tests/transcripts/<name>.txt`
+    pub fn load(name: &str) -> Self {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(TRANSCRIPT_DIR).join(format!("{}.txt", name));
+        let source = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("cannot read {}: {}", path.display(), e));
+        Transcript::parse(name, &source).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Runs the program and checks every expectation, panicking on the first failure
+    pub fn check(&self) {
+        let dir = DataDir::new(&self.name);
+        for (file, contents) in &self.files {
+            let path = dir.path.join(file);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).expect("create data file directory");
+            }
+            fs::write(path, contents).expect("write data file");
+        }
+        let output = self.run(&dir);
+
+        let mut position = 0;
+        for expectation in &self.expectations {
+            match expectation {
+                Expectation::Output { line, text } => match output[position..].find(text.as_str()) {
+                    Some(found) => position += found + text.len(),
+                    None => panic!(
+                        "{}:{}: expected output {:?}\n--- output after the previous match ---\n{}",
+                        self.name, line, text, &output[position..]
+                    ),
+                },
+                Expectation::Saved { line, file, text } => {
+                    let contents = fs::read_to_string(dir.path.join(file)).unwrap_or_default();
+                    assert!(
+                        contents.contains(text.as_str()),
+                        "{}:{}: expected {} to contain {:?}\n--- {} ---\n{}",
+                        self.name, line, file, text, file, contents
+                    );
+                }
+            }
+        }
+    }
+
+    /// Runs the program with the transcript's input and returns everything it printed
+    fn run(&self, dir: &DataDir) -> String {
+        let output_path = dir.path.join("transcript-output.txt");
+        let mut command = Command::new(env!("CARGO_BIN_EXE_yada"));
+        for (key, _) in std::env::vars().filter(|(key, _)| key.starts_with("YADA_")) {
+            command.env_remove(key);
+        }
+        command
+            .env("YADA_DATA_DIR", &dir.path)
+            .envs(self.env.iter().map(|(key, value)| (key, value)))
+            .stdin(Stdio::piped())
+            .stdout(File::create(&output_path).expect("create output file"))
+            .stderr(Stdio::inherit());
+        let mut child = command.spawn().expect("start yada");
+
+        let mut input = self.input.join("\n");
+        input.push('\n');
+        let mut stdin = child.stdin.take().expect("stdin");
+        let writer = thread::spawn(move || {
+            use std::io::Write;
+            // The program may exit before reading everything; that is not an error here
+            let _ = stdin.write_all(input.as_bytes());
+        });
+
+        let started = Instant::now();
+        let status = loop {
+            if let Some(status) = child.try_wait().expect("wait for yada") {
+                break status;
+            }
+            if started.elapsed() > TIMEOUT {
+                let _ = child.kill();
+                panic!("{}: yada did not exit within {:?}", self.name, TIMEOUT);
+            }
+            thread::sleep(Duration::from_millis(10));
+        };
+        let _ = writer.join();
+
+        let output = fs::read_to_string(&output_path).unwrap_or_default();
+        assert!(status.success(), "{}: yada exited with {}\n{}", self.name, status, output);
+        output
+    }
+}
+
+/// Temporary data directory that is removed when dropped
+pub struct DataDir {
+    /// Location of the directory
+    pub path: PathBuf,
+}
+
+impl DataDir {
+    /// Creates an empty directory, unique to this test run, for `name`
+    pub fn new(name: &str) -> Self {
+        let unique = NEXT_DIR.fetch_add(1, Ordering::SeqCst);
+        let path = std::env::temp_dir()
+            .join(format!("yada-flow-{}-{}-{}", name, process::id(), unique));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).expect("create data dir");
+        DataDir { path }
+    }
+}
+
+impl Drop for DataDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
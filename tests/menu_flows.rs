@@ -0,0 +1,241 @@
+//! End-to-end regression tests for the interactive menus
+//!
+//! Each test replays a transcript from `tests/transcripts` against the
+//! `yada` binary; see `common` for the transcript format.
+
+// tests/menu_flows.rs
+mod common;
+
+use common::Transcript;
+
+#[test]
+fn first_run_creates_a_profile() {
+    Transcript::load("first_run").check();
+}
+
+#[test]
+fn adding_a_basic_food() {
+    Transcript::load("add_basic_food").check();
+}
+
+#[test]
+fn searching_the_food_sources() {
+    Transcript::load("import_food_sources").check();
+}
+
+#[test]
+fn logging_a_food_and_undoing_it() {
+    Transcript::load("log_and_undo").check();
+}
+
+#[test]
+fn suggesting_similar_foods_when_a_log_search_finds_nothing() {
+    Transcript::load("log_food_suggestions").check();
+}
+
+#[test]
+fn accepting_suggested_keywords_for_a_new_food() {
+    Transcript::load("keyword_suggestions").check();
+}
+
+#[test]
+fn editing_keywords_of_several_foods() {
+    Transcript::load("edit_keywords").check();
+}
+
+#[test]
+fn showing_food_cost_and_calorie_density() {
+    Transcript::load("food_cost").check();
+}
+
+#[test]
+fn tracking_alcohol_and_caffeine() {
+    Transcript::load("alcohol_caffeine").check();
+}
+
+#[test]
+fn warning_about_foods_that_conflict_with_dietary_restrictions() {
+    Transcript::load("dietary_restrictions").check();
+}
+
+#[test]
+fn writing_a_day_note_with_mood_and_energy() {
+    Transcript::load("day_notes").check();
+}
+
+#[test]
+fn showing_due_reminders_when_the_menu_comes_up() {
+    Transcript::load("reminders").check();
+}
+
+#[test]
+fn changing_units_and_date_format_in_preferences() {
+    Transcript::load("preferences").check();
+}
+
+#[test]
+fn saving_a_search_and_picking_it_by_number() {
+    Transcript::load("saved_searches").check();
+}
+
+#[test]
+fn finding_foods_by_nutrient_limits() {
+    Transcript::load("advanced_search").check();
+}
+
+#[test]
+fn logging_a_meal_template_and_undoing_it_at_once() {
+    Transcript::load("meal_templates").check();
+}
+
+#[test]
+fn logging_a_food_with_its_default_servings() {
+    Transcript::load("default_servings").check();
+}
+
+#[test]
+fn batch_logging_several_foods_and_undoing_them_at_once() {
+    Transcript::load("batch_log").check();
+}
+
+#[test]
+fn logging_foods_described_in_plain_words() {
+    Transcript::load("describe_foods").check();
+}
+
+#[test]
+fn viewing_the_log_across_a_date_range() {
+    Transcript::load("log_range").check();
+}
+
+#[test]
+fn showing_the_top_foods_and_calorie_sources() {
+    Transcript::load("calorie_sources").check();
+}
+
+#[test]
+fn the_adaptive_method_measures_the_tdee_from_intake_and_weight() {
+    Transcript::load("adaptive_tdee").check();
+}
+
+#[test]
+fn breastfeeding_adds_to_the_target() {
+    Transcript::load("life_stage").check();
+}
+
+#[test]
+fn profile_values_out_of_range_are_asked_again() {
+    Transcript::load("profile_validation").check();
+}
+
+#[test]
+fn suspicious_food_values_ask_before_adding() {
+    Transcript::load("food_validation").check();
+}
+
+#[test]
+fn expert_mode_skips_confirmations_that_undo_can_replace() {
+    Transcript::load("confirm_policy").check();
+}
+
+#[test]
+fn foods_are_picked_by_number_and_filtered_as_you_type() {
+    Transcript::load("pick_foods").check();
+}
+
+#[test]
+fn every_view_counts_the_exercise_credit_in_the_target() {
+    Transcript::load("exercise_credit").check();
+}
+
+#[test]
+fn logged_calories_outlast_changed_and_deleted_foods() {
+    Transcript::load("logged_calories").check();
+}
+
+#[test]
+fn cancelling_a_flow() {
+    Transcript::load("cancel").check();
+}
+
+#[test]
+fn bookmarking_and_returning_to_a_date() {
+    Transcript::load("change_date").check();
+}
+
+#[test]
+fn incomplete_profile_shows_an_estimate_only_target() {
+    Transcript::load("estimate_only").check();
+}
+
+#[test]
+fn transcripts_reject_unknown_directives() {
+    assert!(Transcript::parse("bad", "> 1\n< Goodbye!\n").is_ok());
+    assert!(Transcript::parse("bad", "@file foods.txt\nB|apple|Apple|fruit|95\n").is_err());
+    assert!(Transcript::parse("bad", "wait 5\n").is_err());
+}
+
+#[test]
+fn merging_duplicate_foods() {
+    Transcript::load("merge_duplicates").check();
+}
+
+#[test]
+fn editing_a_log_entry_and_undoing_it() {
+    Transcript::load("edit_log_entry").check();
+}
+
+#[test]
+fn logging_for_an_earlier_day_asks_for_the_time() {
+    Transcript::load("retroactive_log").check();
+}
+
+#[test]
+fn exiting_prints_and_journals_the_daily_summary() {
+    Transcript::load("exit_summary").check();
+}
+
+#[test]
+fn statistics_chart_calories_and_weight_trend() {
+    Transcript::load("stats_charts").check();
+}
+
+#[test]
+fn weekday_targets_are_set_and_cleared_in_the_profile() {
+    Transcript::load("weekday_targets").check();
+}
+
+#[test]
+fn a_diet_preset_sets_macro_targets_in_statistics() {
+    Transcript::load("diet_preset").check();
+}
+
+#[test]
+fn macro_targets_show_as_progress_bars_in_statistics() {
+    Transcript::load("macro_targets").check();
+}
+
+#[test]
+fn switching_to_a_new_user_keeps_data_separate() {
+    Transcript::load("switch_user").check();
+}
+
+#[test]
+fn startup_asks_which_user_once_there_are_named_users() {
+    Transcript::load("choose_user").check();
+}
+
+#[test]
+fn basic_profile_changes_keep_a_dated_history() {
+    Transcript::load("profile_history").check();
+}
+
+#[test]
+fn menus_follow_the_yada_lang_locale() {
+    Transcript::load("german_menus").check();
+}
+
+#[test]
+fn damaged_lines_are_listed_at_startup() {
+    Transcript::load("load_warnings").check();
+}
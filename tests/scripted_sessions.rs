@@ -0,0 +1,344 @@
+//! In-process tests of whole menu sessions
+//!
+//! Each test runs `App` with a `ScriptedInput` and a `CapturedOutput`
+//! instead of the terminal, then checks what was printed and saved. Unlike
+//! the transcripts in `menu_flows.rs`, these need no `yada` process, so they
+//! can also look at the data files and the app between sessions.
+
+// tests/scripted_sessions.rs
+mod common;
+
+use std::fs;
+
+use chrono::Local;
+use common::DataDir;
+use yada::app::App;
+use yada::cli::profiling::StartupProfile;
+use yada::config::{AppConfig, DataDirSource};
+use yada::ui::console::{self, CapturedOutput, ScriptedInput};
+use yada::ui::i18n::Message;
+use yada::ui::{prompt, style, table, unlock};
+use yada_core::repositories::encryption;
+use yada_core::repositories::load_report::LoadMode;
+
+/// Profile with today's weight, so sessions start at the main menu
+fn profile_file() -> String {
+    let today = Local::now().date_naive().format("%Y-%m-%d");
+    format!("#yada:profile:v1\nPROFILE|M|180|1990-01-01|mifflin_st_jeor\nDAILY|{}|80|S\n", today)
+}
+
+/// Creates the app for a data directory with scripted input
+///
+/// # Returns
+/// The app, or the startup error, and the capture of its output
+fn start(dir: &DataDir, input: &[&str]) -> (Result<App, std::io::Error>, CapturedOutput) {
+    let output = CapturedOutput::new();
+    let config = AppConfig::with_data_dir(dir.path.clone(), DataDirSource::CommandLine);
+    let app = App::with_console(
+        config,
+        &mut StartupProfile::new(),
+        ScriptedInput::new(input.iter().copied()),
+        output.clone(),
+    );
+    (app, output)
+}
+
+/// Runs a whole session until the input runs out
+///
+/// # Returns
+/// Everything the session printed
+fn run_session(dir: &DataDir, input: &[&str]) -> String {
+    let (app, output) = start(dir, input);
+    app.expect("start app").run();
+    output.contents()
+}
+
+/// Position of `text` in `output`, panicking with the output if it is missing
+fn position(output: &str, text: &str) -> usize {
+    output.find(text).unwrap_or_else(|| panic!("expected {:?} in output:\n{}", text, output))
+}
+
+#[test]
+fn a_first_session_creates_the_profile_from_scripted_answers() {
+    let dir = DataDir::new("scripted-first-run");
+    let output = run_session(&dir, &["2", "165", "1992-04-01", "61.5", "3", "12"]);
+
+    assert!(position(&output, "Profile created successfully!") < position(&output, "------ YADA Main Menu ------"));
+    assert!(output.contains("Goodbye!"));
+    let profile = fs::read_to_string(dir.path.join("profile.txt")).expect("profile saved");
+    assert!(profile.contains("PROFILE|F|165|1992-04-01|harris_benedict"), "{}", profile);
+}
+
+#[test]
+fn running_out_of_input_saves_like_exit() {
+    let dir = DataDir::new("scripted-end-of-input");
+    fs::write(dir.path.join("profile.txt"), profile_file()).unwrap();
+    let foods = "#yada:foods:v1\nB|oats|Rolled Oats|grain|150|protein=5|carbs=27|fat=3\n";
+    fs::write(dir.path.join("foods.txt"), foods).unwrap();
+
+    // Log two servings of oats for breakfast, then stop typing
+    run_session(&dir, &["3", "1", "oats", "2", "1"]);
+
+    let logs = fs::read_to_string(dir.path.join("logs.txt")).expect("log saved");
+    assert!(logs.contains("|oats|2|"), "{}", logs);
+}
+
+#[test]
+fn later_sessions_see_earlier_sessions_data_and_undo_within_their_own() {
+    let dir = DataDir::new("scripted-undo");
+    fs::write(dir.path.join("profile.txt"), profile_file()).unwrap();
+    let foods = "#yada:foods:v1\nB|oats|Rolled Oats|grain|150|protein=5|carbs=27|fat=3\n";
+    fs::write(dir.path.join("foods.txt"), foods).unwrap();
+
+    let first = run_session(&dir, &["3", "1", "oats", "2", "1", "12"]);
+    assert!(first.contains("Food logged successfully!"));
+
+    let second = run_session(&dir, &["4", "7", "10", "4", "2", "12"]);
+    assert!(second.contains("Total calories: 300.0"), "{}", second);
+    // The first session's entry is saved, so there is nothing to undo
+    assert!(position(&second, "Total calories: 300.0") < position(&second, "No commands to undo."));
+}
+
+#[test]
+fn scripted_sessions_fail_on_unreadable_data_instead_of_asking() {
+    let dir = DataDir::new("scripted-recovery");
+    fs::write(dir.path.join("profile.txt"), profile_file()).unwrap();
+    fs::write(dir.path.join("foods.txt"), b"#yada:foods:v1\n\xff\xfe\n").unwrap();
+
+    let (app, output) = start(&dir, &["1"]);
+
+    assert!(app.is_err());
+    assert!(!output.contents().contains("Could not load"), "{}", output.contents());
+}
+
+#[test]
+fn strict_loading_refuses_damaged_files_that_lenient_loading_reports() {
+    let dir = DataDir::new("scripted-strict");
+    fs::write(dir.path.join("profile.txt"), profile_file()).unwrap();
+    fs::write(dir.path.join("foods.txt"), "#yada:foods:v1\nB|oats|Oats|grain|150\nB|short\n").unwrap();
+
+    let mut config = AppConfig::with_data_dir(dir.path.clone(), DataDirSource::CommandLine);
+    config.load_mode = LoadMode::Strict;
+    let error = App::with_console(config, &mut StartupProfile::new(), ScriptedInput::new([""; 0]), CapturedOutput::new())
+        .err()
+        .expect("strict loading fails");
+    assert!(error.to_string().contains("Line 3: skipped"), "{}", error);
+
+    // Lenient loading starts, lists the line, and drops it when saving
+    let output = run_session(&dir, &[]);
+    assert!(output.contains("was not loaded exactly as written (1 line skipped)"), "{}", output);
+    assert!(!fs::read_to_string(dir.path.join("foods.txt")).unwrap().contains("short"));
+}
+
+#[test]
+fn a_second_app_on_the_same_data_directory_is_refused() {
+    let dir = DataDir::new("scripted-lock");
+    fs::write(dir.path.join("profile.txt"), profile_file()).unwrap();
+
+    let (first, _) = start(&dir, &[]);
+    let first = first.expect("first app starts");
+    let (second, _) = start(&dir, &[]);
+    let error = second.err().expect("second app is refused");
+    assert!(error.to_string().contains("Another YADA instance"), "{}", error);
+
+    drop(first);
+    assert!(start(&dir, &[]).0.is_ok(), "the lock is released with the first app");
+}
+
+#[test]
+fn encrypted_data_opens_after_the_password_is_entered() {
+    let dir = DataDir::new("scripted-encrypted");
+    fs::write(dir.path.join("profile.txt"), profile_file()).unwrap();
+    encryption::unlock("open sesame", true);
+    run_session(&dir, &["9"]);
+    let profile = fs::read(dir.path.join("profile.txt")).unwrap();
+    assert!(encryption::is_encrypted(&profile));
+    encryption::lock();
+
+    let config = AppConfig::with_data_dir(dir.path.clone(), DataDirSource::CommandLine);
+    let output = CapturedOutput::new();
+    console::install(ScriptedInput::new(["sesame", "open sesame"]), output.clone());
+    unlock::unlock_data(&config, true).expect("the second password opens the files");
+    assert_eq!(output.contents().matches("Wrong password.").count(), 1, "{}", output.contents());
+
+    let output = run_session(&dir, &["7"]);
+    assert!(output.contains("------ View Statistics ------"), "{}", output);
+    encryption::lock();
+    assert!(unlock::unlock_data(&config, false).unwrap_err().contains("YADA_PASSWORD"));
+}
+
+#[test]
+fn an_exported_bundle_is_imported_on_another_data_directory() {
+    let laptop = DataDir::new("scripted-export");
+    fs::write(laptop.path.join("profile.txt"), profile_file()).unwrap();
+    let foods = "#yada:foods:v1\nB|oats|Rolled Oats|grain|150|protein=5|carbs=27|fat=3\n";
+    fs::write(laptop.path.join("foods.txt"), foods).unwrap();
+    fs::write(laptop.path.join("settings.txt"), "#yada:settings:v1\nPREF|units=imperial\n").unwrap();
+    run_session(&laptop, &["3", "1", "oats", "2", "1", "12"]);
+
+    let bundle = laptop.path.join("backup.zip").to_string_lossy().into_owned();
+    let output = run_session(&laptop, &["6", "9", &bundle, "13", "12"]);
+    assert!(output.contains("Exported foods.txt, settings.txt, logs.txt, profile.txt to"), "{}", output);
+
+    let desktop = DataDir::new("scripted-import");
+    fs::write(desktop.path.join("profile.txt"), profile_file()).unwrap();
+    let output = run_session(&desktop, &["6", "10", &bundle, "yes", "13", "4", "12"]);
+    assert!(output.contains("Imported foods.txt, settings.txt, logs.txt, profile.txt."), "{}", output);
+    assert!(output.contains("Total calories: 300.0"), "{}", output);
+    assert!(fs::read_to_string(desktop.path.join("settings.txt")).unwrap().contains("PREF|units=imperial"));
+    assert!(fs::read_to_string(desktop.path.join("logs.txt")).unwrap().contains("|oats|2|"));
+    assert!(desktop.path.join("foods.txt.bak.1").exists(), "the seeded foods are backed up");
+}
+
+#[test]
+fn another_apps_export_is_previewed_and_imported_from_the_profile_menu() {
+    let dir = DataDir::new("scripted-import-app");
+    fs::write(dir.path.join("profile.txt"), profile_file()).unwrap();
+    fs::write(dir.path.join("foods.txt"), "#yada:foods:v1\nB|milk|Milk|dairy|100\n").unwrap();
+    let export = dir.path.join("servings.csv");
+    fs::write(
+        &export,
+        "Day,Time,Group,Food Name,Amount,Energy (kcal),Protein (g),Carbs (g),Fat (g)\n\
+         2024-03-05,08:10,Breakfast,\"Oats, Rolled\",1.00 cup,307.2,10.7,54.8,5.3\n",
+    )
+    .unwrap();
+    let export = export.to_string_lossy().into_owned();
+
+    // Declining keeps the data unchanged
+    let output = run_session(&dir, &["6", "11", "1", &export, "no", "13", "12"]);
+    assert!(output.contains("1. Cronometer (Servings export"), "{}", output);
+    assert!(output.contains("Would create 1 food(s):\n  Oats, Rolled"), "{}", output);
+    assert!(!fs::read_to_string(dir.path.join("logs.txt")).unwrap_or_default().contains("cronometer_oats_rolled"));
+
+    // The undo history is cleared, since its commands refer to the replaced data
+    let output = run_session(&dir, &["3", "1", "milk", "1", "1", "6", "11", "1", &export, "yes", "13", "10", "12"]);
+    assert!(output.contains("Food logged successfully!"), "{}", output);
+    assert!(output.contains("Imported 1 food(s) and 1 log entries."), "{}", output);
+    assert!(output[position(&output, "Imported 1 food(s)")..].contains("No commands to undo."), "{}", output);
+    let logs = fs::read_to_string(dir.path.join("logs.txt")).unwrap();
+    assert!(logs.contains("2024-03-05|cronometer_oats_rolled|1|2024-03-05T08:10:00"), "{}", logs);
+
+    let output = run_session(&dir, &["6", "11", "1", &export, "13", "12"]);
+    assert!(output.contains("Skipped 1 entries already imported.\nNothing new to import."), "{}", output);
+}
+
+#[test]
+fn prompts_read_from_the_installed_console() {
+    let output = CapturedOutput::new();
+    console::install(ScriptedInput::new(["abc", "0", "3"]), output.clone());
+
+    let choice = prompt::choice(&["Apples", "Pears", "Plums"]).ask();
+
+    assert_eq!(choice, Ok(3));
+    let printed = output.contents();
+    assert!(printed.contains("1. Apples\n2. Pears\n3. Plums\n"), "{}", printed);
+    assert_eq!(printed.matches("Invalid choice.").count(), 2, "{}", printed);
+
+    assert!(prompt::text("Anything else? ").ask().is_err(), "input has run out");
+}
+
+#[test]
+fn number_prompts_reject_infinity() {
+    let output = CapturedOutput::new();
+    console::install(ScriptedInput::new(["inf", "-infinity", "2.5", "infinity", "0"]), output.clone());
+
+    let weight = prompt::positive_f64("Weight: ", "Enter a positive number.").ask();
+    let calories = prompt::non_negative_f64("Calories: ", "Enter zero or more.").ask();
+
+    assert_eq!(weight, Ok(2.5));
+    assert_eq!(calories, Ok(0.0));
+    let printed = output.contents();
+    assert_eq!(printed.matches("Enter a positive number.").count(), 2, "{}", printed);
+    assert_eq!(printed.matches("Enter zero or more.").count(), 1, "{}", printed);
+}
+
+#[test]
+fn styles_mark_headings_and_calories_against_the_target() {
+    let dir = DataDir::new("scripted-styles");
+    fs::write(dir.path.join("profile.txt"), profile_file()).unwrap();
+    fs::write(dir.path.join("foods.txt"), "#yada:foods:v1\nB|oats|Rolled Oats|grain|150\n").unwrap();
+    let today = Local::now().date_naive().format("%Y-%m-%d");
+    fs::write(dir.path.join("logs.txt"), format!("#yada:logs:v1\n{}|oats|1|{}T08:00:00\n", today, today)).unwrap();
+
+    // Styles are off unless turned on, as for output that is not a terminal
+    let plain = run_session(&dir, &["7", "12"]);
+    assert!(!plain.contains('\x1b'));
+
+    style::set_enabled(true);
+    let styled = run_session(&dir, &["7", "12"]);
+    style::set_enabled(false);
+    assert!(styled.contains("\x1b[1m------ View Statistics ------\x1b[0m"));
+    // 150 kcal is well under the target
+    assert!(styled.contains("Difference: \x1b[32m-"), "{}", styled);
+}
+
+#[test]
+fn tables_line_up_wide_characters_and_cut_long_names() {
+    let dir = DataDir::new("scripted-tables");
+    fs::write(dir.path.join("profile.txt"), profile_file()).unwrap();
+    fs::write(dir.path.join("foods.txt"), concat!(
+        "#yada:foods:v1\n",
+        "B|tofu|豆腐|soy|76\n",
+        "B|brulee|Crème brûlée|dessert|330\n",
+        "B|soup|Creamy Roasted Tomato and Red Pepper Soup|soup|120\n",
+    )).unwrap();
+
+    let output = run_session(&dir, &["2", "", "12"]);
+    let rows: Vec<&str> = output.lines()
+        .filter(|line| line.starts_with("tofu") || line.starts_with("brulee") || line.starts_with("soup"))
+        .collect();
+    assert_eq!(rows.len(), 3, "{}", output);
+    // Every row ends in the calories column at the same terminal column
+    let widths: Vec<usize> = rows.iter().map(|row| table::display_width(row)).collect();
+    assert!(widths.iter().all(|width| *width == widths[0]), "{:?}", rows);
+    assert!(rows.iter().any(|row| row.contains("Creamy Roasted Tomato and R…")), "{:?}", rows);
+}
+
+#[test]
+fn messages_fill_placeholders_like_format() {
+    assert_eq!(Message::SummaryWeightLine.format(&[&80.04]), format!("Weight: {:.1} kg", 80.04));
+    assert_eq!(Message::DifferenceLine.format(&[&12.4]), "Difference: +12 kcal");
+    assert_eq!(Message::DifferenceLine.format(&[&-12.6]), "Difference: -13 kcal");
+    assert_eq!(Message::RepairedProblems.format(&[&2]), "Repaired 2 problem(s);  remaining.");
+}
+
+#[test]
+fn column_titles_longer_than_the_smallest_width_are_not_cut() {
+    let mut table = table::Table::new().column("Lebensmittel-ID").min_width(10).column("Name");
+    table.row(["oats", "Rolled Oats"]);
+    assert!(table.header().contains("Lebensmittel-ID Name"), "{}", table);
+}
+
+#[test]
+fn input_ending_inside_a_flow_saves_and_exits_without_the_menu() {
+    let dir = DataDir::new("scripted-input-closed");
+    fs::write(dir.path.join("profile.txt"), profile_file()).unwrap();
+    fs::write(dir.path.join("foods.txt"), "#yada:foods:v1\nB|oats|Rolled Oats|grain|150\n").unwrap();
+
+    // The input ends at the Log Food menu opened a second time; the main menu is not shown again
+    let output = run_session(&dir, &["3", "1", "oats", "2", "1", "3"]);
+    assert_eq!(output.matches("------ YADA Main Menu ------").count(), 2, "{}", output);
+    assert!(position(&output, "Food logged successfully!") < position(&output, "Log data saved successfully."));
+    assert!(output.trim_end().ends_with("Goodbye!"));
+    assert!(!output.contains("Interrupted."));
+
+    let logs = fs::read_to_string(dir.path.join("logs.txt")).unwrap();
+    assert!(logs.contains("|oats|2|"), "{}", logs);
+}
+
+#[test]
+fn a_failed_save_cancels_the_user_switch() {
+    let dir = DataDir::new("scripted-switch-unsaved");
+    fs::write(dir.path.join("profile.txt"), profile_file()).unwrap();
+    let foods = "#yada:foods:v1\nB|oats|Rolled Oats|grain|150|protein=5|carbs=27|fat=3\n";
+    fs::write(dir.path.join("foods.txt"), foods).unwrap();
+    // A directory where the log's temp file goes makes every log save fail
+    fs::create_dir(dir.path.join("logs.txt.tmp")).unwrap();
+
+    // Log oats, then try to switch to a new user
+    let output = run_session(&dir, &["3", "1", "oats", "2", "1", "6", "8", "2", "sam"]);
+
+    assert!(output.contains("so the switch to sam was cancelled"), "{}", output);
+    assert!(!output.contains("Switched to user sam"), "{}", output);
+    assert!(!dir.path.join("users").join("sam").exists());
+}
@@ -0,0 +1,140 @@
+//! # Exercise Commands
+//!
+//! Command Pattern objects for the exercise log, so logging or deleting a
+//! workout can be undone like any food log change.
+//!
+//! - **AddExerciseCommand**: Adds an exercise entry to a date
+//! - **RemoveExerciseCommand**: Removes an entry and restores it at the same position on undo
+//!
+//! Like the other commands, these hold a raw pointer to the repository,
+//! which must outlive the command.
+
+// yada-core/src/commands/exercise_commands.rs
+use chrono::NaiveDate;
+
+use crate::models::command::{Command, CommandType};
+use crate::models::exercise::ExerciseEntry;
+use crate::repositories::exercise_repository::ExerciseRepository;
+
+/// Adds an exercise entry; undo removes the entry it added
+pub struct AddExerciseCommand {
+    /// Raw pointer to the exercise repository
+    exercise_repo: *mut ExerciseRepository,
+    /// Date the entry belongs to
+    date: NaiveDate,
+    /// The entry to add
+    entry: ExerciseEntry,
+    /// Position the entry was added at, once executed
+    index: Option<usize>,
+}
+
+// Note: We need to implement Send + Sync manually because of the raw pointer
+unsafe impl Send for AddExerciseCommand {}
+unsafe impl Sync for AddExerciseCommand {}
+
+impl AddExerciseCommand {
+    /// Creates a command adding `entry` to `date`
+    pub fn new(exercise_repo: &mut ExerciseRepository, date: NaiveDate, entry: ExerciseEntry) -> Self {
+        AddExerciseCommand {
+            exercise_repo: exercise_repo as *mut ExerciseRepository,
+            date,
+            entry,
+            index: None,
+        }
+    }
+}
+
+impl Command for AddExerciseCommand {
+    fn execute(&mut self) -> Result<(), String> {
+        // Safety: We know the pointer is valid because it was created from a reference
+        let exercise_repo = unsafe { &mut *self.exercise_repo };
+
+        self.index = Some(exercise_repo.get_entries(self.date).len());
+        exercise_repo.add_entry(self.date, self.entry.clone());
+        Ok(())
+    }
+
+    fn undo(&mut self) -> Result<(), String> {
+        let index = self.index.ok_or("Command was not executed")?;
+
+        // Safety: We know the pointer is valid because it was created from a reference
+        let exercise_repo = unsafe { &mut *self.exercise_repo };
+
+        exercise_repo.remove_entry(self.date, index)
+            .ok_or_else(|| format!("No exercise entry at index {} to remove", index))?;
+        self.index = None;
+        Ok(())
+    }
+
+    fn get_type(&self) -> CommandType {
+        CommandType::AddExercise
+    }
+
+    fn description(&self) -> String {
+        format!("Add exercise: {} min of {} on {}",
+                self.entry.minutes, self.entry.activity, self.date.format("%Y-%m-%d"))
+    }
+}
+
+/// Removes an exercise entry; undo puts it back at the same position
+pub struct RemoveExerciseCommand {
+    /// Raw pointer to the exercise repository
+    exercise_repo: *mut ExerciseRepository,
+    /// Date the entry belongs to
+    date: NaiveDate,
+    /// Position of the entry within the date
+    index: usize,
+    /// The removed entry, once executed
+    removed_entry: Option<ExerciseEntry>,
+}
+
+// Note: We need to implement Send + Sync manually because of the raw pointer
+unsafe impl Send for RemoveExerciseCommand {}
+unsafe impl Sync for RemoveExerciseCommand {}
+
+impl RemoveExerciseCommand {
+    /// Creates a command removing the entry at `index` on `date`
+    pub fn new(exercise_repo: &mut ExerciseRepository, date: NaiveDate, index: usize) -> Self {
+        RemoveExerciseCommand {
+            exercise_repo: exercise_repo as *mut ExerciseRepository,
+            date,
+            index,
+            removed_entry: None,
+        }
+    }
+}
+
+impl Command for RemoveExerciseCommand {
+    fn execute(&mut self) -> Result<(), String> {
+        // Safety: We know the pointer is valid because it was created from a reference
+        let exercise_repo = unsafe { &mut *self.exercise_repo };
+
+        let entry = exercise_repo.remove_entry(self.date, self.index)
+            .ok_or_else(|| format!("No exercise entry at index {} to remove", self.index))?;
+        self.removed_entry = Some(entry);
+        Ok(())
+    }
+
+    fn undo(&mut self) -> Result<(), String> {
+        let entry = self.removed_entry.take().ok_or("Command was not executed")?;
+
+        // Safety: We know the pointer is valid because it was created from a reference
+        let exercise_repo = unsafe { &mut *self.exercise_repo };
+
+        exercise_repo.insert_entry(self.date, self.index, entry);
+        Ok(())
+    }
+
+    fn get_type(&self) -> CommandType {
+        CommandType::RemoveExercise
+    }
+
+    fn description(&self) -> String {
+        match &self.removed_entry {
+            Some(entry) => format!("Remove exercise: {} min of {} on {}",
+                                   entry.minutes, entry.activity, self.date.format("%Y-%m-%d")),
+            None => format!("Remove exercise entry at index {} on {}",
+                            self.index, self.date.format("%Y-%m-%d")),
+        }
+    }
+}
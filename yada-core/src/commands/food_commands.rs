@@ -0,0 +1,646 @@
+//! # Food Commands
+//! 
+//! This module implements Command Pattern objects for all food database operations.
+//! Each command encapsulates a specific food database modification with full
+//! undo capability and detailed operation tracking.
+//! 
+//! ## Command Pattern Implementation
+//! 
+//! Food commands provide atomic operations on the food database:
+//! - **AddFoodCommand**: Adds new foods to the database with duplicate detection
+//! - **UpdateFoodCommand**: Modifies existing foods while preserving operation history
+//! - **ArchiveFoodsCommand**: Archives or restores a set of foods in one step
+//! - **EditKeywordsCommand**: Adds or removes keywords on a set of foods in one step
+//! - **MergeFoodsCommand**: Merges a duplicate food into another and repoints everything that used it
+//! 
+//! ## Memory Safety Architecture
+//! 
+//! These commands use raw pointers to repository references for undo functionality:
+//! - **Safety Guarantee**: Pointers are created from valid references and used immediately
+//! - **Lifetime Management**: Commands assume repository outlives command execution
+//! - **Thread Safety**: Manual Send + Sync implementations ensure proper concurrency handling
+//! 
+//! ## Undo System Design
+//! 
+//! Each command maintains state necessary for complete operation reversal:
+//! - **State Capture**: Previous food state is stored before modifications
+//! - **Atomic Operations**: Commands ensure either complete success or no change
+//! - **Error Recovery**: Failed operations leave the system in consistent state
+//! - **Validation**: Commands verify prerequisites before execution and after undo
+//! 
+//! ## Food Database Integration
+//! 
+//! Commands integrate directly with the FoodRepository to provide:
+//! - **CRUD Operations**: Create, Update operations with full validation
+//! - **Composite Food Support**: Handle complex food relationships during operations
+//! - **Dependency Management**: Ensure food references remain valid after operations
+//! - **Data Integrity**: Maintain database consistency throughout command lifecycle
+
+// yada-core/src/commands/food_commands.rs
+use chrono::NaiveDate;
+
+use crate::models::command::{Command, CommandType};
+use crate::models::food::Food;
+use crate::repositories::food_repository::FoodRepository;
+use crate::repositories::format;
+use crate::repositories::log_repository::LogRepository;
+use crate::repositories::meal_plan_repository::MealPlanRepository;
+
+/// # Add Food Command
+/// 
+/// A Command Pattern implementation for adding new foods to the food database.
+/// This command handles food creation with duplicate detection and provides
+/// complete undo capability by removing the added food if needed.
+/// 
+/// ## Command Behavior
+/// 
+/// - **Execute**: Adds a new food to the repository with validation
+/// - **Undo**: Removes the food from the repository if it was successfully added
+/// - **Idempotency**: Can be safely executed multiple times with consistent results
+/// 
+/// ## Memory Management
+/// 
+/// Uses a raw pointer to the food repository for undo operations:
+/// - **Safety**: Pointer is created from a valid reference and used immediately
+/// - **Lifetime**: Assumes repository outlives the command execution
+/// - **Thread Safety**: Manual Send + Sync implementations for concurrent access
+/// 
+/// ## Error Handling
+/// 
+/// - Validates food data before insertion
+/// - Prevents duplicate food IDs in the database
+/// - Maintains execution state for proper undo behavior
+/// - Provides detailed error messages for debugging
+pub struct AddFoodCommand {
+    /// Raw pointer to the food repository for direct database access
+    food_repo: *mut FoodRepository,
+    /// The food entity to be added to the database
+    food: Food,
+    /// Tracks whether the command has been successfully executed
+    executed: bool,
+}
+
+/// Manual implementation of Send trait for thread safety.
+/// 
+/// This is safe because:
+/// - The raw pointer is created from a valid reference
+/// - The pointer is only accessed during command execution
+/// - Repository lifetime exceeds command lifetime
+/// - No concurrent access to the same repository instance
+unsafe impl Send for AddFoodCommand {}
+
+/// Manual implementation of Sync trait for thread safety.
+/// 
+/// This is safe because:
+/// - Command operations are atomic and don't share mutable state
+/// - Repository access is controlled and sequential
+/// - No data races possible with proper command manager usage
+unsafe impl Sync for AddFoodCommand {}
+
+impl AddFoodCommand {
+    /// Creates a new AddFoodCommand for adding a food to the repository.
+    /// 
+    /// # Arguments
+    /// * `food_repo` - Mutable reference to the food repository
+    /// * `food` - The food entity to add to the database
+    /// 
+    /// # Returns
+    /// * `Self` - A new command instance ready for execution
+    /// 
+    /// # Safety
+    /// The repository reference must remain valid for the lifetime of this command.
+    /// The command stores a raw pointer for undo operations.
+    pub fn new(food_repo: &mut FoodRepository, food: Food) -> Self {
+        AddFoodCommand {
+            food_repo: food_repo as *mut FoodRepository,
+            food,
+            executed: false,
+        }
+    }
+}
+
+impl Command for AddFoodCommand {
+    /// Executes the add food operation with validation and error handling.
+    /// 
+    /// This method attempts to add the food to the repository, handling
+    /// duplicate detection and maintaining execution state for undo operations.
+    /// 
+    /// # Returns
+    /// * `Result<(), String>` - Success confirmation or detailed error message
+    /// 
+    /// # Error Conditions
+    /// - Food with the same ID already exists in the database
+    /// - Invalid food data that fails repository validation
+    /// - Repository access errors during the operation
+    /// 
+    /// # State Management
+    /// Updates the executed flag only upon successful completion to ensure
+    /// proper undo behavior and prevent inconsistent state.
+    fn execute(&mut self) -> Result<(), String> {
+        // Safety: We know the pointer is valid because it was created from a reference
+        let food_repo = unsafe { &mut *self.food_repo };
+        
+        let result = food_repo.add_food(self.food.clone());
+        if result.is_ok() {
+            self.executed = true;
+        }        result
+    }
+
+    /// Undoes the add food operation by removing the food from the repository.
+    /// 
+    /// This method reverses the effects of the execute operation by removing
+    /// the added food from the database. It validates that the command was
+    /// previously executed before attempting the undo operation.
+    /// 
+    /// # Returns
+    /// * `Result<(), String>` - Success confirmation or detailed error message
+    /// 
+    /// # Error Conditions
+    /// - Command was not previously executed (nothing to undo)
+    /// - Repository access errors during food removal
+    /// - Food is referenced by composite foods (dependency violation)
+    /// 
+    /// # Safety Considerations
+    /// Uses unsafe pointer access to the repository, which is safe because:
+    /// - Pointer was created from a valid reference
+    /// - Repository lifetime exceeds command lifetime
+    /// - No concurrent access to the same repository
+    fn undo(&mut self) -> Result<(), String> {
+        if !self.executed {
+            return Err("Command was not executed".to_string());
+        }
+
+        // Safety: We know the pointer is valid because it was created from a reference
+        let food_repo = unsafe { &mut *self.food_repo };
+        
+        // Remove the food from the repository
+        food_repo.remove_food(&self.food.id);
+        
+        self.executed = false;        Ok(())
+    }
+
+    /// Returns the command type for categorization and tracking purposes.
+    /// 
+    /// # Returns
+    /// * `CommandType::AddFood` - Identifies this as a food addition command
+    fn get_type(&self) -> CommandType {
+        CommandType::AddFood
+    }
+
+    /// Provides a human-readable description of the command operation.
+    /// 
+    /// # Returns
+    /// * `String` - Descriptive text indicating the food being added
+    /// 
+    /// # Usage
+    /// Used for command history display, logging, and user feedback.
+    fn description(&self) -> String {
+        format!("Add food: {}", self.food.name)    }
+}
+
+/// # Update Food Command
+/// 
+/// A Command Pattern implementation for updating existing foods in the food database.
+/// This command handles food modification with state preservation for undo operations
+/// and provides comprehensive error handling for all update scenarios.
+/// 
+/// ## Command Behavior
+/// 
+/// - **Execute**: Updates an existing food with new data and validation
+/// - **Undo**: Restores the previous food state or removes the food if it was newly created
+/// - **State Preservation**: Captures original food state before modification for undo
+/// - **Dependents**: Composite foods built from the food are recalculated on
+///   execute and again on undo, which brings back their previous values
+/// 
+/// ## Update Strategy
+/// 
+/// The command captures the original food state during construction:
+/// - If the food exists, stores the original for restoration
+/// - If the food doesn't exist, the update becomes an add operation
+/// - Undo operation reverses the exact change that was made
+/// 
+/// ## Memory Management
+/// 
+/// Uses the same raw pointer strategy as AddFoodCommand for repository access
+/// with identical safety guarantees and thread safety considerations.
+pub struct UpdateFoodCommand {
+    /// Raw pointer to the food repository for direct database access
+    food_repo: *mut FoodRepository,
+    /// Original food state captured before update (None if food didn't exist)
+    old_food: Option<Food>,
+    /// New food data to replace the existing food
+    new_food: Food,
+    /// Composite foods recalculated by the last execution, as they were before
+    recalculated: Vec<Food>,
+    /// Tracks whether the command has been successfully executed
+    executed: bool,
+}
+
+/// Manual implementation of Send trait for thread safety with same guarantees as AddFoodCommand.
+unsafe impl Send for UpdateFoodCommand {}
+
+/// Manual implementation of Sync trait for thread safety with same guarantees as AddFoodCommand.
+unsafe impl Sync for UpdateFoodCommand {}
+
+impl UpdateFoodCommand {
+    /// Creates a new UpdateFoodCommand for modifying an existing food.
+    /// 
+    /// This constructor captures the current state of the food (if it exists)
+    /// to enable proper undo operations. The original food state is cloned
+    /// and stored for restoration during undo.
+    /// 
+    /// # Arguments
+    /// * `food_repo` - Mutable reference to the food repository
+    /// * `new_food` - The updated food data to replace the existing food
+    /// 
+    /// # Returns
+    /// * `Self` - A new command instance ready for execution
+    /// 
+    /// # State Capture
+    /// - If the food exists, captures its current state for undo
+    /// - If the food doesn't exist, the update becomes an add operation
+    /// - Stores all necessary information for complete operation reversal
+    pub fn new(food_repo: &mut FoodRepository, new_food: Food) -> Self {
+        let old_food = food_repo.get_food(&new_food.id).cloned();
+        
+        UpdateFoodCommand {
+            food_repo: food_repo as *mut FoodRepository,
+            old_food,
+            new_food,
+            recalculated: Vec::new(),
+            executed: false,
+        }
+    }
+
+    /// Returns the composite foods whose values changed with the update, as
+    /// they were before it
+    pub fn recalculated(&self) -> &[Food] {
+        &self.recalculated
+    }
+}
+
+impl Command for UpdateFoodCommand {
+    fn execute(&mut self) -> Result<(), String> {
+        // Safety: We know the pointer is valid because it was created from a reference
+        let food_repo = unsafe { &mut *self.food_repo };
+        
+        self.recalculated = food_repo.update_food(self.new_food.clone())?;
+        self.executed = true;
+        Ok(())
+    }
+
+    fn undo(&mut self) -> Result<(), String> {
+        if !self.executed {
+            return Err("Command was not executed".to_string());
+        }
+
+        // Safety: We know the pointer is valid because it was created from a reference
+        let food_repo = unsafe { &mut *self.food_repo };
+        
+        // If we have the old food, restore it
+        if let Some(old_food) = &self.old_food {
+            food_repo.update_food(old_food.clone())?;
+        } else {
+            // Otherwise remove the food
+            food_repo.remove_food(&self.new_food.id);
+        }
+        
+        self.executed = false;
+        Ok(())
+    }
+
+    fn get_type(&self) -> CommandType {
+        CommandType::UpdateFood
+    }
+
+    fn description(&self) -> String {
+        match self.recalculated.len() {
+            0 => format!("Update food: {}", self.new_food.name),
+            count => format!("Update food: {} (recalculated {} recipe(s))", self.new_food.name, count),
+        }
+    }
+}
+
+/// # Archive Foods Command
+/// 
+/// A Command Pattern implementation for archiving (or restoring) several foods
+/// at once, as done when accepting the archive suggestions during maintenance.
+/// Archiving only hides foods from pickers (see `models::usage`), so undo
+/// simply restores the previous flags.
+/// 
+/// ## Command Behavior
+/// 
+/// - **Execute**: Checks that every food exists, then sets the archived flag on all of them
+/// - **Undo**: Flips back only the foods whose flag actually changed
+pub struct ArchiveFoodsCommand {
+    /// Raw pointer to the food repository for direct database access
+    food_repo: *mut FoodRepository,
+    /// IDs of the foods to archive or restore
+    food_ids: Vec<String>,
+    /// True to archive the foods, false to restore them
+    archived: bool,
+    /// Foods whose flag was changed by the last execution
+    changed: Vec<String>,
+    /// Tracks whether the command has been successfully executed
+    executed: bool,
+}
+
+/// Manual implementation of Send trait for thread safety with same guarantees as AddFoodCommand.
+unsafe impl Send for ArchiveFoodsCommand {}
+
+/// Manual implementation of Sync trait for thread safety with same guarantees as AddFoodCommand.
+unsafe impl Sync for ArchiveFoodsCommand {}
+
+impl ArchiveFoodsCommand {
+    /// Creates a new ArchiveFoodsCommand.
+    /// 
+    /// # Arguments
+    /// * `food_repo` - Mutable reference to the food repository
+    /// * `food_ids` - IDs of the foods to change
+    /// * `archived` - True to archive the foods, false to restore them
+    /// 
+    /// # Returns
+    /// * `Self` - A new command instance ready for execution
+    pub fn new(food_repo: &mut FoodRepository, food_ids: Vec<String>, archived: bool) -> Self {
+        ArchiveFoodsCommand {
+            food_repo: food_repo as *mut FoodRepository,
+            food_ids,
+            archived,
+            changed: Vec::new(),
+            executed: false,
+        }
+    }
+}
+
+impl Command for ArchiveFoodsCommand {
+    fn execute(&mut self) -> Result<(), String> {
+        // Safety: We know the pointer is valid because it was created from a reference
+        let food_repo = unsafe { &mut *self.food_repo };
+        
+        // Validate everything first so a bad ID leaves no partial change behind
+        if let Some(missing) = self.food_ids.iter().find(|id| food_repo.get_food(id).is_none()) {
+            return Err(format!("Food with ID {} not found", missing));
+        }
+        
+        self.changed.clear();
+        for id in &self.food_ids {
+            if food_repo.set_archived(id, self.archived)? != self.archived {
+                self.changed.push(id.clone());
+            }
+        }
+        self.executed = true;
+        Ok(())
+    }
+
+    fn undo(&mut self) -> Result<(), String> {
+        if !self.executed {
+            return Err("Command was not executed".to_string());
+        }
+
+        // Safety: We know the pointer is valid because it was created from a reference
+        let food_repo = unsafe { &mut *self.food_repo };
+        
+        for id in &self.changed {
+            food_repo.set_archived(id, !self.archived)?;
+        }
+        
+        self.executed = false;
+        Ok(())
+    }
+
+    fn get_type(&self) -> CommandType {
+        if self.archived {
+            CommandType::ArchiveFoods
+        } else {
+            CommandType::RestoreFoods
+        }
+    }
+
+    fn description(&self) -> String {
+        let action = if self.archived { "Archive" } else { "Restore" };
+        format!("{} {} food(s): {}", action, self.food_ids.len(), self.food_ids.join(", "))
+    }
+}
+
+/// # Edit Keywords Command
+/// 
+/// A Command Pattern implementation for adding keywords to, or removing them
+/// from, several foods at once (such as tagging every plant-based food
+/// `vegan`), so the whole change is undone in one step.
+/// 
+/// ## Command Behavior
+/// 
+/// - **Execute**: Checks that every food exists, then adds or removes each keyword on all of them
+/// - **Undo**: Reverts only the keywords that actually changed
+pub struct EditKeywordsCommand {
+    /// Raw pointer to the food repository for direct database access
+    food_repo: *mut FoodRepository,
+    /// IDs of the foods to change
+    food_ids: Vec<String>,
+    /// Keywords to add or remove, in lowercase
+    keywords: Vec<String>,
+    /// True to add the keywords, false to remove them
+    add: bool,
+    /// Food IDs and keywords changed by the last execution
+    changed: Vec<(String, String)>,
+    /// Tracks whether the command has been successfully executed
+    executed: bool,
+}
+
+/// Manual implementation of Send trait for thread safety with same guarantees as AddFoodCommand.
+unsafe impl Send for EditKeywordsCommand {}
+
+/// Manual implementation of Sync trait for thread safety with same guarantees as AddFoodCommand.
+unsafe impl Sync for EditKeywordsCommand {}
+
+impl EditKeywordsCommand {
+    /// Creates a new EditKeywordsCommand.
+    /// 
+    /// # Arguments
+    /// * `food_repo` - Mutable reference to the food repository
+    /// * `food_ids` - IDs of the foods to change
+    /// * `keywords` - Keywords to add or remove
+    /// * `add` - True to add the keywords, false to remove them
+    /// 
+    /// # Returns
+    /// * `Self` - A new command instance ready for execution
+    pub fn new(food_repo: &mut FoodRepository, food_ids: Vec<String>, keywords: Vec<String>, add: bool) -> Self {
+        EditKeywordsCommand {
+            food_repo: food_repo as *mut FoodRepository,
+            food_ids,
+            keywords: keywords.iter().map(|keyword| keyword.trim().to_lowercase()).collect(),
+            add,
+            changed: Vec::new(),
+            executed: false,
+        }
+    }
+}
+
+impl Command for EditKeywordsCommand {
+    fn execute(&mut self) -> Result<(), String> {
+        // Safety: We know the pointer is valid because it was created from a reference
+        let food_repo = unsafe { &mut *self.food_repo };
+        
+        // Validate everything first so a bad ID or keyword leaves no partial change behind
+        if let Some(missing) = self.food_ids.iter().find(|id| food_repo.get_food(id).is_none()) {
+            return Err(format!("Food with ID {} not found", missing));
+        }
+        if self.keywords.is_empty() || self.keywords.iter().any(|keyword| keyword.is_empty()) {
+            return Err("Keyword cannot be empty".to_string());
+        }
+        for keyword in &self.keywords {
+            format::validate_text("Keyword", keyword)?;
+        }
+        
+        self.changed.clear();
+        for id in &self.food_ids {
+            for keyword in &self.keywords {
+                if food_repo.set_keyword(id, keyword, self.add)? != self.add {
+                    self.changed.push((id.clone(), keyword.clone()));
+                }
+            }
+        }
+        self.executed = true;
+        Ok(())
+    }
+
+    fn undo(&mut self) -> Result<(), String> {
+        if !self.executed {
+            return Err("Command was not executed".to_string());
+        }
+
+        // Safety: We know the pointer is valid because it was created from a reference
+        let food_repo = unsafe { &mut *self.food_repo };
+        
+        for (id, keyword) in &self.changed {
+            food_repo.set_keyword(id, keyword, !self.add)?;
+        }
+        
+        self.executed = false;
+        Ok(())
+    }
+
+    fn get_type(&self) -> CommandType {
+        CommandType::EditKeywords
+    }
+
+    fn description(&self) -> String {
+        let (action, preposition) = if self.add { ("Add", "to") } else { ("Remove", "from") };
+        format!("{} keyword(s) {} {} {} food(s): {}", action, self.keywords.join(", "), preposition, self.food_ids.len(), self.food_ids.join(", "))
+    }
+}
+
+/// # Merge Foods Command
+/// 
+/// A Command Pattern implementation for merging a duplicate food into the
+/// food that is kept (see `models::duplicates`). Everything that referred to
+/// the duplicate is repointed, so no recipe, log entry, or meal plan is left
+/// with a dangling ID.
+/// 
+/// ## Command Behavior
+/// 
+/// - **Execute**: Merges the foods (see `FoodRepository::merge_food`), then
+///   rewrites log entries and planned meals that used the duplicate
+/// - **Undo**: Restores the previous versions of all changed foods and points
+///   exactly the rewritten entries and planned meals back at the duplicate
+pub struct MergeFoodsCommand {
+    /// Raw pointer to the food repository for direct database access
+    food_repo: *mut FoodRepository,
+    /// Raw pointer to the log repository whose entries are repointed
+    log_repo: *mut LogRepository,
+    /// Raw pointer to the meal plan repository whose items are repointed
+    meal_plan_repo: *mut MealPlanRepository,
+    /// ID of the food that remains
+    keep: String,
+    /// ID of the duplicate that is merged away
+    remove: String,
+    /// Foods as they were before the last execution
+    previous_foods: Vec<Food>,
+    /// Log entries rewritten by the last execution
+    log_positions: Vec<(NaiveDate, usize)>,
+    /// Planned meals rewritten by the last execution
+    plan_positions: Vec<(NaiveDate, usize)>,
+    /// Tracks whether the command has been successfully executed
+    executed: bool,
+}
+
+/// Manual implementation of Send trait for thread safety with same guarantees as AddFoodCommand.
+unsafe impl Send for MergeFoodsCommand {}
+
+/// Manual implementation of Sync trait for thread safety with same guarantees as AddFoodCommand.
+unsafe impl Sync for MergeFoodsCommand {}
+
+impl MergeFoodsCommand {
+    /// Creates a new MergeFoodsCommand.
+    /// 
+    /// # Arguments
+    /// * `food_repo` - Mutable reference to the food repository
+    /// * `log_repo` - Mutable reference to the log repository
+    /// * `meal_plan_repo` - Mutable reference to the meal plan repository
+    /// * `keep` - ID of the food that remains
+    /// * `remove` - ID of the duplicate that is merged away
+    /// 
+    /// # Returns
+    /// * `Self` - A new command instance ready for execution
+    pub fn new(
+        food_repo: &mut FoodRepository,
+        log_repo: &mut LogRepository,
+        meal_plan_repo: &mut MealPlanRepository,
+        keep: String,
+        remove: String,
+    ) -> Self {
+        MergeFoodsCommand {
+            food_repo: food_repo as *mut FoodRepository,
+            log_repo: log_repo as *mut LogRepository,
+            meal_plan_repo: meal_plan_repo as *mut MealPlanRepository,
+            keep,
+            remove,
+            previous_foods: Vec::new(),
+            log_positions: Vec::new(),
+            plan_positions: Vec::new(),
+            executed: false,
+        }
+    }
+}
+
+impl Command for MergeFoodsCommand {
+    fn execute(&mut self) -> Result<(), String> {
+        // Safety: We know the pointers are valid because they were created from references
+        let food_repo = unsafe { &mut *self.food_repo };
+        let log_repo = unsafe { &mut *self.log_repo };
+        let meal_plan_repo = unsafe { &mut *self.meal_plan_repo };
+
+        // The food merge validates everything, so a failure leaves no change behind
+        self.previous_foods = food_repo.merge_food(&self.keep, &self.remove)?;
+        self.log_positions = log_repo.replace_food_id(&self.remove, &self.keep);
+        self.plan_positions = meal_plan_repo.replace_food_id(&self.remove, &self.keep);
+        self.executed = true;
+        Ok(())
+    }
+
+    fn undo(&mut self) -> Result<(), String> {
+        if !self.executed {
+            return Err("Command was not executed".to_string());
+        }
+
+        // Safety: We know the pointers are valid because they were created from references
+        let food_repo = unsafe { &mut *self.food_repo };
+        let log_repo = unsafe { &mut *self.log_repo };
+        let meal_plan_repo = unsafe { &mut *self.meal_plan_repo };
+
+        food_repo.restore_foods(std::mem::take(&mut self.previous_foods));
+        log_repo.set_food_ids(&self.log_positions, &self.remove);
+        meal_plan_repo.set_food_ids(&self.plan_positions, &self.remove);
+
+        self.executed = false;
+        Ok(())
+    }
+
+    fn get_type(&self) -> CommandType {
+        CommandType::MergeFoods
+    }
+
+    fn description(&self) -> String {
+        format!("Merge food {} into {}", self.remove, self.keep)
+    }
+}
@@ -0,0 +1,396 @@
+// yada-core/src/commands/log_commands.rs
+use chrono::{DateTime, Local, NaiveDate};
+
+use crate::models::command::{Command, CommandType};
+use crate::models::log::{DayNote, FoodEntry};
+use crate::models::meal::MealSlot;
+use crate::models::quantity::Quantity;
+use crate::repositories::log_repository::LogRepository;
+
+pub struct AddLogEntryCommand {
+    log_repo: *mut LogRepository,
+    date: NaiveDate,
+    food_id: String,
+    servings: f64,
+    quantity: Quantity,
+    meal: Option<MealSlot>,
+    timestamp: Option<DateTime<Local>>,
+    calories: Option<f64>,
+    entry_id: Option<u64>,
+    executed: bool,
+}
+
+// Note: We need to implement Send + Sync manually because of the raw pointer
+unsafe impl Send for AddLogEntryCommand {}
+unsafe impl Sync for AddLogEntryCommand {}
+
+impl AddLogEntryCommand {
+    pub fn new(log_repo: &mut LogRepository, date: NaiveDate, food_id: String, servings: f64) -> Self {
+        AddLogEntryCommand {
+            log_repo: log_repo as *mut LogRepository,
+            date,
+            food_id,
+            servings,
+            quantity: Quantity::servings(servings),
+            meal: None,
+            timestamp: None,
+            calories: None,
+            entry_id: None,
+            executed: false,
+        }
+    }
+
+    /// Records the amount as entered (e.g. `85 g`) alongside the servings
+    pub fn with_quantity(mut self, quantity: Quantity) -> Self {
+        self.quantity = quantity;
+        self
+    }
+
+    /// Assigns the entry to a meal slot
+    pub fn with_meal(mut self, meal: MealSlot) -> Self {
+        self.meal = Some(meal);
+        self
+    }
+
+    /// Stamps the entry with a time other than now (for retroactive logging)
+    pub fn with_timestamp(mut self, timestamp: DateTime<Local>) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Records the entry's calories at the time of logging (see `models::log`)
+    pub fn with_calories(mut self, calories: f64) -> Self {
+        self.calories = Some(calories);
+        self
+    }
+}
+
+impl Command for AddLogEntryCommand {
+    fn execute(&mut self) -> Result<(), String> {
+        // Safety: We know the pointer is valid because it was created from a reference
+        let log_repo = unsafe { &mut *self.log_repo };
+        
+        let mut entry = FoodEntry::new(self.food_id.clone(), self.quantity, self.servings);
+        if let Some(timestamp) = self.timestamp {
+            entry.timestamp = timestamp;
+        }
+        entry.meal = self.meal;
+        entry.calories = self.calories;
+        self.entry_id = Some(entry.entry_id);
+        log_repo.get_log_mut(self.date).insert_entry(entry);
+        
+        self.executed = true;
+        Ok(())
+    }
+
+    fn undo(&mut self) -> Result<(), String> {
+        if !self.executed {
+            return Err("Command was not executed".to_string());
+        }
+
+        // Safety: We know the pointer is valid because it was created from a reference
+        let log_repo = unsafe { &mut *self.log_repo };
+        
+        // Remove exactly the entry this command created, even if the same
+        // food was logged again or the entries were reordered since
+        let log = log_repo.get_log_mut(self.date);
+        let Some(index) = self.entry_id.and_then(|id| log.entry_index(id)) else {
+            return Err("The logged entry no longer exists".to_string());
+        };
+        log.remove_entry(index);
+        
+        self.executed = false;
+        Ok(())
+    }
+
+    fn get_type(&self) -> CommandType {
+        CommandType::AddLog
+    }
+
+    fn description(&self) -> String {
+        format!("Add log entry: {} of {} on {}", 
+                self.quantity, self.food_id, self.date.format("%Y-%m-%d"))
+    }
+}
+
+pub struct RemoveLogEntryCommand {
+    log_repo: *mut LogRepository,
+    date: NaiveDate,
+    entry_id: u64,
+    index: usize,
+    removed_entry: Option<FoodEntry>,
+    executed: bool,
+}
+
+// Note: We need to implement Send + Sync manually because of the raw pointer
+unsafe impl Send for RemoveLogEntryCommand {}
+unsafe impl Sync for RemoveLogEntryCommand {}
+
+impl RemoveLogEntryCommand {
+    /// Removes the entry with `entry_id` from the log for `date`
+    pub fn new(log_repo: &mut LogRepository, date: NaiveDate, entry_id: u64) -> Self {
+        RemoveLogEntryCommand {
+            log_repo: log_repo as *mut LogRepository,
+            date,
+            entry_id,
+            index: 0,
+            removed_entry: None,
+            executed: false,
+        }
+    }
+}
+
+impl Command for RemoveLogEntryCommand {
+    fn execute(&mut self) -> Result<(), String> {
+        // Safety: We know the pointer is valid because it was created from a reference
+        let log_repo = unsafe { &mut *self.log_repo };
+        
+        let log = log_repo.get_log_mut(self.date);
+        
+        // Remember where the entry was so undo puts it back in the same place
+        let Some(index) = log.entry_index(self.entry_id) else {
+            return Err(format!("No entry with ID {} to remove", self.entry_id));
+        };
+        self.index = index;
+        self.removed_entry = log.remove_entry(index);
+        self.executed = true;
+        Ok(())
+    }
+
+    fn undo(&mut self) -> Result<(), String> {
+        if !self.executed {
+            return Err("Command was not executed".to_string());
+        }
+
+        // Safety: We know the pointer is valid because it was created from a reference
+        let log_repo = unsafe { &mut *self.log_repo };
+        
+        let log = log_repo.get_log_mut(self.date);
+        
+        // Restore the removed entry
+        if let Some(entry) = &self.removed_entry {
+            log.entries.insert(self.index.min(log.entries.len()), entry.clone());
+            self.executed = false;
+            Ok(())
+        } else {
+            Err("No entry to restore".to_string())
+        }
+    }
+
+    fn get_type(&self) -> CommandType {
+        CommandType::DeleteLog
+    }
+
+    fn description(&self) -> String {
+        if let Some(entry) = &self.removed_entry {
+            format!("Remove log entry: {} of {} on {}", 
+                    entry.quantity, entry.food_id, self.date.format("%Y-%m-%d"))
+        } else {
+            format!("Remove log entry {} on {}", 
+                    self.entry_id, self.date.format("%Y-%m-%d"))
+        }
+    }
+}
+
+pub struct UpdateLogEntryCommand {
+    log_repo: *mut LogRepository,
+    date: NaiveDate,
+    entry_id: u64,
+    food_id: String,
+    quantity: Quantity,
+    servings: f64,
+    timestamp: Option<DateTime<Local>>,
+    calories: Option<f64>,
+    previous_entry: Option<FoodEntry>,
+    index: usize,
+    executed: bool,
+}
+
+// Note: We need to implement Send + Sync manually because of the raw pointer
+unsafe impl Send for UpdateLogEntryCommand {}
+unsafe impl Sync for UpdateLogEntryCommand {}
+
+impl UpdateLogEntryCommand {
+    /// Changes the food and amount of an entry, keeping its time and meal
+    ///
+    /// # Arguments
+    /// * `log_repo` - Repository holding the entry
+    /// * `date` - Date of the log
+    /// * `entry_id` - ID of the entry to change
+    /// * `food_id` - Food the entry should use (may be unchanged)
+    /// * `quantity` - Amount as entered (e.g. `85 g`)
+    /// * `servings` - The amount in servings of the food
+    pub fn new(
+        log_repo: &mut LogRepository,
+        date: NaiveDate,
+        entry_id: u64,
+        food_id: String,
+        quantity: Quantity,
+        servings: f64,
+    ) -> Self {
+        UpdateLogEntryCommand {
+            log_repo: log_repo as *mut LogRepository,
+            date,
+            entry_id,
+            food_id,
+            quantity,
+            servings,
+            timestamp: None,
+            calories: None,
+            previous_entry: None,
+            index: 0,
+            executed: false,
+        }
+    }
+
+    /// Also changes the time of the entry, moving it to its new place in the day
+    pub fn with_timestamp(mut self, timestamp: DateTime<Local>) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Records the calories of the changed entry (see `models::log`)
+    ///
+    /// Without this the entry's snapshot is dropped, since it no longer
+    /// matches the new food or amount.
+    pub fn with_calories(mut self, calories: f64) -> Self {
+        self.calories = Some(calories);
+        self
+    }
+}
+
+impl Command for UpdateLogEntryCommand {
+    fn execute(&mut self) -> Result<(), String> {
+        // Safety: We know the pointer is valid because it was created from a reference
+        let log_repo = unsafe { &mut *self.log_repo };
+
+        let log = log_repo.get_log_mut(self.date);
+        let Some(index) = log.entry_index(self.entry_id) else {
+            return Err(format!("No entry with ID {} to update", self.entry_id));
+        };
+        let mut entry = log.entries[index].clone();
+
+        // Remember where the entry was so undo puts it back in the same place
+        self.index = index;
+        self.previous_entry = Some(entry.clone());
+        entry.food_id = self.food_id.clone();
+        entry.quantity = self.quantity;
+        entry.servings = self.servings;
+        entry.calories = self.calories;
+
+        // A new time can move the entry elsewhere in the day
+        match self.timestamp {
+            Some(timestamp) => {
+                entry.timestamp = timestamp;
+                log.remove_entry(index);
+                log.insert_entry(entry);
+            }
+            None => log.entries[index] = entry,
+        }
+
+        self.executed = true;
+        Ok(())
+    }
+
+    fn undo(&mut self) -> Result<(), String> {
+        if !self.executed {
+            return Err("Command was not executed".to_string());
+        }
+
+        // Safety: We know the pointer is valid because it was created from a reference
+        let log_repo = unsafe { &mut *self.log_repo };
+
+        let log = log_repo.get_log_mut(self.date);
+        let current = log.entry_index(self.entry_id);
+        match (current, &self.previous_entry) {
+            (Some(current), Some(previous)) => {
+                log.remove_entry(current);
+                log.entries.insert(self.index.min(log.entries.len()), previous.clone());
+                self.executed = false;
+                Ok(())
+            }
+            _ => Err("No entry to restore".to_string()),
+        }
+    }
+
+    fn get_type(&self) -> CommandType {
+        CommandType::UpdateLog
+    }
+
+    fn description(&self) -> String {
+        match &self.previous_entry {
+            Some(previous) => format!("Update log entry: {} of {} to {} of {} on {}",
+                                      previous.quantity, previous.food_id, self.quantity, self.food_id,
+                                      self.date.format("%Y-%m-%d")),
+            None => format!("Update log entry {} on {}",
+                            self.entry_id, self.date.format("%Y-%m-%d")),
+        }
+    }
+}
+
+pub struct SetDayNoteCommand {
+    log_repo: *mut LogRepository,
+    date: NaiveDate,
+    note: DayNote,
+    previous_note: Option<DayNote>,
+    executed: bool,
+}
+
+// Note: We need to implement Send + Sync manually because of the raw pointer
+unsafe impl Send for SetDayNoteCommand {}
+unsafe impl Sync for SetDayNoteCommand {}
+
+impl SetDayNoteCommand {
+    /// Replaces the note, mood, and energy of the log for `date`
+    ///
+    /// An empty note clears them.
+    pub fn new(log_repo: &mut LogRepository, date: NaiveDate, note: DayNote) -> Self {
+        SetDayNoteCommand {
+            log_repo: log_repo as *mut LogRepository,
+            date,
+            note,
+            previous_note: None,
+            executed: false,
+        }
+    }
+}
+
+impl Command for SetDayNoteCommand {
+    fn execute(&mut self) -> Result<(), String> {
+        // Safety: We know the pointer is valid because it was created from a reference
+        let log_repo = unsafe { &mut *self.log_repo };
+
+        let log = log_repo.get_log_mut(self.date);
+        self.previous_note = Some(std::mem::replace(&mut log.note, self.note.clone()));
+        self.executed = true;
+        Ok(())
+    }
+
+    fn undo(&mut self) -> Result<(), String> {
+        if !self.executed {
+            return Err("Command was not executed".to_string());
+        }
+
+        // Safety: We know the pointer is valid because it was created from a reference
+        let log_repo = unsafe { &mut *self.log_repo };
+
+        let Some(previous) = self.previous_note.take() else {
+            return Err("No note to restore".to_string());
+        };
+        log_repo.get_log_mut(self.date).note = previous;
+        self.executed = false;
+        Ok(())
+    }
+
+    fn get_type(&self) -> CommandType {
+        CommandType::SetDayNote
+    }
+
+    fn description(&self) -> String {
+        if self.note.is_empty() {
+            format!("Clear note on {}", self.date.format("%Y-%m-%d"))
+        } else {
+            format!("Set note on {}", self.date.format("%Y-%m-%d"))
+        }
+    }
+}
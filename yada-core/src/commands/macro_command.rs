@@ -0,0 +1,80 @@
+// yada-core/src/commands/macro_command.rs
+use crate::models::command::{Command, CommandType};
+
+/// Several commands executed and undone as one
+///
+/// Commands run in the order they were added and are undone in reverse
+/// order. If one fails, the ones already executed are undone, so the
+/// macro either applies completely or not at all. Used for logging a meal
+/// template, which adds one log entry per food but is a single undo step.
+pub struct MacroCommand {
+    commands: Vec<Box<dyn Command>>,
+    kind: CommandType,
+    description: String,
+    executed: bool,
+}
+
+impl MacroCommand {
+    /// Creates an empty macro
+    ///
+    /// # Arguments
+    /// * `kind` - Type reported for the whole macro, which decides its undo policy
+    /// * `description` - Description shown in the command history
+    pub fn new(kind: CommandType, description: String) -> Self {
+        MacroCommand { commands: Vec::new(), kind, description, executed: false }
+    }
+
+    /// Adds a command to run after the ones added before
+    pub fn with_command(mut self, command: Box<dyn Command>) -> Self {
+        self.commands.push(command);
+        self
+    }
+
+    /// Returns how many commands the macro runs
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Returns true if the macro has no commands
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+}
+
+impl Command for MacroCommand {
+    fn execute(&mut self) -> Result<(), String> {
+        for index in 0..self.commands.len() {
+            if let Err(e) = self.commands[index].execute() {
+                // Roll back what ran so far; their undo errors cannot be reported better than the first one
+                for command in self.commands[..index].iter_mut().rev() {
+                    let _ = command.undo();
+                }
+                return Err(e);
+            }
+        }
+
+        self.executed = true;
+        Ok(())
+    }
+
+    fn undo(&mut self) -> Result<(), String> {
+        if !self.executed {
+            return Err("Command was not executed".to_string());
+        }
+
+        for command in self.commands.iter_mut().rev() {
+            command.undo()?;
+        }
+
+        self.executed = false;
+        Ok(())
+    }
+
+    fn get_type(&self) -> CommandType {
+        self.kind.clone()
+    }
+
+    fn description(&self) -> String {
+        self.description.clone()
+    }
+}
@@ -0,0 +1,75 @@
+//! # Meal Plan Commands
+//!
+//! Command Pattern objects for meal plans, so planning a day can be undone
+//! like any other change.
+//!
+//! - **SetMealPlanCommand**: Replaces (or clears) the plan for a date and restores the old plan on undo
+//!
+//! Like the other commands, this holds a raw pointer to the repository,
+//! which must outlive the command.
+
+// yada-core/src/commands/meal_plan_commands.rs
+use chrono::NaiveDate;
+
+use crate::models::command::{Command, CommandType};
+use crate::models::meal::MealPlan;
+use crate::repositories::meal_plan_repository::MealPlanRepository;
+
+/// Replaces the plan for a date; undo puts the previous plan back
+pub struct SetMealPlanCommand {
+    /// Raw pointer to the meal plan repository
+    meal_plan_repo: *mut MealPlanRepository,
+    /// Date the plan belongs to
+    date: NaiveDate,
+    /// The new plan (`None` clears the date)
+    plan: Option<MealPlan>,
+    /// The plan stored before execution, once executed
+    previous_plan: Option<Option<MealPlan>>,
+}
+
+// Note: We need to implement Send + Sync manually because of the raw pointer
+unsafe impl Send for SetMealPlanCommand {}
+unsafe impl Sync for SetMealPlanCommand {}
+
+impl SetMealPlanCommand {
+    /// Creates a command storing `plan` for `date` (`None` removes the plan)
+    pub fn new(meal_plan_repo: &mut MealPlanRepository, date: NaiveDate, plan: Option<MealPlan>) -> Self {
+        SetMealPlanCommand {
+            meal_plan_repo: meal_plan_repo as *mut MealPlanRepository,
+            date,
+            plan,
+            previous_plan: None,
+        }
+    }
+}
+
+impl Command for SetMealPlanCommand {
+    fn execute(&mut self) -> Result<(), String> {
+        // Safety: We know the pointer is valid because it was created from a reference
+        let meal_plan_repo = unsafe { &mut *self.meal_plan_repo };
+
+        self.previous_plan = Some(meal_plan_repo.set_plan(self.date, self.plan.clone()));
+        Ok(())
+    }
+
+    fn undo(&mut self) -> Result<(), String> {
+        let previous_plan = self.previous_plan.take().ok_or("Command was not executed")?;
+
+        // Safety: We know the pointer is valid because it was created from a reference
+        let meal_plan_repo = unsafe { &mut *self.meal_plan_repo };
+
+        meal_plan_repo.set_plan(self.date, previous_plan);
+        Ok(())
+    }
+
+    fn get_type(&self) -> CommandType {
+        CommandType::SetMealPlan
+    }
+
+    fn description(&self) -> String {
+        match &self.plan {
+            Some(plan) => format!("Plan {} food(s) for {}", plan.items.len(), self.date.format("%Y-%m-%d")),
+            None => format!("Clear meal plan for {}", self.date.format("%Y-%m-%d")),
+        }
+    }
+}
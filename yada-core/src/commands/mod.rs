@@ -27,6 +27,9 @@
 //! Commands are organized by the domain they operate on:
 //! - **Food Commands**: Manage food database operations (add, update, remove foods)
 //! - **Log Commands**: Handle daily consumption tracking (add, remove log entries)
+//! - **Exercise Commands**: Add and remove exercise entries
+//! - **Meal Plan Commands**: Replace or clear the meal plan for a day
+//! - **Macro Commands**: Run several commands as one undo step, such as logging a meal template
 //! - **Profile Commands**: Manage user profile data (basic profile, daily updates)
 //! 
 //! ## Undo System Integration
@@ -41,9 +44,15 @@
 //! 
 //! - `food_commands`: Food database manipulation commands
 //! - `log_commands`: Daily consumption log management commands  
+//! - `exercise_commands`: Exercise log management commands
+//! - `meal_plan_commands`: Meal plan management commands
+//! - `macro_command`: Several commands executed and undone together
 //! - `profile_commands`: User profile modification commands
 
 // Command pattern implementations for all data modification operations
 pub mod food_commands;
 pub mod log_commands;
+pub mod exercise_commands;
+pub mod meal_plan_commands;
+pub mod macro_command;
 pub mod profile_commands;
\ No newline at end of file
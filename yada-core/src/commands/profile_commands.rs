@@ -1,4 +1,4 @@
-// src/commands/profile_commands.rs
+// yada-core/src/commands/profile_commands.rs
 use crate::models::command::{Command, CommandType};
 use crate::models::profile::{UserProfile, DailyProfile};
 use crate::repositories::profile_repository::ProfileRepository;
@@ -50,11 +50,11 @@ impl Command for UpdateUserProfileCommand {
             profile_repo.set_profile(old_profile.clone());
         } else {
             // No previous profile existed
-            profile_repo.set_profile(UserProfile::new(
-                self.new_profile.gender.clone(),
-                self.new_profile.height,
-                self.new_profile.birth_date,
-            ));
+            profile_repo.set_profile(UserProfile {
+                daily_profiles: Vec::new(),
+                calculation_method: "harris_benedict".to_string(),
+                ..self.new_profile.clone()
+            });
         }
         
         self.executed = false;
@@ -0,0 +1,170 @@
+//! # Aggregated Food Search
+//!
+//! `AggregateFoodSource` sends one search to every source registered in a
+//! `FoodSourceFactory` at once and merges the answers into a single ranked
+//! list, so the user searches "everywhere" instead of picking a database.
+//!
+//! ## Fan-Out
+//!
+//! Each source is searched through an `AsyncFoodSource`, and all searches
+//! start before the first one is waited for, so the whole search takes as
+//! long as the slowest source rather than the sum of all of them. A source
+//! that times out or fails is listed in `AggregateResults::failed` and the
+//! others' results are still returned. A search can also be cancelled as a
+//! whole (see `search_cancellable`); every source still waited for is then
+//! listed as cancelled.
+//!
+//! ## Deduplication
+//!
+//! Two results are the same food when they share a barcode or, failing
+//! that, their names consist of the same words (`Oats, Rolled` and
+//! `rolled oats`). Sources have no barcode field, so a keyword of 8 to 14
+//! digits (EAN-8, UPC-A, EAN-13, GTIN-14) counts as one. The best-ranked copy
+//! is kept and the sources of the others are added to its provenance.
+//!
+//! ## Ranking
+//!
+//! Results are ranked against the words of the query like the local ranked
+//! search (see `models::search`). Results a source returned that match no
+//! query word (found by a field the source searches but YADA does not) score
+//! zero and come last. Ties go to the food found by more sources, then to
+//! the earlier source by name, then to the name.
+
+// yada-core/src/factories/aggregate_food_source.rs
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::factories::async_food_source::{AsyncFoodSource, CancelToken, LookupError};
+use crate::models::food::Food;
+use crate::models::search::{self, name_words};
+
+/// Digits in the barcode kinds recognized among a food's keywords
+const BARCODE_LENGTHS: [usize; 4] = [8, 12, 13, 14];
+
+/// A food source that searches several sources at once
+pub struct AggregateFoodSource {
+    /// Sources in name order, which breaks ranking ties
+    sources: Vec<AsyncFoodSource>,
+}
+
+/// A merged search result and the sources that found it
+#[derive(Debug, Clone)]
+pub struct SourcedFood {
+    /// The best-ranked copy of the food
+    pub food: Food,
+
+    /// Names of the sources that returned the food, best copy first
+    pub sources: Vec<&'static str>,
+
+    /// Ranked search score against the query (see `models::search`)
+    pub score: u32,
+}
+
+/// Everything an aggregated search found
+#[derive(Debug, Clone, Default)]
+pub struct AggregateResults {
+    /// Merged foods, best match first
+    pub foods: Vec<SourcedFood>,
+
+    /// Sources that gave no answer and why
+    pub failed: Vec<(&'static str, LookupError)>,
+}
+
+impl AggregateFoodSource {
+    /// Combines food sources
+    ///
+    /// # Arguments
+    /// * `sources` - Sources to search, in any order
+    pub fn new(mut sources: Vec<AsyncFoodSource>) -> Self {
+        sources.sort_by_key(|source| source.name());
+        AggregateFoodSource { sources }
+    }
+
+    /// Names of the combined sources, in name order
+    pub fn source_names(&self) -> Vec<&'static str> {
+        self.sources.iter().map(|source| source.name()).collect()
+    }
+
+    /// Searches every source and merges the results
+    ///
+    /// # Arguments
+    /// * `query` - Search text, passed to each source unchanged
+    ///
+    /// # Returns
+    /// The merged, ranked foods and the sources that did not answer
+    pub fn search(&self, query: &str) -> AggregateResults {
+        self.search_cancellable(query, &CancelToken::new())
+    }
+
+    /// Searches every source until the search is done or `cancel` is cancelled
+    ///
+    /// # Arguments
+    /// * `query` - Search text, passed to each source unchanged
+    /// * `cancel` - Token stopping the wait for every source
+    ///
+    /// # Returns
+    /// The merged, ranked foods of the sources that answered before the
+    /// cancellation, and the sources that did not answer
+    pub fn search_cancellable(&self, query: &str, cancel: &CancelToken) -> AggregateResults {
+        let pending: Vec<_> = self.sources.iter()
+            .map(|source| (source.name(), source.search_foods(query).with_cancel(cancel.clone())))
+            .collect();
+
+        let mut results = AggregateResults::default();
+        let mut found: Vec<(usize, Food)> = Vec::new();
+        for (order, (name, lookup)) in pending.into_iter().enumerate() {
+            match lookup.wait() {
+                Ok(foods) => found.extend(foods.into_iter().map(|food| (order, food))),
+                Err(error) => results.failed.push((name, error)),
+            }
+        }
+
+        let terms: HashSet<String> = name_words(query).collect();
+        let mut ranked: Vec<(u32, usize, Food)> = found.into_iter()
+            .map(|(order, food)| {
+                let score = search::rank([&food], &terms, false).first().map_or(0, |hit| hit.score);
+                (score, order, food)
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)).then_with(|| a.2.name.cmp(&b.2.name)));
+
+        for (score, order, food) in ranked {
+            let source = self.sources[order].name();
+            match results.foods.iter_mut().find(|merged| is_same_food(&merged.food, &food)) {
+                Some(merged) if !merged.sources.contains(&source) => merged.sources.push(source),
+                Some(_) => {}
+                None => results.foods.push(SourcedFood { food, sources: vec![source], score }),
+            }
+        }
+        // Merging may have made a later food more widely found than an equal earlier one
+        results.foods.sort_by(|a, b| b.score.cmp(&a.score).then(b.sources.len().cmp(&a.sources.len())));
+        results
+    }
+}
+
+impl fmt::Display for SourcedFood {
+    /// Formats the result for a selection list, e.g. `Rolled Oats (150 cal) [usda, off]`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({:.0} cal) [{}]", self.food.name, self.food.calories_per_serving, self.sources.join(", "))
+    }
+}
+
+/// Returns the barcode among a food's keywords, if any
+pub fn barcode(food: &Food) -> Option<&str> {
+    food.keywords.iter()
+        .map(String::as_str)
+        .find(|keyword| BARCODE_LENGTHS.contains(&keyword.len()) && keyword.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Whether two results describe the same food: same barcode, or the same name words
+fn is_same_food(a: &Food, b: &Food) -> bool {
+    if let (Some(a), Some(b)) = (barcode(a), barcode(b)) {
+        return a == b;
+    }
+    let words = |food: &Food| {
+        let mut words: Vec<String> = name_words(&food.name).collect();
+        words.sort();
+        words
+    };
+    words(a) == words(b)
+}
@@ -0,0 +1,295 @@
+//! # Asynchronous Food Source Lookups
+//!
+//! `FoodSource` methods block until the source answers, which for an online
+//! database can take long or never happen. `AsyncFoodSource` runs each
+//! lookup on a background thread and hands back a `PendingLookup` that the
+//! caller waits on with a timeout, so a slow network never freezes the
+//! interactive menu.
+//!
+//! ## Timeouts, Retries, and Cancellation
+//!
+//! - **Timeout**: Each attempt gets `LookupOptions::timeout`
+//!   (`YADA_SOURCE_TIMEOUT` seconds, default 10)
+//! - **Retries**: A timed-out attempt is retried up to
+//!   `LookupOptions::retries` times (`YADA_SOURCE_RETRIES`, default 2) after
+//!   `retry_delay`; a late answer of an earlier attempt is still accepted
+//! - **Cancellation**: A `CancelToken` (e.g. triggered by a key press or
+//!   Ctrl-C handler on another thread) stops the wait within a few
+//!   milliseconds; a token made with `CancelToken::child` is also
+//!   cancelled by its parent, so one lookup can stop on either
+//!
+//! A thread cannot be stopped from outside, so an attempt that is given up
+//! keeps running in the background and its answer is dropped.
+
+// yada-core/src/factories/async_food_source.rs
+use std::env;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::factories::food_source_factory::FoodSource;
+use crate::models::food::Food;
+
+/// Environment variable with the timeout of one lookup attempt in seconds
+pub const TIMEOUT_ENV_VAR: &str = "YADA_SOURCE_TIMEOUT";
+
+/// Environment variable with the number of retries after a timeout
+pub const RETRIES_ENV_VAR: &str = "YADA_SOURCE_RETRIES";
+
+/// A lookup run against the wrapped source on each attempt
+type Lookup<T> = Arc<dyn Fn(&dyn FoodSource) -> T + Send + Sync>;
+
+/// How often a wait checks its cancel token
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Limits of a lookup
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LookupOptions {
+    /// How long one attempt may take
+    pub timeout: Duration,
+
+    /// Attempts made after the first one times out
+    pub retries: u32,
+
+    /// Pause before each retry
+    pub retry_delay: Duration,
+}
+
+impl Default for LookupOptions {
+    fn default() -> Self {
+        LookupOptions {
+            timeout: Duration::from_secs(10),
+            retries: 2,
+            retry_delay: Duration::from_millis(500),
+        }
+    }
+}
+
+impl LookupOptions {
+    /// Reads the timeout and retries from `YADA_SOURCE_TIMEOUT` and `YADA_SOURCE_RETRIES`
+    ///
+    /// Missing or invalid values keep their defaults.
+    pub fn from_env() -> Self {
+        let mut options = LookupOptions::default();
+        if let Some(seconds) = env::var(TIMEOUT_ENV_VAR).ok().and_then(|value| value.trim().parse::<f64>().ok())
+            && seconds > 0.0
+        {
+            options.timeout = Duration::from_secs_f64(seconds);
+        }
+        if let Some(retries) = env::var(RETRIES_ENV_VAR).ok().and_then(|value| value.trim().parse().ok()) {
+            options.retries = retries;
+        }
+        options
+    }
+}
+
+/// Why a lookup produced no answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LookupError {
+    /// Every attempt timed out
+    TimedOut {
+        /// Attempts made
+        attempts: u32,
+    },
+
+    /// The lookup was cancelled
+    Cancelled,
+
+    /// The source failed (its lookup panicked)
+    SourceFailed,
+}
+
+impl fmt::Display for LookupError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LookupError::TimedOut { attempts: 1 } => write!(f, "The food source did not answer in time."),
+            LookupError::TimedOut { attempts } => {
+                write!(f, "The food source did not answer in time ({} attempts).", attempts)
+            }
+            LookupError::Cancelled => write!(f, "The lookup was cancelled."),
+            LookupError::SourceFailed => write!(f, "The food source failed."),
+        }
+    }
+}
+
+/// Shared flag that cancels a pending lookup
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+
+    /// Token whose cancellation also cancels this one
+    parent: Option<Box<CancelToken>>,
+}
+
+impl CancelToken {
+    /// Creates a token that is not cancelled
+    pub fn new() -> Self {
+        CancelToken::default()
+    }
+
+    /// Creates a token cancelled by its own `cancel` or by this token's
+    ///
+    /// Cancelling the child leaves this token as it is.
+    pub fn child(&self) -> Self {
+        CancelToken { cancelled: Arc::default(), parent: Some(Box::new(self.clone())) }
+    }
+
+    /// Cancels every lookup using this token
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether `cancel` was called on this token or a parent
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst) || self.parent.as_ref().is_some_and(|parent| parent.is_cancelled())
+    }
+}
+
+/// Runs a blocking call on a background thread, giving up after a timeout
+///
+/// Used where a source must be asked something before it can be wrapped
+/// (such as a plugin describing itself). The thread keeps running after a
+/// timeout and its answer is dropped.
+///
+/// # Returns
+/// * `Ok(T)` - The call's answer
+/// * `Err(LookupError)` - `TimedOut` if it did not answer in time, or
+///   `SourceFailed` if it panicked
+pub fn call_with_timeout<T: Send + 'static>(timeout: Duration, call: impl FnOnce() -> T + Send + 'static) -> Result<T, LookupError> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let answer = std::panic::catch_unwind(std::panic::AssertUnwindSafe(call));
+        let _ = sender.send(answer);
+    });
+    match receiver.recv_timeout(timeout) {
+        Ok(Ok(answer)) => Ok(answer),
+        Ok(Err(_)) | Err(RecvTimeoutError::Disconnected) => Err(LookupError::SourceFailed),
+        Err(RecvTimeoutError::Timeout) => Err(LookupError::TimedOut { attempts: 1 }),
+    }
+}
+
+/// A food source whose lookups run in the background
+#[derive(Clone)]
+pub struct AsyncFoodSource {
+    inner: Arc<dyn FoodSource>,
+    options: LookupOptions,
+}
+
+impl AsyncFoodSource {
+    /// Wraps a shared food source
+    pub fn new(inner: Arc<dyn FoodSource>, options: LookupOptions) -> Self {
+        AsyncFoodSource { inner, options }
+    }
+
+    /// Name of the wrapped source
+    pub fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    /// Starts looking up a food by its ID
+    pub fn get_food_by_id(&self, id: &str) -> PendingLookup<Option<Food>> {
+        let id = id.to_string();
+        self.start(move |source| source.get_food_by_id(&id))
+    }
+
+    /// Starts a search
+    pub fn search_foods(&self, query: &str) -> PendingLookup<Vec<Food>> {
+        let query = query.to_string();
+        self.start(move |source| source.search_foods(&query))
+    }
+
+    /// Creates a pending lookup and starts its first attempt
+    fn start<T: Send + 'static>(&self, lookup: impl Fn(&dyn FoodSource) -> T + Send + Sync + 'static) -> PendingLookup<T> {
+        let (sender, receiver) = mpsc::channel();
+        let pending = PendingLookup {
+            source: self.inner.clone(),
+            lookup: Arc::new(lookup),
+            options: self.options,
+            cancel: CancelToken::new(),
+            sender,
+            receiver,
+        };
+        pending.spawn_attempt();
+        pending
+    }
+}
+
+/// A lookup running in the background
+pub struct PendingLookup<T> {
+    source: Arc<dyn FoodSource>,
+    lookup: Lookup<T>,
+    options: LookupOptions,
+    cancel: CancelToken,
+    sender: Sender<thread::Result<T>>,
+    receiver: Receiver<thread::Result<T>>,
+}
+
+impl<T: Send + 'static> PendingLookup<T> {
+    /// Token that cancels this lookup from another thread
+    pub fn cancel_token(&self) -> CancelToken {
+        self.cancel.clone()
+    }
+
+    /// Makes the lookup stop when `token` is cancelled, in place of its own token
+    pub fn with_cancel(mut self, token: CancelToken) -> Self {
+        self.cancel = token;
+        self
+    }
+
+    /// Waits for the answer, retrying attempts that time out
+    ///
+    /// # Returns
+    /// * `Ok(T)` - The source's answer
+    /// * `Err(LookupError)` - If every attempt timed out, the lookup was
+    ///   cancelled, or the source failed
+    pub fn wait(self) -> Result<T, LookupError> {
+        let mut attempts = 1;
+        loop {
+            match self.wait_for(self.options.timeout) {
+                Err(LookupError::TimedOut { .. }) if attempts <= self.options.retries => {
+                    // The delay is also a chance for the earlier attempt to answer
+                    match self.wait_for(self.options.retry_delay) {
+                        Err(LookupError::TimedOut { .. }) => {}
+                        answer => return answer,
+                    }
+                    attempts += 1;
+                    self.spawn_attempt();
+                }
+                Err(LookupError::TimedOut { .. }) => return Err(LookupError::TimedOut { attempts }),
+                answer => return answer,
+            }
+        }
+    }
+
+    /// Waits up to a duration for any attempt to answer, checking for cancellation
+    fn wait_for(&self, duration: Duration) -> Result<T, LookupError> {
+        let deadline = Instant::now() + duration;
+        loop {
+            if self.cancel.is_cancelled() {
+                return Err(LookupError::Cancelled);
+            }
+            let left = deadline.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                return Err(LookupError::TimedOut { attempts: 1 });
+            }
+            match self.receiver.recv_timeout(left.min(POLL_INTERVAL)) {
+                Ok(Ok(answer)) => return Ok(answer),
+                Ok(Err(_)) | Err(RecvTimeoutError::Disconnected) => return Err(LookupError::SourceFailed),
+                Err(RecvTimeoutError::Timeout) => {}
+            }
+        }
+    }
+
+    /// Runs one attempt on a new thread
+    fn spawn_attempt(&self) {
+        let (source, lookup, sender) = (self.source.clone(), self.lookup.clone(), self.sender.clone());
+        thread::spawn(move || {
+            let answer = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| lookup(source.as_ref())));
+            // The receiver is gone once the caller stopped waiting
+            let _ = sender.send(answer);
+        });
+    }
+}
@@ -0,0 +1,240 @@
+//! # Caching Food Source
+//!
+//! `CachingFoodSource` wraps another `FoodSource` (typically an online
+//! database such as USDA or Open Food Facts) and keeps every food it
+//! fetches on disk, so repeated lookups and searches are answered locally
+//! and keep working offline.
+//!
+//! ## Expiry
+//!
+//! Cached foods and search results are fresh for a time to live (TTL,
+//! `DEFAULT_TTL` unless given). Fresh entries are answered from the cache
+//! without asking the wrapped source. Stale entries are fetched again; if
+//! the source returns nothing (it is offline, or the food was removed), the
+//! stale entry is returned instead, since old data beats none.
+//!
+//! ## Cache Files
+//!
+//! Each wrapped source gets two files in the cache directory, named after
+//! the source:
+//!
+//! - `<name>_foods.txt`: The cached foods, in the format of `foods.txt`
+//! - `<name>_cache.txt`: When each food and search was fetched
+//!
+//! ```text
+//! #yada:food_cache:v1
+//! FOOD|<food id>|<fetched, RFC 3339>
+//! QUERY|<normalized query>|<fetched, RFC 3339>|<food id>,<food id>,...
+//! ```
+//!
+//! The cache is best effort: `FoodSource` methods cannot report errors, so a
+//! cache that cannot be written only means the next lookup asks the source
+//! again.
+
+// yada-core/src/factories/caching_food_source.rs
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::factories::food_source_factory::FoodSource;
+use crate::models::food::Food;
+use crate::repositories::food_repository::FoodRepository;
+use crate::repositories::format;
+use crate::repositories::storage;
+
+/// How long cached foods and searches are used without asking the source again
+pub const DEFAULT_TTL: Duration = Duration::days(30);
+
+/// File kind in the header of the fetch-time file
+const CACHE_KIND: &str = "food_cache";
+
+/// A `FoodSource` decorator that persists fetched foods with a time to live
+pub struct CachingFoodSource {
+    inner: Box<dyn FoodSource>,
+    ttl: Duration,
+    state: Mutex<CacheState>,
+}
+
+/// Cached data, changed by the `&self` methods of `FoodSource` (possibly from several threads)
+struct CacheState {
+    /// Cached foods, saved to `<name>_foods.txt`
+    foods: FoodRepository,
+
+    /// When each cached food was fetched
+    fetched: HashMap<String, DateTime<Utc>>,
+
+    /// When each search was made and the IDs of the foods it found
+    queries: HashMap<String, (DateTime<Utc>, Vec<String>)>,
+
+    /// Path of `<name>_cache.txt`
+    times_path: String,
+}
+
+impl CachingFoodSource {
+    /// Wraps a food source, loading what an earlier session cached
+    ///
+    /// # Arguments
+    /// * `inner` - Source to fetch foods from
+    /// * `cache_dir` - Directory for the cache files (created when needed)
+    /// * `ttl` - How long cached foods and searches stay fresh
+    ///
+    /// # Returns
+    /// * `Ok(CachingFoodSource)` - The caching source
+    /// * `Err(io::Error)` - If the cache directory or files cannot be read
+    pub fn new(inner: Box<dyn FoodSource>, cache_dir: &Path, ttl: Duration) -> Result<Self, io::Error> {
+        std::fs::create_dir_all(cache_dir)?;
+        let foods_path = cache_dir.join(format!("{}_foods.txt", inner.name()));
+        let times_path = cache_dir.join(format!("{}_cache.txt", inner.name()));
+        let mut state = CacheState {
+            foods: FoodRepository::new(&foods_path.to_string_lossy())?,
+            fetched: HashMap::new(),
+            queries: HashMap::new(),
+            times_path: times_path.to_string_lossy().into_owned(),
+        };
+        if times_path.exists() {
+            state.load_times()?;
+        }
+        Ok(CachingFoodSource { inner, ttl, state: Mutex::new(state) })
+    }
+
+    /// Number of foods in the cache, fresh or stale
+    pub fn cached_food_count(&self) -> usize {
+        self.state().foods.get_foods().len()
+    }
+
+    /// Locks the cached data; a lookup that panicked leaves the data usable
+    fn state(&self) -> MutexGuard<'_, CacheState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Whether a time is within the TTL
+    fn is_fresh(&self, fetched: DateTime<Utc>) -> bool {
+        Utc::now() - fetched < self.ttl
+    }
+}
+
+impl FoodSource for CachingFoodSource {
+    fn get_food_by_id(&self, id: &str) -> Option<Food> {
+        let cached = {
+            let state = self.state();
+            state.fetched.get(id).copied().zip(state.foods.get_food(id).cloned())
+        };
+        if let Some((fetched, food)) = &cached
+            && self.is_fresh(*fetched)
+        {
+            return Some(food.clone());
+        }
+
+        match self.inner.get_food_by_id(id) {
+            Some(food) => {
+                let mut state = self.state();
+                state.store(&food, Utc::now());
+                state.save();
+                Some(food)
+            }
+            None => cached.map(|(_, food)| food),
+        }
+    }
+
+    fn search_foods(&self, query: &str) -> Vec<Food> {
+        let key = query.trim().to_lowercase();
+        let cached = {
+            let state = self.state();
+            state.queries.get(&key).map(|(fetched, ids)| {
+                let foods: Vec<Food> = ids.iter().filter_map(|id| state.foods.get_food(id).cloned()).collect();
+                (*fetched, foods)
+            })
+        };
+        if let Some((fetched, foods)) = &cached
+            && self.is_fresh(*fetched)
+        {
+            return foods.clone();
+        }
+
+        let found = self.inner.search_foods(query);
+        if found.is_empty() {
+            return cached.map(|(_, foods)| foods).unwrap_or_default();
+        }
+        let now = Utc::now();
+        let mut state = self.state();
+        for food in &found {
+            state.store(food, now);
+        }
+        state.queries.insert(key, (now, found.iter().map(|food| food.id.clone()).collect()));
+        state.save();
+        found
+    }
+
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn description(&self) -> &'static str {
+        self.inner.description()
+    }
+}
+
+impl CacheState {
+    /// Adds or replaces a cached food
+    fn store(&mut self, food: &Food, fetched: DateTime<Utc>) {
+        let stored = if self.foods.get_food(&food.id).is_some() {
+            self.foods.update_food(food.clone()).map(|_| ())
+        } else {
+            self.foods.add_food(food.clone())
+        };
+        if stored.is_ok() {
+            self.fetched.insert(food.id.clone(), fetched);
+        }
+    }
+
+    /// Writes both cache files, ignoring errors (see the module documentation)
+    fn save(&mut self) {
+        if self.foods.save().is_err() {
+            return;
+        }
+        let mut contents = format!("{}\n", format::header(CACHE_KIND));
+        let mut fetched: Vec<_> = self.fetched.iter().collect();
+        fetched.sort();
+        for (id, time) in fetched {
+            contents.push_str(&format!("FOOD|{}|{}\n", format::escape(id), time.to_rfc3339()));
+        }
+        let mut queries: Vec<_> = self.queries.iter().collect();
+        queries.sort_by(|a, b| a.0.cmp(b.0));
+        for (query, (time, ids)) in queries {
+            let ids: Vec<String> = ids.iter().map(|id| format::escape(id)).collect();
+            contents.push_str(&format!("QUERY|{}|{}|{}\n", format::escape(query), time.to_rfc3339(), ids.join(",")));
+        }
+        let _ = storage::write_data_file(&self.times_path, contents.as_bytes());
+    }
+
+    /// Reads the fetch times; lines that cannot be read are dropped, so their foods count as stale
+    fn load_times(&mut self) -> Result<(), io::Error> {
+        let contents = storage::read_data_file(&self.times_path)?;
+        let text = String::from_utf8_lossy(&contents);
+        let parse_time = |time: &str| DateTime::parse_from_rfc3339(time).ok().map(|time| time.with_timezone(&Utc));
+        for line in text.lines().filter(|line| !format::is_ignored_line(line)) {
+            match format::split_escaped(line, '|').as_slice() {
+                ["FOOD", id, time] => {
+                    if let Some(time) = parse_time(time) {
+                        self.fetched.insert(format::unescape(id), time);
+                    }
+                }
+                ["QUERY", query, time, ids] => {
+                    if let Some(time) = parse_time(time) {
+                        let ids = format::split_escaped(ids, ',')
+                            .into_iter()
+                            .filter(|id| !id.is_empty())
+                            .map(format::unescape)
+                            .collect();
+                        self.queries.insert(format::unescape(query), (time, ids));
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
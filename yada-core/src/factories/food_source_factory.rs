@@ -0,0 +1,165 @@
+// yada-core/src/factories/food_source_factory.rs
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use chrono::Duration;
+
+use crate::factories::aggregate_food_source::AggregateFoodSource;
+use crate::factories::async_food_source::{AsyncFoodSource, LookupOptions};
+use crate::factories::caching_food_source::{CachingFoodSource, DEFAULT_TTL};
+use crate::factories::plugin_food_source;
+use crate::models::food::Food;
+
+// Send + Sync so lookups can run off the menu thread (see `AsyncFoodSource`)
+pub trait FoodSource: Send + Sync {
+    fn get_food_by_id(&self, id: &str) -> Option<Food>;
+    fn search_foods(&self, query: &str) -> Vec<Food>;
+    fn name(&self) -> &'static str;
+    fn description(&self) -> &'static str;
+}
+
+pub struct FoodSourceFactory {
+    sources: HashMap<String, Arc<dyn FoodSource>>,
+}
+
+impl Default for FoodSourceFactory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FoodSourceFactory {
+    pub fn new() -> Self {
+        let mut factory = FoodSourceFactory {
+            sources: HashMap::new(),
+        };
+        
+        // Register built-in sources
+        factory.register_source(Box::new(LocalFoodSource {}));
+        
+        factory
+    }
+    
+    pub fn register_source(&mut self, source: Box<dyn FoodSource>) {
+        self.sources.insert(source.name().to_string(), Arc::from(source));
+    }
+    
+    // Online sources are registered through this, so their foods stay available offline
+    pub fn register_cached_source(&mut self, source: Box<dyn FoodSource>, cache_dir: &Path, ttl: Duration) -> io::Result<()> {
+        let cached = CachingFoodSource::new(source, cache_dir, ttl)?;
+        self.register_source(Box::new(cached));
+        Ok(())
+    }
+    
+    // Plugins are cached like online sources; returns why plugins that did not load (or answer in time) were skipped
+    pub fn register_plugins(&mut self, plugin_dir: &Path, cache_dir: &Path, options: LookupOptions) -> Vec<String> {
+        let (plugins, mut problems) = plugin_food_source::discover(plugin_dir, options);
+        for plugin in plugins {
+            let name = plugin.name();
+            if self.sources.contains_key(name) {
+                problems.push(format!("{}: a source named \"{}\" already exists", plugin.path().display(), name));
+            } else if let Err(e) = self.register_cached_source(Box::new(plugin), cache_dir, DEFAULT_TTL) {
+                problems.push(format!("{}: {}", name, e));
+            }
+        }
+        problems
+    }
+    
+    pub fn get_source(&self, name: &str) -> Option<&dyn FoodSource> {
+        self.sources.get(name).map(|boxed| boxed.as_ref())
+    }
+    
+    // Lookups through the returned source time out instead of blocking the caller
+    pub fn get_async_source(&self, name: &str, options: LookupOptions) -> Option<AsyncFoodSource> {
+        self.sources.get(name).map(|source| AsyncFoodSource::new(source.clone(), options))
+    }
+    
+    // Searches through the returned source go to every registered source at once
+    pub fn aggregate(&self, options: LookupOptions) -> AggregateFoodSource {
+        AggregateFoodSource::new(self.sources.values().map(|source| AsyncFoodSource::new(source.clone(), options)).collect())
+    }
+    
+    pub fn get_all_sources(&self) -> Vec<&str> {
+        self.sources.keys().map(|s| s.as_str()).collect()
+    }
+}
+
+// Name of the built-in source, which finds nothing; any other source is real
+pub const LOCAL_SOURCE: &str = "local";
+
+// A simple local food source that doesn't actually do anything
+// This is just a placeholder to show how the factory pattern would work
+struct LocalFoodSource {}
+
+impl FoodSource for LocalFoodSource {
+    fn get_food_by_id(&self, _id: &str) -> Option<Food> {
+        None
+    }
+    
+    fn search_foods(&self, _query: &str) -> Vec<Food> {
+        Vec::new()
+    }
+    
+    fn name(&self) -> &'static str {
+        LOCAL_SOURCE
+    }
+    
+    fn description(&self) -> &'static str {
+        "Local food database"
+    }
+}
+
+// In a real application, you might have implementations like
+// (registered with `register_cached_source` to keep their results offline):
+// - USDAFoodSource that connects to the USDA food database API
+// - McDonaldsSource that scrapes McDonald's nutrition information
+// - etc.
+
+// Example of how a third-party API food source might look:
+/*
+struct USDAFoodSource {
+    api_key: String,
+    client: USDAClient,
+}
+
+impl FoodSource for USDAFoodSource {
+    fn get_food_by_id(&self, id: &str) -> Option<Food> {
+        match self.client.get_food_details(id) {
+            Ok(details) => {
+                let mut keywords = HashSet::new();
+                keywords.insert(details.name.to_lowercase());
+                for category in &details.categories {
+                    keywords.insert(category.to_lowercase());
+                }
+                
+                Some(Food::new_basic(
+                    format!("usda_{}", id),
+                    details.name,
+                    keywords,
+                    details.calories_per_100g / 100.0, // Convert to calories per 1g
+                ))
+            },
+            Err(_) => None,
+        }
+    }
+    
+    fn search_foods(&self, query: &str) -> Vec<Food> {
+        match self.client.search_foods(query) {
+            Ok(results) => {
+                results.iter().filter_map(|item| self.get_food_by_id(&item.id)).collect()
+            },
+            Err(_) => Vec::new(),
+        }
+    }
+    
+    fn name(&self) -> &'static str {
+        "usda"
+    }
+    
+    fn description(&self) -> &'static str {
+        "USDA Food Database"
+    }
+}
+*/
\ No newline at end of file
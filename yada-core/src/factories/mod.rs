@@ -0,0 +1,6 @@
+// yada-core/src/factories/mod.rs
+pub mod food_source_factory;
+pub mod caching_food_source;
+pub mod async_food_source;
+pub mod aggregate_food_source;
+pub mod plugin_food_source;
\ No newline at end of file
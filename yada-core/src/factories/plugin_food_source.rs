@@ -0,0 +1,239 @@
+//! # Plugin Food Sources
+//!
+//! Third parties can add food databases (restaurant chains, regional
+//! databases, a company cafeteria) without changing YADA, in two ways:
+//!
+//! - **Rust crates**: Implement `FoodSource` and register it with
+//!   `FoodSourceFactory::register_source` in a program built on `yada_core`
+//! - **Executables**: Any program, in any language, placed in the plugin
+//!   directory and speaking the JSON protocol below; `PluginFoodSource`
+//!   runs it and translates its answers
+//!
+//! ## Protocol
+//!
+//! YADA starts the executable once per request, writes one JSON request to
+//! its standard input, closes it, and reads one JSON answer from its
+//! standard output. The program should exit with status 0.
+//!
+//! ```text
+//! {"request": "describe"}
+//!     → {"name": "burgerdb", "description": "Burger chain menus"}
+//! {"request": "search", "query": "cheeseburger"}
+//!     → {"foods": [<food>, ...]}
+//! {"request": "get", "id": "bk_whopper"}
+//!     → {"food": <food>}  or  {"food": null}
+//! ```
+//!
+//! A food is an object with `id`, `name` and `calories` (per serving), and
+//! optionally `keywords` (list of words; the name's words if missing),
+//! `protein`, `carbs`, `fat`, `fiber`, `alcohol` (grams per serving),
+//! `caffeine` (milligrams per serving), `grams` (weight of a serving) and
+//! `category`. Unknown fields are ignored, so plugins may
+//! send more than YADA reads.
+//!
+//! Any answer may instead be `{"error": "<message>"}`. The name must be a
+//! single word (letters, digits, `_`, `-`); it names the source in search
+//! results and its cache files.
+//!
+//! ## Failures
+//!
+//! `FoodSource` methods cannot report errors, so a plugin that fails, exits
+//! with an error, or answers something unreadable simply finds nothing.
+//! Plugins that cannot be described are reported by `discover` instead of
+//! being registered, and so are plugins that do not describe themselves
+//! within `LookupOptions::timeout` (the describe step runs on a background
+//! thread, like an `AsyncFoodSource` lookup). Lookups have no time limit
+//! here; callers wrap plugins in an `AsyncFoodSource` (as the aggregated
+//! search does) to limit them.
+
+// yada-core/src/factories/plugin_food_source.rs
+use std::collections::HashSet;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use serde_json::{json, Value};
+
+use crate::factories::async_food_source::{self, LookupError, LookupOptions};
+use crate::factories::food_source_factory::FoodSource;
+use crate::models::food::{Food, Macros};
+use crate::models::search::name_words;
+
+/// A food source backed by an executable speaking the plugin protocol
+#[derive(Debug)]
+pub struct PluginFoodSource {
+    /// Path of the executable
+    path: PathBuf,
+
+    /// Name from the plugin's `describe` answer
+    name: &'static str,
+
+    /// Description from the plugin's `describe` answer
+    description: &'static str,
+}
+
+impl PluginFoodSource {
+    /// Starts a plugin and asks it to describe itself
+    ///
+    /// The name and description are kept for the rest of the program, as
+    /// `FoodSource` hands them out as `&'static str`.
+    ///
+    /// # Arguments
+    /// * `path` - Path of the plugin executable
+    ///
+    /// # Returns
+    /// * `Ok(PluginFoodSource)` - The plugin, ready to search
+    /// * `Err(String)` - If the plugin cannot be run or its description is invalid
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let answer = run(path, &json!({ "request": "describe" }))?;
+        let name = answer["name"].as_str().ok_or("the description has no \"name\"")?;
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+            return Err(format!("invalid source name \"{}\"", name));
+        }
+        let description = answer["description"].as_str().unwrap_or(name);
+        Ok(PluginFoodSource {
+            path: path.to_path_buf(),
+            name: Box::leak(name.to_string().into_boxed_str()),
+            description: Box::leak(description.to_string().into_boxed_str()),
+        })
+    }
+
+    /// Path of the plugin executable
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl FoodSource for PluginFoodSource {
+    fn get_food_by_id(&self, id: &str) -> Option<Food> {
+        let answer = run(&self.path, &json!({ "request": "get", "id": id })).ok()?;
+        food_from_json(&answer["food"]).ok()
+    }
+
+    fn search_foods(&self, query: &str) -> Vec<Food> {
+        match run(&self.path, &json!({ "request": "search", "query": query })) {
+            Ok(answer) => answer["foods"].as_array()
+                .map(|foods| foods.iter().filter_map(|food| food_from_json(food).ok()).collect())
+                .unwrap_or_default(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn description(&self) -> &'static str {
+        self.description
+    }
+}
+
+/// Loads every plugin in a directory
+///
+/// Every file in the directory is treated as a plugin (on Unix, only files
+/// marked executable); files are loaded in name order. A missing directory
+/// has no plugins.
+///
+/// # Arguments
+/// * `dir` - Plugin directory
+/// * `options` - Limits of each plugin's describe step (only the timeout is used)
+///
+/// # Returns
+/// The plugins that loaded, and a message for each file that did not
+pub fn discover(dir: &Path, options: LookupOptions) -> (Vec<PluginFoodSource>, Vec<String>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return (Vec::new(), Vec::new());
+    };
+    let mut paths: Vec<PathBuf> = entries.filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| is_executable(path))
+        .collect();
+    paths.sort();
+
+    let mut plugins: Vec<PluginFoodSource> = Vec::new();
+    let mut problems = Vec::new();
+    for path in paths {
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        let loading = path.clone();
+        let loaded = async_food_source::call_with_timeout(options.timeout, move || PluginFoodSource::load(&loading));
+        match loaded.unwrap_or_else(|e| Err(describe_error(e, options))) {
+            Ok(plugin) if plugins.iter().any(|loaded| loaded.name == plugin.name) => {
+                problems.push(format!("{}: another plugin is already named \"{}\"", file_name, plugin.name));
+            }
+            Ok(plugin) => plugins.push(plugin),
+            Err(e) => problems.push(format!("{}: {}", file_name, e)),
+        }
+    }
+    (plugins, problems)
+}
+
+/// Explains why a plugin's describe step gave no answer
+fn describe_error(error: LookupError, options: LookupOptions) -> String {
+    match error {
+        LookupError::TimedOut { .. } => format!("did not answer within {:.0} s", options.timeout.as_secs_f64()),
+        _ => error.to_string(),
+    }
+}
+
+/// Runs a plugin with one request and returns its answer
+fn run(path: &Path, request: &Value) -> Result<Value, String> {
+    let mut child = Command::new(path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("cannot run: {}", e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A plugin that does not read its request may close stdin early
+        let _ = stdin.write_all(format!("{}\n", request).as_bytes());
+    }
+    let output = child.wait_with_output().map_err(|e| format!("cannot run: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("exited with {}", output.status));
+    }
+    let answer: Value = serde_json::from_slice(&output.stdout).map_err(|e| format!("invalid answer: {}", e))?;
+    match answer["error"].as_str() {
+        Some(message) => Err(message.to_string()),
+        None => Ok(answer),
+    }
+}
+
+/// Converts a food object of the protocol into a food
+fn food_from_json(value: &Value) -> Result<Food, String> {
+    let text = |key: &str| value[key].as_str().map(str::trim).filter(|text| !text.is_empty());
+    let number = |key: &str| value[key].as_f64().filter(|number| number.is_finite() && *number >= 0.0);
+
+    let id = text("id").ok_or("a food has no \"id\"")?;
+    let name = text("name").ok_or_else(|| format!("food {} has no \"name\"", id))?;
+    let calories = number("calories").ok_or_else(|| format!("food {} has no valid \"calories\"", id))?;
+    let mut keywords: HashSet<String> = value["keywords"].as_array()
+        .map(|words| words.iter().filter_map(Value::as_str).map(|word| word.trim().to_lowercase()).filter(|word| !word.is_empty()).collect())
+        .unwrap_or_default();
+    if keywords.is_empty() {
+        keywords = name_words(name).collect();
+    }
+
+    let macros = Macros::new(number("protein").unwrap_or(0.0), number("carbs").unwrap_or(0.0), number("fat").unwrap_or(0.0))
+        .with_fiber(number("fiber").unwrap_or(0.0))
+        .with_alcohol_and_caffeine(number("alcohol").unwrap_or(0.0), number("caffeine").unwrap_or(0.0));
+    Ok(Food::new_basic(id.replace(char::is_whitespace, "_"), name.to_string(), keywords, calories)
+        .with_macros(macros)
+        .with_serving_details(number("grams").filter(|grams| *grams > 0.0), None)
+        .with_metadata(text("category").map(str::to_string), HashSet::new()))
+}
+
+/// Whether a directory entry can be run as a plugin
+fn is_executable(path: &Path) -> bool {
+    let Ok(metadata) = fs::metadata(path) else {
+        return false;
+    };
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        metadata.is_file() && metadata.permissions().mode() & 0o111 != 0
+    }
+    #[cfg(not(unix))]
+    {
+        metadata.is_file()
+    }
+}
@@ -0,0 +1,55 @@
+//! # Cronometer Importer
+//!
+//! Reads the "Servings" export of Cronometer (`servings.csv`, from Account,
+//! Export Data), which has one row per logged food:
+//!
+//! ```text
+//! Day,Time,Group,Food Name,Amount,Energy (kcal),Protein (g),Carbs (g),Fat (g),Fiber (g),...
+//! 2024-03-05,08:10,Breakfast,"Oats, Rolled",1.00 cup,307.2,10.7,54.8,5.3,8.2,...
+//! ```
+//!
+//! The "Daily Nutrition" export (`dailysummary.csv`, without a food column)
+//! is read as one total per day. Entries keep their `Time` when it is set;
+//! the groups `Breakfast`, `Lunch`, `Dinner`, and `Snacks` map to meal slots
+//! (`Uncategorized` and custom groups have none).
+
+// yada-core/src/importers/cronometer.rs
+use super::Importer;
+use super::diary::{self, DiaryColumns, DiaryRow};
+
+/// Columns of Cronometer exports
+const COLUMNS: DiaryColumns = DiaryColumns {
+    app_name: "Cronometer",
+    date: &["day", "date"],
+    date_formats: &["%Y-%m-%d"],
+    time: &["time"],
+    meal: &["group"],
+    skipped_meals: &[],
+    food: &["food name"],
+    calories: &["energy (kcal)", "calories (kcal)"],
+    protein: &["protein (g)"],
+    carbs: &["carbs (g)", "net carbs (g)"],
+    fat: &["fat (g)"],
+    fiber: &["fiber (g)"],
+};
+
+/// Importer for Cronometer CSV exports
+pub struct CronometerImporter;
+
+impl Importer for CronometerImporter {
+    fn name(&self) -> &'static str {
+        "cronometer"
+    }
+
+    fn app_name(&self) -> &'static str {
+        "Cronometer"
+    }
+
+    fn description(&self) -> &'static str {
+        "Servings export (servings.csv) or Daily Nutrition export"
+    }
+
+    fn read_rows(&self, text: &str) -> Result<Vec<DiaryRow>, String> {
+        diary::read_rows(text, &COLUMNS)
+    }
+}
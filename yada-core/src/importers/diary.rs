@@ -0,0 +1,282 @@
+//! # Diary Rows
+//!
+//! The part of importing that every app shares. Importers describe their
+//! CSV columns with `DiaryColumns` and read them into `DiaryRow`s, and
+//! `import_rows` turns the rows into foods and log entries:
+//!
+//! - **Rows with a food name**: each food becomes a YADA food
+//!   `<prefix>_<name>` whose serving is the amount in its first row; later
+//!   rows of the same food are logged as the servings that match their
+//!   calories
+//! - **Rows without a food name** (meal or day totals): each meal of each
+//!   day becomes an archived food `<prefix>_<date>_<meal>` holding that
+//!   meal's totals, logged once
+//!
+//! Column names are matched case-insensitively against a list of names per
+//! value, so `Fat (g)` and `Fat` both work.
+//!
+//! ## Repeated Imports
+//!
+//! Entries are stamped at the time in the export or, without one, at a
+//! fixed time per meal (breakfast 08:00, lunch 12:30, dinner 19:00, snacks
+//! and entries without a meal 15:30); entries at the same time are one
+//! second apart. Importing the same file again therefore skips the entries
+//! that already exist instead of logging them twice. Existing foods are
+//! never changed.
+
+// yada-core/src/importers/diary.rs
+use std::collections::HashMap;
+
+use chrono::{Duration, NaiveDate, NaiveTime};
+
+use crate::ingest::csv::{Row, Table};
+use crate::ingest::records::{FoodRecord, LogEntryRecord};
+use crate::ingest::{ConflictPolicy, IngestReport, import_foods, import_log_entries};
+use crate::models::meal::MealSlot;
+use crate::models::quantity::Quantity;
+use crate::repositories::food_repository::FoodRepository;
+use crate::repositories::log_repository::LogRepository;
+
+/// Longest food ID made from a food name, prefix included
+const MAX_ID_LENGTH: usize = 48;
+
+/// One food or meal entry of an export
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiaryRow {
+    /// Day the entry was eaten
+    pub date: NaiveDate,
+
+    /// Time it was logged, if the export records one
+    pub time: Option<NaiveTime>,
+
+    /// Meal it belongs to; custom meals have none
+    pub meal: Option<MealSlot>,
+
+    /// Food name; `None` for meal or day totals
+    pub food: Option<String>,
+
+    /// Calories of the entry
+    pub calories: f64,
+
+    /// Protein in grams
+    pub protein: f64,
+
+    /// Carbohydrates in grams
+    pub carbs: f64,
+
+    /// Fat in grams
+    pub fat: f64,
+
+    /// Fiber in grams
+    pub fiber: f64,
+}
+
+/// Outcome of importing an export
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportReport {
+    /// Rows read from the export
+    pub rows: usize,
+
+    /// Names of the foods created
+    pub new_foods: Vec<String>,
+
+    /// Foods created, and the ones that failed
+    pub foods: IngestReport,
+
+    /// Log entries added, skipped as already imported, and failed
+    pub entries: IngestReport,
+
+    /// First and last day with an entry
+    pub dates: Option<(NaiveDate, NaiveDate)>,
+}
+
+/// Column names of an app's export, lowercased, in order of preference
+pub(crate) struct DiaryColumns {
+    /// App name used in error messages
+    pub app_name: &'static str,
+    pub date: &'static [&'static str],
+    /// Accepted date formats (`chrono` syntax)
+    pub date_formats: &'static [&'static str],
+    pub time: &'static [&'static str],
+    pub meal: &'static [&'static str],
+    /// Meals whose rows are not food, e.g. exercise
+    pub skipped_meals: &'static [&'static str],
+    pub food: &'static [&'static str],
+    pub calories: &'static [&'static str],
+    pub protein: &'static [&'static str],
+    pub carbs: &'static [&'static str],
+    pub fat: &'static [&'static str],
+    pub fiber: &'static [&'static str],
+}
+
+/// Reads the rows of a CSV export
+///
+/// # Returns
+/// * `Ok(Vec<DiaryRow>)` - The rows, without those of skipped meals
+/// * `Err(String)` - If the date or calorie column is missing or a row cannot be read
+pub(crate) fn read_rows(text: &str, columns: &DiaryColumns) -> Result<Vec<DiaryRow>, String> {
+    let not_export = |e: String| format!("Not a {} export: {}", columns.app_name, e);
+    let table = Table::parse(text.trim_start_matches('\u{feff}'), &[]).map_err(not_export)?;
+    for (name, aliases) in [("date", columns.date), ("calories", columns.calories)] {
+        if !table.has_any(aliases) {
+            return Err(not_export(format!("CSV header has no {} column ({})", name, aliases.join(", "))));
+        }
+    }
+
+    let mut rows = Vec::new();
+    for row in table.rows() {
+        let meal_name = row.text_any(columns.meal).map(|meal| meal.to_lowercase());
+        if meal_name.as_deref().is_some_and(|meal| columns.skipped_meals.contains(&meal)) {
+            continue;
+        }
+        let date_text = row.text_any(columns.date).ok_or_else(|| row.error("date is missing"))?;
+        let date = parse_with(&date_text, columns.date_formats, NaiveDate::parse_from_str)
+            .ok_or_else(|| row.error(&format!("date '{}' is not {}", date_text, columns.date_formats.join(" or "))))?;
+        let time = match row.text_any(columns.time) {
+            Some(text) => Some(
+                parse_with(&text, &["%H:%M", "%H:%M:%S", "%I:%M %p"], NaiveTime::parse_from_str)
+                    .ok_or_else(|| row.error(&format!("time '{}' is not HH:MM", text)))?,
+            ),
+            None => None,
+        };
+        rows.push(DiaryRow {
+            date,
+            time,
+            meal: meal_name.as_deref().and_then(parse_meal),
+            food: row.text_any(columns.food),
+            calories: row.number_any(columns.calories)?.ok_or_else(|| row.error("calories are missing"))?,
+            protein: grams(&row, columns.protein)?,
+            carbs: grams(&row, columns.carbs)?,
+            fat: grams(&row, columns.fat)?,
+            fiber: grams(&row, columns.fiber)?,
+        });
+    }
+    Ok(rows)
+}
+
+/// Creates the missing foods of the rows and logs the rows
+///
+/// # Arguments
+/// * `food_repo` - Food database that receives the missing foods
+/// * `log_repo` - Food log that receives the entries
+/// * `prefix` - Food ID prefix without the underscore, e.g. `mfp`
+/// * `app_name` - App name used in the names of meal total foods
+/// * `rows` - Rows read from the export
+pub fn import_rows(
+    food_repo: &mut FoodRepository,
+    log_repo: &mut LogRepository,
+    prefix: &str,
+    app_name: &str,
+    rows: &[DiaryRow],
+) -> ImportReport {
+    let keyword: String = app_name.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect();
+    let mut report = ImportReport { rows: rows.len(), ..ImportReport::default() };
+
+    // Per-serving values come from the existing food or the first row of a new one
+    let mut foods: Vec<FoodRecord> = Vec::new();
+    let mut calories_per_serving: HashMap<String, f64> = HashMap::new();
+    let mut summaries = Vec::new();
+    let mut entries = Vec::new();
+    let mut times_used: HashMap<(NaiveDate, NaiveTime), i64> = HashMap::new();
+
+    for row in rows.iter().filter(|row| row.calories > 0.0 || row.food.is_some()) {
+        let (id, name) = match &row.food {
+            Some(food) => (food_id(prefix, food), food.clone()),
+            None => {
+                let meal = row.meal.map_or("Day".to_string(), |meal| meal.to_string());
+                let id = format!("{}_{}_{}", prefix, row.date.format("%Y%m%d"), meal.to_lowercase());
+                (id, format!("{} {} {}", app_name, meal, row.date.format("%Y-%m-%d")))
+            }
+        };
+        let per_serving = match food_repo.get_food(&id) {
+            Some(existing) => existing.calories_per_serving,
+            None => *calories_per_serving.entry(id.clone()).or_insert_with(|| {
+                foods.push(FoodRecord {
+                    id: id.clone(),
+                    name: name.clone(),
+                    calories: row.calories,
+                    keywords: vec![keyword.clone()],
+                    protein: row.protein,
+                    carbs: row.carbs,
+                    fat: row.fat,
+                    fiber: row.fiber,
+                    ..FoodRecord::default()
+                });
+                if row.food.is_none() {
+                    summaries.push(id.clone());
+                }
+                row.calories
+            }),
+        };
+        let servings = if per_serving > 0.0 && row.calories > 0.0 {
+            (row.calories / per_serving * 100.0).round() / 100.0
+        } else {
+            1.0
+        };
+
+        let time = row.time.unwrap_or_else(|| meal_time(row.meal));
+        let count = times_used.entry((row.date, time)).or_insert(0);
+        let timestamp = row.date.and_time(time) + Duration::seconds(*count);
+        *count += 1;
+        entries.push(LogEntryRecord {
+            meal: row.meal,
+            timestamp: Some(timestamp),
+            ..LogEntryRecord::new(row.date, &id, Quantity::servings(servings.max(0.01)))
+        });
+    }
+
+    report.foods = import_foods(food_repo, &foods, ConflictPolicy::Skip);
+    report.new_foods = foods
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| report.foods.errors.iter().all(|error| error.index != *index))
+        .map(|(_, food)| food.name.clone())
+        .collect();
+    for id in &summaries {
+        let _ = food_repo.set_archived(id, true);
+    }
+    report.entries = import_log_entries(log_repo, food_repo, &entries, ConflictPolicy::Skip);
+    let dates = entries.iter().map(|entry| entry.date);
+    report.dates = dates.clone().min().zip(dates.max());
+    report
+}
+
+/// Parses an optional gram value, missing as 0
+fn grams(row: &Row, columns: &[&str]) -> Result<f64, String> {
+    Ok(row.number_any(columns)?.unwrap_or(0.0))
+}
+
+/// Parses a value with the first format that fits
+fn parse_with<T>(value: &str, formats: &[&str], parse: fn(&str, &str) -> chrono::ParseResult<T>) -> Option<T> {
+    formats.iter().find_map(|format| parse(value, format).ok())
+}
+
+/// Maps meal names such as `Snacks`; other names have no slot
+fn parse_meal(value: &str) -> Option<MealSlot> {
+    let value = value.trim();
+    MealSlot::parse(value).or_else(|| MealSlot::parse(value.strip_suffix('s')?))
+}
+
+/// Time of day entries of a meal are stamped with when the export has none
+fn meal_time(meal: Option<MealSlot>) -> NaiveTime {
+    let (hour, minute) = match meal {
+        Some(MealSlot::Breakfast) => (8, 0),
+        Some(MealSlot::Lunch) => (12, 30),
+        Some(MealSlot::Dinner) => (19, 0),
+        Some(MealSlot::Snack) | None => (15, 30),
+    };
+    NaiveTime::from_hms_opt(hour, minute, 0).unwrap_or_default()
+}
+
+/// Builds a food ID from a food name, e.g. `mfp_greek_yogurt_plain`
+fn food_id(prefix: &str, name: &str) -> String {
+    let mut id = prefix.to_string();
+    for word in name.split(|c: char| !c.is_alphanumeric()).filter(|word| !word.is_empty()) {
+        id.push('_');
+        id.extend(word.chars().flat_map(char::to_lowercase));
+    }
+    match id.char_indices().nth(MAX_ID_LENGTH) {
+        Some((end, _)) => id[..end].trim_end_matches('_').to_string(),
+        None => id,
+    }
+}
@@ -0,0 +1,53 @@
+//! # Lose It! Importer
+//!
+//! Reads the food log export of Lose It! (Insights, Export to spreadsheet),
+//! which has one row per logged food:
+//!
+//! ```text
+//! Date,Name,Icon,Meal,Quantity,Units,Calories,Fat (g),Protein (g),Carbohydrates (g),Fiber (g),...
+//! 03/05/2024,Banana,Banana,Breakfast,1,Each,105,0.4,1.3,27,3.1,...
+//! ```
+//!
+//! Dates are `MM/DD/YYYY`. Exercise rows (meal `Exercise`) are left out;
+//! the meals `Breakfast`, `Lunch`, `Dinner`, and `Snacks` map to meal slots.
+
+// yada-core/src/importers/loseit.rs
+use super::Importer;
+use super::diary::{self, DiaryColumns, DiaryRow};
+
+/// Columns of Lose It! exports
+const COLUMNS: DiaryColumns = DiaryColumns {
+    app_name: "Lose It!",
+    date: &["date"],
+    date_formats: &["%m/%d/%Y", "%Y-%m-%d"],
+    time: &[],
+    meal: &["meal"],
+    skipped_meals: &["exercise"],
+    food: &["name", "food"],
+    calories: &["calories"],
+    protein: &["protein (g)", "protein"],
+    carbs: &["carbohydrates (g)", "carbohydrates", "carbs (g)"],
+    fat: &["fat (g)", "fat"],
+    fiber: &["fiber (g)", "fiber"],
+};
+
+/// Importer for Lose It! food log exports
+pub struct LoseItImporter;
+
+impl Importer for LoseItImporter {
+    fn name(&self) -> &'static str {
+        "loseit"
+    }
+
+    fn app_name(&self) -> &'static str {
+        "Lose It!"
+    }
+
+    fn description(&self) -> &'static str {
+        "Food log spreadsheet export"
+    }
+
+    fn read_rows(&self, text: &str) -> Result<Vec<DiaryRow>, String> {
+        diary::read_rows(text, &COLUMNS)
+    }
+}
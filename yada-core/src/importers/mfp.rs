@@ -0,0 +1,53 @@
+//! # MyFitnessPal Importer
+//!
+//! Reads MyFitnessPal CSV exports. Two layouts are understood:
+//!
+//! - **Per food** (`Date,Meal,Food,Calories,...`, as written by diary
+//!   exporters): one row per logged food
+//! - **Per meal** (the "Nutrition" export of MyFitnessPal itself, without a
+//!   food column): one row with the totals of each meal of each day
+//!
+//! Dates may be `YYYY-MM-DD` or `MM/DD/YYYY`; the meals `Breakfast`,
+//! `Lunch`, `Dinner`, and `Snacks` map to meal slots (custom meals have
+//! none).
+
+// yada-core/src/importers/mfp.rs
+use super::Importer;
+use super::diary::{self, DiaryColumns, DiaryRow};
+
+/// Columns of MyFitnessPal exports
+const COLUMNS: DiaryColumns = DiaryColumns {
+    app_name: "MyFitnessPal",
+    date: &["date"],
+    date_formats: &["%Y-%m-%d", "%m/%d/%Y"],
+    time: &["time"],
+    meal: &["meal"],
+    skipped_meals: &[],
+    food: &["food", "food name", "name", "description"],
+    calories: &["calories", "calories (kcal)", "energy (kcal)"],
+    protein: &["protein (g)", "protein"],
+    carbs: &["carbohydrates (g)", "carbohydrates", "carbs (g)", "carbs"],
+    fat: &["fat (g)", "fat", "total fat (g)"],
+    fiber: &["fiber (g)", "fiber", "fibre"],
+};
+
+/// Importer for MyFitnessPal CSV exports
+pub struct MfpImporter;
+
+impl Importer for MfpImporter {
+    fn name(&self) -> &'static str {
+        "mfp"
+    }
+
+    fn app_name(&self) -> &'static str {
+        "MyFitnessPal"
+    }
+
+    fn description(&self) -> &'static str {
+        "CSV export, one row per food or per meal"
+    }
+
+    fn read_rows(&self, text: &str) -> Result<Vec<DiaryRow>, String> {
+        diary::read_rows(text, &COLUMNS)
+    }
+}
@@ -0,0 +1,118 @@
+//! # Importers Module
+//!
+//! This module brings food diaries kept in other apps into YADA. Each app's
+//! export format has an `Importer` that reads it into diary rows; the rows
+//! then go through the same steps for every app: missing foods are created
+//! (with the ID prefix `<importer name>_`) and the entries are logged with
+//! `ingest::import_foods` and `ingest::import_log_entries`.
+//!
+//! ## Factory Pattern
+//!
+//! `ImporterFactory` holds the available importers by name, the same way
+//! `FoodSourceFactory` holds food sources, so menus and the command line list
+//! them without knowing the formats. Other formats are added by implementing
+//! `Importer` and calling `register_importer`.
+//!
+//! ## Built-in Importers
+//!
+//! - **mfp**: MyFitnessPal CSV exports, per food or per meal
+//! - **cronometer**: Cronometer "Servings" exports (`servings.csv`)
+//! - **loseit**: Lose It! food log exports
+//!
+//! ## Module Organization
+//!
+//! - `diary`: Diary rows, column matching, and the shared import steps
+//! - `mfp`: MyFitnessPal
+//! - `cronometer`: Cronometer
+//! - `loseit`: Lose It!
+
+// yada-core/src/importers/mod.rs
+pub mod diary;
+pub mod mfp;
+pub mod cronometer;
+pub mod loseit;
+
+use std::collections::HashMap;
+
+use crate::repositories::food_repository::FoodRepository;
+use crate::repositories::log_repository::LogRepository;
+use diary::{DiaryRow, ImportReport};
+
+/// Reader for another app's diary export
+pub trait Importer {
+    /// Short name used on the command line and as food ID prefix, e.g. `mfp`
+    fn name(&self) -> &'static str;
+
+    /// Name of the app the export comes from, e.g. `MyFitnessPal`
+    fn app_name(&self) -> &'static str;
+
+    /// What kind of file to export from the app
+    fn description(&self) -> &'static str;
+
+    /// Reads the rows of an export
+    ///
+    /// # Returns
+    /// * `Ok(Vec<DiaryRow>)` - One row per food or meal entry
+    /// * `Err(String)` - If the text is not an export of this app or a row cannot be read
+    fn read_rows(&self, text: &str) -> Result<Vec<DiaryRow>, String>;
+
+    /// Imports an export into the food database and log
+    ///
+    /// Nothing is saved, so a dry run simply discards the repositories.
+    ///
+    /// # Arguments
+    /// * `food_repo` - Food database that receives the missing foods
+    /// * `log_repo` - Food log that receives the entries
+    /// * `text` - Contents of the export
+    ///
+    /// # Returns
+    /// * `Ok(ImportReport)` - What was created and added
+    /// * `Err(String)` - If the text cannot be read; nothing is changed then
+    fn import(&self, food_repo: &mut FoodRepository, log_repo: &mut LogRepository, text: &str) -> Result<ImportReport, String> {
+        let rows = self.read_rows(text)?;
+        Ok(diary::import_rows(food_repo, log_repo, self.name(), self.app_name(), &rows))
+    }
+}
+
+/// Registry of the available importers
+pub struct ImporterFactory {
+    importers: HashMap<String, Box<dyn Importer>>,
+}
+
+impl Default for ImporterFactory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ImporterFactory {
+    /// Creates a factory with the built-in importers
+    pub fn new() -> Self {
+        let mut factory = ImporterFactory {
+            importers: HashMap::new(),
+        };
+
+        factory.register_importer(Box::new(mfp::MfpImporter));
+        factory.register_importer(Box::new(cronometer::CronometerImporter));
+        factory.register_importer(Box::new(loseit::LoseItImporter));
+
+        factory
+    }
+
+    /// Adds an importer, replacing one with the same name
+    pub fn register_importer(&mut self, importer: Box<dyn Importer>) {
+        self.importers.insert(importer.name().to_string(), importer);
+    }
+
+    /// Looks up an importer by name (case-insensitive)
+    pub fn get_importer(&self, name: &str) -> Option<&dyn Importer> {
+        self.importers.get(&name.trim().to_lowercase()).map(|boxed| boxed.as_ref())
+    }
+
+    /// All importers, ordered by app name
+    pub fn get_all_importers(&self) -> Vec<&dyn Importer> {
+        let mut importers: Vec<&dyn Importer> = self.importers.values().map(|boxed| boxed.as_ref()).collect();
+        importers.sort_by_key(|importer| importer.app_name().to_lowercase());
+        importers
+    }
+}
@@ -0,0 +1,236 @@
+//! # CSV Records
+//!
+//! Builds ingest records from CSV text. The first row names the columns, so
+//! columns may appear in any order and optional ones may be left out.
+//! Fields follow the usual CSV quoting rules: a field containing commas,
+//! quotes, or line breaks is wrapped in double quotes, and quotes inside it
+//! are doubled.
+//!
+//! ## Food Columns
+//!
+//! `id`, `name`, and `calories` are required. Optional: `keywords`,
+//! `protein`, `carbs`, `fat`, `fiber`, `grams`, `cost`, `category`, `tags`.
+//! Keywords and tags are separated by semicolons (`fruit;apple`).
+//!
+//! ```text
+//! id,name,calories,keywords,protein,grams
+//! apple,Apple (medium),95,fruit;apple,0.5,182
+//! ```
+//!
+//! ## Log Entry Columns
+//!
+//! `date` (`YYYY-MM-DD`) and `food_id` are required. Optional: `amount`
+//! (servings or a unit such as `85 g`; default 1 serving), `meal`
+//! (breakfast, lunch, dinner, snack), and `timestamp`
+//! (`YYYY-MM-DDTHH:MM:SS`, local time).
+//!
+//! Empty optional fields count as missing. A row that cannot be parsed
+//! fails the whole text with its row number; whether the values make sense
+//! is checked later by the import functions.
+
+// yada-core/src/ingest/csv.rs
+use std::collections::HashMap;
+
+use chrono::{NaiveDate, NaiveDateTime};
+
+use crate::ingest::records::{FoodRecord, LogEntryRecord};
+use crate::models::meal::MealSlot;
+use crate::models::quantity::Quantity;
+
+/// Parses food records from CSV text with a header row
+///
+/// # Returns
+/// * `Ok(Vec<FoodRecord>)` - One record per data row
+/// * `Err(String)` - If a required column is missing or a number cannot be parsed
+pub fn food_records(text: &str) -> Result<Vec<FoodRecord>, String> {
+    let table = Table::parse(text, &["id", "name", "calories"])?;
+    table.rows().map(|row| {
+        Ok(FoodRecord {
+            id: row.text("id").unwrap_or_default(),
+            name: row.text("name").unwrap_or_default(),
+            calories: row.number("calories")?.unwrap_or(0.0),
+            keywords: row.list("keywords"),
+            protein: row.number("protein")?.unwrap_or(0.0),
+            carbs: row.number("carbs")?.unwrap_or(0.0),
+            fat: row.number("fat")?.unwrap_or(0.0),
+            fiber: row.number("fiber")?.unwrap_or(0.0),
+            grams: row.number("grams")?,
+            cost: row.number("cost")?,
+            category: row.text("category"),
+            tags: row.list("tags"),
+        })
+    }).collect()
+}
+
+/// Parses food log entry records from CSV text with a header row
+///
+/// # Returns
+/// * `Ok(Vec<LogEntryRecord>)` - One record per data row
+/// * `Err(String)` - If a required column is missing or a value cannot be parsed
+pub fn log_entry_records(text: &str) -> Result<Vec<LogEntryRecord>, String> {
+    let table = Table::parse(text, &["date", "food_id"])?;
+    table.rows().map(|row| {
+        let date = row.parse("date", |value| NaiveDate::parse_from_str(value, "%Y-%m-%d").ok(), "a YYYY-MM-DD date")?
+            .ok_or_else(|| row.error("date is missing"))?;
+        let quantity = match row.text("amount") {
+            Some(amount) => Quantity::parse(&amount).map_err(|e| row.error(&e))?,
+            None => Quantity::servings(1.0),
+        };
+        Ok(LogEntryRecord {
+            date,
+            food_id: row.text("food_id").unwrap_or_default(),
+            quantity,
+            meal: row.parse("meal", MealSlot::parse, "breakfast, lunch, dinner, or snack")?,
+            timestamp: row.parse("timestamp", parse_timestamp, "a YYYY-MM-DDTHH:MM:SS time")?,
+        })
+    }).collect()
+}
+
+/// Parses a local timestamp with a `T` or a space between date and time
+fn parse_timestamp(value: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S"))
+        .ok()
+}
+
+/// CSV text split into a header and data rows
+pub(crate) struct Table {
+    /// Column index for each lowercased header name
+    columns: HashMap<String, usize>,
+    /// Data rows, without the header
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    /// Splits CSV text into rows and checks that the required columns exist
+    pub(crate) fn parse(text: &str, required: &[&str]) -> Result<Self, String> {
+        let mut rows = split_rows(text)?.into_iter();
+        let header = rows.next().ok_or("CSV text has no header row")?;
+        let columns: HashMap<String, usize> = header.iter()
+            .enumerate()
+            .map(|(index, name)| (name.trim().to_lowercase(), index))
+            .collect();
+        if let Some(missing) = required.iter().find(|name| !columns.contains_key(**name)) {
+            return Err(format!("CSV header has no '{}' column", missing));
+        }
+        Ok(Table { columns, rows: rows.collect() })
+    }
+
+    /// Returns true if the header has any of the column names
+    pub(crate) fn has_any(&self, columns: &[&str]) -> bool {
+        columns.iter().any(|column| self.columns.contains_key(*column))
+    }
+
+    /// Iterates over the data rows
+    pub(crate) fn rows(&self) -> impl Iterator<Item = Row<'_>> {
+        self.rows.iter().enumerate().map(|(index, fields)| Row {
+            table: self,
+            fields,
+            number: index + 2,
+        })
+    }
+}
+
+/// One data row of a `Table`
+pub(crate) struct Row<'a> {
+    /// The table the row belongs to
+    table: &'a Table,
+    /// The row's fields, in column order
+    fields: &'a [String],
+    /// Row number in the text, counting the header as row 1
+    number: usize,
+}
+
+impl Row<'_> {
+    /// Returns a trimmed, non-empty field
+    pub(crate) fn text(&self, column: &str) -> Option<String> {
+        let index = *self.table.columns.get(column)?;
+        self.fields.get(index)
+            .map(|field| field.trim())
+            .filter(|field| !field.is_empty())
+            .map(str::to_string)
+    }
+
+    /// Parses an optional field, naming the expected format on failure
+    pub(crate) fn parse<T>(&self, column: &str, parse: impl Fn(&str) -> Option<T>, expected: &str) -> Result<Option<T>, String> {
+        match self.text(column) {
+            Some(value) => parse(&value)
+                .map(Some)
+                .ok_or_else(|| self.error(&format!("{} '{}' is not {}", column, value, expected))),
+            None => Ok(None),
+        }
+    }
+
+    /// Parses an optional numeric field
+    pub(crate) fn number(&self, column: &str) -> Result<Option<f64>, String> {
+        self.parse(column, |value| value.parse::<f64>().ok(), "a number")
+    }
+
+    /// Returns the first trimmed, non-empty field among alternative column names
+    pub(crate) fn text_any(&self, columns: &[&str]) -> Option<String> {
+        columns.iter().find_map(|column| self.text(column))
+    }
+
+    /// Parses the first numeric field among alternative column names
+    ///
+    /// Thousands separators (`1,250`) are accepted.
+    pub(crate) fn number_any(&self, columns: &[&str]) -> Result<Option<f64>, String> {
+        let Some(column) = columns.iter().find(|column| self.text(column).is_some()) else {
+            return Ok(None);
+        };
+        self.parse(column, |value| value.replace(',', "").parse::<f64>().ok(), "a number")
+    }
+
+    /// Splits an optional field on semicolons
+    fn list(&self, column: &str) -> Vec<String> {
+        self.text(column)
+            .map(|value| value.split(';').map(|item| item.trim().to_string()).filter(|item| !item.is_empty()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Prefixes a message with the row number
+    pub(crate) fn error(&self, message: &str) -> String {
+        format!("row {}: {}", self.number, message)
+    }
+}
+
+/// Splits CSV text into rows of fields, honoring double quotes
+///
+/// Blank lines are ignored. Both `\n` and `\r\n` line endings are accepted.
+fn split_rows(text: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', true) => in_quotes = false,
+            ('"', false) if field.is_empty() => in_quotes = true,
+            (',', false) => row.push(std::mem::take(&mut field)),
+            ('\r', false) if chars.peek() == Some(&'\n') => {}
+            ('\n', false) => {
+                row.push(std::mem::take(&mut field));
+                if row.iter().any(|field| !field.trim().is_empty()) {
+                    rows.push(std::mem::take(&mut row));
+                } else {
+                    row.clear();
+                }
+            }
+            (c, _) => field.push(c),
+        }
+    }
+    if in_quotes {
+        return Err("CSV text ends inside a quoted field".to_string());
+    }
+    row.push(field);
+    if row.iter().any(|field| !field.trim().is_empty()) {
+        rows.push(row);
+    }
+    Ok(rows)
+}
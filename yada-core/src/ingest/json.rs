@@ -0,0 +1,167 @@
+//! # JSON Records
+//!
+//! Builds ingest records from JSON text: an array with one object per
+//! record. Keys match the CSV column names (see the `csv` module), so the
+//! same required and optional fields apply. A missing key and `null` both
+//! count as missing; unknown keys are ignored.
+//!
+//! ## Food Objects
+//!
+//! `id` and `name` are strings, `calories` and the other nutrients are
+//! numbers, and `keywords` and `tags` are arrays of strings.
+//!
+//! ```text
+//! [{"id": "apple", "name": "Apple (medium)", "calories": 95, "keywords": ["fruit", "apple"], "grams": 182}]
+//! ```
+//!
+//! ## Log Entry Objects
+//!
+//! `date` and `food_id` are required. `amount` is either a number of
+//! servings or a string with a unit (`"85 g"`); `meal` and `timestamp` are
+//! strings in the same formats as in CSV.
+//!
+//! A record that cannot be parsed fails the whole text with its one-based
+//! record number; whether the values make sense is checked later by the
+//! import functions.
+
+// yada-core/src/ingest/json.rs
+use chrono::{NaiveDate, NaiveDateTime};
+use serde_json::{Map, Value};
+
+use crate::ingest::records::{FoodRecord, LogEntryRecord};
+use crate::models::meal::MealSlot;
+use crate::models::quantity::Quantity;
+
+/// Parses food records from a JSON array of objects
+///
+/// # Returns
+/// * `Ok(Vec<FoodRecord>)` - One record per object
+/// * `Err(String)` - If the text is not an array of objects, or a field is missing or has the wrong type
+pub fn food_records(text: &str) -> Result<Vec<FoodRecord>, String> {
+    objects(text)?.into_iter().map(|object| {
+        Ok(FoodRecord {
+            id: object.required("id", Object::text)?,
+            name: object.required("name", Object::text)?,
+            calories: object.required("calories", Object::number)?,
+            keywords: object.list("keywords")?,
+            protein: object.number("protein")?.unwrap_or(0.0),
+            carbs: object.number("carbs")?.unwrap_or(0.0),
+            fat: object.number("fat")?.unwrap_or(0.0),
+            fiber: object.number("fiber")?.unwrap_or(0.0),
+            grams: object.number("grams")?,
+            cost: object.number("cost")?,
+            category: object.text("category")?,
+            tags: object.list("tags")?,
+        })
+    }).collect()
+}
+
+/// Parses food log entry records from a JSON array of objects
+///
+/// # Returns
+/// * `Ok(Vec<LogEntryRecord>)` - One record per object
+/// * `Err(String)` - If the text is not an array of objects, or a field is missing or cannot be parsed
+pub fn log_entry_records(text: &str) -> Result<Vec<LogEntryRecord>, String> {
+    objects(text)?.into_iter().map(|object| {
+        let date = object.required("date", |object, key| {
+            object.parse(key, |value| NaiveDate::parse_from_str(value, "%Y-%m-%d").ok(), "a YYYY-MM-DD date")
+        })?;
+        let quantity = match object.fields.get("amount") {
+            None | Some(Value::Null) => Quantity::servings(1.0),
+            Some(Value::Number(number)) => Quantity::servings(number.as_f64().unwrap_or(f64::NAN)),
+            Some(Value::String(amount)) => Quantity::parse(amount).map_err(|e| object.error(&e))?,
+            Some(_) => return Err(object.error("amount must be a number or a string")),
+        };
+        Ok(LogEntryRecord {
+            date,
+            food_id: object.required("food_id", Object::text)?,
+            quantity,
+            meal: object.parse("meal", MealSlot::parse, "breakfast, lunch, dinner, or snack")?,
+            timestamp: object.parse("timestamp", parse_timestamp, "a YYYY-MM-DDTHH:MM:SS time")?,
+        })
+    }).collect()
+}
+
+/// Parses a local timestamp with a `T` or a space between date and time
+fn parse_timestamp(value: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S"))
+        .ok()
+}
+
+/// Parses the text into its record objects
+fn objects(text: &str) -> Result<Vec<Object>, String> {
+    let value: Value = serde_json::from_str(text).map_err(|e| format!("invalid JSON: {}", e))?;
+    let Value::Array(items) = value else {
+        return Err("JSON text is not an array of records".to_string());
+    };
+    items.into_iter()
+        .enumerate()
+        .map(|(index, item)| match item {
+            Value::Object(fields) => Ok(Object { fields, number: index + 1 }),
+            _ => Err(format!("record {}: not an object", index + 1)),
+        })
+        .collect()
+}
+
+/// One record object of the array
+struct Object {
+    /// The object's keys and values
+    fields: Map<String, Value>,
+    /// One-based position of the record in the array
+    number: usize,
+}
+
+impl Object {
+    /// Returns a trimmed, non-empty string field
+    fn text(&self, key: &str) -> Result<Option<String>, String> {
+        match self.fields.get(key) {
+            None | Some(Value::Null) => Ok(None),
+            Some(Value::String(value)) => Ok(Some(value.trim().to_string()).filter(|value| !value.is_empty())),
+            Some(_) => Err(self.error(&format!("{} must be a string", key))),
+        }
+    }
+
+    /// Returns a numeric field
+    fn number(&self, key: &str) -> Result<Option<f64>, String> {
+        match self.fields.get(key) {
+            None | Some(Value::Null) => Ok(None),
+            Some(Value::Number(value)) => Ok(value.as_f64()),
+            Some(_) => Err(self.error(&format!("{} must be a number", key))),
+        }
+    }
+
+    /// Parses an optional string field, naming the expected format on failure
+    fn parse<T>(&self, key: &str, parse: impl Fn(&str) -> Option<T>, expected: &str) -> Result<Option<T>, String> {
+        match self.text(key)? {
+            Some(value) => parse(&value)
+                .map(Some)
+                .ok_or_else(|| self.error(&format!("{} '{}' is not {}", key, value, expected))),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns a field that every record must have
+    fn required<T>(&self, key: &str, field: impl Fn(&Self, &str) -> Result<Option<T>, String>) -> Result<T, String> {
+        field(self, key)?.ok_or_else(|| self.error(&format!("{} is missing", key)))
+    }
+
+    /// Returns an optional array of strings
+    fn list(&self, key: &str) -> Result<Vec<String>, String> {
+        match self.fields.get(key) {
+            None | Some(Value::Null) => Ok(Vec::new()),
+            Some(Value::Array(items)) => items.iter()
+                .map(|item| item.as_str()
+                    .map(|item| item.trim().to_string())
+                    .ok_or_else(|| self.error(&format!("{} must be an array of strings", key))))
+                .filter(|item| !matches!(item, Ok(item) if item.is_empty()))
+                .collect(),
+            Some(_) => Err(self.error(&format!("{} must be an array of strings", key))),
+        }
+    }
+
+    /// Prefixes a message with the record number
+    fn error(&self, message: &str) -> String {
+        format!("record {}: {}", self.number, message)
+    }
+}
@@ -0,0 +1,245 @@
+//! # Ingest Module
+//!
+//! This module lets other programs push data into YADA without going through
+//! the interactive UI, for example a sync daemon that mirrors a nutrition
+//! database or a shared household log. Callers build plain records (by hand,
+//! or from CSV or JSON with the `csv` and `json` helpers), and the import
+//! functions validate them and apply them to the repositories.
+//!
+//! ## Import Functions
+//!
+//! - **import_foods**: Adds or replaces basic foods
+//! - **import_log_entries**: Adds log entries for foods that exist in the food database
+//!
+//! Diary exports of other apps are read by the `importers` module, which
+//! creates foods and log entries through these functions.
+//!
+//! Every record is validated on its own. Invalid records are reported with
+//! their position and skipped; valid records are applied. Nothing is saved:
+//! the caller decides when to call `save()` on the repositories, so an
+//! import can be inspected (or discarded) first. Foods with suspicious
+//! values (see `models::validation::food_warnings`) are imported, and listed
+//! as warnings so the caller can point them out. Imports are not undoable
+//! commands; reload the repository to discard them. Callers that wrap an
+//! import in a command should report `CommandType::Import`, which the
+//! command manager does not keep for undo by default.
+//!
+//! ## Conflict Policies
+//!
+//! A record conflicts with existing data when a food with the same ID exists,
+//! or when a log entry for the same date, food, and timestamp exists (log
+//! records without a timestamp never conflict). The `ConflictPolicy` decides
+//! what happens:
+//! - **Skip**: Keep the existing data and count the record as skipped
+//! - **Replace**: Overwrite the existing data with the record
+//! - **Reject**: Report the record as an error
+//!
+//! ## Module Organization
+//!
+//! - `records`: Food and log entry records with validation
+//! - `csv`: Parsing records from CSV text with a header row
+//! - `json`: Parsing records from a JSON array of objects
+
+// yada-core/src/ingest/mod.rs
+pub mod records;
+pub mod csv;
+pub mod json;
+
+use chrono::{Local, TimeZone};
+
+use crate::models::food::FoodType;
+use crate::models::validation;
+use crate::repositories::food_repository::FoodRepository;
+use crate::repositories::log_repository::LogRepository;
+use records::{FoodRecord, LogEntryRecord};
+
+/// What to do with a record that conflicts with existing data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// Keep the existing data
+    Skip,
+
+    /// Overwrite the existing data
+    Replace,
+
+    /// Report the record as an error
+    Reject,
+}
+
+/// A record that could not be imported
+#[derive(Debug, Clone, PartialEq)]
+pub struct IngestError {
+    /// Zero-based position of the record in the input
+    pub index: usize,
+
+    /// Why the record was not imported
+    pub message: String,
+}
+
+/// Outcome of an import
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IngestReport {
+    /// Records added as new foods or entries
+    pub added: usize,
+
+    /// Records that replaced existing data
+    pub replaced: usize,
+
+    /// Conflicting records left out under `ConflictPolicy::Skip`
+    pub skipped: usize,
+
+    /// Records that failed validation or were rejected
+    pub errors: Vec<IngestError>,
+
+    /// Imported records with values that look like typos, one per value
+    pub warnings: Vec<IngestError>,
+}
+
+impl IngestReport {
+    /// Reports whether every record was imported or deliberately skipped
+    pub fn is_clean(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Records an error for the record at `index`
+    fn error(&mut self, index: usize, message: String) {
+        self.errors.push(IngestError { index, message });
+    }
+
+    /// Records the warnings of the imported record at `index`
+    fn warn(&mut self, index: usize, warnings: Vec<String>) {
+        self.warnings.extend(warnings.into_iter().map(|message| IngestError { index, message }));
+    }
+}
+
+/// Imports basic foods into the food database
+///
+/// Replacing a food keeps its usage history (last used date, archived flag)
+/// and recalculates the composite foods built from it. Composite foods are
+/// never replaced by a record.
+///
+/// # Arguments
+/// * `food_repo` - The food database to import into
+/// * `records` - Foods to import, in order
+/// * `policy` - What to do with records whose ID already exists
+///
+/// # Returns
+/// Counts of added, replaced, and skipped records, the records that failed,
+/// and warnings about the values of imported foods
+pub fn import_foods(food_repo: &mut FoodRepository, records: &[FoodRecord], policy: ConflictPolicy) -> IngestReport {
+    let mut report = IngestReport::default();
+
+    for (index, record) in records.iter().enumerate() {
+        let mut food = match record.to_food() {
+            Ok(food) => food,
+            Err(message) => {
+                report.error(index, message);
+                continue;
+            }
+        };
+        let warnings: Vec<String> = validation::food_warnings(&food).into_iter()
+            .map(|warning| format!("{}: {}", food.id, warning))
+            .collect();
+
+        let Some(existing) = food_repo.get_food(&food.id) else {
+            match food_repo.add_food(food) {
+                Ok(()) => {
+                    report.added += 1;
+                    report.warn(index, warnings);
+                }
+                Err(message) => report.error(index, message),
+            }
+            continue;
+        };
+
+        match policy {
+            ConflictPolicy::Skip => report.skipped += 1,
+            ConflictPolicy::Reject => report.error(index, format!("Food with ID {} already exists", food.id)),
+            ConflictPolicy::Replace if existing.food_type != FoodType::Basic => {
+                report.error(index, format!("Food with ID {} is a composite food and cannot be replaced", food.id));
+            }
+            ConflictPolicy::Replace => {
+                food.last_used = existing.last_used;
+                food.archived = existing.archived;
+                match food_repo.update_food(food) {
+                    Ok(_) => {
+                        report.replaced += 1;
+                        report.warn(index, warnings);
+                    }
+                    Err(message) => report.error(index, message),
+                }
+            }
+        }
+    }
+
+    if report.replaced > 0 {
+        food_repo.recalculate_composites();
+    }
+    report
+}
+
+/// Imports food log entries
+///
+/// Each record must name a food in the food database, in an amount the food
+/// can be measured in. Entries without a timestamp are stamped with the
+/// current time.
+///
+/// # Arguments
+/// * `log_repo` - The food log to import into
+/// * `food_repo` - The food database used to validate food IDs and amounts
+/// * `records` - Entries to import, in order
+/// * `policy` - What to do with records matching an existing entry (same date, food, and timestamp)
+///
+/// # Returns
+/// Counts of added, replaced, and skipped records, and the records that failed
+pub fn import_log_entries(
+    log_repo: &mut LogRepository,
+    food_repo: &FoodRepository,
+    records: &[LogEntryRecord],
+    policy: ConflictPolicy,
+) -> IngestReport {
+    let mut report = IngestReport::default();
+
+    for (index, record) in records.iter().enumerate() {
+        let mut entry = match record.to_entry(food_repo.get_foods()) {
+            Ok(entry) => entry,
+            Err(message) => {
+                report.error(index, message);
+                continue;
+            }
+        };
+        if let Some(time) = record.timestamp {
+            match Local.from_local_datetime(&time).earliest() {
+                Some(timestamp) => entry.timestamp = timestamp,
+                None => {
+                    report.error(index, format!("Timestamp {} does not exist in the local time zone", time));
+                    continue;
+                }
+            }
+        }
+
+        let log = log_repo.get_log_mut(record.date);
+        let existing = record.timestamp.and_then(|time| {
+            log.entries.iter().position(|other| {
+                other.food_id == entry.food_id && other.timestamp.naive_local() == time
+            })
+        });
+
+        match (existing, policy) {
+            (None, _) => {
+                log.insert_entry(entry);
+                report.added += 1;
+            }
+            (Some(_), ConflictPolicy::Skip) => report.skipped += 1,
+            (Some(_), ConflictPolicy::Reject) => {
+                report.error(index, format!("An entry for {} at {} already exists", entry.food_id, entry.timestamp.format("%Y-%m-%d %H:%M:%S")));
+            }
+            (Some(position), ConflictPolicy::Replace) => {
+                log.entries[position] = entry;
+                report.replaced += 1;
+            }
+        }
+    }
+
+    report
+}
@@ -0,0 +1,184 @@
+//! # Ingest Records
+//!
+//! Plain data records accepted by the import functions, and their
+//! validation. Records mirror what the interactive UI asks for, and are
+//! checked by the same rules:
+//!
+//! - Food IDs are non-empty and contain no whitespace; names are non-empty
+//! - Calories and macronutrients are finite and not negative
+//! - Serving weights are positive, costs are not negative
+//! - Log amounts are positive and the food can be measured in their unit
+//!
+//! Keywords and tags are trimmed and lowercased like the UI does. A food
+//! without a category is given one guessed from its name and keywords (see
+//! `models::category::infer`), if any word suggests one.
+
+// yada-core/src/ingest/records.rs
+use std::collections::{HashMap, HashSet};
+
+use chrono::{NaiveDate, NaiveDateTime};
+
+use crate::models::category;
+use crate::models::food::{Food, Macros};
+use crate::models::log::FoodEntry;
+use crate::models::meal::MealSlot;
+use crate::models::quantity::Quantity;
+
+/// A basic food to import
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FoodRecord {
+    /// Unique food ID (no whitespace)
+    pub id: String,
+
+    /// Display name
+    pub name: String,
+
+    /// Calories per serving
+    pub calories: f64,
+
+    /// Search keywords
+    pub keywords: Vec<String>,
+
+    /// Protein per serving in grams
+    pub protein: f64,
+
+    /// Carbohydrates per serving in grams
+    pub carbs: f64,
+
+    /// Fat per serving in grams
+    pub fat: f64,
+
+    /// Fiber per serving in grams
+    pub fiber: f64,
+
+    /// Weight of one serving in grams, if known
+    pub grams: Option<f64>,
+
+    /// Price of one serving, if known
+    pub cost: Option<f64>,
+
+    /// Food group such as `Dairy`
+    pub category: Option<String>,
+
+    /// Structured tags such as `vegan`
+    pub tags: Vec<String>,
+}
+
+impl FoodRecord {
+    /// Validates the record and builds the food it describes
+    ///
+    /// # Returns
+    /// * `Ok(Food)` - A basic food ready to be added to a repository
+    /// * `Err(String)` - The first validation problem found
+    pub fn to_food(&self) -> Result<Food, String> {
+        let id = self.id.trim();
+        if id.is_empty() {
+            return Err("Food ID cannot be empty".to_string());
+        }
+        if id.chars().any(char::is_whitespace) {
+            return Err(format!("Food ID '{}' cannot contain spaces", id));
+        }
+        if self.name.trim().is_empty() {
+            return Err(format!("Food '{}' has no name", id));
+        }
+        for (label, value) in [
+            ("calories", self.calories),
+            ("protein", self.protein),
+            ("carbs", self.carbs),
+            ("fat", self.fat),
+            ("fiber", self.fiber),
+        ] {
+            non_negative(id, label, value)?;
+        }
+        if let Some(grams) = self.grams.filter(|grams| !grams.is_finite() || *grams <= 0.0) {
+            return Err(format!("Food '{}' has an invalid serving weight: {}", id, grams));
+        }
+        if let Some(cost) = self.cost {
+            non_negative(id, "cost", cost)?;
+        }
+
+        let keywords = normalize(&self.keywords);
+        let category = self.category.as_deref().and_then(category::normalize)
+            .or_else(|| category::infer(&self.name, &keywords).map(str::to_string));
+        let food = Food::new_basic(id.to_string(), self.name.trim().to_string(), keywords, self.calories)
+            .with_macros(Macros::new(self.protein, self.carbs, self.fat).with_fiber(self.fiber))
+            .with_serving_details(self.grams, self.cost)
+            .with_metadata(category, normalize(&self.tags));
+        Ok(food)
+    }
+}
+
+/// A food log entry to import
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogEntryRecord {
+    /// Day the food was eaten
+    pub date: NaiveDate,
+
+    /// ID of a food in the food database
+    pub food_id: String,
+
+    /// Amount eaten, in servings or a unit
+    pub quantity: Quantity,
+
+    /// Meal the entry belongs to, if any
+    pub meal: Option<MealSlot>,
+
+    /// Local time the food was logged; also identifies the entry for conflicts
+    pub timestamp: Option<NaiveDateTime>,
+}
+
+impl LogEntryRecord {
+    /// Creates a record for an amount of a food, without meal or timestamp
+    pub fn new(date: NaiveDate, food_id: &str, quantity: Quantity) -> Self {
+        LogEntryRecord {
+            date,
+            food_id: food_id.to_string(),
+            quantity,
+            meal: None,
+            timestamp: None,
+        }
+    }
+
+    /// Validates the record and builds the log entry it describes
+    ///
+    /// The entry is stamped with the current time; `import_log_entries`
+    /// applies the record's own timestamp.
+    ///
+    /// # Arguments
+    /// * `food_db` - Foods the entry may refer to
+    ///
+    /// # Returns
+    /// * `Ok(FoodEntry)` - The entry with its amount converted to servings
+    /// * `Err(String)` - If the food is unknown or the amount is invalid
+    pub fn to_entry(&self, food_db: &HashMap<String, Food>) -> Result<FoodEntry, String> {
+        let food_id = self.food_id.trim();
+        let food = food_db.get(food_id)
+            .ok_or_else(|| format!("Food with ID '{}' doesn't exist", food_id))?;
+        if !self.quantity.amount.is_finite() || self.quantity.amount <= 0.0 {
+            return Err(format!("Invalid amount for '{}': {}", food_id, self.quantity.amount));
+        }
+        let servings = self.quantity.to_servings(food)?;
+
+        let mut entry = FoodEntry::new(food_id.to_string(), self.quantity, servings);
+        entry.meal = self.meal;
+        entry.record_calories(food);
+        Ok(entry)
+    }
+}
+
+/// Checks that a numeric field is finite and not negative
+fn non_negative(id: &str, label: &str, value: f64) -> Result<(), String> {
+    if value.is_finite() && value >= 0.0 {
+        Ok(())
+    } else {
+        Err(format!("Food '{}' has invalid {}: {}", id, label, value))
+    }
+}
+
+/// Trims and lowercases keywords or tags, dropping empty ones
+fn normalize(values: &[String]) -> HashSet<String> {
+    values.iter()
+        .map(|value| value.trim().to_lowercase())
+        .filter(|value| !value.is_empty())
+        .collect()
+}
@@ -0,0 +1,48 @@
+//! # YADA Core
+//!
+//! This crate contains the frontend-independent logic of YADA (Yet Another
+//! Diet Assistant): data models, file-backed repositories, undoable commands,
+//! calorie calculation strategies, and extensible factories. The `yada` CLI is
+//! a thin layer on top of it, and other frontends (GUI, web) can reuse the
+//! same API.
+//!
+//! ## Design Patterns
+//!
+//! - **Command Pattern**: `models::command`, `models::command_manager`, and `commands`
+//! - **Repository Pattern**: `repositories` for file-based persistence
+//! - **Strategy Pattern**: `strategies` for calorie calculation methods
+//! - **Factory Pattern**: `factories` for extensible food sources, `importers` for other apps' exports
+//! - **Composite Pattern**: `models::food` for basic and composite foods
+//!
+//! ## Module Organization
+//!
+//! - `models`: Core data structures and business logic
+//! - `repositories`: Data access layer for persistent storage
+//! - `commands`: Command pattern implementations for undo functionality
+//! - `strategies`: Strategy pattern for different calorie calculation methods
+//! - `factories`: Factory pattern for creating extensible components
+//! - `ingest`: Validated bulk import of foods and log entries for other tools
+//! - `importers`: Diary exports of other apps (MyFitnessPal, Cronometer, Lose It!)
+//! - `sync`: Synchronization of the data files with a folder, WebDAV, or S3
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use yada_core::repositories::food_repository::FoodRepository;
+//!
+//! let foods = FoodRepository::new("foods.txt")?;
+//! if let Some(apple) = foods.get_food("apple") {
+//!     println!("{}: {} kcal", apple.name, apple.calories_per_serving);
+//! }
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+// yada-core/src/lib.rs
+pub mod models;
+pub mod repositories;
+pub mod commands;
+pub mod strategies;
+pub mod factories;
+pub mod ingest;
+pub mod importers;
+pub mod sync;
@@ -0,0 +1,54 @@
+//! # Age
+//!
+//! Age in complete years from a birth date, used for the age shown in the
+//! profile and by the calorie calculators.
+//!
+//! ## Leap-Day Birthdays
+//!
+//! Someone born on February 29 has a birthday only in leap years. In other
+//! years the birthday is taken to be March 1, the day after February 28, so
+//! a year is completed once February 28 has fully passed.
+//!
+//! ## Dates Before Birth
+//!
+//! A date before the birth date has an age of 0, the same as the first
+//! year of life, so callers never see a negative or wrapped-around age.
+
+// yada-core/src/models/age.rs
+use chrono::{Datelike, NaiveDate};
+
+/// Returns the birthday of someone born on a date in a given year
+///
+/// # Arguments
+/// * `birth_date` - The birth date
+/// * `year` - Year of the birthday
+///
+/// # Returns
+/// * `Some(NaiveDate)` - The birthday (March 1 for February 29 births in common years)
+/// * `None` - If the year is outside the range of dates chrono supports
+pub fn birthday_in(birth_date: NaiveDate, year: i32) -> Option<NaiveDate> {
+    birth_date.with_year(year)
+        .or_else(|| NaiveDate::from_ymd_opt(year, 3, 1))
+}
+
+/// Returns the age in complete years on a date
+///
+/// # Arguments
+/// * `birth_date` - The birth date
+/// * `date` - Day the age is calculated for
+///
+/// # Returns
+/// Completed years, or 0 if the date is before the birth date
+pub fn age_on(birth_date: NaiveDate, date: NaiveDate) -> u32 {
+    if date < birth_date {
+        return 0;
+    }
+    let years = (date.year() - birth_date.year()) as u32;
+    // Comparing (month, day) puts a February 29 birthday after February 28
+    // in every year, so in common years it falls on March 1
+    if (date.month(), date.day()) < (birth_date.month(), birth_date.day()) {
+        years - 1
+    } else {
+        years
+    }
+}
@@ -0,0 +1,103 @@
+//! # Batch Logging
+//!
+//! Several foods typed on one line, so a whole meal can be logged without
+//! going through the food list once per food:
+//!
+//! ```text
+//! apple:1, milk_whole:1, bread_wheat:2, chicken:150 g
+//! ```
+//!
+//! Items are separated by commas. Each is a food ID, optionally followed by
+//! `:` and an amount in servings or with a unit (see `models::quantity`);
+//! without an amount the food's default servings are used (see
+//! `Food::servings_to_log`).
+//!
+//! Parsing only checks the syntax. `resolve` then checks every item against
+//! the food database and reports all problems at once, so a line is either
+//! logged completely or not at all.
+
+// yada-core/src/models/batch_log.rs
+use std::collections::HashMap;
+
+use crate::models::food::Food;
+use crate::models::quantity::Quantity;
+
+/// One food of a batch line as typed
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchItem {
+    /// ID of the food to log
+    pub food_id: String,
+
+    /// Amount typed after `:`, if any
+    pub quantity: Option<Quantity>,
+}
+
+/// One food of a batch line checked against the food database
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedItem {
+    /// ID of the food to log
+    pub food_id: String,
+
+    /// Amount as typed, or the food's default servings
+    pub quantity: Quantity,
+
+    /// The amount in servings of the food
+    pub servings: f64,
+}
+
+/// Parses a batch line such as `apple:1, bread_wheat:2`
+///
+/// # Returns
+/// * `Ok(Vec<BatchItem>)` - The items in the order typed
+/// * `Err(String)` - A message naming the first item that is not valid, or
+///   saying the line has no items
+pub fn parse_batch(text: &str) -> Result<Vec<BatchItem>, String> {
+    let mut items = Vec::new();
+    for part in text.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+        let (food_id, amount) = match part.split_once(':') {
+            Some((food_id, amount)) => (food_id.trim(), Some(amount.trim())),
+            None => (part, None),
+        };
+        if food_id.is_empty() || food_id.contains(char::is_whitespace) {
+            return Err(format!("'{}' does not start with a food ID", part));
+        }
+        let quantity = match amount {
+            Some(amount) => Some(Quantity::parse(amount).map_err(|e| format!("{}: {}", food_id, e))?),
+            None => None,
+        };
+        items.push(BatchItem { food_id: food_id.to_string(), quantity });
+    }
+
+    if items.is_empty() {
+        return Err("Please enter at least one food, e.g. apple:1, milk:0.5".to_string());
+    }
+    Ok(items)
+}
+
+/// Checks parsed items against the food database
+///
+/// # Returns
+/// * `Ok(Vec<ResolvedItem>)` - Every item with its amount in servings
+/// * `Err(Vec<String>)` - One message per item that names an unknown food
+///   or an amount the food cannot be measured in
+pub fn resolve(items: &[BatchItem], food_db: &HashMap<String, Food>) -> Result<Vec<ResolvedItem>, Vec<String>> {
+    let mut resolved = Vec::new();
+    let mut problems = Vec::new();
+    for item in items {
+        let Some(food) = food_db.get(&item.food_id) else {
+            problems.push(format!("Food with ID '{}' doesn't exist.", item.food_id));
+            continue;
+        };
+        let quantity = item.quantity.unwrap_or_else(|| Quantity::servings(food.servings_to_log()));
+        match quantity.to_servings(food) {
+            Ok(servings) => resolved.push(ResolvedItem { food_id: item.food_id.clone(), quantity, servings }),
+            Err(e) => problems.push(format!("{}: {}", item.food_id, e)),
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(resolved)
+    } else {
+        Err(problems)
+    }
+}
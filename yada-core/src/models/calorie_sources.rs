@@ -0,0 +1,154 @@
+//! # Calorie Sources
+//!
+//! Shows what actually drives intake over a period: which foods are eaten
+//! most often, which contribute the most calories, and how the calories
+//! split across food categories (see `models::category`).
+//!
+//! ## Counting
+//!
+//! Every log entry counts once for its food, whatever the amount, and
+//! contributes its calories as logged (see `FoodEntry::calories_of`). Like
+//! the daily totals, entries of foods that no longer exist are left out.
+//! Foods without a category are grouped under `category::UNCATEGORIZED`.
+//!
+//! ## Ordering
+//!
+//! Ties are broken by the other measure and then by name, so the lists are
+//! the same every time they are shown.
+
+// yada-core/src/models/calorie_sources.rs
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::models::category;
+use crate::models::food::Food;
+use crate::models::log::DailyLog;
+
+/// Number of foods the menus list as top foods
+pub const TOP_FOOD_COUNT: usize = 5;
+
+/// How often one food was logged and how many calories it contributed
+#[derive(Debug, Clone, PartialEq)]
+pub struct FoodIntake {
+    /// References a food item in the food database
+    pub food_id: String,
+
+    /// Food name at the time of the summary
+    pub name: String,
+
+    /// Number of log entries of the food
+    pub entries: usize,
+
+    /// Calories of all entries of the food
+    pub calories: f64,
+}
+
+/// Calories contributed by one food category
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategoryShare {
+    /// Category name, or `category::UNCATEGORIZED`
+    pub category: String,
+
+    /// Calories of all entries of foods in the category
+    pub calories: f64,
+
+    /// Share of the period's calories, from 0 to 100
+    pub percent: f64,
+}
+
+/// Foods and categories behind the calories of a period
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CalorieSources {
+    /// Every food logged in the period, most calories first
+    pub foods: Vec<FoodIntake>,
+
+    /// Every category logged in the period, most calories first
+    pub categories: Vec<CategoryShare>,
+
+    /// Calories of all entries in the period
+    pub total_calories: f64,
+
+    /// Number of entries counted
+    pub total_entries: usize,
+}
+
+impl CalorieSources {
+    /// Adds up the entries of the given logs by food and by category
+    ///
+    /// # Arguments
+    /// * `logs` - Daily logs of the period (see `LogRepository::get_logs_between`)
+    /// * `food_db` - HashMap of all available foods for names, categories, and calories
+    pub fn summarize<'a>(logs: impl IntoIterator<Item = &'a DailyLog>, food_db: &HashMap<String, Food>) -> Self {
+        let mut foods: HashMap<&str, FoodIntake> = HashMap::new();
+        for entry in logs.into_iter().flat_map(|log| &log.entries) {
+            let Some(food) = food_db.get(&entry.food_id) else {
+                continue;
+            };
+            let intake = foods.entry(entry.food_id.as_str()).or_insert_with(|| FoodIntake {
+                food_id: entry.food_id.clone(),
+                name: food.name.clone(),
+                entries: 0,
+                calories: 0.0,
+            });
+            intake.entries += 1;
+            intake.calories += entry.calories_of(food);
+        }
+
+        let mut foods: Vec<FoodIntake> = foods.into_values().collect();
+        foods.sort_by(by_calories);
+        let total_calories: f64 = foods.iter().map(|food| food.calories).sum();
+        let total_entries = foods.iter().map(|food| food.entries).sum();
+
+        let mut by_category: HashMap<String, f64> = HashMap::new();
+        for intake in &foods {
+            let name = food_db.get(&intake.food_id)
+                .and_then(|food| food.category.clone())
+                .unwrap_or_else(|| category::UNCATEGORIZED.to_string());
+            *by_category.entry(name).or_insert(0.0) += intake.calories;
+        }
+        let mut categories: Vec<CategoryShare> = by_category.into_iter()
+            .map(|(category, calories)| CategoryShare {
+                category,
+                calories,
+                percent: if total_calories > 0.0 { calories / total_calories * 100.0 } else { 0.0 },
+            })
+            .collect();
+        categories.sort_by(|a, b| b.calories.total_cmp(&a.calories).then_with(|| a.category.cmp(&b.category)));
+
+        CalorieSources { foods, categories, total_calories, total_entries }
+    }
+
+    /// Returns true if no entries were counted
+    pub fn is_empty(&self) -> bool {
+        self.foods.is_empty()
+    }
+
+    /// Returns the foods logged most often, at most `limit` of them
+    pub fn top_by_frequency(&self, limit: usize) -> Vec<&FoodIntake> {
+        let mut foods: Vec<&FoodIntake> = self.foods.iter().collect();
+        foods.sort_by(|a, b| b.entries.cmp(&a.entries).then_with(|| by_calories(a, b)));
+        foods.truncate(limit);
+        foods
+    }
+
+    /// Returns the foods that contributed the most calories, at most `limit` of them
+    pub fn top_by_calories(&self, limit: usize) -> Vec<&FoodIntake> {
+        self.foods.iter().take(limit).collect()
+    }
+
+    /// Returns a food's share of the period's calories, from 0 to 100
+    pub fn percent_of_calories(&self, intake: &FoodIntake) -> f64 {
+        if self.total_calories > 0.0 {
+            intake.calories / self.total_calories * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Orders foods by calories, most first, then by entries and name
+fn by_calories(a: &FoodIntake, b: &FoodIntake) -> Ordering {
+    b.calories.total_cmp(&a.calories)
+        .then_with(|| b.entries.cmp(&a.entries))
+        .then_with(|| a.name.cmp(&b.name))
+}
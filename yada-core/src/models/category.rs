@@ -0,0 +1,106 @@
+//! # Food Categories
+//!
+//! Every food may belong to one food group such as `Dairy` or `Fruit`. The
+//! category is stored on the food as text, so users can invent their own,
+//! but the standard categories below are recognized regardless of case:
+//! typing `dairy` files the food under `Dairy`, and browsing groups foods
+//! whose categories differ only in case.
+//!
+//! ## Standard Categories
+//!
+//! Prepared Meal, Nuts & Seeds, Dairy, Protein, Legume, Beverage, Sweets,
+//! Fruit, Vegetable, Grain, Fats & Oils, Condiment, Snack
+//!
+//! ## Automatic Assignment
+//!
+//! Foods imported from other tools (see `ingest`) often arrive without a
+//! category. `infer` guesses one from the words of the food's name and its
+//! keywords: the first category above with a matching word wins, so
+//! "Peanut Butter Sandwich" is a Prepared Meal rather than Nuts & Seeds,
+//! peanut butter is Nuts & Seeds rather than Dairy, and grape jelly is
+//! Sweets rather than Fruit. Foods nothing matches stay uncategorized.
+//!
+//! ## Browsing
+//!
+//! View Foods can show a single category, and a search term such as
+//! `category:dairy` (see `SEARCH_PREFIX`) limits search results to one.
+
+// yada-core/src/models/category.rs
+use std::collections::HashSet;
+
+use crate::models::food::Food;
+use crate::models::search;
+
+/// Label for foods without a category when browsing
+pub const UNCATEGORIZED: &str = "Uncategorized";
+
+/// Prefix of a search term that restricts results to a category (`category:dairy`)
+pub const SEARCH_PREFIX: &str = "category:";
+
+/// Standard categories and the words that suggest them, in priority order
+const RULES: &[(&str, &[&str])] = &[
+    ("Prepared Meal", &["sandwich", "pizza", "burger", "burrito", "taco", "salad", "soup", "stew", "curry", "casserole", "lasagna", "meal"]),
+    ("Nuts & Seeds", &["nut", "peanut", "almond", "walnut", "cashew", "pecan", "pistachio", "seed", "chia", "flax"]),
+    ("Dairy", &["dairy", "milk", "cheese", "cheddar", "mozzarella", "yogurt", "butter", "cream", "kefir"]),
+    ("Protein", &["protein", "meat", "chicken", "beef", "pork", "turkey", "lamb", "fish", "tuna", "salmon", "shrimp", "egg", "tofu", "tempeh"]),
+    ("Legume", &["legume", "bean", "lentil", "chickpea", "pea", "hummus", "edamame"]),
+    ("Beverage", &["drink", "beverage", "soda", "juice", "coffee", "tea", "water", "beer", "wine"]),
+    ("Sweets", &["sugar", "candy", "chocolate", "cookie", "cake", "dessert", "jelly", "jam", "honey"]),
+    ("Fruit", &["fruit", "apple", "banana", "orange", "berry", "strawberry", "blueberry", "grape", "mango", "pear", "peach", "citrus", "melon"]),
+    ("Vegetable", &["vegetable", "veggie", "broccoli", "carrot", "spinach", "potato", "tomato", "lettuce", "kale", "pepper", "onion", "cucumber", "leafy"]),
+    ("Grain", &["grain", "bread", "rice", "pasta", "oat", "oats", "oatmeal", "wheat", "cereal", "quinoa", "barley", "tortilla", "noodle"]),
+    ("Fats & Oils", &["oil", "fat", "lard", "margarine", "ghee"]),
+    ("Condiment", &["condiment", "sauce", "spread", "ketchup", "mustard", "mayonnaise", "dressing", "salsa"]),
+    ("Snack", &["snack", "chips", "crackers", "popcorn", "pretzel", "bar"]),
+];
+
+/// Returns the standard categories in priority order
+pub fn standard_categories() -> impl Iterator<Item = &'static str> {
+    RULES.iter().map(|(category, _)| *category)
+}
+
+/// Cleans up a category typed by a user or read from a file
+///
+/// # Returns
+/// * `Some(String)` - The standard spelling if the text names a standard
+///   category (ignoring case), otherwise the trimmed text
+/// * `None` - If the text is empty
+pub fn normalize(text: &str) -> Option<String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    let standard = standard_categories().find(|category| category.eq_ignore_ascii_case(text));
+    Some(standard.map_or_else(|| text.to_string(), str::to_string))
+}
+
+/// Returns true if a food belongs to a category (ignoring case)
+pub fn matches(food: &Food, category: &str) -> bool {
+    food.category.as_deref().is_some_and(|own| own.eq_ignore_ascii_case(category.trim()))
+}
+
+/// Guesses a standard category from a food's name and keywords
+///
+/// # Arguments
+/// * `name` - Food name
+/// * `keywords` - Food keywords
+///
+/// # Returns
+/// * `Some(&str)` - The first standard category with a matching word
+/// * `None` - If no word suggests a category
+pub fn infer(name: &str, keywords: &HashSet<String>) -> Option<&'static str> {
+    let words: HashSet<String> = search::name_words(name)
+        .chain(keywords.iter().flat_map(|keyword| search::name_words(keyword)))
+        .collect();
+    RULES.iter()
+        .find(|(_, cues)| cues.iter().any(|cue| words.contains(*cue) || words.contains(&plural(cue))))
+        .map(|(category, _)| *category)
+}
+
+/// Returns the regular English plural of a cue word (`berry` → `berries`)
+fn plural(word: &str) -> String {
+    match word.strip_suffix('y') {
+        Some(stem) if !stem.ends_with(['a', 'e', 'o', 'u']) => format!("{}ies", stem),
+        _ => format!("{}s", word),
+    }
+}
@@ -13,9 +13,11 @@
 //! 
 //! ## Supported Operations:
 //! All data-modifying operations in YADA implement this Command interface,
-//! including food management, logging, and profile updates.
+//! including food management, logging, exercise, meal plans, and profile
+//! updates. Each command reports a `CommandType`, which decides how the
+//! command manager treats it on undo (see `command_manager`).
 
-// src/models/command.rs
+// yada-core/src/models/command.rs
 use std::fmt;
 
 /// Enumeration of all supported command types in the application
@@ -24,37 +26,141 @@ use std::fmt;
 /// that can be executed and undone. This enables:
 /// - Type-safe command identification
 /// - User-friendly command descriptions
-/// - Command filtering and analysis
+/// - Per-type undo policies (see `command_manager::UndoPolicies`)
 /// - Audit trail categorization
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CommandType {
     /// Adding new food items to the database
     AddFood,
     
+    /// Changing an existing food item
+    UpdateFood,
+    
     /// Removing food items from the database
     RemoveFood,
     
+    /// Hiding unused foods from food lists
+    ArchiveFoods,
+    
+    /// Showing archived foods in food lists again
+    RestoreFoods,
+    
+    /// Merging a duplicate food into another
+    MergeFoods,
+    
+    /// Adding or removing keywords of one or more foods
+    EditKeywords,
+    
     /// Adding new entries to food logs
     AddLog,
     
+    /// Changing the food or amount of a food log entry
+    UpdateLog,
+    
     /// Deleting entries from food logs
     DeleteLog,
     
+    /// Adding exercise entries
+    AddExercise,
+    
+    /// Deleting exercise entries
+    RemoveExercise,
+    
+    /// Replacing or clearing the meal plan for a day
+    SetMealPlan,
+    
+    /// Writing or clearing the note, mood, and energy of a day
+    SetDayNote,
+    
     /// Updating user profile information
     UpdateProfile,
     
+    /// Bulk imports of foods or log entries (see `ingest`)
+    Import,
+    
     /// Extensible category for future command types
     Other(String),
 }
 
+impl CommandType {
+    /// Every type with a fixed key, in the order they are documented
+    pub const ALL: [CommandType; 16] = [
+        CommandType::AddFood,
+        CommandType::UpdateFood,
+        CommandType::RemoveFood,
+        CommandType::ArchiveFoods,
+        CommandType::RestoreFoods,
+        CommandType::MergeFoods,
+        CommandType::EditKeywords,
+        CommandType::AddLog,
+        CommandType::UpdateLog,
+        CommandType::DeleteLog,
+        CommandType::AddExercise,
+        CommandType::RemoveExercise,
+        CommandType::SetMealPlan,
+        CommandType::SetDayNote,
+        CommandType::UpdateProfile,
+        CommandType::Import,
+    ];
+
+    /// Returns the key naming this type in settings (e.g. `update_profile`)
+    /// 
+    /// All `Other` commands share the key `other`.
+    pub fn key(&self) -> &'static str {
+        match self {
+            CommandType::AddFood => "add_food",
+            CommandType::UpdateFood => "update_food",
+            CommandType::RemoveFood => "remove_food",
+            CommandType::ArchiveFoods => "archive_foods",
+            CommandType::RestoreFoods => "restore_foods",
+            CommandType::MergeFoods => "merge_foods",
+            CommandType::EditKeywords => "edit_keywords",
+            CommandType::AddLog => "add_log",
+            CommandType::UpdateLog => "update_log",
+            CommandType::DeleteLog => "delete_log",
+            CommandType::AddExercise => "add_exercise",
+            CommandType::RemoveExercise => "remove_exercise",
+            CommandType::SetMealPlan => "set_meal_plan",
+            CommandType::SetDayNote => "set_day_note",
+            CommandType::UpdateProfile => "update_profile",
+            CommandType::Import => "import",
+            CommandType::Other(_) => "other",
+        }
+    }
+
+    /// Finds the type with a settings key, ignoring case
+    /// 
+    /// # Returns
+    /// * `Some(CommandType)` - The matching type (`other` gives an unnamed `Other`)
+    /// * `None` - If no type has the key
+    pub fn from_key(key: &str) -> Option<Self> {
+        let key = key.trim().to_lowercase();
+        if key == "other" {
+            return Some(CommandType::Other(String::new()));
+        }
+        CommandType::ALL.into_iter().find(|kind| kind.key() == key)
+    }
+}
+
 impl fmt::Display for CommandType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             CommandType::AddFood => write!(f, "Add Food"),
+            CommandType::UpdateFood => write!(f, "Update Food"),
             CommandType::RemoveFood => write!(f, "Remove Food"),
+            CommandType::ArchiveFoods => write!(f, "Archive Foods"),
+            CommandType::RestoreFoods => write!(f, "Restore Foods"),
+            CommandType::MergeFoods => write!(f, "Merge Foods"),
+            CommandType::EditKeywords => write!(f, "Edit Keywords"),
             CommandType::AddLog => write!(f, "Add Log Entry"),
+            CommandType::UpdateLog => write!(f, "Update Log Entry"),
             CommandType::DeleteLog => write!(f, "Delete Log Entry"),
+            CommandType::AddExercise => write!(f, "Add Exercise"),
+            CommandType::RemoveExercise => write!(f, "Remove Exercise"),
+            CommandType::SetMealPlan => write!(f, "Set Meal Plan"),
+            CommandType::SetDayNote => write!(f, "Set Day Note"),
             CommandType::UpdateProfile => write!(f, "Update Profile"),
+            CommandType::Import => write!(f, "Import"),
             CommandType::Other(s) => write!(f, "{}", s),
         }
     }
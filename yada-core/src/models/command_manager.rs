@@ -0,0 +1,398 @@
+//! Command Manager - Central Command Pattern Orchestration
+//! 
+//! This module implements the command execution and undo management system
+//! for the YADA application. It provides centralized control over all
+//! data-modifying operations with robust undo functionality.
+//! 
+//! ## Key Features:
+//! - Command execution with automatic undo stack management
+//! - Configurable undo stack size with automatic cleanup
+//! - Command history tracking for audit and display purposes
+//! - Error handling for both execution and undo operations
+//! - Memory management to prevent unlimited command accumulation
+//! 
+//! ## Design Benefits:
+//! - Centralized command execution ensures consistent behavior
+//! - Automatic undo stack management simplifies client code
+//! - Bounded memory usage prevents command history from growing indefinitely
+//! - Type-safe command handling through trait objects
+//! 
+//! ## Undo Policies:
+//! Each `CommandType` has an `UndoPolicy`:
+//! - **Allow**: The command can be undone right away
+//! - **Confirm**: The command can be undone, but the user is asked first
+//! - **Skip**: The command is not kept for undo. Older commands are dropped
+//!   too, since undoing changes made before it could overwrite its results
+//! 
+//! By default imports are skipped, profile updates ask for confirmation,
+//! and everything else is allowed. The `YADA_UNDO_POLICY` environment
+//! variable changes this with comma-separated `type=policy` pairs, using the
+//! keys from `CommandType::key` and `default` for every type not named:
+//! ```text
+//! YADA_UNDO_POLICY=import=allow,delete_log=confirm
+//! YADA_UNDO_POLICY=default=confirm,add_log=allow
+//! ```
+//!
+//! ## Audit Log:
+//! A manager given an `AuditSink` (see `with_audit_sink`) reports every
+//! command it executes or undoes to it; the application passes the
+//! file-backed `repositories::audit_log::AuditLog`. Recording never fails a
+//! command; the error is kept for the caller to report (`take_audit_error`).
+
+// yada-core/src/models/command_manager.rs
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+
+use crate::models::command::{Command, CommandType};
+
+/// Environment variable holding per-type undo policies
+pub const UNDO_POLICY_ENV_VAR: &str = "YADA_UNDO_POLICY";
+
+/// How the command manager treats a type of command on undo
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UndoPolicy {
+    /// Kept for undo; undone without asking
+    Allow,
+
+    /// Kept for undo; the user confirms before it is undone
+    Confirm,
+
+    /// Not kept for undo, and ends the undo history before it
+    Skip,
+}
+
+impl UndoPolicy {
+    /// Parses `allow`, `confirm`, or `skip` (ignoring case)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "allow" | "yes" => Some(UndoPolicy::Allow),
+            "confirm" | "ask" => Some(UndoPolicy::Confirm),
+            "skip" | "no" | "never" => Some(UndoPolicy::Skip),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for UndoPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            UndoPolicy::Allow => "allow",
+            UndoPolicy::Confirm => "confirm",
+            UndoPolicy::Skip => "skip",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Undo policy for every command type
+#[derive(Debug, Clone, PartialEq)]
+pub struct UndoPolicies {
+    /// Policies of types with their own setting, by `CommandType::key`
+    by_type: HashMap<&'static str, UndoPolicy>,
+
+    /// Policy of all other types
+    fallback: UndoPolicy,
+}
+
+impl Default for UndoPolicies {
+    /// Imports are skipped, profile updates confirmed, everything else allowed
+    fn default() -> Self {
+        let by_type = HashMap::from([
+            (CommandType::Import.key(), UndoPolicy::Skip),
+            (CommandType::UpdateProfile.key(), UndoPolicy::Confirm),
+        ]);
+        UndoPolicies { by_type, fallback: UndoPolicy::Allow }
+    }
+}
+
+impl UndoPolicies {
+    /// Parses policies from their setting, starting from the defaults
+    /// 
+    /// # Arguments
+    /// * `value` - Comma-separated `type=policy` pairs; `default=policy`
+    ///   applies to every type without a policy of its own
+    /// 
+    /// # Returns
+    /// * `Ok(UndoPolicies)` - The defaults with the given changes
+    /// * `Err(String)` - The first pair that is not understood
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let mut policies = UndoPolicies::default();
+        for pair in value.split(',').map(str::trim).filter(|pair| !pair.is_empty()) {
+            let (key, policy) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("Expected type=policy, got '{}'", pair))?;
+            let policy = UndoPolicy::parse(policy)
+                .ok_or_else(|| format!("Unknown undo policy '{}' (use allow, confirm, or skip)", policy.trim()))?;
+            if key.trim().eq_ignore_ascii_case("default") {
+                policies.fallback = policy;
+            } else {
+                let kind = CommandType::from_key(key).ok_or_else(|| format!("Unknown command type '{}'", key.trim()))?;
+                policies.set(&kind, policy);
+            }
+        }
+        Ok(policies)
+    }
+
+    /// Reads the policies from the `YADA_UNDO_POLICY` environment variable
+    /// 
+    /// Falls back to the defaults when the variable is missing or invalid.
+    pub fn from_env() -> Self {
+        env::var(UNDO_POLICY_ENV_VAR)
+            .ok()
+            .and_then(|value| Self::parse(&value).ok())
+            .unwrap_or_default()
+    }
+
+    /// Sets the policy of one command type
+    pub fn set(&mut self, kind: &CommandType, policy: UndoPolicy) {
+        self.by_type.insert(kind.key(), policy);
+    }
+
+    /// Returns the policy of a command type
+    pub fn policy_for(&self, kind: &CommandType) -> UndoPolicy {
+        self.by_type.get(kind.key()).copied().unwrap_or(self.fallback)
+    }
+}
+
+/// What happened to a command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditEvent {
+    /// The command made its change
+    Execute,
+
+    /// The command's change was undone
+    Undo,
+}
+
+impl fmt::Display for AuditEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AuditEvent::Execute => write!(f, "execute"),
+            AuditEvent::Undo => write!(f, "undo"),
+        }
+    }
+}
+
+/// Receives the commands a manager executes or undoes, such as an audit log file
+pub trait AuditSink {
+    /// Records one event
+    ///
+    /// # Arguments
+    /// * `event` - Whether the command was executed or undone
+    /// * `kind` - Type of the command
+    /// * `description` - The command's description
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the event was recorded
+    /// * `Err(String)` - If it could not be; the command is not affected
+    fn record(&self, event: AuditEvent, kind: &CommandType, description: &str) -> Result<(), String>;
+}
+
+/// Central manager for command execution and undo functionality
+/// 
+/// CommandManager provides the core infrastructure for the Command Pattern
+/// implementation in YADA. It manages:
+/// - Command execution with automatic success tracking
+/// - Undo stack maintenance with configurable size limits
+/// - Command history for user interface and debugging
+/// - Memory management to prevent unbounded growth
+/// 
+/// ## Undo Stack Management:
+/// Only successfully executed commands are added to the undo stack, and only
+/// if their type's policy keeps them (see `UndoPolicies`).
+/// The stack has a configurable maximum size, with oldest commands
+/// automatically removed when the limit is exceeded.
+pub struct CommandManager {
+    /// Stack of successfully executed commands available for undo
+    undo_stack: Vec<Box<dyn Command>>,
+    
+    /// Maximum number of commands to keep in undo history
+    max_stack_size: usize,
+    
+    /// How each type of command is treated on undo
+    policies: UndoPolicies,
+    
+    /// Where executed and undone commands are recorded, if anywhere
+    audit_sink: Option<Box<dyn AuditSink>>,
+    
+    /// Latest failure to write the audit log, not yet reported
+    audit_error: Option<String>,
+}
+
+impl CommandManager {
+    /// Creates a new CommandManager with specified undo stack size limit
+    /// 
+    /// The default undo policies apply; see `with_policies`.
+    /// 
+    /// # Arguments
+    /// * `max_stack_size` - Maximum number of commands to retain for undo
+    /// 
+    /// # Examples
+    /// ```ignore
+    /// let manager = CommandManager::new(50); // Keep last 50 commands
+    /// ```
+    pub fn new(max_stack_size: usize) -> Self {
+        CommandManager {
+            undo_stack: Vec::new(),
+            max_stack_size,
+            policies: UndoPolicies::default(),
+            audit_sink: None,
+            audit_error: None,
+        }
+    }
+    
+    /// Replaces the undo policies
+    /// 
+    /// # Examples
+    /// ```ignore
+    /// let manager = CommandManager::new(100).with_policies(UndoPolicies::from_env());
+    /// ```
+    pub fn with_policies(mut self, policies: UndoPolicies) -> Self {
+        self.policies = policies;
+        self
+    }
+    
+    /// Records executed and undone commands in an audit sink
+    /// 
+    /// # Examples
+    /// ```ignore
+    /// let manager = CommandManager::new(100).with_audit_sink(AuditLog::new("audit.log"));
+    /// ```
+    pub fn with_audit_sink(mut self, sink: impl AuditSink + 'static) -> Self {
+        self.audit_sink = Some(Box::new(sink));
+        self
+    }
+    
+    /// Returns the latest failure to write the audit log, once
+    pub fn take_audit_error(&mut self) -> Option<String> {
+        self.audit_error.take()
+    }
+    
+    /// Reports an event to the audit sink, keeping any failure for `take_audit_error`
+    fn audit(&mut self, event: AuditEvent, command: &dyn Command) {
+        let Some(sink) = &self.audit_sink else {
+            return;
+        };
+        if let Err(e) = sink.record(event, &command.get_type(), &command.description()) {
+            self.audit_error = Some(e);
+        }
+    }
+    
+    /// Returns the undo policies in effect
+    pub fn policies(&self) -> &UndoPolicies {
+        &self.policies
+    }
+    
+    /// Executes a command and manages undo stack automatically
+    /// 
+    /// This method:
+    /// 1. Attempts to execute the provided command, reporting it to the
+    ///    audit sink if it succeeds
+    /// 2. On success, adds the command to the undo stack, unless its policy
+    ///    is `Skip`; then the undo stack is cleared instead
+    /// 3. Manages stack size by removing oldest commands if needed
+    /// 4. On failure, discards the command (no undo stack modification)
+    /// 
+    /// # Arguments
+    /// * `command` - Boxed command object implementing the Command trait
+    /// 
+    /// # Returns
+    /// * `Ok(())` - Command executed successfully
+    /// * `Err(String)` - Command execution failed with error description
+    pub fn execute_command(&mut self, mut command: Box<dyn Command>) -> Result<(), String> {
+        let result = command.execute();
+        if result.is_ok() {
+            self.audit(AuditEvent::Execute, command.as_ref());
+        }
+        
+        if result.is_ok() && self.policies.policy_for(&command.get_type()) == UndoPolicy::Skip {
+            // Older commands cannot be undone safely underneath this one
+            self.undo_stack.clear();
+        } else if result.is_ok() {
+            // Add to undo stack
+            self.undo_stack.push(command);
+            
+            // If we've exceeded the max stack size, remove the oldest command
+            if self.undo_stack.len() > self.max_stack_size {
+                self.undo_stack.remove(0);
+            }
+        }
+        
+        result
+    }
+    
+    /// Undoes the most recently executed command
+    /// 
+    /// This method:
+    /// 1. Removes the most recent command from the undo stack
+    /// 2. Calls the command's undo() method to reverse its effects
+    /// 3. Permanently removes the command from undo history
+    /// 
+    /// Note: Once undone, commands cannot be redone (no redo stack)
+    /// 
+    /// # Returns
+    /// * `Ok(())` - Command undone successfully
+    /// * `Err(String)` - No commands to undo or undo operation failed
+    pub fn undo_last_command(&mut self) -> Result<(), String> {
+        if let Some(mut command) = self.undo_stack.pop() {
+            let result = command.undo();
+            if result.is_ok() {
+                self.audit(AuditEvent::Undo, command.as_ref());
+            }
+            result
+        } else {
+            Err("No command to undo".to_string())
+        }
+    }
+    
+    /// Returns the policy of the command that would be undone next
+    /// 
+    /// # Returns
+    /// * `Some(UndoPolicy)` - `Allow` or `Confirm` for the most recent command
+    /// * `None` - If there is nothing to undo
+    pub fn next_undo_policy(&self) -> Option<UndoPolicy> {
+        self.undo_stack.last().map(|command| self.policies.policy_for(&command.get_type()))
+    }
+    
+    /// Returns the current number of commands available for undo
+    /// 
+    /// Useful for user interface elements that show undo availability
+    /// or for debugging and monitoring purposes.
+    /// 
+    /// # Returns
+    /// Number of commands currently in the undo stack
+    pub fn get_undo_stack_size(&self) -> usize {
+        self.undo_stack.len()
+    }
+    
+    /// Checks whether any commands are available for undo
+    /// 
+    /// This is a convenience method for user interface logic that
+    /// needs to enable/disable undo functionality.
+    /// 
+    /// # Returns
+    /// * `true` - At least one command is available for undo
+    /// * `false` - No commands available for undo
+    pub fn has_commands_to_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+    
+    /// Generates a list of command descriptions for history display
+    /// 
+    /// This method creates a human-readable command history by collecting
+    /// the description() from each command in the undo stack. Useful for:
+    /// - Displaying command history to users
+    /// - Debugging and audit trails
+    /// - Undo preview functionality
+    /// 
+    /// # Returns
+    /// Vector of strings describing each command in chronological order
+    /// (oldest commands first, newest commands last)
+    pub fn get_command_history(&self) -> Vec<String> {
+        self.undo_stack
+            .iter()
+            .map(|cmd| cmd.description())
+            .collect()
+    }
+}
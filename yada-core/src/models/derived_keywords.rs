@@ -0,0 +1,127 @@
+//! # Derived Keywords
+//!
+//! Foods carry structured metadata (a category such as `Dairy` and tags such
+//! as `contains-gluten`) in addition to the free-form keywords typed by the
+//! user. This module turns that metadata into extra searchable keywords so
+//! that a keyword search for `dairy` or `gluten` finds every food with the
+//! matching category or tag, even if nobody typed those keywords by hand.
+//!
+//! ## Consistency
+//!
+//! Derived keywords are kept separate from the user's keywords
+//! (`Food::derived_keywords`) and are never written to disk. They are
+//! recomputed whenever a food enters the repository, so changing or removing
+//! a category or tag can never leave stale keywords behind.
+//!
+//! ## Derivation Rules
+//!
+//! Each category or tag value contributes:
+//! - The whole value, lowercased, with spaces replaced by `-` (`Whole Grain` → `whole-grain`)
+//! - Each word of the value when it consists of several (`whole`, `grain`)
+//!
+//! ## Configuration
+//!
+//! The sources used are read from the `YADA_DERIVED_KEYWORDS` environment variable:
+//! - `all` (default): derive keywords from the category and from tags
+//! - `category`: derive keywords from the category only
+//! - `tags`: derive keywords from tags only
+//! - `off`: never derive keywords
+
+// yada-core/src/models/derived_keywords.rs
+use std::collections::HashSet;
+use std::env;
+
+use crate::models::food::Food;
+
+/// Environment variable used to configure keyword derivation
+pub const DERIVED_KEYWORDS_ENV_VAR: &str = "YADA_DERIVED_KEYWORDS";
+
+/// Which food metadata is turned into searchable keywords
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeywordDerivation {
+    /// Derive keywords from the food's category
+    pub from_category: bool,
+
+    /// Derive keywords from the food's tags
+    pub from_tags: bool,
+}
+
+impl Default for KeywordDerivation {
+    fn default() -> Self {
+        KeywordDerivation {
+            from_category: true,
+            from_tags: true,
+        }
+    }
+}
+
+impl KeywordDerivation {
+    /// Parses a derivation setting from its textual configuration form
+    ///
+    /// # Arguments
+    /// * `value` - `all`, `category`, `tags`, or `off`
+    ///
+    /// # Returns
+    /// * `Some(KeywordDerivation)` - The parsed setting
+    /// * `None` - If the value is not recognized
+    pub fn parse(value: &str) -> Option<Self> {
+        let (from_category, from_tags) = match value.trim().to_lowercase().as_str() {
+            "all" | "on" => (true, true),
+            "category" => (true, false),
+            "tags" => (false, true),
+            "off" | "none" => (false, false),
+            _ => return None,
+        };
+        Some(KeywordDerivation { from_category, from_tags })
+    }
+
+    /// Reads the setting from the `YADA_DERIVED_KEYWORDS` environment variable
+    ///
+    /// Falls back to deriving from all metadata when the variable is missing or invalid.
+    pub fn from_env() -> Self {
+        env::var(DERIVED_KEYWORDS_ENV_VAR)
+            .ok()
+            .and_then(|value| Self::parse(&value))
+            .unwrap_or_default()
+    }
+
+    /// Computes the keywords derived from a food's category and tags
+    ///
+    /// # Arguments
+    /// * `food` - The food whose metadata is used
+    ///
+    /// # Returns
+    /// Lowercase keywords, excluding those the user already entered
+    pub fn derive(&self, food: &Food) -> HashSet<String> {
+        let mut derived = HashSet::new();
+
+        if self.from_category && let Some(category) = &food.category {
+            add_value_keywords(&mut derived, category);
+        }
+        if self.from_tags {
+            for tag in &food.tags {
+                add_value_keywords(&mut derived, tag);
+            }
+        }
+
+        derived.retain(|keyword| !food.keywords.contains(keyword));
+        derived
+    }
+}
+
+/// Adds the keywords for one category or tag value
+fn add_value_keywords(keywords: &mut HashSet<String>, value: &str) {
+    let value = value.trim().to_lowercase();
+    let words: Vec<&str> = value
+        .split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    if words.is_empty() {
+        return;
+    }
+    keywords.insert(value.split_whitespace().collect::<Vec<_>>().join("-"));
+    if words.len() > 1 {
+        keywords.extend(words.iter().map(|word| word.to_string()));
+    }
+}
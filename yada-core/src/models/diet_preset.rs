@@ -0,0 +1,108 @@
+//! # Diet Presets
+//!
+//! A preset configures a style of eating in one step: the share of calories
+//! that should come from protein, carbohydrates, and fat, and an adjustment
+//! of the calculated calorie target.
+//!
+//! ## Presets
+//!
+//! | Preset       | Protein | Carbs | Fat | Calories |
+//! |--------------|---------|-------|-----|----------|
+//! | Keto         | 20%     | 5%    | 75% | ±0       |
+//! | High Protein | 40%     | 30%   | 30% | ±0       |
+//! | Cutting      | 35%     | 40%   | 25% | -500     |
+//! | Bulking      | 25%     | 50%   | 25% | +300     |
+//!
+//! The calorie adjustment is applied to the target the calculation method
+//! returns, before any weekday override (see `models::weekday_target`), and
+//! only on days that have a target. The preset's macro ratios are the macro
+//! targets unless the profile sets its own (see `models::macro_target`).
+
+// yada-core/src/models/diet_preset.rs
+use std::fmt;
+
+use crate::models::macro_target::MacroRatios;
+
+/// A predefined style of eating
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DietPreset {
+    /// Very low carbohydrate, high fat
+    Keto,
+
+    /// Extra protein at the calculated calories
+    HighProtein,
+
+    /// Calorie deficit with extra protein to keep muscle
+    Cutting,
+
+    /// Calorie surplus with plenty of carbohydrates
+    Bulking,
+}
+
+impl DietPreset {
+    /// Every preset, in menu order
+    pub const ALL: [DietPreset; 4] = [
+        DietPreset::Keto,
+        DietPreset::HighProtein,
+        DietPreset::Cutting,
+        DietPreset::Bulking,
+    ];
+
+    /// Key used in the profile file
+    pub fn key(&self) -> &'static str {
+        match self {
+            DietPreset::Keto => "keto",
+            DietPreset::HighProtein => "high_protein",
+            DietPreset::Cutting => "cutting",
+            DietPreset::Bulking => "bulking",
+        }
+    }
+
+    /// Finds a preset by its key
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|preset| preset.key() == key.trim())
+    }
+
+    /// Target share of calories from each macronutrient
+    pub fn ratios(&self) -> MacroRatios {
+        let (protein, carbs, fat) = match self {
+            DietPreset::Keto => (20.0, 5.0, 75.0),
+            DietPreset::HighProtein => (40.0, 30.0, 30.0),
+            DietPreset::Cutting => (35.0, 40.0, 25.0),
+            DietPreset::Bulking => (25.0, 50.0, 25.0),
+        };
+        MacroRatios { protein, carbs, fat }
+    }
+
+    /// Calories added to (or taken from) the calculated target
+    pub fn calorie_adjustment(&self) -> f64 {
+        match self {
+            DietPreset::Keto | DietPreset::HighProtein => 0.0,
+            DietPreset::Cutting => -500.0,
+            DietPreset::Bulking => 300.0,
+        }
+    }
+
+    /// Applies the calorie adjustment to a calculated target
+    ///
+    /// Days without a target (0) keep none, and the result is never negative.
+    pub fn adjust(&self, target: f64) -> f64 {
+        if target > 0.0 {
+            (target + self.calorie_adjustment()).max(0.0)
+        } else {
+            target
+        }
+    }
+}
+
+impl fmt::Display for DietPreset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            DietPreset::Keto => "Keto",
+            DietPreset::HighProtein => "High Protein",
+            DietPreset::Cutting => "Cutting",
+            DietPreset::Bulking => "Bulking",
+        };
+        write!(f, "{}", name)
+    }
+}
@@ -0,0 +1,206 @@
+//! # Dietary Restrictions
+//!
+//! Food tags follow a small vocabulary for allergens and diets. Tags such as
+//! `contains-gluten` flag an ingredient; tags such as `vegan` or `nut-free`
+//! describe the food as a whole. A profile declares restrictions, and foods
+//! flagged with an ingredient a restriction rules out are reported as
+//! conflicts when they are logged.
+//!
+//! ## Restrictions and Flags
+//!
+//! | Restriction   | Conflicting tags                                           |
+//! |---------------|------------------------------------------------------------|
+//! | `gluten-free` | `contains-gluten`                                          |
+//! | `nut-free`    | `contains-nuts`                                            |
+//! | `dairy-free`  | `contains-dairy`                                           |
+//! | `vegetarian`  | `contains-meat`, `contains-fish`                           |
+//! | `vegan`       | the vegetarian ones, `contains-dairy`, `contains-egg`, `contains-honey` |
+//!
+//! Tags naming a restriction (`gluten-free`, `vegan`, ...) describe a food
+//! for searching (see `models::derived_keywords`); only flags cause
+//! conflicts, so a food tagged both `vegan` and `contains-dairy` conflicts
+//! with a vegan diet.
+//!
+//! ## Composite Foods
+//!
+//! Composite foods are checked with all of their components, recursively, so
+//! a sandwich made with bread tagged `contains-gluten` conflicts with a
+//! gluten-free diet even if the sandwich itself has no tags. Each flagged
+//! food is reported once, with the composite it was found in.
+
+// yada-core/src/models/dietary.rs
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::models::food::Food;
+
+/// A diet or allergy the user declared in their profile
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum DietaryRestriction {
+    /// No wheat, barley, rye, or other gluten
+    GlutenFree,
+
+    /// No tree nuts or peanuts
+    NutFree,
+
+    /// No milk or milk products
+    DairyFree,
+
+    /// No meat or fish
+    Vegetarian,
+
+    /// No animal products
+    Vegan,
+}
+
+impl DietaryRestriction {
+    /// Every restriction, in menu order
+    pub const ALL: [DietaryRestriction; 5] = [
+        DietaryRestriction::GlutenFree,
+        DietaryRestriction::NutFree,
+        DietaryRestriction::DairyFree,
+        DietaryRestriction::Vegetarian,
+        DietaryRestriction::Vegan,
+    ];
+
+    /// Key used in the profile file, which is also the tag describing a compatible food
+    pub fn key(&self) -> &'static str {
+        match self {
+            DietaryRestriction::GlutenFree => "gluten-free",
+            DietaryRestriction::NutFree => "nut-free",
+            DietaryRestriction::DairyFree => "dairy-free",
+            DietaryRestriction::Vegetarian => "vegetarian",
+            DietaryRestriction::Vegan => "vegan",
+        }
+    }
+
+    /// Finds a restriction by its key, ignoring case and surrounding whitespace
+    pub fn from_key(key: &str) -> Option<Self> {
+        let key = key.trim().to_lowercase();
+        Self::ALL.into_iter().find(|restriction| restriction.key() == key)
+    }
+
+    /// Tags flagging an ingredient this restriction rules out
+    pub fn conflicting_tags(&self) -> &'static [&'static str] {
+        match self {
+            DietaryRestriction::GlutenFree => &["contains-gluten"],
+            DietaryRestriction::NutFree => &["contains-nuts"],
+            DietaryRestriction::DairyFree => &["contains-dairy"],
+            DietaryRestriction::Vegetarian => &["contains-meat", "contains-fish"],
+            DietaryRestriction::Vegan => &[
+                "contains-meat", "contains-fish", "contains-dairy", "contains-egg", "contains-honey",
+            ],
+        }
+    }
+}
+
+impl fmt::Display for DietaryRestriction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.key())
+    }
+}
+
+/// Parses a comma-separated list of restriction keys
+///
+/// # Returns
+/// * `Ok(Vec<DietaryRestriction>)` - The restrictions, sorted and without duplicates
+/// * `Err(String)` - If a key is not a known restriction
+pub fn parse_restrictions(input: &str) -> Result<Vec<DietaryRestriction>, String> {
+    let mut restrictions = Vec::new();
+    for key in input.split(',').map(str::trim).filter(|key| !key.is_empty()) {
+        let restriction = DietaryRestriction::from_key(key)
+            .ok_or_else(|| format!("'{}' is not a known restriction", key))?;
+        restrictions.push(restriction);
+    }
+    restrictions.sort();
+    restrictions.dedup();
+    Ok(restrictions)
+}
+
+/// Lists restrictions by key, separated by commas (`gluten-free, vegan`)
+pub fn list(restrictions: &[DietaryRestriction]) -> String {
+    let keys: Vec<&str> = restrictions.iter().map(DietaryRestriction::key).collect();
+    keys.join(", ")
+}
+
+/// A flagged food that conflicts with a restriction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    /// The restriction the food conflicts with
+    pub restriction: DietaryRestriction,
+
+    /// The flag that conflicts, such as `contains-gluten`
+    pub tag: String,
+
+    /// Name of the flagged food
+    pub food: String,
+
+    /// Name of the composite food the flagged food is a component of, if any
+    pub within: Option<String>,
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.food)?;
+        if let Some(within) = &self.within {
+            write!(f, " (in {})", within)?;
+        }
+        write!(f, " is tagged {}, which conflicts with your {} restriction", self.tag, self.restriction)
+    }
+}
+
+/// Finds the conflicts between a food, including its components, and restrictions
+///
+/// # Arguments
+/// * `food` - Food about to be logged
+/// * `foods` - All foods by ID, to look up components
+/// * `restrictions` - Restrictions declared in the profile
+///
+/// # Returns
+/// One conflict per flagged food and tag, the food itself first, then its
+/// components in recipe order; empty if there are no restrictions
+pub fn conflicts(food: &Food, foods: &HashMap<String, Food>, restrictions: &[DietaryRestriction]) -> Vec<Conflict> {
+    let mut found = Vec::new();
+    if restrictions.is_empty() {
+        return found;
+    }
+    let mut visited = HashSet::new();
+    collect_conflicts(food, None, foods, restrictions, &mut visited, &mut found);
+    found
+}
+
+/// Adds the conflicts of a food and, for composites, of its components
+///
+/// `visited` keeps foods from being reported twice and guards against
+/// recipes that contain themselves.
+fn collect_conflicts(
+    food: &Food,
+    within: Option<&str>,
+    foods: &HashMap<String, Food>,
+    restrictions: &[DietaryRestriction],
+    visited: &mut HashSet<String>,
+    found: &mut Vec<Conflict>,
+) {
+    if !visited.insert(food.id.clone()) {
+        return;
+    }
+    for restriction in restrictions {
+        for tag in restriction.conflicting_tags() {
+            let already_reported = found.iter().any(|conflict| conflict.food == food.name && conflict.tag == *tag);
+            if food.tags.contains(*tag) && !already_reported {
+                found.push(Conflict {
+                    restriction: *restriction,
+                    tag: tag.to_string(),
+                    food: food.name.clone(),
+                    within: within.map(str::to_string),
+                });
+            }
+        }
+    }
+    let within = within.unwrap_or(&food.name);
+    for (component_id, _) in &food.components {
+        if let Some(component) = foods.get(component_id) {
+            collect_conflicts(component, Some(within), foods, restrictions, visited, found);
+        }
+    }
+}
@@ -0,0 +1,118 @@
+//! # Duplicate Foods
+//!
+//! Databases built from several imports, or by hand over a long time, tend
+//! to collect the same food twice under different IDs, such as "Greek
+//! Yogurt" and "greek yoghurt". This module finds candidate pairs so the
+//! user can merge them (see `commands::food_commands::MergeFoodsCommand`).
+//!
+//! ## Matching Rules
+//!
+//! Two foods are reported as a pair when all of these hold:
+//! - **Same kind**: Both are basic foods, or both are composite foods
+//! - **Similar names**: Their names have the same words (ignoring case and
+//!   punctuation), or differ by a few typos; the allowed edit distance
+//!   follows search (`search::max_distance`)
+//! - **Similar calories**: Calories per serving differ by at most
+//!   `CALORIE_TOLERANCE_KCAL` or `CALORIE_TOLERANCE_RATIO` of the larger
+//!   value, whichever is more generous
+//!
+//! To keep large databases fast, only names starting with the same letter
+//! are compared, so a typo in the first letter is not caught.
+
+// yada-core/src/models/duplicates.rs
+use std::collections::HashMap;
+
+use crate::models::food::Food;
+use crate::models::search;
+
+/// Calorie difference always accepted between duplicates
+pub const CALORIE_TOLERANCE_KCAL: f64 = 10.0;
+
+/// Calorie difference accepted between duplicates, relative to the larger value
+pub const CALORIE_TOLERANCE_RATIO: f64 = 0.1;
+
+/// Two foods that look like the same food
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicatePair {
+    /// ID of the first food (the smaller ID)
+    pub first: String,
+
+    /// ID of the second food
+    pub second: String,
+
+    /// Edits between the normalized names (0 when they have the same words)
+    pub name_distance: usize,
+
+    /// Absolute difference in calories per serving
+    pub calorie_difference: f64,
+}
+
+/// Returns a food name as lowercase words separated by single spaces
+pub fn normalized_name(name: &str) -> String {
+    search::name_words(name).collect::<Vec<_>>().join(" ")
+}
+
+/// Returns true if two calorie values are close enough for duplicates
+pub fn similar_calories(a: f64, b: f64) -> bool {
+    (a - b).abs() <= CALORIE_TOLERANCE_KCAL.max(a.abs().max(b.abs()) * CALORIE_TOLERANCE_RATIO)
+}
+
+/// Finds pairs of foods that are probably the same food
+///
+/// # Arguments
+/// * `foods` - All known foods, archived ones included
+///
+/// # Returns
+/// The candidate pairs, closest names first, then by ID
+pub fn find_duplicates(foods: &HashMap<String, Food>) -> Vec<DuplicatePair> {
+    // Group foods by the first letter of their normalized name
+    let mut groups: HashMap<char, Vec<(&Food, String)>> = HashMap::new();
+    for food in foods.values() {
+        let name = normalized_name(&food.name);
+        if let Some(initial) = name.chars().next() {
+            groups.entry(initial).or_default().push((food, name));
+        }
+    }
+
+    let mut pairs = Vec::new();
+    for group in groups.values() {
+        for (i, (a, a_name)) in group.iter().enumerate() {
+            for (b, b_name) in &group[i + 1..] {
+                if let Some(pair) = compare(a, a_name, b, b_name) {
+                    pairs.push(pair);
+                }
+            }
+        }
+    }
+    pairs.sort_by(|a, b| {
+        a.name_distance.cmp(&b.name_distance)
+            .then_with(|| a.first.cmp(&b.first))
+            .then_with(|| a.second.cmp(&b.second))
+    });
+    pairs
+}
+
+/// Checks one pair of foods against the matching rules
+fn compare(a: &Food, a_name: &str, b: &Food, b_name: &str) -> Option<DuplicatePair> {
+    if a.food_type != b.food_type || !similar_calories(a.calories_per_serving, b.calories_per_serving) {
+        return None;
+    }
+
+    // Cheap length check before computing the distance
+    let allowed = search::max_distance(a_name).min(search::max_distance(b_name));
+    if a_name.chars().count().abs_diff(b_name.chars().count()) > allowed {
+        return None;
+    }
+    let name_distance = search::edit_distance(a_name, b_name);
+    if name_distance > allowed {
+        return None;
+    }
+
+    let (first, second) = if a.id < b.id { (a, b) } else { (b, a) };
+    Some(DuplicatePair {
+        first: first.id.clone(),
+        second: second.id.clone(),
+        name_distance,
+        calorie_difference: (a.calories_per_serving - b.calories_per_serving).abs(),
+    })
+}
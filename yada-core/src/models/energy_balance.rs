@@ -0,0 +1,216 @@
+//! # Energy Balance
+//!
+//! Compares what was eaten with how the weight changed, to estimate the
+//! calories actually burned per day (an adaptive TDEE) from the user's own
+//! data instead of a formula.
+//!
+//! ## Estimate
+//!
+//! Eating more than is burned stores the surplus, at about `KCAL_PER_KG`
+//! per kilogram of body weight, so over a period:
+//!
+//! ```text
+//! TDEE = average calories per logged day - weight trend (kg/day) x KCAL_PER_KG
+//! ```
+//!
+//! The weight trend is the least-squares slope through every weigh-in of
+//! the period, so one unusually heavy or light morning moves it less than
+//! comparing the first and last weigh-in would. Days without food entries
+//! are left out of the average, which assumes unlogged days were like
+//! logged ones. An estimate needs at least `MIN_LOGGED_DAYS` logged days
+//! and weigh-ins at least `MIN_WEIGHT_SPAN_DAYS` days apart.
+//!
+//! ## Weeks
+//!
+//! `weekly_balance` pairs each week's average intake with its weight
+//! change: the last weigh-in of the week against the last weigh-in before
+//! the week, or against the week's first weigh-in when there is none before.
+
+// yada-core/src/models/energy_balance.rs
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{Duration, NaiveDate};
+
+use crate::models::food::Food;
+use crate::models::log::DailyLog;
+use crate::models::profile::UserProfile;
+use crate::models::week::WeekStart;
+
+/// Energy stored or released per kilogram of body weight change
+pub const KCAL_PER_KG: f64 = 7700.0;
+
+/// Logged days an estimate needs
+pub const MIN_LOGGED_DAYS: usize = 7;
+
+/// Days the weigh-ins of an estimate must span
+pub const MIN_WEIGHT_SPAN_DAYS: i64 = 7;
+
+/// Calories burned per day as estimated from intake and weight trend
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdaptiveEstimate {
+    /// First day of the period
+    pub start: NaiveDate,
+
+    /// Last day of the period
+    pub end: NaiveDate,
+
+    /// Days of the period with food entries
+    pub days_logged: usize,
+
+    /// Average calories per logged day
+    pub average_calories: f64,
+
+    /// Weight trend in kilograms per week (negative when losing)
+    pub weight_trend_per_week: f64,
+
+    /// Estimated total daily energy expenditure
+    pub tdee: f64,
+}
+
+/// Intake and weight change of one week
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeekBalance {
+    /// First day of the week
+    pub start: NaiveDate,
+
+    /// Days of the week with food entries
+    pub days_logged: usize,
+
+    /// Average calories per logged day, or `None` if nothing was logged
+    pub average_calories: Option<f64>,
+
+    /// Last weigh-in of the week, if any
+    pub weight: Option<f64>,
+
+    /// Weight change over the week, if it can be measured
+    pub weight_change: Option<f64>,
+}
+
+/// Returns the calories of every day with food entries
+///
+/// # Arguments
+/// * `logs` - Daily logs to add up
+/// * `food_db` - HashMap of all available foods for calorie lookup
+pub fn daily_calories<'a>(logs: impl IntoIterator<Item = &'a DailyLog>, food_db: &HashMap<String, Food>) -> BTreeMap<NaiveDate, f64> {
+    logs.into_iter()
+        .filter(|log| !log.entries.is_empty())
+        .map(|log| (log.date, log.total_calories(food_db)))
+        .collect()
+}
+
+/// Returns the weights entered on a day, leaving out those carried forward
+///
+/// # Returns
+/// `(date, weight)` pairs in date order
+pub fn measured_weights(profile: &UserProfile) -> Vec<(NaiveDate, f64)> {
+    let mut weights: Vec<(NaiveDate, f64)> = profile.daily_profiles.iter()
+        .filter(|daily| daily.carried_from.is_none())
+        .map(|daily| (daily.date, daily.weight))
+        .collect();
+    weights.sort_by_key(|(date, _)| *date);
+    weights
+}
+
+/// Estimates the calories burned per day over a period
+///
+/// # Arguments
+/// * `daily_calories` - Calories of each logged day (see `daily_calories`)
+/// * `weights` - Weigh-ins in date order (see `measured_weights`)
+/// * `start` - First day of the period
+/// * `end` - Last day of the period
+///
+/// # Returns
+/// * `Some(AdaptiveEstimate)` - The estimate
+/// * `None` - If the period has too few logged days or its weigh-ins span too few days
+pub fn estimate(
+    daily_calories: &BTreeMap<NaiveDate, f64>,
+    weights: &[(NaiveDate, f64)],
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Option<AdaptiveEstimate> {
+    let intake: Vec<f64> = daily_calories.range(start..=end).map(|(_, calories)| *calories).collect();
+    if intake.len() < MIN_LOGGED_DAYS {
+        return None;
+    }
+    let weights: Vec<(NaiveDate, f64)> = weights.iter()
+        .filter(|(date, _)| start <= *date && *date <= end)
+        .copied()
+        .collect();
+    let (first, last) = (weights.first()?.0, weights.last()?.0);
+    if (last - first).num_days() < MIN_WEIGHT_SPAN_DAYS {
+        return None;
+    }
+
+    let slope = weight_slope(&weights);
+    let average_calories = intake.iter().sum::<f64>() / intake.len() as f64;
+    Some(AdaptiveEstimate {
+        start,
+        end,
+        days_logged: intake.len(),
+        average_calories,
+        weight_trend_per_week: slope * 7.0,
+        tdee: average_calories - slope * KCAL_PER_KG,
+    })
+}
+
+/// Pairs each week's average intake with its weight change
+///
+/// # Arguments
+/// * `daily_calories` - Calories of each logged day (see `daily_calories`)
+/// * `weights` - Weigh-ins in date order (see `measured_weights`)
+/// * `start` - A day of the first week
+/// * `end` - A day of the last week
+/// * `week_start` - First day of the week
+///
+/// # Returns
+/// Every week from the one containing `start` to the one containing `end`,
+/// in order, including weeks without data
+pub fn weekly_balance(
+    daily_calories: &BTreeMap<NaiveDate, f64>,
+    weights: &[(NaiveDate, f64)],
+    start: NaiveDate,
+    end: NaiveDate,
+    week_start: WeekStart,
+) -> Vec<WeekBalance> {
+    let mut weeks = Vec::new();
+    let mut first_day = week_start.week_of(start);
+    while first_day <= end {
+        let last_day = first_day + Duration::days(6);
+        let intake: Vec<f64> = daily_calories.range(first_day..=last_day).map(|(_, calories)| *calories).collect();
+        let in_week: Vec<f64> = weights.iter()
+            .filter(|(date, _)| first_day <= *date && *date <= last_day)
+            .map(|(_, weight)| *weight)
+            .collect();
+        let before = weights.iter().rev().find(|(date, _)| *date < first_day).map(|(_, weight)| *weight);
+        let weight = in_week.last().copied();
+        let weight_change = match (before, in_week.first(), weight) {
+            (Some(before), _, Some(last)) => Some(last - before),
+            (None, Some(first), Some(last)) if in_week.len() > 1 => Some(last - first),
+            _ => None,
+        };
+
+        weeks.push(WeekBalance {
+            start: first_day,
+            days_logged: intake.len(),
+            average_calories: (!intake.is_empty()).then(|| intake.iter().sum::<f64>() / intake.len() as f64),
+            weight,
+            weight_change,
+        });
+        first_day += Duration::days(7);
+    }
+    weeks
+}
+
+/// Least-squares slope of weight over time in kilograms per day
+fn weight_slope(weights: &[(NaiveDate, f64)]) -> f64 {
+    let origin = weights[0].0;
+    let points: Vec<(f64, f64)> = weights.iter()
+        .map(|(date, weight)| ((*date - origin).num_days() as f64, *weight))
+        .collect();
+    let count = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / count;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / count;
+    let covariance: f64 = points.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    if variance > 0.0 { covariance / variance } else { 0.0 }
+}
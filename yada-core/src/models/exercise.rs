@@ -0,0 +1,117 @@
+//! # Exercise Model
+//!
+//! Exercise entries record an activity, how long it lasted, and the
+//! calories it burned. Calories are estimated from the activity's MET value
+//! (metabolic equivalent of task): one MET is roughly one kilocalorie per
+//! kilogram of body weight per hour, so
+//!
+//! ```text
+//! calories = MET × weight (kg) × duration (hours)
+//! ```
+//!
+//! The estimate is stored with the entry when it is logged, so later weight
+//! changes do not rewrite past days.
+//!
+//! ## Calorie Credit
+//!
+//! The activity level in the user profile already accounts for habitual
+//! exercise, so burned calories are only added back to the daily target when
+//! enabled with the `YADA_EXERCISE_CREDIT` environment variable (`on`/`off`,
+//! default `off`). Turn it on when the profile's activity level describes the
+//! day without workouts.
+
+// yada-core/src/models/exercise.rs
+use std::env;
+
+use chrono::{DateTime, Local};
+
+/// Environment variable enabling the exercise calorie credit
+pub const EXERCISE_CREDIT_ENV_VAR: &str = "YADA_EXERCISE_CREDIT";
+
+/// An activity with its MET value
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Activity {
+    /// Name shown to the user and stored in the data file
+    pub name: &'static str,
+
+    /// Metabolic equivalent of task
+    pub met: f64,
+}
+
+/// Built-in activities, in the order they are listed to the user
+///
+/// Values follow the Compendium of Physical Activities for moderate effort.
+pub const ACTIVITIES: [Activity; 10] = [
+    Activity { name: "Walking", met: 3.5 },
+    Activity { name: "Brisk walking", met: 4.3 },
+    Activity { name: "Running", met: 9.8 },
+    Activity { name: "Cycling", met: 7.5 },
+    Activity { name: "Swimming", met: 6.0 },
+    Activity { name: "Strength training", met: 5.0 },
+    Activity { name: "Yoga", met: 2.5 },
+    Activity { name: "Hiking", met: 6.0 },
+    Activity { name: "Rowing", met: 7.0 },
+    Activity { name: "Dancing", met: 5.0 },
+];
+
+/// Finds a built-in activity by name (case-insensitive)
+pub fn find_activity(name: &str) -> Option<&'static Activity> {
+    ACTIVITIES.iter().find(|activity| activity.name.eq_ignore_ascii_case(name.trim()))
+}
+
+/// Estimates the calories burned by an activity
+///
+/// # Arguments
+/// * `met` - MET value of the activity
+/// * `weight_kg` - Body weight in kilograms
+/// * `minutes` - Duration of the activity
+pub fn estimate_calories(met: f64, weight_kg: f64, minutes: f64) -> f64 {
+    met * weight_kg * minutes / 60.0
+}
+
+/// Returns whether burned calories should be added to the daily target
+///
+/// Reads `YADA_EXERCISE_CREDIT`; only `on` (or `yes`, `true`, `1`) enables it.
+pub fn credit_enabled() -> bool {
+    env::var(EXERCISE_CREDIT_ENV_VAR)
+        .map(|value| matches!(value.trim().to_lowercase().as_str(), "on" | "yes" | "true" | "1"))
+        .unwrap_or(false)
+}
+
+/// One logged exercise session
+#[derive(Debug, Clone)]
+pub struct ExerciseEntry {
+    /// Activity name (a built-in activity or one typed by the user)
+    pub activity: String,
+
+    /// Duration in minutes
+    pub minutes: f64,
+
+    /// MET value used for the estimate
+    pub met: f64,
+
+    /// Estimated calories burned
+    pub calories_burned: f64,
+
+    /// When the entry was logged
+    pub timestamp: DateTime<Local>,
+}
+
+impl ExerciseEntry {
+    /// Creates an entry, estimating the calories burned from the body weight
+    ///
+    /// # Arguments
+    /// * `activity` - Activity name
+    /// * `met` - MET value of the activity
+    /// * `minutes` - Duration in minutes
+    /// * `weight_kg` - Body weight used for the estimate
+    pub fn new(activity: String, met: f64, minutes: f64, weight_kg: f64) -> Self {
+        ExerciseEntry {
+            activity,
+            minutes,
+            met,
+            calories_burned: estimate_calories(met, weight_kg, minutes),
+            timestamp: Local::now(),
+        }
+    }
+}
@@ -0,0 +1,516 @@
+//! Food Model - Implements Composite Pattern for Food Hierarchy
+//! 
+//! This module defines the core food entities used throughout the YADA application.
+//! It implements the Composite Pattern to handle both simple and complex food types
+//! seamlessly within the same interface.
+//! 
+//! ## Design Pattern: Composite Pattern
+//! The Food struct can represent:
+//! - **Basic Foods**: Simple food items with direct calorie values (e.g., apple, bread)
+//! - **Composite Foods**: Complex foods built from multiple components (e.g., sandwich, recipe)
+//! 
+//! This allows treating individual foods and compositions of foods uniformly,
+//! enabling complex meal planning and nutritional calculations.
+
+// yada-core/src/models/food.rs
+use std::collections::{HashMap, HashSet};
+use std::ops::AddAssign;
+
+use chrono::NaiveDate;
+
+use crate::models::category;
+use crate::models::derived_keywords::KeywordDerivation;
+use crate::models::serving::{ServingSize, ServingUnit, UnitKind};
+
+/// Enumeration defining the type of food item
+/// 
+/// This supports the Composite Pattern by distinguishing between:
+/// - Basic: Simple food items with direct nutritional values
+/// - Composite: Complex foods composed of multiple food components
+#[derive(Debug, Clone, PartialEq)]
+pub enum FoodType {
+    Basic,
+    Composite,
+}
+
+/// Macronutrient content of a serving, in grams, with its alcohol and caffeine
+/// 
+/// Basic foods store the values entered by the user (all zero when unknown);
+/// composite foods derive them from their components like calories.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Macros {
+    /// Protein in grams
+    pub protein: f64,
+    
+    /// Carbohydrates in grams
+    pub carbs: f64,
+    
+    /// Fat in grams
+    pub fat: f64,
+    
+    /// Dietary fiber in grams (part of the carbohydrates; 0 when unknown)
+    pub fiber: f64,
+    
+    /// Alcohol in grams (0 when unknown)
+    pub alcohol: f64,
+    
+    /// Caffeine in milligrams (0 when unknown); not a macronutrient, but
+    /// added up per recipe and per day the same way
+    pub caffeine: f64,
+}
+
+impl Macros {
+    /// Creates a macronutrient set from gram values
+    pub fn new(protein: f64, carbs: f64, fat: f64) -> Self {
+        Macros { protein, carbs, fat, ..Macros::default() }
+    }
+    
+    /// Sets the fiber content in grams
+    pub fn with_fiber(mut self, fiber: f64) -> Self {
+        self.fiber = fiber;
+        self
+    }
+    
+    /// Sets the alcohol content in grams and the caffeine content in milligrams
+    pub fn with_alcohol_and_caffeine(mut self, alcohol: f64, caffeine: f64) -> Self {
+        self.alcohol = alcohol;
+        self.caffeine = caffeine;
+        self
+    }
+    
+    /// Returns these macros multiplied by a number of servings
+    pub fn scaled(&self, servings: f64) -> Self {
+        Macros {
+            protein: self.protein * servings,
+            carbs: self.carbs * servings,
+            fat: self.fat * servings,
+            fiber: self.fiber * servings,
+            alcohol: self.alcohol * servings,
+            caffeine: self.caffeine * servings,
+        }
+    }
+    
+    /// Returns true when no macronutrient information is recorded
+    pub fn is_empty(&self) -> bool {
+        self.protein == 0.0 && self.carbs == 0.0 && self.fat == 0.0 && self.fiber == 0.0
+            && self.alcohol == 0.0 && self.caffeine == 0.0
+    }
+}
+
+impl AddAssign for Macros {
+    fn add_assign(&mut self, other: Macros) {
+        self.protein += other.protein;
+        self.carbs += other.carbs;
+        self.fat += other.fat;
+        self.fiber += other.fiber;
+        self.alcohol += other.alcohol;
+        self.caffeine += other.caffeine;
+    }
+}
+
+/// Core food entity implementing the Composite Pattern
+/// 
+/// The Food struct provides a unified interface for both basic and composite foods:
+/// 
+/// ## Basic Foods:
+/// - Have direct calorie values (`calories_per_serving`)
+/// - Empty components vector
+/// - Represent simple food items (fruits, vegetables, basic ingredients)
+/// 
+/// ## Composite Foods:
+/// - Calorie value calculated from components
+/// - Components vector contains (food_id, servings) pairs
+/// - Represent complex foods (recipes, meals, prepared dishes)
+/// 
+/// ## Search Functionality:
+/// Both food types support keyword-based searching with AND/OR logic
+/// for flexible food discovery and management. Keywords derived from the
+/// category and tags are searched as well (see `derived_keywords`).
+#[derive(Debug, Clone)]
+pub struct Food {
+    /// Unique identifier for the food item (no spaces, used for lookups)
+    pub id: String,
+    
+    /// Human-readable name for display purposes
+    pub name: String,
+    
+    /// Set of lowercase keywords for search functionality
+    pub keywords: HashSet<String>,
+    
+    /// Calories per serving (direct for basic foods, calculated for composite)
+    pub calories_per_serving: f64,
+    
+    /// Macronutrients per serving (direct for basic foods, calculated for composite)
+    pub macros: Macros,
+    
+    /// Weight of one serving in grams, if known (enables per-100 g comparisons)
+    pub grams_per_serving: Option<f64>,
+    
+    /// Price of one serving, if known (in the user's currency)
+    pub cost_per_serving: Option<f64>,
+    
+    /// What one serving is, such as `1 cup` or `30 g`, if known
+    pub serving_size: Option<ServingSize>,
+    
+    /// Servings offered when logging the food, if not 1 (e.g. 2 for eggs)
+    pub default_servings: Option<f64>,
+    
+    /// Food group such as "Dairy" or "Fruit", if assigned
+    pub category: Option<String>,
+    
+    /// Structured tags such as "vegan" or "contains-gluten"
+    pub tags: HashSet<String>,
+    
+    /// Keywords derived from the category and tags (never persisted)
+    pub derived_keywords: HashSet<String>,
+    
+    /// Most recent date the food was logged, directly or as a component
+    /// (see `models::usage`)
+    pub last_used: Option<NaiveDate>,
+    
+    /// Archived foods are hidden from pickers but still resolve by ID
+    pub archived: bool,
+    
+    /// Food pack the food was installed from, if any (see `repositories::food_pack`)
+    pub pack: Option<String>,
+    
+    /// Type indicator for Composite Pattern implementation
+    pub food_type: FoodType,
+    
+    /// Components for composite foods: (food_id, serving_amount) pairs
+    pub components: Vec<(String, f64)>,
+}
+
+impl Food {
+    /// Creates a new basic food item with direct calorie specification
+    /// 
+    /// Basic foods represent simple food items that have known nutritional
+    /// values and don't need to be broken down into components.
+    /// 
+    /// # Arguments
+    /// * `id` - Unique identifier (no spaces)
+    /// * `name` - Display name for the food
+    /// * `keywords` - Search keywords (should be lowercase)
+    /// * `calories` - Direct calorie value per serving
+    /// 
+    /// # Examples
+    /// ```ignore
+    /// let apple = Food::new_basic(
+    ///     "apple".to_string(),
+    ///     "Apple".to_string(),
+    ///     keywords,
+    ///     95.0
+    /// );
+    /// ```
+    pub fn new_basic(id: String, name: String, keywords: HashSet<String>, calories: f64) -> Self {
+        Food {
+            id,
+            name,
+            keywords,
+            calories_per_serving: calories,
+            macros: Macros::default(),
+            grams_per_serving: None,
+            cost_per_serving: None,
+            serving_size: None,
+            category: None,
+            tags: HashSet::new(),
+            derived_keywords: HashSet::new(),
+            last_used: None,
+            archived: false,
+            pack: None,
+            default_servings: None,
+            food_type: FoodType::Basic,
+            components: Vec::new(),
+        }
+    }
+
+    /// Creates a new basic food whose nutrition is given per 100 g
+    /// 
+    /// Labels usually list nutrition per 100 g. Such a food has a serving
+    /// size of exactly 100 g, so it can be logged by weight ("85 g") and the
+    /// calories and macros entered are the per-100 g values.
+    /// 
+    /// # Arguments
+    /// * `id` - Unique identifier (no spaces)
+    /// * `name` - Display name for the food
+    /// * `keywords` - Search keywords (should be lowercase)
+    /// * `calories_per_100g` - Calories in 100 g of the food
+    pub fn new_basic_per_100g(id: String, name: String, keywords: HashSet<String>, calories_per_100g: f64) -> Self {
+        Food::new_basic(id, name, keywords, calories_per_100g)
+            .with_serving_size(Some(ServingSize::new(100.0, ServingUnit::Gram)))
+    }
+
+    /// Creates a new composite food item built from existing food components
+    /// 
+    /// Composite foods implement the Composite Pattern by allowing complex foods
+    /// to be built from simpler components. The calorie value is calculated
+    /// automatically based on the components and their serving amounts.
+    /// 
+    /// # Arguments
+    /// * `id` - Unique identifier (no spaces)
+    /// * `name` - Display name for the composite food
+    /// * `keywords` - Search keywords (should be lowercase)
+    /// * `components` - Vector of (food_id, servings) pairs that make up this food
+    /// 
+    /// # Examples
+    /// ```ignore
+    /// let sandwich = Food::new_composite(
+    ///     "sandwich".to_string(),
+    ///     "Ham Sandwich".to_string(),
+    ///     keywords,
+    ///     vec![("bread".to_string(), 2.0), ("ham".to_string(), 1.0)]
+    /// );
+    /// ```
+    /// 
+    /// Note: The calories_per_serving is initially set to 0.0 and should be
+    /// calculated with `calculate_from_components` once the components are known.
+    pub fn new_composite(id: String, name: String, keywords: HashSet<String>, components: Vec<(String, f64)>) -> Self {
+        Food {
+            id,
+            name,
+            keywords,
+            calories_per_serving: 0.0, // Will be calculated later based on components
+            macros: Macros::default(),
+            grams_per_serving: None,
+            cost_per_serving: None,
+            serving_size: None,
+            category: None,
+            tags: HashSet::new(),
+            derived_keywords: HashSet::new(),
+            last_used: None,
+            archived: false,
+            pack: None,
+            default_servings: None,
+            food_type: FoodType::Composite,
+            components,
+        }
+    }
+
+    /// Sets the macronutrients per serving (builder style, for basic foods)
+    /// 
+    /// # Examples
+    /// ```ignore
+    /// let egg = Food::new_basic(id, name, keywords, 70.0)
+    ///     .with_macros(Macros::new(6.0, 0.6, 5.0));
+    /// ```
+    pub fn with_macros(mut self, macros: Macros) -> Self {
+        self.macros = macros;
+        self
+    }
+
+    /// Sets the serving weight and cost (builder style, for basic foods)
+    /// 
+    /// # Arguments
+    /// * `grams` - Weight of one serving in grams, if known
+    /// * `cost` - Price of one serving, if known
+    pub fn with_serving_details(mut self, grams: Option<f64>, cost: Option<f64>) -> Self {
+        self.grams_per_serving = grams;
+        self.cost_per_serving = cost;
+        self
+    }
+
+    /// Sets what one serving is (builder style, for basic foods)
+    /// 
+    /// A serving size given as a mass also fills in `grams_per_serving`
+    /// when that is not known yet.
+    /// 
+    /// # Examples
+    /// ```ignore
+    /// let milk = Food::new_basic(id, name, keywords, 150.0)
+    ///     .with_serving_size(Some(ServingSize::new(1.0, ServingUnit::Cup)));
+    /// ```
+    pub fn with_serving_size(mut self, size: Option<ServingSize>) -> Self {
+        self.serving_size = size;
+        if self.grams_per_serving.is_none() {
+            self.grams_per_serving = size.and_then(|size| size.grams());
+        }
+        self
+    }
+
+    /// Sets the servings offered when logging the food (builder style)
+    /// 
+    /// `None` or 1 means the usual single serving; amounts that are not
+    /// positive are ignored.
+    pub fn with_default_servings(mut self, servings: Option<f64>) -> Self {
+        self.default_servings = servings
+            .filter(|servings| *servings > 0.0 && servings.is_finite() && *servings != 1.0);
+        self
+    }
+    
+    /// Returns the servings offered when logging the food (1 unless set)
+    pub fn servings_to_log(&self) -> f64 {
+        self.default_servings.unwrap_or(1.0)
+    }
+
+    /// Sets the category and tags (builder style)
+    /// 
+    /// Standard categories get their standard spelling (see `category`);
+    /// tags are trimmed and lowercased; empty values are dropped. Derived
+    /// keywords are filled in once the food is added to a repository.
+    /// 
+    /// # Examples
+    /// ```ignore
+    /// let milk = Food::new_basic(id, name, keywords, 120.0)
+    ///     .with_metadata(Some("Dairy".to_string()), tags);
+    /// ```
+    pub fn with_metadata(mut self, category: Option<String>, tags: HashSet<String>) -> Self {
+        self.category = category.as_deref().and_then(category::normalize);
+        self.tags = tags
+            .iter()
+            .map(|t| t.trim().to_lowercase())
+            .filter(|t| !t.is_empty())
+            .collect();
+        self
+    }
+
+    /// Recomputes the keywords derived from the category and tags
+    /// 
+    /// # Arguments
+    /// * `derivation` - Which metadata to derive keywords from
+    pub fn refresh_derived_keywords(&mut self, derivation: &KeywordDerivation) {
+        self.derived_keywords = derivation.derive(self);
+    }
+
+    /// Returns true if a keyword was entered by the user or derived from metadata
+    pub fn has_keyword(&self, keyword: &str) -> bool {
+        self.keywords.contains(keyword) || self.derived_keywords.contains(keyword)
+    }
+
+    /// Scales a per-serving value to a per-100 g value
+    /// 
+    /// # Returns
+    /// * `Some(f64)` - The value per 100 g
+    /// * `None` - If the serving weight is unknown or zero
+    pub fn per_100g(&self, per_serving: f64) -> Option<f64> {
+        match self.grams_per_serving {
+            Some(grams) if grams > 0.0 => Some(per_serving * 100.0 / grams),
+            _ => None,
+        }
+    }
+
+    /// Calories in 100 g of the food (its calorie density)
+    /// 
+    /// # Returns
+    /// * `Some(f64)` - kcal per 100 g
+    /// * `None` - If the serving weight is unknown or zero
+    pub fn calorie_density(&self) -> Option<f64> {
+        self.per_100g(self.calories_per_serving)
+    }
+
+    /// Scales a per-serving value to the amount one unit of money buys
+    /// 
+    /// # Returns
+    /// * `Some(f64)` - The value per unit of currency (e.g. kcal per dollar)
+    /// * `None` - If the cost is unknown or zero
+    pub fn per_unit_cost(&self, per_serving: f64) -> Option<f64> {
+        match self.cost_per_serving {
+            Some(cost) if cost > 0.0 => Some(per_serving / cost),
+            _ => None,
+        }
+    }
+
+    /// Converts an amount of this food into servings
+    /// 
+    /// Amounts in the unit kind of the serving size (e.g. ml for a food
+    /// served by the cup) convert through the serving size. Masses also
+    /// convert through `grams_per_serving`, which composite foods derive
+    /// from their components.
+    /// 
+    /// # Arguments
+    /// * `amount` - The amount eaten or used
+    /// * `unit` - Unit of `amount`; `None` means servings
+    /// 
+    /// # Returns
+    /// * `Ok(f64)` - The amount in servings
+    /// * `Err(String)` - If the food has no size information for that unit
+    pub fn servings_for(&self, amount: f64, unit: Option<ServingUnit>) -> Result<f64, String> {
+        let Some(unit) = unit else {
+            return Ok(amount);
+        };
+        
+        if let Some(size) = self.serving_size
+            && let Some(in_serving_units) = unit.convert(amount, size.unit)
+            && size.amount > 0.0
+        {
+            return Ok(in_serving_units / size.amount);
+        }
+        if unit.kind() == UnitKind::Mass
+            && let Some(grams) = unit.convert(amount, ServingUnit::Gram)
+            && let Some(serving_grams) = self.grams_per_serving.filter(|g| *g > 0.0)
+        {
+            return Ok(grams / serving_grams);
+        }
+        
+        let known = match self.serving_size {
+            Some(size) => format!("one serving is {}", size),
+            None => "its serving size is unknown".to_string(),
+        };
+        Err(format!("Cannot measure {} in {}: {}.", self.name, unit, known))
+    }
+
+    /// Recalculates calories and macros of a composite food from its components
+    /// 
+    /// Each component contributes its per-serving values multiplied by the
+    /// number of servings used. Components missing from `food_db` are ignored.
+    /// Serving weight and cost are only derived when every component knows
+    /// them, since a partial sum would be misleading.
+    /// Basic foods are left unchanged.
+    /// 
+    /// # Arguments
+    /// * `food_db` - All known foods, used to resolve component IDs
+    pub fn calculate_from_components(&mut self, food_db: &HashMap<String, Food>) {
+        if self.food_type != FoodType::Composite {
+            return;
+        }
+        
+        let mut total_calories = 0.0;
+        let mut total_macros = Macros::default();
+        let mut total_grams = Some(0.0);
+        let mut total_cost = Some(0.0);
+        for (comp_id, servings) in &self.components {
+            if let Some(component) = food_db.get(comp_id) {
+                total_calories += component.calories_per_serving * servings;
+                total_macros += component.macros.scaled(*servings);
+                total_grams = total_grams.zip(component.grams_per_serving).map(|(t, g)| t + g * servings);
+                total_cost = total_cost.zip(component.cost_per_serving).map(|(t, c)| t + c * servings);
+            }
+        }
+        
+        self.calories_per_serving = total_calories;
+        self.macros = total_macros;
+        self.grams_per_serving = total_grams;
+        self.cost_per_serving = total_cost;
+    }
+
+    /// Performs keyword-based search matching with flexible AND/OR logic
+    /// 
+    /// This method enables flexible food searching by allowing users to specify
+    /// whether all keywords must match (AND logic) or any keyword can match (OR logic).
+    /// 
+    /// # Arguments
+    /// * `search_keywords` - Set of keywords to search for (should be lowercase)
+    /// * `match_all` - If true, ALL search keywords must be found (AND logic).
+    ///   If false, ANY search keyword match is sufficient (OR logic)
+    /// 
+    /// # Returns
+    /// * `true` if the food matches the search criteria
+    /// * `false` if the food doesn't match the search criteria
+    /// 
+    /// # Examples
+    /// ```ignore
+    /// // AND search: food must have both "fruit" AND "red" keywords
+    /// let matches_and = food.matches_keywords(&search_terms, true);
+    /// 
+    /// // OR search: food must have either "fruit" OR "red" keyword
+    /// let matches_or = food.matches_keywords(&search_terms, false);
+    /// ```
+    pub fn matches_keywords(&self, search_keywords: &HashSet<String>, match_all: bool) -> bool {
+        if match_all {
+            // AND logic: all search keywords must be present in food keywords
+            search_keywords.iter().all(|k| self.has_keyword(k))
+        } else {
+            // OR logic: any search keyword match is sufficient
+            search_keywords.iter().any(|k| self.has_keyword(k))
+        }
+    }
+}
\ No newline at end of file
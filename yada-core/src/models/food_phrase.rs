@@ -0,0 +1,185 @@
+//! # Natural-Language Food Phrases
+//!
+//! Reads what was eaten as the user would say it, such as
+//! `2 eggs and a slice of wheat bread`, so logging takes one line instead of
+//! a trip through the food list for every food.
+//!
+//! ## Phrases
+//!
+//! The text is split into phrases at commas, `and`, `&`, `plus`, and
+//! `with`. Each phrase is an optional amount, an optional unit, and the
+//! words naming the food:
+//!
+//! - **Amount**: a number (`2`, `1.5`, `1/2`, `150g`), `a`/`an`/`one` to
+//!   `twelve`, or `half` (`half a cup`). Without one the food's default
+//!   servings are used (see `Food::servings_to_log`).
+//! - **Unit**: a serving unit (`g`, `ml`, `cups`, `pieces`, `oz`; see
+//!   `models::serving`) or a word that means one serving, such as `slice`,
+//!   `bowl`, or `glass`. A following `of` is skipped.
+//! - **Food words**: everything else except filler words (`some`, `the`).
+//!   Plurals are reduced to a singular stem (`eggs` → `egg`), which still
+//!   matches the full word by prefix.
+//!
+//! The food words are search terms: the caller finds the food with ranked
+//! search (see `FoodRepository::search_ranked`), every word having to match,
+//! and confirms the interpretation before logging.
+
+// yada-core/src/models/food_phrase.rs
+use std::collections::HashSet;
+
+use crate::models::food::Food;
+use crate::models::quantity::Quantity;
+use crate::models::search;
+use crate::models::serving::{self, ServingUnit};
+
+/// Words that separate phrases
+const SEPARATORS: [&str; 6] = [",", "and", "&", "plus", "with", "+"];
+
+/// Words that describe one serving of whatever follows
+const SERVING_WORDS: [&str; 16] = [
+    "serving", "servings", "portion", "portions", "helping", "helpings", "slice", "slices",
+    "bowl", "bowls", "glass", "glasses", "plate", "plates", "scoop", "scoops",
+];
+
+/// Words ignored among the food words
+const FILLER_WORDS: [&str; 5] = ["of", "some", "the", "my", "x"];
+
+/// Amounts written as words
+const NUMBER_WORDS: [(&str, f64); 15] = [
+    ("a", 1.0), ("an", 1.0), ("one", 1.0), ("two", 2.0), ("three", 3.0), ("four", 4.0),
+    ("five", 5.0), ("six", 6.0), ("seven", 7.0), ("eight", 8.0), ("nine", 9.0), ("ten", 10.0),
+    ("eleven", 11.0), ("twelve", 12.0), ("half", 0.5),
+];
+
+/// One food of a sentence with the amount said for it
+#[derive(Debug, Clone, PartialEq)]
+pub struct FoodPhrase {
+    /// The phrase as typed, for showing the interpretation
+    pub text: String,
+
+    /// Amount said, if any
+    pub quantity: Option<Quantity>,
+
+    /// Lowercase search terms naming the food
+    pub terms: HashSet<String>,
+}
+
+impl FoodPhrase {
+    /// Converts the phrase's amount into servings of the matched food
+    ///
+    /// Without an amount the food's default servings are used. Pieces of a
+    /// food that is not measured in pieces count as servings, so
+    /// `a piece of cake` works for any cake.
+    ///
+    /// # Returns
+    /// * `Ok((Quantity, f64))` - The quantity logged and its value in servings
+    /// * `Err(String)` - If the food cannot be measured in the unit said
+    pub fn to_servings(&self, food: &Food) -> Result<(Quantity, f64), String> {
+        let quantity = self.quantity.unwrap_or_else(|| Quantity::servings(food.servings_to_log()));
+        match quantity.to_servings(food) {
+            Ok(servings) => Ok((quantity, servings)),
+            Err(_) if quantity.unit == Some(ServingUnit::Piece) => Ok((Quantity::servings(quantity.amount), quantity.amount)),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Splits a sentence into food phrases
+///
+/// # Returns
+/// * `Ok(Vec<FoodPhrase>)` - The phrases in the order typed
+/// * `Err(String)` - If a phrase names no food, or the sentence has none
+pub fn parse(text: &str) -> Result<Vec<FoodPhrase>, String> {
+    let spaced = text.to_lowercase().replace(',', " , ");
+    let mut phrases = Vec::new();
+    let mut words: Vec<&str> = Vec::new();
+    for word in spaced.split_whitespace().chain([","]) {
+        if !SEPARATORS.contains(&word) {
+            words.push(word);
+            continue;
+        }
+        if !words.is_empty() {
+            phrases.push(parse_phrase(&words)?);
+            words.clear();
+        }
+    }
+
+    if phrases.is_empty() {
+        return Err("Please say what you ate, e.g. 2 eggs and a slice of wheat bread".to_string());
+    }
+    Ok(phrases)
+}
+
+/// Reads the amount, unit, and food words of one phrase
+fn parse_phrase(words: &[&str]) -> Result<FoodPhrase, String> {
+    let text = words.join(" ");
+    let mut rest = words;
+
+    let mut quantity = None;
+    if let Some((first, after)) = rest.split_first()
+        && let Some(amount) = parse_amount(first)
+    {
+        quantity = Some(amount);
+        rest = after;
+        // "half a cup" reads the article as part of the amount
+        if *first == "half" {
+            rest = rest.strip_prefix(&["a"]).or_else(|| rest.strip_prefix(&["an"])).unwrap_or(rest);
+        }
+    }
+
+    // A unit needs food words after it, so "2 cups" alone names a food called cups
+    if let (Some(amount), Some((word, after))) = (quantity, rest.split_first())
+        && amount.unit.is_none()
+        && after.iter().any(|word| !FILLER_WORDS.contains(word))
+    {
+        if let Some(unit) = ServingUnit::parse(word) {
+            quantity = Some(Quantity::in_unit(amount.amount, unit));
+            rest = after;
+        } else if SERVING_WORDS.contains(word) {
+            rest = after;
+        }
+    }
+
+    let terms: HashSet<String> = rest.iter()
+        .filter(|word| !FILLER_WORDS.contains(word))
+        .flat_map(|word| search::name_words(word).collect::<Vec<_>>())
+        .map(|word| singular(&word))
+        .collect();
+    if terms.is_empty() {
+        return Err(format!("'{}' does not say which food", text));
+    }
+    Ok(FoodPhrase { text, quantity, terms })
+}
+
+/// Reads an amount word: a number, a fraction, a number with a unit, or a number word
+fn parse_amount(word: &str) -> Option<Quantity> {
+    if let Some((_, amount)) = NUMBER_WORDS.iter().find(|(name, _)| *name == word) {
+        return Some(Quantity::servings(*amount));
+    }
+    if let Some((numerator, denominator)) = word.split_once('/') {
+        let numerator: f64 = numerator.parse().ok()?;
+        let denominator: f64 = denominator.parse().ok()?;
+        let amount = numerator / denominator;
+        return (amount > 0.0 && amount.is_finite()).then(|| Quantity::servings(amount));
+    }
+    if !word.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+        return None;
+    }
+    serving::parse_quantity(word).ok().map(|(amount, unit)| Quantity { amount, unit })
+}
+
+/// Reduces a plural to its singular stem (`berries` → `berry`, `eggs` → `egg`)
+///
+/// Short words and words ending in `ss` are kept as they are.
+fn singular(word: &str) -> String {
+    if word.chars().count() <= 3 || word.ends_with("ss") {
+        return word.to_string();
+    }
+    if let Some(stem) = word.strip_suffix("ies") {
+        return format!("{}y", stem);
+    }
+    if let Some(stem) = word.strip_suffix("oes") {
+        return format!("{}o", stem);
+    }
+    word.strip_suffix('s').unwrap_or(word).to_string()
+}
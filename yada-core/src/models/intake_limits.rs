@@ -0,0 +1,120 @@
+//! # Alcohol and Caffeine Limits
+//!
+//! Foods may record their alcohol (grams) and caffeine (milligrams) per
+//! serving (see `Macros`). The day's totals are compared with daily limits,
+//! so statistics can warn people cutting back when a day goes over.
+//!
+//! ## Limits
+//!
+//! - **Alcohol**: `YADA_ALCOHOL_LIMIT` grams per day (default 28, about two
+//!   standard drinks of 14 g)
+//! - **Caffeine**: `YADA_CAFFEINE_LIMIT` milligrams per day (default 400,
+//!   the amount generally considered safe for healthy adults)
+//!
+//! Setting a limit to `0` or `off` turns its warning off. Invalid or
+//! negative values fall back to the default.
+
+// yada-core/src/models/intake_limits.rs
+use std::env;
+use std::fmt;
+
+use crate::models::food::Macros;
+
+/// Environment variable with the daily alcohol limit in grams
+pub const ALCOHOL_LIMIT_ENV_VAR: &str = "YADA_ALCOHOL_LIMIT";
+
+/// Environment variable with the daily caffeine limit in milligrams
+pub const CAFFEINE_LIMIT_ENV_VAR: &str = "YADA_CAFFEINE_LIMIT";
+
+/// Default daily alcohol limit in grams
+pub const DEFAULT_ALCOHOL_LIMIT: f64 = 28.0;
+
+/// Default daily caffeine limit in milligrams
+pub const DEFAULT_CAFFEINE_LIMIT: f64 = 400.0;
+
+/// Daily limits; `None` means no warning
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IntakeLimits {
+    /// Grams of alcohol per day
+    pub alcohol: Option<f64>,
+
+    /// Milligrams of caffeine per day
+    pub caffeine: Option<f64>,
+}
+
+impl Default for IntakeLimits {
+    fn default() -> Self {
+        IntakeLimits {
+            alcohol: Some(DEFAULT_ALCOHOL_LIMIT),
+            caffeine: Some(DEFAULT_CAFFEINE_LIMIT),
+        }
+    }
+}
+
+/// A daily total over its limit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LimitWarning {
+    /// What went over the limit (`Alcohol` or `Caffeine`)
+    pub substance: &'static str,
+
+    /// Amount consumed during the day
+    pub amount: f64,
+
+    /// The daily limit
+    pub limit: f64,
+
+    /// Unit of the amounts (`g` or `mg`)
+    pub unit: &'static str,
+}
+
+impl fmt::Display for LimitWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {:.0} {unit} is over your daily limit of {:.0} {unit}",
+               self.substance, self.amount, self.limit, unit = self.unit)
+    }
+}
+
+impl IntakeLimits {
+    /// Reads the limits from `YADA_ALCOHOL_LIMIT` and `YADA_CAFFEINE_LIMIT`
+    pub fn from_env() -> Self {
+        IntakeLimits {
+            alcohol: parse_limit(env::var(ALCOHOL_LIMIT_ENV_VAR).ok().as_deref(), DEFAULT_ALCOHOL_LIMIT),
+            caffeine: parse_limit(env::var(CAFFEINE_LIMIT_ENV_VAR).ok().as_deref(), DEFAULT_CAFFEINE_LIMIT),
+        }
+    }
+
+    /// Returns the day's totals that are over their limits
+    ///
+    /// # Arguments
+    /// * `totals` - What was consumed during the day
+    pub fn exceeded(&self, totals: &Macros) -> Vec<LimitWarning> {
+        [
+            ("Alcohol", totals.alcohol, self.alcohol, "g"),
+            ("Caffeine", totals.caffeine, self.caffeine, "mg"),
+        ]
+        .into_iter()
+        .filter_map(|(substance, amount, limit, unit)| {
+            limit.filter(|limit| amount > *limit).map(|limit| LimitWarning { substance, amount, limit, unit })
+        })
+        .collect()
+    }
+}
+
+/// Reads one limit setting
+///
+/// # Returns
+/// * `Some(f64)` - The limit, or the default if the setting is missing or invalid
+/// * `None` - If the setting is `0` or `off`
+pub fn parse_limit(value: Option<&str>, default: f64) -> Option<f64> {
+    let Some(value) = value.map(str::trim) else {
+        return Some(default);
+    };
+    if value.eq_ignore_ascii_case("off") {
+        return None;
+    }
+    match value.parse::<f64>() {
+        Ok(0.0) => None,
+        Ok(limit) if limit.is_finite() && limit > 0.0 => Some(limit),
+        _ => Some(default),
+    }
+}
@@ -0,0 +1,103 @@
+//! # Keyword Suggestions
+//!
+//! Typing keyword lists by hand for every new food is tedious and leads to
+//! near-duplicates (`fruit` on one food, `fruits` on the next). When a food
+//! is added, `suggest` proposes keywords from its name, preferring the
+//! keywords the database already uses.
+//!
+//! ## Sources
+//!
+//! In this order:
+//! - **Name words already used as keywords**: `Greek Yogurt` suggests
+//!   `yogurt` when another food has that keyword; plurals find singular
+//!   keywords and the other way round (`Blueberries` suggests `blueberry`)
+//! - **Keywords of related foods**: keywords of existing foods whose names
+//!   share a word with the new name, in either number (`Greek Yogurt`
+//!   suggests `dairy` when `Plain Yogurt` has it), the most common first
+//!
+//! Words shorter than three letters never relate foods, and at most
+//! `MAX_SUGGESTIONS` keywords are suggested.
+
+// yada-core/src/models/keyword_suggestion.rs
+use std::collections::{HashMap, HashSet};
+
+use crate::models::food::Food;
+use crate::models::search;
+
+/// Most keywords suggested for a food
+pub const MAX_SUGGESTIONS: usize = 8;
+
+/// Shortest name word used to find related foods
+const MIN_WORD_LENGTH: usize = 3;
+
+/// Counts how many foods use each keyword
+pub fn vocabulary<'a>(foods: impl IntoIterator<Item = &'a Food>) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for keyword in foods.into_iter().flat_map(|food| &food.keywords) {
+        *counts.entry(keyword.clone()).or_default() += 1;
+    }
+    counts
+}
+
+/// Suggests keywords for a new food
+///
+/// # Arguments
+/// * `name` - Name of the new food
+/// * `foods` - Existing foods, whose keywords form the vocabulary
+///
+/// # Returns
+/// Suggested keywords, best first; empty if nothing in the database relates to the name
+pub fn suggest<'a>(name: &str, foods: impl IntoIterator<Item = &'a Food> + Clone) -> Vec<String> {
+    let vocabulary = vocabulary(foods.clone());
+    let words: Vec<String> = search::name_words(name).collect();
+
+    let mut suggestions: Vec<String> = Vec::new();
+    for word in &words {
+        if let Some(keyword) = variants(word).into_iter().find(|variant| vocabulary.contains_key(variant))
+            && !suggestions.contains(&keyword) {
+            suggestions.push(keyword);
+        }
+    }
+
+    // Keywords of foods sharing a name word, by how many of them use it
+    let related_words: HashSet<String> = words.iter()
+        .filter(|word| word.chars().count() >= MIN_WORD_LENGTH)
+        .flat_map(|word| variants(word))
+        .collect();
+    let mut related: HashMap<&String, usize> = HashMap::new();
+    for food in foods {
+        if search::name_words(&food.name).any(|word| related_words.contains(&word)) {
+            for keyword in &food.keywords {
+                *related.entry(keyword).or_default() += 1;
+            }
+        }
+    }
+    let mut related: Vec<(&String, usize)> = related.into_iter()
+        .filter(|(keyword, _)| !suggestions.contains(keyword))
+        .collect();
+    related.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    suggestions.extend(related.into_iter().map(|(keyword, _)| keyword.clone()));
+
+    suggestions.truncate(MAX_SUGGESTIONS);
+    suggestions
+}
+
+/// Returns a word followed by its possible singular and plural forms
+/// (`berries` → `berry`, `tomatoes` → `tomato`, `apple` → `apples`)
+fn variants(word: &str) -> Vec<String> {
+    let mut forms = vec![word.to_string()];
+    if let Some(stem) = word.strip_suffix("ies") {
+        forms.push(format!("{}y", stem));
+    }
+    if let Some(stem) = word.strip_suffix("es") {
+        forms.push(stem.to_string());
+    }
+    if let Some(stem) = word.strip_suffix('s') {
+        forms.push(stem.to_string());
+    }
+    match word.strip_suffix('y') {
+        Some(stem) if !stem.ends_with(['a', 'e', 'o', 'u']) => forms.push(format!("{}ies", stem)),
+        _ => forms.push(format!("{}s", word)),
+    }
+    forms
+}
@@ -0,0 +1,124 @@
+//! # Life Stages
+//!
+//! The calculation methods estimate what a non-pregnant adult burns.
+//! Pregnancy and breastfeeding need extra energy, which is added to the
+//! calculated TDEE before the diet preset and weekday override are applied.
+//!
+//! ## Additions
+//!
+//! Following the Institute of Medicine's estimated energy requirements:
+//!
+//! | Stage                                  | Calories |
+//! |----------------------------------------|----------|
+//! | Pregnancy, 1st trimester (weeks 1-13)  | +0       |
+//! | Pregnancy, 2nd trimester (weeks 14-27) | +340     |
+//! | Pregnancy, 3rd trimester (week 28 on)  | +452     |
+//! | Breastfeeding, first 6 months          | +330     |
+//! | Breastfeeding, after 6 months          | +400     |
+//!
+//! Pregnancy weeks are counted from 280 days before the due date, and a
+//! stage applies until it is changed. Days before a stage began get no
+//! addition, so targets of earlier days stay as they were. The stage is
+//! stored in the profile file (see `ProfileRepository`), such as
+//! `LIFESTAGE|pregnant|2024-09-01`.
+
+// yada-core/src/models/life_stage.rs
+use std::fmt;
+
+use chrono::{Duration, Months, NaiveDate};
+
+/// Length of a pregnancy from its first week to the due date
+const PREGNANCY_DAYS: i64 = 280;
+
+/// A life stage that needs more energy than the formulas estimate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LifeStage {
+    /// Pregnant, with the expected due date
+    Pregnant { due_date: NaiveDate },
+
+    /// Breastfeeding since the baby's birth date
+    Breastfeeding { since: NaiveDate },
+}
+
+impl LifeStage {
+    /// Key used in the profile file
+    pub fn key(&self) -> &'static str {
+        match self {
+            LifeStage::Pregnant { .. } => "pregnant",
+            LifeStage::Breastfeeding { .. } => "breastfeeding",
+        }
+    }
+
+    /// Date stored with the stage: the due date or the birth date
+    pub fn date(&self) -> NaiveDate {
+        match self {
+            LifeStage::Pregnant { due_date } => *due_date,
+            LifeStage::Breastfeeding { since } => *since,
+        }
+    }
+
+    /// Creates a stage from its key and date
+    pub fn from_key(key: &str, date: NaiveDate) -> Option<Self> {
+        match key.trim() {
+            "pregnant" => Some(LifeStage::Pregnant { due_date: date }),
+            "breastfeeding" => Some(LifeStage::Breastfeeding { since: date }),
+            _ => None,
+        }
+    }
+
+    /// Returns the week of pregnancy on a date (1 in the first week), if pregnant by then
+    pub fn pregnancy_week(&self, date: NaiveDate) -> Option<i64> {
+        let LifeStage::Pregnant { due_date } = self else {
+            return None;
+        };
+        let days = (date - (*due_date - Duration::days(PREGNANCY_DAYS))).num_days();
+        (days >= 0).then_some(days / 7 + 1)
+    }
+
+    /// Calories added to the calculated TDEE on a date
+    pub fn calorie_addition(&self, date: NaiveDate) -> f64 {
+        match self {
+            LifeStage::Pregnant { .. } => match self.pregnancy_week(date) {
+                Some(week) if week >= 28 => 452.0,
+                Some(week) if week >= 14 => 340.0,
+                _ => 0.0,
+            },
+            LifeStage::Breastfeeding { since } if date < *since => 0.0,
+            LifeStage::Breastfeeding { since } => {
+                if date < *since + Months::new(6) { 330.0 } else { 400.0 }
+            }
+        }
+    }
+
+    /// Adds the stage's calories to a calculated TDEE
+    ///
+    /// Days without a target (0, before the first weigh-in) keep none.
+    pub fn adjust(&self, target: f64, date: NaiveDate) -> f64 {
+        if target > 0.0 { target + self.calorie_addition(date) } else { target }
+    }
+
+    /// Describes the stage on a date, such as `Pregnancy, week 20 (2nd trimester)`
+    pub fn describe(&self, date: NaiveDate) -> String {
+        match (self, self.pregnancy_week(date)) {
+            (LifeStage::Pregnant { .. }, Some(week)) => {
+                let trimester = match week {
+                    ..14 => "1st",
+                    14..28 => "2nd",
+                    _ => "3rd",
+                };
+                format!("Pregnancy, week {} ({} trimester)", week, trimester)
+            }
+            (LifeStage::Pregnant { due_date }, None) => format!("Pregnancy (due {})", due_date.format("%Y-%m-%d")),
+            (LifeStage::Breastfeeding { since }, _) => format!("Breastfeeding since {}", since.format("%Y-%m-%d")),
+        }
+    }
+}
+
+impl fmt::Display for LifeStage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LifeStage::Pregnant { due_date } => write!(f, "Pregnant (due {})", due_date.format("%Y-%m-%d")),
+            LifeStage::Breastfeeding { since } => write!(f, "Breastfeeding (since {})", since.format("%Y-%m-%d")),
+        }
+    }
+}
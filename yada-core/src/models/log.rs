@@ -0,0 +1,449 @@
+//! Food Log Model - Daily Food Consumption Tracking
+//! 
+//! This module implements the data structures for tracking daily food consumption.
+//! It provides a date-based organization system for logging food entries with
+//! precise timestamps and serving amounts.
+//! 
+//! ## Key Features:
+//! - Date-based log organization for daily tracking
+//! - Timestamped food entries, kept in chronological order (entries logged
+//!   later for an earlier time of day are inserted where they belong)
+//! - Flexible serving amounts (not limited to whole servings)
+//! - Calorie calculation integration with food database
+//! - Daily food cost and calorie density, from the foods whose cost or
+//!   serving weight is known
+//! - Entry management (add/remove) with index-based operations
+//! 
+//! ## Historical Calories:
+//! Each entry keeps the calories it had when it was logged (`FoodEntry::calories`),
+//! so correcting a food later does not silently rewrite the totals of days
+//! already logged. Calorie totals use these snapshots; entries without one
+//! (logged before snapshots existed) use the food's current values until the
+//! log repository backfills them (see `LogRepository::backfill_calories`).
+//! A snapshot still counts after its food is deleted. Only calories are
+//! frozen: macros, cost, and weight always come from the current food values.
+//! 
+//! ## Day Notes:
+//! Each day can also carry a free-text note and a mood and energy rating from
+//! 1 to 5 (`DailyLog::note`), so how a day felt can be compared with what
+//! was eaten. A day with a note but no entries counts as not logged.
+//! 
+//! ## Integration:
+//! Works seamlessly with the Food model and Repository pattern to provide
+//! comprehensive food consumption tracking and calorie analysis.
+
+// yada-core/src/models/log.rs
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, TimeZone};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::food::{Food, Macros};
+use super::meal::MealSlot;
+use super::quantity::Quantity;
+
+/// Source of entry IDs; never hands out 0 or an ID reserved by loading
+static NEXT_ENTRY_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Returns a new entry ID, larger than every ID issued or reserved so far
+pub fn next_entry_id() -> u64 {
+    NEXT_ENTRY_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Makes sure `next_entry_id` never returns `entry_id` (used when loading saved entries)
+pub fn reserve_entry_id(entry_id: u64) {
+    NEXT_ENTRY_ID.fetch_max(entry_id.saturating_add(1), Ordering::Relaxed);
+}
+
+/// Individual food consumption entry with timing and quantity information
+/// 
+/// Each FoodEntry represents a single instance of food consumption, containing:
+/// - Reference to the consumed food (via food_id)
+/// - Amount consumed in servings (supports fractional amounts)
+/// - Precise timestamp for chronological tracking
+/// - A unique ID, so entries can be referred to without their position
+/// 
+/// This granular approach enables detailed analysis of eating patterns
+/// and accurate calorie tracking throughout the day.
+#[derive(Debug, Clone)]
+pub struct FoodEntry {
+    /// Identifies this entry among all entries; saved with the entry, so it
+    /// stays the same across runs (copies made for undo keep it)
+    pub entry_id: u64,
+    
+    /// References a food item in the food database
+    pub food_id: String,
+    
+    /// Amount consumed in servings, as converted when the entry was logged
+    /// (supports fractional servings like 0.5, 1.5, etc.)
+    pub servings: f64,
+    
+    /// Amount as entered, e.g. `85 g` (see `models::quantity`)
+    pub quantity: Quantity,
+    
+    /// Exact time when the food was logged (enables chronological analysis)
+    pub timestamp: DateTime<Local>,
+    
+    /// Meal the entry belongs to, if one was chosen (see `models::meal`)
+    pub meal: Option<MealSlot>,
+    
+    /// Calories of the entry when it was logged, if recorded (see the module
+    /// documentation on historical calories)
+    pub calories: Option<f64>,
+}
+
+impl FoodEntry {
+    /// Creates an entry stamped with the current time, with a new ID and no meal
+    /// 
+    /// # Arguments
+    /// * `food_id` - Reference to a food item in the food database
+    /// * `quantity` - Amount as entered by the user
+    /// * `servings` - The quantity converted into servings of the food
+    pub fn new(food_id: String, quantity: Quantity, servings: f64) -> Self {
+        FoodEntry {
+            entry_id: next_entry_id(),
+            food_id,
+            servings,
+            quantity,
+            timestamp: Local::now(),
+            meal: None,
+            calories: None,
+        }
+    }
+
+    /// Returns the servings this entry represents for the food's current definition
+    /// 
+    /// Quantities in units are converted again, so correcting a food's serving
+    /// weight also corrects entries logged by weight. If the food can no longer
+    /// be measured in the entry's unit, the servings recorded at logging time are used.
+    pub fn servings_of(&self, food: &Food) -> f64 {
+        self.quantity.to_servings(food).unwrap_or(self.servings)
+    }
+
+    /// Returns the calories of this entry: those recorded when it was logged,
+    /// or the food's current calories for entries without a snapshot
+    pub fn calories_of(&self, food: &Food) -> f64 {
+        self.calories.unwrap_or_else(|| food.calories_per_serving * self.servings_of(food))
+    }
+
+    /// Returns the calories recorded when the entry was logged, or those of
+    /// the food's current values; 0 without either (a deleted food logged
+    /// before snapshots existed)
+    pub fn logged_calories(&self, food: Option<&Food>) -> f64 {
+        match (self.calories, food) {
+            (Some(calories), _) => calories,
+            (None, Some(food)) => self.calories_of(food),
+            (None, None) => 0.0,
+        }
+    }
+
+    /// Whether the calories recorded when the entry was logged differ from
+    /// the food's current values, or the food no longer exists
+    pub fn calories_changed(&self, food: Option<&Food>) -> bool {
+        match (self.calories, food) {
+            (Some(calories), Some(food)) => (calories - food.calories_per_serving * self.servings_of(food)).abs() >= 0.05,
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+
+    /// Records the entry's calories from the food's current values
+    pub fn record_calories(&mut self, food: &Food) {
+        self.calories = Some(food.calories_per_serving * self.servings_of(food));
+    }
+}
+
+/// What the food logged on a day cost
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CostSummary {
+    /// Total cost of the entries whose food has a cost
+    pub cost: f64,
+
+    /// Calories of the entries whose food has a cost
+    pub priced_calories: f64,
+
+    /// Protein of the entries whose food has a cost, in grams
+    pub priced_protein: f64,
+
+    /// Entries whose food has a cost
+    pub priced_entries: usize,
+
+    /// Entries whose food has no cost, left out of the totals
+    pub unpriced_entries: usize,
+}
+
+impl CostSummary {
+    /// Calories bought per unit of money, if anything was priced
+    pub fn calories_per_unit_cost(&self) -> Option<f64> {
+        (self.cost > 0.0).then(|| self.priced_calories / self.cost)
+    }
+
+    /// Grams of protein bought per unit of money, if anything was priced
+    pub fn protein_per_unit_cost(&self) -> Option<f64> {
+        (self.cost > 0.0).then(|| self.priced_protein / self.cost)
+    }
+}
+
+/// Lowest mood or energy rating
+pub const RATING_MIN: u8 = 1;
+
+/// Highest mood or energy rating
+pub const RATING_MAX: u8 = 5;
+
+/// How a day went, in the user's words and ratings
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DayNote {
+    /// Free-text note about the day
+    pub text: Option<String>,
+
+    /// Mood from `RATING_MIN` (bad) to `RATING_MAX` (great)
+    pub mood: Option<u8>,
+
+    /// Energy from `RATING_MIN` (exhausted) to `RATING_MAX` (energetic)
+    pub energy: Option<u8>,
+}
+
+impl DayNote {
+    /// Returns true if nothing was written or rated
+    pub fn is_empty(&self) -> bool {
+        self.text.is_none() && self.mood.is_none() && self.energy.is_none()
+    }
+}
+
+/// Parses a mood or energy rating
+/// 
+/// # Returns
+/// * `Ok(u8)` - The rating
+/// * `Err(String)` - If the input is not a whole number from `RATING_MIN` to `RATING_MAX`
+pub fn parse_rating(input: &str) -> Result<u8, String> {
+    input.trim().parse::<u8>().ok()
+        .filter(|rating| (RATING_MIN..=RATING_MAX).contains(rating))
+        .ok_or_else(|| format!("Please enter a rating from {} to {}", RATING_MIN, RATING_MAX))
+}
+
+/// Daily food consumption log containing all entries for a specific date
+/// 
+/// DailyLog organizes food consumption by date, providing:
+/// - Date-based organization for daily tracking
+/// - Collection of all food entries for the day
+/// - Methods for entry management and calorie calculations
+/// - Integration with food database for nutritional analysis
+/// 
+/// This structure supports the application's daily tracking workflow
+/// and enables comprehensive nutritional analysis and reporting.
+#[derive(Debug, Clone)]
+pub struct DailyLog {
+    /// The date for which this log tracks food consumption
+    pub date: NaiveDate,
+    
+    /// All food entries logged for this date (chronologically ordered)
+    pub entries: Vec<FoodEntry>,
+    
+    /// Note, mood, and energy written for this date
+    pub note: DayNote,
+}
+
+/// Returns the local time on a date, or `None` if a clock change skips it
+pub fn local_timestamp(date: NaiveDate, time: NaiveTime) -> Option<DateTime<Local>> {
+    Local.from_local_datetime(&date.and_time(time)).earliest()
+}
+
+impl DailyLog {
+    /// Creates a new empty daily log for the specified date
+    /// 
+    /// # Arguments
+    /// * `date` - The date for which this log will track food consumption
+    /// 
+    /// # Returns
+    /// A new DailyLog instance with no entries and an empty note
+    pub fn new(date: NaiveDate) -> Self {
+        DailyLog {
+            date,
+            entries: Vec::new(),
+            note: DayNote::default(),
+        }
+    }
+
+    /// Adds a new food entry to the daily log with current timestamp
+    /// 
+    /// This method creates a new FoodEntry and inserts it into the log:
+    /// 1. Creates entry with current timestamp for chronological tracking
+    /// 2. Inserts it after all entries with an earlier or equal time (see `insert_entry`)
+    /// 3. Supports fractional servings for precise quantity tracking
+    /// 
+    /// # Arguments
+    /// * `food_id` - Reference to a food item in the food database
+    /// * `servings` - Amount consumed (supports fractions like 0.5, 1.5)
+    /// 
+    /// # Examples
+    /// ```ignore
+    /// log.add_entry("apple".to_string(), 1.0);     // One apple
+    /// log.add_entry("bread".to_string(), 0.5);     // Half serving of bread
+    /// ```
+    pub fn add_entry(&mut self, food_id: String, servings: f64) {
+        self.add_quantity_entry(food_id, Quantity::servings(servings), servings);
+    }
+
+    /// Adds a new food entry measured in any quantity, such as `85 g`
+    /// 
+    /// # Arguments
+    /// * `food_id` - Reference to a food item in the food database
+    /// * `quantity` - Amount as entered by the user
+    /// * `servings` - The quantity converted into servings of the food
+    /// 
+    /// # Returns
+    /// The index the entry was inserted at
+    pub fn add_quantity_entry(&mut self, food_id: String, quantity: Quantity, servings: f64) -> usize {
+        self.insert_entry(FoodEntry::new(food_id, quantity, servings))
+    }
+
+    /// Inserts an entry at its chronological position
+    /// 
+    /// The entry goes after every entry with an earlier or equal timestamp,
+    /// so entries logged at the same time keep the order they were added in.
+    /// 
+    /// # Returns
+    /// The index the entry was inserted at
+    pub fn insert_entry(&mut self, entry: FoodEntry) -> usize {
+        let index = self.entries.partition_point(|other| other.timestamp <= entry.timestamp);
+        self.entries.insert(index, entry);
+        index
+    }
+
+    /// Puts the entries in chronological order
+    /// 
+    /// The sort is stable: entries with equal timestamps keep their order.
+    pub fn sort_entries(&mut self) {
+        self.entries.sort_by_key(|entry| entry.timestamp);
+    }
+
+    /// Returns the position of the entry with an ID, if it is in this log
+    pub fn entry_index(&self, entry_id: u64) -> Option<usize> {
+        self.entries.iter().position(|entry| entry.entry_id == entry_id)
+    }
+
+    /// Removes a food entry from the log by index position
+    /// 
+    /// This method enables deletion of specific food entries:
+    /// 1. Validates index bounds to prevent panics
+    /// 2. Removes entry and returns it for potential undo operations
+    /// 3. Maintains chronological order of remaining entries
+    /// 
+    /// # Arguments
+    /// * `index` - Zero-based index of the entry to remove
+    /// 
+    /// # Returns
+    /// * `Some(FoodEntry)` - The removed entry if index was valid
+    /// * `None` - If index was out of bounds
+    /// 
+    /// # Examples
+    /// ```ignore
+    /// if let Some(removed_entry) = log.remove_entry(0) {
+    ///     println!("Removed: {} servings of {}", removed_entry.servings, removed_entry.food_id);
+    /// }
+    /// ```
+    pub fn remove_entry(&mut self, index: usize) -> Option<FoodEntry> {
+        if index < self.entries.len() {
+            Some(self.entries.remove(index))
+        } else {
+            None
+        }
+    }
+
+    /// Calculates total calories consumed for the day based on food database
+    /// 
+    /// This method performs calorie aggregation by:
+    /// 1. Iterating through all food entries for the day
+    /// 2. Looking up calorie information from the food database
+    /// 3. Taking the calories recorded when each entry was logged, or for entries
+    ///    without a snapshot food.calories_per_serving * servings, where servings
+    ///    come from converting the entry's quantity (see `FoodEntry::calories_of`)
+    /// 4. Summing all entry calories for daily total
+    /// 
+    /// # Arguments
+    /// * `food_db` - HashMap containing food definitions with calorie information
+    /// 
+    /// # Returns
+    /// Total calories consumed for the day as f64
+    /// 
+    /// # Note
+    /// Entries referencing non-existent foods count with their calorie
+    /// snapshot, and are ignored if they have none (see `FoodEntry::logged_calories`).
+    pub fn total_calories(&self, food_db: &HashMap<String, Food>) -> f64 {
+        self.entries.iter()
+            .map(|entry| entry.logged_calories(food_db.get(&entry.food_id)))
+            .sum()
+    }
+
+    /// Calculates the total macronutrients consumed for this day
+    /// 
+    /// Works like `total_calories`: each entry contributes the food's
+    /// per-serving macros multiplied by its servings, and entries referencing
+    /// unknown foods are ignored.
+    /// 
+    /// # Arguments
+    /// * `food_db` - HashMap of all available foods for nutritional lookup
+    /// 
+    /// # Returns
+    /// Total protein, carbohydrates, and fat in grams
+    pub fn total_macros(&self, food_db: &HashMap<String, Food>) -> Macros {
+        let mut total = Macros::default();
+        for entry in &self.entries {
+            if let Some(food) = food_db.get(&entry.food_id) {
+                total += food.macros.scaled(entry.servings_of(food));
+            }
+        }
+        total
+    }
+
+    /// Adds up what the day's food cost
+    /// 
+    /// Only entries whose food has a cost per serving count; the others are
+    /// counted as unpriced so a partial total is not mistaken for the whole.
+    /// Entries referencing unknown foods are ignored.
+    /// 
+    /// # Arguments
+    /// * `food_db` - HashMap of all available foods for cost lookup
+    pub fn total_cost(&self, food_db: &HashMap<String, Food>) -> CostSummary {
+        let mut summary = CostSummary::default();
+        for entry in &self.entries {
+            let Some(food) = food_db.get(&entry.food_id) else {
+                continue;
+            };
+            let servings = entry.servings_of(food);
+            match food.cost_per_serving {
+                Some(cost) => {
+                    summary.cost += cost * servings;
+                    summary.priced_calories += entry.calories_of(food);
+                    summary.priced_protein += food.macros.protein * servings;
+                    summary.priced_entries += 1;
+                }
+                None => summary.unpriced_entries += 1,
+            }
+        }
+        summary
+    }
+
+    /// Calculates the calorie density of the day's food
+    /// 
+    /// Only entries whose food has a serving weight count. Entries
+    /// referencing unknown foods are ignored.
+    /// 
+    /// # Arguments
+    /// * `food_db` - HashMap of all available foods for weight lookup
+    /// 
+    /// # Returns
+    /// * `Some(f64)` - kcal per 100 g eaten
+    /// * `None` - If no entry's weight is known
+    pub fn calorie_density(&self, food_db: &HashMap<String, Food>) -> Option<f64> {
+        let (mut calories, mut grams) = (0.0, 0.0);
+        for entry in &self.entries {
+            if let Some(food) = food_db.get(&entry.food_id)
+                && let Some(weight) = food.grams_per_serving.filter(|weight| *weight > 0.0) {
+                let servings = entry.servings_of(food);
+                calories += entry.calories_of(food);
+                grams += weight * servings;
+            }
+        }
+        (grams > 0.0).then(|| calories * 100.0 / grams)
+    }
+}
@@ -0,0 +1,112 @@
+//! # Macro Targets
+//!
+//! How much protein, carbohydrate, and fat the user aims to eat each day.
+//! Targets come in two forms:
+//!
+//! - **Ratios**: Percent of the day's calorie target from each macronutrient
+//!   (adding up to 100), converted to grams at 4 kcal per gram of protein
+//!   and carbohydrates and 9 kcal per gram of fat
+//! - **Grams**: Fixed grams of each macronutrient, whatever the calorie target
+//!
+//! Targets set in the profile take precedence over the ratios of the diet
+//! preset (see `models::diet_preset`).
+
+// yada-core/src/models/macro_target.rs
+use std::fmt;
+
+use crate::models::food::Macros;
+
+/// Calories per gram of protein, carbohydrates, and fat
+pub const CALORIES_PER_GRAM: [f64; 3] = [4.0, 4.0, 9.0];
+
+/// How far percentages may be from adding up to 100 (rounding)
+const PERCENT_TOLERANCE: f64 = 0.5;
+
+/// Share of calories from each macronutrient, in percent
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MacroRatios {
+    /// Percent of calories from protein
+    pub protein: f64,
+
+    /// Percent of calories from carbohydrates
+    pub carbs: f64,
+
+    /// Percent of calories from fat
+    pub fat: f64,
+}
+
+impl MacroRatios {
+    /// Creates ratios from percentages
+    ///
+    /// # Returns
+    /// * `Ok(MacroRatios)` - The ratios
+    /// * `Err(String)` - If a percentage is negative or they do not add up to 100
+    pub fn new(protein: f64, carbs: f64, fat: f64) -> Result<Self, String> {
+        if [protein, carbs, fat].iter().any(|percent| !percent.is_finite() || *percent < 0.0) {
+            return Err("Percentages cannot be negative.".to_string());
+        }
+        let total = protein + carbs + fat;
+        if (total - 100.0).abs() > PERCENT_TOLERANCE {
+            return Err(format!("Percentages must add up to 100 (they add up to {}).", total));
+        }
+        Ok(MacroRatios { protein, carbs, fat })
+    }
+
+    /// Returns the grams of each macronutrient for a calorie target
+    ///
+    /// # Arguments
+    /// * `calories` - The day's calorie target
+    pub fn grams(&self, calories: f64) -> Macros {
+        Macros::new(
+            calories * self.protein / 100.0 / CALORIES_PER_GRAM[0],
+            calories * self.carbs / 100.0 / CALORIES_PER_GRAM[1],
+            calories * self.fat / 100.0 / CALORIES_PER_GRAM[2],
+        )
+    }
+}
+
+impl fmt::Display for MacroRatios {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "protein {:.0}% / carbs {:.0}% / fat {:.0}%", self.protein, self.carbs, self.fat)
+    }
+}
+
+/// Daily macronutrient targets
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MacroTargets {
+    /// Percent of the calorie target from each macronutrient
+    Ratios(MacroRatios),
+
+    /// Fixed grams of protein, carbohydrates, and fat
+    Grams(Macros),
+}
+
+impl MacroTargets {
+    /// Returns the targets in grams for a day
+    ///
+    /// # Arguments
+    /// * `calories` - The day's calorie target
+    ///
+    /// # Returns
+    /// * `Some(Macros)` - Grams of each macronutrient
+    /// * `None` - If the targets are ratios and the day has no calorie target
+    pub fn grams(&self, calories: f64) -> Option<Macros> {
+        match self {
+            MacroTargets::Ratios(ratios) => (calories > 0.0).then(|| ratios.grams(calories)),
+            MacroTargets::Grams(grams) => Some(*grams),
+        }
+    }
+}
+
+impl fmt::Display for MacroTargets {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MacroTargets::Ratios(ratios) => write!(f, "{}", ratios),
+            MacroTargets::Grams(grams) => write!(
+                f,
+                "protein {:.0} g / carbs {:.0} g / fat {:.0} g",
+                grams.protein, grams.carbs, grams.fat
+            ),
+        }
+    }
+}
@@ -0,0 +1,168 @@
+//! # Meals and Meal Plans
+//!
+//! Log entries can be assigned to a meal slot (breakfast, lunch, dinner, or
+//! snack). A meal plan lists the foods intended for each slot of one day;
+//! comparing it with the log shows which meal went over its share of the
+//! calorie budget.
+//!
+//! ## Compliance
+//!
+//! `meal_compliance` returns one row per slot that is planned or logged,
+//! in slot order, with planned and logged calories. Entries logged without
+//! a slot are reported in a final row whose `slot` is `None`, so the logged
+//! column always adds up to the day's total.
+
+// yada-core/src/models/meal.rs
+use std::collections::HashMap;
+use std::fmt;
+
+use chrono::{NaiveDate, NaiveTime, Timelike};
+
+use crate::models::food::Food;
+use crate::models::log::DailyLog;
+
+/// The meal a food entry belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum MealSlot {
+    Breakfast,
+    Lunch,
+    Dinner,
+    Snack,
+}
+
+impl MealSlot {
+    /// All slots, in the order they are listed to the user
+    pub const ALL: [MealSlot; 4] = [MealSlot::Breakfast, MealSlot::Lunch, MealSlot::Dinner, MealSlot::Snack];
+
+    /// Lowercase name used in the data files
+    pub fn key(&self) -> &'static str {
+        match self {
+            MealSlot::Breakfast => "breakfast",
+            MealSlot::Lunch => "lunch",
+            MealSlot::Dinner => "dinner",
+            MealSlot::Snack => "snack",
+        }
+    }
+
+    /// Parses a slot from its name (case-insensitive)
+    pub fn parse(text: &str) -> Option<Self> {
+        MealSlot::ALL.into_iter().find(|slot| slot.key().eq_ignore_ascii_case(text.trim()))
+    }
+
+    /// Suggests the slot for food eaten at a time of day
+    ///
+    /// Breakfast before 11:00, lunch before 16:00, dinner before 21:00, snack otherwise.
+    pub fn for_time(time: NaiveTime) -> Self {
+        match time.hour() {
+            5..=10 => MealSlot::Breakfast,
+            11..=15 => MealSlot::Lunch,
+            16..=20 => MealSlot::Dinner,
+            _ => MealSlot::Snack,
+        }
+    }
+}
+
+impl fmt::Display for MealSlot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            MealSlot::Breakfast => "Breakfast",
+            MealSlot::Lunch => "Lunch",
+            MealSlot::Dinner => "Dinner",
+            MealSlot::Snack => "Snack",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// One food planned for a meal slot
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlannedItem {
+    /// Slot the food is planned for
+    pub slot: MealSlot,
+
+    /// References a food item in the food database
+    pub food_id: String,
+
+    /// Planned servings
+    pub servings: f64,
+}
+
+/// The foods planned for one day
+#[derive(Debug, Clone, PartialEq)]
+pub struct MealPlan {
+    /// The day the plan is for
+    pub date: NaiveDate,
+
+    /// Planned foods, in the order they were added
+    pub items: Vec<PlannedItem>,
+}
+
+impl MealPlan {
+    /// Creates an empty plan for a date
+    pub fn new(date: NaiveDate) -> Self {
+        MealPlan { date, items: Vec::new() }
+    }
+
+    /// Adds a food to a slot of the plan
+    pub fn add_item(&mut self, slot: MealSlot, food_id: String, servings: f64) {
+        self.items.push(PlannedItem { slot, food_id, servings });
+    }
+
+    /// Returns the planned calories for a slot (unknown foods count as zero)
+    pub fn planned_calories(&self, slot: MealSlot, food_db: &HashMap<String, Food>) -> f64 {
+        self.items.iter()
+            .filter(|item| item.slot == slot)
+            .filter_map(|item| food_db.get(&item.food_id).map(|food| food.calories_per_serving * item.servings))
+            .sum()
+    }
+}
+
+/// Planned versus logged calories for one meal slot
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotCompliance {
+    /// The slot, or `None` for entries logged without a slot
+    pub slot: Option<MealSlot>,
+
+    /// Calories planned for the slot
+    pub planned: f64,
+
+    /// Calories logged in the slot
+    pub logged: f64,
+}
+
+impl SlotCompliance {
+    /// Returns how many calories the slot went over its plan (negative when under)
+    pub fn difference(&self) -> f64 {
+        self.logged - self.planned
+    }
+}
+
+/// Compares a day's plan with its log, slot by slot
+///
+/// # Arguments
+/// * `plan` - The meal plan for the day
+/// * `log` - The food log for the same day, if anything was logged
+/// * `food_db` - Food definitions for calorie lookups
+///
+/// # Returns
+/// One row per planned or logged slot, followed by an unassigned row if needed
+pub fn meal_compliance(plan: &MealPlan, log: Option<&DailyLog>, food_db: &HashMap<String, Food>) -> Vec<SlotCompliance> {
+    let mut logged: HashMap<Option<MealSlot>, f64> = HashMap::new();
+    for entry in log.map(|log| log.entries.as_slice()).unwrap_or(&[]) {
+        *logged.entry(entry.meal).or_insert(0.0) += entry.logged_calories(food_db.get(&entry.food_id));
+    }
+
+    let mut rows: Vec<SlotCompliance> = MealSlot::ALL.iter()
+        .filter(|slot| plan.items.iter().any(|item| item.slot == **slot) || logged.contains_key(&Some(**slot)))
+        .map(|slot| SlotCompliance {
+            slot: Some(*slot),
+            planned: plan.planned_calories(*slot, food_db),
+            logged: logged.get(&Some(*slot)).copied().unwrap_or(0.0),
+        })
+        .collect();
+
+    if let Some(unassigned) = logged.get(&None) {
+        rows.push(SlotCompliance { slot: None, planned: 0.0, logged: *unassigned });
+    }
+    rows
+}
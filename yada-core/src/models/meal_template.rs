@@ -0,0 +1,67 @@
+//! # Meal Templates
+//!
+//! A meal template is a named list of foods eaten together, such as
+//! "Monday breakfast" = oatmeal + milk + banana. Unlike a composite food it
+//! is not a food itself: logging a template adds one log entry per food, so
+//! each food keeps its own line, calories, and macros in the log, and the
+//! servings of each food can be changed when logging.
+//!
+//! Templates may name the meal they are usually eaten at, which is then
+//! offered when they are logged.
+
+// yada-core/src/models/meal_template.rs
+use std::collections::HashMap;
+
+use crate::models::food::Food;
+use crate::models::meal::MealSlot;
+
+/// One food of a meal template
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateItem {
+    /// References a food item in the food database
+    pub food_id: String,
+
+    /// Servings logged unless changed when logging
+    pub servings: f64,
+}
+
+/// A named list of foods logged together
+#[derive(Debug, Clone, PartialEq)]
+pub struct MealTemplate {
+    /// Name the template is picked by (unique, ignoring case)
+    pub name: String,
+
+    /// Meal the template is usually eaten at, if any
+    pub meal: Option<MealSlot>,
+
+    /// Foods in the order they are logged
+    pub items: Vec<TemplateItem>,
+}
+
+impl MealTemplate {
+    /// Creates an empty template
+    pub fn new(name: String, meal: Option<MealSlot>) -> Self {
+        MealTemplate { name, meal, items: Vec::new() }
+    }
+
+    /// Adds a food to the template
+    pub fn add_item(&mut self, food_id: String, servings: f64) {
+        self.items.push(TemplateItem { food_id, servings });
+    }
+
+    /// Returns the template's calories with its default servings
+    /// (unknown foods count as zero)
+    pub fn total_calories(&self, food_db: &HashMap<String, Food>) -> f64 {
+        self.items.iter()
+            .filter_map(|item| food_db.get(&item.food_id).map(|food| food.calories_per_serving * item.servings))
+            .sum()
+    }
+
+    /// Lists the items as `1 oatmeal, 0.5 milk`
+    pub fn describe_items(&self) -> String {
+        self.items.iter()
+            .map(|item| format!("{} {}", item.servings, item.food_id))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
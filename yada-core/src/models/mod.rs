@@ -0,0 +1,77 @@
+//! Models Module - Core Data Structures and Business Logic
+//! 
+//! This module contains the fundamental data models and business logic for the YADA application.
+//! It implements several key design patterns:
+//! 
+//! ## Design Patterns Implemented:
+//! - **Composite Pattern**: Food model supports both basic and composite foods
+//! - **Command Pattern**: Command trait and command manager for undo functionality
+//! - **Repository Pattern**: Data models designed for repository abstraction
+//! 
+//! ## Module Organization:
+//! - `food`: Food entities with support for basic and composite food types
+//! - `serving`: Serving sizes, units, and conversion of amounts into servings
+//! - `quantity`: Amounts of food in servings or units, converted for calorie totals
+//! - `derived_keywords`: Searchable keywords derived from food categories and tags
+//! - `search`: Ranked food search with prefix, substring, and fuzzy matching
+//! - `nutrient_filter`: Numeric conditions such as `calories < 200` for searches
+//! - `keyword_suggestion`: Keywords suggested for new foods from the existing vocabulary
+//! - `category`: Standard food categories and automatic category assignment
+//! - `duplicates`: Near-duplicate food detection for merging
+//! - `usage`: Last-used dates, popularity decay, and archive suggestions
+//! - `log`: Daily food consumption logging with date-based organization
+//! - `intake_limits`: Daily alcohol and caffeine limits and warnings
+//! - `exercise`: Exercise entries with MET-based calorie estimates
+//! - `meal`: Meal slots for log entries and per-day meal plans
+//! - `meal_template`: Named lists of foods logged together, such as a usual breakfast
+//! - `batch_log`: Several foods with amounts typed on one line, such as `apple:1, milk:0.5`
+//! - `food_phrase`: What was eaten as the user would say it, such as `2 eggs and a slice of bread`
+//! - `calorie_sources`: Top foods and the share of calories by category over a period
+//! - `energy_balance`: Weekly intake against weight change and the adaptive TDEE estimate
+//! - `week`: First day of the week and weekly totals
+//! - `age`: Age in complete years, including leap-day birthdays
+//! - `profile`: User profile management with basic and daily profile components
+//! - `validation`: Accepted ranges of heights, weights, and birth dates
+//! - `weekday_target`: Per-weekday overrides of the calorie target
+//! - `life_stage`: Pregnancy and breastfeeding calorie additions
+//! - `diet_preset`: Diet presets with macro ratios and a calorie adjustment
+//! - `macro_target`: Daily protein, carbohydrate, and fat targets
+//! - `dietary`: Dietary restrictions and the food tags that conflict with them
+//! - `reminder`: Reminders shown at a time of day, skipped once their check is done
+//! - `preferences`: Units and date format the menus show and accept
+//! - `command`: Command trait definition for the Command Pattern implementation
+//! - `command_manager`: Command execution and undo management system
+
+// yada-core/src/models/mod.rs
+pub mod food;
+pub mod serving;
+pub mod quantity;
+pub mod derived_keywords;
+pub mod search;
+pub mod nutrient_filter;
+pub mod keyword_suggestion;
+pub mod category;
+pub mod log;
+pub mod intake_limits;
+pub mod exercise;
+pub mod meal;
+pub mod meal_template;
+pub mod batch_log;
+pub mod food_phrase;
+pub mod calorie_sources;
+pub mod energy_balance;
+pub mod week;
+pub mod usage;
+pub mod duplicates;
+pub mod age;
+pub mod profile;
+pub mod validation;
+pub mod weekday_target;
+pub mod life_stage;
+pub mod diet_preset;
+pub mod macro_target;
+pub mod dietary;
+pub mod reminder;
+pub mod preferences;
+pub mod command;
+pub mod command_manager;
@@ -0,0 +1,243 @@
+//! # Nutrient Filters
+//!
+//! Numeric conditions on a food's values per serving, such as
+//! `calories < 200` or `protein > 10 g`, used alongside keywords to find
+//! foods that fit what is left of the day's budget (see
+//! `FoodRepository::search_foods`).
+//!
+//! ## Syntax
+//!
+//! `NUTRIENT OPERATOR NUMBER [UNIT]`, where the operator is `<`, `<=`, `>`,
+//! `>=`, or `=`. Spaces are optional and the unit, when given, must be the
+//! nutrient's own (`kcal`, `g`, or `mg`). Several filters are separated by
+//! commas and must all hold:
+//!
+//! ```text
+//! calories <= 250, protein >= 10g, fat < 5 g
+//! ```
+//!
+//! ## Nutrients
+//!
+//! - **calories** (`cal`, `kcal`): kcal per serving
+//! - **protein**, **carbs** (`carbohydrates`), **fat**, **fiber**,
+//!   **alcohol**: grams per serving
+//! - **caffeine**: milligrams per serving
+//!
+//! Foods without a recorded value count as 0 for it, like everywhere else
+//! macros are added up.
+
+// yada-core/src/models/nutrient_filter.rs
+use std::fmt;
+
+use crate::models::food::Food;
+
+/// Tolerance used by `=` so rounding in stored values does not matter
+const EQUAL_TOLERANCE: f64 = 0.05;
+
+/// A value per serving a filter can compare
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Nutrient {
+    /// Calories (kcal)
+    Calories,
+
+    /// Protein (g)
+    Protein,
+
+    /// Carbohydrates (g)
+    Carbs,
+
+    /// Fat (g)
+    Fat,
+
+    /// Dietary fiber (g)
+    Fiber,
+
+    /// Alcohol (g)
+    Alcohol,
+
+    /// Caffeine (mg)
+    Caffeine,
+}
+
+impl Nutrient {
+    /// All nutrients, in the order they are listed in help texts
+    pub const ALL: [Nutrient; 7] = [
+        Nutrient::Calories, Nutrient::Protein, Nutrient::Carbs, Nutrient::Fat,
+        Nutrient::Fiber, Nutrient::Alcohol, Nutrient::Caffeine,
+    ];
+
+    /// Name used in filters
+    pub fn key(self) -> &'static str {
+        match self {
+            Nutrient::Calories => "calories",
+            Nutrient::Protein => "protein",
+            Nutrient::Carbs => "carbs",
+            Nutrient::Fat => "fat",
+            Nutrient::Fiber => "fiber",
+            Nutrient::Alcohol => "alcohol",
+            Nutrient::Caffeine => "caffeine",
+        }
+    }
+
+    /// Unit of the nutrient's values
+    pub fn unit(self) -> &'static str {
+        match self {
+            Nutrient::Calories => "kcal",
+            Nutrient::Caffeine => "mg",
+            _ => "g",
+        }
+    }
+
+    /// Parses a nutrient name or its alias (case-insensitive)
+    pub fn parse(name: &str) -> Option<Self> {
+        let name = name.trim().to_lowercase();
+        match name.as_str() {
+            "cal" | "kcal" => Some(Nutrient::Calories),
+            "carbohydrates" => Some(Nutrient::Carbs),
+            _ => Self::ALL.into_iter().find(|nutrient| nutrient.key() == name),
+        }
+    }
+
+    /// Returns the food's value per serving
+    pub fn amount(self, food: &Food) -> f64 {
+        match self {
+            Nutrient::Calories => food.calories_per_serving,
+            Nutrient::Protein => food.macros.protein,
+            Nutrient::Carbs => food.macros.carbs,
+            Nutrient::Fat => food.macros.fat,
+            Nutrient::Fiber => food.macros.fiber,
+            Nutrient::Alcohol => food.macros.alcohol,
+            Nutrient::Caffeine => food.macros.caffeine,
+        }
+    }
+}
+
+/// How a food's value is compared with the filter's value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    /// `<`
+    Less,
+
+    /// `<=`
+    AtMost,
+
+    /// `>`
+    Greater,
+
+    /// `>=`
+    AtLeast,
+
+    /// `=`, within rounding
+    Equal,
+}
+
+impl Comparison {
+    /// Operators with their comparisons; `parse` prefers `<=` over `<` at the same place
+    const OPERATORS: [(&'static str, Comparison); 5] = [
+        ("<=", Comparison::AtMost),
+        (">=", Comparison::AtLeast),
+        ("<", Comparison::Less),
+        (">", Comparison::Greater),
+        ("=", Comparison::Equal),
+    ];
+
+    /// Operator as written in filters
+    pub fn symbol(self) -> &'static str {
+        Self::OPERATORS.iter()
+            .find(|(_, comparison)| *comparison == self)
+            .map_or("=", |(symbol, _)| symbol)
+    }
+
+    /// Returns true if `amount` compares as required with `value`
+    pub fn holds(self, amount: f64, value: f64) -> bool {
+        match self {
+            Comparison::Less => amount < value,
+            Comparison::AtMost => amount <= value,
+            Comparison::Greater => amount > value,
+            Comparison::AtLeast => amount >= value,
+            Comparison::Equal => (amount - value).abs() < EQUAL_TOLERANCE,
+        }
+    }
+}
+
+/// A numeric condition on a food's value per serving
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NutrientFilter {
+    /// Value compared
+    pub nutrient: Nutrient,
+
+    /// How it is compared
+    pub comparison: Comparison,
+
+    /// Value it is compared with, in the nutrient's unit
+    pub value: f64,
+}
+
+impl NutrientFilter {
+    /// Creates a filter
+    pub fn new(nutrient: Nutrient, comparison: Comparison, value: f64) -> Self {
+        NutrientFilter { nutrient, comparison, value }
+    }
+
+    /// Parses one filter such as `protein > 10 g`
+    ///
+    /// # Returns
+    /// * `Ok(NutrientFilter)` - The filter
+    /// * `Err(String)` - A message naming what is wrong
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        let (position, symbol, comparison) = Comparison::OPERATORS.iter()
+            .filter_map(|(symbol, comparison)| text.find(symbol).map(|position| (position, *symbol, *comparison)))
+            .min_by_key(|(position, symbol, _)| (*position, usize::MAX - symbol.len()))
+            .ok_or_else(|| format!("'{}' has no <, <=, >, >=, or =", text))?;
+
+        let name = &text[..position];
+        let nutrient = Nutrient::parse(name).ok_or_else(|| format!(
+            "'{}' is not a nutrient; use {}",
+            name.trim(),
+            Nutrient::ALL.iter().map(|nutrient| nutrient.key()).collect::<Vec<_>>().join(", ")
+        ))?;
+
+        let rest = text[position + symbol.len()..].trim();
+        let number_end = rest.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(rest.len());
+        let value: f64 = rest[..number_end].parse()
+            .ok()
+            .filter(|value: &f64| value.is_finite())
+            .ok_or_else(|| format!("'{}' needs a number after {}", text, symbol))?;
+        let unit = rest[number_end..].trim();
+        if !unit.is_empty() && !unit.eq_ignore_ascii_case(nutrient.unit()) {
+            return Err(format!("{} is measured in {}, not {}", nutrient.key(), nutrient.unit(), unit));
+        }
+
+        Ok(NutrientFilter::new(nutrient, comparison, value))
+    }
+
+    /// Returns true if the food's value per serving passes the filter
+    pub fn matches(&self, food: &Food) -> bool {
+        self.comparison.holds(self.nutrient.amount(food), self.value)
+    }
+}
+
+impl fmt::Display for NutrientFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {} {}", self.nutrient.key(), self.comparison.symbol(), self.value, self.nutrient.unit())
+    }
+}
+
+/// Parses comma-separated filters; empty text gives no filters
+///
+/// # Returns
+/// * `Ok(Vec<NutrientFilter>)` - The filters in the order typed
+/// * `Err(String)` - The message of the first filter that is not valid
+pub fn parse_filters(text: &str) -> Result<Vec<NutrientFilter>, String> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(NutrientFilter::parse)
+        .collect()
+}
+
+/// Returns true if the food passes every filter
+pub fn matches_all(filters: &[NutrientFilter], food: &Food) -> bool {
+    filters.iter().all(|filter| filter.matches(food))
+}
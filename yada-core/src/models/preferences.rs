@@ -0,0 +1,266 @@
+//! # Display Preferences
+//!
+//! How the menus show and ask for weights, heights, and dates. Data files
+//! always store kilograms, centimeters, and `YYYY-MM-DD` dates; the
+//! preferences only change what the user sees and types.
+//!
+//! ## Units
+//!
+//! - **Metric** (default): kilograms and centimeters
+//! - **Imperial**: pounds and inches
+//!
+//! ## Date Formats
+//!
+//! - **ISO** (default): `2024-03-05`
+//! - **Day first**: `05.03.2024`
+//! - **Month first**: `03/05/2024`
+//!
+//! Dates can be typed in any of the three formats whatever the preference;
+//! the separators tell them apart.
+//!
+//! ## Confirmations
+//!
+//! - **Safe** (default): Deletions, profile overwrites, and bulk changes ask
+//!   the user to type `yes` first
+//! - **Expert**: Only changes that cannot be undone ask; the rest go ahead,
+//!   since Undo reverses them
+//!
+//! Whether a change can be undone follows the undo policies (see
+//! `models::command_manager`): commands of a type set to `skip`, and changes
+//! that are not commands at all, such as replacing the data files, always ask.
+
+// yada-core/src/models/preferences.rs
+use std::fmt;
+
+use chrono::NaiveDate;
+
+/// Kilograms in a pound
+const KG_PER_LB: f64 = 0.453_592_37;
+
+/// Centimeters in an inch
+const CM_PER_INCH: f64 = 2.54;
+
+/// Units for body weight and height
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnitSystem {
+    /// Kilograms and centimeters
+    #[default]
+    Metric,
+
+    /// Pounds and inches
+    Imperial,
+}
+
+impl UnitSystem {
+    /// Both unit systems, in menu order
+    pub const ALL: [UnitSystem; 2] = [UnitSystem::Metric, UnitSystem::Imperial];
+
+    /// Key used in the settings file
+    pub fn key(self) -> &'static str {
+        match self {
+            UnitSystem::Metric => "metric",
+            UnitSystem::Imperial => "imperial",
+        }
+    }
+
+    /// Parses a key (case-insensitive)
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|units| value.trim().eq_ignore_ascii_case(units.key()))
+    }
+
+    /// Short weight unit (`kg`, `lb`)
+    pub fn weight_unit(self) -> &'static str {
+        match self {
+            UnitSystem::Metric => "kg",
+            UnitSystem::Imperial => "lb",
+        }
+    }
+
+    /// Weight unit written out for prompts (`kilograms`, `pounds`)
+    pub fn weight_unit_name(self) -> &'static str {
+        match self {
+            UnitSystem::Metric => "kilograms",
+            UnitSystem::Imperial => "pounds",
+        }
+    }
+
+    /// Short height unit (`cm`, `in`)
+    pub fn height_unit(self) -> &'static str {
+        match self {
+            UnitSystem::Metric => "cm",
+            UnitSystem::Imperial => "in",
+        }
+    }
+
+    /// Height unit written out for prompts (`centimeters`, `inches`)
+    pub fn height_unit_name(self) -> &'static str {
+        match self {
+            UnitSystem::Metric => "centimeters",
+            UnitSystem::Imperial => "inches",
+        }
+    }
+
+    /// Converts kilograms into this system's weight unit
+    pub fn weight_from_kg(self, kg: f64) -> f64 {
+        match self {
+            UnitSystem::Metric => kg,
+            UnitSystem::Imperial => kg / KG_PER_LB,
+        }
+    }
+
+    /// Converts a weight in this system's unit into kilograms
+    pub fn weight_to_kg(self, weight: f64) -> f64 {
+        match self {
+            UnitSystem::Metric => weight,
+            UnitSystem::Imperial => weight * KG_PER_LB,
+        }
+    }
+
+    /// Converts centimeters into this system's height unit
+    pub fn height_from_cm(self, cm: f64) -> f64 {
+        match self {
+            UnitSystem::Metric => cm,
+            UnitSystem::Imperial => cm / CM_PER_INCH,
+        }
+    }
+
+    /// Converts a height in this system's unit into centimeters
+    pub fn height_to_cm(self, height: f64) -> f64 {
+        match self {
+            UnitSystem::Metric => height,
+            UnitSystem::Imperial => height * CM_PER_INCH,
+        }
+    }
+
+    /// Formats a weight given in kilograms, such as `176.4 lb`
+    pub fn format_weight(self, kg: f64) -> String {
+        format!("{:.1} {}", self.weight_from_kg(kg), self.weight_unit())
+    }
+
+    /// Formats a height given in centimeters, such as `70.9 in`
+    pub fn format_height(self, cm: f64) -> String {
+        format!("{:.1} {}", self.height_from_cm(cm), self.height_unit())
+    }
+}
+
+impl fmt::Display for UnitSystem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UnitSystem::Metric => write!(f, "Metric (kg, cm)"),
+            UnitSystem::Imperial => write!(f, "Imperial (lb, in)"),
+        }
+    }
+}
+
+/// How dates are shown in the menus
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DateFormat {
+    /// `2024-03-05`
+    #[default]
+    Iso,
+
+    /// `05.03.2024`
+    DayFirst,
+
+    /// `03/05/2024`
+    MonthFirst,
+}
+
+impl DateFormat {
+    /// All date formats, in menu order
+    pub const ALL: [DateFormat; 3] = [DateFormat::Iso, DateFormat::DayFirst, DateFormat::MonthFirst];
+
+    /// Key used in the settings file
+    pub fn key(self) -> &'static str {
+        match self {
+            DateFormat::Iso => "iso",
+            DateFormat::DayFirst => "day-first",
+            DateFormat::MonthFirst => "month-first",
+        }
+    }
+
+    /// Parses a key (case-insensitive)
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|format| value.trim().eq_ignore_ascii_case(format.key()))
+    }
+
+    /// `chrono` format string of the date format
+    pub fn pattern(self) -> &'static str {
+        match self {
+            DateFormat::Iso => "%Y-%m-%d",
+            DateFormat::DayFirst => "%d.%m.%Y",
+            DateFormat::MonthFirst => "%m/%d/%Y",
+        }
+    }
+
+    /// Formats a date
+    pub fn format(self, date: NaiveDate) -> String {
+        date.format(self.pattern()).to_string()
+    }
+}
+
+impl fmt::Display for DateFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DateFormat::Iso => write!(f, "ISO (YYYY-MM-DD)"),
+            DateFormat::DayFirst => write!(f, "Day first (DD.MM.YYYY)"),
+            DateFormat::MonthFirst => write!(f, "Month first (MM/DD/YYYY)"),
+        }
+    }
+}
+
+/// When the menus ask the user to type `yes` before a change
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConfirmPolicy {
+    /// Ask before deletions, profile overwrites, and bulk changes
+    #[default]
+    Safe,
+
+    /// Ask only before changes that cannot be undone
+    Expert,
+}
+
+impl ConfirmPolicy {
+    /// Both policies, in menu order
+    pub const ALL: [ConfirmPolicy; 2] = [ConfirmPolicy::Safe, ConfirmPolicy::Expert];
+
+    /// Key used in the settings file
+    pub fn key(self) -> &'static str {
+        match self {
+            ConfirmPolicy::Safe => "safe",
+            ConfirmPolicy::Expert => "expert",
+        }
+    }
+
+    /// Parses a key (case-insensitive)
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|policy| value.trim().eq_ignore_ascii_case(policy.key()))
+    }
+
+    /// Reports whether the user has to confirm a change
+    ///
+    /// # Arguments
+    /// * `undoable` - Whether Undo can reverse the change
+    pub fn requires_confirmation(self, undoable: bool) -> bool {
+        self == ConfirmPolicy::Safe || !undoable
+    }
+}
+
+impl fmt::Display for ConfirmPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfirmPolicy::Safe => write!(f, "Safe (ask before deleting or overwriting)"),
+            ConfirmPolicy::Expert => write!(f, "Expert (ask only when undo is not possible)"),
+        }
+    }
+}
+
+/// Parses a date typed in any of the date formats
+///
+/// # Returns
+/// * `Some(NaiveDate)` - The date
+/// * `None` - If the text is not a date in any format
+pub fn parse_date(text: &str) -> Option<NaiveDate> {
+    DateFormat::ALL.into_iter()
+        .find_map(|format| NaiveDate::parse_from_str(text.trim(), format.pattern()).ok())
+}
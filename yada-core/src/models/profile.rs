@@ -0,0 +1,494 @@
+//! User Profile Model - Personal Information and Daily Tracking
+//! 
+//! This module implements user profile management with support for both static
+//! personal information and dynamic daily tracking data. It enables accurate
+//! calorie calculations by combining personal characteristics with daily variables.
+//! 
+//! ## Profile Architecture:
+//! - **UserProfile**: Static information (gender, height, birth date)
+//! - **DailyProfile**: Daily variables (weight, activity level)
+//! - **Strategy Integration**: Calorie calculation method selection
+//! - **Weekday Targets**: Overrides of the calculated target by day of the
+//!   week (see `models::weekday_target`)
+//! - **Diet Preset**: Macro ratio targets and a calorie adjustment chosen in
+//!   one step (see `models::diet_preset`)
+//! - **Life Stage**: Pregnancy or breastfeeding, which adds to the calculated
+//!   TDEE (see `models::life_stage`)
+//! - **Macro Targets**: Protein, carbohydrate, and fat targets set by the
+//!   user, taking precedence over the preset's (see `models::macro_target`)
+//! - **ProfileVersion**: Earlier gender, height, and calculation method
+//!   with the day they stopped applying
+//! 
+//! ## Profile History:
+//! Editing the gender, height, or calculation method does not rewrite the
+//! past. The values that applied until then are kept as a `ProfileVersion`
+//! ending on the day of the change, and `gender_on`, `height_on`, and
+//! `calculation_method_on` look up the values in effect on any day, so
+//! targets for earlier days stay as they were. A change dated before later
+//! changes replaces them: its values apply from its date on.
+//! 
+//! ## Key Features:
+//! - Age calculation accounting for leap years and birth dates
+//! - Daily profile management with date-based organization
+//! - Flexible activity level tracking for accurate TDEE calculations
+//! - Integration with Strategy pattern for calorie calculation methods
+//! 
+//! ## Low-Data Mode:
+//! A height or birth date that is missing from the profile file, or cannot
+//! be used (not a positive number, a birth date after the day being
+//! calculated), is kept as unknown instead of being read as 0 cm or some
+//! arbitrary date. `body_metrics` then fills the gap with a population
+//! default (`default_height_cm`, `DEFAULT_AGE_YEARS`) and lists what was
+//! estimated, so calorie targets stay plausible and every screen showing
+//! them can say they are an estimate only.
+
+// yada-core/src/models/profile.rs
+use chrono::NaiveDate;
+
+use crate::models::age;
+use crate::models::diet_preset::DietPreset;
+use crate::models::dietary::DietaryRestriction;
+use crate::models::life_stage::LifeStage;
+use crate::models::macro_target::MacroTargets;
+use crate::models::validation;
+use crate::models::weekday_target::WeekdayTargets;
+
+/// Age assumed when the birth date is unknown (years)
+pub const DEFAULT_AGE_YEARS: u32 = 40;
+
+/// User gender enumeration for biological calorie calculation differences
+/// 
+/// Gender affects BMR calculations as men and women have different
+/// metabolic rates due to differences in muscle mass and body composition.
+/// The "Other" option provides inclusivity while defaulting to gender-neutral
+/// calculation methods when implemented.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Gender {
+    Male,
+    Female,
+    Other,
+}
+
+/// Activity level enumeration for TDEE (Total Daily Energy Expenditure) calculations
+/// 
+/// Activity level significantly impacts calorie needs by multiplying BMR
+/// with appropriate activity factors:
+/// - Sedentary: Desk job, little/no exercise (BMR × 1.2)
+/// - Lightly Active: Light exercise 1-3 days/week (BMR × 1.375)
+/// - Moderately Active: Moderate exercise 3-5 days/week (BMR × 1.55)
+/// - Very Active: Hard exercise 6-7 days/week (BMR × 1.725)
+/// - Extremely Active: Very hard exercise, physical job (BMR × 1.9)
+#[derive(Debug, Clone, PartialEq)]
+pub enum ActivityLevel {
+    Sedentary,
+    LightlyActive,
+    ModeratelyActive,
+    VeryActive,
+    ExtremelyActive,
+}
+
+impl ActivityLevel {
+    /// Factor the BMR is multiplied by to estimate TDEE
+    pub fn multiplier(&self) -> f64 {
+        match self {
+            ActivityLevel::Sedentary => 1.2,
+            ActivityLevel::LightlyActive => 1.375,
+            ActivityLevel::ModeratelyActive => 1.55,
+            ActivityLevel::VeryActive => 1.725,
+            ActivityLevel::ExtremelyActive => 1.9,
+        }
+    }
+}
+
+/// Daily profile tracking weight and activity level for specific dates
+/// 
+/// DailyProfile enables day-to-day tracking of variables that affect
+/// calorie calculations:
+/// - Weight changes over time for accurate BMR calculations
+/// - Activity level variations (rest days vs workout days)
+/// - Date-specific data for historical tracking and analysis
+/// 
+/// This granular approach provides more accurate calorie targets than
+/// static profile information alone.
+#[derive(Debug, Clone)]
+pub struct DailyProfile {
+    /// Date for which this profile applies
+    pub date: NaiveDate,
+    
+    /// Current weight in kilograms (affects BMR calculations)
+    pub weight: f64,
+    
+    /// Activity level for this specific date (affects TDEE multiplier)
+    pub activity_level: ActivityLevel,
+    
+    /// Date of the entered profile this one was copied from, if it was
+    /// carried forward automatically instead of entered by the user
+    pub carried_from: Option<NaiveDate>,
+}
+
+/// Basic profile values that applied until a change
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfileVersion {
+    /// First day the values no longer applied (the day of the change)
+    pub until: NaiveDate,
+    
+    /// Gender before the change
+    pub gender: Gender,
+    
+    /// Height in centimeters before the change, or `None` if unknown
+    pub height: Option<f64>,
+    
+    /// Calculation method before the change
+    pub calculation_method: String,
+}
+
+/// Main user profile containing static personal information and daily tracking
+/// 
+/// UserProfile combines static personal characteristics with a collection
+/// of daily profiles to enable accurate, personalized calorie calculations.
+/// 
+/// ## Static Information:
+/// - Gender, height, birth date (used for BMR calculations)
+/// - Calorie calculation method preference (Strategy pattern)
+/// 
+/// ## Dynamic Information:
+/// - Collection of daily profiles (weight, activity level by date)
+/// - Enables tracking changes over time for improved accuracy
+#[derive(Debug, Clone)]
+pub struct UserProfile {
+    /// Biological gender for BMR calculation differences
+    pub gender: Gender,
+    
+    /// Height in centimeters (static personal characteristic), or `None`
+    /// if it is missing or not a positive number
+    pub height: Option<f64>,
+    
+    /// Birth date for accurate age calculation, or `None` if it is missing
+    pub birth_date: Option<NaiveDate>,
+    
+    /// Collection of daily profiles indexed by date
+    pub daily_profiles: Vec<DailyProfile>,
+    
+    /// Selected calorie calculation method (Strategy pattern identifier)
+    pub calculation_method: String,
+    
+    /// Calorie target overrides by day of the week
+    pub weekday_targets: WeekdayTargets,
+    
+    /// Selected diet preset, if any
+    pub diet_preset: Option<DietPreset>,
+    
+    /// Pregnancy or breastfeeding, if set
+    pub life_stage: Option<LifeStage>,
+    
+    /// Macro targets set by the user, if any (see `active_macro_targets`)
+    pub macro_targets: Option<MacroTargets>,
+    
+    /// Declared dietary restrictions, sorted (see `models::dietary`)
+    pub restrictions: Vec<DietaryRestriction>,
+    
+    /// Earlier basic values, oldest first (the fields above are the latest)
+    pub history: Vec<ProfileVersion>,
+}
+
+impl UserProfile {
+    /// Creates a new user profile with basic personal information
+    /// 
+    /// Initializes a profile with static information and sets default
+    /// calculation method to Harris-Benedict formula.
+    /// 
+    /// # Arguments
+    /// * `gender` - Biological gender for BMR calculations
+    /// * `height` - Height in centimeters; kept as unknown unless within the
+    ///   accepted range (see `models::validation`)
+    /// * `birth_date` - Birth date for age calculations
+    /// 
+    /// # Returns
+    /// New UserProfile with empty daily profiles and history, no weekday
+    /// targets, diet preset, life stage, macro targets, or restrictions, and
+    /// default calculation method
+    pub fn new(gender: Gender, height: f64, birth_date: NaiveDate) -> Self {
+        UserProfile {
+            gender,
+            height: validation::height_cm(height).ok(),
+            birth_date: Some(birth_date),
+            daily_profiles: Vec::new(),
+            calculation_method: "harris_benedict".to_string(), // Default
+            weekday_targets: WeekdayTargets::default(),
+            diet_preset: None,
+            life_stage: None,
+            macro_targets: None,
+            restrictions: Vec::new(),
+            history: Vec::new(),
+        }
+    }
+
+    /// Keeps the basic values of an earlier state of this profile as history
+    /// 
+    /// Call after changing the gender, height, or calculation method. If
+    /// any of them differ from `previous`, the previous values are recorded
+    /// as applying until `effective`. History ending after `effective` is
+    /// dropped, since the new values now apply from that day on; if earlier
+    /// history already ends on `effective`, the previous values were never
+    /// in effect for a whole day and are not recorded.
+    /// 
+    /// # Arguments
+    /// * `previous` - The profile before the change
+    /// * `effective` - First day the new values apply
+    pub fn record_basic_change(&mut self, previous: &UserProfile, effective: NaiveDate) {
+        let changed = self.gender != previous.gender
+            || self.height != previous.height
+            || self.calculation_method != previous.calculation_method;
+        if !changed {
+            return;
+        }
+        self.history = previous.history.clone();
+        self.history.retain(|version| version.until <= effective);
+        if self.history.last().is_none_or(|version| version.until < effective) {
+            self.history.push(ProfileVersion {
+                until: effective,
+                gender: previous.gender.clone(),
+                height: previous.height,
+                calculation_method: previous.calculation_method.clone(),
+            });
+        }
+    }
+
+    /// Returns the history entry in effect on a date, if the date is before the latest change
+    fn version_on(&self, date: NaiveDate) -> Option<&ProfileVersion> {
+        self.history.iter().find(|version| date < version.until)
+    }
+
+    /// Gender in effect on a date
+    pub fn gender_on(&self, date: NaiveDate) -> &Gender {
+        self.version_on(date).map_or(&self.gender, |version| &version.gender)
+    }
+
+    /// Height in effect on a date (`None` if it was unknown)
+    pub fn height_on(&self, date: NaiveDate) -> Option<f64> {
+        self.version_on(date).map_or(self.height, |version| version.height)
+    }
+
+    /// Calculation method in effect on a date
+    pub fn calculation_method_on(&self, date: NaiveDate) -> &str {
+        self.version_on(date).map_or(&self.calculation_method, |version| &version.calculation_method)
+    }
+
+    /// Whether a calculation method is in effect now or was on any earlier day
+    pub fn uses_method(&self, method: &str) -> bool {
+        self.calculation_method == method || self.history.iter().any(|version| version.calculation_method == method)
+    }
+
+    /// Calculates current age based on birth date and reference date
+    /// 
+    /// This method performs accurate age calculation accounting for:
+    /// 1. Year difference between dates
+    /// 2. Whether birthday has occurred in the current year
+    /// 3. Leap year considerations for February 29th births (see `models::age`)
+    /// 
+    /// A birth date after the reference date is treated as a data entry
+    /// error rather than an age of 0, so calculators fall back to the
+    /// default age instead (see `body_metrics`).
+    /// 
+    /// # Arguments
+    /// * `as_of_date` - Reference date for age calculation (typically current date)
+    /// 
+    /// # Returns
+    /// * `Some(u32)` - Age in complete years as of the reference date
+    /// * `None` - If the birth date is unknown or after the reference date
+    /// 
+    /// # Examples
+    /// ```ignore
+    /// let age = profile.age(NaiveDate::from_ymd(2024, 1, 15));
+    /// ```
+    pub fn age(&self, as_of_date: NaiveDate) -> Option<u32> {
+        let birth_date = self.birth_date.filter(|birth| *birth <= as_of_date)?;
+        Some(age::age_on(birth_date, as_of_date))
+    }
+
+    /// Returns the height and age calculators should use on a date
+    /// 
+    /// Unknown values are replaced with population defaults and listed in
+    /// `BodyMetrics::estimated`, so callers can warn that results based on
+    /// them are an estimate only.
+    /// 
+    /// # Arguments
+    /// * `date` - Day the height and age apply to (see `height_on`)
+    pub fn body_metrics(&self, date: NaiveDate) -> BodyMetrics {
+        let mut estimated = Vec::new();
+        let height_cm = self.height_on(date).unwrap_or_else(|| {
+            estimated.push(EstimatedField::Height);
+            default_height_cm(self.gender_on(date))
+        });
+        let age_years = self.age(date).unwrap_or_else(|| {
+            estimated.push(EstimatedField::Age);
+            DEFAULT_AGE_YEARS
+        });
+        BodyMetrics { height_cm, age_years, estimated }
+    }
+
+    /// Returns the macro targets in effect
+    /// 
+    /// # Returns
+    /// * `Some(MacroTargets)` - The user's own targets, or else the diet preset's ratios
+    /// * `None` - If neither is set
+    pub fn active_macro_targets(&self) -> Option<MacroTargets> {
+        self.macro_targets
+            .or_else(|| self.diet_preset.map(|preset| MacroTargets::Ratios(preset.ratios())))
+    }
+
+    /// Retrieves daily profile data for a specific date
+    /// 
+    /// This method searches the daily profiles collection for data
+    /// matching the specified date, enabling date-specific calorie
+    /// calculations and tracking.
+    /// 
+    /// # Arguments
+    /// * `date` - The date for which to retrieve daily profile data
+    /// 
+    /// # Returns
+    /// * `Some(&DailyProfile)` - Reference to daily profile if found
+    /// * `None` - If no daily profile exists for the specified date
+    /// 
+    /// # Usage
+    /// Used to show and edit what was recorded for a day. Calorie
+    /// calculation strategies use `latest_daily_profile` instead, so days
+    /// without a weigh-in still have a target.
+    pub fn get_daily_profile(&self, date: NaiveDate) -> Option<&DailyProfile> {
+        self.daily_profiles.iter().find(|&p| p.date == date)
+    }
+
+    /// Returns the most recent daily profile on or before a date
+    /// 
+    /// Unlike `get_daily_profile`, this finds the last known weight and
+    /// activity level when nothing was recorded for the date itself.
+    /// 
+    /// # Returns
+    /// * `Some(&DailyProfile)` - The latest daily profile not after `date`
+    /// * `None` - If every daily profile is later than `date` (or there are none)
+    pub fn latest_daily_profile(&self, date: NaiveDate) -> Option<&DailyProfile> {
+        self.daily_profiles.iter()
+            .filter(|p| p.date <= date)
+            .max_by_key(|p| p.date)
+    }
+
+    /// Creates a daily profile for a date by copying the latest earlier one
+    /// 
+    /// Used when a new day is opened so calorie targets never drop to zero.
+    /// The copy is marked with the date of the profile the user actually
+    /// entered (following chains of carried-forward days back to it), so it
+    /// can be shown as unconfirmed until the user confirms or edits it.
+    /// 
+    /// # Arguments
+    /// * `date` - The day that needs a daily profile
+    /// 
+    /// # Returns
+    /// * `Some(&DailyProfile)` - The new carried-forward profile
+    /// * `None` - If the date already has a profile or there is no earlier one to copy
+    pub fn carry_forward(&mut self, date: NaiveDate) -> Option<&DailyProfile> {
+        if self.get_daily_profile(date).is_some() {
+            return None;
+        }
+        let source = self.latest_daily_profile(date)?;
+        let carried = DailyProfile {
+            date,
+            weight: source.weight,
+            activity_level: source.activity_level.clone(),
+            carried_from: Some(source.carried_from.unwrap_or(source.date)),
+        };
+        self.daily_profiles.push(carried);
+        self.daily_profiles.last()
+    }
+
+    /// Adds new daily profile or updates existing one for the specified date
+    /// 
+    /// This method manages daily profile data by:
+    /// 1. Searching for existing profile with matching date
+    /// 2. Updating existing profile if found
+    /// 3. Adding new profile if no existing entry found
+    /// 
+    /// This approach ensures one profile per date while allowing updates
+    /// to weight and activity level throughout the day.
+    /// 
+    /// # Arguments
+    /// * `profile` - DailyProfile containing date, weight, and activity level
+    /// 
+    /// # Examples
+    /// ```ignore
+    /// let daily = DailyProfile {
+    ///     date: today,
+    ///     weight: 70.0,
+    ///     activity_level: ActivityLevel::ModeratelyActive,
+    ///     carried_from: None,
+    /// };
+    /// user_profile.add_or_update_daily_profile(daily);
+    /// ```
+    pub fn add_or_update_daily_profile(&mut self, profile: DailyProfile) {
+        if let Some(idx) = self.daily_profiles.iter().position(|p| p.date == profile.date) {
+            self.daily_profiles[idx] = profile;
+        } else {
+            self.daily_profiles.push(profile);
+        }
+    }
+}
+/// A profile value replaced by a population default
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EstimatedField {
+    /// Height is missing or not positive
+    Height,
+
+    /// Birth date is missing or after the day being calculated
+    Age,
+}
+
+/// Height and age as used by calorie calculators
+#[derive(Debug, Clone, PartialEq)]
+pub struct BodyMetrics {
+    /// Height in centimeters
+    pub height_cm: f64,
+
+    /// Age in complete years
+    pub age_years: u32,
+
+    /// Values that came from population defaults rather than the profile
+    pub estimated: Vec<EstimatedField>,
+}
+
+impl BodyMetrics {
+    /// Returns true if any value is a population default
+    pub fn is_estimate(&self) -> bool {
+        !self.estimated.is_empty()
+    }
+
+    /// Describes the defaults in use, for an "estimate only" banner
+    /// 
+    /// # Returns
+    /// * `Some(String)` - e.g. "Estimate only: height unknown, assuming 176 cm."
+    /// * `None` - If nothing was estimated
+    pub fn estimate_notice(&self) -> Option<String> {
+        if !self.is_estimate() {
+            return None;
+        }
+        let assumptions: Vec<String> = self.estimated.iter().map(|field| match field {
+            EstimatedField::Height => format!("height unknown, assuming {:.0} cm", self.height_cm),
+            EstimatedField::Age => format!("age unknown, assuming {} years", self.age_years),
+        }).collect();
+        Some(format!(
+            "Estimate only: {}. Set your {} in Manage Profile for an accurate target.",
+            assumptions.join("; "),
+            self.estimated.iter().map(|field| match field {
+                EstimatedField::Height => "height",
+                EstimatedField::Age => "birth date",
+            }).collect::<Vec<_>>().join(" and ")
+        ))
+    }
+}
+
+/// Returns the average adult height used when the profile has none (cm)
+pub fn default_height_cm(gender: &Gender) -> f64 {
+    match gender {
+        Gender::Male => 176.0,
+        Gender::Female => 163.0,
+        Gender::Other => 169.5,
+    }
+}
@@ -0,0 +1,87 @@
+//! # Quantity Model
+//!
+//! A quantity is an amount of food as the user entered it: a number of
+//! servings (`1.5`) or an amount in a unit (`85 g`, `250 ml`). Log entries
+//! keep the quantity so that "85 g of chicken" stays 85 g even if the
+//! food's serving weight is corrected later; it is converted into servings
+//! whenever calories or macros are computed.
+//!
+//! ## Conversion
+//!
+//! Conversion goes through `Food::servings_for` (see `models::serving`).
+//! A food defined per 100 g (`Food::new_basic_per_100g`) has a serving of
+//! exactly 100 g, so any mass converts directly.
+
+// yada-core/src/models/quantity.rs
+use std::fmt;
+
+use crate::models::food::{Food, Macros};
+use crate::models::serving::{self, ServingUnit};
+
+/// An amount of food in servings or in a unit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quantity {
+    /// The number entered by the user
+    pub amount: f64,
+
+    /// Unit of `amount`; `None` means servings
+    pub unit: Option<ServingUnit>,
+}
+
+impl Quantity {
+    /// Creates a quantity counted in servings
+    pub fn servings(amount: f64) -> Self {
+        Quantity { amount, unit: None }
+    }
+
+    /// Creates a quantity in a unit such as grams
+    pub fn in_unit(amount: f64, unit: ServingUnit) -> Self {
+        Quantity { amount, unit: Some(unit) }
+    }
+
+    /// Parses a quantity typed by the user (`2`, `85 g`, `1.5 cups`)
+    ///
+    /// # Returns
+    /// * `Ok(Quantity)` - The parsed quantity
+    /// * `Err(String)` - If the amount is not positive or the unit is unknown
+    pub fn parse(text: &str) -> Result<Self, String> {
+        serving::parse_quantity(text).map(|(amount, unit)| Quantity { amount, unit })
+    }
+
+    /// Converts the quantity into servings of a food
+    ///
+    /// # Returns
+    /// * `Ok(f64)` - Number of servings
+    /// * `Err(String)` - If the food cannot be measured in this unit
+    pub fn to_servings(&self, food: &Food) -> Result<f64, String> {
+        food.servings_for(self.amount, self.unit)
+    }
+
+    /// Returns the calories in this quantity of a food
+    ///
+    /// # Returns
+    /// * `Ok(f64)` - Calories
+    /// * `Err(String)` - If the food cannot be measured in this unit
+    pub fn calories(&self, food: &Food) -> Result<f64, String> {
+        Ok(food.calories_per_serving * self.to_servings(food)?)
+    }
+
+    /// Returns the macronutrients in this quantity of a food
+    ///
+    /// # Returns
+    /// * `Ok(Macros)` - Protein, carbohydrates, and fat in grams
+    /// * `Err(String)` - If the food cannot be measured in this unit
+    pub fn macros(&self, food: &Food) -> Result<Macros, String> {
+        Ok(food.macros.scaled(self.to_servings(food)?))
+    }
+}
+
+impl fmt::Display for Quantity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.unit {
+            Some(unit) => write!(f, "{} {}", self.amount, unit),
+            None if self.amount == 1.0 => write!(f, "1 serving"),
+            None => write!(f, "{} servings", self.amount),
+        }
+    }
+}
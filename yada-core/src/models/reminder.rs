@@ -0,0 +1,184 @@
+//! # Reminders
+//!
+//! A reminder is a message shown at a time of day, on every day or on chosen
+//! weekdays, such as "log lunch at 13:00" or "weigh in every morning". The
+//! interactive menu shows reminders as they come due, and `yada remind`
+//! prints the due ones for cron jobs. Reminders are stored in the settings
+//! file (see `repositories::settings_repository`).
+//!
+//! ## Checks
+//!
+//! A reminder may name what it asks for, so it is not shown once that is
+//! done for the day:
+//! - **Meal** (`lunch`, ...): a food was logged for the meal
+//! - **Weigh-in** (`weigh-in`): a weight was entered for the day (a weight
+//!   carried forward from an earlier day does not count)
+//!
+//! ## Due Reminders
+//!
+//! A reminder is due once its time has passed on a day it applies to, until
+//! it has been shown that day or its check is done. Each reminder remembers
+//! the last day it was shown, so it is shown at most once a day.
+
+// yada-core/src/models/reminder.rs
+use std::fmt;
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+
+use crate::models::log::DailyLog;
+use crate::models::meal::MealSlot;
+use crate::models::profile::UserProfile;
+
+/// What a reminder asks for, so it can be skipped once it is done
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReminderCheck {
+    /// Logging a food for a meal
+    Meal(MealSlot),
+
+    /// Entering the day's weight
+    WeighIn,
+}
+
+impl ReminderCheck {
+    /// Key used in the settings file and on the command line
+    pub fn key(&self) -> &'static str {
+        match self {
+            ReminderCheck::Meal(slot) => slot.key(),
+            ReminderCheck::WeighIn => "weigh-in",
+        }
+    }
+
+    /// Parses a meal key or `weigh-in`, ignoring case
+    pub fn parse(text: &str) -> Option<Self> {
+        if text.trim().eq_ignore_ascii_case("weigh-in") {
+            return Some(ReminderCheck::WeighIn);
+        }
+        MealSlot::parse(text).map(ReminderCheck::Meal)
+    }
+
+    /// Returns true if what the reminder asks for was done on a date
+    ///
+    /// # Arguments
+    /// * `date` - The day to check
+    /// * `log` - The food log of that day, if any
+    /// * `profile` - The user profile with the daily weights, if any
+    pub fn is_done(&self, date: NaiveDate, log: Option<&DailyLog>, profile: Option<&UserProfile>) -> bool {
+        match self {
+            ReminderCheck::Meal(slot) => log.is_some_and(|log| log.entries.iter().any(|entry| entry.meal == Some(*slot))),
+            ReminderCheck::WeighIn => profile.is_some_and(|profile| {
+                profile.daily_profiles.iter().any(|daily| daily.date == date && daily.carried_from.is_none())
+            }),
+        }
+    }
+}
+
+/// A message shown at a time of day
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reminder {
+    /// Number identifying the reminder, unique within the settings file
+    pub id: u32,
+
+    /// Time of day the reminder comes due
+    pub time: NaiveTime,
+
+    /// Weekdays the reminder applies to; empty for every day
+    pub days: Vec<Weekday>,
+
+    /// What the reminder asks for, if it can be checked
+    pub check: Option<ReminderCheck>,
+
+    /// Text shown to the user
+    pub message: String,
+
+    /// Last day the reminder was shown
+    pub last_shown: Option<NaiveDate>,
+}
+
+impl Reminder {
+    /// Returns true if the reminder applies to a date's weekday
+    pub fn applies_on(&self, date: NaiveDate) -> bool {
+        self.days.is_empty() || self.days.contains(&date.weekday())
+    }
+
+    /// Returns true if the reminder's time has passed on a day it applies
+    /// to and it has not been shown that day yet
+    ///
+    /// Checks are not considered; see `due_reminders`.
+    pub fn is_due(&self, now: NaiveDateTime) -> bool {
+        self.applies_on(now.date()) && self.time <= now.time() && self.last_shown != Some(now.date())
+    }
+
+    /// Describes when the reminder applies (`every day`, `Mon, Wed`)
+    pub fn schedule(&self) -> String {
+        if self.days.is_empty() {
+            return "every day".to_string();
+        }
+        self.days.iter().map(|day| day.to_string()).collect::<Vec<_>>().join(", ")
+    }
+}
+
+impl fmt::Display for Reminder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}: {}", self.time.format("%H:%M"), self.schedule(), self.message)
+    }
+}
+
+/// Parses a time of day written as `HH:MM`
+pub fn parse_time(text: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(text.trim(), "%H:%M")
+        .map_err(|_| format!("'{}' is not a time of day (HH:MM)", text.trim()))
+}
+
+/// Parses the weekdays a reminder applies to
+///
+/// # Arguments
+/// * `text` - `daily` (or nothing) for every day, `weekdays`, `weekends`, or
+///   comma-separated day names such as `mon,wed,fri`
+///
+/// # Returns
+/// * `Ok(Vec<Weekday>)` - The days in week order; empty for every day
+/// * `Err(String)` - If a day name is not recognized
+pub fn parse_days(text: &str) -> Result<Vec<Weekday>, String> {
+    let text = text.trim().to_lowercase();
+    let mut days = match text.as_str() {
+        "" | "daily" => return Ok(Vec::new()),
+        "weekdays" => vec![Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri],
+        "weekends" => vec![Weekday::Sat, Weekday::Sun],
+        _ => text.split(',')
+            .map(str::trim)
+            .filter(|day| !day.is_empty())
+            .map(|day| day.parse::<Weekday>().map_err(|_| format!("'{}' is not a day of the week", day)))
+            .collect::<Result<Vec<_>, _>>()?,
+    };
+    days.sort_by_key(|day| day.num_days_from_monday());
+    days.dedup();
+    // All seven days are the same as every day
+    if days.len() == 7 {
+        days.clear();
+    }
+    Ok(days)
+}
+
+/// Returns the reminders due at a time whose checks are not done yet
+///
+/// # Arguments
+/// * `reminders` - All reminders
+/// * `now` - The current date and time
+/// * `log` - Today's food log, if any
+/// * `profile` - The user profile, if any
+///
+/// # Returns
+/// The due reminders, earliest time first
+pub fn due_reminders<'a>(
+    reminders: &'a [Reminder],
+    now: NaiveDateTime,
+    log: Option<&DailyLog>,
+    profile: Option<&UserProfile>,
+) -> Vec<&'a Reminder> {
+    let mut due: Vec<&Reminder> = reminders.iter()
+        .filter(|reminder| reminder.is_due(now))
+        .filter(|reminder| !reminder.check.is_some_and(|check| check.is_done(now.date(), log, profile)))
+        .collect();
+    due.sort_by_key(|reminder| (reminder.time, reminder.id));
+    due
+}
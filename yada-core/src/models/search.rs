@@ -0,0 +1,316 @@
+//! # Ranked Food Search
+//!
+//! Keyword search (`Food::matches_keywords`) only finds foods whose keywords
+//! equal a search term. Ranked search is more forgiving: each term is matched
+//! against the food's keywords (typed and derived), the words of its name,
+//! and the whole name, and the best match counts.
+//!
+//! ## Match Quality
+//!
+//! From best to worst:
+//! - **Exact**: The term equals a keyword, a name word, or the whole name
+//! - **Prefix**: A keyword, name word, or the name starts with the term (`chick` → `chicken`)
+//! - **Substring**: The term appears inside one of them (`nut` → `peanut butter`)
+//! - **Fuzzy**: The term is within a small edit distance of a keyword or
+//!   name word (`brocoli` → `broccoli`); see `max_distance`
+//!
+//! Edit distance counts inserted, deleted, and substituted characters, and
+//! swapped neighbours (`bnaana` → `banana`) as one edit each. Terms shorter
+//! than four characters are never matched fuzzily, since almost every short
+//! word is one edit away from another.
+//!
+//! ## Ranking
+//!
+//! A food's score is the sum of the scores of its matched terms, so foods
+//! matching more terms, and matching them better, come first. Ties are
+//! ordered by name.
+//!
+//! ## Search Modes
+//!
+//! A search matches foods with any of its terms (OR) by default, or only
+//! foods matching all of them (AND); see `SearchMode`.
+//!
+//! Searches used often can be saved under a name with their terms and mode
+//! (`SavedSearch`, kept in the settings file).
+//!
+//! ## Suggestions
+//!
+//! When a search finds nothing, `similar` suggests the foods whose words
+//! resemble the search terms most, without the edit limits of fuzzy
+//! matching (`chikn` suggests `Chicken Breast`). Similarity is 1 minus the
+//! edit distance divided by the length of the longer word, averaged over the
+//! terms; foods below `MIN_SIMILARITY` are not suggested.
+
+// yada-core/src/models/search.rs
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::models::food::Food;
+
+/// Lowest similarity of a food suggested by `similar`
+pub const MIN_SIMILARITY: f64 = 0.5;
+
+/// Foods suggested when a search finds nothing
+pub const SUGGESTION_COUNT: usize = 5;
+
+/// Whether a search needs every term or any term to match
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SearchMode {
+    /// Any term may match (OR search)
+    #[default]
+    Any,
+
+    /// Every term must match (AND search)
+    All,
+}
+
+impl SearchMode {
+    /// Key used in the settings file
+    pub fn key(self) -> &'static str {
+        match self {
+            SearchMode::Any => "any",
+            SearchMode::All => "all",
+        }
+    }
+
+    /// Parses `any`/`or` or `all`/`and` (case-insensitive)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "any" | "or" => Some(SearchMode::Any),
+            "all" | "and" => Some(SearchMode::All),
+            _ => None,
+        }
+    }
+
+    /// Returns true if every term must match
+    pub fn match_all(self) -> bool {
+        self == SearchMode::All
+    }
+}
+
+impl fmt::Display for SearchMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SearchMode::Any => write!(f, "any keyword (OR search)"),
+            SearchMode::All => write!(f, "all keywords (AND search)"),
+        }
+    }
+}
+
+/// Search terms and mode saved under a name, such as "high-protein snacks"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SavedSearch {
+    /// Name the search is picked by
+    pub name: String,
+
+    /// Search terms as typed at the search prompt, in sorted order
+    pub terms: Vec<String>,
+
+    /// Whether any or all terms must match
+    pub mode: SearchMode,
+}
+
+impl fmt::Display for SavedSearch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {} [{}]", self.name, self.terms.join(", "), self.mode)
+    }
+}
+
+/// How well a search term matched a food
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchQuality {
+    /// Equal to a keyword, name word, or the whole name
+    Exact,
+
+    /// Start of a keyword, name word, or the name
+    Prefix,
+
+    /// Inside a keyword, name word, or the name
+    Substring,
+
+    /// Within `distance` edits of a keyword or name word
+    Fuzzy { distance: usize },
+}
+
+impl MatchQuality {
+    /// Points the match adds to a food's score
+    pub fn score(self) -> u32 {
+        match self {
+            MatchQuality::Exact => 100,
+            MatchQuality::Prefix => 70,
+            MatchQuality::Substring => 50,
+            MatchQuality::Fuzzy { distance } => 40u32.saturating_sub(10 * distance as u32),
+        }
+    }
+}
+
+/// A food found by ranked search
+#[derive(Debug, Clone, Copy)]
+pub struct SearchHit<'a> {
+    /// The matching food
+    pub food: &'a Food,
+
+    /// Sum of the scores of the matched terms
+    pub score: u32,
+
+    /// Best match among the terms
+    pub best: MatchQuality,
+}
+
+/// Returns the largest edit distance accepted for a term
+///
+/// No edits below 4 characters, one edit up to 7 characters, two beyond.
+pub fn max_distance(term: &str) -> usize {
+    match term.chars().count() {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
+}
+
+/// Matches one search term against a food
+///
+/// # Arguments
+/// * `food` - Food to match
+/// * `term` - Lowercase search term; may contain spaces
+///
+/// # Returns
+/// * `Some(MatchQuality)` - The best way the term matched
+/// * `None` - If the term does not match the food at all
+pub fn match_term(food: &Food, term: &str) -> Option<MatchQuality> {
+    let term = term.trim();
+    if term.is_empty() {
+        return None;
+    }
+    let name = food.name.to_lowercase();
+    let tokens = food_tokens(food);
+
+    if name == term || tokens.contains(term) {
+        return Some(MatchQuality::Exact);
+    }
+    if name.starts_with(term) || tokens.iter().any(|token| token.starts_with(term)) {
+        return Some(MatchQuality::Prefix);
+    }
+    if name.contains(term) || tokens.iter().any(|token| token.contains(term)) {
+        return Some(MatchQuality::Substring);
+    }
+
+    let allowed = max_distance(term);
+    if allowed == 0 {
+        return None;
+    }
+    tokens.iter()
+        .map(|token| edit_distance(term, token))
+        .filter(|distance| *distance <= allowed)
+        .min()
+        .map(|distance| MatchQuality::Fuzzy { distance })
+}
+
+/// Returns the words a term is matched against: the food's typed and
+/// derived keywords, and the lowercase words of its name
+pub fn food_tokens(food: &Food) -> HashSet<String> {
+    let mut tokens: HashSet<String> = name_words(&food.name).collect();
+    tokens.extend(food.keywords.iter().chain(&food.derived_keywords).cloned());
+    tokens
+}
+
+/// Splits text into lowercase words at every character that is not a letter or digit
+pub fn name_words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+}
+
+/// Ranks foods against search terms, best match first
+///
+/// # Arguments
+/// * `foods` - Foods to search
+/// * `terms` - Lowercase search terms
+/// * `match_all` - If true, every term must match; otherwise any term may
+///
+/// # Returns
+/// Matching foods ordered by score, then name
+pub fn rank<'a>(foods: impl IntoIterator<Item = &'a Food>, terms: &HashSet<String>, match_all: bool) -> Vec<SearchHit<'a>> {
+    let mut hits: Vec<SearchHit<'a>> = foods.into_iter().filter_map(|food| {
+        let matches: Vec<MatchQuality> = terms.iter().filter_map(|term| match_term(food, term)).collect();
+        if matches.is_empty() || (match_all && matches.len() < terms.len()) {
+            return None;
+        }
+        let best = matches.iter().copied().max_by_key(|quality| quality.score())?;
+        let score = matches.iter().map(|quality| quality.score()).sum();
+        Some(SearchHit { food, score, best })
+    }).collect();
+
+    hits.sort_by(|a, b| {
+        b.score.cmp(&a.score)
+            .then_with(|| a.food.name.cmp(&b.food.name))
+            .then_with(|| a.food.id.cmp(&b.food.id))
+    });
+    hits
+}
+
+/// Suggests the foods most similar to search terms that matched nothing
+///
+/// # Arguments
+/// * `foods` - Foods to choose from
+/// * `terms` - Lowercase search terms
+/// * `limit` - Most foods returned
+///
+/// # Returns
+/// Foods with a similarity of at least `MIN_SIMILARITY`, most similar first, then by name
+pub fn similar<'a>(foods: impl IntoIterator<Item = &'a Food>, terms: &HashSet<String>, limit: usize) -> Vec<&'a Food> {
+    if terms.is_empty() {
+        return Vec::new();
+    }
+    let mut scored: Vec<(f64, &Food)> = foods.into_iter().filter_map(|food| {
+        let mut tokens = food_tokens(food);
+        tokens.insert(food.name.to_lowercase());
+        let total: f64 = terms.iter()
+            .map(|term| tokens.iter().map(|token| similarity(term, token)).fold(0.0, f64::max))
+            .sum();
+        let score = total / terms.len() as f64;
+        (score >= MIN_SIMILARITY).then_some((score, food))
+    }).collect();
+
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.1.name.cmp(&b.1.name)));
+    scored.into_iter().take(limit).map(|(_, food)| food).collect()
+}
+
+/// How alike two words are, from 0 (nothing alike) to 1 (equal)
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let longest = a.chars().count().max(b.chars().count());
+    if longest == 0 {
+        return 1.0;
+    }
+    1.0 - edit_distance(a, b) as f64 / longest as f64
+}
+
+/// Counts the edits needed to turn one string into another
+///
+/// Insertions, deletions, substitutions, and swaps of neighbouring
+/// characters each count as one edit (optimal string alignment distance).
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    // rows[i][j] is the distance between the first i chars of a and the first j of b
+    let mut rows = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = distance;
+        }
+    }
+    rows[a.len()][b.len()]
+}
@@ -0,0 +1,187 @@
+//! # Serving Sizes and Units
+//!
+//! Foods are measured in servings, but people think in grams, cups, or
+//! pieces. This module describes what one serving of a food is
+//! (`ServingSize`, e.g. `1 cup` or `30 g`) and converts amounts given in
+//! any supported unit into servings.
+//!
+//! ## Units
+//!
+//! | Unit        | Symbol  | Kind   | Base amount |
+//! |-------------|---------|--------|-------------|
+//! | Gram        | `g`     | Mass   | 1 g         |
+//! | Ounce       | `oz`    | Mass   | 28.3495 g   |
+//! | Milliliter  | `ml`    | Volume | 1 ml        |
+//! | Cup         | `cup`   | Volume | 236.588 ml  |
+//! | Piece       | `piece` | Count  | 1 piece     |
+//!
+//! Units convert freely within their kind. Mass and volume are never
+//! converted into each other, since that would require the food's density.
+//!
+//! ## Quantities
+//!
+//! `parse_quantity` reads user input such as `2`, `85 g`, `85g`, or
+//! `1.5 cups`. A bare number (or the word `serving`) means servings.
+
+// yada-core/src/models/serving.rs
+use std::fmt;
+
+/// Grams in one ounce
+const GRAMS_PER_OUNCE: f64 = 28.3495;
+
+/// Milliliters in one US cup
+const MILLILITERS_PER_CUP: f64 = 236.588;
+
+/// What a unit measures; only units of the same kind convert into each other
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnitKind {
+    Mass,
+    Volume,
+    Count,
+}
+
+/// Unit in which a serving size or an amount of food is given
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServingUnit {
+    Gram,
+    Milliliter,
+    Cup,
+    Piece,
+    Ounce,
+}
+
+impl ServingUnit {
+    /// All units, in the order they are listed to the user
+    pub const ALL: [ServingUnit; 5] = [
+        ServingUnit::Gram,
+        ServingUnit::Milliliter,
+        ServingUnit::Cup,
+        ServingUnit::Piece,
+        ServingUnit::Ounce,
+    ];
+
+    /// Short name used for display and in the data files
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            ServingUnit::Gram => "g",
+            ServingUnit::Milliliter => "ml",
+            ServingUnit::Cup => "cup",
+            ServingUnit::Piece => "piece",
+            ServingUnit::Ounce => "oz",
+        }
+    }
+
+    /// Parses a unit from its symbol or a common spelling
+    ///
+    /// Matching is case-insensitive and accepts plurals (`cups`, `grams`).
+    ///
+    /// # Returns
+    /// * `Some(ServingUnit)` - The recognized unit
+    /// * `None` - If the text names no supported unit
+    pub fn parse(text: &str) -> Option<Self> {
+        match text.trim().to_lowercase().as_str() {
+            "g" | "gram" | "grams" | "gr" => Some(ServingUnit::Gram),
+            "ml" | "milliliter" | "milliliters" | "millilitre" | "millilitres" => Some(ServingUnit::Milliliter),
+            "cup" | "cups" => Some(ServingUnit::Cup),
+            "piece" | "pieces" | "pc" | "pcs" => Some(ServingUnit::Piece),
+            "oz" | "ounce" | "ounces" => Some(ServingUnit::Ounce),
+            _ => None,
+        }
+    }
+
+    /// Returns what the unit measures
+    pub fn kind(&self) -> UnitKind {
+        match self {
+            ServingUnit::Gram | ServingUnit::Ounce => UnitKind::Mass,
+            ServingUnit::Milliliter | ServingUnit::Cup => UnitKind::Volume,
+            ServingUnit::Piece => UnitKind::Count,
+        }
+    }
+
+    /// Amount of the kind's base unit (g, ml, or piece) in one of this unit
+    fn base_amount(&self) -> f64 {
+        match self {
+            ServingUnit::Gram | ServingUnit::Milliliter | ServingUnit::Piece => 1.0,
+            ServingUnit::Ounce => GRAMS_PER_OUNCE,
+            ServingUnit::Cup => MILLILITERS_PER_CUP,
+        }
+    }
+
+    /// Converts an amount in this unit into another unit
+    ///
+    /// # Returns
+    /// * `Some(f64)` - The amount in `target` units
+    /// * `None` - If the units measure different kinds of quantity
+    pub fn convert(&self, amount: f64, target: ServingUnit) -> Option<f64> {
+        if self.kind() != target.kind() {
+            return None;
+        }
+        Some(amount * self.base_amount() / target.base_amount())
+    }
+}
+
+impl fmt::Display for ServingUnit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+/// The amount of food that makes up one serving, such as `1 cup` or `30 g`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ServingSize {
+    /// Amount in `unit` (always positive)
+    pub amount: f64,
+
+    /// Unit of `amount`
+    pub unit: ServingUnit,
+}
+
+impl ServingSize {
+    /// Creates a serving size
+    pub fn new(amount: f64, unit: ServingUnit) -> Self {
+        ServingSize { amount, unit }
+    }
+
+    /// Returns the weight of one serving in grams, if the unit is a mass
+    pub fn grams(&self) -> Option<f64> {
+        self.unit.convert(self.amount, ServingUnit::Gram)
+    }
+}
+
+impl fmt::Display for ServingSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.amount, self.unit)
+    }
+}
+
+/// Parses an amount of food as typed by the user
+///
+/// # Arguments
+/// * `text` - A positive number, optionally followed by a unit (`85 g`, `85g`, `1.5 cups`)
+///
+/// # Returns
+/// * `Ok((amount, Some(unit)))` - An amount in a specific unit
+/// * `Ok((amount, None))` - A number of servings
+/// * `Err(String)` - If the number or unit cannot be read
+pub fn parse_quantity(text: &str) -> Result<(f64, Option<ServingUnit>), String> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+
+    let amount: f64 = number
+        .parse()
+        .map_err(|_| format!("Invalid amount '{}'. Enter a number, optionally with a unit (e.g. 2, 85 g, 1.5 cups).", text))?;
+    if amount <= 0.0 {
+        return Err("Amount must be a positive number.".to_string());
+    }
+
+    match unit.trim().to_lowercase().as_str() {
+        "" | "serving" | "servings" => Ok((amount, None)),
+        unit => ServingUnit::parse(unit).map(|unit| (amount, Some(unit))).ok_or_else(|| {
+            let known: Vec<&str> = ServingUnit::ALL.iter().map(|u| u.symbol()).collect();
+            format!("Unknown unit '{}'. Use servings or one of: {}.", unit, known.join(", "))
+        }),
+    }
+}
@@ -0,0 +1,180 @@
+//! # Food Usage and Popularity
+//!
+//! After years of use the food database accumulates foods that are never
+//! eaten any more. This module measures how foods are used so that pickers
+//! can list the relevant ones first and maintenance can suggest archiving
+//! the rest.
+//!
+//! ## Last Used
+//!
+//! Each food remembers the most recent date it was logged (`Food::last_used`).
+//! Logging a composite food also counts as using every food it is built
+//! from, so the ingredients of a recipe that is still eaten are never
+//! suggested for archiving.
+//!
+//! ## Popularity Decay
+//!
+//! Popularity is the number of times a food was logged directly, with each
+//! use weighted by its age: a use loses half its weight every
+//! `POPULARITY_HALF_LIFE_DAYS` days. A food eaten daily last year therefore
+//! ranks below one eaten a few times last week.
+//!
+//! ## Quick Log
+//!
+//! For logging the same foods again, `LogRepository` ranks foods by how
+//! often and how recently they were logged (see `LoggedFood`). Unlike
+//! popularity these counts are not decayed, and only direct entries count.
+//!
+//! ## Archiving
+//!
+//! Foods unused for `ARCHIVE_AFTER_DAYS` days are suggested for archiving.
+//! Archived foods disappear from pickers but keep resolving by ID, so old
+//! logs and composite foods are unaffected, and logging an archived food
+//! by ID restores it.
+
+// yada-core/src/models/usage.rs
+use std::collections::{HashMap, HashSet};
+
+use chrono::NaiveDate;
+
+use crate::models::food::Food;
+use crate::models::log::DailyLog;
+use crate::models::quantity::Quantity;
+
+/// Number of days after which a use counts half as much towards popularity
+pub const POPULARITY_HALF_LIFE_DAYS: f64 = 90.0;
+
+/// Number of foods in each quick-log list (most used, and recent)
+pub const QUICK_LOG_COUNT: usize = 10;
+
+/// Number of days without use after which a food is suggested for archiving
+pub const ARCHIVE_AFTER_DAYS: i64 = 365;
+
+/// How one food has been used, as found in the logs
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FoodUsage {
+    /// Number of log entries for the food itself
+    pub uses: usize,
+
+    /// Most recent date the food was logged, directly or as a component
+    pub last_used: Option<NaiveDate>,
+
+    /// Decayed number of uses (see module docs)
+    pub popularity: f64,
+}
+
+/// How often and how recently a food was logged directly
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoggedFood {
+    /// ID of the logged food
+    pub food_id: String,
+
+    /// Number of log entries for the food
+    pub uses: usize,
+
+    /// Date of the most recent entry
+    pub last_date: NaiveDate,
+
+    /// Amount of the most recent entry, as entered
+    pub last_quantity: Quantity,
+}
+
+/// Measures how every food has been used across the given logs
+///
+/// # Arguments
+/// * `logs` - Daily logs to scan
+/// * `foods` - All known foods, used to resolve composite components
+/// * `today` - Reference date for popularity decay
+///
+/// # Returns
+/// Usage per food ID; foods never logged have no entry
+pub fn collect_usage<'a>(
+    logs: impl IntoIterator<Item = &'a DailyLog>,
+    foods: &HashMap<String, Food>,
+    today: NaiveDate,
+) -> HashMap<String, FoodUsage> {
+    let mut usage: HashMap<String, FoodUsage> = HashMap::new();
+
+    for log in logs {
+        let age_days = (today - log.date).num_days().max(0) as f64;
+        let weight = 0.5_f64.powf(age_days / POPULARITY_HALF_LIFE_DAYS);
+
+        for entry in &log.entries {
+            let direct = usage.entry(entry.food_id.clone()).or_default();
+            direct.uses += 1;
+            direct.popularity += weight;
+
+            for id in used_food_ids(&entry.food_id, foods) {
+                let food_usage = usage.entry(id).or_default();
+                food_usage.last_used = food_usage.last_used.max(Some(log.date));
+            }
+        }
+    }
+    usage
+}
+
+/// Returns a food's ID followed by the IDs of all foods it is built from
+///
+/// Components of components are included; each ID appears once even if
+/// the composite structure contains a cycle.
+pub fn used_food_ids(food_id: &str, foods: &HashMap<String, Food>) -> Vec<String> {
+    let mut ids = vec![food_id.to_string()];
+    let mut seen: HashSet<String> = ids.iter().cloned().collect();
+    let mut index = 0;
+
+    while index < ids.len() {
+        if let Some(food) = foods.get(&ids[index]) {
+            for (component, _) in &food.components {
+                if seen.insert(component.clone()) {
+                    ids.push(component.clone());
+                }
+            }
+        }
+        index += 1;
+    }
+    ids
+}
+
+/// Sorts foods so the most popular come first
+///
+/// Foods with equal popularity (including all unused foods) are sorted by name.
+pub fn sort_by_popularity(foods: &mut [&Food], usage: &HashMap<String, FoodUsage>) {
+    let popularity = |food: &Food| usage.get(&food.id).map_or(0.0, |u| u.popularity);
+    foods.sort_by(|a, b| {
+        popularity(b)
+            .total_cmp(&popularity(a))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+}
+
+/// Finds foods that have not been used for a given number of days
+///
+/// Foods without a `last_used` date and foods already archived are never
+/// suggested. Neither are foods used by a composite food that is kept,
+/// since archiving them would hide ingredients of a live recipe.
+///
+/// # Arguments
+/// * `foods` - All known foods
+/// * `today` - Reference date
+/// * `after_days` - Minimum number of days since the last use
+///
+/// # Returns
+/// IDs of the suggested foods, least recently used first
+pub fn archive_candidates(foods: &HashMap<String, Food>, today: NaiveDate, after_days: i64) -> Vec<String> {
+    let is_stale = |food: &Food| {
+        !food.archived && food.last_used.is_some_and(|date| (today - date).num_days() >= after_days)
+    };
+
+    let kept_components: HashSet<String> = foods
+        .values()
+        .filter(|food| !food.archived && !is_stale(food) && !food.components.is_empty())
+        .flat_map(|food| used_food_ids(&food.id, foods).into_iter().skip(1))
+        .collect();
+
+    let mut candidates: Vec<&Food> = foods
+        .values()
+        .filter(|food| is_stale(food) && !kept_components.contains(&food.id))
+        .collect();
+    candidates.sort_by(|a, b| a.last_used.cmp(&b.last_used).then_with(|| a.id.cmp(&b.id)));
+    candidates.into_iter().map(|food| food.id.clone()).collect()
+}
@@ -0,0 +1,151 @@
+//! # Validation
+//!
+//! Range checks for values typed by the user or read from the data files,
+//! so the prompts and the repositories agree on what is possible instead of
+//! each checking for themselves.
+//!
+//! ## Profile Ranges
+//!
+//! | Value      | Accepted                                        |
+//! |------------|-------------------------------------------------|
+//! | Height     | `MIN_HEIGHT_CM` to `MAX_HEIGHT_CM` (50-272 cm)  |
+//! | Weight     | `MIN_WEIGHT_KG` to `MAX_WEIGHT_KG` (20-500 kg)  |
+//! | Birth date | Not in the future, and under `MAX_AGE_YEARS`    |
+//!
+//! The limits are generous on purpose: they catch typos such as a height in
+//! meters or a weight with an extra digit, not unusual bodies. Heights and
+//! weights are checked in metric units; the prompts convert first.
+//!
+//! ## Food Warnings
+//!
+//! Food values are not rejected, since labels vary, but `food_warnings`
+//! lists those that look like typos so the user can check them:
+//! - **No calories**: 0 kcal for a food not measured by volume (drinks such
+//!   as water or black coffee may have none, solid foods rarely do)
+//! - **Large serving**: More than `MAX_CALORIES_PER_SERVING` per serving,
+//!   often a whole package entered as one serving
+//! - **Macros off**: Protein, carbs, fat, and alcohol adding up to calories
+//!   more than `MACRO_CALORIE_TOLERANCE` (and `MACRO_CALORIE_SLACK`) away
+//!   from the food's calories
+//!
+//! Composite foods take their values from their components and are not checked.
+
+// yada-core/src/models/validation.rs
+use chrono::NaiveDate;
+
+use crate::models::age;
+use crate::models::food::{Food, FoodType};
+use crate::models::macro_target::CALORIES_PER_GRAM;
+use crate::models::serving::UnitKind;
+
+/// Shortest height accepted, in centimeters
+pub const MIN_HEIGHT_CM: f64 = 50.0;
+
+/// Tallest height accepted, in centimeters
+pub const MAX_HEIGHT_CM: f64 = 272.0;
+
+/// Lightest weight accepted, in kilograms
+pub const MIN_WEIGHT_KG: f64 = 20.0;
+
+/// Heaviest weight accepted, in kilograms
+pub const MAX_WEIGHT_KG: f64 = 500.0;
+
+/// Age in years a birth date must stay under
+pub const MAX_AGE_YEARS: u32 = 130;
+
+/// Checks a height in centimeters
+///
+/// # Returns
+/// * `Ok(f64)` - The height
+/// * `Err(String)` - If it is not a number within the accepted range
+pub fn height_cm(height: f64) -> Result<f64, String> {
+    if height.is_finite() && (MIN_HEIGHT_CM..=MAX_HEIGHT_CM).contains(&height) {
+        Ok(height)
+    } else {
+        Err(format!("Height must be between {} and {} cm.", MIN_HEIGHT_CM, MAX_HEIGHT_CM))
+    }
+}
+
+/// Checks a weight in kilograms
+///
+/// # Returns
+/// * `Ok(f64)` - The weight
+/// * `Err(String)` - If it is not a number within the accepted range
+pub fn weight_kg(weight: f64) -> Result<f64, String> {
+    if weight.is_finite() && (MIN_WEIGHT_KG..=MAX_WEIGHT_KG).contains(&weight) {
+        Ok(weight)
+    } else {
+        Err(format!("Weight must be between {} and {} kg.", MIN_WEIGHT_KG, MAX_WEIGHT_KG))
+    }
+}
+
+/// Checks a birth date against the current date
+///
+/// # Arguments
+/// * `birth_date` - The birth date
+/// * `today` - The current date
+///
+/// # Returns
+/// * `Ok(NaiveDate)` - The birth date
+/// * `Err(String)` - If it is in the future or `MAX_AGE_YEARS` or more ago
+pub fn birth_date(birth_date: NaiveDate, today: NaiveDate) -> Result<NaiveDate, String> {
+    if birth_date > today {
+        Err("Birth date cannot be in the future.".to_string())
+    } else if age::age_on(birth_date, today) >= MAX_AGE_YEARS {
+        Err(format!("Birth date must be less than {} years ago.", MAX_AGE_YEARS))
+    } else {
+        Ok(birth_date)
+    }
+}
+
+/// Calories per serving above which a food is reported
+pub const MAX_CALORIES_PER_SERVING: f64 = 900.0;
+
+/// Share of the calories the macros may differ by before a food is reported
+pub const MACRO_CALORIE_TOLERANCE: f64 = 0.2;
+
+/// Calories the macros may always differ by (labels round each value)
+pub const MACRO_CALORIE_SLACK: f64 = 20.0;
+
+/// Calories per gram of alcohol
+const ALCOHOL_CALORIES_PER_GRAM: f64 = 7.0;
+
+/// Lists the values of a basic food that look like typos
+///
+/// # Arguments
+/// * `food` - The food to check
+///
+/// # Returns
+/// One message per suspicious value; empty if the food looks plausible
+pub fn food_warnings(food: &Food) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if food.food_type != FoodType::Basic {
+        return warnings;
+    }
+
+    let calories = food.calories_per_serving;
+    let liquid = food.serving_size.is_some_and(|size| size.unit.kind() == UnitKind::Volume);
+    if calories == 0.0 && !liquid {
+        warnings.push("The food has 0 kcal per serving, which is rare for solid foods.".to_string());
+    }
+    if calories > MAX_CALORIES_PER_SERVING {
+        warnings.push(format!(
+            "The food has {:.0} kcal per serving, more than {:.0}. Is the serving a whole package?",
+            calories, MAX_CALORIES_PER_SERVING
+        ));
+    }
+
+    let macros = &food.macros;
+    let from_macros = macros.protein * CALORIES_PER_GRAM[0]
+        + macros.carbs * CALORIES_PER_GRAM[1]
+        + macros.fat * CALORIES_PER_GRAM[2]
+        + macros.alcohol * ALCOHOL_CALORIES_PER_GRAM;
+    let allowed = (calories * MACRO_CALORIE_TOLERANCE).max(MACRO_CALORIE_SLACK);
+    if calories > 0.0 && from_macros > 0.0 && (from_macros - calories).abs() > allowed {
+        warnings.push(format!(
+            "The macros add up to {:.0} kcal, but the food has {:.0} kcal per serving.",
+            from_macros, calories
+        ));
+    }
+    warnings
+}
@@ -0,0 +1,161 @@
+//! # Weeks
+//!
+//! Everything that groups days into weeks (weekly totals, week-based
+//! streaks, and weekday layouts) uses one first day of the week, so a week
+//! never starts on Monday in one report and on Sunday in another.
+//!
+//! ## First Day of the Week
+//!
+//! Weeks start on Monday (ISO 8601) unless the `YADA_WEEK_START`
+//! environment variable is set to `sunday`. A week is identified by the
+//! date of its first day.
+//!
+//! ## Weekly Totals
+//!
+//! `weekly_totals` adds up the calories, macronutrients, and food cost of
+//! every logged day, grouped by week. Weeks without any logged day are left out.
+
+// yada-core/src/models/week.rs
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::fmt;
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+use crate::models::food::{Food, Macros};
+use crate::models::log::DailyLog;
+
+/// Environment variable choosing the first day of the week
+pub const WEEK_START_ENV_VAR: &str = "YADA_WEEK_START";
+
+/// Day a week starts on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WeekStart {
+    /// Weeks run Sunday to Saturday
+    Sunday,
+
+    /// Weeks run Monday to Sunday (ISO 8601)
+    #[default]
+    Monday,
+}
+
+impl WeekStart {
+    /// Reads the first day of the week from `YADA_WEEK_START`
+    ///
+    /// Unset or unrecognized values fall back to Monday.
+    pub fn from_env() -> Self {
+        env::var(WEEK_START_ENV_VAR)
+            .ok()
+            .and_then(|value| Self::parse(&value))
+            .unwrap_or_default()
+    }
+
+    /// Parses `sunday`/`sun` or `monday`/`mon` (case-insensitive)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "sunday" | "sun" => Some(WeekStart::Sunday),
+            "monday" | "mon" => Some(WeekStart::Monday),
+            _ => None,
+        }
+    }
+
+    /// Returns the weekday weeks start on
+    pub fn weekday(self) -> Weekday {
+        match self {
+            WeekStart::Sunday => Weekday::Sun,
+            WeekStart::Monday => Weekday::Mon,
+        }
+    }
+
+    /// Returns the first day of the week containing a date
+    pub fn week_of(self, date: NaiveDate) -> NaiveDate {
+        let offset = date.weekday().days_since(self.weekday());
+        date - Duration::days(offset as i64)
+    }
+
+    /// Returns the seven weekdays in order, starting with the first day of the week
+    pub fn weekdays(self) -> [Weekday; 7] {
+        let mut day = self.weekday();
+        std::array::from_fn(|_| {
+            let current = day;
+            day = day.succ();
+            current
+        })
+    }
+}
+
+impl fmt::Display for WeekStart {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WeekStart::Sunday => write!(f, "Sunday"),
+            WeekStart::Monday => write!(f, "Monday"),
+        }
+    }
+}
+
+/// Calories and macros logged during one week
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeekTotals {
+    /// First day of the week
+    pub start: NaiveDate,
+
+    /// Days of the week with at least one log entry
+    pub days_logged: usize,
+
+    /// Calories consumed during the week
+    pub calories: f64,
+
+    /// Macronutrients consumed during the week
+    pub macros: Macros,
+
+    /// Cost of the food eaten during the week, counting only foods with a cost
+    pub cost: f64,
+}
+
+impl WeekTotals {
+    /// Last day of the week
+    pub fn end(&self) -> NaiveDate {
+        self.start + Duration::days(6)
+    }
+
+    /// Average calories per logged day
+    pub fn average_calories(&self) -> f64 {
+        if self.days_logged == 0 {
+            0.0
+        } else {
+            self.calories / self.days_logged as f64
+        }
+    }
+}
+
+/// Adds up logged days by week
+///
+/// # Arguments
+/// * `logs` - Daily logs to group; logs without entries are ignored
+/// * `food_db` - Foods used to compute calories and macros
+/// * `week_start` - First day of the week
+///
+/// # Returns
+/// Totals for every week with a logged day, earliest week first
+pub fn weekly_totals<'a>(
+    logs: impl IntoIterator<Item = &'a DailyLog>,
+    food_db: &HashMap<String, Food>,
+    week_start: WeekStart,
+) -> Vec<WeekTotals> {
+    let mut weeks: BTreeMap<NaiveDate, WeekTotals> = BTreeMap::new();
+    for log in logs.into_iter().filter(|log| !log.entries.is_empty()) {
+        let start = week_start.week_of(log.date);
+        let week = weeks.entry(start).or_insert(WeekTotals {
+            start,
+            days_logged: 0,
+            calories: 0.0,
+            macros: Macros::default(),
+            cost: 0.0,
+        });
+        week.days_logged += 1;
+        week.calories += log.total_calories(food_db);
+        week.macros += log.total_macros(food_db);
+        week.cost += log.total_cost(food_db).cost;
+    }
+    weeks.into_values().collect()
+}
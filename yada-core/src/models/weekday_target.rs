@@ -0,0 +1,115 @@
+//! # Weekday Targets
+//!
+//! Calorie cycling means eating more on some days of the week (training
+//! days) and less on others. A profile can hold one override per weekday,
+//! applied on top of the target the selected calculation method returns
+//! (after the diet preset's adjustment, see `models::diet_preset`):
+//!
+//! - **Fixed** (`2500`): The day's target is exactly this many calories
+//! - **Adjustment** (`+300`, `-200`): This many calories are added to or
+//!   taken from the calculated target
+//!
+//! Overrides only apply to days that have a target, that is days on or
+//! after the first daily profile, and an adjusted target never drops below
+//! zero.
+
+// yada-core/src/models/weekday_target.rs
+use std::fmt;
+
+use chrono::{Datelike, NaiveDate, Weekday};
+
+/// Change to the calculated calorie target of one weekday
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TargetOverride {
+    /// Use this target instead of the calculated one (calories)
+    Fixed(f64),
+
+    /// Add this to the calculated target (calories, may be negative)
+    Adjust(f64),
+}
+
+impl TargetOverride {
+    /// Parses `2500` as a fixed target and `+300` or `-200` as an adjustment
+    ///
+    /// # Returns
+    /// * `Ok(TargetOverride)` - The override
+    /// * `Err(String)` - If the value is not a number, or a fixed target is not positive
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let value = value.trim();
+        let number: f64 = value.parse()
+            .ok()
+            .filter(|number: &f64| number.is_finite())
+            .ok_or_else(|| format!("'{}' is not a calorie target or adjustment", value))?;
+        if value.starts_with(['+', '-']) {
+            Ok(TargetOverride::Adjust(number))
+        } else if number > 0.0 {
+            Ok(TargetOverride::Fixed(number))
+        } else {
+            Err("A fixed target must be positive".to_string())
+        }
+    }
+
+    /// Applies the override to a calculated target
+    pub fn apply(&self, target: f64) -> f64 {
+        match self {
+            TargetOverride::Fixed(fixed) => *fixed,
+            TargetOverride::Adjust(change) => (target + change).max(0.0),
+        }
+    }
+}
+
+impl fmt::Display for TargetOverride {
+    /// Formats the override the way `parse` reads it
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TargetOverride::Fixed(fixed) => write!(f, "{}", fixed),
+            TargetOverride::Adjust(change) => write!(f, "{:+}", change),
+        }
+    }
+}
+
+/// Overrides of the calorie target by day of the week
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WeekdayTargets {
+    /// Override for each weekday, Monday first
+    overrides: [Option<TargetOverride>; 7],
+}
+
+impl WeekdayTargets {
+    /// Returns the override for a weekday, if any
+    pub fn get(&self, weekday: Weekday) -> Option<TargetOverride> {
+        self.overrides[weekday.num_days_from_monday() as usize]
+    }
+
+    /// Sets or clears (`None`) the override for a weekday
+    pub fn set(&mut self, weekday: Weekday, target: Option<TargetOverride>) {
+        self.overrides[weekday.num_days_from_monday() as usize] = target;
+    }
+
+    /// Returns true if no weekday has an override
+    pub fn is_empty(&self) -> bool {
+        self.overrides.iter().all(Option::is_none)
+    }
+
+    /// Returns the weekdays with an override, Monday first
+    pub fn iter(&self) -> impl Iterator<Item = (Weekday, TargetOverride)> + '_ {
+        self.overrides.iter().enumerate().filter_map(|(index, target)| {
+            target.map(|target| (Weekday::try_from(index as u8).unwrap_or(Weekday::Mon), target))
+        })
+    }
+
+    /// Applies the override of a date's weekday to its calculated target
+    ///
+    /// # Arguments
+    /// * `date` - The day of the target
+    /// * `target` - Target from the calculation method (0 when the day has none)
+    ///
+    /// # Returns
+    /// The target to use; days without a target keep 0
+    pub fn apply(&self, date: NaiveDate, target: f64) -> f64 {
+        match self.get(date.weekday()) {
+            Some(target_override) if target > 0.0 => target_override.apply(target),
+            _ => target,
+        }
+    }
+}
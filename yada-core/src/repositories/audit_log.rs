@@ -0,0 +1,114 @@
+//! # Audit Log
+//!
+//! An append-only record of every change made through a command, kept next
+//! to the user's data files as `audit.log`. It answers "when did this entry
+//! appear, and was it undone?" when a data file looks wrong, and lets a
+//! coach review what a client logged and changed.
+//!
+//! ## Format
+//!
+//! One line per event, oldest first, with the fields separated by `|`:
+//! ```text
+//! 2024-03-04T08:15:02|execute|add_log|Add log entry: 2 servings of oats on 2024-03-04
+//! 2024-03-04T08:15:40|undo|add_log|Add log entry: 2 servings of oats on 2024-03-04
+//! ```
+//! - **Time**: Local date and time the event happened
+//! - **Event**: `execute` for a change made, `undo` for a change undone
+//! - **Type**: The command's type, as in `CommandType::key`
+//! - **Description**: The command's description (last, so it may contain `|`)
+//!
+//! Lines are only ever added; the file is never rewritten, loaded, or
+//! bundled with the data files. Commands that fail are not recorded.
+//!
+//! The command manager reports commands through its `AuditSink` trait,
+//! which `AuditLog` implements.
+//!
+//! ## When Nothing Is Written
+//!
+//! - `YADA_AUDIT_LOG=off` turns the log off
+//! - While the data files are encrypted (see `encryption`), since the log
+//!   would keep food names and amounts in plain text
+
+// yada-core/src/repositories/audit_log.rs
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{Local, NaiveDateTime};
+
+use crate::models::command::CommandType;
+use crate::models::command_manager::{AuditEvent, AuditSink};
+use crate::repositories::encryption;
+
+/// Environment variable turning the audit log off (`off`)
+pub const AUDIT_LOG_ENV_VAR: &str = "YADA_AUDIT_LOG";
+
+/// Appends command events to an audit log file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditLog {
+    /// The file, or `None` when the log is off
+    path: Option<PathBuf>,
+}
+
+impl AuditLog {
+    /// Creates a log writing to `path`, unless `YADA_AUDIT_LOG` turns it off
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        let off = env::var(AUDIT_LOG_ENV_VAR).is_ok_and(|value| value.trim().eq_ignore_ascii_case("off"));
+        AuditLog { path: (!off).then(|| path.as_ref().to_path_buf()) }
+    }
+
+    /// Creates a log that records nothing
+    pub fn disabled() -> Self {
+        AuditLog { path: None }
+    }
+
+    /// Returns the file written to, or `None` when the log is off
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Appends one event, stamped with the current time
+    ///
+    /// # Arguments
+    /// * `event` - Whether the command was executed or undone
+    /// * `kind` - Type of the command
+    /// * `description` - The command's description
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the line was written, or nothing is to be written
+    /// * `Err(io::Error)` - If the file could not be opened or written
+    pub fn record(&self, event: AuditEvent, kind: &CommandType, description: &str) -> io::Result<()> {
+        self.record_at(Local::now().naive_local(), event, kind, description)
+    }
+
+    /// Appends one event with the given time
+    pub fn record_at(&self, time: NaiveDateTime, event: AuditEvent, kind: &CommandType, description: &str) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if encryption::encrypts_saves() {
+            return Ok(());
+        }
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        let line = format_line(time, event, kind, description);
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        file.write_all(line.as_bytes())
+    }
+}
+
+impl AuditSink for AuditLog {
+    fn record(&self, event: AuditEvent, kind: &CommandType, description: &str) -> Result<(), String> {
+        AuditLog::record(self, event, kind, description).map_err(|e| e.to_string())
+    }
+}
+
+/// Formats one line of the log, ending in a newline
+///
+/// Line breaks in the description become spaces, so every event stays on one line.
+pub fn format_line(time: NaiveDateTime, event: AuditEvent, kind: &CommandType, description: &str) -> String {
+    let description = description.replace(['\r', '\n'], " ");
+    format!("{}|{}|{}|{}\n", time.format("%Y-%m-%dT%H:%M:%S"), event, kind.key(), description)
+}
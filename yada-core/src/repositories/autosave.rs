@@ -0,0 +1,168 @@
+//! # Autosave Policy
+//!
+//! This module decides *when* repositories should be written to disk without
+//! the user explicitly choosing "Save Data". Combined with the dirty flags kept
+//! by each repository, it limits data loss after a crash to the changes made
+//! since the last autosave instead of the whole session.
+//!
+//! ## Policies
+//!
+//! - **AfterEveryCommand**: Persist as soon as any command executes or is undone
+//! - **Interval**: Persist at most once per configured number of minutes
+//! - **Disabled**: Only persist on manual save or clean exit (legacy behavior)
+//!
+//! ## Configuration
+//!
+//! The policy is read from the `YADA_AUTOSAVE` environment variable, or
+//! else the autosave preference in the settings file:
+//! - `command` (default): save after every command
+//! - `<minutes>`: save every N minutes (e.g. `5`)
+//! - `off`: disable autosave
+//!
+//! The interval policy is checked whenever the application regains control
+//! (after a command or when returning to the main menu); no background thread
+//! touches the repositories.
+
+// yada-core/src/repositories/autosave.rs
+use std::env;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Environment variable used to configure the autosave policy
+pub const AUTOSAVE_ENV_VAR: &str = "YADA_AUTOSAVE";
+
+/// When dirty repositories should be written to disk automatically
+#[derive(Debug, Clone, PartialEq)]
+pub enum AutosavePolicy {
+    /// Never save automatically
+    Disabled,
+
+    /// Save after every executed or undone command
+    AfterEveryCommand,
+
+    /// Save when at least this much time has passed since the last save
+    Interval(Duration),
+}
+
+impl AutosavePolicy {
+    /// Parses a policy from its textual configuration form
+    ///
+    /// # Arguments
+    /// * `value` - `command`, `off`, or a positive number of minutes
+    ///
+    /// # Returns
+    /// * `Some(AutosavePolicy)` - The parsed policy
+    /// * `None` - If the value is not recognized
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "command" | "always" => Some(AutosavePolicy::AfterEveryCommand),
+            "off" | "never" | "disabled" => Some(AutosavePolicy::Disabled),
+            minutes => match minutes.parse::<u64>() {
+                Ok(m) if m > 0 => Some(AutosavePolicy::Interval(Duration::from_secs(m * 60))),
+                _ => None,
+            },
+        }
+    }
+
+    /// Reads the policy from the `YADA_AUTOSAVE` environment variable
+    ///
+    /// Falls back to `AfterEveryCommand` when the variable is missing or invalid.
+    pub fn from_env() -> Self {
+        Self::from_env_or(AutosavePolicy::AfterEveryCommand)
+    }
+
+    /// Reads the policy from the `YADA_AUTOSAVE` environment variable
+    ///
+    /// # Arguments
+    /// * `fallback` - Policy used when the variable is missing or invalid,
+    ///   such as the autosave preference
+    pub fn from_env_or(fallback: AutosavePolicy) -> Self {
+        Self::env_override().unwrap_or(fallback)
+    }
+
+    /// Returns the policy set with `YADA_AUTOSAVE`, if it is set to a valid value
+    pub fn env_override() -> Option<Self> {
+        env::var(AUTOSAVE_ENV_VAR)
+            .ok()
+            .and_then(|value| Self::parse(&value))
+    }
+
+    /// Returns the textual configuration form read by `parse`
+    pub fn key(&self) -> String {
+        match self {
+            AutosavePolicy::Disabled => "off".to_string(),
+            AutosavePolicy::AfterEveryCommand => "command".to_string(),
+            AutosavePolicy::Interval(interval) => (interval.as_secs() / 60).to_string(),
+        }
+    }
+}
+
+impl fmt::Display for AutosavePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AutosavePolicy::Disabled => write!(f, "off (save manually or on exit)"),
+            AutosavePolicy::AfterEveryCommand => write!(f, "after every change"),
+            AutosavePolicy::Interval(interval) => write!(f, "every {} minute(s)", interval.as_secs() / 60),
+        }
+    }
+}
+
+/// Events that give the autosaver a chance to persist data
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AutosaveTrigger {
+    /// A command was executed or undone
+    Command,
+
+    /// The application is idle (e.g. back at the main menu)
+    Tick,
+}
+
+/// Tracks the autosave policy and the time of the last save
+pub struct Autosaver {
+    /// Configured policy
+    policy: AutosavePolicy,
+
+    /// When data was last written to disk (manually or automatically)
+    last_save: Instant,
+}
+
+impl Autosaver {
+    /// Creates an autosaver with the given policy
+    pub fn new(policy: AutosavePolicy) -> Self {
+        Autosaver {
+            policy,
+            last_save: Instant::now(),
+        }
+    }
+
+    /// Decides whether dirty repositories should be saved for this trigger
+    ///
+    /// # Arguments
+    /// * `trigger` - The event that just happened
+    ///
+    /// # Returns
+    /// * `true` - Dirty repositories should be saved now
+    /// * `false` - Saving should be deferred
+    pub fn is_due(&self, trigger: AutosaveTrigger) -> bool {
+        match &self.policy {
+            AutosavePolicy::Disabled => false,
+            AutosavePolicy::AfterEveryCommand => trigger == AutosaveTrigger::Command,
+            AutosavePolicy::Interval(interval) => self.last_save.elapsed() >= *interval,
+        }
+    }
+
+    /// Returns the policy in use
+    pub fn policy(&self) -> &AutosavePolicy {
+        &self.policy
+    }
+
+    /// Switches to another policy, such as a changed preference
+    pub fn set_policy(&mut self, policy: AutosavePolicy) {
+        self.policy = policy;
+    }
+
+    /// Records that data was just saved, restarting the interval timer
+    pub fn record_save(&mut self) {
+        self.last_save = Instant::now();
+    }
+}
@@ -0,0 +1,200 @@
+//! # Data Bundles
+//!
+//! A bundle is a single zip archive holding all data files of one user (the
+//! food database, settings, logs, profile, exercise, and meal plans), for backups and
+//! for moving to another computer. `export_bundle` writes one and
+//! `restore_bundle` puts its files back.
+//!
+//! ## Archive Layout
+//!
+//! ```text
+//! manifest.txt      #yada:bundle:v1, CREATED|<time>, FILE|<name>|<kind> per file
+//! foods.txt         the data files, in the format they were saved in
+//! logs.txt
+//! ...
+//! ```
+//!
+//! Files are stored decrypted (see `repositories::encryption`), so a bundle
+//! opens anywhere; restored files are encrypted again if the session
+//! encrypts saves. Restoring checks every file's header before writing
+//! anything, and the replaced files are backed up like any other save.
+
+// yada-core/src/repositories/bundle.rs
+use std::fs;
+use std::io::{self, Cursor, Read, Write};
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::repositories::format::{self, FORMAT_VERSION};
+use crate::repositories::storage;
+
+/// Name of the manifest inside a bundle
+pub const MANIFEST: &str = "manifest.txt";
+
+/// A data file that can be bundled
+#[derive(Debug, Clone, PartialEq)]
+pub struct BundleEntry {
+    /// Name inside the bundle, e.g. `logs.txt`
+    pub name: String,
+
+    /// Header kind of the file, e.g. `logs` (see `format::header`)
+    pub kind: String,
+
+    /// Path of the data file on disk
+    pub path: String,
+}
+
+impl BundleEntry {
+    /// Creates an entry
+    pub fn new(name: &str, kind: &str, path: &str) -> Self {
+        BundleEntry { name: name.to_string(), kind: kind.to_string(), path: path.to_string() }
+    }
+}
+
+/// Contents of a bundle read from disk
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bundle {
+    /// When the bundle was exported, if recorded
+    pub created: Option<DateTime<Utc>>,
+
+    /// Name, kind, and contents of each file, in manifest order
+    pub files: Vec<(String, String, Vec<u8>)>,
+}
+
+impl Bundle {
+    /// Names of the files in the bundle
+    pub fn names(&self) -> Vec<&str> {
+        self.files.iter().map(|(name, _, _)| name.as_str()).collect()
+    }
+}
+
+/// Writes the existing data files into a bundle
+///
+/// # Arguments
+/// * `bundle_path` - Where to write the zip archive (replaced atomically)
+/// * `entries` - Data files to include; missing files are left out
+///
+/// # Returns
+/// * `Ok(Vec<String>)` - Names of the files written into the bundle
+/// * `Err(String)` - If a data file cannot be read or the bundle cannot be written
+pub fn export_bundle(bundle_path: &str, entries: &[BundleEntry]) -> Result<Vec<String>, String> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut manifest = format!("{}\nCREATED|{}\n", format::header("bundle"), Utc::now().to_rfc3339());
+    let mut exported = Vec::new();
+
+    for entry in entries {
+        if !Path::new(&entry.path).exists() {
+            continue;
+        }
+        let contents =
+            storage::read_data_file(&entry.path).map_err(|e| format!("Error reading {}: {}", entry.path, e))?;
+        zip.start_file(entry.name.as_str(), options).map_err(|e| bundle_error(bundle_path, e))?;
+        zip.write_all(&contents).map_err(|e| bundle_error(bundle_path, e))?;
+        manifest.push_str(&format!("FILE|{}|{}\n", format::escape(&entry.name), format::escape(&entry.kind)));
+        exported.push(entry.name.clone());
+    }
+
+    zip.start_file(MANIFEST, options).map_err(|e| bundle_error(bundle_path, e))?;
+    zip.write_all(manifest.as_bytes()).map_err(|e| bundle_error(bundle_path, e))?;
+    let archive = zip.finish().map_err(|e| bundle_error(bundle_path, e))?.into_inner();
+    storage::write_atomic(bundle_path, &archive, 0).map_err(|e| bundle_error(bundle_path, e))?;
+    Ok(exported)
+}
+
+/// Reads and checks a bundle
+///
+/// # Returns
+/// * `Ok(Bundle)` - The bundle's files, each with a header of its kind
+/// * `Err(String)` - If the file is not a YADA bundle, a listed file is
+///   missing, or a file was written by a newer version of YADA
+pub fn read_bundle(bundle_path: &str) -> Result<Bundle, String> {
+    let data = fs::read(bundle_path).map_err(|e| format!("Error reading {}: {}", bundle_path, e))?;
+    let mut archive = ZipArchive::new(Cursor::new(data))
+        .map_err(|e| format!("{} is not a YADA data bundle: {}", bundle_path, e))?;
+    let manifest = read_member(&mut archive, MANIFEST)
+        .map_err(|_| format!("{} is not a YADA data bundle (no {})", bundle_path, MANIFEST))?;
+    let manifest = String::from_utf8_lossy(&manifest).into_owned();
+    match manifest.lines().next().and_then(format::parse_header) {
+        Some((kind, version)) if kind == "bundle" && version <= FORMAT_VERSION => {}
+        Some((kind, version)) if kind == "bundle" => {
+            return Err(format!("{} was written by a newer YADA (bundle format v{})", bundle_path, version));
+        }
+        _ => return Err(format!("{} is not a YADA data bundle (bad manifest)", bundle_path)),
+    }
+
+    let mut bundle = Bundle { created: None, files: Vec::new() };
+    for line in manifest.lines().filter(|line| !format::is_ignored_line(line)) {
+        match format::split_escaped(line, '|').as_slice() {
+            ["CREATED", time] => {
+                bundle.created = DateTime::parse_from_rfc3339(time).ok().map(|time| time.with_timezone(&Utc));
+            }
+            ["FILE", name, kind] => {
+                let (name, kind) = (format::unescape(name), format::unescape(kind));
+                let contents = read_member(&mut archive, &name)
+                    .map_err(|e| format!("{} in {} cannot be read: {}", name, bundle_path, e))?;
+                check_header(&name, &kind, &contents)?;
+                bundle.files.push((name, kind, contents));
+            }
+            _ => {}
+        }
+    }
+    Ok(bundle)
+}
+
+/// Writes a bundle's files over the data files
+///
+/// # Arguments
+/// * `bundle` - Bundle from `read_bundle`
+/// * `entries` - Where each kind of file goes; bundle files of other kinds are ignored
+///
+/// # Returns
+/// * `Ok(Vec<String>)` - Names of the files restored
+/// * `Err(String)` - If a file cannot be written (files restored before it stay restored)
+pub fn restore_bundle(bundle: &Bundle, entries: &[BundleEntry]) -> Result<Vec<String>, String> {
+    let mut restored = Vec::new();
+    for (name, kind, contents) in &bundle.files {
+        let Some(entry) = entries.iter().find(|entry| &entry.kind == kind) else {
+            continue;
+        };
+        if let Some(parent) = Path::new(&entry.path).parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Error writing {}: {}", entry.path, e))?;
+        }
+        storage::write_data_file(&entry.path, contents).map_err(|e| format!("Error writing {}: {}", entry.path, e))?;
+        restored.push(name.clone());
+    }
+    Ok(restored)
+}
+
+/// Reads one file of the archive
+fn read_member(archive: &mut ZipArchive<Cursor<Vec<u8>>>, name: &str) -> io::Result<Vec<u8>> {
+    let mut file = archive.by_name(name).map_err(io::Error::other)?;
+    let mut contents = Vec::new();
+    file.read_to_end(&mut contents)?;
+    Ok(contents)
+}
+
+/// Checks that a bundled file is a data file of its kind that this version can read
+///
+/// Files without a header (written before headers existed) are accepted.
+fn check_header(name: &str, kind: &str, contents: &[u8]) -> Result<(), String> {
+    let text = std::str::from_utf8(contents).map_err(|_| format!("{} in the bundle is not a text file", name))?;
+    let first = text.lines().find(|line| !line.trim().is_empty()).unwrap_or("");
+    match format::parse_header(first) {
+        Some((found, _)) if found != kind => {
+            Err(format!("{} in the bundle holds {} data, expected {}", name, found, kind))
+        }
+        Some((_, version)) if version > FORMAT_VERSION => {
+            Err(format!("{} in the bundle was written by a newer YADA (format v{})", name, version))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Formats an error reading or writing a bundle
+fn bundle_error(bundle_path: &str, error: impl std::fmt::Display) -> String {
+    format!("Error writing {}: {}", bundle_path, error)
+}
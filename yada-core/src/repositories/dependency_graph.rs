@@ -0,0 +1,128 @@
+//! # Food Dependency Graph
+//!
+//! Composite foods store their components, not their nutrition: calories,
+//! macros, weight, and cost are calculated from the components (see
+//! `Food::calculate_from_components`). When a food changes, every recipe
+//! built from it, directly or through other recipes, has to be calculated
+//! again. The graph maps each food to the composite foods using it, so those
+//! dependents are found without looking at every food.
+//!
+//! ## Recalculation Order
+//!
+//! A recipe can only be calculated once its components are current, so
+//! dependents are returned in recalculation order: each food after every
+//! food in the set it is built from. Foods in a cycle (which the menus
+//! never create) come last, ordered by ID.
+//!
+//! ## Maintenance
+//!
+//! `FoodRepository` updates the graph when foods are added, updated, or
+//! removed, and rebuilds it on load and after changes made through
+//! `get_foods_mut`.
+
+// yada-core/src/repositories/dependency_graph.rs
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::models::food::Food;
+
+/// Composite foods by the foods they are built from
+#[derive(Debug, Clone, Default)]
+pub struct DependencyGraph {
+    /// IDs of the composite foods listing each food as a component
+    dependents: HashMap<String, HashSet<String>>,
+}
+
+impl DependencyGraph {
+    /// Builds the graph of a collection of foods
+    pub fn build<'a>(foods: impl IntoIterator<Item = &'a Food>) -> Self {
+        let mut graph = DependencyGraph::default();
+        for food in foods {
+            graph.insert(food);
+        }
+        graph
+    }
+
+    /// Adds the edges from a food's components to the food
+    pub fn insert(&mut self, food: &Food) {
+        for (component, _) in &food.components {
+            self.dependents.entry(component.clone()).or_default().insert(food.id.clone());
+        }
+    }
+
+    /// Removes the edges of a food, as it was when inserted
+    pub fn remove(&mut self, food: &Food) {
+        for (component, _) in &food.components {
+            if let Some(ids) = self.dependents.get_mut(component) {
+                ids.remove(&food.id);
+                if ids.is_empty() {
+                    self.dependents.remove(component);
+                }
+            }
+        }
+    }
+
+    /// Returns the composite foods listing a food as a component
+    pub fn direct_dependents(&self, id: &str) -> impl Iterator<Item = &String> {
+        self.dependents.get(id).into_iter().flatten()
+    }
+
+    /// Returns every composite food built from a food, directly or through
+    /// other composite foods
+    ///
+    /// # Returns
+    /// The dependents in recalculation order, without the food itself;
+    /// empty if no recipe uses the food
+    pub fn dependents(&self, id: &str) -> Vec<String> {
+        let mut found: HashSet<String> = HashSet::new();
+        let mut pending: Vec<&String> = self.direct_dependents(id).collect();
+        while let Some(dependent) = pending.pop() {
+            if dependent != id && found.insert(dependent.clone()) {
+                pending.extend(self.direct_dependents(dependent));
+            }
+        }
+        self.order(&found)
+    }
+
+    /// Orders foods so that each comes after the foods in the set it is built from
+    ///
+    /// # Arguments
+    /// * `ids` - IDs of the foods to order
+    ///
+    /// # Returns
+    /// The IDs in recalculation order; ties and foods in a cycle by ID
+    pub fn order(&self, ids: &HashSet<String>) -> Vec<String> {
+        // Number of components of each food that are in the set and not ordered yet
+        let mut waiting: HashMap<&String, usize> = ids.iter().map(|id| (id, 0)).collect();
+        for id in ids {
+            for dependent in self.direct_dependents(id) {
+                if let Some(count) = waiting.get_mut(dependent) {
+                    *count += 1;
+                }
+            }
+        }
+
+        let mut ready: BTreeSet<&String> = waiting.iter()
+            .filter(|(_, count)| **count == 0)
+            .map(|(id, _)| *id)
+            .collect();
+        let mut ordered: Vec<String> = Vec::with_capacity(ids.len());
+        while let Some(id) = ready.pop_first() {
+            waiting.remove(id);
+            for dependent in self.direct_dependents(id) {
+                if let Some(count) = waiting.get_mut(dependent) {
+                    *count -= 1;
+                    if *count == 0 {
+                        ready.insert(dependent);
+                    }
+                }
+            }
+            ordered.push(id.clone());
+        }
+
+        // Foods in a cycle never become ready
+        let mut cyclic: Vec<String> = waiting.into_keys().cloned().collect();
+        cyclic.sort();
+        ordered.extend(cyclic);
+        ordered
+    }
+}
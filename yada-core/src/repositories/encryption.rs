@@ -0,0 +1,220 @@
+//! # Encrypted Storage
+//!
+//! Profiles, weights, and food logs are personal health data. On a shared
+//! machine they can be stored encrypted instead of as plain text: once a
+//! password is given with `unlock`, every repository save goes through
+//! `encrypt`, and every load through `decrypt` (both called by
+//! `storage::write_data_file` and `storage::read_data_file`).
+//!
+//! ## File Layout
+//!
+//! ```text
+//! #yada:encrypted:v1\n | salt (16 bytes) | nonce (12 bytes) | ciphertext and tag
+//! ```
+//!
+//! The key is derived from the password and the salt with Argon2id, and the
+//! contents are sealed with ChaCha20-Poly1305, so a wrong password or a
+//! damaged file is detected instead of producing garbage. Plain files keep
+//! loading while encryption is on and are encrypted when next saved.
+//!
+//! ## Scope
+//!
+//! The password applies to the current thread, like the console (see
+//! `App::with_console`), so sessions on other threads keep their own
+//! setting. Backups (`.bak.N`) are copies of the encrypted files; exported
+//! reports are written as plain text.
+
+// yada-core/src/repositories/encryption.rs
+use std::cell::RefCell;
+use std::env;
+use std::io;
+
+use argon2::Argon2;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+
+/// Environment variable turning encryption on or off (`on`/`off`)
+pub const ENCRYPTION_ENV_VAR: &str = "YADA_ENCRYPT";
+
+/// Environment variable providing the password without asking
+pub const PASSWORD_ENV_VAR: &str = "YADA_PASSWORD";
+
+/// First line of every encrypted file
+const MAGIC: &[u8] = b"#yada:encrypted:v1\n";
+
+/// Length of the key derivation salt
+const SALT_LEN: usize = 16;
+
+/// Length of the ChaCha20-Poly1305 nonce
+const NONCE_LEN: usize = 12;
+
+/// The password of the current thread's session and the keys derived from it
+struct Session {
+    password: String,
+
+    /// Whether saves are encrypted (files are decrypted either way)
+    encrypt_saves: bool,
+
+    /// Salt used for files written in this session
+    salt: [u8; SALT_LEN],
+
+    /// Keys derived so far, by salt (derivation is deliberately slow)
+    keys: Vec<([u8; SALT_LEN], [u8; 32])>,
+}
+
+impl Session {
+    /// Returns the key for a salt, deriving it on first use
+    fn key(&mut self, salt: &[u8; SALT_LEN]) -> io::Result<[u8; 32]> {
+        if let Some((_, key)) = self.keys.iter().find(|(known, _)| known == salt) {
+            return Ok(*key);
+        }
+        let key = derive_key(&self.password, salt)?;
+        self.keys.push((*salt, key));
+        Ok(key)
+    }
+}
+
+thread_local! {
+    static SESSION: RefCell<Option<Session>> = const { RefCell::new(None) };
+}
+
+/// Reads whether encryption is wanted from `YADA_ENCRYPT`
+///
+/// # Returns
+/// `Some(true)` for `on`, `Some(false)` for `off`, and `None` when unset,
+/// in which case encryption stays on exactly if the files are already encrypted
+pub fn setting_from_env() -> Option<bool> {
+    match env::var(ENCRYPTION_ENV_VAR).ok()?.trim().to_lowercase().as_str() {
+        "on" | "true" | "1" => Some(true),
+        "off" | "false" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// Reads the password from `YADA_PASSWORD`, if set
+pub fn password_from_env() -> Option<String> {
+    env::var(PASSWORD_ENV_VAR).ok().filter(|password| !password.is_empty())
+}
+
+/// Gives the current thread the password for its data files
+///
+/// # Arguments
+/// * `password` - Password the files are (or will be) encrypted with
+/// * `encrypt_saves` - Whether saves are encrypted; with `false`, encrypted
+///   files can still be read and are saved as plain text
+pub fn unlock(password: &str, encrypt_saves: bool) {
+    let mut salt = [0; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let session = Session { password: password.to_string(), encrypt_saves, salt, keys: Vec::new() };
+    SESSION.with(|current| *current.borrow_mut() = Some(session));
+}
+
+/// Forgets the current thread's password; saves are plain text again
+pub fn lock() {
+    SESSION.with(|current| *current.borrow_mut() = None);
+}
+
+/// Whether saves on the current thread are encrypted
+pub fn encrypts_saves() -> bool {
+    SESSION.with(|current| current.borrow().as_ref().is_some_and(|session| session.encrypt_saves))
+}
+
+/// Whether file contents are encrypted
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+/// Checks a password against encrypted contents without unlocking
+///
+/// # Returns
+/// * `Ok(true)` - The password decrypts the contents
+/// * `Ok(false)` - Wrong password, or the contents are damaged
+/// * `Err(io::Error)` - The contents are not encrypted, or the key cannot be derived
+pub fn check_password(data: &[u8], password: &str) -> io::Result<bool> {
+    let (salt, nonce, sealed) = split(data)?;
+    let key = derive_key(password, &salt)?;
+    Ok(open(&key, &salt, nonce, sealed).is_ok())
+}
+
+/// Encrypts file contents when the current thread encrypts saves
+///
+/// # Returns
+/// The encrypted contents, or the contents unchanged when encryption is off
+pub fn encrypt(plain: &[u8]) -> io::Result<Vec<u8>> {
+    SESSION.with(|current| {
+        let mut current = current.borrow_mut();
+        let Some(session) = current.as_mut().filter(|session| session.encrypt_saves) else {
+            return Ok(plain.to_vec());
+        };
+        let salt = session.salt;
+        let key = session.key(&salt)?;
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let sealed = ChaCha20Poly1305::new(Key::from_slice(&key))
+            .encrypt(&nonce, Payload { msg: plain, aad: &associated_data(&salt) })
+            .map_err(|_| io::Error::other("encryption failed"))?;
+
+        let mut data = Vec::with_capacity(MAGIC.len() + SALT_LEN + NONCE_LEN + sealed.len());
+        data.extend_from_slice(MAGIC);
+        data.extend_from_slice(&salt);
+        data.extend_from_slice(&nonce);
+        data.extend_from_slice(&sealed);
+        Ok(data)
+    })
+}
+
+/// Decrypts file contents; plain contents are returned unchanged
+///
+/// # Returns
+/// * `Ok(Vec<u8>)` - The plain contents
+/// * `Err(io::Error)` - `PermissionDenied` if the contents are encrypted and
+///   no password was given, `InvalidData` for a wrong password or damaged file
+pub fn decrypt(data: &[u8]) -> io::Result<Vec<u8>> {
+    if !is_encrypted(data) {
+        return Ok(data.to_vec());
+    }
+    let (salt, nonce, sealed) = split(data)?;
+    SESSION.with(|current| {
+        let mut current = current.borrow_mut();
+        let Some(session) = current.as_mut() else {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!("the file is encrypted; a password is needed (set {})", PASSWORD_ENV_VAR),
+            ));
+        };
+        let key = session.key(&salt)?;
+        open(&key, &salt, nonce, sealed)
+    })
+}
+
+/// Splits encrypted contents into salt, nonce, and sealed data
+fn split(data: &[u8]) -> io::Result<([u8; SALT_LEN], &[u8], &[u8])> {
+    let body = data
+        .strip_prefix(MAGIC)
+        .filter(|body| body.len() >= SALT_LEN + NONCE_LEN)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "not an encrypted YADA file"))?;
+    let (salt, rest) = body.split_at(SALT_LEN);
+    let (nonce, sealed) = rest.split_at(NONCE_LEN);
+    Ok((salt.try_into().expect("salt length checked above"), nonce, sealed))
+}
+
+/// Decrypts and authenticates sealed data
+fn open(key: &[u8; 32], salt: &[u8; SALT_LEN], nonce: &[u8], sealed: &[u8]) -> io::Result<Vec<u8>> {
+    ChaCha20Poly1305::new(Key::from_slice(key))
+        .decrypt(Nonce::from_slice(nonce), Payload { msg: sealed, aad: &associated_data(salt) })
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "wrong password or damaged encrypted file"))
+}
+
+/// Header bytes authenticated along with the contents
+fn associated_data(salt: &[u8; SALT_LEN]) -> Vec<u8> {
+    [MAGIC, salt.as_slice()].concat()
+}
+
+/// Derives a 256-bit key from a password with Argon2id
+fn derive_key(password: &str, salt: &[u8; SALT_LEN]) -> io::Result<[u8; 32]> {
+    let mut key = [0; 32];
+    Argon2::default()
+        .hash_password_into(password.as_bytes(), salt, &mut key)
+        .map_err(|e| io::Error::other(format!("key derivation failed: {}", e)))?;
+    Ok(key)
+}
@@ -0,0 +1,201 @@
+//! # Exercise Repository
+//!
+//! This module implements the Repository Pattern for exercise entries. Like
+//! the food log, entries are organized by date and kept in memory; the file
+//! is rewritten atomically on save.
+//!
+//! ## File Format Specification
+//!
+//! ```text
+//! YYYY-MM-DD|activity|minutes|met|calories_burned|YYYY-MM-DDTHH:MM:SS
+//! ```
+//!
+//! The first line is the format header `#yada:exercise:v1`, entries are
+//! written in date order, and activity names are backslash-escaped (see
+//! `repositories::format`).
+
+// yada-core/src/repositories/exercise_repository.rs
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::path::Path;
+use chrono::{DateTime, Local, NaiveDate};
+
+use crate::models::exercise::ExerciseEntry;
+use crate::repositories::{format, schema, storage};
+use crate::repositories::load_report::LoadReport;
+
+/// Repository of exercise entries organized by date
+pub struct ExerciseRepository {
+    /// Entries for each date, in the order they were logged
+    entries: HashMap<NaiveDate, Vec<ExerciseEntry>>,
+    /// File system path for persistent storage
+    file_path: String,
+    /// Whether in-memory data has changed since the last save or load
+    dirty: bool,
+    /// Lines skipped by the last load
+    load_report: LoadReport,
+}
+
+impl ExerciseRepository {
+    /// Creates a repository and loads existing entries if the file exists
+    ///
+    /// # Arguments
+    /// * `file_path` - Path to the file where exercise data is stored
+    ///
+    /// # Returns
+    /// * `Result<Self, io::Error>` - A new repository instance or an IO error if loading fails
+    pub fn new(file_path: &str) -> Result<Self, io::Error> {
+        let mut repo = ExerciseRepository {
+            entries: HashMap::new(),
+            file_path: file_path.to_string(),
+            dirty: false,
+            load_report: LoadReport::new(),
+        };
+
+        if Path::new(file_path).exists() {
+            repo.load()?;
+        }
+        Ok(repo)
+    }
+
+    /// Returns the entries logged on a date (empty if there are none)
+    pub fn get_entries(&self, date: NaiveDate) -> &[ExerciseEntry] {
+        self.entries.get(&date).map(|entries| entries.as_slice()).unwrap_or(&[])
+    }
+
+    /// Returns the total calories burned on a date
+    pub fn calories_burned(&self, date: NaiveDate) -> f64 {
+        self.get_entries(date).iter().map(|entry| entry.calories_burned).sum()
+    }
+
+    /// Adds an entry to a date
+    pub fn add_entry(&mut self, date: NaiveDate, entry: ExerciseEntry) {
+        self.entries.entry(date).or_default().push(entry);
+        self.dirty = true;
+    }
+
+    /// Inserts an entry at a position within a date (used to undo a removal)
+    ///
+    /// Positions past the end append the entry.
+    pub fn insert_entry(&mut self, date: NaiveDate, index: usize, entry: ExerciseEntry) {
+        let entries = self.entries.entry(date).or_default();
+        entries.insert(index.min(entries.len()), entry);
+        self.dirty = true;
+    }
+
+    /// Removes an entry from a date
+    ///
+    /// # Returns
+    /// * `Some(ExerciseEntry)` - The removed entry
+    /// * `None` - If there is no entry at `index` on that date
+    pub fn remove_entry(&mut self, date: NaiveDate, index: usize) -> Option<ExerciseEntry> {
+        let entries = self.entries.get_mut(&date)?;
+        if index >= entries.len() {
+            return None;
+        }
+        let entry = entries.remove(index);
+        if entries.is_empty() {
+            self.entries.remove(&date);
+        }
+        self.dirty = true;
+        Some(entry)
+    }
+
+    /// Persists all entries in date order
+    ///
+    /// # Returns
+    /// * `Result<(), io::Error>` - Success confirmation or IO error details
+    ///
+    /// The file is replaced atomically with rotating `.bak.N` backups
+    /// (see `storage::write_atomic`).
+    pub fn save(&mut self) -> Result<(), io::Error> {
+        let mut buffer = Vec::new();
+        writeln!(buffer, "{}", format::header("exercise"))?;
+
+        let mut dates: Vec<&NaiveDate> = self.entries.keys().collect();
+        dates.sort();
+        for date in dates {
+            for entry in &self.entries[date] {
+                writeln!(
+                    buffer,
+                    "{}|{}|{}|{}|{}|{}",
+                    date.format("%Y-%m-%d"),
+                    format::escape(&entry.activity),
+                    entry.minutes,
+                    entry.met,
+                    entry.calories_burned,
+                    entry.timestamp.format("%Y-%m-%dT%H:%M:%S")
+                )?;
+            }
+        }
+
+        storage::write_data_file(&self.file_path, &buffer)?;
+
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Loads all entries from the configured file, skipping malformed lines
+    ///
+    /// # Returns
+    /// * `Result<(), io::Error>` - Success confirmation or IO error details
+    pub fn load(&mut self) -> Result<(), io::Error> {
+        let contents = storage::read_data_file(&self.file_path)?;
+        let reader = contents.as_slice();
+        self.entries.clear();
+        let mut report = LoadReport::new();
+
+        for record in schema::records("exercise", reader) {
+            let (number, line) = record?;
+            let parts: Vec<&str> = format::split_escaped(&line, '|');
+            match parse_entry(&parts) {
+                Some((date, entry)) => self.entries.entry(date).or_default().push(entry),
+                None => report.skip(number, "not a valid exercise entry"),
+            }
+        }
+        self.load_report = report;
+
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Reports whether there are changes that have not been saved yet
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Returns how many malformed lines the last `load()` skipped
+    pub fn skipped_line_count(&self) -> usize {
+        self.load_report.skipped_count()
+    }
+
+    /// Returns what the last `load()` skipped, with line numbers
+    pub fn load_report(&self) -> &LoadReport {
+        &self.load_report
+    }
+}
+
+/// Parses the fields of one entry line
+///
+/// # Returns
+/// * `Some((date, entry))` - The entry and the date it belongs to
+/// * `None` - If a field is missing or not a valid value
+fn parse_entry(parts: &[&str]) -> Option<(NaiveDate, ExerciseEntry)> {
+    if parts.len() < 6 {
+        return None;
+    }
+    let date = NaiveDate::parse_from_str(parts[0], "%Y-%m-%d").ok()?;
+    let number = |field: &str| field.parse::<f64>().ok().filter(|value| *value >= 0.0);
+    let timestamp = DateTime::parse_from_str(&format!("{}+00:00", parts[5]), "%Y-%m-%dT%H:%M:%S%z")
+        .map(|time| time.with_timezone(&Local))
+        .unwrap_or_else(|_| Local::now());
+
+    let entry = ExerciseEntry {
+        activity: format::unescape(parts[1]),
+        minutes: number(parts[2])?,
+        met: number(parts[3])?,
+        calories_burned: number(parts[4])?,
+        timestamp,
+    };
+    Some((date, entry))
+}
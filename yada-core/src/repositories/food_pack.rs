@@ -0,0 +1,259 @@
+//! # Food Packs
+//!
+//! A food pack is a curated file of branded foods, such as a restaurant
+//! chain's menu (`mcdonalds.yadapack`), that can be installed into the food
+//! database in one go and removed again later.
+//!
+//! ## File Format
+//!
+//! ```text
+//! #yada:pack:v1
+//! PACK|mcd|McDonald's|2024-05
+//! B|bigmac|Big Mac|burger,mcdonalds|563|protein=26|carbs=45|fat=33|grams=219
+//! B|fries_medium|Medium Fries|fries,mcdonalds|320|protein=5|carbs=43|fat=15
+//! C|bigmac_meal|Big Mac Meal|meal,mcdonalds|bigmac:1,fries_medium:1
+//! ```
+//!
+//! After the header, the `PACK` line gives the pack's name (lowercase
+//! letters, digits, and `_`), a title, and optionally a version. The other
+//! lines use the format of `foods.txt` (see `food_repository`), including
+//! its optional attributes; `last_used` and `archived` are ignored.
+//!
+//! ## Namespacing
+//!
+//! Every food ID is prefixed with the pack's name (`bigmac` becomes
+//! `mcd_bigmac`; IDs that already carry the prefix are kept), so packs
+//! cannot collide with each other or with the user's own foods. Components
+//! of composite foods are renamed the same way and must be foods of the
+//! same pack. Installed foods remember their pack in the `pack` attribute.
+//!
+//! ## Installing and Uninstalling
+//!
+//! Installing a pack again (for example a newer version) updates its foods
+//! and adds new ones. A food whose namespaced ID is taken by a food of
+//! another pack or of the user is skipped and reported.
+//!
+//! Uninstalling removes the pack's foods, except those still in use: logged
+//! or planned (the caller names those foods), or a component of a food that
+//! stays. Foods kept this way still belong to the pack, so uninstalling
+//! again once they are no longer used removes them.
+
+// yada-core/src/repositories/food_pack.rs
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use crate::models::food::{Food, FoodType};
+use crate::repositories::food_repository::{self, FoodRepository};
+use crate::repositories::format;
+use crate::repositories::load_report::LoadReport;
+
+/// File extension of food packs
+pub const EXTENSION: &str = "yadapack";
+
+/// File kind in the header of a pack
+const PACK_KIND: &str = "pack";
+
+/// A food pack read from a file
+#[derive(Debug, Clone)]
+pub struct FoodPack {
+    /// Short name, also the prefix of the pack's food IDs
+    pub name: String,
+
+    /// Title such as `McDonald's`
+    pub title: String,
+
+    /// Version given by the pack's author, if any
+    pub version: Option<String>,
+
+    /// The pack's foods, with namespaced IDs and the pack set
+    pub foods: Vec<Food>,
+}
+
+/// What installing a pack changed
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InstallReport {
+    /// Foods that were new
+    pub added: usize,
+
+    /// Foods of an earlier installation that were replaced
+    pub updated: usize,
+
+    /// IDs that were taken by a food that is not part of the pack
+    pub conflicts: Vec<String>,
+}
+
+/// What uninstalling a pack changed
+#[derive(Debug, Clone, Default)]
+pub struct UninstallReport {
+    /// Foods that were removed
+    pub removed: Vec<Food>,
+
+    /// IDs of the pack's foods kept because they are still in use
+    pub kept: Vec<String>,
+}
+
+impl FoodPack {
+    /// Reads a pack file
+    ///
+    /// # Returns
+    /// * `Ok(FoodPack)` - The pack
+    /// * `Err(String)` - If the file cannot be read or is not a valid pack
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("Error reading {}: {}", path.display(), e))?;
+        FoodPack::parse(&text).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// Parses the text of a pack file
+    ///
+    /// Unlike loading `foods.txt`, nothing is skipped: any line that cannot
+    /// be read makes the whole pack invalid, so a pack is never installed
+    /// half-way.
+    ///
+    /// # Returns
+    /// * `Ok(FoodPack)` - The pack
+    /// * `Err(String)` - What is wrong with the first problems found
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut lines = text.lines().enumerate().map(|(index, line)| (index + 1, line));
+        match lines.next().and_then(|(_, line)| format::parse_header(line)) {
+            Some((kind, _)) if kind == PACK_KIND => {}
+            _ => return Err(format!("not a food pack (the first line must be {})", format::header(PACK_KIND))),
+        }
+
+        let mut header: Option<(String, String, Option<String>)> = None;
+        let mut foods = Vec::new();
+        let mut report = LoadReport::new();
+        for (number, line) in lines.filter(|(_, line)| !format::is_ignored_line(line)) {
+            let parts = format::split_escaped(line, '|');
+            if parts[0] == "PACK" {
+                if header.is_some() {
+                    return Err(format!("line {}: a second PACK line", number));
+                }
+                header = Some(parse_pack_line(&parts).map_err(|e| format!("line {}: {}", number, e))?);
+            } else if let Some(food) = food_repository::parse_food_line(&parts, number, &mut report) {
+                foods.push(food);
+            }
+        }
+        if !report.is_clean() {
+            return Err(report.describe().join("; "));
+        }
+        let Some((name, title, version)) = header else {
+            return Err("the PACK line with the pack's name is missing".to_string());
+        };
+
+        let foods = namespace(&name, foods)?;
+        Ok(FoodPack { name, title, version, foods })
+    }
+
+    /// Adds the pack's foods to a food database, replacing those of an earlier installation
+    ///
+    /// Composite foods are recalculated afterwards. Nothing is saved.
+    pub fn install(&self, food_repo: &mut FoodRepository) -> InstallReport {
+        let mut report = InstallReport::default();
+        for food in &self.foods {
+            let mut food = food.clone();
+            let result = match food_repo.get_food(&food.id) {
+                Some(existing) if existing.pack.as_deref() != Some(self.name.as_str()) => {
+                    report.conflicts.push(food.id);
+                    continue;
+                }
+                Some(existing) => {
+                    // Usage belongs to the user, not to the pack
+                    (food.last_used, food.archived) = (existing.last_used, existing.archived);
+                    food_repo.update_food(food.clone()).map(|_| report.updated += 1)
+                }
+                None => food_repo.add_food(food.clone()).map(|_| report.added += 1),
+            };
+            if result.is_err() {
+                report.conflicts.push(food.id.clone());
+            }
+        }
+        food_repo.recalculate_composites();
+        report
+    }
+}
+
+/// Removes the foods of an installed pack that are not in use
+///
+/// # Arguments
+/// * `food_repo` - Food database
+/// * `name` - Name of the pack
+/// * `in_use` - IDs of foods that are logged or planned
+///
+/// # Returns
+/// * `Ok(UninstallReport)` - The removed and kept foods
+/// * `Err(String)` - If no food of the pack is installed
+pub fn uninstall(food_repo: &mut FoodRepository, name: &str, in_use: &HashSet<String>) -> Result<UninstallReport, String> {
+    let foods = food_repo.get_foods();
+    let pack_ids: HashSet<&String> = foods.values()
+        .filter(|food| food.pack.as_deref() == Some(name))
+        .map(|food| &food.id)
+        .collect();
+    if pack_ids.is_empty() {
+        return Err(format!("No food pack named '{}' is installed.", name));
+    }
+
+    // Start with the foods in use and the components of foods outside the
+    // pack, then keep the components of every kept composite as well
+    let mut kept: HashSet<&String> = pack_ids.iter().copied().filter(|id| in_use.contains(*id)).collect();
+    kept.extend(foods.values()
+        .filter(|food| !pack_ids.contains(&food.id))
+        .flat_map(|food| food.components.iter().map(|(id, _)| id))
+        .filter(|id| pack_ids.contains(id)));
+    let mut pending: Vec<&String> = kept.iter().copied().collect();
+    while let Some(id) = pending.pop() {
+        for (component, _) in &foods[id].components {
+            if pack_ids.contains(component) && kept.insert(component) {
+                pending.push(component);
+            }
+        }
+    }
+
+    let mut removed_ids: Vec<String> = pack_ids.iter().filter(|id| !kept.contains(*id)).map(|id| id.to_string()).collect();
+    removed_ids.sort();
+    let mut kept: Vec<String> = kept.into_iter().cloned().collect();
+    kept.sort();
+    let removed = removed_ids.iter().filter_map(|id| food_repo.remove_food(id)).collect();
+    Ok(UninstallReport { removed, kept })
+}
+
+/// Names of the installed packs and how many foods each has, sorted by name
+pub fn installed_packs(food_repo: &FoodRepository) -> Vec<(String, usize)> {
+    let mut packs: BTreeMap<String, usize> = BTreeMap::new();
+    for pack in food_repo.get_foods().values().filter_map(|food| food.pack.as_ref()) {
+        *packs.entry(pack.clone()).or_default() += 1;
+    }
+    packs.into_iter().collect()
+}
+
+/// Reads `PACK|name|title[|version]`
+fn parse_pack_line(parts: &[&str]) -> Result<(String, String, Option<String>), String> {
+    let field = |index: usize| parts.get(index).map(|field| format::unescape(field).trim().to_string()).filter(|field| !field.is_empty());
+    let name = field(1).ok_or("the pack has no name")?;
+    if !name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_') {
+        return Err(format!("invalid pack name '{}' (use lowercase letters, digits, and _)", name));
+    }
+    let title = field(2).unwrap_or_else(|| name.clone());
+    Ok((name, title, field(3)))
+}
+
+/// Prefixes the IDs of a pack's foods and their components with the pack's name
+fn namespace(name: &str, foods: Vec<Food>) -> Result<Vec<Food>, String> {
+    let prefix = format!("{}_", name);
+    let prefixed = |id: &str| if id.starts_with(&prefix) { id.to_string() } else { format!("{}{}", prefix, id) };
+    let ids: HashMap<String, String> = foods.iter().map(|food| (food.id.clone(), prefixed(&food.id))).collect();
+
+    foods.into_iter().map(|mut food| {
+        if food.food_type == FoodType::Composite {
+            for (component, _) in &mut food.components {
+                *component = ids.get(component.as_str()).cloned()
+                    .ok_or_else(|| format!("{} uses '{}', which is not in the pack", food.id, component))?;
+            }
+        }
+        food.id = prefixed(&food.id);
+        food.pack = Some(name.to_string());
+        food.last_used = None;
+        food.archived = false;
+        Ok(food)
+    }).collect()
+}
@@ -0,0 +1,1197 @@
+//! # Food Repository
+//! 
+//! This module implements the data access layer for the food database using the Repository Pattern.
+//! It provides a clean interface for managing food data persistence while supporting both basic 
+//! and composite foods through the Composite Pattern.
+//! 
+//! ## Repository Pattern Implementation
+//! 
+//! The `FoodRepository` abstracts all food-related data operations:
+//! - **CRUD Operations**: Add, update, retrieve, and manage food entities
+//! - **Search Functionality**: Keyword-based searching with AND/OR logic and nutrient filters
+//! - **File Persistence**: Save and load operations for durable storage
+//! - **Composite Food Support**: Handles recursive calorie calculations for recipes
+//! - **Error Management**: Comprehensive error handling for all operations
+//! 
+//! ## File Format Specification
+//! 
+//! The repository uses a pipe-delimited text format for data storage:
+//! 
+//! ### Basic Foods
+//! ```text
+//! B|food_id|food_name|keyword1,keyword2,keyword3|calories_per_serving
+//! ```
+//! 
+//! ### Composite Foods
+//! ```text
+//! C|food_id|food_name|keyword1,keyword2,keyword3|component1:servings1,component2:servings2
+//! ```
+//! 
+//! ### Header and Escaping
+//! The first line is the format header `#yada:foods:v1`. Foods are written
+//! sorted by ID with sorted keywords, and delimiter characters inside text
+//! fields are backslash-escaped (see `repositories::format`). Text containing
+//! control characters is rejected by `add_food` and `update_food`.
+//! 
+//! ### Optional Attributes
+//! Any number of `key=value` fields may follow the fixed columns. Files
+//! written by older versions simply have none, and unknown keys are ignored.
+//! ```text
+//! B|eggs|Eggs (1 large)|eggs,protein|70|protein=6|carbs=0.6|fat=5
+//! ```
+//! 
+//! Supported keys:
+//! - `protein`, `carbs`, `fat`: Macronutrients per serving in grams (basic foods only;
+//!   composite foods derive them from their components)
+//! - `fiber`: Dietary fiber per serving in grams (basic foods only; omitted when unknown)
+//! - `alcohol`, `caffeine`: Alcohol in grams and caffeine in milligrams per serving
+//!   (basic foods only; omitted when unknown)
+//! - `grams`: Weight of one serving in grams (basic foods only)
+//! - `cost`: Price of one serving (basic foods only)
+//! - `serving_size`, `serving_unit`: What one serving is, e.g. `1` and `cup`
+//!   (basic foods only; see `models::serving`)
+//! - `category`: Food group such as `Dairy`
+//! - `tags`: Comma-separated structured tags such as `vegan,contains-gluten`
+//! - `last_used`: Most recent date the food was logged (`YYYY-MM-DD`)
+//! - `archived`: `true` for foods hidden from pickers (see `models::usage`)
+//! - `pack`: Name of the food pack the food was installed from (see `repositories::food_pack`)
+//! - `default_servings`: Servings offered when logging the food, if not 1
+//! 
+//! Keywords derived from `category` and `tags` are not stored; they are
+//! recomputed on load (see `models::derived_keywords`).
+//! 
+//! ## Data Integrity Features
+//! 
+//! - **Duplicate Prevention**: Enforces unique food IDs across the database
+//! - **Dependency Management**: Validates composite food components exist
+//! - **Recursive Calculation**: Automatically updates composite food calories
+//! - **Error Recovery**: Graceful handling of malformed data entries
+//! - **Consistency Checks**: Ensures data integrity during load operations
+
+// yada-core/src/repositories/food_repository.rs
+use std::cell::OnceCell;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::io::{self, Write};
+use std::path::Path;
+
+use chrono::NaiveDate;
+
+use crate::repositories::{format, schema, storage};
+use crate::repositories::load_report::LoadReport;
+use crate::repositories::search_index::SearchIndex;
+use crate::repositories::dependency_graph::DependencyGraph;
+use crate::models::derived_keywords::KeywordDerivation;
+use crate::models::food::{Food, FoodType, Macros};
+use crate::models::nutrient_filter::{self, NutrientFilter};
+use crate::models::keyword_suggestion;
+use crate::models::search::{self, SearchHit};
+use crate::models::serving::{ServingSize, ServingUnit};
+use crate::models::usage::{self, FoodUsage};
+
+/// Order in which `FoodRepository::get_foods_sorted` lists foods
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FoodSort {
+    /// Alphabetically by name, ignoring case
+    #[default]
+    Name,
+
+    /// Lowest calories per serving first
+    Calories,
+
+    /// Alphabetically by ID
+    Id,
+}
+
+impl fmt::Display for FoodSort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FoodSort::Name => write!(f, "name"),
+            FoodSort::Calories => write!(f, "calories"),
+            FoodSort::Id => write!(f, "ID"),
+        }
+    }
+}
+
+/// Date format of the `last_used` attribute
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// # Food Repository
+/// 
+/// A Repository Pattern implementation that manages the persistent storage and retrieval 
+/// of food data. This repository supports both basic and composite foods, providing a 
+/// clean interface for all food-related data operations.
+/// 
+/// ## Core Responsibilities
+/// 
+/// - **Data Management**: Add, update, and retrieve food entities with validation
+/// - **Search Operations**: Keyword-based food discovery with flexible matching
+/// - **File Persistence**: Durable storage using human-readable text format
+/// - **Composite Food Support**: Automatic calorie calculation for recipes
+/// - **Data Integrity**: Ensures consistency and validates food dependencies
+/// 
+/// ## Storage Format
+/// 
+/// The repository uses a pipe-delimited format optimized for both human readability 
+/// and programmatic parsing, supporting the full spectrum of food types in the system.
+pub struct FoodRepository {
+    /// In-memory cache of all foods, indexed by unique food ID for O(1) access
+    foods: HashMap<String, Food>,
+    /// File system path for persistent storage of food data
+    file_path: String,
+    /// Whether in-memory data has changed since the last save or load
+    dirty: bool,
+    /// Which metadata is turned into derived search keywords
+    keyword_derivation: KeywordDerivation,
+    /// Lines skipped and values defaulted by the last load
+    load_report: LoadReport,
+    /// Inverted search index; built by the first search after a load, and
+    /// emptied when foods are changed through `get_foods_mut` or their
+    /// derived keywords change, to be rebuilt by the next search
+    index: OnceCell<SearchIndex>,
+    /// Composite foods by the foods they are built from; emptied and rebuilt
+    /// like the search index
+    graph: OnceCell<DependencyGraph>,
+}
+
+impl FoodRepository {
+    /// Creates a new FoodRepository instance and initializes it with data from the specified file.
+    /// 
+    /// This constructor implements the Repository Pattern by providing a clean interface for
+    /// data access while hiding the underlying storage implementation details.
+    /// 
+    /// # Arguments
+    /// * `file_path` - Path to the file where food data will be stored and loaded from
+    /// 
+    /// # Returns
+    /// * `Result<Self, io::Error>` - A new repository instance or an IO error if file loading fails
+    /// 
+    /// # Examples
+    /// ```ignore
+    /// let repo = FoodRepository::new("foods.txt")?;
+    /// ```
+    /// 
+    /// # Workflow
+    /// 1. Initialize empty food cache and store file path
+    /// 2. Check if data file exists in the file system
+    /// 3. If file exists, load all food data into memory
+    /// 4. Return fully initialized repository ready for operations
+    pub fn new(file_path: &str) -> Result<Self, io::Error> {
+        let mut repo = FoodRepository {
+            foods: HashMap::new(),
+            file_path: file_path.to_string(),
+            dirty: false,
+            keyword_derivation: KeywordDerivation::from_env(),
+            load_report: LoadReport::new(),
+            index: OnceCell::new(),
+            graph: OnceCell::new(),
+        };
+        
+        // Load foods from file if it exists
+        if Path::new(file_path).exists() {
+            repo.load()?;
+        }
+          Ok(repo)
+    }
+    
+    /// Adds a new food to the repository with duplicate detection.
+    /// 
+    /// This method enforces data integrity by preventing duplicate food IDs and validates
+    /// that the food entity meets all repository requirements before insertion.
+    /// 
+    /// # Arguments
+    /// * `food` - The food entity to add to the repository
+    /// 
+    /// # Returns
+    /// * `Result<(), String>` - Success confirmation or detailed error message
+    /// 
+    /// # Errors
+    /// * Returns error if a food with the same ID already exists
+    /// * Returns error if the ID is empty or a text field contains a control character
+    /// 
+    /// # Examples
+    /// ```ignore
+    /// let apple = Food::new_basic("apple".to_string(), "Apple".to_string(), 
+    ///                           vec!["fruit".to_string()], 52.0);
+    /// repo.add_food(apple)?;
+    /// ```
+    pub fn add_food(&mut self, mut food: Food) -> Result<(), String> {
+        if self.foods.contains_key(&food.id) {
+            return Err(format!("Food with ID {} already exists", food.id));
+        }
+        validate_text_fields(&food)?;
+        food.refresh_derived_keywords(&self.keyword_derivation);
+        if let Some(index) = self.index.get_mut() {
+            index.insert(&food);
+        }
+        if let Some(graph) = self.graph.get_mut() {
+            graph.insert(&food);
+        }
+        self.foods.insert(food.id.clone(), food);
+        self.dirty = true;
+        Ok(())
+    }
+    
+    /// Updates an existing food in the repository with validation.
+    /// 
+    /// This method modifies an existing food entity while maintaining data integrity
+    /// and ensuring that all references to the food remain valid.
+    /// 
+    /// # Arguments
+    /// * `food` - The updated food entity with the same ID as the existing food
+    /// 
+    /// # Returns
+    /// * `Ok(Vec<Food>)` - The previous versions of the composite foods whose
+    ///   values changed with the update (see `recalculate_dependents`)
+    /// * `Err(String)` - Detailed error message
+    /// 
+    /// # Errors
+    /// * Returns error if no food exists with the specified ID
+    /// * Returns error if a text field contains a control character
+    /// 
+    /// # Note
+    /// Composite foods built from the updated food, directly or through other
+    /// recipes, are recalculated before this returns. Logged entries keep the
+    /// calories they were logged with (see `models::log`).
+    pub fn update_food(&mut self, mut food: Food) -> Result<Vec<Food>, String> {
+        if !self.foods.contains_key(&food.id) {
+            return Err(format!("Food with ID {} not found", food.id));
+        }
+        validate_text_fields(&food)?;
+        food.refresh_derived_keywords(&self.keyword_derivation);
+        if let Some(old) = self.foods.get(&food.id) {
+            if let Some(index) = self.index.get_mut() {
+                index.remove(old);
+                index.insert(&food);
+            }
+            if let Some(graph) = self.graph.get_mut() {
+                graph.remove(old);
+                graph.insert(&food);
+            }
+        }
+        let id = food.id.clone();
+        self.foods.insert(id.clone(), food);
+        self.dirty = true;
+        Ok(self.recalculate_dependents(&id))
+    }
+
+    /// Removes a food from the repository.
+    /// 
+    /// Does not check whether composite foods or logs refer to the food;
+    /// commands that remove foods are responsible for that.
+    /// 
+    /// # Arguments
+    /// * `id` - The unique identifier of the food to remove
+    /// 
+    /// # Returns
+    /// * `Option<Food>` - The removed food, or None if no food had the ID
+    pub fn remove_food(&mut self, id: &str) -> Option<Food> {
+        let food = self.foods.remove(id)?;
+        if let Some(index) = self.index.get_mut() {
+            index.remove(&food);
+        }
+        if let Some(graph) = self.graph.get_mut() {
+            graph.remove(&food);
+        }
+        self.dirty = true;
+        Some(food)
+    }
+    
+    /// Retrieves a food by its unique identifier.
+    /// 
+    /// Provides O(1) access to food entities through the internal HashMap index,
+    /// supporting efficient lookups for both display and calculation operations.
+    /// 
+    /// # Arguments
+    /// * `id` - The unique identifier of the food to retrieve
+    /// 
+    /// # Returns
+    /// * `Option<&Food>` - A reference to the food if found, None otherwise
+    /// 
+    /// # Examples
+    /// ```ignore
+    /// if let Some(apple) = repo.get_food("apple") {
+    ///     println!("Calories: {}", apple.calories_per_serving);
+    /// }
+    /// ```
+    pub fn get_food(&self, id: &str) -> Option<&Food> {        self.foods.get(id)
+    }
+    
+    /// Returns all foods in the repository as a vector of references.
+    /// 
+    /// Provides access to the complete food database for operations like
+    /// browsing, bulk processing, or generating comprehensive reports.
+    /// 
+    /// # Returns
+    /// * `Vec<&Food>` - A vector containing references to all foods in the repository
+    /// 
+    /// # Performance
+    /// This operation creates a new vector but uses references to avoid copying
+    /// food data, making it efficient for read-only operations.
+    pub fn get_all_foods(&self) -> Vec<&Food> {        self.foods.values().collect()
+    }
+    
+    /// Searches for foods based on keyword matching with configurable logic.
+    /// 
+    /// Implements flexible search functionality supporting both AND and OR logic
+    /// for keyword matching, enabling users to find foods with varying levels
+    /// of specificity in their search criteria.
+    /// 
+    /// # Arguments
+    /// * `keywords` - Set of keywords to search for in food keywords
+    /// * `match_all` - If true, uses AND logic (all keywords must match); if false, uses OR logic
+    /// * `filters` - Nutrient conditions every result must also meet, such as
+    ///   `calories < 200` (see `models::nutrient_filter`)
+    /// 
+    /// # Returns
+    /// * `Vec<&Food>` - Vector of food references matching the search criteria
+    /// 
+    /// # Search Logic
+    /// - **AND Logic**: Food must contain ALL specified keywords
+    /// - **OR Logic**: Food must contain AT LEAST ONE specified keyword
+    /// - **Filters**: Food must pass every filter; with no keywords and AND
+    ///   logic, every food is filtered
+    /// 
+    /// Keywords are looked up in an inverted index, so the cost grows with
+    /// the number of matching foods rather than the size of the database.
+    /// 
+    /// # Examples
+    /// ```ignore
+    /// // Find foods that are both "fruit" AND "sweet"
+    /// let keywords = HashSet::from(["fruit".to_string(), "sweet".to_string()]);
+    /// let results = repo.search_foods(&keywords, true, &[]);
+    /// 
+    /// // Find foods that are either "fruit" OR "vegetable"
+    /// let results = repo.search_foods(&keywords, false, &[]);
+    /// 
+    /// // Find any food under 200 calories with more than 10 g of protein
+    /// let filters = nutrient_filter::parse_filters("calories < 200, protein > 10 g")?;
+    /// let results = repo.search_foods(&HashSet::new(), true, &filters);
+    /// ```
+    pub fn search_foods(&self, keywords: &HashSet<String>, match_all: bool, filters: &[NutrientFilter]) -> Vec<&Food> {
+        if keywords.is_empty() && match_all {
+            // Every food has all of no keywords
+            return self.foods.values().filter(|food| nutrient_filter::matches_all(filters, food)).collect();
+        }
+        // Read only the postings of the keywords (see repositories::search_index)
+        self.search_index()
+            .keyword_matches(keywords, match_all)
+            .iter()
+            .filter_map(|id| self.foods.get(id))
+            .filter(|food| nutrient_filter::matches_all(filters, food))
+            .collect()
+    }
+
+    /// Searches foods by name and keywords, tolerating partial words and typos.
+    /// 
+    /// Unlike `search_foods`, terms also match the start or middle of a
+    /// keyword or name word, or a word a small number of edits away; results
+    /// are ordered by match quality (see `models::search`).
+    /// 
+    /// # Arguments
+    /// * `terms` - Lowercase search terms
+    /// * `match_all` - If true, every term must match; if false, any term may
+    /// 
+    /// # Returns
+    /// * `Vec<SearchHit>` - Matching foods with their scores, best match first
+    /// 
+    /// # Examples
+    /// ```ignore
+    /// // Finds "Chicken Breast" despite the typo and partial word
+    /// let terms = HashSet::from(["chiken".to_string(), "bre".to_string()]);
+    /// let hits = repo.search_ranked(&terms, true);
+    /// ```
+    pub fn search_ranked(&self, terms: &HashSet<String>, match_all: bool) -> Vec<SearchHit<'_>> {
+        // Rank only foods the index offers for some term; terms spanning
+        // several words fall back to checking every food
+        let index = self.search_index();
+        let mut candidates: HashSet<String> = HashSet::new();
+        for term in terms {
+            match index.candidates(term) {
+                Some(ids) => candidates.extend(ids),
+                None => return search::rank(self.foods.values(), terms, match_all),
+            }
+        }
+        search::rank(candidates.iter().filter_map(|id| self.foods.get(id)), terms, match_all)
+    }
+
+    /// Suggests keywords for a new food from the keywords already in use
+    /// 
+    /// # Arguments
+    /// * `name` - Name of the new food
+    /// 
+    /// # Returns
+    /// * `Vec<String>` - Suggested keywords, best first (see `models::keyword_suggestion`)
+    pub fn suggest_keywords(&self, name: &str) -> Vec<String> {
+        keyword_suggestion::suggest(name, self.foods.values())
+    }
+
+    /// Returns the search index, building it if foods were changed through `get_foods_mut`
+    fn search_index(&self) -> &SearchIndex {
+        self.index.get_or_init(|| SearchIndex::build(self.foods.values()))
+    }
+
+    /// Returns the graph of which composite foods are built from which foods,
+    /// building it if foods were changed through `get_foods_mut`
+    /// (see `repositories::dependency_graph`)
+    pub fn dependency_graph(&self) -> &DependencyGraph {
+        self.graph.get_or_init(|| DependencyGraph::build(self.foods.values()))
+    }
+    
+    /// Persists all food data to the configured file using a structured format.
+    /// 
+    /// Implements the repository's persistence responsibility by serializing all
+    /// food entities to a human-readable, parseable text format. The method handles
+    /// both basic and composite foods with their respective data requirements.
+    /// 
+    /// # Returns
+    /// * `Result<(), io::Error>` - Success confirmation or IO error details
+    /// 
+    /// # File Format
+    /// - **Basic Foods**: `B|id|name|keywords|calories`
+    /// - **Composite Foods**: `C|id|name|keywords|component1:servings1,component2:servings2`
+    /// - **Attributes**: Optional trailing `|key=value` fields (see module docs)
+    /// 
+    /// # Error Handling
+    /// - File creation failures
+    /// - Write permission issues
+    /// - Disk space limitations
+    /// 
+    /// # Data Integrity
+    /// The data is written to a temporary file and atomically renamed over
+    /// the original (see `storage::write_atomic`), so a crash mid-save can
+    /// never truncate the food database. Previous versions are kept as
+    /// rotating `.bak.N` backups.
+    pub fn save(&mut self) -> Result<(), io::Error> {
+        // Serialize into memory first; the file is replaced atomically below
+        let mut buffer = Vec::new();
+        writeln!(buffer, "{}", format::header("foods"))?;
+        
+        // Sort by ID for stable, diff-friendly output
+        let mut foods: Vec<&Food> = self.foods.values().collect();
+        foods.sort_by(|a, b| a.id.cmp(&b.id));
+        
+        for food in foods {
+            let keywords = format_list(&food.keywords);
+            
+            match food.food_type {
+                FoodType::Basic => {
+                    writeln!(
+                        buffer,
+                        "B|{}|{}|{}|{}{}",
+                        format::escape(&food.id),
+                        format::escape(&food.name),
+                        keywords,
+                        food.calories_per_serving,
+                        format_attributes(food)
+                    )?;
+                }
+                FoodType::Composite => {
+                    let components = food
+                        .components
+                        .iter()
+                        .map(|(id, servings)| format!("{}:{}", format::escape(id), servings))
+                        .collect::<Vec<_>>()
+                        .join(",");
+                    
+                    writeln!(
+                        buffer,
+                        "C|{}|{}|{}|{}{}",
+                        format::escape(&food.id),
+                        format::escape(&food.name),
+                        keywords,
+                        components,
+                        format_attributes(food)
+                    )?;
+                }
+            }
+        }
+        
+        storage::write_data_file(&self.file_path, &buffer)?;
+        
+        self.dirty = false;
+        Ok(())
+    }
+    
+    /// Loads all food data from the configured file into memory.
+    /// 
+    /// This method implements the repository's data loading responsibility,
+    /// parsing the structured text format and reconstructing food entities
+    /// in memory. It handles both basic and composite foods with proper
+    /// dependency resolution and calorie calculation.
+    /// 
+    /// # Returns
+    /// * `Result<(), io::Error>` - Success confirmation or IO error details
+    /// 
+    /// # Loading Process
+    /// 1. **Clear Cache**: Remove any existing in-memory food data
+    /// 2. **Parse File**: Process each line according to food type format
+    /// 3. **Create Entities**: Construct Food objects from parsed data
+    /// 4. **Resolve Dependencies**: Calculate composite food calories
+    /// 5. **Validate Integrity**: Ensure all food references are valid
+    /// 
+    /// # Error Recovery
+    /// - Skips malformed lines rather than failing completely
+    /// - Continues processing valid data when encountering errors
+    /// - Provides detailed error information for debugging
+    /// 
+    /// # Composite Food Handling
+    /// Uses a two-pass approach to ensure all component foods are loaded
+    /// before calculating composite food calorie values.
+    pub fn load(&mut self) -> Result<(), io::Error> {
+        let contents = storage::read_data_file(&self.file_path)?;
+        let reader = contents.as_slice();
+        self.foods.clear();
+        let mut report = LoadReport::new();
+        
+        for record in schema::records("foods", reader) {
+            let (number, line) = record?;
+            let parts: Vec<&str> = format::split_escaped(&line, '|');
+            let Some(mut food) = parse_food_line(&parts, number, &mut report) else {
+                continue; // Skip invalid lines
+            };
+            food.refresh_derived_keywords(&self.keyword_derivation);
+            if food.food_type == FoodType::Composite {
+                // Calculate calories and macros based on components
+                food.calculate_from_components(&self.foods);
+            }
+            
+            let id = food.id.clone();
+            if self.foods.insert(id.clone(), food).is_some() {
+                // A later duplicate replaces the earlier line
+                report.skip(number, format!("duplicate food ID '{}'; this line replaces the earlier one", id));
+            }
+        }
+        self.load_report = report;
+        
+        // Recalculate calories for all composite foods
+        // (need to do this after loading all foods to ensure dependencies are loaded)
+        self.graph = OnceCell::from(DependencyGraph::build(self.foods.values()));
+        self.recalculate_composites();
+        // Sessions that never search never pay for the index
+        self.index = OnceCell::new();
+        
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Recalculates the calories and macros of every composite food from its components
+    /// 
+    /// Called after loading, and after bulk changes to basic foods (see
+    /// `ingest`) so recipes reflect the current component values. Recipes
+    /// made from other recipes are calculated after them.
+    pub fn recalculate_composites(&mut self) {
+        let composites: HashSet<String> = self.foods
+            .values()
+            .filter(|f| matches!(f.food_type, FoodType::Composite))
+            .map(|f| f.id.clone())
+            .collect();
+        let order = self.dependency_graph().order(&composites);
+        self.recalculate(order);
+    }
+
+    /// Recalculates the composite foods built from a food, directly or
+    /// through other recipes, in dependency order
+    /// 
+    /// `update_food` calls this for every update.
+    /// 
+    /// # Arguments
+    /// * `id` - ID of the food that changed
+    /// 
+    /// # Returns
+    /// The previous versions of the dependents whose calories, macros,
+    /// weight, or cost changed
+    pub fn recalculate_dependents(&mut self, id: &str) -> Vec<Food> {
+        let order = self.dependency_graph().dependents(id);
+        self.recalculate(order)
+    }
+
+    /// Recalculates composite foods in the given order
+    /// 
+    /// # Returns
+    /// The previous versions of the foods whose values changed
+    fn recalculate(&mut self, order: Vec<String>) -> Vec<Food> {
+        let mut changed = Vec::new();
+        for id in order {
+            // Temporarily take the food out so the rest of the map can be read
+            if let Some(mut food) = self.foods.remove(&id) {
+                let previous = food.clone();
+                food.calculate_from_components(&self.foods);
+                if food.calories_per_serving != previous.calories_per_serving
+                    || food.macros != previous.macros
+                    || food.grams_per_serving != previous.grams_per_serving
+                    || food.cost_per_serving != previous.cost_per_serving
+                {
+                    changed.push(previous);
+                }
+                self.foods.insert(id, food);
+            }
+        }
+        changed
+    }
+
+    /// Reports whether the in-memory food database has unsaved changes.
+    /// 
+    /// The flag is raised by every mutating operation (including handing out
+    /// mutable access, since callers may change data through it) and cleared
+    /// by a successful `save()` or `load()`. Autosave uses it to skip writing
+    /// files that have not changed.
+    /// 
+    /// # Returns
+    /// * `bool` - True if there are changes that have not been persisted yet
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Returns how many lines the last `load()` skipped.
+    /// 
+    /// Counts malformed lines and earlier definitions of duplicate IDs
+    /// (the last one wins). Blank lines, the header, and comments are not counted.
+    pub fn skipped_line_count(&self) -> usize {
+        self.load_report.skipped_count()
+    }
+
+    /// Returns what the last `load()` skipped or defaulted, with line numbers.
+    pub fn load_report(&self) -> &LoadReport {
+        &self.load_report
+    }
+
+    /// Changes which metadata is turned into derived keywords.
+    /// 
+    /// Derived keywords of all foods are recomputed immediately. Since they
+    /// are never persisted, this does not mark the repository dirty.
+    /// 
+    /// # Arguments
+    /// * `derivation` - The new derivation setting
+    pub fn set_keyword_derivation(&mut self, derivation: KeywordDerivation) {
+        self.keyword_derivation = derivation;
+        for food in self.foods.values_mut() {
+            food.refresh_derived_keywords(&derivation);
+        }
+        self.index = OnceCell::new();
+    }
+
+    /// Returns all foods that are not archived.
+    /// 
+    /// Pickers list these; archived foods stay reachable through `get_food`.
+    pub fn get_active_foods(&self) -> Vec<&Food> {
+        self.foods.values().filter(|food| !food.archived).collect()
+    }
+
+    /// Returns all foods that are not archived, in a stable order.
+    /// 
+    /// Foods that tie on the sort key (same name or calories) are ordered by
+    /// ID, so paging through the list never shows a food twice or skips one.
+    /// 
+    /// # Arguments
+    /// * `sort` - Order of the list
+    /// 
+    /// # Returns
+    /// * `Vec<&Food>` - Active foods in the requested order
+    pub fn get_foods_sorted(&self, sort: FoodSort) -> Vec<&Food> {
+        let mut foods = self.get_active_foods();
+        match sort {
+            FoodSort::Name => foods.sort_by_cached_key(|food| (food.name.to_lowercase(), food.id.clone())),
+            FoodSort::Calories => foods.sort_by(|a, b| {
+                a.calories_per_serving.total_cmp(&b.calories_per_serving).then_with(|| a.id.cmp(&b.id))
+            }),
+            FoodSort::Id => foods.sort_by(|a, b| a.id.cmp(&b.id)),
+        }
+        foods
+    }
+
+    /// Returns the categories of the foods that are not archived.
+    /// 
+    /// Categories that differ only in case are counted together under the
+    /// first spelling found in ID order.
+    /// 
+    /// # Returns
+    /// * `Vec<(String, usize)>` - Each category with its number of foods, by
+    ///   name; foods without a category are not counted
+    pub fn categories(&self) -> Vec<(String, usize)> {
+        let mut counts: Vec<(String, usize)> = Vec::new();
+        for food in self.get_foods_sorted(FoodSort::Id) {
+            let Some(name) = &food.category else {
+                continue;
+            };
+            match counts.iter_mut().find(|(known, _)| known.eq_ignore_ascii_case(name)) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((name.clone(), 1)),
+            }
+        }
+        counts.sort_by_key(|(name, _)| name.to_lowercase());
+        counts
+    }
+
+    /// Records that a food was logged on a date.
+    /// 
+    /// The food and every food it is built from get their `last_used` date
+    /// moved forward (never back). A directly logged food that was archived
+    /// is restored, since it is evidently in use again.
+    /// 
+    /// # Arguments
+    /// * `id` - ID of the logged food
+    /// * `date` - Date of the log entry
+    pub fn record_usage(&mut self, id: &str, date: NaiveDate) {
+        for used_id in usage::used_food_ids(id, &self.foods) {
+            let Some(food) = self.foods.get_mut(&used_id) else {
+                continue;
+            };
+            if food.last_used < Some(date) {
+                food.last_used = Some(date);
+                self.dirty = true;
+            }
+            if used_id == id && food.archived {
+                food.archived = false;
+                self.dirty = true;
+            }
+        }
+    }
+
+    /// Brings `last_used` dates up to date with usage found in the logs.
+    /// 
+    /// Dates only move forward. Foods with no known date at all (added by an
+    /// older version, or never logged) are stamped with `today`, so that they
+    /// get a full archive period before being suggested for archiving.
+    /// 
+    /// # Arguments
+    /// * `found` - Usage collected from the logs (see `usage::collect_usage`)
+    /// * `today` - Date used for foods without any known use
+    pub fn sync_usage(&mut self, found: &HashMap<String, FoodUsage>, today: NaiveDate) {
+        for food in self.foods.values_mut() {
+            let logged = found.get(&food.id).and_then(|u| u.last_used);
+            let last_used = food.last_used.max(logged).or(Some(today));
+            if food.last_used != last_used {
+                food.last_used = last_used;
+                self.dirty = true;
+            }
+        }
+    }
+
+    /// Archives or restores a food.
+    /// 
+    /// # Arguments
+    /// * `id` - ID of the food
+    /// * `archived` - The new state
+    /// 
+    /// # Returns
+    /// * `Ok(bool)` - The previous state
+    /// * `Err(String)` - If no food exists with the ID
+    pub fn set_archived(&mut self, id: &str, archived: bool) -> Result<bool, String> {
+        let food = self
+            .foods
+            .get_mut(id)
+            .ok_or_else(|| format!("Food with ID {} not found", id))?;
+        let previous = food.archived;
+        if previous != archived {
+            food.archived = archived;
+            self.dirty = true;
+        }
+        Ok(previous)
+    }
+
+    /// Adds a keyword to a food or removes it, keeping the search index current.
+    /// 
+    /// # Arguments
+    /// * `id` - ID of the food
+    /// * `keyword` - The keyword; compared in lowercase
+    /// * `present` - True to add the keyword, false to remove it
+    /// 
+    /// # Returns
+    /// * `Ok(bool)` - Whether the food had the keyword before
+    /// * `Err(String)` - If no food exists with the ID, or the keyword is empty or invalid
+    pub fn set_keyword(&mut self, id: &str, keyword: &str, present: bool) -> Result<bool, String> {
+        let keyword = keyword.trim().to_lowercase();
+        if keyword.is_empty() {
+            return Err("Keyword cannot be empty".to_string());
+        }
+        let mut food = self.get_food(id).cloned().ok_or_else(|| format!("Food with ID {} not found", id))?;
+        let previous = food.keywords.contains(&keyword);
+        if previous != present {
+            if present {
+                food.keywords.insert(keyword);
+            } else {
+                food.keywords.remove(&keyword);
+            }
+            self.update_food(food)?;
+        }
+        Ok(previous)
+    }
+
+    /// Merges a duplicate food into the food that is kept.
+    /// 
+    /// Composite foods using the duplicate use the kept food instead; a
+    /// recipe that already contains both gets their servings added together.
+    /// The kept food keeps its name and nutrition values, gains the
+    /// duplicate's keywords and tags, takes any serving details, category,
+    /// or usage date it lacks, and stays visible unless both were archived.
+    /// Log entries and meal plans are not touched; see `MergeFoodsCommand`.
+    /// 
+    /// # Arguments
+    /// * `keep` - ID of the food that remains
+    /// * `remove` - ID of the duplicate that is removed
+    /// 
+    /// # Returns
+    /// * `Ok(Vec<Food>)` - The previous version of every food changed or
+    ///   removed, so the merge can be reverted with `restore_foods`
+    /// * `Err(String)` - If a food is missing, the IDs are equal, or one food
+    ///   is built from the other
+    pub fn merge_food(&mut self, keep: &str, remove: &str) -> Result<Vec<Food>, String> {
+        if keep == remove {
+            return Err("A food cannot be merged into itself".to_string());
+        }
+        let kept = self.foods.get(keep).ok_or_else(|| format!("Food with ID {} not found", keep))?;
+        let removed = self.foods.get(remove).ok_or_else(|| format!("Food with ID {} not found", remove))?;
+        if usage::used_food_ids(keep, &self.foods).iter().any(|id| id == remove)
+            || usage::used_food_ids(remove, &self.foods).iter().any(|id| id == keep)
+        {
+            return Err(format!("{} and {} cannot be merged because one is made from the other", keep, remove));
+        }
+
+        let mut merged = kept.clone();
+        merged.keywords.extend(removed.keywords.iter().cloned());
+        merged.tags.extend(removed.tags.iter().cloned());
+        merged.category = merged.category.or_else(|| removed.category.clone());
+        merged.last_used = merged.last_used.max(removed.last_used);
+        merged.archived = merged.archived && removed.archived;
+        if merged.food_type == FoodType::Basic {
+            merged.grams_per_serving = merged.grams_per_serving.or(removed.grams_per_serving);
+            merged.cost_per_serving = merged.cost_per_serving.or(removed.cost_per_serving);
+            merged.serving_size = merged.serving_size.or(removed.serving_size);
+        }
+
+        let mut previous = vec![kept.clone(), removed.clone()];
+        let mut rewritten = vec![merged];
+        for food in self.foods.values().filter(|food| food.components.iter().any(|(id, _)| id == remove)) {
+            previous.push(food.clone());
+            let mut recipe = food.clone();
+            recipe.components.clear();
+            for (id, servings) in &food.components {
+                let id = if id == remove { keep } else { id.as_str() };
+                match recipe.components.iter_mut().find(|(existing, _)| existing == id) {
+                    Some((_, total)) => *total += servings,
+                    None => recipe.components.push((id.to_string(), *servings)),
+                }
+            }
+            rewritten.push(recipe);
+        }
+
+        for food in rewritten {
+            self.update_food(food)?;
+        }
+        self.remove_food(remove);
+        self.recalculate_composites();
+        Ok(previous)
+    }
+
+    /// Puts back foods saved before a change, such as the result of `merge_food`.
+    /// 
+    /// Foods that still exist are replaced and missing ones are added again;
+    /// composite foods are recalculated afterwards.
+    pub fn restore_foods(&mut self, foods: Vec<Food>) {
+        for food in foods {
+            if let Some(index) = self.index.get_mut() {
+                if let Some(old) = self.foods.get(&food.id) {
+                    index.remove(old);
+                }
+                index.insert(&food);
+            }
+            if let Some(graph) = self.graph.get_mut() {
+                if let Some(old) = self.foods.get(&food.id) {
+                    graph.remove(old);
+                }
+                graph.insert(&food);
+            }
+            self.foods.insert(food.id.clone(), food);
+        }
+        self.recalculate_composites();
+        self.dirty = true;
+    }
+
+    /// Provides mutable access to the internal food HashMap for advanced operations.
+    /// 
+    /// This method exposes the internal data structure for operations that require
+    /// direct manipulation of the food collection, such as batch updates or
+    /// complex data transformations that aren't covered by standard CRUD operations.
+    /// 
+    /// # Returns
+    /// * `&mut HashMap<String, Food>` - Mutable reference to the internal food storage
+    /// 
+    /// # Use Cases
+    /// - Batch operations that modify multiple foods
+    /// - Complex data migrations or transformations
+    /// - Advanced search operations requiring custom logic
+    /// 
+    /// # Warning
+    /// Direct manipulation of the HashMap bypasses repository validation,
+    /// so callers must ensure data integrity when using this method.
+    /// The repository is marked dirty because changes cannot be observed,
+    /// and the search index and dependency graph are dropped and rebuilt
+    /// when next needed.
+    pub fn get_foods_mut(&mut self) -> &mut HashMap<String, Food> {
+        self.dirty = true;
+        self.index = OnceCell::new();
+        self.graph = OnceCell::new();
+        &mut self.foods
+    }
+    
+    /// Provides immutable access to the internal food HashMap for read operations.
+    /// 
+    /// This method allows efficient read-only access to the complete food collection
+    /// without the overhead of creating new data structures, supporting advanced
+    /// querying and analysis operations.
+    /// 
+    /// # Returns
+    /// * `&HashMap<String, Food>` - Immutable reference to the internal food storage
+    /// 
+    /// # Use Cases
+    /// - Performance-critical read operations
+    /// - Complex queries requiring HashMap iteration
+    /// - Statistical analysis of the food database
+    /// - Custom search algorithms
+    /// 
+    /// # Benefits
+    /// - O(1) access to individual foods by ID
+    /// - Efficient iteration over the entire collection
+    /// - No data copying overhead for large operations
+    pub fn get_foods(&self) -> &HashMap<String, Food> {
+        &self.foods
+    }
+}
+
+/// Checks that every text field of a food survives a save and load
+///
+/// Delimiters are escaped on save; this rejects what escaping cannot carry
+/// (control characters) and an empty ID, which would make the line unreadable.
+fn validate_text_fields(food: &Food) -> Result<(), String> {
+    if food.id.trim().is_empty() {
+        return Err("Food ID cannot be empty".to_string());
+    }
+    format::validate_text("Food ID", &food.id)?;
+    format::validate_text("Food name", &food.name)?;
+    for keyword in &food.keywords {
+        format::validate_text("Keyword", keyword)?;
+    }
+    for tag in &food.tags {
+        format::validate_text("Tag", tag)?;
+    }
+    if let Some(category) = &food.category {
+        format::validate_text("Category", category)?;
+    }
+    for (id, _) in &food.components {
+        format::validate_text("Component ID", id)?;
+    }
+    Ok(())
+}
+
+/// Serializes a food's optional attributes as trailing `|key=value` fields
+/// 
+/// Only attributes that carry information are written, so foods without
+/// them produce exactly the original five-column format.
+fn format_attributes(food: &Food) -> String {
+    let mut attributes = String::new();
+    
+    if let Some(category) = &food.category {
+        attributes.push_str(&format!("|category={}", format::escape(category)));
+    }
+    if !food.tags.is_empty() {
+        attributes.push_str(&format!("|tags={}", format_list(&food.tags)));
+    }
+    if let Some(date) = food.last_used {
+        attributes.push_str(&format!("|last_used={}", date.format(DATE_FORMAT)));
+    }
+    if food.archived {
+        attributes.push_str("|archived=true");
+    }
+    if let Some(pack) = &food.pack {
+        attributes.push_str(&format!("|pack={}", format::escape(pack)));
+    }
+    if let Some(servings) = food.default_servings {
+        attributes.push_str(&format!("|default_servings={}", servings));
+    }
+    
+    // Composite values are derived from components and never stored
+    if food.food_type != FoodType::Basic {
+        return attributes;
+    }
+    
+    // Alcohol and caffeine alone do not make the other macros known
+    let nutrients = Macros { alcohol: 0.0, caffeine: 0.0, ..food.macros };
+    if !nutrients.is_empty() {
+        attributes.push_str(&format!(
+            "|protein={}|carbs={}|fat={}",
+            food.macros.protein, food.macros.carbs, food.macros.fat
+        ));
+    }
+    if food.macros.fiber > 0.0 {
+        attributes.push_str(&format!("|fiber={}", food.macros.fiber));
+    }
+    if food.macros.alcohol > 0.0 {
+        attributes.push_str(&format!("|alcohol={}", food.macros.alcohol));
+    }
+    if food.macros.caffeine > 0.0 {
+        attributes.push_str(&format!("|caffeine={}", food.macros.caffeine));
+    }
+    if let Some(grams) = food.grams_per_serving {
+        attributes.push_str(&format!("|grams={}", grams));
+    }
+    if let Some(cost) = food.cost_per_serving {
+        attributes.push_str(&format!("|cost={}", cost));
+    }
+    if let Some(size) = food.serving_size {
+        attributes.push_str(&format!("|serving_size={}|serving_unit={}", size.amount, size.unit.symbol()));
+    }
+    
+    attributes
+}
+
+/// Reads the `serving_size` and `serving_unit` attributes
+/// 
+/// Both must be present and valid; otherwise the serving size is unknown.
+fn parse_serving_size(attributes: &HashMap<String, String>) -> Option<ServingSize> {
+    let amount = attributes
+        .get("serving_size")
+        .and_then(|v| v.parse::<f64>().ok())
+        .filter(|amount| *amount > 0.0)?;
+    let unit = attributes.get("serving_unit").and_then(|v| ServingUnit::parse(v))?;
+    Some(ServingSize::new(amount, unit))
+}
+
+/// Reads the `last_used` and `archived` attributes into a food
+/// 
+/// An unreadable date is treated as unknown rather than rejecting the food.
+fn apply_usage_attributes(food: &mut Food, attributes: &HashMap<String, String>) {
+    food.last_used = attributes
+        .get("last_used")
+        .and_then(|date| NaiveDate::parse_from_str(date, DATE_FORMAT).ok());
+    food.archived = attributes.get("archived").is_some_and(|value| value == "true");
+}
+
+/// Writes a set of words as a sorted, escaped, comma-separated field
+fn format_list(words: &HashSet<String>) -> String {
+    let mut words: Vec<String> = words.iter().map(|w| format::escape(w)).collect();
+    words.sort();
+    words.join(",")
+}
+
+/// Reads a comma-separated field written by `format_list`
+fn parse_list(field: &str) -> HashSet<String> {
+    format::split_escaped(field, ',')
+        .into_iter()
+        .map(|w| format::unescape(w.trim()))
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+/// Reads the comma-separated `tags` attribute
+/// 
+/// Attribute values are kept escaped by `parse_attributes`, so the list can
+/// still be split on unescaped commas here.
+fn parse_tags(attributes: &HashMap<String, String>) -> HashSet<String> {
+    attributes
+        .get("tags")
+        .map(|tags| parse_list(tags))
+        .unwrap_or_default()
+}
+
+/// Parses trailing `key=value` fields into a lookup map
+/// 
+/// Fields without an `=` are ignored so that malformed attributes never
+/// prevent the food itself from loading. Values stay escaped; callers
+/// unescape them (or split them further) as needed.
+fn parse_attributes(fields: &[&str]) -> HashMap<String, String> {
+    fields
+        .iter()
+        .filter_map(|field| format::split_once_escaped(field, '='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
+
+/// Parses one `B` or `C` line of the food file format, already split at unescaped `|`
+///
+/// Derived keywords and composite values are left for the caller, which
+/// knows the keyword settings and the other foods. Food packs (see
+/// `repositories::food_pack`) use the same line format.
+///
+/// # Returns
+/// * `Some(Food)` - The food on the line
+/// * `None` - If the line cannot be read; the reason is recorded in `report`
+pub(crate) fn parse_food_line(parts: &[&str], number: usize, report: &mut LoadReport) -> Option<Food> {
+    if parts.len() < 4 {
+        report.skip(number, "too few fields");
+        return None;
+    }
+    
+    let (mut food, attributes) = match parts[0] {
+        "B" => {
+            // Basic food format: B|id|name|keywords|calories[|key=value...]
+            if parts.len() < 5 {
+                report.skip(number, "basic food without calories");
+                return None;
+            }
+            let attributes = parse_attributes(&parts[5..]);
+            
+            let id = format::unescape(parts[1]);
+            let name = format::unescape(parts[2]);
+            let keywords = parse_list(parts[3]);
+            let calories: f64 = report.number(number, "calories", parts[4], 0.0);
+            
+            let mut attribute = |key: &str| match attributes.get(key) {
+                Some(value) => report.number(number, key, value, 0.0),
+                None => 0.0,
+            };
+            let macros = Macros::new(attribute("protein"), attribute("carbs"), attribute("fat"))
+                .with_fiber(attribute("fiber"))
+                .with_alcohol_and_caffeine(attribute("alcohol"), attribute("caffeine"));
+            
+            let mut optional = |key: &str| report.optional_number(number, key, attributes.get(key).map(String::as_str));
+            
+            let food = Food::new_basic(id, name, keywords, calories)
+                .with_macros(macros)
+                .with_serving_details(optional("grams"), optional("cost"))
+                .with_serving_size(parse_serving_size(&attributes))
+                .with_metadata(attributes.get("category").map(|c| format::unescape(c)), parse_tags(&attributes));
+            (food, attributes)
+        }
+        "C" => {
+            // Composite food format: C|id|name|keywords|component1:servings1,...[|key=value...]
+            if parts.len() < 5 {
+                report.skip(number, "composite food without components");
+                return None;
+            }
+            let attributes = parse_attributes(&parts[5..]);
+            
+            let id = format::unescape(parts[1]);
+            let name = format::unescape(parts[2]);
+            let keywords = parse_list(parts[3]);
+            
+            let components = format::split_escaped(parts[4], ',')
+                .into_iter()
+                .filter_map(|comp| {
+                    let comp_parts: Vec<&str> = format::split_escaped(comp, ':');
+                    if comp_parts.len() != 2 {
+                        return None;
+                    }
+                    
+                    let comp_id = format::unescape(comp_parts[0]);
+                    let servings: f64 = report.number(number, &format!("servings of '{}'", comp_id), comp_parts[1], 0.0);
+                    Some((comp_id, servings))
+                })
+                .collect();
+            
+            let food = Food::new_composite(id, name, keywords, components)
+                .with_metadata(attributes.get("category").map(|c| format::unescape(c)), parse_tags(&attributes));
+            (food, attributes)
+        }
+        other => {
+            // Skip unknown food types
+            report.skip(number, format!("unknown record type '{}'", other));
+            return None;
+        }
+    };
+    apply_usage_attributes(&mut food, &attributes);
+    food.pack = attributes.get("pack").map(|pack| format::unescape(pack));
+    let default_servings = report.optional_number(number, "default_servings", attributes.get("default_servings").map(String::as_str));
+    let food = food.with_default_servings(default_servings);
+    Some(food)
+}
@@ -0,0 +1,152 @@
+//! # Canonical File Format
+//!
+//! This module holds the pieces of the pipe-delimited text format that all
+//! three repositories share: the versioned header line and escaping of
+//! delimiter characters inside text fields.
+//!
+//! ## Header
+//!
+//! Files written by this version start with a header naming the file kind
+//! and the format version:
+//! ```text
+//! #yada:foods:v1
+//! ```
+//! Lines starting with `#` are ignored by the loaders, so files without a
+//! header (written by older versions) still load, and older versions skip
+//! the header as an unknown line.
+//!
+//! ## Escaping
+//!
+//! Text fields (names, keywords, IDs, categories) may contain the characters
+//! used as delimiters. They are written with a backslash in front:
+//!
+//! - `\` becomes `\\`
+//! - `|`, `,`, `:`, and `=` become `\|`, `\,`, `\:`, and `\=`
+//! - A newline becomes `\n`
+//!
+//! Loaders first split a line on unescaped delimiters with `split_escaped`
+//! and then turn each piece back into plain text with `unescape`.
+//!
+//! Other control characters (tabs, carriage returns, ...) cannot be read
+//! back reliably, so repositories reject text containing them with
+//! `validate_text` before it is stored.
+
+// yada-core/src/repositories/format.rs
+
+/// Current version of the data file format
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Prefix of the header line and of comment lines
+const COMMENT_PREFIX: char = '#';
+
+/// Builds the header line for a data file
+///
+/// # Arguments
+/// * `kind` - File kind: `foods`, `logs`, or `profile`
+pub fn header(kind: &str) -> String {
+    format!("{}yada:{}:v{}", COMMENT_PREFIX, kind, FORMAT_VERSION)
+}
+
+/// Parses a header line
+///
+/// # Returns
+/// * `Some((kind, version))` - If the line is a YADA header
+/// * `None` - For any other line (including files without a header)
+pub fn parse_header(line: &str) -> Option<(String, u32)> {
+    let rest = line.trim().strip_prefix(COMMENT_PREFIX)?.strip_prefix("yada:")?;
+    let (kind, version) = rest.rsplit_once(':')?;
+    let version = version.strip_prefix('v')?.parse().ok()?;
+    Some((kind.to_string(), version))
+}
+
+/// Returns true for lines the loaders should skip (blank, header, comments)
+pub fn is_ignored_line(line: &str) -> bool {
+    let line = line.trim();
+    line.is_empty() || line.starts_with(COMMENT_PREFIX)
+}
+
+/// Escapes delimiter characters in a text field
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | '|' | ',' | ':' | '=' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Checks that a text field can be written and read back unchanged
+///
+/// # Arguments
+/// * `field` - Name of the field, used in the error (e.g. `Food name`)
+/// * `text` - The text to store
+///
+/// # Returns
+/// * `Ok(())` - If the text survives a save and load
+/// * `Err(String)` - If it contains a control character
+pub fn validate_text(field: &str, text: &str) -> Result<(), String> {
+    match text.chars().find(|c| c.is_control()) {
+        Some(c) => Err(format!("{} contains an unsupported character ({:?})", field, c)),
+        None => Ok(()),
+    }
+}
+
+/// Turns an escaped field back into plain text
+///
+/// A backslash at the very end of the field is kept as is.
+pub fn unescape(field: &str) -> String {
+    let mut text = String::with_capacity(field.len());
+    let mut chars = field.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => text.push('\n'),
+            Some(next) => text.push(next),
+            None => text.push('\\'),
+        }
+    }
+    text
+}
+
+/// Splits text on a delimiter, ignoring delimiters preceded by a backslash
+///
+/// The pieces keep their escapes so they can be split further on other
+/// delimiters before being passed to `unescape`.
+pub fn split_escaped(text: &str, delimiter: char) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    let mut escaped = false;
+
+    for (index, c) in text.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == delimiter {
+            pieces.push(&text[start..index]);
+            start = index + c.len_utf8();
+        }
+    }
+    pieces.push(&text[start..]);
+    pieces
+}
+
+/// Splits text at the first unescaped occurrence of a delimiter
+pub fn split_once_escaped(text: &str, delimiter: char) -> Option<(&str, &str)> {
+    let mut pieces = split_escaped(text, delimiter);
+    if pieces.len() < 2 {
+        return None;
+    }
+    let first = pieces.remove(0);
+    Some((first, &text[first.len() + delimiter.len_utf8()..]))
+}
@@ -0,0 +1,214 @@
+//! # Referential Integrity
+//!
+//! The data files refer to each other by food ID, and nothing stops a food
+//! from disappearing from `foods.txt` (edited by hand, restored from an old
+//! backup, or lost to a bad line) while logs and recipes still use it. This
+//! module finds such dangling references, and daily profiles whose values
+//! cannot be right, and applies the repairs the user chooses.
+//!
+//! ## Problems Found
+//!
+//! - **Missing logged food**: A log entry refers to a food that does not exist
+//! - **Missing component**: A composite food uses a food that does not exist
+//! - **Impossible weight**: A daily profile has a weight outside the range
+//!   `models::validation` accepts
+//!
+//! ## Repairs
+//!
+//! - **Replace**: Point the entry or component at an existing food instead
+//! - **Remove**: Delete the entry, the component, or the daily profile
+//! - **Set weight**: Correct the weight of a daily profile
+//!
+//! Repairs change the repositories in memory only; the caller saves them.
+
+// yada-core/src/repositories/integrity.rs
+use std::fmt;
+
+use chrono::NaiveDate;
+
+use crate::models::usage;
+use crate::models::validation;
+use crate::repositories::food_repository::FoodRepository;
+use crate::repositories::log_repository::LogRepository;
+use crate::repositories::profile_repository::ProfileRepository;
+
+/// A reference or value that cannot be right
+#[derive(Debug, Clone, PartialEq)]
+pub enum Problem {
+    /// Entry `index` of the log for `date` uses a food that does not exist
+    MissingLoggedFood { date: NaiveDate, index: usize, food_id: String },
+
+    /// Composite food `food_id` uses `component`, which does not exist
+    MissingComponent { food_id: String, component: String },
+
+    /// The daily profile for `date` has an impossible weight
+    ImpossibleWeight { date: NaiveDate, weight: f64 },
+}
+
+impl Problem {
+    /// Returns true if the repair can be applied to this problem
+    pub fn accepts(&self, repair: &Repair) -> bool {
+        let is_weight = matches!(self, Problem::ImpossibleWeight { .. });
+        match repair {
+            Repair::Remove => true,
+            Repair::Replace(_) => !is_weight,
+            Repair::SetWeight(_) => is_weight,
+        }
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Problem::MissingLoggedFood { date, index, food_id } => {
+                write!(f, "{}: log entry {} uses unknown food '{}'", date, index + 1, food_id)
+            }
+            Problem::MissingComponent { food_id, component } => {
+                write!(f, "Composite food '{}' uses unknown food '{}'", food_id, component)
+            }
+            Problem::ImpossibleWeight { date, weight } => {
+                write!(f, "{}: daily profile weight {} kg is impossible", date, weight)
+            }
+        }
+    }
+}
+
+/// What to do about a problem
+#[derive(Debug, Clone, PartialEq)]
+pub enum Repair {
+    /// Use the food with this ID instead of the missing one
+    Replace(String),
+
+    /// Delete the entry, component, or daily profile
+    Remove,
+
+    /// Correct the weight of a daily profile (kilograms)
+    SetWeight(f64),
+}
+
+/// Returns true if a weight in kilograms is possible
+pub fn is_possible_weight(weight: f64) -> bool {
+    validation::weight_kg(weight).is_ok()
+}
+
+/// Finds every dangling food reference and impossible daily profile
+///
+/// # Returns
+/// The problems: log entries by date and position, then composite foods
+/// by ID, then daily profiles by date
+pub fn check(food_repo: &FoodRepository, log_repo: &LogRepository, profile_repo: &ProfileRepository) -> Vec<Problem> {
+    let foods = food_repo.get_foods();
+    let mut problems = Vec::new();
+
+    for log in log_repo.get_logs() {
+        for (index, entry) in log.entries.iter().enumerate() {
+            if !foods.contains_key(&entry.food_id) {
+                problems.push(Problem::MissingLoggedFood { date: log.date, index, food_id: entry.food_id.clone() });
+            }
+        }
+    }
+
+    let mut composites: Vec<_> = foods.values().filter(|food| !food.components.is_empty()).collect();
+    composites.sort_by(|a, b| a.id.cmp(&b.id));
+    for food in composites {
+        for (component, _) in &food.components {
+            if !foods.contains_key(component) {
+                problems.push(Problem::MissingComponent { food_id: food.id.clone(), component: component.clone() });
+            }
+        }
+    }
+
+    if let Some(profile) = profile_repo.get_profile() {
+        let mut dailies: Vec<_> = profile.daily_profiles.iter().collect();
+        dailies.sort_by_key(|daily| daily.date);
+        for daily in dailies {
+            if !is_possible_weight(daily.weight) {
+                problems.push(Problem::ImpossibleWeight { date: daily.date, weight: daily.weight });
+            }
+        }
+    }
+    problems
+}
+
+/// Applies repairs chosen for problems found by `check`
+///
+/// All repairs are validated before anything changes. Log entries are
+/// removed last and from the back, so removing one never shifts another
+/// repaired entry.
+///
+/// # Arguments
+/// * `repairs` - Problems with the repair chosen for each
+///
+/// # Returns
+/// * `Ok(usize)` - Number of repairs applied
+/// * `Err(String)` - If a repair does not fit its problem or names an unknown food
+pub fn apply(
+    repairs: &[(Problem, Repair)],
+    food_repo: &mut FoodRepository,
+    log_repo: &mut LogRepository,
+    profile_repo: &mut ProfileRepository,
+) -> Result<usize, String> {
+    for (problem, repair) in repairs {
+        if !problem.accepts(repair) {
+            return Err(format!("That repair does not fit the problem: {}", problem));
+        }
+        match repair {
+            Repair::Replace(id) if food_repo.get_food(id).is_none() => {
+                return Err(format!("Food with ID {} not found", id));
+            }
+            Repair::Replace(id) if matches!(problem, Problem::MissingComponent { food_id, .. }
+                if usage::used_food_ids(id, food_repo.get_foods()).contains(food_id)) =>
+            {
+                return Err(format!("{} cannot be used because it is made from the composite food", id));
+            }
+            Repair::SetWeight(weight) if !is_possible_weight(*weight) => {
+                return Err(format!("{} kg is not a possible weight", weight));
+            }
+            _ => {}
+        }
+    }
+
+    let mut removed_entries: Vec<(NaiveDate, usize)> = Vec::new();
+    for (problem, repair) in repairs {
+        match (problem, repair) {
+            (Problem::MissingLoggedFood { date, index, .. }, Repair::Replace(id)) => {
+                log_repo.set_food_ids(&[(*date, *index)], id);
+            }
+            (Problem::MissingLoggedFood { date, index, .. }, _) => removed_entries.push((*date, *index)),
+            (Problem::MissingComponent { food_id, component }, repair) => {
+                let Some(mut food) = food_repo.get_food(food_id).cloned() else {
+                    continue;
+                };
+                match repair {
+                    Repair::Replace(id) => {
+                        for (used, _) in food.components.iter_mut().filter(|(used, _)| used == component) {
+                            *used = id.clone();
+                        }
+                    }
+                    _ => food.components.retain(|(used, _)| used != component),
+                }
+                food_repo.update_food(food)?;
+            }
+            (Problem::ImpossibleWeight { date, .. }, repair) => {
+                let Some(profile) = profile_repo.get_profile_mut() else {
+                    continue;
+                };
+                match repair {
+                    Repair::SetWeight(weight) => profile.daily_profiles
+                        .iter_mut()
+                        .filter(|daily| daily.date == *date)
+                        .for_each(|daily| daily.weight = *weight),
+                    _ => profile.daily_profiles.retain(|daily| daily.date != *date),
+                }
+            }
+        }
+    }
+
+    removed_entries.sort_by(|a, b| b.cmp(a));
+    removed_entries.dedup();
+    for (date, index) in removed_entries {
+        log_repo.get_log_mut(date).remove_entry(index);
+    }
+    food_repo.recalculate_composites();
+    Ok(repairs.len())
+}
@@ -0,0 +1,225 @@
+//! # Load Reports
+//!
+//! Repositories tolerate damaged data files: a line that cannot be parsed is
+//! skipped, and a number that cannot be read is replaced with a default so
+//! the rest of the record survives. This module records each of those
+//! decisions with its line number, so the user learns at startup that part
+//! of a file was not loaded as written instead of finding out much later.
+//!
+//! ## Load Modes
+//!
+//! - **Lenient** (default): Problems are reported and loading continues
+//! - **Strict**: Any problem aborts loading with the report as the error,
+//!   leaving the file untouched for `yada doctor` or manual repair
+//!
+//! The mode is read from the `YADA_STRICT_LOAD` environment variable (`on`
+//! or `off`); the interactive menu also accepts `--strict`.
+
+// yada-core/src/repositories/load_report.rs
+use std::env;
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+
+/// Environment variable turning strict loading on
+pub const STRICT_LOAD_ENV_VAR: &str = "YADA_STRICT_LOAD";
+
+/// Problems listed individually by `LoadReport::describe`; the rest are counted
+pub const LISTED_ISSUES: usize = 5;
+
+/// How repositories react to damaged lines
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LoadMode {
+    /// Skip or default what cannot be read, and report it
+    #[default]
+    Lenient,
+
+    /// Refuse to load a file with any problem
+    Strict,
+}
+
+impl LoadMode {
+    /// Parses a mode from `on`/`off` (or `strict`/`lenient`)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "on" | "true" | "1" | "strict" => Some(LoadMode::Strict),
+            "off" | "false" | "0" | "lenient" => Some(LoadMode::Lenient),
+            _ => None,
+        }
+    }
+
+    /// Reads the mode from `YADA_STRICT_LOAD`, defaulting to lenient
+    pub fn from_env() -> Self {
+        env::var(STRICT_LOAD_ENV_VAR)
+            .ok()
+            .and_then(|value| Self::parse(&value))
+            .unwrap_or_default()
+    }
+
+    /// Applies the mode to a finished load
+    ///
+    /// # Arguments
+    /// * `file` - Name of the file, used in the error
+    /// * `report` - What the load skipped and defaulted
+    ///
+    /// # Returns
+    /// * `Ok(())` - In lenient mode, or when the file had no problems
+    /// * `Err(io::Error)` - `InvalidData` listing the problems, in strict mode
+    pub fn check(&self, file: &str, report: &LoadReport) -> Result<(), io::Error> {
+        if *self == LoadMode::Strict && !report.is_clean() {
+            let mut message = format!("{} is damaged ({}); strict loading stopped.", file, report.summary());
+            for line in report.describe() {
+                message.push_str("\n  ");
+                message.push_str(&line);
+            }
+            return Err(io::Error::new(io::ErrorKind::InvalidData, message));
+        }
+        Ok(())
+    }
+}
+
+/// What happened to a damaged part of a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueKind {
+    /// The whole line was ignored
+    Skipped,
+
+    /// A value was replaced with a default and the line was kept
+    Defaulted,
+}
+
+/// One damaged line or value
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadIssue {
+    /// Line number in the file (1-based, counting the header)
+    pub line: usize,
+
+    /// Whether the line was skipped or a value defaulted
+    pub kind: IssueKind,
+
+    /// What was wrong, e.g. `unknown record type 'X'`
+    pub detail: String,
+}
+
+impl fmt::Display for LoadIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            IssueKind::Skipped => write!(f, "Line {}: skipped ({})", self.line, self.detail),
+            IssueKind::Defaulted => write!(f, "Line {}: {}", self.line, self.detail),
+        }
+    }
+}
+
+/// Everything a repository skipped or defaulted during its last load
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LoadReport {
+    issues: Vec<LoadIssue>,
+}
+
+impl LoadReport {
+    /// Creates an empty report
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a skipped line
+    ///
+    /// # Arguments
+    /// * `line` - Line number (1-based)
+    /// * `reason` - Why the line could not be used
+    pub fn skip(&mut self, line: usize, reason: impl Into<String>) {
+        self.issues.push(LoadIssue { line, kind: IssueKind::Skipped, detail: reason.into() });
+    }
+
+    /// Records a value replaced with a default
+    ///
+    /// # Arguments
+    /// * `line` - Line number (1-based)
+    /// * `detail` - Which value and what was used instead
+    pub fn defaulted(&mut self, line: usize, detail: impl Into<String>) {
+        self.issues.push(LoadIssue { line, kind: IssueKind::Defaulted, detail: detail.into() });
+    }
+
+    /// Parses a number, recording a default when the text is not one
+    ///
+    /// # Arguments
+    /// * `line` - Line number (1-based)
+    /// * `field` - Name of the value, e.g. `calories`
+    /// * `text` - Text from the file
+    /// * `default` - Value used when the text does not parse
+    pub fn number<T>(&mut self, line: usize, field: &str, text: &str, default: T) -> T
+    where
+        T: FromStr + fmt::Display,
+    {
+        match text.trim().parse() {
+            Ok(value) => value,
+            Err(_) => {
+                self.defaulted(line, format!("{} '{}' is not a number; using {}", field, text, default));
+                default
+            }
+        }
+    }
+
+    /// Parses an optional number, recording it as dropped when the text is not one
+    ///
+    /// # Returns
+    /// `None` when the field is missing or unreadable
+    pub fn optional_number<T: FromStr>(&mut self, line: usize, field: &str, text: Option<&str>) -> Option<T> {
+        let text = text?;
+        match text.trim().parse() {
+            Ok(value) => Some(value),
+            Err(_) => {
+                self.defaulted(line, format!("{} '{}' is not a number; left empty", field, text));
+                None
+            }
+        }
+    }
+
+    /// Every problem, in the order found
+    pub fn issues(&self) -> &[LoadIssue] {
+        &self.issues
+    }
+
+    /// Number of skipped lines
+    pub fn skipped_count(&self) -> usize {
+        self.issues.iter().filter(|issue| issue.kind == IssueKind::Skipped).count()
+    }
+
+    /// Number of defaulted values
+    pub fn defaulted_count(&self) -> usize {
+        self.issues.iter().filter(|issue| issue.kind == IssueKind::Defaulted).count()
+    }
+
+    /// Whether the file loaded exactly as written
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Counts of the problems, e.g. `2 lines skipped, 1 value defaulted`
+    pub fn summary(&self) -> String {
+        let plural = |count: usize, one: &str, many: &str| {
+            format!("{} {}", count, if count == 1 { one } else { many })
+        };
+        let mut parts = Vec::new();
+        if self.skipped_count() > 0 {
+            parts.push(plural(self.skipped_count(), "line skipped", "lines skipped"));
+        }
+        if self.defaulted_count() > 0 {
+            parts.push(plural(self.defaulted_count(), "value defaulted", "values defaulted"));
+        }
+        if parts.is_empty() {
+            "no problems".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+
+    /// One line per problem, the first `LISTED_ISSUES` of them, then how many more
+    pub fn describe(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self.issues.iter().take(LISTED_ISSUES).map(ToString::to_string).collect();
+        if self.issues.len() > LISTED_ISSUES {
+            lines.push(format!("... and {} more", self.issues.len() - LISTED_ISSUES));
+        }
+        lines
+    }
+}
@@ -0,0 +1,114 @@
+//! # Data Directory Lock
+//!
+//! Two YADA instances working on the same data directory would each save
+//! their own copy of the files, and the last save would silently discard the
+//! other's changes. An instance that writes data therefore holds a lock file
+//! (`yada.lock`, containing its process ID) in the data directory for as long
+//! as it runs; a second instance finds the file and refuses to start.
+//!
+//! The process ID is written to a temporary file first, which is then
+//! hard-linked into place, so the lock file never exists without its
+//! process ID; another instance can't mistake a lock being taken for a stale one.
+//!
+//! ## Stale Locks
+//!
+//! A lock left behind by an instance that crashed is taken over when its
+//! process is known to be gone (on systems with `/proc`). Elsewhere the error
+//! names the lock file so it can be deleted by hand.
+
+// yada-core/src/repositories/lock.rs
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+
+/// Name of the lock file inside the data directory
+pub const LOCK_FILE: &str = "yada.lock";
+
+/// Exclusive use of a data directory, released when dropped
+#[derive(Debug)]
+pub struct DataLock {
+    path: PathBuf,
+}
+
+impl DataLock {
+    /// Locks a data directory for this process
+    ///
+    /// # Arguments
+    /// * `data_dir` - Directory containing the data files
+    ///
+    /// # Returns
+    /// * `Ok(DataLock)` - The lock, held until it is dropped
+    /// * `Err(io::Error)` - `ResourceBusy` naming the other instance's process
+    ///   ID if it holds the lock, or the error creating the lock file
+    pub fn acquire(data_dir: &Path) -> Result<Self, io::Error> {
+        let path = data_dir.join(LOCK_FILE);
+        let temp = data_dir.join(format!("{}.{}.tmp", LOCK_FILE, process::id()));
+        fs::write(&temp, format!("{}\n", process::id()))?;
+        let result = Self::link(data_dir, &temp, path);
+        let _ = fs::remove_file(&temp);
+        result
+    }
+
+    /// Links the temporary file holding the process ID into place as the lock
+    fn link(data_dir: &Path, temp: &Path, path: PathBuf) -> Result<Self, io::Error> {
+        // The second attempt follows the removal of a stale lock
+        for _ in 0..2 {
+            match fs::hard_link(temp, &path) {
+                Ok(()) => return Ok(DataLock { path }),
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+                Err(e) => return Err(e),
+            }
+
+            match holder(data_dir) {
+                Some(pid) if is_running(pid) != Some(false) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::ResourceBusy,
+                        format!(
+                            "Another YADA instance (process {}) is using {}. Close it first, \
+                             or delete {} if no other instance is running.",
+                            pid,
+                            data_dir.display(),
+                            path.display()
+                        ),
+                    ));
+                }
+                _ => {
+                    let _ = fs::remove_file(&path);
+                }
+            }
+        }
+        Err(io::Error::new(
+            io::ErrorKind::ResourceBusy,
+            format!("Could not lock {}; delete {} and try again.", data_dir.display(), path.display()),
+        ))
+    }
+
+    /// Path of the lock file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for DataLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Returns the process ID written in a data directory's lock file
+///
+/// # Returns
+/// `None` if the directory is not locked or the file holds no process ID
+pub fn holder(data_dir: &Path) -> Option<u32> {
+    fs::read_to_string(data_dir.join(LOCK_FILE)).ok()?.trim().parse().ok()
+}
+
+/// Whether a process is running, if the system can tell
+fn is_running(pid: u32) -> Option<bool> {
+    if pid == process::id() {
+        return Some(true);
+    }
+    let proc_dir = Path::new("/proc");
+    proc_dir.join("self").exists().then(|| proc_dir.join(pid.to_string()).exists())
+}
@@ -0,0 +1,651 @@
+//! # Log Repository
+//! 
+//! This module implements the Repository Pattern for managing daily food consumption logs.
+//! It provides temporal organization of food entries, enabling users to track their
+//! dietary intake across different dates with precise timestamps.
+//! 
+//! ## Repository Pattern Implementation
+//! 
+//! The `LogRepository` manages the persistence and retrieval of daily food logs:
+//! - **Temporal Organization**: Organizes food entries by date for chronological tracking
+//! - **Timestamped Entries**: Maintains precise consumption timing for detailed analysis
+//! - **Efficient Access**: Date-based indexing for O(1) daily log retrieval
+//! - **Batch Operations**: Handles multiple entries per day with atomic persistence
+//! - **Data Consistency**: Ensures temporal integrity and proper entry sequencing
+//! 
+//! ## File Format Specification
+//! 
+//! The repository uses a pipe-delimited format optimized for temporal data:
+//! ```text
+//! YYYY-MM-DD|food_id|servings|YYYY-MM-DDTHH:MM:SS
+//! ```
+//! 
+//! Entries logged in a unit other than servings carry the amount as entered
+//! in two trailing fields; `servings` holds the converted value:
+//! ```text
+//! 2024-03-05|chicken|0.85|2024-03-05T12:30:00|amount=85|unit=g
+//! ```
+//! 
+//! Entries assigned to a meal carry a `meal=` field as well:
+//! ```text
+//! 2024-03-05|oatmeal|1|2024-03-05T08:10:00|meal=breakfast
+//! ```
+//! 
+//! Entries record their calories as logged in a `kcal=` field, so later
+//! changes to the food do not rewrite past totals (see `models::log`):
+//! ```text
+//! 2024-03-05|oatmeal|1|2024-03-05T08:10:00|meal=breakfast|kcal=150
+//! ```
+//! Entries written before snapshots existed have no `kcal=` field; loading
+//! leaves them without a snapshot, and `backfill_calories` records one from
+//! the current food values.
+//! 
+//! A day's note and ratings (see `models::log::DayNote`) are a `NOTE` line written
+//! before the day's entries; each field is optional:
+//! ```text
+//! NOTE|2024-03-05|mood=4|energy=3|note=Slept well\, ate late
+//! ```
+//! 
+//! Every entry ends with its unique ID, so the same entry keeps the same ID
+//! from one run to the next:
+//! ```text
+//! 2024-03-05|oatmeal|1|2024-03-05T08:10:00|meal=breakfast|kcal=150|id=42
+//! ```
+//! Entries without an ID (written by older versions) or with an ID already
+//! used by another entry are given a new one when loaded, which is saved the
+//! next time the log is saved.
+//! 
+//! The first line is the format header `#yada:logs:v1`, entries are written
+//! in date order, and food IDs are backslash-escaped (see `repositories::format`).
+//! 
+//! ## Temporal Features
+//! 
+//! - **Date Indexing**: Efficient access to any day's consumption data
+//! - **Chronological Ordering**: Maintains temporal sequence for analysis
+//! - **Cross-Date Tracking**: Supports consumption logging for any date
+//! - **Historical Analysis**: Enables tracking of dietary patterns over time
+//! - **Future Planning**: Allows pre-planning of meals for upcoming dates
+//! 
+//! ## Analytics Queries
+//! 
+//! Reports and statistics read the log through these methods rather than
+//! walking the days themselves; date ranges include both ends:
+//! - `get_logs_between(start, end)`: The daily logs of a range, in date order
+//! - `days_logged_in(start, end)`: The dates of a range with food entries
+//! - `total_calories_between(start, end, food_db)`: Calories logged in a range
+//! - `entries_for_food(food_id)`: Every entry of one food with its date
+//! - `most_used_foods(limit)` and `recent_foods(limit)`: Foods for quick logging
+
+// yada-core/src/repositories/log_repository.rs
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Write};
+use std::path::Path;
+use chrono::{NaiveDate, NaiveDateTime, Local, DateTime};
+
+use crate::repositories::{format, schema, storage};
+use crate::repositories::load_report::LoadReport;
+use crate::models::food::Food;
+use crate::models::log::{self, DailyLog, DayNote, FoodEntry};
+use crate::models::meal::MealSlot;
+use crate::models::quantity::Quantity;
+use crate::models::serving::ServingUnit;
+use crate::models::usage::LoggedFood;
+
+/// # Log Repository
+/// 
+/// A Repository Pattern implementation for managing daily food consumption logs
+/// with temporal organization and precise timestamping. This repository provides
+/// efficient access to consumption data organized by date.
+/// 
+/// ## Core Responsibilities
+/// 
+/// - **Daily Log Management**: Create and maintain daily food consumption records
+/// - **Temporal Indexing**: Organize data by date for efficient chronological access
+/// - **Entry Tracking**: Manage individual food entries with precise timestamps
+/// - **Historical Persistence**: Maintain long-term consumption history
+/// - **Data Retrieval**: Provide efficient access to both current and historical data
+/// 
+/// ## Storage Strategy
+/// 
+/// The repository uses date-based partitioning in memory with unified file storage,
+/// optimizing for both temporal queries and persistent storage efficiency.
+pub struct LogRepository {
+    /// Date-indexed collection of daily logs for O(1) access to any day's data
+    logs: HashMap<NaiveDate, DailyLog>,
+    /// File system path for persistent storage of consumption logs
+    file_path: String,
+    /// Whether in-memory data has changed since the last save or load
+    dirty: bool,
+    /// Lines skipped and values defaulted by the last load
+    load_report: LoadReport,
+}
+
+impl LogRepository {
+    /// Creates a new LogRepository instance and initializes it with existing log data.
+    /// 
+    /// This constructor establishes the repository's connection to persistent storage
+    /// and loads any existing consumption data into memory for efficient access.
+    /// 
+    /// # Arguments
+    /// * `file_path` - Path to the file where log data will be stored and loaded from
+    /// 
+    /// # Returns
+    /// * `Result<Self, io::Error>` - A new repository instance or an IO error if file loading fails
+    /// 
+    /// # Initialization Process
+    /// 1. Create empty in-memory log collection indexed by date
+    /// 2. Store file path for future persistence operations
+    /// 3. Load existing log data if the file exists
+    /// 4. Return fully initialized repository ready for operations
+    pub fn new(file_path: &str) -> Result<Self, io::Error> {
+        let mut repo = LogRepository {
+            logs: HashMap::new(),
+            file_path: file_path.to_string(),
+            dirty: false,
+            load_report: LoadReport::new(),
+        };
+        
+        // Load logs from file if it exists
+        if Path::new(file_path).exists() {
+            repo.load()?;
+        }
+          Ok(repo)
+    }
+    
+    /// Retrieves an immutable reference to a specific day's food log.
+    /// 
+    /// Provides efficient read-only access to daily consumption data without
+    /// creating new log entries. Returns None if no consumption was recorded
+    /// for the specified date.
+    /// 
+    /// # Arguments
+    /// * `date` - The date for which to retrieve the food log
+    /// 
+    /// # Returns
+    /// * `Option<&DailyLog>` - Reference to the daily log if it exists, None otherwise
+    /// 
+    /// # Examples
+    /// ```ignore
+    /// use chrono::NaiveDate;
+    /// let date = NaiveDate::from_ymd(2025, 5, 25);
+    /// if let Some(log) = repo.get_log(date) {
+    ///     println!("Total entries for today: {}", log.entries.len());
+    /// }
+    /// ```
+    pub fn get_log(&self, date: NaiveDate) -> Option<&DailyLog> {        self.logs.get(&date)
+    }
+    
+    /// Retrieves a mutable reference to a specific day's food log, creating it if necessary.
+    /// 
+    /// This method provides write access to daily logs and automatically creates
+    /// new log entries for dates that haven't been accessed before. It's the primary
+    /// method for adding new food entries to daily consumption records.
+    /// 
+    /// # Arguments
+    /// * `date` - The date for which to retrieve or create a food log
+    /// 
+    /// # Returns
+    /// * `&mut DailyLog` - Mutable reference to the daily log (guaranteed to exist)
+    /// 
+    /// # Automatic Creation
+    /// If no log exists for the specified date, this method automatically creates
+    /// a new DailyLog instance, ensuring that callers always receive a valid log.
+    /// The repository is marked dirty since the caller may modify the log.
+    /// 
+    /// # Examples
+    /// ```ignore
+    /// use chrono::NaiveDate;
+    /// let date = NaiveDate::from_ymd(2025, 5, 25);
+    /// let log = repo.get_log_mut(date);
+    /// log.add_entry(food_entry);
+    /// ```
+    pub fn get_log_mut(&mut self, date: NaiveDate) -> &mut DailyLog {
+        self.dirty = true;
+        self.logs.entry(date).or_insert_with(|| DailyLog::new(date))
+    }
+    
+    /// Persists all log data to the configured file in chronological order.
+    /// 
+    /// This method implements the repository's persistence responsibility by
+    /// serializing all daily logs and their entries to a structured text format.
+    /// The output is sorted chronologically for human readability and consistency.
+    /// 
+    /// # Returns
+    /// * `Result<(), io::Error>` - Success confirmation or IO error details
+    /// 
+    /// # File Format
+    /// Each line represents a single food entry in the format:
+    /// `YYYY-MM-DD|food_id|servings|YYYY-MM-DDTHH:MM:SS`
+    /// 
+    /// # Chronological Organization
+    /// - Dates are sorted chronologically in the output file
+    /// - Entries within each day maintain their original temporal order
+    /// - Consistent format enables reliable parsing and analysis
+    /// 
+    /// # Error Handling
+    /// - File creation and write permission issues
+    /// - Disk space limitations
+    /// - Data formatting errors during serialization
+    /// 
+    /// # Data Integrity
+    /// The file is replaced atomically with rotating `.bak.N` backups
+    /// (see `storage::write_atomic`), so a failed save leaves the previous
+    /// version intact.
+    pub fn save(&mut self) -> Result<(), io::Error> {
+        // Serialize into memory first; the file is replaced atomically below
+        let mut buffer = Vec::new();
+        writeln!(buffer, "{}", format::header("logs"))?;
+        
+        // Sort dates for consistent output
+        let mut dates: Vec<&NaiveDate> = self.logs.keys().collect();
+        dates.sort();
+        
+        for date in dates {
+            if let Some(log) = self.logs.get(date) {
+                if !log.note.is_empty() {
+                    write!(buffer, "NOTE|{}", date.format("%Y-%m-%d"))?;
+                    if let Some(mood) = log.note.mood {
+                        write!(buffer, "|mood={}", mood)?;
+                    }
+                    if let Some(energy) = log.note.energy {
+                        write!(buffer, "|energy={}", energy)?;
+                    }
+                    if let Some(text) = &log.note.text {
+                        write!(buffer, "|note={}", format::escape(text))?;
+                    }
+                    writeln!(buffer)?;
+                }
+                for entry in &log.entries {
+                    let quantity = match entry.quantity.unit {
+                        Some(unit) => format!("|amount={}|unit={}", entry.quantity.amount, unit.symbol()),
+                        None => String::new(),
+                    };
+                    let meal = entry.meal.map_or(String::new(), |slot| format!("|meal={}", slot.key()));
+                    let calories = entry.calories.map_or(String::new(), |calories| format!("|kcal={}", calories));
+                    writeln!(
+                        buffer,
+                        "{}|{}|{}|{}{}{}{}|id={}",
+                        date.format("%Y-%m-%d"),
+                        format::escape(&entry.food_id),
+                        entry.servings,
+                        entry.timestamp.format("%Y-%m-%dT%H:%M:%S"),
+                        quantity,
+                        meal,
+                        calories,
+                        entry.entry_id
+                    )?;
+                }
+            }
+        }
+        
+        storage::write_data_file(&self.file_path, &buffer)?;
+        
+        self.dirty = false;
+        Ok(())
+    }
+    
+    /// Loads all log data from the configured file into memory.
+    /// 
+    /// This method reconstructs the complete consumption history from persistent
+    /// storage, parsing each entry and organizing it by date for efficient access.
+    /// It handles data validation and provides error recovery for malformed entries.
+    /// 
+    /// # Returns
+    /// * `Result<(), io::Error>` - Success confirmation or IO error details
+    /// 
+    /// # Loading Process
+    /// 1. **Clear Cache**: Remove any existing in-memory log data
+    /// 2. **Parse File**: Process each line according to the expected format
+    /// 3. **Validate Data**: Ensure dates, IDs, and timestamps are valid
+    /// 4. **Organize Entries**: Group food entries by date into daily logs
+    /// 5. **Maintain Order**: Sort each day's entries by timestamp (ties keep file order)
+    /// 
+    /// # Error Recovery
+    /// - Skips malformed lines to prevent complete loading failure
+    /// - Uses current timestamp as fallback for invalid timestamps
+    /// - Continues processing valid data when encountering errors
+    /// - Provides detailed error information for debugging
+    /// 
+    /// # Data Integrity
+    /// Validates date formats and handles timezone conversions properly
+    /// to ensure accurate temporal representation across different systems.
+    pub fn load(&mut self) -> Result<(), io::Error> {
+        let contents = storage::read_data_file(&self.file_path)?;
+        let reader = contents.as_slice();
+        self.logs.clear();
+        let mut report = LoadReport::new();
+        let mut saved_ids = HashSet::new();
+        
+        for record in schema::records("logs", reader) {
+            let (number, line) = record?;
+            let parts: Vec<&str> = format::split_escaped(&line, '|');
+            
+            if parts[0] == "NOTE" {
+                match parts.get(1).and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()) {
+                    Some(date) => {
+                        let log = self.logs.entry(date).or_insert_with(|| DailyLog::new(date));
+                        log.note = parse_day_note(&parts[2..]);
+                    }
+                    None => report.skip(number, "note without a valid date"),
+                }
+                continue;
+            }
+            
+            if parts.len() < 4 {
+                report.skip(number, "too few fields");
+                continue; // Skip invalid lines
+            }
+            
+            if let Ok(date) = NaiveDate::parse_from_str(parts[0], "%Y-%m-%d") {
+                let food_id = format::unescape(parts[1]);
+                let servings: f64 = report.number(number, "servings", parts[2], 0.0);
+                // Timestamps are written in local time (see `save`)
+                let timestamp = NaiveDateTime::parse_from_str(parts[3], "%Y-%m-%dT%H:%M:%S")
+                    .ok()
+                    .and_then(|time| log::local_timestamp(time.date(), time.time()))
+                    .unwrap_or_else(|| {
+                        report.defaulted(number, format!("time '{}' is not valid; using the current time", parts[3]));
+                        Local::now()
+                    });
+                
+                let quantity = parse_quantity_fields(&parts[4..]).unwrap_or(Quantity::servings(servings));
+                let meal = trailing_field(&parts[4..], "meal").and_then(MealSlot::parse);
+                let calories = trailing_field(&parts[4..], "kcal")
+                    .and_then(|calories| calories.parse::<f64>().ok())
+                    .filter(|calories| calories.is_finite() && *calories >= 0.0);
+                
+                let mut entry = FoodEntry::new(food_id, quantity, servings);
+                entry.timestamp = timestamp;
+                entry.meal = meal;
+                entry.calories = calories;
+                
+                // 0 marks entries that need a new ID once every saved ID is reserved
+                entry.entry_id = trailing_field(&parts[4..], "id")
+                    .and_then(|id| id.parse::<u64>().ok())
+                    .filter(|id| *id > 0 && saved_ids.insert(*id))
+                    .unwrap_or(0);
+                log::reserve_entry_id(entry.entry_id);
+                
+                let log = self.logs.entry(date).or_insert_with(|| DailyLog::new(date));
+                log.entries.push(entry);
+            } else {
+                report.skip(number, format!("invalid date '{}'", parts[0]));
+            }
+        }
+        self.load_report = report;
+        
+        for log in self.logs.values_mut() {
+            log.sort_entries();
+            for entry in log.entries.iter_mut().filter(|entry| entry.entry_id == 0) {
+                entry.entry_id = log::next_entry_id();
+            }
+        }
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Reports whether the in-memory log data has unsaved changes.
+    /// 
+    /// The flag is raised by every mutating operation (including handing out
+    /// mutable access, since callers may change data through it) and cleared
+    /// by a successful `save()` or `load()`. Autosave uses it to skip writing
+    /// files that have not changed.
+    /// 
+    /// # Returns
+    /// * `bool` - True if there are changes that have not been persisted yet
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Returns how many malformed lines the last `load()` skipped.
+    /// 
+    /// Blank lines, the header, and comments are not counted.
+    pub fn skipped_line_count(&self) -> usize {
+        self.load_report.skipped_count()
+    }
+
+    /// Returns what the last `load()` skipped or defaulted, with line numbers.
+    pub fn load_report(&self) -> &LoadReport {
+        &self.load_report
+    }
+
+    /// Returns all logged dates in ascending order.
+    pub fn get_dates(&self) -> Vec<NaiveDate> {
+        let mut dates: Vec<NaiveDate> = self.logs.keys().copied().collect();
+        dates.sort();
+        dates
+    }
+
+    /// Returns all daily logs in ascending date order.
+    pub fn get_logs(&self) -> Vec<&DailyLog> {
+        let mut logs: Vec<&DailyLog> = self.logs.values().collect();
+        logs.sort_by_key(|log| log.date);
+        logs
+    }
+
+    /// Returns the daily logs from `start` to `end` (both included) in ascending date order.
+    ///
+    /// Days without a log are left out; a range whose start is after its end is empty.
+    pub fn get_logs_between(&self, start: NaiveDate, end: NaiveDate) -> Vec<&DailyLog> {
+        let mut logs: Vec<&DailyLog> = self.logs.values()
+            .filter(|log| start <= log.date && log.date <= end)
+            .collect();
+        logs.sort_by_key(|log| log.date);
+        logs
+    }
+
+    /// Returns the dates from `start` to `end` (both included) with at least one
+    /// food entry, in ascending order.
+    ///
+    /// Days with only a note do not count as logged.
+    pub fn days_logged_in(&self, start: NaiveDate, end: NaiveDate) -> Vec<NaiveDate> {
+        self.get_logs_between(start, end)
+            .into_iter()
+            .filter(|log| !log.entries.is_empty())
+            .map(|log| log.date)
+            .collect()
+    }
+
+    /// Returns the calories logged from `start` to `end` (both included).
+    ///
+    /// Each entry counts with the calories it was logged with; entries of
+    /// foods no longer in `food_db` count as zero (see `DailyLog::total_calories`).
+    pub fn total_calories_between(&self, start: NaiveDate, end: NaiveDate, food_db: &HashMap<String, Food>) -> f64 {
+        self.get_logs_between(start, end)
+            .into_iter()
+            .map(|log| log.total_calories(food_db))
+            .sum()
+    }
+
+    /// Returns every entry of a food with the date it was logged for,
+    /// in ascending date order and in time order within a day.
+    pub fn entries_for_food(&self, food_id: &str) -> Vec<(NaiveDate, &FoodEntry)> {
+        self.get_logs()
+            .into_iter()
+            .flat_map(|log| log.entries.iter().map(move |entry| (log.date, entry)))
+            .filter(|(_, entry)| entry.food_id == food_id)
+            .collect()
+    }
+
+    /// Returns the IDs of the foods logged on any date.
+    pub fn food_ids(&self) -> HashSet<String> {
+        self.logs.values().flat_map(|log| log.entries.iter().map(|entry| entry.food_id.clone())).collect()
+    }
+
+    /// Returns the dates that logged any of the given foods, in ascending order.
+    /// 
+    /// These are the days whose totals would follow a change to the foods,
+    /// such as a basic food and the recipes recalculated with it (see
+    /// `FoodRepository::update_food`). Their calorie totals keep the
+    /// snapshots taken when the entries were logged (see `backfill_calories`);
+    /// macro and cost totals use the current food values.
+    pub fn dates_with_foods(&self, food_ids: &HashSet<String>) -> Vec<NaiveDate> {
+        let mut dates: Vec<NaiveDate> = self.logs.values()
+            .filter(|log| log.entries.iter().any(|entry| food_ids.contains(&entry.food_id)))
+            .map(|log| log.date)
+            .collect();
+        dates.sort();
+        dates
+    }
+
+    /// Records the calories of entries logged before calorie snapshots existed.
+    /// 
+    /// Entries without a snapshot get one from the current values of their
+    /// food, so later changes to the food no longer change their day's total.
+    /// Entries whose food is unknown are left alone. Run once after loading
+    /// both the log and the food database; the log is marked dirty if
+    /// anything was recorded, so the snapshots are saved.
+    /// 
+    /// # Arguments
+    /// * `food_db` - Current food definitions
+    /// 
+    /// # Returns
+    /// The number of entries given a snapshot
+    pub fn backfill_calories(&mut self, food_db: &HashMap<String, Food>) -> usize {
+        let mut recorded = 0;
+        for entry in self.logs.values_mut().flat_map(|log| log.entries.iter_mut()) {
+            if entry.calories.is_none() && let Some(food) = food_db.get(&entry.food_id) {
+                entry.record_calories(food);
+                recorded += 1;
+            }
+        }
+        if recorded > 0 {
+            self.dirty = true;
+        }
+        recorded
+    }
+
+    /// Points every entry for one food at another food, as when merging duplicates.
+    /// 
+    /// # Arguments
+    /// * `from` - ID of the food whose entries are rewritten
+    /// * `to` - ID the entries refer to afterwards
+    /// 
+    /// # Returns
+    /// * `Vec<(NaiveDate, usize)>` - Date and index of every rewritten entry in
+    ///   date order, so the change can be reverted with `set_food_ids`
+    pub fn replace_food_id(&mut self, from: &str, to: &str) -> Vec<(NaiveDate, usize)> {
+        let mut positions = Vec::new();
+        for log in self.logs.values_mut() {
+            for (index, entry) in log.entries.iter_mut().enumerate() {
+                if entry.food_id == from {
+                    entry.food_id = to.to_string();
+                    positions.push((log.date, index));
+                }
+            }
+        }
+        if !positions.is_empty() {
+            self.dirty = true;
+        }
+        positions.sort();
+        positions
+    }
+
+    /// Sets the food ID of the entries at the given positions.
+    /// 
+    /// Positions that no longer exist are ignored.
+    /// 
+    /// # Arguments
+    /// * `positions` - Date and index of each entry, as returned by `replace_food_id`
+    /// * `food_id` - ID the entries refer to afterwards
+    pub fn set_food_ids(&mut self, positions: &[(NaiveDate, usize)], food_id: &str) {
+        for (date, index) in positions {
+            if let Some(entry) = self.logs.get_mut(date).and_then(|log| log.entries.get_mut(*index)) {
+                entry.food_id = food_id.to_string();
+                self.dirty = true;
+            }
+        }
+    }
+
+    /// Returns the most frequently logged foods, for quick logging.
+    /// 
+    /// Ties are broken by recency, so of two foods logged equally often the
+    /// one eaten last comes first.
+    /// 
+    /// # Arguments
+    /// * `limit` - Maximum number of foods to return
+    /// 
+    /// # Returns
+    /// * `Vec<LoggedFood>` - Foods by number of entries, most used first
+    pub fn most_used_foods(&self, limit: usize) -> Vec<LoggedFood> {
+        let mut foods = self.logged_foods();
+        foods.sort_by(|a, b| b.0.uses.cmp(&a.0.uses).then_with(|| b.1.cmp(&a.1)));
+        foods.into_iter().take(limit).map(|(food, _)| food).collect()
+    }
+
+    /// Returns the most recently logged foods, for quick logging.
+    /// 
+    /// Recency follows the date an entry was logged for, then its timestamp.
+    /// 
+    /// # Arguments
+    /// * `limit` - Maximum number of foods to return
+    /// 
+    /// # Returns
+    /// * `Vec<LoggedFood>` - Foods by their latest entry, most recent first
+    pub fn recent_foods(&self, limit: usize) -> Vec<LoggedFood> {
+        let mut foods = self.logged_foods();
+        foods.sort_by_key(|(_, latest)| Reverse(*latest));
+        foods.into_iter().take(limit).map(|(food, _)| food).collect()
+    }
+
+    /// Collects usage per food along with the date and time of its latest entry
+    fn logged_foods(&self) -> Vec<(LoggedFood, (NaiveDate, DateTime<Local>))> {
+        let mut foods: HashMap<&str, (LoggedFood, (NaiveDate, DateTime<Local>))> = HashMap::new();
+        for log in self.logs.values() {
+            for entry in &log.entries {
+                let logged_at = (log.date, entry.timestamp);
+                let (food, latest) = foods.entry(entry.food_id.as_str()).or_insert_with(|| {
+                    let food = LoggedFood {
+                        food_id: entry.food_id.clone(),
+                        uses: 0,
+                        last_date: log.date,
+                        last_quantity: entry.quantity,
+                    };
+                    (food, logged_at)
+                });
+                food.uses += 1;
+                if logged_at > *latest {
+                    *latest = logged_at;
+                    food.last_date = log.date;
+                    food.last_quantity = entry.quantity;
+                }
+            }
+        }
+        // Ties on every key are ordered by ID so results do not depend on hashing
+        let mut foods: Vec<_> = foods.into_values().collect();
+        foods.sort_by(|a, b| a.0.food_id.cmp(&b.0.food_id));
+        foods
+    }
+}
+/// Reads the optional trailing `amount=` and `unit=` fields of an entry
+/// 
+/// # Returns
+/// * `Some(Quantity)` - If both fields are present and valid
+/// * `None` - For entries logged in servings (or with unreadable fields)
+fn parse_quantity_fields(fields: &[&str]) -> Option<Quantity> {
+    let amount: f64 = trailing_field(fields, "amount")?.parse().ok().filter(|amount| *amount > 0.0)?;
+    let unit = ServingUnit::parse(trailing_field(fields, "unit")?)?;
+    Some(Quantity::in_unit(amount, unit))
+}
+
+/// Reads the fields of a `NOTE` line after its date
+/// 
+/// Ratings outside `RATING_MIN..=RATING_MAX` are dropped.
+fn parse_day_note(fields: &[&str]) -> DayNote {
+    let rating = |key| trailing_field(fields, key).and_then(|rating| log::parse_rating(rating).ok());
+    DayNote {
+        text: trailing_field(fields, "note").map(format::unescape).filter(|text| !text.is_empty()),
+        mood: rating("mood"),
+        energy: rating("energy"),
+    }
+}
+
+/// Returns the value of a trailing `key=value` field of an entry, if present
+fn trailing_field<'a>(fields: &[&'a str], key: &str) -> Option<&'a str> {
+    fields
+        .iter()
+        .filter_map(|field| format::split_once_escaped(field, '='))
+        .find(|(name, _)| name.trim() == key)
+        .map(|(_, value)| value.trim())
+}
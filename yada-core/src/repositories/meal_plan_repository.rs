@@ -0,0 +1,207 @@
+//! # Meal Plan Repository
+//!
+//! This module implements the Repository Pattern for meal plans. Each date
+//! has at most one plan; plans are kept in memory and the file is rewritten
+//! atomically on save.
+//!
+//! ## File Format Specification
+//!
+//! ```text
+//! YYYY-MM-DD|slot|food_id|servings
+//! ```
+//!
+//! The first line is the format header `#yada:meal_plans:v1`, one line is
+//! written per planned food in date order, and food IDs are
+//! backslash-escaped (see `repositories::format`).
+
+// yada-core/src/repositories/meal_plan_repository.rs
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Write};
+use std::path::Path;
+use chrono::NaiveDate;
+
+use crate::models::meal::{MealPlan, MealSlot};
+use crate::repositories::{format, schema, storage};
+use crate::repositories::load_report::LoadReport;
+
+/// Repository of meal plans organized by date
+pub struct MealPlanRepository {
+    /// The plan for each date that has one
+    plans: HashMap<NaiveDate, MealPlan>,
+    /// File system path for persistent storage
+    file_path: String,
+    /// Whether in-memory data has changed since the last save or load
+    dirty: bool,
+    /// Lines skipped by the last load
+    load_report: LoadReport,
+}
+
+impl MealPlanRepository {
+    /// Creates a repository and loads existing plans if the file exists
+    ///
+    /// # Arguments
+    /// * `file_path` - Path to the file where meal plans are stored
+    ///
+    /// # Returns
+    /// * `Result<Self, io::Error>` - A new repository instance or an IO error if loading fails
+    pub fn new(file_path: &str) -> Result<Self, io::Error> {
+        let mut repo = MealPlanRepository {
+            plans: HashMap::new(),
+            file_path: file_path.to_string(),
+            dirty: false,
+            load_report: LoadReport::new(),
+        };
+
+        if Path::new(file_path).exists() {
+            repo.load()?;
+        }
+        Ok(repo)
+    }
+
+    /// Returns the plan for a date, if one exists
+    pub fn get_plan(&self, date: NaiveDate) -> Option<&MealPlan> {
+        self.plans.get(&date)
+    }
+
+    /// Returns the IDs of the foods planned on any date
+    pub fn food_ids(&self) -> HashSet<String> {
+        self.plans.values().flat_map(|plan| plan.items.iter().map(|item| item.food_id.clone())).collect()
+    }
+
+    /// Replaces the plan for a date; an empty or missing plan removes it
+    ///
+    /// # Returns
+    /// The plan that was stored for the date before, if any
+    pub fn set_plan(&mut self, date: NaiveDate, plan: Option<MealPlan>) -> Option<MealPlan> {
+        self.dirty = true;
+        match plan.filter(|plan| !plan.items.is_empty()) {
+            Some(plan) => self.plans.insert(date, plan),
+            None => self.plans.remove(&date),
+        }
+    }
+
+    /// Points every planned item for one food at another food
+    ///
+    /// # Returns
+    /// Date and index of every rewritten item in date order, so the change
+    /// can be reverted with `set_food_ids`
+    pub fn replace_food_id(&mut self, from: &str, to: &str) -> Vec<(NaiveDate, usize)> {
+        let mut positions = Vec::new();
+        for plan in self.plans.values_mut() {
+            for (index, item) in plan.items.iter_mut().enumerate() {
+                if item.food_id == from {
+                    item.food_id = to.to_string();
+                    positions.push((plan.date, index));
+                }
+            }
+        }
+        if !positions.is_empty() {
+            self.dirty = true;
+        }
+        positions.sort();
+        positions
+    }
+
+    /// Sets the food ID of the planned items at the given positions, ignoring missing ones
+    pub fn set_food_ids(&mut self, positions: &[(NaiveDate, usize)], food_id: &str) {
+        for (date, index) in positions {
+            if let Some(item) = self.plans.get_mut(date).and_then(|plan| plan.items.get_mut(*index)) {
+                item.food_id = food_id.to_string();
+                self.dirty = true;
+            }
+        }
+    }
+
+    /// Persists all plans in date order
+    ///
+    /// # Returns
+    /// * `Result<(), io::Error>` - Success confirmation or IO error details
+    ///
+    /// The file is replaced atomically with rotating `.bak.N` backups
+    /// (see `storage::write_atomic`).
+    pub fn save(&mut self) -> Result<(), io::Error> {
+        let mut buffer = Vec::new();
+        writeln!(buffer, "{}", format::header("meal_plans"))?;
+
+        let mut dates: Vec<&NaiveDate> = self.plans.keys().collect();
+        dates.sort();
+        for date in dates {
+            for item in &self.plans[date].items {
+                writeln!(
+                    buffer,
+                    "{}|{}|{}|{}",
+                    date.format("%Y-%m-%d"),
+                    item.slot.key(),
+                    format::escape(&item.food_id),
+                    item.servings
+                )?;
+            }
+        }
+
+        storage::write_data_file(&self.file_path, &buffer)?;
+
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Loads all plans from the configured file, skipping malformed lines
+    ///
+    /// # Returns
+    /// * `Result<(), io::Error>` - Success confirmation or IO error details
+    pub fn load(&mut self) -> Result<(), io::Error> {
+        let contents = storage::read_data_file(&self.file_path)?;
+        let reader = contents.as_slice();
+        self.plans.clear();
+        let mut report = LoadReport::new();
+
+        for record in schema::records("meal_plans", reader) {
+            let (number, line) = record?;
+            let parts: Vec<&str> = format::split_escaped(&line, '|');
+            match parse_item(&parts) {
+                Some((date, slot, food_id, servings)) => self.plans
+                    .entry(date)
+                    .or_insert_with(|| MealPlan::new(date))
+                    .add_item(slot, food_id, servings),
+                None => report.skip(number, "not a valid planned food"),
+            }
+        }
+        self.load_report = report;
+
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Reports whether there are changes that have not been saved yet
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Returns how many malformed lines the last `load()` skipped
+    pub fn skipped_line_count(&self) -> usize {
+        self.load_report.skipped_count()
+    }
+
+    /// Returns what the last `load()` skipped, with line numbers
+    pub fn load_report(&self) -> &LoadReport {
+        &self.load_report
+    }
+}
+
+/// Parses the fields of one planned food line
+///
+/// # Returns
+/// * `Some((date, slot, food_id, servings))` - The planned food
+/// * `None` - If a field is missing or not a valid value
+fn parse_item(parts: &[&str]) -> Option<(NaiveDate, MealSlot, String, f64)> {
+    if parts.len() < 4 {
+        return None;
+    }
+    let date = NaiveDate::parse_from_str(parts[0], "%Y-%m-%d").ok()?;
+    let slot = MealSlot::parse(parts[1])?;
+    let food_id = format::unescape(parts[2]);
+    let servings = parts[3].parse::<f64>().ok().filter(|servings| *servings > 0.0)?;
+    if food_id.is_empty() {
+        return None;
+    }
+    Some((date, slot, food_id, servings))
+}
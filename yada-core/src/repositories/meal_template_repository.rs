@@ -0,0 +1,217 @@
+//! # Meal Template Repository
+//!
+//! This module implements the Repository Pattern for meal templates (see
+//! `models::meal_template`). Templates are kept in memory and the file is
+//! rewritten atomically on save.
+//!
+//! ## File Format Specification
+//!
+//! ```text
+//! name|meal|food_id|servings
+//! ```
+//!
+//! The first line is the format header `#yada:meal_templates:v1`, one line
+//! is written per food in template order with templates sorted by name, and
+//! `meal` is `-` for templates without a usual meal. Names and food IDs are
+//! backslash-escaped (see `repositories::format`).
+
+// yada-core/src/repositories/meal_template_repository.rs
+use std::collections::HashSet;
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::models::meal::MealSlot;
+use crate::models::meal_template::MealTemplate;
+use crate::repositories::{format, schema, storage};
+use crate::repositories::load_report::LoadReport;
+
+/// Repository of meal templates
+pub struct MealTemplateRepository {
+    /// Templates sorted by name, ignoring case
+    templates: Vec<MealTemplate>,
+    /// File system path for persistent storage
+    file_path: String,
+    /// Whether in-memory data has changed since the last save or load
+    dirty: bool,
+    /// Lines skipped by the last load
+    load_report: LoadReport,
+}
+
+impl MealTemplateRepository {
+    /// Creates a repository and loads existing templates if the file exists
+    ///
+    /// # Arguments
+    /// * `file_path` - Path to the file where meal templates are stored
+    ///
+    /// # Returns
+    /// * `Result<Self, io::Error>` - A new repository instance or an IO error if loading fails
+    pub fn new(file_path: &str) -> Result<Self, io::Error> {
+        let mut repo = MealTemplateRepository {
+            templates: Vec::new(),
+            file_path: file_path.to_string(),
+            dirty: false,
+            load_report: LoadReport::new(),
+        };
+
+        if Path::new(file_path).exists() {
+            repo.load()?;
+        }
+        Ok(repo)
+    }
+
+    /// Returns all templates sorted by name
+    pub fn templates(&self) -> &[MealTemplate] {
+        &self.templates
+    }
+
+    /// Finds a template by name, ignoring case
+    pub fn get_template(&self, name: &str) -> Option<&MealTemplate> {
+        self.templates.iter().find(|template| template.name.eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Returns the IDs of the foods used by any template
+    pub fn food_ids(&self) -> HashSet<String> {
+        self.templates.iter().flat_map(|template| template.items.iter().map(|item| item.food_id.clone())).collect()
+    }
+
+    /// Stores a template, replacing one with the same name
+    ///
+    /// # Returns
+    /// * `Ok(Some(MealTemplate))` - The template that was replaced
+    /// * `Ok(None)` - If the template was added
+    /// * `Err(String)` - If the name is empty or has control characters,
+    ///   the template has no foods, or a food has no positive servings
+    pub fn save_template(&mut self, mut template: MealTemplate) -> Result<Option<MealTemplate>, String> {
+        template.name = template.name.trim().to_string();
+        if template.name.is_empty() {
+            return Err("A meal template needs a name".to_string());
+        }
+        format::validate_text("Template name", &template.name)?;
+        if template.items.is_empty() {
+            return Err("A meal template needs at least one food".to_string());
+        }
+        if let Some(item) = template.items.iter().find(|item| !(item.servings > 0.0 && item.servings.is_finite())) {
+            return Err(format!("Servings of '{}' must be a positive number", item.food_id));
+        }
+
+        let replaced = self.remove_template(&template.name);
+        let index = self.templates
+            .partition_point(|other| other.name.to_lowercase() < template.name.to_lowercase());
+        self.templates.insert(index, template);
+        self.dirty = true;
+        Ok(replaced)
+    }
+
+    /// Removes a template by name, ignoring case
+    ///
+    /// # Returns
+    /// The removed template, or `None` if no template has the name
+    pub fn remove_template(&mut self, name: &str) -> Option<MealTemplate> {
+        let index = self.templates.iter().position(|template| template.name.eq_ignore_ascii_case(name.trim()))?;
+        self.dirty = true;
+        Some(self.templates.remove(index))
+    }
+
+    /// Persists all templates sorted by name
+    ///
+    /// # Returns
+    /// * `Result<(), io::Error>` - Success confirmation or IO error details
+    ///
+    /// The file is replaced atomically with rotating `.bak.N` backups
+    /// (see `storage::write_atomic`).
+    pub fn save(&mut self) -> Result<(), io::Error> {
+        let mut buffer = Vec::new();
+        writeln!(buffer, "{}", format::header("meal_templates"))?;
+
+        for template in &self.templates {
+            let meal = template.meal.map_or("-", |slot| slot.key());
+            for item in &template.items {
+                writeln!(
+                    buffer,
+                    "{}|{}|{}|{}",
+                    format::escape(&template.name),
+                    meal,
+                    format::escape(&item.food_id),
+                    item.servings
+                )?;
+            }
+        }
+
+        storage::write_data_file(&self.file_path, &buffer)?;
+
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Loads all templates from the configured file, skipping malformed lines
+    ///
+    /// The meal of a template's first line applies to the whole template.
+    ///
+    /// # Returns
+    /// * `Result<(), io::Error>` - Success confirmation or IO error details
+    pub fn load(&mut self) -> Result<(), io::Error> {
+        let contents = storage::read_data_file(&self.file_path)?;
+        let reader = contents.as_slice();
+        self.templates.clear();
+        let mut report = LoadReport::new();
+
+        for record in schema::records("meal_templates", reader) {
+            let (number, line) = record?;
+            let parts: Vec<&str> = format::split_escaped(&line, '|');
+            let Some((name, meal, food_id, servings)) = parse_item(&parts) else {
+                report.skip(number, "not a valid template food");
+                continue;
+            };
+            match self.templates.iter_mut().find(|template| template.name.eq_ignore_ascii_case(&name)) {
+                Some(template) => template.add_item(food_id, servings),
+                None => {
+                    let mut template = MealTemplate::new(name, meal);
+                    template.add_item(food_id, servings);
+                    self.templates.push(template);
+                }
+            }
+        }
+        self.templates.sort_by_key(|template| template.name.to_lowercase());
+        self.load_report = report;
+
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Reports whether there are changes that have not been saved yet
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Returns how many malformed lines the last `load()` skipped
+    pub fn skipped_line_count(&self) -> usize {
+        self.load_report.skipped_count()
+    }
+
+    /// Returns what the last `load()` skipped, with line numbers
+    pub fn load_report(&self) -> &LoadReport {
+        &self.load_report
+    }
+}
+
+/// Parses the fields of one template food line
+///
+/// # Returns
+/// * `Some((name, meal, food_id, servings))` - The template food
+/// * `None` - If a field is missing or not a valid value
+fn parse_item(parts: &[&str]) -> Option<(String, Option<MealSlot>, String, f64)> {
+    if parts.len() < 4 {
+        return None;
+    }
+    let name = format::unescape(parts[0]).trim().to_string();
+    let meal = match parts[1].trim() {
+        "-" | "" => None,
+        slot => Some(MealSlot::parse(slot)?),
+    };
+    let food_id = format::unescape(parts[2]);
+    let servings = parts[3].parse::<f64>().ok().filter(|servings| *servings > 0.0 && servings.is_finite())?;
+    if name.is_empty() || food_id.is_empty() {
+        return None;
+    }
+    Some((name, meal, food_id, servings))
+}
@@ -0,0 +1,326 @@
+//! # Data File Migration
+//!
+//! This module converts data files written by older versions of YADA into
+//! the canonical format described in `repositories::format`: a versioned
+//! header, records in sorted order, and escaped delimiter characters.
+//!
+//! ## Process
+//!
+//! 1. **Inspect**: Read the header of `foods.txt`, `logs.txt`, and `profile.txt`
+//! 2. **Load and Validate**: Load each file through its repository, counting
+//!    skipped lines and collecting warnings (unknown food references,
+//!    impossible values)
+//! 3. **Back Up**: Copy each file that will be rewritten to `<file>.pre-migration`
+//!    (or `.pre-migration.N` if that name is taken)
+//! 4. **Rewrite**: Save the repository, producing the canonical format
+//!
+//! Records of older versions are upgraded while loading by the steps in
+//! `repositories::schema`; the report lists the steps each file needed.
+//! Log entries without a calorie snapshot are given one from the current
+//! food values (see `LogRepository::backfill_calories`), which rewrites an
+//! otherwise current log file.
+//!
+//! Files that already carry the current header are validated but left
+//! untouched unless a rewrite is forced. Files written by a newer version
+//! are never touched.
+//!
+//! ## Data Loss
+//!
+//! Lines the loaders cannot parse are dropped from the rewritten file. They
+//! are counted in the report and remain available in the backup copy.
+
+// yada-core/src/repositories/migration.rs
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, BufRead};
+use std::path::Path;
+
+use crate::models::food::{Food, FoodType};
+use crate::models::validation;
+use crate::repositories::food_repository::FoodRepository;
+use crate::repositories::format::{self, FORMAT_VERSION};
+use crate::repositories::log_repository::LogRepository;
+use crate::repositories::profile_repository::ProfileRepository;
+use crate::repositories::{schema, storage};
+
+/// Suffix of the copies made before a file is rewritten
+const BACKUP_SUFFIX: &str = "pre-migration";
+
+/// What happened (or would happen) to one data file
+#[derive(Debug, Clone, PartialEq)]
+pub enum MigrationStatus {
+    /// The file does not exist; nothing to do
+    Missing,
+
+    /// The file already uses the current format and was left alone
+    UpToDate,
+
+    /// The file was backed up and rewritten in the current format
+    Migrated,
+
+    /// Dry run: the file would be backed up and rewritten
+    WouldMigrate,
+
+    /// The file was written by a newer version of YADA and was left alone
+    Unsupported(u32),
+}
+
+/// Migration result for one data file
+#[derive(Debug, Clone)]
+pub struct FileMigration {
+    /// Path of the data file
+    pub path: String,
+
+    /// Format version found in the header (`None` for legacy files without one)
+    pub found_version: Option<u32>,
+
+    /// Outcome for this file
+    pub status: MigrationStatus,
+
+    /// Number of records loaded (foods, log entries, or profile lines)
+    pub records: usize,
+
+    /// Number of lines that could not be loaded and are dropped on rewrite
+    pub skipped_lines: usize,
+
+    /// Upgrade steps applied to the records, oldest first
+    pub upgrades: Vec<&'static str>,
+
+    /// Problems found in the loaded data that migration does not fix
+    pub warnings: Vec<String>,
+
+    /// Where the original file was copied to, if it was rewritten
+    pub backup_path: Option<String>,
+}
+
+/// Migration results for all data files
+#[derive(Debug, Clone)]
+pub struct MigrationReport {
+    /// Results for the food, log, and profile files, in that order
+    pub files: Vec<FileMigration>,
+}
+
+impl MigrationReport {
+    /// Returns true if any file was (or in a dry run would be) rewritten
+    pub fn changed_anything(&self) -> bool {
+        self.files.iter().any(|file| {
+            matches!(file.status, MigrationStatus::Migrated | MigrationStatus::WouldMigrate)
+        })
+    }
+}
+
+/// Validates and rewrites the three data files in the canonical format
+///
+/// # Arguments
+/// * `foods_path` - Path of the food database
+/// * `logs_path` - Path of the food log
+/// * `profile_path` - Path of the user profile
+/// * `dry_run` - Only report what would happen; write nothing
+/// * `force` - Also rewrite files that already use the current format
+///
+/// # Returns
+/// * `Ok(MigrationReport)` - What happened to each file
+/// * `Err(String)` - If a file cannot be read, backed up, or written
+pub fn migrate_data_files(
+    foods_path: &str,
+    logs_path: &str,
+    profile_path: &str,
+    dry_run: bool,
+    force: bool,
+) -> Result<MigrationReport, String> {
+    let mut food_repo = FoodRepository::new(foods_path)
+        .map_err(|e| format!("Error reading {}: {}", foods_path, e))?;
+    let mut log_repo = LogRepository::new(logs_path)
+        .map_err(|e| format!("Error reading {}: {}", logs_path, e))?;
+    let mut profile_repo = ProfileRepository::new(profile_path)
+        .map_err(|e| format!("Error reading {}: {}", profile_path, e))?;
+
+    let foods = food_repo.get_foods();
+
+    let mut food_file = inspect_file(foods_path, "foods", force, dry_run)?;
+    food_file.records = foods.len();
+    food_file.skipped_lines = food_repo.skipped_line_count();
+    food_file.warnings = validate_foods(foods);
+
+    let mut log_file = inspect_file(logs_path, "logs", force, dry_run)?;
+    log_file.records = log_repo
+        .get_dates()
+        .iter()
+        .filter_map(|date| log_repo.get_log(*date))
+        .map(|log| log.entries.len())
+        .sum();
+    log_file.skipped_lines = log_repo.skipped_line_count();
+    log_file.warnings = validate_logs(&log_repo, foods);
+    if log_repo.backfill_calories(foods) > 0 {
+        if log_file.status == MigrationStatus::UpToDate {
+            log_file.status = if dry_run { MigrationStatus::WouldMigrate } else { MigrationStatus::Migrated };
+        }
+        if matches!(log_file.status, MigrationStatus::Migrated | MigrationStatus::WouldMigrate) {
+            log_file.upgrades.push("record calorie snapshots for entries logged before they existed");
+        }
+    }
+
+    let mut profile_file = inspect_file(profile_path, "profile", force, dry_run)?;
+    profile_file.records = profile_repo
+        .get_profile()
+        .map_or(0, |profile| 1 + profile.daily_profiles.len());
+    profile_file.skipped_lines = profile_repo.skipped_line_count();
+    profile_file.warnings = validate_profile(&profile_repo);
+
+    if !dry_run {
+        if food_file.status == MigrationStatus::Migrated {
+            food_file.backup_path = Some(back_up(foods_path)?);
+            food_repo.save().map_err(|e| format!("Error writing {}: {}", foods_path, e))?;
+        }
+        if log_file.status == MigrationStatus::Migrated {
+            log_file.backup_path = Some(back_up(logs_path)?);
+            log_repo.save().map_err(|e| format!("Error writing {}: {}", logs_path, e))?;
+        }
+        if profile_file.status == MigrationStatus::Migrated {
+            profile_file.backup_path = Some(back_up(profile_path)?);
+            profile_repo.save().map_err(|e| format!("Error writing {}: {}", profile_path, e))?;
+        }
+    }
+
+    Ok(MigrationReport {
+        files: vec![food_file, log_file, profile_file],
+    })
+}
+
+/// Reads the format version from a data file's header
+///
+/// # Returns
+/// * `Ok(Some(version))` - The version named in the header
+/// * `Ok(None)` - The file has no header (legacy format) or is empty
+/// * `Err(io::Error)` - The file cannot be read
+pub fn file_format_version(path: &str) -> Result<Option<u32>, io::Error> {
+    let contents = storage::read_data_file(path)?;
+    for line in contents.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        return Ok(format::parse_header(&line).map(|(_, version)| version));
+    }
+    Ok(None)
+}
+
+/// Decides what should happen to one file based on its header
+fn inspect_file(path: &str, kind: &str, force: bool, dry_run: bool) -> Result<FileMigration, String> {
+    let mut file = FileMigration {
+        path: path.to_string(),
+        found_version: None,
+        status: MigrationStatus::Missing,
+        records: 0,
+        skipped_lines: 0,
+        upgrades: Vec::new(),
+        warnings: Vec::new(),
+        backup_path: None,
+    };
+
+    if !Path::new(path).exists() {
+        return Ok(file);
+    }
+
+    file.found_version = file_format_version(path)
+        .map_err(|e| format!("Error reading {}: {}", path, e))?;
+    file.status = match file.found_version {
+        Some(version) if version > FORMAT_VERSION => MigrationStatus::Unsupported(version),
+        Some(version) if version == FORMAT_VERSION && !force => MigrationStatus::UpToDate,
+        _ if dry_run => MigrationStatus::WouldMigrate,
+        _ => MigrationStatus::Migrated,
+    };
+    if !matches!(file.status, MigrationStatus::Unsupported(_)) {
+        file.upgrades = schema::steps_from(kind, file.found_version.unwrap_or(0))
+            .into_iter()
+            .map(|step| step.description)
+            .collect();
+    }
+    Ok(file)
+}
+
+/// Copies a file to the first free `.pre-migration` name
+///
+/// # Returns
+/// * `Ok(String)` - Path of the backup copy
+/// * `Err(String)` - If the copy fails
+fn back_up(path: &str) -> Result<String, String> {
+    let mut backup = format!("{}.{}", path, BACKUP_SUFFIX);
+    let mut index = 2;
+    while Path::new(&backup).exists() {
+        backup = format!("{}.{}.{}", path, BACKUP_SUFFIX, index);
+        index += 1;
+    }
+
+    fs::copy(path, &backup).map_err(|e| format!("Error backing up {}: {}", path, e))?;
+    Ok(backup)
+}
+
+/// Finds foods with impossible values or unknown components
+fn validate_foods(foods: &HashMap<String, Food>) -> Vec<String> {
+    let mut ids: Vec<&String> = foods.keys().collect();
+    ids.sort();
+
+    let mut warnings = Vec::new();
+    for id in ids {
+        let food = &foods[id];
+        if id.chars().any(char::is_whitespace) {
+            warnings.push(format!("Food ID '{}' contains whitespace", id));
+        }
+        if food.food_type == FoodType::Basic && food.calories_per_serving < 0.0 {
+            warnings.push(format!("Food '{}' has negative calories", id));
+        }
+        for (component, servings) in &food.components {
+            if !foods.contains_key(component) {
+                warnings.push(format!("Composite food '{}' uses unknown food '{}'", id, component));
+            }
+            if *servings <= 0.0 {
+                warnings.push(format!("Composite food '{}' uses {} servings of '{}'", id, servings, component));
+            }
+        }
+    }
+    warnings
+}
+
+/// Finds log entries for unknown foods, with non-positive servings, or in units
+/// their food can no longer be measured in
+fn validate_logs(log_repo: &LogRepository, foods: &HashMap<String, Food>) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for date in log_repo.get_dates() {
+        let Some(log) = log_repo.get_log(date) else {
+            continue;
+        };
+        for entry in &log.entries {
+            if !foods.contains_key(&entry.food_id) {
+                warnings.push(format!("{}: entry for unknown food '{}'", date, entry.food_id));
+            }
+            if entry.servings <= 0.0 {
+                warnings.push(format!("{}: {} servings of '{}'", date, entry.servings, entry.food_id));
+            }
+            if let Some(food) = foods.get(&entry.food_id)
+                && let Err(e) = entry.quantity.to_servings(food)
+            {
+                warnings.push(format!("{}: {} Using the {} servings recorded when logged.", date, e, entry.servings));
+            }
+        }
+    }
+    warnings
+}
+
+/// Finds impossible body measurements in the profile
+fn validate_profile(profile_repo: &ProfileRepository) -> Vec<String> {
+    let Some(profile) = profile_repo.get_profile() else {
+        return Vec::new();
+    };
+
+    let mut warnings = Vec::new();
+    if profile.height.is_none() {
+        warnings.push("Height is missing or out of range; targets use a population default".to_string());
+    }
+    for daily in &profile.daily_profiles {
+        if let Err(e) = validation::weight_kg(daily.weight) {
+            warnings.push(format!("{}: weight {} kg is out of range. {}", daily.date, daily.weight, e));
+        }
+    }
+    warnings
+}
@@ -34,10 +34,48 @@
 //! ## Module Organization
 //! 
 //! - `food_repository`: Manages the food database with composite pattern support
+//! - `search_index`: Inverted index from keywords and name words to foods
+//! - `dependency_graph`: Composite foods by the foods they are built from, for recalculation
 //! - `log_repository`: Handles daily food consumption logs with temporal organization
+//! - `exercise_repository`: Stores exercise entries by date
+//! - `meal_plan_repository`: Stores the planned foods per meal slot for each date
+//! - `meal_template_repository`: Stores named lists of foods logged together
 //! - `profile_repository`: Manages user profile data with validation and history
+//! - `settings_repository`: App-wide settings shared by all users, such as reminders
+//! - `autosave`: Policy deciding when dirty repositories are persisted automatically
+//! - `storage`: Atomic file writes with rotating backups shared by all repositories
+//! - `encryption`: Opt-in password encryption of the data files
+//! - `format`: Versioned header and delimiter escaping shared by all data files
+//! - `schema`: Step-wise upgrades of records written in older format versions
+//! - `migration`: One-time conversion of legacy data files to the canonical format
+//! - `integrity`: Dangling food references and impossible daily profiles, with repairs
+//! - `recovery`: Restoring, setting aside, and checking data files that fail to load
+//! - `load_report`: Lines skipped and values defaulted while loading, and strict loading
+//! - `lock`: Lock file keeping a second instance out of a data directory in use
+//! - `audit_log`: Append-only record of executed and undone commands (`audit.log`)
+//! - `bundle`: Zip archive of all data files for backups and moving to another computer
+//! - `food_pack`: Installing and uninstalling curated packs of branded foods
 
 // Repository modules for data persistence (Repository Pattern implementation)
 pub mod food_repository;
+pub mod search_index;
+pub mod dependency_graph;
 pub mod log_repository;
-pub mod profile_repository;
\ No newline at end of file
+pub mod exercise_repository;
+pub mod meal_plan_repository;
+pub mod meal_template_repository;
+pub mod profile_repository;
+pub mod settings_repository;
+pub mod autosave;
+pub mod storage;
+pub mod encryption;
+pub mod format;
+pub mod schema;
+pub mod migration;
+pub mod integrity;
+pub mod recovery;
+pub mod load_report;
+pub mod lock;
+pub mod audit_log;
+pub mod bundle;
+pub mod food_pack;
@@ -0,0 +1,649 @@
+//! # Profile Repository
+//! 
+//! This module implements the Repository Pattern for managing user profile data,
+//! including both basic profile information and time-varying daily profiles.
+//! It provides centralized management of user data with validation and persistence.
+//! 
+//! ## Repository Pattern Implementation
+//! 
+//! The `ProfileRepository` manages the complete user profile lifecycle:
+//! - **Profile Creation**: Initialize new user profiles with validation
+//! - **Data Management**: Update basic and daily profile information
+//! - **Historical Tracking**: Maintain chronological records of daily changes
+//! - **Persistence**: Reliable storage and retrieval of profile data
+//! - **Data Integrity**: Ensure consistency and validate profile updates
+//! 
+//! ## File Format Specification
+//! 
+//! The repository uses a structured format supporting multiple data types:
+//! 
+//! ### Basic Profile
+//! ```text
+//! PROFILE|gender|height|birth_date|calculation_method
+//! ```
+//! 
+//! An unknown height or birth date is written as an empty field. Such
+//! fields, and ones that do not parse or are out of range (see
+//! `models::validation`), load as `None` (see the low-data mode notes in
+//! `models::profile`).
+//! 
+//! ### Daily Profiles
+//! ```text
+//! DAILY|date|weight|activity_level
+//! ```
+//! 
+//! A daily profile carried forward from an earlier day (see
+//! `UserProfile::carry_forward`) has a trailing attribute naming the day
+//! the values were entered:
+//! ```text
+//! DAILY|2024-03-06|70|M|carried_from=2024-03-05
+//! ```
+//! 
+//! Lines with an invalid date or a weight out of range are skipped.
+//! 
+//! ### Weekday Targets
+//! ```text
+//! TARGET|weekday|override
+//! ```
+//! 
+//! One line per weekday with an override (see `models::weekday_target`),
+//! such as `TARGET|Mon|+300` or `TARGET|Sat|2500`. Weekdays are written
+//! Monday first; lines with an unknown weekday or value are skipped.
+//! 
+//! ### Diet Preset
+//! ```text
+//! PRESET|key
+//! ```
+//! 
+//! Written only when a preset is selected, with the key of the preset
+//! (see `models::diet_preset`), such as `PRESET|cutting`.
+//! 
+//! ### Life Stage
+//! ```text
+//! LIFESTAGE|key|date
+//! ```
+//! 
+//! Written only for a pregnancy or breastfeeding (see `models::life_stage`):
+//! `LIFESTAGE|pregnant|2024-09-01` with the due date, or
+//! `LIFESTAGE|breastfeeding|2024-03-01` with the baby's birth date.
+//! 
+//! ### Macro Targets
+//! ```text
+//! MACROS|percent|protein|carbs|fat
+//! MACROS|grams|protein|carbs|fat
+//! ```
+//! 
+//! Written only when the user set macro targets (see `models::macro_target`).
+//! Percentages that do not add up to 100 are skipped.
+//! 
+//! ### Dietary Restrictions
+//! ```text
+//! RESTRICTIONS|key,key
+//! ```
+//! 
+//! Written only when the user declared restrictions, with their keys (see
+//! `models::dietary`), such as `RESTRICTIONS|gluten-free,vegan`. Lines
+//! naming an unknown restriction are skipped.
+//! 
+//! ### Profile History
+//! ```text
+//! HISTORY|until|gender|height|calculation_method
+//! ```
+//! 
+//! One line per earlier version of the basic profile (see
+//! `models::profile::ProfileVersion`), oldest first, such as
+//! `HISTORY|2024-03-01|M|180|harris_benedict`: the values that applied
+//! before 2024-03-01. Heights and methods are written as in `PROFILE` lines.
+//! 
+//! The first line is the format header `#yada:profile:v1`, daily profiles are
+//! written in date order, and the calculation method is backslash-escaped
+//! (see `repositories::format`).
+//! 
+//! ## Data Validation Features
+//! 
+//! - **Type Safety**: Ensures proper data types for all profile fields
+//! - **Range Validation**: Validates reasonable values for height, weight, dates
+//! - **Enum Mapping**: Safe conversion between storage codes and enum values
+//! - **Default Fallbacks**: Graceful handling of invalid data with sensible defaults
+//! - **Consistency Checks**: Maintains referential integrity between basic and daily profiles
+
+// yada-core/src/repositories/profile_repository.rs
+use std::io::{self, Write};
+use std::path::Path;
+use chrono::{Local, NaiveDate, Weekday};
+
+use crate::repositories::{format, schema, storage};
+use crate::repositories::load_report::LoadReport;
+use crate::models::profile::{UserProfile, DailyProfile, ProfileVersion, Gender, ActivityLevel};
+use crate::models::validation;
+use crate::models::weekday_target::{TargetOverride, WeekdayTargets};
+use crate::models::diet_preset::DietPreset;
+use crate::models::life_stage::LifeStage;
+use crate::models::dietary::{self, DietaryRestriction};
+use crate::models::food::Macros;
+use crate::models::macro_target::{MacroRatios, MacroTargets};
+
+/// # Profile Repository
+/// 
+/// A Repository Pattern implementation for managing user profile data with support
+/// for both static personal information and time-varying daily profiles. This
+/// repository ensures data consistency and provides reliable profile management.
+/// 
+/// ## Core Responsibilities
+/// 
+/// - **Profile Lifecycle**: Create, update, and maintain user profiles
+/// - **Daily Tracking**: Manage time-varying data like weight and activity levels
+/// - **Data Validation**: Ensure profile data meets application requirements
+/// - **Historical Records**: Maintain chronological profile changes
+/// - **Persistence**: Reliable storage and retrieval with error handling
+/// 
+/// ## Profile Data Structure
+/// 
+/// The repository manages a composite profile structure:
+/// - **Basic Profile**: Static data like gender, height, birth date
+/// - **Daily Profiles**: Time-varying data like current weight and activity level
+/// - **Calculation Preferences**: User's preferred calorie calculation method
+/// 
+/// ## Singleton Pattern
+/// 
+/// The repository maintains at most one user profile, reflecting the single-user
+/// nature of the diet tracking application.
+pub struct ProfileRepository {
+    /// The user's profile data (None if no profile has been created)
+    profile: Option<UserProfile>,
+    /// File system path for persistent storage of profile data
+    file_path: String,
+    /// Whether in-memory data has changed since the last save or load
+    dirty: bool,
+    /// Lines skipped and values defaulted by the last load
+    load_report: LoadReport,
+}
+
+impl ProfileRepository {
+    /// Creates a new ProfileRepository instance and loads existing profile data.
+    /// 
+    /// This constructor establishes the repository's connection to persistent storage
+    /// and loads any existing user profile, enabling immediate access to user data.
+    /// 
+    /// # Arguments
+    /// * `file_path` - Path to the file where profile data will be stored and loaded from
+    /// 
+    /// # Returns
+    /// * `Result<Self, io::Error>` - A new repository instance or an IO error if file loading fails
+    /// 
+    /// # Initialization Process
+    /// 1. Initialize repository with empty profile state
+    /// 2. Store file path for future persistence operations
+    /// 3. Load existing profile data if the file exists
+    /// 4. Return fully initialized repository ready for operations
+    pub fn new(file_path: &str) -> Result<Self, io::Error> {
+        let mut repo = ProfileRepository {
+            profile: None,
+            file_path: file_path.to_string(),
+            dirty: false,
+            load_report: LoadReport::new(),
+        };
+        
+        // Load profile from file if it exists
+        if Path::new(file_path).exists() {
+            repo.load()?;
+        }
+          Ok(repo)
+    }
+    
+    /// Retrieves an immutable reference to the user profile.
+    /// 
+    /// Provides read-only access to the complete user profile including both
+    /// basic information and all daily profiles. Returns None if no profile
+    /// has been created for this user.
+    /// 
+    /// # Returns
+    /// * `Option<&UserProfile>` - Reference to the user profile if it exists, None otherwise
+    /// 
+    /// # Examples
+    /// ```ignore
+    /// if let Some(profile) = repo.get_profile() {
+    ///     println!("User height: {:?} cm", profile.height);
+    ///     println!("Number of daily profiles: {}", profile.daily_profiles.len());
+    /// }
+    /// ```
+    pub fn get_profile(&self) -> Option<&UserProfile> {        self.profile.as_ref()
+    }
+    
+    /// Retrieves a mutable reference to the user profile.
+    /// 
+    /// Provides write access to the complete user profile for updating both
+    /// basic information and daily profiles. Returns None if no profile exists.
+    /// 
+    /// # Returns
+    /// * `Option<&mut UserProfile>` - Mutable reference to the user profile if it exists
+    /// 
+    /// # Use Cases
+    /// - Updating basic profile information (height, calculation method)
+    /// - Adding or modifying daily profile entries
+    /// - Batch operations on profile data
+    /// 
+    /// # Examples
+    /// ```ignore
+    /// if let Some(profile) = repo.get_profile_mut() {
+    ///     profile.height = Some(170.0);
+    ///     profile.add_or_update_daily_profile(daily_profile);
+    /// }
+    /// ```
+    pub fn get_profile_mut(&mut self) -> Option<&mut UserProfile> {
+        self.dirty = true;
+        self.profile.as_mut()
+    }
+    
+    /// Sets the user profile, replacing any existing profile.
+    /// 
+    /// This method performs a complete profile replacement, which is typically
+    /// used during initial profile creation or when migrating profile data.
+    /// It replaces both basic information and all daily profiles.
+    /// 
+    /// # Arguments
+    /// * `profile` - The new user profile to store in the repository
+    /// 
+    /// # Behavior
+    /// - Completely replaces any existing profile data
+    /// - Does not merge with existing data
+    /// - Immediately updates the in-memory profile
+    /// - Requires explicit save() call for persistence
+    /// 
+    /// # Examples
+    /// ```ignore
+    /// let new_profile = UserProfile::new(Gender::Female, 165.0, birth_date);
+    /// repo.set_profile(new_profile);
+    /// repo.save()?; // Persist the new profile
+    /// ```
+    pub fn set_profile(&mut self, profile: UserProfile) {
+        self.profile = Some(profile);
+        self.dirty = true;
+    }
+    
+    /// Persists the current profile data to the configured file.
+    /// 
+    /// This method serializes the complete user profile including basic information
+    /// and all daily profiles to a structured text format. If no profile exists,
+    /// the method succeeds but writes no data.
+    /// 
+    /// # Returns
+    /// * `Result<(), io::Error>` - Success confirmation or IO error details
+    /// 
+    /// # File Format
+    /// The method writes data in a structured format with type prefixes:
+    /// - **PROFILE**: Basic user information (gender, height, birth date, calculation method)
+    /// - **DAILY**: Daily profile entries (date, weight, activity level)
+    /// - **TARGET**: Weekday calorie target overrides (weekday, override)
+    /// - **PRESET**: Selected diet preset (key)
+    /// - **LIFESTAGE**: Pregnancy or breastfeeding (key, date)
+    /// - **MACROS**: Macro targets (percent or grams, protein, carbs, fat)
+    /// - **HISTORY**: Earlier basic profile values (until, gender, height, calculation method)
+    /// 
+    /// # Data Encoding
+    /// - Gender: M (Male), F (Female), O (Other)
+    /// - Activity Level: S (Sedentary), L (Lightly Active), M (Moderately Active), V (Very Active), E (Extremely Active)
+    /// - Dates: ISO format (YYYY-MM-DD)
+    /// 
+    /// # Error Handling
+    /// - File creation and write permission issues
+    /// - Disk space limitations
+    /// - Data formatting errors during serialization
+    /// 
+    /// # Data Integrity
+    /// The file is replaced atomically with rotating `.bak.N` backups
+    /// (see `storage::write_atomic`), so a failed save leaves the previous
+    /// version intact.
+    pub fn save(&mut self) -> Result<(), io::Error> {
+        // Serialize into memory first; the file is replaced atomically below
+        let mut buffer = Vec::new();
+        writeln!(buffer, "{}", format::header("profile"))?;
+        
+        if let Some(profile) = &self.profile {
+            // Write basic profile info
+            writeln!(
+                buffer,
+                "PROFILE|{}|{}|{}|{}",
+                gender_code(&profile.gender),
+                profile.height.map_or(String::new(), |height| height.to_string()),
+                profile.birth_date.map_or(String::new(), |birth| birth.format("%Y-%m-%d").to_string()),
+                format::escape(&profile.calculation_method)
+            )?;
+            
+            // Write daily profiles in date order
+            let mut daily_profiles: Vec<&DailyProfile> = profile.daily_profiles.iter().collect();
+            daily_profiles.sort_by_key(|daily| daily.date);
+            for daily in daily_profiles {
+                let carried = daily.carried_from
+                    .map_or(String::new(), |from| format!("|carried_from={}", from.format("%Y-%m-%d")));
+                writeln!(
+                    buffer,
+                    "DAILY|{}|{}|{}{}",
+                    daily.date.format("%Y-%m-%d"),
+                    daily.weight,
+                    match daily.activity_level {
+                        ActivityLevel::Sedentary => "S",
+                        ActivityLevel::LightlyActive => "L",
+                        ActivityLevel::ModeratelyActive => "M",
+                        ActivityLevel::VeryActive => "V",
+                        ActivityLevel::ExtremelyActive => "E",
+                    },
+                    carried
+                )?;
+            }
+            
+            // Write weekday target overrides, Monday first
+            for (weekday, target) in profile.weekday_targets.iter() {
+                writeln!(buffer, "TARGET|{}|{}", weekday, target)?;
+            }
+            
+            if let Some(preset) = profile.diet_preset {
+                writeln!(buffer, "PRESET|{}", preset.key())?;
+            }
+            
+            if let Some(stage) = profile.life_stage {
+                writeln!(buffer, "LIFESTAGE|{}|{}", stage.key(), stage.date().format("%Y-%m-%d"))?;
+            }
+            
+            match profile.macro_targets {
+                Some(MacroTargets::Ratios(ratios)) => {
+                    writeln!(buffer, "MACROS|percent|{}|{}|{}", ratios.protein, ratios.carbs, ratios.fat)?;
+                }
+                Some(MacroTargets::Grams(grams)) => {
+                    writeln!(buffer, "MACROS|grams|{}|{}|{}", grams.protein, grams.carbs, grams.fat)?;
+                }
+                None => {}
+            }
+            
+            if !profile.restrictions.is_empty() {
+                let keys: Vec<&str> = profile.restrictions.iter().map(DietaryRestriction::key).collect();
+                writeln!(buffer, "RESTRICTIONS|{}", keys.join(","))?;
+            }
+            
+            for version in &profile.history {
+                writeln!(
+                    buffer,
+                    "HISTORY|{}|{}|{}|{}",
+                    version.until.format("%Y-%m-%d"),
+                    gender_code(&version.gender),
+                    version.height.map_or(String::new(), |height| height.to_string()),
+                    format::escape(&version.calculation_method)
+                )?;
+            }
+        }
+        
+        storage::write_data_file(&self.file_path, &buffer)?;
+        
+        self.dirty = false;
+        Ok(())
+    }
+    
+    /// Loads profile data from the configured file into memory.
+    /// 
+    /// This method reconstructs the complete user profile from persistent storage,
+    /// parsing both basic profile information and all daily profile entries.
+    /// It provides robust error handling and data validation during the loading process.
+    /// 
+    /// # Returns
+    /// * `Result<(), io::Error>` - Success confirmation or IO error details
+    /// 
+    /// # Loading Process
+    /// 1. **File Parsing**: Read and parse each line according to its type prefix
+    /// 2. **Profile Construction**: Build basic profile from PROFILE lines
+    /// 3. **Daily Integration**: Add daily profiles to the basic profile
+    /// 4. **Data Validation**: Ensure all data meets application requirements
+    /// 5. **Error Recovery**: Skip malformed lines and continue processing
+    /// 
+    /// # Data Validation
+    /// - Skips daily profiles with invalid dates or weights out of range
+    /// - Maps gender and activity level codes to proper enum values
+    /// - Checks heights, weights, and birth dates with `models::validation`
+    /// - Maintains referential integrity between basic and daily profiles
+    /// 
+    /// # Error Recovery Strategy
+    /// - Skips malformed lines to prevent complete loading failure
+    /// - Uses default values for invalid enum mappings
+    /// - Continues processing valid data when encountering errors
+    /// - Leaves unusable heights and birth dates unknown
+    /// 
+    /// # Multi-Pass Processing
+    /// The method processes PROFILE lines first to establish the basic profile,
+    /// then adds DAILY entries to ensure proper data relationship maintenance.
+    pub fn load(&mut self) -> Result<(), io::Error> {
+        let contents = storage::read_data_file(&self.file_path)?;
+        let reader = contents.as_slice();
+        let mut main_profile: Option<UserProfile> = None;
+        let mut report = LoadReport::new();
+        
+        for record in schema::records("profile", reader) {
+            let (number, line) = record?;
+            let parts: Vec<&str> = format::split_escaped(&line, '|');
+            
+            match parts[0] {
+                "PROFILE" => {
+                    if parts.len() != 5 {
+                        report.skip(number, "profile needs exactly 5 fields");
+                        continue;
+                    }
+                    
+                    let gender = parse_gender(parts[1]);
+                    
+                    // Missing or unusable values stay unknown so calculators
+                    // can fall back to population defaults (low-data mode)
+                    let height = match parts[2].trim().parse::<f64>() {
+                        _ if parts[2].trim().is_empty() => None,
+                        Ok(height) => validation::height_cm(height)
+                            .inspect_err(|e| report.defaulted(number, format!("height '{}': {} Left unknown", parts[2], e)))
+                            .ok(),
+                        Err(_) => {
+                            report.defaulted(number, format!("height '{}' is not a number; left unknown", parts[2]));
+                            None
+                        }
+                    };
+                    let birth_date = match NaiveDate::parse_from_str(parts[3], "%Y-%m-%d") {
+                        _ if parts[3].trim().is_empty() => None,
+                        Ok(birth_date) => validation::birth_date(birth_date, Local::now().date_naive())
+                            .inspect_err(|e| report.defaulted(number, format!("birth date '{}': {} Left unknown", parts[3], e)))
+                            .ok(),
+                        Err(_) => {
+                            report.defaulted(number, format!("birth date '{}' is not a date; left unknown", parts[3]));
+                            None
+                        }
+                    };
+                    
+                    let calculation_method = format::unescape(parts[4]);
+                    
+                    main_profile = Some(UserProfile {
+                        gender,
+                        height,
+                        birth_date,
+                        daily_profiles: Vec::new(),
+                        calculation_method,
+                        weekday_targets: WeekdayTargets::default(),
+                        diet_preset: None,
+                        life_stage: None,
+                        macro_targets: None,
+                        restrictions: Vec::new(),
+                        history: Vec::new(),
+                    });
+                }
+                "DAILY" => {
+                    if parts.len() < 4 || main_profile.is_none() {
+                        report.skip(number, if main_profile.is_none() { "daily data before the profile line" } else { "too few fields" });
+                        continue;
+                    }
+                    
+                    // A daily profile without a usable date or weight is left out
+                    // rather than given a made-up one
+                    let Ok(date) = NaiveDate::parse_from_str(parts[1], "%Y-%m-%d") else {
+                        report.skip(number, format!("date '{}' is not valid", parts[1]));
+                        continue;
+                    };
+                    let weight = match parts[2].trim().parse::<f64>() {
+                        Ok(weight) => validation::weight_kg(weight),
+                        Err(_) => Err("Weight is not a number.".to_string()),
+                    };
+                    let weight = match weight {
+                        Ok(weight) => weight,
+                        Err(e) => {
+                            report.skip(number, format!("weight '{}': {}", parts[2], e));
+                            continue;
+                        }
+                    };
+                    
+                    let activity_level = match parts[3] {
+                        "S" => ActivityLevel::Sedentary,
+                        "L" => ActivityLevel::LightlyActive,
+                        "M" => ActivityLevel::ModeratelyActive,
+                        "V" => ActivityLevel::VeryActive,
+                        "E" => ActivityLevel::ExtremelyActive,
+                        other => {
+                            report.defaulted(number, format!("activity level '{}' is unknown; using sedentary", other));
+                            ActivityLevel::Sedentary
+                        }
+                    };
+                    
+                    // Optional trailing attribute marking a carried-forward day
+                    let carried_from = parts[4..].iter()
+                        .filter_map(|field| field.strip_prefix("carried_from="))
+                        .find_map(|value| NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d").ok());
+                    
+                    let daily_profile = DailyProfile {
+                        date,
+                        weight,
+                        activity_level,
+                        carried_from,
+                    };
+                    
+                    if let Some(profile) = &mut main_profile {
+                        profile.add_or_update_daily_profile(daily_profile);
+                    }
+                }
+                "TARGET" => {
+                    let weekday = parts.get(1).and_then(|day| day.trim().parse::<Weekday>().ok());
+                    let target = parts.get(2).and_then(|value| TargetOverride::parse(value).ok());
+                    match (&mut main_profile, weekday, target) {
+                        (Some(profile), Some(weekday), Some(target)) if parts.len() == 3 => {
+                            profile.weekday_targets.set(weekday, Some(target));
+                        }
+                        _ => report.skip(number, "not a valid weekday target"),
+                    }
+                }
+                "PRESET" => {
+                    let preset = parts.get(1).and_then(|key| DietPreset::from_key(key));
+                    match (&mut main_profile, preset) {
+                        (Some(profile), Some(preset)) if parts.len() == 2 => profile.diet_preset = Some(preset),
+                        _ => report.skip(number, "not a valid diet preset"),
+                    }
+                }
+                "LIFESTAGE" => {
+                    let date = parts.get(2).and_then(|date| NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").ok());
+                    let stage = date.and_then(|date| LifeStage::from_key(parts[1], date));
+                    match (&mut main_profile, stage) {
+                        (Some(profile), Some(stage)) if parts.len() == 3 => profile.life_stage = Some(stage),
+                        _ => report.skip(number, "not a valid life stage"),
+                    }
+                }
+                "MACROS" => {
+                    let values: Option<Vec<f64>> = parts.get(2..)
+                        .map(|fields| fields.iter().map(|field| field.trim().parse().ok()).collect())
+                        .unwrap_or(None);
+                    let targets = match (parts.get(1).copied(), values.as_deref()) {
+                        (Some("percent"), Some(&[protein, carbs, fat])) => {
+                            MacroRatios::new(protein, carbs, fat).ok().map(MacroTargets::Ratios)
+                        }
+                        (Some("grams"), Some(&[protein, carbs, fat]))
+                            if [protein, carbs, fat].iter().all(|grams| grams.is_finite() && *grams >= 0.0) =>
+                        {
+                            Some(MacroTargets::Grams(Macros::new(protein, carbs, fat)))
+                        }
+                        _ => None,
+                    };
+                    match (&mut main_profile, targets) {
+                        (Some(profile), Some(targets)) => profile.macro_targets = Some(targets),
+                        _ => report.skip(number, "not valid macro targets"),
+                    }
+                }
+                "RESTRICTIONS" => {
+                    let restrictions = parts.get(1).and_then(|keys| dietary::parse_restrictions(keys).ok());
+                    match (&mut main_profile, restrictions) {
+                        (Some(profile), Some(restrictions)) if parts.len() == 2 => profile.restrictions = restrictions,
+                        _ => report.skip(number, "not valid dietary restrictions"),
+                    }
+                }
+                "HISTORY" => {
+                    let until = parts.get(1).and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok());
+                    match (&mut main_profile, until) {
+                        (Some(profile), Some(until)) if parts.len() == 5 => {
+                            profile.history.push(ProfileVersion {
+                                until,
+                                gender: parse_gender(parts[2]),
+                                height: parts[3].parse().ok().and_then(|height| validation::height_cm(height).ok()),
+                                calculation_method: format::unescape(parts[4]),
+                            });
+                        }
+                        _ => report.skip(number, "not a valid profile history entry"),
+                    }
+                }
+                other => {
+                    // Unknown line type, skip
+                    report.skip(number, format!("unknown record type '{}'", other));
+                    continue;
+                }
+            }
+        }
+        self.load_report = report;
+        
+        if let Some(profile) = &mut main_profile {
+            profile.history.sort_by_key(|version| version.until);
+        }
+        self.profile = main_profile;
+        self.dirty = false;
+        
+        Ok(())
+    }
+
+    /// Reports whether the in-memory profile has unsaved changes.
+    /// 
+    /// The flag is raised by every mutating operation (including handing out
+    /// mutable access, since callers may change data through it) and cleared
+    /// by a successful `save()` or `load()`. Autosave uses it to skip writing
+    /// files that have not changed.
+    /// 
+    /// # Returns
+    /// * `bool` - True if there are changes that have not been persisted yet
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Returns how many malformed lines the last `load()` skipped.
+    /// 
+    /// Blank lines, the header, and comments are not counted.
+    pub fn skipped_line_count(&self) -> usize {
+        self.load_report.skipped_count()
+    }
+
+    /// Returns what the last `load()` skipped or defaulted, with line numbers.
+    pub fn load_report(&self) -> &LoadReport {
+        &self.load_report
+    }
+
+}
+
+/// Storage code of a gender
+fn gender_code(gender: &Gender) -> &'static str {
+    match gender {
+        Gender::Male => "M",
+        Gender::Female => "F",
+        Gender::Other => "O",
+    }
+}
+
+/// Reads a gender code, treating unknown codes as `Other`
+fn parse_gender(code: &str) -> Gender {
+    match code {
+        "M" => Gender::Male,
+        "F" => Gender::Female,
+        _ => Gender::Other,
+    }
+}
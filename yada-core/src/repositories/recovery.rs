@@ -0,0 +1,157 @@
+//! # Data File Recovery
+//!
+//! Malformed lines are skipped by the loaders, but loading a data file can
+//! still fail outright: the file may be unreadable or contain bytes that are
+//! not UTF-8 text. This module holds the file operations behind the startup
+//! recovery menu, independent of how the choices are presented.
+//!
+//! ## Recovery Options
+//!
+//! - **Restore**: Replace the file with one of its rotating backups
+//!   (`<file>.bak.N`, see `storage`)
+//! - **Skip**: Move the file aside so the application starts without it
+//! - **Check**: Report what is wrong with the file, line by line
+//!
+//! Both restoring and skipping first move the broken file to
+//! `<file>.broken` (or `<file>.broken.N` if that name is taken), so no
+//! data is ever deleted by the recovery process.
+
+// yada-core/src/repositories/recovery.rs
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::SystemTime;
+
+use crate::repositories::format::{self, FORMAT_VERSION};
+use crate::repositories::storage;
+
+/// Suffix of the name a broken data file is moved to
+pub const BROKEN_SUFFIX: &str = "broken";
+
+/// Maximum number of problems listed by `check_file`
+const MAX_PROBLEMS: usize = 20;
+
+/// A backup of a data file that can be restored
+#[derive(Debug, Clone)]
+pub struct BackupFile {
+    /// Path of the backup
+    pub path: String,
+
+    /// When the backed-up version was saved, if the platform reports it
+    pub modified: Option<SystemTime>,
+}
+
+/// Result of checking a data file's integrity
+#[derive(Debug, Clone, Default)]
+pub struct IntegrityReport {
+    /// Number of lines in the file
+    pub lines: usize,
+
+    /// Format version from the header, if there is one
+    pub version: Option<u32>,
+
+    /// Problems found, each naming its line where possible
+    pub problems: Vec<String>,
+}
+
+/// Lists the backups of a data file, newest first
+///
+/// # Arguments
+/// * `file_path` - Data file whose `.bak.N` backups should be found
+pub fn list_backups(file_path: &str) -> Vec<BackupFile> {
+    (1..)
+        .map(|index| storage::backup_path(file_path, index))
+        .take_while(|path| Path::new(path).exists())
+        .map(|path| {
+            let modified = fs::metadata(&path).and_then(|meta| meta.modified()).ok();
+            BackupFile { path, modified }
+        })
+        .collect()
+}
+
+/// Moves a data file to the first free `.broken` name
+///
+/// # Returns
+/// * `Ok(String)` - Path the file was moved to
+/// * `Err(io::Error)` - If the file cannot be renamed
+pub fn set_aside(file_path: &str) -> Result<String, io::Error> {
+    let mut target = format!("{}.{}", file_path, BROKEN_SUFFIX);
+    let mut index = 2;
+    while Path::new(&target).exists() {
+        target = format!("{}.{}.{}", file_path, BROKEN_SUFFIX, index);
+        index += 1;
+    }
+
+    fs::rename(file_path, &target)?;
+    Ok(target)
+}
+
+/// Replaces a data file with one of its backups
+///
+/// The backup itself is copied, not moved, so it stays available.
+///
+/// # Returns
+/// * `Ok(String)` - Path the broken file was moved to
+/// * `Err(io::Error)` - If the file cannot be moved or the backup cannot be copied
+pub fn restore_backup(file_path: &str, backup: &BackupFile) -> Result<String, io::Error> {
+    let broken = set_aside(file_path)?;
+    fs::copy(&backup.path, file_path)?;
+    Ok(broken)
+}
+
+/// Checks a data file for the problems that stop it from loading
+///
+/// Lines that are not UTF-8 text are reported, as is a header from a newer
+/// format version (which explains why many lines would be skipped). Lines
+/// the loaders merely skip (wrong field count) are not reported; they are
+/// counted by the repositories themselves.
+pub fn check_file(file_path: &str) -> IntegrityReport {
+    let mut report = IntegrityReport::default();
+    let bytes = match storage::read_data_file(file_path) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            report.problems.push(format!("The file cannot be read: {}", e));
+            return report;
+        }
+    };
+
+    for (index, raw) in bytes.split(|byte| *byte == b'\n').enumerate() {
+        report.lines = index + 1;
+        let line = match std::str::from_utf8(raw) {
+            Ok(line) => line,
+            Err(e) => {
+                report.problems.push(format!(
+                    "Line {}: not valid UTF-8 text (bad byte at column {})",
+                    index + 1,
+                    e.valid_up_to() + 1
+                ));
+                continue;
+            }
+        };
+
+        if let Some((_, version)) = format::parse_header(line) {
+            if report.version.is_none() {
+                report.version = Some(version);
+            }
+            if version > FORMAT_VERSION {
+                report.problems.push(format!(
+                    "Line {}: written by a newer version of YADA (format v{}, this version reads v{})",
+                    index + 1,
+                    version,
+                    FORMAT_VERSION
+                ));
+            }
+        }
+    }
+    // A final newline (or an empty file) does not start another line
+    if bytes.is_empty() || bytes.ends_with(b"\n") {
+        report.lines -= 1;
+    }
+
+    let total = report.problems.len();
+    if total > MAX_PROBLEMS {
+        report.problems.truncate(MAX_PROBLEMS);
+        report.problems.push(format!("... and {} more", total - MAX_PROBLEMS));
+    }
+    report
+}
@@ -0,0 +1,143 @@
+//! # Format Upgrades
+//!
+//! Each data file names its format version in its header (see
+//! `repositories::format`); files without a header are version 0. When a
+//! version changes how records are written, an upgrade step is added here
+//! that rewrites a record of the old version into the new one. Loaders read
+//! every record through `records`, which applies the steps between the
+//! file's version and `FORMAT_VERSION` before the record is parsed, so the
+//! parsers only ever see the current format and old files keep loading.
+//!
+//! ## Upgrade Steps
+//!
+//! | From | Files | Change |
+//! |------|-------|--------|
+//! | v0   | all   | Backslashes are literal text; escape them |
+//!
+//! ## Adding a Version
+//!
+//! 1. Bump `FORMAT_VERSION` and write the new layout in the repository's `save`
+//! 2. Add a step to `UPGRADES` with `from` set to the previous version,
+//!    rewriting one old record into the new layout
+//! 3. Leave the parser expecting only the new layout
+//!
+//! New optional `key=value` attributes (such as macros, serving units, and
+//! categories) need no step: parsers ignore unknown keys and treat missing
+//! ones as unknown. Files are upgraded on disk the next time they are saved,
+//! or at once with `yada migrate`.
+
+// yada-core/src/repositories/schema.rs
+use std::io::{self, BufRead, Lines};
+
+use crate::repositories::format::{self, FORMAT_VERSION};
+
+/// One change of the record layout between two format versions
+#[derive(Debug, Clone, Copy)]
+pub struct UpgradeStep {
+    /// Version the step upgrades from (to `from + 1`)
+    pub from: u32,
+
+    /// File kinds the step applies to (`foods`, `logs`, ...); empty for all
+    pub kinds: &'static [&'static str],
+
+    /// What the step changes, shown by `yada migrate`
+    pub description: &'static str,
+
+    /// Rewrites one record of version `from` in the layout of `from + 1`
+    pub upgrade: fn(&str) -> String,
+}
+
+impl UpgradeStep {
+    /// Whether the step applies to a file kind
+    pub fn applies_to(&self, kind: &str) -> bool {
+        self.kinds.is_empty() || self.kinds.contains(&kind)
+    }
+}
+
+/// Every upgrade step, oldest first
+pub const UPGRADES: &[UpgradeStep] = &[
+    UpgradeStep {
+        from: 0,
+        kinds: &[],
+        description: "escape backslashes written before escaping existed",
+        upgrade: escape_backslashes,
+    },
+];
+
+/// Returns the steps that upgrade a file kind from a version to the current one
+///
+/// # Arguments
+/// * `kind` - File kind, as in the header (`foods`, `logs`, `profile`, ...)
+/// * `version` - Version of the file (0 for files without a header)
+pub fn steps_from(kind: &str, version: u32) -> Vec<&'static UpgradeStep> {
+    UPGRADES
+        .iter()
+        .filter(|step| step.from >= version && step.from < FORMAT_VERSION && step.applies_to(kind))
+        .collect()
+}
+
+/// Upgrades one record to the current format
+///
+/// Records of the current version, or of newer versions this one cannot
+/// know about, are returned unchanged.
+///
+/// # Arguments
+/// * `kind` - File kind, as in the header
+/// * `version` - Version of the file the record comes from
+/// * `record` - The record line
+pub fn upgrade_record(kind: &str, version: u32, record: &str) -> String {
+    steps_from(kind, version)
+        .into_iter()
+        .fold(record.to_string(), |record, step| (step.upgrade)(&record))
+}
+
+/// Reads the records of a data file in the current format
+///
+/// Blank lines, comments, and the header are skipped; the header sets the
+/// version the remaining records are upgraded from.
+///
+/// # Arguments
+/// * `kind` - File kind, as in the header
+/// * `reader` - The file
+///
+/// # Returns
+/// An iterator of `(line number, record)`, line numbers counting from 1
+/// including skipped lines, or the read error
+pub fn records<R: BufRead>(kind: &str, reader: R) -> Records<R> {
+    Records { kind: kind.to_string(), version: 0, lines: reader.lines(), number: 0 }
+}
+
+/// Iterator returned by `records`
+pub struct Records<R> {
+    kind: String,
+    version: u32,
+    lines: Lines<R>,
+    number: usize,
+}
+
+impl<R: BufRead> Iterator for Records<R> {
+    type Item = io::Result<(usize, String)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e)),
+            };
+            self.number += 1;
+            if let Some((_, version)) = format::parse_header(&line) {
+                self.version = version;
+                continue;
+            }
+            if format::is_ignored_line(&line) {
+                continue;
+            }
+            return Some(Ok((self.number, upgrade_record(&self.kind, self.version, &line))));
+        }
+    }
+}
+
+/// v0 to v1: backslashes had no special meaning before escaping existed
+fn escape_backslashes(record: &str) -> String {
+    record.replace('\\', "\\\\")
+}
@@ -0,0 +1,148 @@
+//! # Food Search Index
+//!
+//! An inverted index from search words to the IDs of the foods containing
+//! them, so searching a food database with tens of thousands of imported
+//! entries does not have to look at every food.
+//!
+//! ## Postings
+//!
+//! - **Keywords**: Typed and derived keywords, used by exact keyword search
+//!   (`FoodRepository::search_foods`)
+//! - **Tokens**: Keywords plus the words of each food's name, used to find
+//!   candidates for ranked search (`FoodRepository::search_ranked`)
+//!
+//! Exact searches read only the postings of their keywords. Ranked searches
+//! scan the distinct tokens (usually far fewer than foods) for prefix,
+//! substring, and fuzzy matches and rank only the foods posted under them;
+//! the ranking itself re-checks every candidate (see `models::search`), so
+//! the index only has to never miss a match.
+//!
+//! ## Maintenance
+//!
+//! `FoodRepository` updates the index when foods are added, updated, or
+//! removed, and rebuilds it on load and when keyword derivation changes.
+
+// yada-core/src/repositories/search_index.rs
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::models::food::Food;
+use crate::models::search;
+
+/// Inverted index over food keywords and name words
+#[derive(Debug, Clone, Default)]
+pub struct SearchIndex {
+    /// Food IDs per typed or derived keyword
+    keywords: HashMap<String, HashSet<String>>,
+    /// Food IDs per keyword or name word, ordered for prefix scans
+    tokens: BTreeMap<String, HashSet<String>>,
+}
+
+impl SearchIndex {
+    /// Builds an index over a collection of foods
+    pub fn build<'a>(foods: impl IntoIterator<Item = &'a Food>) -> Self {
+        let mut index = SearchIndex::default();
+        for food in foods {
+            index.insert(food);
+        }
+        index
+    }
+
+    /// Adds a food's keywords and name words
+    pub fn insert(&mut self, food: &Food) {
+        for keyword in food.keywords.iter().chain(&food.derived_keywords) {
+            self.keywords.entry(keyword.clone()).or_default().insert(food.id.clone());
+        }
+        for token in search::food_tokens(food) {
+            self.tokens.entry(token).or_default().insert(food.id.clone());
+        }
+    }
+
+    /// Removes a food, as it was when inserted
+    pub fn remove(&mut self, food: &Food) {
+        for keyword in food.keywords.iter().chain(&food.derived_keywords) {
+            remove_posting(&mut self.keywords, keyword, &food.id);
+        }
+        for token in search::food_tokens(food) {
+            if let Some(ids) = self.tokens.get_mut(&token) {
+                ids.remove(&food.id);
+                if ids.is_empty() {
+                    self.tokens.remove(&token);
+                }
+            }
+        }
+    }
+
+    /// Finds foods by exact keywords
+    ///
+    /// # Arguments
+    /// * `keywords` - Keywords to look up
+    /// * `match_all` - If true, a food must have every keyword; otherwise any one
+    ///
+    /// # Returns
+    /// IDs of the matching foods
+    pub fn keyword_matches(&self, keywords: &HashSet<String>, match_all: bool) -> HashSet<String> {
+        let mut postings = keywords.iter().map(|keyword| self.keywords.get(keyword));
+        if match_all {
+            let Some(Some(first)) = postings.next() else {
+                return HashSet::new();
+            };
+            let mut ids = first.clone();
+            for posting in postings {
+                match posting {
+                    Some(posting) => ids.retain(|id| posting.contains(id)),
+                    None => return HashSet::new(),
+                }
+            }
+            ids
+        } else {
+            postings.flatten().flatten().cloned().collect()
+        }
+    }
+
+    /// Finds the foods a ranked search term could match
+    ///
+    /// # Returns
+    /// * `Some(ids)` - Foods posted under a token that starts with, contains,
+    ///   or is within the allowed edit distance of the term
+    /// * `None` - If the term spans several words and cannot be answered
+    ///   from single tokens; the caller must check every food
+    pub fn candidates(&self, term: &str) -> Option<HashSet<String>> {
+        let term = term.trim();
+        if term.is_empty() {
+            return Some(HashSet::new());
+        }
+        if term.chars().any(|c| !c.is_alphanumeric()) {
+            return None;
+        }
+
+        // Prefix matches are contiguous in the ordered token map
+        let mut ids: HashSet<String> = self.tokens
+            .range(term.to_string()..)
+            .take_while(|(token, _)| token.starts_with(term))
+            .flat_map(|(_, ids)| ids.iter().cloned())
+            .collect();
+
+        let allowed = search::max_distance(term);
+        let length = term.chars().count();
+        for (token, posting) in &self.tokens {
+            if token.starts_with(term) {
+                continue;
+            }
+            let fuzzy = || token.chars().count().abs_diff(length) <= allowed && search::edit_distance(term, token) <= allowed;
+            if token.contains(term) || (allowed > 0 && fuzzy()) {
+                ids.extend(posting.iter().cloned());
+            }
+        }
+        Some(ids)
+    }
+}
+
+/// Removes one food ID from a posting list, dropping the list when it empties
+fn remove_posting(postings: &mut HashMap<String, HashSet<String>>, key: &str, id: &str) {
+    if let Some(ids) = postings.get_mut(key) {
+        ids.remove(id);
+        if ids.is_empty() {
+            postings.remove(key);
+        }
+    }
+}
@@ -0,0 +1,433 @@
+//! # Settings Repository
+//!
+//! This module implements the Repository Pattern for app-wide settings that
+//! are not part of any user's data: preferences, saved food searches, and
+//! reminders (see `models::reminder`). The settings file lives in the data directory and is
+//! shared by all users.
+//!
+//! ## Preferences
+//!
+//! - **units**: `metric` or `imperial` (see `models::preferences`)
+//! - **autosave**: `command`, `off`, or minutes (see `repositories::autosave`)
+//! - **search**: `any` or `all` keywords by default (see `models::search`)
+//! - **dates**: `iso`, `day-first`, or `month-first`
+//! - **confirm**: `safe` or `expert` (see `models::preferences::ConfirmPolicy`)
+//!
+//! ## File Format Specification
+//!
+//! ```text
+//! PREF|units=imperial
+//! SEARCH|mode=all|terms=protein,snack|name=High-protein snacks
+//! REMINDER|id=1|time=13:00|days=mon,tue|check=lunch|shown=2024-03-05|message=Log lunch
+//! ```
+//!
+//! Preferences that are not in the file have their default value, and
+//! values that are not valid are replaced by it. Saved search terms are
+//! escaped one by one and joined with commas. For reminders, `days`
+//! (every day when missing), `check`, and `shown` are optional. The first
+//! line is the format header `#yada:settings:v1`, and the message is
+//! backslash-escaped (see `repositories::format`).
+
+// yada-core/src/repositories/settings_repository.rs
+use std::io::{self, Write};
+use std::path::Path;
+
+use chrono::{NaiveDate, NaiveTime, Weekday};
+
+use crate::models::preferences::{ConfirmPolicy, DateFormat, UnitSystem};
+use crate::models::reminder::{self, Reminder, ReminderCheck};
+use crate::models::search::{SavedSearch, SearchMode};
+use crate::repositories::autosave::AutosavePolicy;
+use crate::repositories::{format, schema, storage};
+use crate::repositories::load_report::LoadReport;
+
+/// App-wide preferences
+#[derive(Debug, Clone, PartialEq)]
+pub struct Preferences {
+    /// Units of weights and heights in the menus
+    pub units: UnitSystem,
+
+    /// When changes are saved without choosing Save Data (`YADA_AUTOSAVE` wins)
+    pub autosave: AutosavePolicy,
+
+    /// Whether food searches match any or all keywords by default
+    pub search_mode: SearchMode,
+
+    /// How the menus show dates
+    pub date_format: DateFormat,
+
+    /// Which changes the user confirms by typing `yes`
+    pub confirm: ConfirmPolicy,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Preferences {
+            units: UnitSystem::default(),
+            autosave: AutosavePolicy::AfterEveryCommand,
+            search_mode: SearchMode::default(),
+            date_format: DateFormat::default(),
+            confirm: ConfirmPolicy::default(),
+        }
+    }
+}
+
+/// Repository of app-wide settings
+pub struct SettingsRepository {
+    /// Preferences, defaults when the file has none
+    preferences: Preferences,
+    /// Saved food searches in the order they were added
+    saved_searches: Vec<SavedSearch>,
+    /// Reminders in the order they were added
+    reminders: Vec<Reminder>,
+    /// File system path for persistent storage
+    file_path: String,
+    /// Whether in-memory data has changed since the last save or load
+    dirty: bool,
+    /// Lines skipped by the last load
+    load_report: LoadReport,
+}
+
+impl SettingsRepository {
+    /// Creates a repository and loads existing settings if the file exists
+    ///
+    /// # Arguments
+    /// * `file_path` - Path to the file where settings are stored
+    ///
+    /// # Returns
+    /// * `Result<Self, io::Error>` - A new repository instance or an IO error if loading fails
+    pub fn new(file_path: &str) -> Result<Self, io::Error> {
+        let mut repo = SettingsRepository {
+            preferences: Preferences::default(),
+            saved_searches: Vec::new(),
+            reminders: Vec::new(),
+            file_path: file_path.to_string(),
+            dirty: false,
+            load_report: LoadReport::new(),
+        };
+
+        if Path::new(file_path).exists() {
+            repo.load()?;
+        }
+        Ok(repo)
+    }
+
+    /// Returns the preferences
+    pub fn preferences(&self) -> &Preferences {
+        &self.preferences
+    }
+
+    /// Replaces the preferences
+    pub fn set_preferences(&mut self, preferences: Preferences) {
+        if preferences != self.preferences {
+            self.preferences = preferences;
+            self.dirty = true;
+        }
+    }
+
+    /// Returns the saved searches in the order they were added
+    pub fn saved_searches(&self) -> &[SavedSearch] {
+        &self.saved_searches
+    }
+
+    /// Finds a saved search by name, ignoring case
+    pub fn find_search(&self, name: &str) -> Option<&SavedSearch> {
+        self.saved_searches.iter().find(|search| search.name.eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Saves a search, replacing one with the same name
+    ///
+    /// # Arguments
+    /// * `name` - Name to pick the search by
+    /// * `terms` - Search terms as typed at the search prompt
+    /// * `mode` - Whether any or all terms must match
+    ///
+    /// # Returns
+    /// * `Ok(true)` - If a search with the name was replaced
+    /// * `Ok(false)` - If the search was added
+    /// * `Err(String)` - If the name is empty or has a comma, there are no
+    ///   terms, or a text contains control characters
+    pub fn save_search(&mut self, name: &str, terms: &[String], mode: SearchMode) -> Result<bool, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("A saved search needs a name".to_string());
+        }
+        if name.contains(',') {
+            return Err("A saved search's name cannot contain commas".to_string());
+        }
+        format::validate_text("Search name", name)?;
+        let mut terms: Vec<String> = terms.iter()
+            .map(|term| term.trim().to_lowercase())
+            .filter(|term| !term.is_empty())
+            .collect();
+        if terms.is_empty() {
+            return Err("A saved search needs at least one keyword".to_string());
+        }
+        for term in &terms {
+            format::validate_text("Search keyword", term)?;
+        }
+        terms.sort();
+        terms.dedup();
+
+        let search = SavedSearch { name: name.to_string(), terms, mode };
+        self.dirty = true;
+        match self.saved_searches.iter_mut().find(|other| other.name.eq_ignore_ascii_case(name)) {
+            Some(existing) => {
+                *existing = search;
+                Ok(true)
+            }
+            None => {
+                self.saved_searches.push(search);
+                Ok(false)
+            }
+        }
+    }
+
+    /// Removes a saved search by name, ignoring case
+    ///
+    /// # Returns
+    /// The removed search, or `None` if no search has the name
+    pub fn remove_search(&mut self, name: &str) -> Option<SavedSearch> {
+        let index = self.saved_searches.iter().position(|search| search.name.eq_ignore_ascii_case(name.trim()))?;
+        self.dirty = true;
+        Some(self.saved_searches.remove(index))
+    }
+
+    /// Returns all reminders in the order they were added
+    pub fn reminders(&self) -> &[Reminder] {
+        &self.reminders
+    }
+
+    /// Adds a reminder with the next free ID
+    ///
+    /// # Arguments
+    /// * `time` - Time of day the reminder comes due
+    /// * `days` - Weekdays it applies to; empty for every day
+    /// * `check` - What it asks for, if it can be checked
+    /// * `message` - Text shown to the user
+    ///
+    /// # Returns
+    /// * `Ok(&Reminder)` - The new reminder
+    /// * `Err(String)` - If the message is empty or contains control characters
+    pub fn add_reminder(
+        &mut self,
+        time: NaiveTime,
+        days: Vec<Weekday>,
+        check: Option<ReminderCheck>,
+        message: &str,
+    ) -> Result<&Reminder, String> {
+        let message = message.trim();
+        if message.is_empty() {
+            return Err("A reminder needs a message".to_string());
+        }
+        format::validate_text("Reminder message", message)?;
+
+        let id = self.reminders.iter().map(|reminder| reminder.id).max().unwrap_or(0) + 1;
+        self.reminders.push(Reminder { id, time, days, check, message: message.to_string(), last_shown: None });
+        self.dirty = true;
+        Ok(&self.reminders[self.reminders.len() - 1])
+    }
+
+    /// Removes a reminder
+    ///
+    /// # Returns
+    /// The removed reminder, or `None` if no reminder has the ID
+    pub fn remove_reminder(&mut self, id: u32) -> Option<Reminder> {
+        let index = self.reminders.iter().position(|reminder| reminder.id == id)?;
+        self.dirty = true;
+        Some(self.reminders.remove(index))
+    }
+
+    /// Records that a reminder was shown on a date, so it is not shown again that day
+    pub fn mark_shown(&mut self, id: u32, date: NaiveDate) {
+        if let Some(reminder) = self.reminders.iter_mut().find(|reminder| reminder.id == id)
+            && reminder.last_shown != Some(date)
+        {
+            reminder.last_shown = Some(date);
+            self.dirty = true;
+        }
+    }
+
+    /// Persists all settings
+    ///
+    /// # Returns
+    /// * `Result<(), io::Error>` - Success confirmation or IO error details
+    ///
+    /// The file is replaced atomically with rotating `.bak.N` backups
+    /// (see `storage::write_atomic`).
+    pub fn save(&mut self) -> Result<(), io::Error> {
+        let mut buffer = Vec::new();
+        writeln!(buffer, "{}", format::header("settings"))?;
+
+        let preferences = &self.preferences;
+        writeln!(buffer, "PREF|units={}", preferences.units.key())?;
+        writeln!(buffer, "PREF|autosave={}", preferences.autosave.key())?;
+        writeln!(buffer, "PREF|search={}", preferences.search_mode.key())?;
+        writeln!(buffer, "PREF|dates={}", preferences.date_format.key())?;
+        writeln!(buffer, "PREF|confirm={}", preferences.confirm.key())?;
+
+        for search in &self.saved_searches {
+            let terms: Vec<String> = search.terms.iter().map(|term| format::escape(term)).collect();
+            writeln!(
+                buffer, "SEARCH|mode={}|terms={}|name={}",
+                search.mode.key(), terms.join(","), format::escape(&search.name)
+            )?;
+        }
+
+        for reminder in &self.reminders {
+            write!(buffer, "REMINDER|id={}|time={}", reminder.id, reminder.time.format("%H:%M"))?;
+            if !reminder.days.is_empty() {
+                let days: Vec<String> = reminder.days.iter().map(|day| day.to_string().to_lowercase()).collect();
+                write!(buffer, "|days={}", days.join(","))?;
+            }
+            if let Some(check) = reminder.check {
+                write!(buffer, "|check={}", check.key())?;
+            }
+            if let Some(shown) = reminder.last_shown {
+                write!(buffer, "|shown={}", shown.format("%Y-%m-%d"))?;
+            }
+            writeln!(buffer, "|message={}", format::escape(&reminder.message))?;
+        }
+
+        storage::write_data_file(&self.file_path, &buffer)?;
+
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Loads all settings from the configured file, skipping malformed lines
+    ///
+    /// # Returns
+    /// * `Result<(), io::Error>` - Success confirmation or IO error details
+    pub fn load(&mut self) -> Result<(), io::Error> {
+        let contents = storage::read_data_file(&self.file_path)?;
+        let reader = contents.as_slice();
+        self.reminders.clear();
+        self.preferences = Preferences::default();
+        self.saved_searches.clear();
+        let mut report = LoadReport::new();
+
+        for record in schema::records("settings", reader) {
+            let (number, line) = record?;
+            let parts: Vec<&str> = format::split_escaped(&line, '|');
+            match parts[0] {
+                "PREF" if parts.len() == 2 => match format::split_once_escaped(parts[1], '=') {
+                    Some((key, value)) => self.load_preference(key.trim(), value.trim(), number, &mut report),
+                    None => report.skip(number, "preference without a value"),
+                },
+                "PREF" => report.skip(number, "preference with more than one value"),
+                "SEARCH" => match parse_saved_search(&parts[1..]) {
+                    Some(search) if self.find_search(&search.name).is_none() => self.saved_searches.push(search),
+                    Some(search) => report.skip(number, format!("saved search '{}' is there twice", search.name)),
+                    None => report.skip(number, "not a valid saved search"),
+                },
+                "REMINDER" => match parse_reminder(&parts[1..]) {
+                    Some(reminder) if self.reminders.iter().all(|other| other.id != reminder.id) => {
+                        self.reminders.push(reminder);
+                    }
+                    Some(reminder) => report.skip(number, format!("reminder ID {} is used twice", reminder.id)),
+                    None => report.skip(number, "not a valid reminder"),
+                },
+                other => report.skip(number, format!("unknown record type '{}'", other)),
+            }
+        }
+        self.load_report = report;
+
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Sets one preference from its `key=value` line
+    ///
+    /// Unknown keys are skipped and invalid values keep the default, both
+    /// noted in the load report.
+    fn load_preference(&mut self, key: &str, value: &str, number: usize, report: &mut LoadReport) {
+        let preferences = &mut self.preferences;
+        let valid = match key {
+            "units" => UnitSystem::parse(value).map(|units| preferences.units = units),
+            "autosave" => AutosavePolicy::parse(value).map(|autosave| preferences.autosave = autosave),
+            "search" => SearchMode::parse(value).map(|mode| preferences.search_mode = mode),
+            "dates" => DateFormat::parse(value).map(|format| preferences.date_format = format),
+            "confirm" => ConfirmPolicy::parse(value).map(|policy| preferences.confirm = policy),
+            other => {
+                report.skip(number, format!("unknown preference '{}'", other));
+                return;
+            }
+        };
+        if valid.is_none() {
+            report.defaulted(number, format!("{} '{}' is not valid; using the default", key, value));
+        }
+    }
+
+    /// Reports whether there are changes that have not been saved yet
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Returns how many malformed lines the last `load()` skipped
+    pub fn skipped_line_count(&self) -> usize {
+        self.load_report.skipped_count()
+    }
+
+    /// Returns what the last `load()` skipped, with line numbers
+    pub fn load_report(&self) -> &LoadReport {
+        &self.load_report
+    }
+}
+
+/// Parses the `key=value` fields of a saved search line
+///
+/// # Returns
+/// * `Some(SavedSearch)` - The saved search
+/// * `None` - If the name or terms are missing, or the mode is not valid
+fn parse_saved_search(fields: &[&str]) -> Option<SavedSearch> {
+    let field = |key: &str| {
+        fields.iter()
+            .filter_map(|field| format::split_once_escaped(field, '='))
+            .find(|(name, _)| name.trim() == key)
+            .map(|(_, value)| value.trim())
+    };
+
+    let name = format::unescape(field("name")?);
+    let terms: Vec<String> = format::split_escaped(field("terms")?, ',')
+        .into_iter()
+        .map(|term| format::unescape(term.trim()))
+        .filter(|term| !term.is_empty())
+        .collect();
+    let mode = SearchMode::parse(field("mode").unwrap_or("any"))?;
+    if name.is_empty() || terms.is_empty() {
+        return None;
+    }
+    Some(SavedSearch { name, terms, mode })
+}
+
+/// Parses the `key=value` fields of a reminder line
+///
+/// # Returns
+/// * `Some(Reminder)` - The reminder
+/// * `None` - If the ID, time, or message is missing, or a field is not valid
+fn parse_reminder(fields: &[&str]) -> Option<Reminder> {
+    let field = |key: &str| {
+        fields.iter()
+            .filter_map(|field| format::split_once_escaped(field, '='))
+            .find(|(name, _)| name.trim() == key)
+            .map(|(_, value)| value.trim())
+    };
+
+    let id = field("id")?.parse::<u32>().ok().filter(|id| *id > 0)?;
+    let time = reminder::parse_time(field("time")?).ok()?;
+    let days = reminder::parse_days(&format::unescape(field("days").unwrap_or(""))).ok()?;
+    let check = match field("check") {
+        Some(check) => Some(ReminderCheck::parse(check)?),
+        None => None,
+    };
+    let last_shown = match field("shown") {
+        Some(shown) => Some(NaiveDate::parse_from_str(shown, "%Y-%m-%d").ok()?),
+        None => None,
+    };
+    let message = format::unescape(field("message")?);
+    if message.is_empty() {
+        return None;
+    }
+    Some(Reminder { id, time, days, check, message, last_shown })
+}
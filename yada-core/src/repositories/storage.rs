@@ -0,0 +1,187 @@
+//! # Storage Helpers
+//!
+//! This module contains the low-level file operations shared by all
+//! repositories. Repositories serialize their data into memory and hand the
+//! finished buffer to `write_atomic`, which guarantees that the data file is
+//! always either the complete old version or the complete new version.
+//!
+//! ## Atomic Write Procedure
+//!
+//! 1. **Temp File**: Write the new contents to `<file>.tmp` in the same directory
+//! 2. **Flush**: `fsync` the temp file so the data is on disk
+//! 3. **Backup**: Rotate existing backups and copy the current file to `<file>.bak.1`
+//! 4. **Rename**: Atomically replace the data file with the temp file
+//!
+//! A crash before step 4 leaves the original file untouched; a crash after it
+//! leaves the new file in place. The data file is never truncated in place.
+//!
+//! A save whose contents match the file on disk writes nothing, so saving
+//! unchanged data (as every exit does) never pushes a real backup out of the
+//! rotation.
+//!
+//! ## Backup Rotation
+//!
+//! Backups are numbered from newest to oldest (`foods.txt.bak.1` is the most
+//! recent previous version). Each backup keeps the modification time of the
+//! save it came from. The number of backups defaults to
+//! `DEFAULT_BACKUP_COUNT` and can be changed with the `YADA_BACKUPS`
+//! environment variable (`0` disables backups).
+//!
+//! ## Data Files
+//!
+//! Repositories save with `write_data_file` and load with `read_data_file`,
+//! which add the atomic write and the configured backups to the encryption
+//! layer in `repositories::encryption`.
+
+// yada-core/src/repositories/storage.rs
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::repositories::encryption;
+
+/// Environment variable used to configure how many backups are kept
+pub const BACKUP_COUNT_ENV_VAR: &str = "YADA_BACKUPS";
+
+/// Number of backups kept per data file when not configured
+pub const DEFAULT_BACKUP_COUNT: usize = 5;
+
+/// Returns the configured number of backups per data file
+///
+/// Reads `YADA_BACKUPS`, falling back to `DEFAULT_BACKUP_COUNT` when the
+/// variable is missing or not a number.
+pub fn backup_count() -> usize {
+    env::var(BACKUP_COUNT_ENV_VAR)
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(DEFAULT_BACKUP_COUNT)
+}
+
+/// Returns the path of the N-th backup of a data file (1 = newest)
+pub fn backup_path(file_path: &str, index: usize) -> String {
+    format!("{}.bak.{}", file_path, index)
+}
+
+/// Returns the path of the temporary file used while saving
+fn temp_path(file_path: &str) -> String {
+    format!("{}.tmp", file_path)
+}
+
+/// Saves a repository's data file, encrypted if the session encrypts saves
+///
+/// # Arguments
+/// * `file_path` - Data file to write
+/// * `contents` - Complete new file contents, as plain text
+///
+/// # Returns
+/// * `Ok(())` - The file now holds `contents`
+/// * `Err(io::Error)` - The write failed; the original file is unchanged
+pub fn write_data_file(file_path: &str, contents: &[u8]) -> Result<(), io::Error> {
+    if is_unchanged(file_path, contents) {
+        return Ok(());
+    }
+    write_atomic(file_path, &encryption::encrypt(contents)?, backup_count())
+}
+
+/// Whether a data file already holds `contents`, stored the way this session saves
+///
+/// Encrypted files are compared by their plain contents, since every
+/// encryption of the same data differs. A file encrypted when saves are plain
+/// (or the other way round) counts as changed, so it is rewritten.
+fn is_unchanged(file_path: &str, contents: &[u8]) -> bool {
+    let Ok(current) = fs::read(file_path) else {
+        return false;
+    };
+    encryption::is_encrypted(&current) == encryption::encrypts_saves()
+        && encryption::decrypt(&current).is_ok_and(|plain| plain == contents)
+}
+
+/// Reads a repository's data file, decrypting it if it is encrypted
+///
+/// # Returns
+/// * `Ok(Vec<u8>)` - The plain contents
+/// * `Err(io::Error)` - The file cannot be read, or is encrypted and cannot
+///   be decrypted (see `encryption::decrypt`)
+pub fn read_data_file(file_path: &str) -> Result<Vec<u8>, io::Error> {
+    encryption::decrypt(&fs::read(file_path)?)
+}
+
+/// Atomically replaces a file's contents, keeping rotated backups
+///
+/// # Arguments
+/// * `file_path` - Data file to write
+/// * `contents` - Complete new file contents
+/// * `backups` - Number of previous versions to keep (0 disables backups)
+///
+/// # Returns
+/// * `Ok(())` - The file now holds `contents`; nothing is written (and no
+///   backup rotated) when it already did
+/// * `Err(io::Error)` - The write failed; the original file is unchanged
+pub fn write_atomic(file_path: &str, contents: &[u8], backups: usize) -> Result<(), io::Error> {
+    if fs::read(file_path).is_ok_and(|current| current == contents) {
+        return Ok(());
+    }
+    let temp = temp_path(file_path);
+
+    // Write and flush the new contents before touching the original file
+    let write_result = File::create(&temp).and_then(|mut file| {
+        file.write_all(contents)?;
+        file.sync_all()
+    });
+    if let Err(e) = write_result {
+        let _ = fs::remove_file(&temp);
+        return Err(e);
+    }
+
+    if backups > 0 && Path::new(file_path).exists() && let Err(e) = rotate_backups(file_path, backups) {
+        let _ = fs::remove_file(&temp);
+        return Err(e);
+    }
+
+    if let Err(e) = fs::rename(&temp, file_path) {
+        let _ = fs::remove_file(&temp);
+        return Err(e);
+    }
+    sync_parent_dir(file_path);
+    Ok(())
+}
+
+/// Shifts `.bak.N` files up by one and copies the current file to `.bak.1`
+///
+/// The oldest backup beyond `backups` is discarded. Copying (instead of
+/// renaming) keeps the data file in place until the atomic rename; the copy
+/// is given the data file's modification time, which renames keep.
+fn rotate_backups(file_path: &str, backups: usize) -> Result<(), io::Error> {
+    let oldest = backup_path(file_path, backups);
+    if Path::new(&oldest).exists() {
+        fs::remove_file(&oldest)?;
+    }
+
+    for index in (1..backups).rev() {
+        let from = backup_path(file_path, index);
+        if Path::new(&from).exists() {
+            fs::rename(&from, backup_path(file_path, index + 1))?;
+        }
+    }
+
+    let newest = backup_path(file_path, 1);
+    fs::copy(file_path, &newest)?;
+    let modified = fs::metadata(file_path)?.modified()?;
+    File::options().write(true).open(&newest)?.set_modified(modified)?;
+    Ok(())
+}
+
+/// Flushes the directory entry so the rename survives a power loss
+///
+/// Best effort: not all platforms allow opening directories, so errors are ignored.
+fn sync_parent_dir(file_path: &str) {
+    let parent = match Path::new(file_path).parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+
+    if let Ok(dir) = File::open(parent) {
+        let _ = dir.sync_all();
+    }
+}
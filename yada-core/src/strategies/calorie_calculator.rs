@@ -0,0 +1,401 @@
+// yada-core/src/strategies/calorie_calculator.rs
+use chrono::{Duration, NaiveDate};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::models::energy_balance::{self, AdaptiveEstimate};
+use crate::models::profile::{UserProfile, Gender};
+use crate::repositories::food_repository::FoodRepository;
+use crate::repositories::log_repository::LogRepository;
+use crate::strategies::target_modifier::{Adjustment, TargetPipeline};
+
+/// Method used when a profile names one that is not registered
+pub const FALLBACK_METHOD: &str = "harris_benedict";
+
+/// Name of the adaptive TDEE method
+pub const ADAPTIVE_METHOD: &str = "adaptive_tdee";
+
+/// Formula the adaptive method uses until there is enough data
+pub const ADAPTIVE_FALLBACK_METHOD: &str = "mifflin_st_jeor";
+
+/// Days before the target's date whose intake and weigh-ins the adaptive method uses
+pub const ADAPTIVE_WINDOW_DAYS: i64 = 28;
+
+/// Name of the method valid for children and teens
+pub const SCHOFIELD_METHOD: &str = "schofield";
+
+/// Age from which the adult-only formulas apply
+pub const ADULT_AGE: u32 = 18;
+
+/// How a day's calorie target was calculated
+#[derive(Debug, Clone, PartialEq)]
+pub struct TargetCalculation {
+    /// Basal metabolic rate in calories
+    pub bmr: f64,
+
+    /// Factor of the day's activity level
+    pub activity_multiplier: f64,
+
+    /// The method's estimate of the calories burned per day
+    pub tdee: f64,
+
+    /// Target modifiers that changed the TDEE, in the order applied
+    pub adjustments: Vec<Adjustment>,
+
+    /// Target after every modifier (0 when the day has none)
+    pub target: f64,
+
+    /// How the method arrived at the TDEE
+    pub explanation: String,
+}
+
+pub trait CalorieCalculator {
+    /// Basal metabolic rate on a date (0 before the first daily profile)
+    fn calculate_bmr(&self, profile: &UserProfile, date: NaiveDate) -> f64;
+    fn name(&self) -> &'static str;
+    fn description(&self) -> &'static str;
+
+    /// TDEE: the BMR times the multiplier of the day's activity level
+    ///
+    /// Days without a daily profile use the latest earlier one, and days
+    /// before the first have no target (0).
+    fn calculate_tdee(&self, profile: &UserProfile, date: NaiveDate) -> f64 {
+        profile.latest_daily_profile(date)
+            .map_or(0.0, |daily| self.calculate_bmr(profile, date) * daily.activity_level.multiplier())
+    }
+
+    /// Calculates a day's target with the standard target modifiers
+    ///
+    /// # Returns
+    /// The BMR, activity multiplier, TDEE, adjustments, and final target;
+    /// all 0 before the first daily profile
+    fn calculate_target_calories(&self, profile: &UserProfile, date: NaiveDate) -> TargetCalculation {
+        self.calculate_target_with(profile, date, &TargetPipeline::standard())
+    }
+
+    /// Calculates a day's target with the given target modifiers
+    ///
+    /// The menus use this to add the exercise credit (see
+    /// `strategies::target_modifier`).
+    fn calculate_target_with(&self, profile: &UserProfile, date: NaiveDate, pipeline: &TargetPipeline) -> TargetCalculation {
+        let Some(daily) = profile.latest_daily_profile(date) else {
+            return TargetCalculation {
+                bmr: 0.0,
+                activity_multiplier: 0.0,
+                tdee: 0.0,
+                adjustments: Vec::new(),
+                target: 0.0,
+                explanation: format!("No weight recorded on or before {}", date.format("%Y-%m-%d")),
+            };
+        };
+        let tdee = self.calculate_tdee(profile, date);
+        let breakdown = pipeline.apply(profile, date, tdee);
+        TargetCalculation {
+            bmr: self.calculate_bmr(profile, date),
+            activity_multiplier: daily.activity_level.multiplier(),
+            tdee,
+            adjustments: breakdown.adjustments,
+            target: breakdown.target,
+            explanation: self.explain(profile, date).unwrap_or_else(|| {
+                format!("{} BMR times the {:?} activity multiplier", self.description(), daily.activity_level)
+            }),
+        }
+    }
+
+    /// Final target of a day with the standard target modifiers
+    ///
+    /// This is the target shown to the user; `calculate_tdee` is the
+    /// strategy's own estimate before the modifiers.
+    fn daily_target_calories(&self, profile: &UserProfile, date: NaiveDate) -> f64 {
+        self.calculate_target_calories(profile, date).target
+    }
+
+    /// How the method arrived at the TDEE of a date, or how well it fits the
+    /// profile, when there is more to say than BMR times activity level
+    fn explain(&self, _profile: &UserProfile, _date: NaiveDate) -> Option<String> {
+        None
+    }
+}
+
+pub struct CalorieCalculatorFactory {
+    calculators: HashMap<String, Box<dyn CalorieCalculator>>,
+}
+
+impl Default for CalorieCalculatorFactory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CalorieCalculatorFactory {
+    pub fn new() -> Self {
+        let mut factory = CalorieCalculatorFactory {
+            calculators: HashMap::new(),
+        };
+        
+        // Register available calculators
+        factory.register_calculator(Box::new(HarrisBenedictCalculator {}));
+        factory.register_calculator(Box::new(MifflinStJeorCalculator {}));
+        factory.register_calculator(Box::new(AdaptiveTdeeCalculator::new(BTreeMap::new())));
+        factory.register_calculator(Box::new(SchofieldCalculator {}));
+        
+        factory
+    }
+    
+    /// Creates the factory with the logged intake the adaptive method needs
+    ///
+    /// # Arguments
+    /// * `daily_calories` - Calories of each logged day (see `energy_balance::daily_calories`)
+    pub fn with_intake(daily_calories: BTreeMap<NaiveDate, f64>) -> Self {
+        let mut factory = Self::new();
+        factory.set_intake(daily_calories);
+        factory
+    }
+    
+    /// Creates the factory with the intake logged in the repositories
+    ///
+    /// Adds up the whole log, so callers that keep the factory should only
+    /// call it again after the log changes.
+    pub fn from_repositories(food_repo: &FoodRepository, log_repo: &LogRepository) -> Self {
+        Self::with_intake(energy_balance::daily_calories(log_repo.get_logs(), food_repo.get_foods()))
+    }
+    
+    /// Replaces the logged intake the adaptive method uses
+    pub fn set_intake(&mut self, daily_calories: BTreeMap<NaiveDate, f64>) {
+        self.register_calculator(Box::new(AdaptiveTdeeCalculator::new(daily_calories)));
+    }
+    
+    pub fn register_calculator(&mut self, calculator: Box<dyn CalorieCalculator>) {
+        self.calculators.insert(calculator.name().to_string(), calculator);
+    }
+    
+    pub fn get_calculator(&self, name: &str) -> Option<&dyn CalorieCalculator> {
+        self.calculators.get(name).map(|boxed| boxed.as_ref())
+    }
+    
+    /// Calculator of the method the profile used on a date
+    ///
+    /// Days before a change of method keep the method of the time (see
+    /// `UserProfile::calculation_method_on`); an unknown method falls back
+    /// to `FALLBACK_METHOD`.
+    pub fn calculator_for(&self, profile: &UserProfile, date: NaiveDate) -> Option<&dyn CalorieCalculator> {
+        self.get_calculator(profile.calculation_method_on(date))
+            .or_else(|| self.get_calculator(FALLBACK_METHOD))
+    }
+    
+    /// Names of all registered methods, sorted
+    pub fn get_all_calculators(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.calculators.keys().map(|s| s.as_str()).collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+// Harris-Benedict Equation
+pub struct HarrisBenedictCalculator {}
+
+impl CalorieCalculator for HarrisBenedictCalculator {
+    fn calculate_bmr(&self, profile: &UserProfile, date: NaiveDate) -> f64 {
+        // Days without a weigh-in use the latest earlier one
+        let daily_profile = match profile.latest_daily_profile(date) {
+            Some(p) => p,
+            None => return 0.0, // No profile on or before this date
+        };
+        
+        // Unknown height or age fall back to population defaults
+        let metrics = profile.body_metrics(date);
+        let age = metrics.age_years;
+        let height = metrics.height_cm; // cm
+        let weight = daily_profile.weight; // kg
+        
+        // Base metabolic rate (BMR) calculation
+        match profile.gender_on(date) {
+            Gender::Male => 88.362 + (13.397 * weight) + (4.799 * height) - (5.677 * age as f64),
+            Gender::Female => 447.593 + (9.247 * weight) + (3.098 * height) - (4.330 * age as f64),
+            Gender::Other => {
+                // Average of male and female equations
+                let male_bmr = 88.362 + (13.397 * weight) + (4.799 * height) - (5.677 * age as f64);
+                let female_bmr = 447.593 + (9.247 * weight) + (3.098 * height) - (4.330 * age as f64);
+                (male_bmr + female_bmr) / 2.0
+            }
+        }
+    }
+    
+    fn explain(&self, profile: &UserProfile, date: NaiveDate) -> Option<String> {
+        adult_only_note(profile, date)
+    }
+
+    fn name(&self) -> &'static str {
+        "harris_benedict"
+    }
+    
+    fn description(&self) -> &'static str {
+        "Harris-Benedict Equation (Revised 1984)"
+    }
+}
+
+// Mifflin-St Jeor Equation
+pub struct MifflinStJeorCalculator {}
+
+impl CalorieCalculator for MifflinStJeorCalculator {
+    fn calculate_bmr(&self, profile: &UserProfile, date: NaiveDate) -> f64 {
+        // Days without a weigh-in use the latest earlier one
+        let daily_profile = match profile.latest_daily_profile(date) {
+            Some(p) => p,
+            None => return 0.0, // No profile on or before this date
+        };
+        
+        // Unknown height or age fall back to population defaults
+        let metrics = profile.body_metrics(date);
+        let age = metrics.age_years;
+        let height = metrics.height_cm; // cm
+        let weight = daily_profile.weight; // kg
+        
+        // Base metabolic rate (BMR) calculation
+        match profile.gender_on(date) {
+            Gender::Male => (10.0 * weight) + (6.25 * height) - (5.0 * age as f64) + 5.0,
+            Gender::Female => (10.0 * weight) + (6.25 * height) - (5.0 * age as f64) - 161.0,
+            Gender::Other => {
+                // Average of male and female equations
+                let male_bmr = (10.0 * weight) + (6.25 * height) - (5.0 * age as f64) + 5.0;
+                let female_bmr = (10.0 * weight) + (6.25 * height) - (5.0 * age as f64) - 161.0;
+                (male_bmr + female_bmr) / 2.0
+            }
+        }
+    }
+    
+    fn explain(&self, profile: &UserProfile, date: NaiveDate) -> Option<String> {
+        adult_only_note(profile, date)
+    }
+
+    fn name(&self) -> &'static str {
+        "mifflin_st_jeor"
+    }
+    
+    fn description(&self) -> &'static str {
+        "Mifflin-St Jeor Equation"
+    }
+}
+
+// Adaptive TDEE from the user's own intake and weight trend
+//
+// Formulas estimate what an average body of the user's size burns; this
+// method measures it instead (see `models::energy_balance`). The TDEE of a
+// date comes from the logged intake and the weigh-ins of the
+// `ADAPTIVE_WINDOW_DAYS` days before it, so the day being logged does not
+// count half-finished. Until those days have enough data, the Mifflin-St
+// Jeor TDEE is used. The BMR cannot be measured this way and is always
+// Mifflin-St Jeor's.
+pub struct AdaptiveTdeeCalculator {
+    daily_calories: BTreeMap<NaiveDate, f64>,
+    fallback: MifflinStJeorCalculator,
+}
+
+impl AdaptiveTdeeCalculator {
+    /// Creates the calculator from the calories of each logged day
+    pub fn new(daily_calories: BTreeMap<NaiveDate, f64>) -> Self {
+        AdaptiveTdeeCalculator { daily_calories, fallback: MifflinStJeorCalculator {} }
+    }
+
+    /// Estimate for a date from the window before it, if there is enough data
+    pub fn estimate(&self, profile: &UserProfile, date: NaiveDate) -> Option<AdaptiveEstimate> {
+        let weights = energy_balance::measured_weights(profile);
+        energy_balance::estimate(&self.daily_calories, &weights, date - Duration::days(ADAPTIVE_WINDOW_DAYS), date - Duration::days(1))
+    }
+}
+
+impl CalorieCalculator for AdaptiveTdeeCalculator {
+    fn calculate_bmr(&self, profile: &UserProfile, date: NaiveDate) -> f64 {
+        self.fallback.calculate_bmr(profile, date)
+    }
+
+    fn calculate_tdee(&self, profile: &UserProfile, date: NaiveDate) -> f64 {
+        if profile.latest_daily_profile(date).is_none() {
+            return 0.0;
+        }
+        match self.estimate(profile, date) {
+            Some(estimate) => estimate.tdee,
+            None => self.fallback.calculate_tdee(profile, date),
+        }
+    }
+
+    fn explain(&self, profile: &UserProfile, date: NaiveDate) -> Option<String> {
+        Some(match self.estimate(profile, date) {
+            Some(estimate) => format!(
+                "TDEE measured from {} logged day(s) averaging {:.0} kcal and a weight trend of {:+.2} kg/week",
+                estimate.days_logged, estimate.average_calories, estimate.weight_trend_per_week
+            ),
+            None => format!(
+                "Not enough logged days and weigh-ins in the last {} days yet; using the {} TDEE",
+                ADAPTIVE_WINDOW_DAYS, self.fallback.description()
+            ),
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        ADAPTIVE_METHOD
+    }
+
+    fn description(&self) -> &'static str {
+        "Adaptive TDEE from your logged intake and weight trend"
+    }
+}
+
+// Schofield Equations (WHO/FAO/UNU 1985)
+//
+// Weight-based equations with separate coefficients for age bands from
+// infancy on, so unlike Harris-Benedict and Mifflin-St Jeor they are valid
+// for children and teens. Adults get the Schofield adult bands.
+pub struct SchofieldCalculator {}
+
+impl SchofieldCalculator {
+    /// Slope and intercept of the equation for an age and gender
+    fn coefficients(age: u32, gender: &Gender) -> (f64, f64) {
+        let (male, female) = match age {
+            0..3 => ((59.512, -30.4), (58.317, -31.1)),
+            3..10 => ((22.706, 504.3), (20.315, 485.9)),
+            10..18 => ((17.686, 658.2), (13.384, 692.6)),
+            18..30 => ((15.057, 692.2), (14.818, 486.6)),
+            30..60 => ((11.472, 873.1), (8.126, 845.6)),
+            _ => ((11.711, 587.7), (9.082, 658.5)),
+        };
+        match gender {
+            Gender::Male => male,
+            Gender::Female => female,
+            // Average of male and female equations
+            Gender::Other => ((male.0 + female.0) / 2.0, (male.1 + female.1) / 2.0),
+        }
+    }
+}
+
+impl CalorieCalculator for SchofieldCalculator {
+    fn calculate_bmr(&self, profile: &UserProfile, date: NaiveDate) -> f64 {
+        // Days without a weigh-in use the latest earlier one
+        let daily_profile = match profile.latest_daily_profile(date) {
+            Some(p) => p,
+            None => return 0.0, // No profile on or before this date
+        };
+
+        // Unknown age falls back to the population default
+        let age = profile.body_metrics(date).age_years;
+        let (slope, intercept) = Self::coefficients(age, profile.gender_on(date));
+        slope * daily_profile.weight + intercept
+    }
+
+    fn name(&self) -> &'static str {
+        SCHOFIELD_METHOD
+    }
+
+    fn description(&self) -> &'static str {
+        "Schofield Equations (WHO/FAO/UNU), valid for children and teens"
+    }
+}
+
+/// Warns that an adult-only formula is used for someone under `ADULT_AGE`
+fn adult_only_note(profile: &UserProfile, date: NaiveDate) -> Option<String> {
+    profile.age(date)
+        .filter(|age| *age < ADULT_AGE)
+        .map(|age| format!(
+            "This formula is meant for adults; at {} the Schofield method ({}) fits better",
+            age, SCHOFIELD_METHOD
+        ))
+}
@@ -0,0 +1,132 @@
+//! # Health Metrics
+//!
+//! The steps from body measurements to the calorie target, so the
+//! statistics screen can show how a target is derived:
+//!
+//! 1. **BMI**: Weight in kilograms divided by the square of the height in
+//!    meters, with its WHO category (only when the height is known)
+//! 2. **BMR**: Calories burned at rest, from the selected calculation method
+//! 3. **Activity multiplier**: Factor of the day's activity level
+//! 4. **TDEE**: BMR times the activity multiplier
+//! 5. **Target**: TDEE after the standard target modifiers (see
+//!    `strategies::target_modifier`)
+//!
+//! Days without a daily profile use the latest earlier one, like the
+//! calculators do.
+
+// yada-core/src/strategies/health_metrics.rs
+use std::fmt;
+
+use chrono::NaiveDate;
+
+use crate::models::profile::{ActivityLevel, UserProfile};
+use crate::strategies::calorie_calculator::CalorieCalculator;
+
+/// WHO weight category of a BMI
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BmiCategory {
+    /// Below 18.5
+    Underweight,
+
+    /// 18.5 up to 25
+    Normal,
+
+    /// 25 up to 30
+    Overweight,
+
+    /// 30 and above
+    Obese,
+}
+
+impl BmiCategory {
+    /// Returns the category of a BMI
+    pub fn from_bmi(bmi: f64) -> Self {
+        if bmi < 18.5 {
+            BmiCategory::Underweight
+        } else if bmi < 25.0 {
+            BmiCategory::Normal
+        } else if bmi < 30.0 {
+            BmiCategory::Overweight
+        } else {
+            BmiCategory::Obese
+        }
+    }
+}
+
+impl fmt::Display for BmiCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            BmiCategory::Underweight => "Underweight",
+            BmiCategory::Normal => "Normal weight",
+            BmiCategory::Overweight => "Overweight",
+            BmiCategory::Obese => "Obese",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Body mass index from weight and height
+///
+/// # Arguments
+/// * `weight_kg` - Weight in kilograms
+/// * `height_cm` - Height in centimeters
+pub fn bmi(weight_kg: f64, height_cm: f64) -> f64 {
+    let height_m = height_cm / 100.0;
+    weight_kg / (height_m * height_m)
+}
+
+/// How a day's calorie target is derived
+#[derive(Debug, Clone, PartialEq)]
+pub struct HealthMetrics {
+    /// Weight the calculation uses in kilograms
+    pub weight_kg: f64,
+
+    /// Body mass index, or `None` if the height is unknown
+    pub bmi: Option<f64>,
+
+    /// Basal metabolic rate in calories
+    pub bmr: f64,
+
+    /// Activity level the multiplier comes from
+    pub activity_level: ActivityLevel,
+
+    /// Factor the BMR is multiplied by
+    pub activity_multiplier: f64,
+
+    /// Total daily energy expenditure in calories
+    pub tdee: f64,
+
+    /// Target after the standard target modifiers, in calories
+    pub target: f64,
+}
+
+impl HealthMetrics {
+    /// Calculates the metrics of a day
+    ///
+    /// # Arguments
+    /// * `profile` - The user profile
+    /// * `calculator` - Calculation method of the day
+    /// * `date` - The day
+    ///
+    /// # Returns
+    /// * `Some(HealthMetrics)` - The metrics
+    /// * `None` - If there is no daily profile on or before the date
+    pub fn calculate(profile: &UserProfile, calculator: &dyn CalorieCalculator, date: NaiveDate) -> Option<Self> {
+        let daily = profile.latest_daily_profile(date)?;
+        let calculation = calculator.calculate_target_calories(profile, date);
+        Some(HealthMetrics {
+            weight_kg: daily.weight,
+            bmi: profile.height_on(date).map(|height| bmi(daily.weight, height)),
+            bmr: calculation.bmr,
+            activity_level: daily.activity_level.clone(),
+            activity_multiplier: calculation.activity_multiplier,
+            tdee: calculation.tdee,
+            target: calculation.target,
+        })
+    }
+
+    /// WHO category of the BMI, if the height is known
+    pub fn bmi_category(&self) -> Option<BmiCategory> {
+        self.bmi.map(BmiCategory::from_bmi)
+    }
+}
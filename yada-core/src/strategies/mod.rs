@@ -0,0 +1,4 @@
+// yada-core/src/strategies/mod.rs
+pub mod calorie_calculator;
+pub mod health_metrics;
+pub mod target_modifier;
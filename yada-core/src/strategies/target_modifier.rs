@@ -0,0 +1,168 @@
+//! # Target Modifiers
+//!
+//! A calculation method estimates the calories burned per day (the TDEE).
+//! The target the user sees is that estimate run through a pipeline of
+//! modifiers, each adjusting the result of the one before:
+//!
+//! 1. **Life stage**: Pregnancy or breastfeeding additions (see `models::life_stage`)
+//! 2. **Goal**: The diet preset's deficit or surplus (see `models::diet_preset`)
+//! 3. **Weekday cycling**: The override of the day's weekday (see `models::weekday_target`)
+//! 4. **Exercise credit**: Calories burned, when `YADA_EXERCISE_CREDIT` is on
+//!    (see `models::exercise`); only pipelines given the exercise log have it
+//!
+//! Every step that changes the target is recorded, so the statistics screen
+//! can list them without knowing which features exist. Days without a
+//! target (0, before the first weigh-in) skip the pipeline and keep none.
+
+// yada-core/src/strategies/target_modifier.rs
+use chrono::{Datelike, NaiveDate};
+
+use crate::models::exercise;
+use crate::models::profile::UserProfile;
+use crate::models::weekday_target::TargetOverride;
+use crate::repositories::exercise_repository::ExerciseRepository;
+
+/// One step of the pipeline that changed the target
+#[derive(Debug, Clone, PartialEq)]
+pub struct Adjustment {
+    /// What changed the target, such as `Cutting Preset Adjustment`
+    pub label: String,
+
+    /// Calories added (negative when taken away)
+    pub change: f64,
+}
+
+/// A target and the steps that led to it
+#[derive(Debug, Clone, PartialEq)]
+pub struct TargetBreakdown {
+    /// Target from the calculation method, before any modifier
+    pub base: f64,
+
+    /// Steps that changed the target, in the order applied
+    pub adjustments: Vec<Adjustment>,
+
+    /// Target after every modifier
+    pub target: f64,
+}
+
+/// One step of the target pipeline
+pub trait TargetModifier {
+    /// Returns the target after this step
+    ///
+    /// # Arguments
+    /// * `profile` - The user profile
+    /// * `date` - The day of the target
+    /// * `target` - Target after the previous steps (always above 0)
+    ///
+    /// # Returns
+    /// * `Some((f64, String))` - The new target and the label describing the step
+    /// * `None` - If the step does not apply to the day
+    fn modify(&self, profile: &UserProfile, date: NaiveDate, target: f64) -> Option<(f64, String)>;
+}
+
+/// Adds the pregnancy or breastfeeding calories of the profile's life stage
+pub struct LifeStageAddition;
+
+impl TargetModifier for LifeStageAddition {
+    fn modify(&self, profile: &UserProfile, date: NaiveDate, target: f64) -> Option<(f64, String)> {
+        let stage = profile.life_stage?;
+        Some((stage.adjust(target, date), stage.describe(date)))
+    }
+}
+
+/// Applies the deficit or surplus of the profile's diet preset
+pub struct GoalAdjustment;
+
+impl TargetModifier for GoalAdjustment {
+    fn modify(&self, profile: &UserProfile, _date: NaiveDate, target: f64) -> Option<(f64, String)> {
+        let preset = profile.diet_preset?;
+        Some((preset.adjust(target), format!("{} Preset Adjustment", preset)))
+    }
+}
+
+/// Applies the override of the day's weekday
+pub struct WeekdayCycling;
+
+impl TargetModifier for WeekdayCycling {
+    fn modify(&self, profile: &UserProfile, date: NaiveDate, target: f64) -> Option<(f64, String)> {
+        let weekday = date.weekday();
+        let target_override = profile.weekday_targets.get(weekday)?;
+        let label = match target_override {
+            TargetOverride::Fixed(fixed) => format!("{} Target Override ({} kcal)", weekday, fixed),
+            TargetOverride::Adjust(_) => format!("{} Target Override", weekday),
+        };
+        Some((profile.weekday_targets.apply(date, target), label))
+    }
+}
+
+/// Adds the calories burned by the day's exercise
+pub struct ExerciseCredit<'a> {
+    exercises: &'a ExerciseRepository,
+}
+
+impl<'a> ExerciseCredit<'a> {
+    /// Creates the modifier from the exercise log
+    pub fn new(exercises: &'a ExerciseRepository) -> Self {
+        ExerciseCredit { exercises }
+    }
+}
+
+impl TargetModifier for ExerciseCredit<'_> {
+    fn modify(&self, _profile: &UserProfile, date: NaiveDate, target: f64) -> Option<(f64, String)> {
+        let burned = self.exercises.calories_burned(date);
+        Some((target + burned, "Exercise Credit".to_string()))
+    }
+}
+
+/// Modifiers run in order on a calculated target
+pub struct TargetPipeline<'a> {
+    modifiers: Vec<Box<dyn TargetModifier + 'a>>,
+}
+
+impl<'a> TargetPipeline<'a> {
+    /// Pipeline of the modifiers every target gets: life stage, goal, and weekday cycling
+    pub fn standard() -> Self {
+        TargetPipeline { modifiers: Vec::new() }
+            .with(LifeStageAddition)
+            .with(GoalAdjustment)
+            .with(WeekdayCycling)
+    }
+
+    /// Adds a modifier after those already in the pipeline
+    pub fn with(mut self, modifier: impl TargetModifier + 'a) -> Self {
+        self.modifiers.push(Box::new(modifier));
+        self
+    }
+
+    /// Adds the exercise credit, if `YADA_EXERCISE_CREDIT` enables it
+    pub fn with_exercise_credit(self, exercises: &'a ExerciseRepository) -> Self {
+        if exercise::credit_enabled() {
+            self.with(ExerciseCredit::new(exercises))
+        } else {
+            self
+        }
+    }
+
+    /// Runs a calculated target through the modifiers
+    ///
+    /// # Arguments
+    /// * `profile` - The user profile
+    /// * `date` - The day of the target
+    /// * `base` - Target from the calculation method (0 when the day has none)
+    pub fn apply(&self, profile: &UserProfile, date: NaiveDate, base: f64) -> TargetBreakdown {
+        let mut adjustments = Vec::new();
+        let mut target = base;
+        if base > 0.0 {
+            for modifier in &self.modifiers {
+                let Some((modified, label)) = modifier.modify(profile, date, target) else {
+                    continue;
+                };
+                if modified != target {
+                    adjustments.push(Adjustment { label, change: modified - target });
+                }
+                target = modified;
+            }
+        }
+        TargetBreakdown { base, adjustments, target }
+    }
+}
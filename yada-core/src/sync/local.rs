@@ -0,0 +1,59 @@
+//! # Folder Backend
+//!
+//! Keeps the copies in an ordinary folder, mirroring the layout of the data
+//! directory. Useful with a mounted network share, a USB stick, or a folder
+//! that another tool (Syncthing, Dropbox, ...) already synchronizes.
+
+// yada-core/src/sync/local.rs
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+
+use super::{SyncBackend, whole_seconds};
+use crate::repositories::storage;
+
+/// Sync backend storing files in a folder
+#[derive(Debug, Clone)]
+pub struct FolderBackend {
+    root: PathBuf,
+}
+
+impl FolderBackend {
+    /// Creates a backend for a folder, which is created on the first upload
+    pub fn new(root: impl AsRef<Path>) -> Self {
+        FolderBackend { root: root.as_ref().to_path_buf() }
+    }
+
+    /// Path of a stored file
+    fn path(&self, name: &str) -> PathBuf {
+        name.split('/').fold(self.root.clone(), |path, part| path.join(part))
+    }
+}
+
+impl SyncBackend for FolderBackend {
+    fn describe(&self) -> String {
+        format!("folder {}", self.root.display())
+    }
+
+    fn modified(&self, name: &str) -> io::Result<Option<DateTime<Utc>>> {
+        match fs::metadata(self.path(name)) {
+            Ok(metadata) => Ok(Some(whole_seconds(metadata.modified()?.into()))),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn download(&self, name: &str) -> io::Result<Vec<u8>> {
+        fs::read(self.path(name))
+    }
+
+    fn upload(&self, name: &str, contents: &[u8]) -> io::Result<()> {
+        let path = self.path(name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        storage::write_atomic(&path.to_string_lossy(), contents, 0)
+    }
+}
@@ -0,0 +1,333 @@
+//! # Sync Module
+//!
+//! Keeps the data files of one data directory in step with a copy kept
+//! elsewhere, so the same logs can be used on a laptop and a desktop. Where
+//! the copy lives is a `SyncBackend`:
+//!
+//! - `local::FolderBackend`: Any folder, such as a mounted network share or a
+//!   folder synchronized by another tool
+//! - `webdav::WebDavBackend`: A WebDAV collection (Nextcloud, ownCloud, ...)
+//! - `s3::S3Backend`: A bucket of Amazon S3 or a compatible service
+//!
+//! ## Conflict Detection
+//!
+//! After each sync, the last-modified times of both copies of every file are
+//! recorded in `sync_state.txt` in the data directory. On the next sync, a
+//! file changed on one side only is copied to the other side. A file changed
+//! on both sides is a conflict: nothing is copied unless the copies are
+//! identical or a side is preferred (`Side`), and the conflict is reported.
+//! Files are always copied whole and byte for byte, so encrypted files stay
+//! encrypted. Local files replaced by a download are backed up first (see
+//! `repositories::storage`).
+//!
+//! ## Configuration
+//!
+//! `backend_from_spec` builds a backend from a setting such as the
+//! `YADA_SYNC` environment variable:
+//! ```text
+//! folder:/mnt/share/yada
+//! webdav:https://cloud.example.com/remote.php/dav/files/sam/yada
+//! s3:my-bucket/yada
+//! ```
+//! WebDAV credentials come from `YADA_SYNC_USER` and `YADA_SYNC_PASSWORD`;
+//! S3 uses the usual `AWS_*` variables (see `s3`).
+//!
+//! ## Module Organization
+//!
+//! - `local`: Folder backend
+//! - `webdav`: WebDAV backend
+//! - `s3`: S3 backend with request signing
+
+// yada-core/src/sync/mod.rs
+pub mod local;
+pub mod webdav;
+pub mod s3;
+
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use chrono::{DateTime, TimeZone, Utc};
+
+use crate::repositories::{format, storage};
+
+/// Environment variable naming the sync backend
+pub const SYNC_ENV_VAR: &str = "YADA_SYNC";
+
+/// File in the data directory recording the state after the last sync
+pub const STATE_FILE: &str = "sync_state.txt";
+
+/// Where a copy of the data files is kept
+///
+/// File names are relative to the data directory and use `/` between
+/// directories (e.g. `users/sam/logs.txt`).
+pub trait SyncBackend {
+    /// Describes the location, e.g. `folder /mnt/share/yada`
+    fn describe(&self) -> String;
+
+    /// Last-modified time of a stored file
+    ///
+    /// # Returns
+    /// * `Ok(Some(time))` - When the file was last written
+    /// * `Ok(None)` - The file is not stored
+    fn modified(&self, name: &str) -> io::Result<Option<DateTime<Utc>>>;
+
+    /// Reads a stored file
+    fn download(&self, name: &str) -> io::Result<Vec<u8>>;
+
+    /// Stores a file, replacing any previous version
+    fn upload(&self, name: &str, contents: &[u8]) -> io::Result<()>;
+}
+
+/// Side that wins conflicts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    /// The files in the data directory
+    Local,
+
+    /// The copies kept by the backend
+    Remote,
+}
+
+/// What a sync did (or in a dry run would do) with one file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncAction {
+    /// Neither copy changed since the last sync, or both are identical
+    UpToDate,
+
+    /// The local file was copied to the backend
+    Uploaded,
+
+    /// The backend's copy replaced the local file
+    Downloaded,
+
+    /// Both copies changed since the last sync; nothing was copied
+    Conflict,
+
+    /// The file exists on neither side
+    Missing,
+}
+
+/// Result for one file
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileSync {
+    /// File name relative to the data directory
+    pub name: String,
+
+    /// What happened to the file
+    pub action: SyncAction,
+}
+
+/// Result of a sync
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncReport {
+    /// The backend, as described by `SyncBackend::describe`
+    pub backend: String,
+
+    /// Results per file, in the order given
+    pub files: Vec<FileSync>,
+
+    /// Whether nothing was written
+    pub dry_run: bool,
+}
+
+impl SyncReport {
+    /// Names of the files left in conflict
+    pub fn conflicts(&self) -> Vec<&str> {
+        self.files
+            .iter()
+            .filter(|file| file.action == SyncAction::Conflict)
+            .map(|file| file.name.as_str())
+            .collect()
+    }
+}
+
+/// Builds a backend from a setting such as `folder:<dir>`, `webdav:<url>`, or `s3:<bucket>[/<prefix>]`
+///
+/// # Returns
+/// * `Ok(Box<dyn SyncBackend>)` - The backend
+/// * `Err(String)` - If the setting is not understood or credentials are missing
+pub fn backend_from_spec(spec: &str) -> Result<Box<dyn SyncBackend>, String> {
+    let spec = spec.trim();
+    let (kind, target) = spec
+        .split_once(':')
+        .ok_or_else(|| format!("Sync setting '{}' must start with folder:, webdav:, or s3:", spec))?;
+    let target = target.trim();
+    if target.is_empty() {
+        return Err(format!("Sync setting '{}' names no location", spec));
+    }
+    match kind.trim().to_lowercase().as_str() {
+        "folder" => Ok(Box::new(local::FolderBackend::new(target))),
+        "webdav" => Ok(Box::new(webdav::WebDavBackend::from_env(target)?)),
+        "s3" => Ok(Box::new(s3::S3Backend::from_env(target)?)),
+        other => Err(format!("Unknown sync backend '{}'; use folder, webdav, or s3", other)),
+    }
+}
+
+/// Builds the backend named by `YADA_SYNC`
+///
+/// # Returns
+/// `None` when the variable is not set
+pub fn backend_from_env() -> Option<Result<Box<dyn SyncBackend>, String>> {
+    let spec = env::var(SYNC_ENV_VAR).ok().filter(|spec| !spec.trim().is_empty())?;
+    Some(backend_from_spec(&spec))
+}
+
+/// Synchronizes data files with a backend
+///
+/// # Arguments
+/// * `data_dir` - The data directory (also holds the sync state)
+/// * `names` - Files to synchronize, relative to the data directory
+/// * `backend` - Where the other copies are kept
+/// * `prefer` - Side whose copy wins conflicts; `None` leaves conflicts alone
+/// * `dry_run` - Only report what would happen; write nothing
+///
+/// # Returns
+/// * `Ok(SyncReport)` - What happened to each file
+/// * `Err(String)` - If a file cannot be read, written, or transferred
+pub fn sync_files(
+    data_dir: &Path,
+    names: &[String],
+    backend: &dyn SyncBackend,
+    prefer: Option<Side>,
+    dry_run: bool,
+) -> Result<SyncReport, String> {
+    let describe = backend.describe();
+    let state_path = data_dir.join(STATE_FILE).to_string_lossy().into_owned();
+    let mut state = SyncState::load(&state_path, &describe);
+    let mut report = SyncReport { backend: describe.clone(), files: Vec::new(), dry_run };
+
+    for name in names {
+        let path = data_dir.join(name);
+        let local = local_modified(&path).map_err(|e| format!("Error reading {}: {}", path.display(), e))?;
+        let remote = backend.modified(name).map_err(|e| format!("Error checking {} on {}: {}", name, describe, e))?;
+        let known = state.files.get(name).copied().unwrap_or_default();
+        let local_changed = local != known.local;
+        let remote_changed = remote != known.remote;
+
+        let action = match (local, remote) {
+            (None, None) => SyncAction::Missing,
+            (Some(_), None) => SyncAction::Uploaded,
+            (None, Some(_)) => SyncAction::Downloaded,
+            _ if !local_changed && !remote_changed => SyncAction::UpToDate,
+            _ if !remote_changed => SyncAction::Uploaded,
+            _ if !local_changed => SyncAction::Downloaded,
+            _ => match prefer {
+                Some(Side::Local) => SyncAction::Uploaded,
+                Some(Side::Remote) => SyncAction::Downloaded,
+                None if same_contents(&path, name, backend)? => SyncAction::UpToDate,
+                None => SyncAction::Conflict,
+            },
+        };
+        report.files.push(FileSync { name: name.clone(), action });
+        if dry_run {
+            continue;
+        }
+
+        let transfer_error = |e: io::Error| format!("Error syncing {} with {}: {}", name, describe, e);
+        match action {
+            SyncAction::Uploaded => {
+                let contents = fs::read(&path).map_err(transfer_error)?;
+                backend.upload(name, &contents).map_err(transfer_error)?;
+            }
+            SyncAction::Downloaded => {
+                let contents = backend.download(name).map_err(transfer_error)?;
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent).map_err(transfer_error)?;
+                }
+                storage::write_atomic(&path.to_string_lossy(), &contents, storage::backup_count())
+                    .map_err(transfer_error)?;
+            }
+            SyncAction::UpToDate => {}
+            SyncAction::Conflict | SyncAction::Missing => continue,
+        }
+        let synced = FileState {
+            local: local_modified(&path).map_err(transfer_error)?,
+            remote: backend.modified(name).map_err(transfer_error)?,
+        };
+        state.files.insert(name.clone(), synced);
+    }
+
+    if !dry_run {
+        state.save(&state_path).map_err(|e| format!("Error writing {}: {}", state_path, e))?;
+    }
+    Ok(report)
+}
+
+/// Whether the local file and the backend's copy hold the same bytes
+fn same_contents(path: &Path, name: &str, backend: &dyn SyncBackend) -> Result<bool, String> {
+    let local = fs::read(path).map_err(|e| format!("Error reading {}: {}", path.display(), e))?;
+    let remote = backend.download(name).map_err(|e| format!("Error reading {} from {}: {}", name, backend.describe(), e))?;
+    Ok(local == remote)
+}
+
+/// Last-modified time of a local file, to the second
+fn local_modified(path: &Path) -> io::Result<Option<DateTime<Utc>>> {
+    match fs::metadata(path) {
+        Ok(metadata) => Ok(Some(whole_seconds(metadata.modified()?.into()))),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Drops the fraction of a second, which HTTP dates cannot carry
+pub(crate) fn whole_seconds(time: DateTime<Utc>) -> DateTime<Utc> {
+    Utc.timestamp_opt(time.timestamp(), 0).single().unwrap_or(time)
+}
+
+/// Last-modified times of both copies of a file after the last sync
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct FileState {
+    local: Option<DateTime<Utc>>,
+    remote: Option<DateTime<Utc>>,
+}
+
+/// Contents of the sync state file
+///
+/// ```text
+/// #yada:sync:v1
+/// BACKEND|folder /mnt/share/yada
+/// logs.txt|2024-03-05T08:00:00+00:00|2024-03-05T08:00:01+00:00
+/// ```
+struct SyncState {
+    backend: String,
+    files: HashMap<String, FileState>,
+}
+
+impl SyncState {
+    /// Reads the state recorded for a backend; state of another backend is ignored
+    fn load(path: &str, backend: &str) -> Self {
+        let mut state = SyncState { backend: backend.to_string(), files: HashMap::new() };
+        let Ok(contents) = fs::read_to_string(path) else {
+            return state;
+        };
+        let mut same_backend = false;
+        for line in contents.lines().filter(|line| !format::is_ignored_line(line)) {
+            let parts = format::split_escaped(line, '|');
+            match parts.as_slice() {
+                ["BACKEND", name] => same_backend = format::unescape(name) == backend,
+                [name, local, remote] if same_backend => {
+                    let time = |text: &str| DateTime::parse_from_rfc3339(text).ok().map(|time| time.with_timezone(&Utc));
+                    state.files.insert(format::unescape(name), FileState { local: time(local), remote: time(remote) });
+                }
+                _ => {}
+            }
+        }
+        state
+    }
+
+    /// Writes the state
+    fn save(&self, path: &str) -> io::Result<()> {
+        let time = |time: Option<DateTime<Utc>>| time.map_or(String::new(), |time| time.to_rfc3339());
+        let mut names: Vec<&String> = self.files.keys().collect();
+        names.sort();
+        let mut contents = format!("{}\nBACKEND|{}\n", format::header("sync"), format::escape(&self.backend));
+        for name in names {
+            let file = self.files[name];
+            contents.push_str(&format!("{}|{}|{}\n", format::escape(name), time(file.local), time(file.remote)));
+        }
+        storage::write_atomic(path, contents.as_bytes(), 0)
+    }
+}
@@ -0,0 +1,246 @@
+//! # S3 Backend
+//!
+//! Keeps the copies as objects in an Amazon S3 bucket, or in any service
+//! speaking the S3 API (MinIO, Backblaze B2, Cloudflare R2, ...). Requests
+//! use path-style URLs and are signed with AWS Signature Version 4.
+//!
+//! ## Settings
+//!
+//! - `AWS_ACCESS_KEY_ID` and `AWS_SECRET_ACCESS_KEY`: Credentials (required)
+//! - `AWS_SESSION_TOKEN`: Token for temporary credentials
+//! - `AWS_REGION`: Region of the bucket (default `us-east-1`)
+//! - `YADA_S3_ENDPOINT`: Endpoint of another S3 service, e.g.
+//!   `http://localhost:9000` (default: Amazon S3 in the region)
+
+// yada-core/src/sync/s3.rs
+use std::env;
+use std::io::{self, Read};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+use super::SyncBackend;
+
+/// Environment variable with the endpoint of a non-Amazon S3 service
+pub const ENDPOINT_ENV_VAR: &str = "YADA_S3_ENDPOINT";
+
+/// Region used when `AWS_REGION` is not set
+const DEFAULT_REGION: &str = "us-east-1";
+
+/// Time allowed for each request
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Credentials for signing requests
+#[derive(Debug, Clone)]
+pub struct S3Credentials {
+    /// Access key ID
+    pub access_key: String,
+
+    /// Secret access key
+    pub secret_key: String,
+
+    /// Session token of temporary credentials
+    pub session_token: Option<String>,
+}
+
+/// Sync backend storing files as objects in an S3 bucket
+pub struct S3Backend {
+    endpoint: String,
+    region: String,
+    bucket: String,
+    prefix: String,
+    credentials: S3Credentials,
+    agent: ureq::Agent,
+}
+
+impl S3Backend {
+    /// Creates a backend for a bucket
+    ///
+    /// # Arguments
+    /// * `endpoint` - Base URL of the service, e.g. `https://s3.eu-west-1.amazonaws.com`
+    /// * `region` - Region used in signatures
+    /// * `location` - Bucket name, optionally followed by `/` and a key prefix
+    /// * `credentials` - Keys for signing requests
+    pub fn new(endpoint: &str, region: &str, location: &str, credentials: S3Credentials) -> Self {
+        let (bucket, prefix) = location.split_once('/').unwrap_or((location, ""));
+        S3Backend {
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            region: region.to_string(),
+            bucket: bucket.to_string(),
+            prefix: prefix.trim_matches('/').to_string(),
+            credentials,
+            agent: ureq::AgentBuilder::new().timeout(TIMEOUT).build(),
+        }
+    }
+
+    /// Creates a backend with the settings from the environment (see the module documentation)
+    ///
+    /// # Returns
+    /// * `Ok(S3Backend)` - The backend
+    /// * `Err(String)` - If the credentials are not set
+    pub fn from_env(location: &str) -> Result<Self, String> {
+        let variable = |name: &str| env::var(name).ok().filter(|value| !value.is_empty());
+        let (Some(access_key), Some(secret_key)) = (variable("AWS_ACCESS_KEY_ID"), variable("AWS_SECRET_ACCESS_KEY"))
+        else {
+            return Err("S3 sync needs AWS_ACCESS_KEY_ID and AWS_SECRET_ACCESS_KEY".to_string());
+        };
+        let region = variable("AWS_REGION").unwrap_or_else(|| DEFAULT_REGION.to_string());
+        let endpoint = variable(ENDPOINT_ENV_VAR).unwrap_or_else(|| format!("https://s3.{}.amazonaws.com", region));
+        let credentials = S3Credentials { access_key, secret_key, session_token: variable("AWS_SESSION_TOKEN") };
+        Ok(Self::new(&endpoint, &region, location, credentials))
+    }
+
+    /// Path of an object, URI-encoded as signatures require
+    fn object_path(&self, name: &str) -> String {
+        let key = if self.prefix.is_empty() { name.to_string() } else { format!("{}/{}", self.prefix, name) };
+        let mut path = format!("/{}", uri_encode(&self.bucket));
+        for segment in key.split('/') {
+            path.push('/');
+            path.push_str(&uri_encode(segment));
+        }
+        path
+    }
+
+    /// Builds a signed request for an object
+    fn request(&self, method: &str, name: &str, payload: &[u8]) -> ureq::Request {
+        let path = self.object_path(name);
+        let host = self.endpoint.split_once("://").map_or(self.endpoint.as_str(), |(_, rest)| rest);
+        let host = host.split('/').next().unwrap_or(host);
+        let now = Utc::now();
+        let date_time = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let payload_hash = hex(&Sha256::digest(payload));
+
+        let mut headers = vec![
+            ("host", host.to_string()),
+            ("x-amz-content-sha256", payload_hash.clone()),
+            ("x-amz-date", date_time.clone()),
+        ];
+        if let Some(token) = &self.credentials.session_token {
+            headers.push(("x-amz-security-token", token.clone()));
+        }
+        let authorization = self.authorization(method, &path, &headers, &payload_hash, &date_time);
+
+        let mut request = self
+            .agent
+            .request(method, &format!("{}{}", self.endpoint, path))
+            .set("Authorization", &authorization);
+        for (name, value) in headers.iter().filter(|(name, _)| *name != "host") {
+            request = request.set(name, value);
+        }
+        request
+    }
+
+    /// Computes the Signature Version 4 `Authorization` header
+    ///
+    /// # Arguments
+    /// * `headers` - Signed headers, lowercase and sorted by name
+    fn authorization(
+        &self,
+        method: &str,
+        path: &str,
+        headers: &[(&str, String)],
+        payload_hash: &str,
+        date_time: &str,
+    ) -> String {
+        let date = &date_time[..8];
+        let scope = format!("{}/{}/s3/aws4_request", date, self.region);
+        let signed_headers = headers.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(";");
+        let canonical_headers: String =
+            headers.iter().map(|(name, value)| format!("{}:{}\n", name, value.trim())).collect();
+        let canonical_request =
+            format!("{}\n{}\n\n{}\n{}\n{}", method, path, canonical_headers, signed_headers, payload_hash);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            date_time,
+            scope,
+            hex(&Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let key = [date, &self.region, "s3", "aws4_request"]
+            .iter()
+            .fold(format!("AWS4{}", self.credentials.secret_key).into_bytes(), |key, part| hmac(&key, part.as_bytes()));
+        format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.credentials.access_key,
+            scope,
+            signed_headers,
+            hex(&hmac(&key, string_to_sign.as_bytes()))
+        )
+    }
+}
+
+impl SyncBackend for S3Backend {
+    fn describe(&self) -> String {
+        if self.prefix.is_empty() {
+            format!("S3 {}", self.bucket)
+        } else {
+            format!("S3 {}/{}", self.bucket, self.prefix)
+        }
+    }
+
+    fn modified(&self, name: &str) -> io::Result<Option<DateTime<Utc>>> {
+        match self.request("HEAD", name, b"").call() {
+            Ok(response) => {
+                let header = response.header("Last-Modified").ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidData, "the service sent no Last-Modified time")
+                })?;
+                let time = DateTime::parse_from_rfc2822(header)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("bad Last-Modified '{}': {}", header, e)))?;
+                Ok(Some(time.with_timezone(&Utc)))
+            }
+            Err(ureq::Error::Status(404, _)) => Ok(None),
+            Err(e) => Err(s3_error(e)),
+        }
+    }
+
+    fn download(&self, name: &str) -> io::Result<Vec<u8>> {
+        let response = self.request("GET", name, b"").call().map_err(s3_error)?;
+        let mut contents = Vec::new();
+        response.into_reader().read_to_end(&mut contents)?;
+        Ok(contents)
+    }
+
+    fn upload(&self, name: &str, contents: &[u8]) -> io::Result<()> {
+        self.request("PUT", name, contents).send_bytes(contents).map_err(s3_error)?;
+        Ok(())
+    }
+}
+
+/// Converts a request failure to an I/O error
+fn s3_error(error: ureq::Error) -> io::Error {
+    match error {
+        ureq::Error::Status(403, _) => io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "access denied; check the AWS credentials and region",
+        ),
+        ureq::Error::Status(code, response) => {
+            io::Error::other(format!("the service answered {} {}", code, response.status_text()))
+        }
+        ureq::Error::Transport(transport) => io::Error::other(transport.to_string()),
+    }
+}
+
+/// HMAC-SHA256 of a message
+fn hmac(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Lowercase hexadecimal digits of bytes
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Percent-encodes a path segment as Signature Version 4 requires
+fn uri_encode(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (byte as char).to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
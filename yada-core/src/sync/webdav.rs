@@ -0,0 +1,134 @@
+//! # WebDAV Backend
+//!
+//! Keeps the copies in a WebDAV collection, as offered by Nextcloud, ownCloud,
+//! and most NAS systems. Files are read with `GET`, written with `PUT`, and
+//! their last-modified times come from the `Last-Modified` header of a
+//! `HEAD` request. Missing parent collections are created with `MKCOL`.
+//!
+//! ## Credentials
+//!
+//! `YADA_SYNC_USER` and `YADA_SYNC_PASSWORD` are sent with Basic
+//! authentication, so the URL should use `https`.
+
+// yada-core/src/sync/webdav.rs
+use std::env;
+use std::io::{self, Read};
+use std::time::Duration;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use chrono::{DateTime, Utc};
+
+use super::SyncBackend;
+
+/// Environment variable with the WebDAV user name
+pub const USER_ENV_VAR: &str = "YADA_SYNC_USER";
+
+/// Environment variable with the WebDAV password
+pub const PASSWORD_ENV_VAR: &str = "YADA_SYNC_PASSWORD";
+
+/// Time allowed for each request
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Sync backend storing files in a WebDAV collection
+pub struct WebDavBackend {
+    base_url: String,
+    authorization: Option<String>,
+    agent: ureq::Agent,
+}
+
+impl WebDavBackend {
+    /// Creates a backend for a collection URL
+    ///
+    /// # Arguments
+    /// * `url` - URL of the collection holding the files
+    /// * `credentials` - User name and password, if the server needs them
+    pub fn new(url: &str, credentials: Option<(&str, &str)>) -> Self {
+        WebDavBackend {
+            base_url: url.trim_end_matches('/').to_string(),
+            authorization: credentials
+                .map(|(user, password)| format!("Basic {}", STANDARD.encode(format!("{}:{}", user, password)))),
+            agent: ureq::AgentBuilder::new().timeout(TIMEOUT).build(),
+        }
+    }
+
+    /// Creates a backend with the credentials from `YADA_SYNC_USER` and `YADA_SYNC_PASSWORD`
+    ///
+    /// # Returns
+    /// * `Ok(WebDavBackend)` - The backend
+    /// * `Err(String)` - If the URL is not an HTTP URL
+    pub fn from_env(url: &str) -> Result<Self, String> {
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err(format!("WebDAV location '{}' must be an http:// or https:// URL", url));
+        }
+        let user = env::var(USER_ENV_VAR).ok();
+        let password = env::var(PASSWORD_ENV_VAR).unwrap_or_default();
+        Ok(Self::new(url, user.as_deref().map(|user| (user, password.as_str()))))
+    }
+
+    /// URL of a stored file or collection
+    fn url(&self, name: &str) -> String {
+        format!("{}/{}", self.base_url, name)
+    }
+
+    /// Starts a request with the credentials set
+    fn request(&self, method: &str, url: &str) -> ureq::Request {
+        let request = self.agent.request(method, url);
+        match &self.authorization {
+            Some(authorization) => request.set("Authorization", authorization),
+            None => request,
+        }
+    }
+}
+
+impl SyncBackend for WebDavBackend {
+    fn describe(&self) -> String {
+        format!("WebDAV {}", self.base_url)
+    }
+
+    fn modified(&self, name: &str) -> io::Result<Option<DateTime<Utc>>> {
+        match self.request("HEAD", &self.url(name)).call() {
+            Ok(response) => {
+                let header = response.header("Last-Modified").ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidData, "the server sent no Last-Modified time")
+                })?;
+                let time = DateTime::parse_from_rfc2822(header)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("bad Last-Modified '{}': {}", header, e)))?;
+                Ok(Some(time.with_timezone(&Utc)))
+            }
+            Err(ureq::Error::Status(404, _)) => Ok(None),
+            Err(e) => Err(http_error(e)),
+        }
+    }
+
+    fn download(&self, name: &str) -> io::Result<Vec<u8>> {
+        let response = self.request("GET", &self.url(name)).call().map_err(http_error)?;
+        let mut contents = Vec::new();
+        response.into_reader().read_to_end(&mut contents)?;
+        Ok(contents)
+    }
+
+    fn upload(&self, name: &str, contents: &[u8]) -> io::Result<()> {
+        // Create parent collections; existing ones answer 405, which is fine
+        let parts: Vec<&str> = name.split('/').collect();
+        for depth in 1..parts.len() {
+            let _ = self.request("MKCOL", &self.url(&parts[..depth].join("/"))).call();
+        }
+        self.request("PUT", &self.url(name)).send_bytes(contents).map_err(http_error)?;
+        Ok(())
+    }
+}
+
+/// Converts a request failure to an I/O error
+fn http_error(error: ureq::Error) -> io::Error {
+    match error {
+        ureq::Error::Status(401 | 403, _) => io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("access denied; check {} and {}", USER_ENV_VAR, PASSWORD_ENV_VAR),
+        ),
+        ureq::Error::Status(code, response) => {
+            io::Error::other(format!("the server answered {} {}", code, response.status_text()))
+        }
+        ureq::Error::Transport(transport) => io::Error::other(transport.to_string()),
+    }
+}
@@ -0,0 +1,76 @@
+//! Tests for age calculation, including leap-day birthdays and dates before birth
+
+// yada-core/tests/age.rs
+use chrono::{Datelike, Duration, NaiveDate};
+use proptest::prelude::*;
+
+use yada_core::models::age::{age_on, birthday_in};
+use yada_core::models::profile::{Gender, UserProfile};
+
+fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+/// Any day from 1900 to 2100
+fn any_date() -> impl Strategy<Value = NaiveDate> {
+    (0i64..73_049).prop_map(|offset| ymd(1900, 1, 1) + Duration::days(offset))
+}
+
+#[test]
+fn leap_day_birthdays_count_from_march_first_in_common_years() {
+    let birth = ymd(2000, 2, 29);
+    assert_eq!(birthday_in(birth, 2023), Some(ymd(2023, 3, 1)));
+    assert_eq!(birthday_in(birth, 2024), Some(ymd(2024, 2, 29)));
+
+    assert_eq!(age_on(birth, ymd(2023, 2, 28)), 22);
+    assert_eq!(age_on(birth, ymd(2023, 3, 1)), 23);
+    assert_eq!(age_on(birth, ymd(2024, 2, 28)), 23);
+    assert_eq!(age_on(birth, ymd(2024, 2, 29)), 24);
+
+    // The old calculation counted the birthday as passed from January 1
+    assert_eq!(age_on(birth, ymd(2023, 1, 1)), 22);
+}
+
+#[test]
+fn dates_before_birth_have_an_age_of_zero() {
+    let birth = ymd(1990, 6, 15);
+    assert_eq!(age_on(birth, ymd(1990, 6, 14)), 0);
+    assert_eq!(age_on(birth, ymd(1980, 1, 1)), 0);
+    assert_eq!(age_on(birth, birth), 0);
+    assert_eq!(age_on(birth, ymd(1991, 6, 14)), 0);
+    assert_eq!(age_on(birth, ymd(1991, 6, 15)), 1);
+
+    // The profile treats such a birth date as unusable instead
+    let profile = UserProfile::new(Gender::Female, 165.0, birth);
+    assert_eq!(profile.age(ymd(1990, 6, 14)), None);
+    assert_eq!(profile.age(ymd(2024, 2, 29)), Some(33));
+}
+
+proptest! {
+    #[test]
+    fn age_never_decreases(birth in any_date(), date in any_date(), later in 0i64..800) {
+        prop_assert!(age_on(birth, date) <= age_on(birth, date + Duration::days(later)));
+    }
+
+    #[test]
+    fn age_goes_up_by_one_on_each_birthday(birth in any_date(), years in 1i32..100) {
+        let Some(birthday) = birthday_in(birth, birth.year() + years) else {
+            return Ok(());
+        };
+        prop_assert_eq!(age_on(birth, birthday), years as u32);
+        prop_assert_eq!(age_on(birth, birthday - Duration::days(1)), years as u32 - 1);
+    }
+
+    #[test]
+    fn age_is_zero_until_the_first_birthday(birth in any_date(), days in 0i64..365) {
+        prop_assert_eq!(age_on(birth, birth - Duration::days(days)), 0);
+        prop_assert_eq!(age_on(birth, birth + Duration::days(days)), 0);
+    }
+
+    #[test]
+    fn age_is_at_most_one_year_off_the_year_difference(birth in any_date(), date in any_date()) {
+        let difference = (date.year() - birth.year()).max(0) as u32;
+        let age = age_on(birth, date);
+        prop_assert!(age == difference || age + 1 == difference);
+    }
+}
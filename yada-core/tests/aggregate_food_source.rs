@@ -0,0 +1,137 @@
+//! Integration tests for searching several food sources at once
+
+// yada-core/tests/aggregate_food_source.rs
+mod common;
+
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use common::keywords;
+use yada_core::factories::aggregate_food_source::{barcode, AggregateFoodSource};
+use yada_core::factories::async_food_source::{AsyncFoodSource, CancelToken, LookupError, LookupOptions};
+use yada_core::factories::food_source_factory::{FoodSource, FoodSourceFactory};
+use yada_core::models::food::Food;
+
+/// Source that returns the same foods for every search, after a delay
+struct FixedSource {
+    name: &'static str,
+    foods: Vec<Food>,
+    delay: Duration,
+}
+
+impl FixedSource {
+    fn new(name: &'static str, foods: &[(&str, &str, &[&str])]) -> Self {
+        let foods = foods.iter()
+            .map(|(id, food_name, words)| Food::new_basic(id.to_string(), food_name.to_string(), keywords(words), 150.0))
+            .collect();
+        FixedSource { name, foods, delay: Duration::ZERO }
+    }
+
+    fn async_source(self, timeout_ms: u64) -> AsyncFoodSource {
+        let options = LookupOptions { timeout: Duration::from_millis(timeout_ms), retries: 0, ..LookupOptions::default() };
+        AsyncFoodSource::new(Arc::new(self), options)
+    }
+}
+
+impl FoodSource for FixedSource {
+    fn get_food_by_id(&self, id: &str) -> Option<Food> {
+        self.foods.iter().find(|food| food.id == id).cloned()
+    }
+
+    fn search_foods(&self, _query: &str) -> Vec<Food> {
+        thread::sleep(self.delay);
+        self.foods.clone()
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn description(&self) -> &'static str {
+        "Fixed test source"
+    }
+}
+
+#[test]
+fn duplicates_are_merged_and_labelled_with_every_source() {
+    let usda = FixedSource::new("usda", &[
+        ("usda_1", "Oats, Rolled", &["oats"]),
+        ("usda_2", "Oat Milk", &["milk"]),
+    ]);
+    let off = FixedSource::new("off", &[
+        ("off_1", "rolled oats", &["cereal"]),
+        ("off_2", "Oatly Barista", &["4012345678901"]),
+        ("off_3", "Chocolate", &[]),
+    ]);
+    let store = FixedSource::new("store", &[("store_1", "Barista Edition Oat Drink", &["4012345678901"])]);
+    let aggregate = AggregateFoodSource::new(vec![usda.async_source(1_000), off.async_source(1_000), store.async_source(1_000)]);
+    assert_eq!(aggregate.source_names(), vec!["off", "store", "usda"]);
+
+    let results = aggregate.search("rolled oats");
+    let labels: Vec<String> = results.foods.iter().map(|result| result.to_string()).collect();
+    assert_eq!(labels, vec![
+        "rolled oats (150 cal) [off, usda]",
+        "Barista Edition Oat Drink (150 cal) [store, off]",
+        "Oat Milk (150 cal) [usda]",
+        "Chocolate (150 cal) [off]",
+    ]);
+    assert_eq!(results.foods[0].food.id, "off_1", "ties go to the earlier source");
+    assert_eq!(results.foods[1].score, results.foods[2].score, "ties go to the food more sources found");
+    assert_eq!(results.foods[3].score, 0, "results matching no query word come last");
+    assert_eq!(barcode(&results.foods[1].food), Some("4012345678901"));
+    assert!(results.failed.is_empty());
+}
+
+#[test]
+fn slow_sources_are_skipped_without_losing_the_others() {
+    let mut slow = FixedSource::new("slow", &[("slow_1", "Apple", &["fruit"])]);
+    slow.delay = Duration::from_secs(5);
+    let fast = FixedSource::new("fast", &[("fast_1", "Apple Pie", &["dessert"])]);
+    let aggregate = AggregateFoodSource::new(vec![slow.async_source(50), fast.async_source(1_000)]);
+
+    let results = aggregate.search("apple");
+    assert_eq!(results.foods.len(), 1);
+    assert_eq!(results.foods[0].sources, vec!["fast"]);
+    assert_eq!(results.failed, vec![("slow", LookupError::TimedOut { attempts: 1 })]);
+}
+
+#[test]
+fn cancelling_a_parent_token_stops_the_whole_search() {
+    let mut slow = FixedSource::new("slow", &[("slow_1", "Apple", &["fruit"])]);
+    slow.delay = Duration::from_secs(5);
+    let aggregate = AggregateFoodSource::new(vec![slow.async_source(5_000)]);
+    let interrupt = CancelToken::new();
+    let search = interrupt.child();
+    let canceller = {
+        let interrupt = interrupt.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            interrupt.cancel();
+        })
+    };
+
+    let started = Instant::now();
+    let results = aggregate.search_cancellable("apple", &search);
+    assert!(started.elapsed() < Duration::from_secs(2));
+    assert!(results.foods.is_empty());
+    assert_eq!(results.failed, vec![("slow", LookupError::Cancelled)]);
+    canceller.join().unwrap();
+
+    let child = CancelToken::new();
+    let grandchild = child.child();
+    grandchild.cancel();
+    assert!(!child.is_cancelled(), "cancelling a child leaves its parent alone");
+}
+
+#[test]
+fn the_factory_aggregates_every_registered_source() {
+    let mut factory = FoodSourceFactory::new();
+    factory.register_source(Box::new(FixedSource::new("usda", &[("usda_1", "Banana", &["fruit"])])));
+
+    let aggregate = factory.aggregate(LookupOptions::default());
+    assert_eq!(aggregate.source_names(), vec!["local", "usda"]);
+    let results = aggregate.search("banana");
+    assert_eq!(results.foods.len(), 1);
+    assert_eq!(results.foods[0].sources, vec!["usda"]);
+}
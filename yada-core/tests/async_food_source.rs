@@ -0,0 +1,101 @@
+//! Integration tests for food source lookups with timeouts
+
+// yada-core/tests/async_food_source.rs
+mod common;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use common::basic_food;
+use yada_core::factories::async_food_source::{AsyncFoodSource, LookupError, LookupOptions};
+use yada_core::factories::food_source_factory::FoodSource;
+use yada_core::models::food::{Food, Macros};
+
+/// Source that answers after a delay, slower on the first attempts
+struct SlowSource {
+    delays: Vec<Duration>,
+    attempts: Arc<AtomicUsize>,
+}
+
+impl SlowSource {
+    fn shared(delays_ms: &[u64]) -> (Arc<dyn FoodSource>, Arc<AtomicUsize>) {
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let delays = delays_ms.iter().map(|ms| Duration::from_millis(*ms)).collect();
+        (Arc::new(SlowSource { delays, attempts: attempts.clone() }), attempts)
+    }
+}
+
+impl FoodSource for SlowSource {
+    fn get_food_by_id(&self, id: &str) -> Option<Food> {
+        if id == "broken" {
+            panic!("source failure");
+        }
+        self.search_foods(id).into_iter().next()
+    }
+
+    fn search_foods(&self, query: &str) -> Vec<Food> {
+        let attempt = self.attempts.fetch_add(1, Ordering::SeqCst);
+        thread::sleep(self.delays[attempt.min(self.delays.len() - 1)]);
+        vec![basic_food(query, 100.0, Macros::new(1.0, 2.0, 3.0), 50.0)]
+    }
+
+    fn name(&self) -> &'static str {
+        "slow"
+    }
+
+    fn description(&self) -> &'static str {
+        "Slow test source"
+    }
+}
+
+fn options(timeout_ms: u64, retries: u32) -> LookupOptions {
+    LookupOptions {
+        timeout: Duration::from_millis(timeout_ms),
+        retries,
+        retry_delay: Duration::from_millis(10),
+    }
+}
+
+#[test]
+fn lookups_answer_within_the_timeout() {
+    let (source, _) = SlowSource::shared(&[10]);
+    let source = AsyncFoodSource::new(source, options(2_000, 0));
+    let found = source.search_foods("oats").wait().unwrap();
+    assert_eq!(found[0].id, "oats");
+    assert_eq!(source.get_food_by_id("rice").wait().unwrap().unwrap().id, "rice");
+}
+
+#[test]
+fn slow_attempts_are_retried_and_then_give_up() {
+    let (source, attempts) = SlowSource::shared(&[5_000, 10]);
+    let found = AsyncFoodSource::new(source, options(100, 1)).search_foods("oats").wait();
+    assert_eq!(found.unwrap().len(), 1, "the retry answers");
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+
+    let (source, attempts) = SlowSource::shared(&[5_000]);
+    let started = Instant::now();
+    let result = AsyncFoodSource::new(source, options(50, 2)).search_foods("oats").wait();
+    assert_eq!(result.unwrap_err(), LookupError::TimedOut { attempts: 3 });
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    assert!(started.elapsed() < Duration::from_secs(2), "waiting stops at the timeouts");
+}
+
+#[test]
+fn cancelled_and_failed_lookups_report_why() {
+    let (source, _) = SlowSource::shared(&[5_000]);
+    let source = AsyncFoodSource::new(source, options(5_000, 0));
+    let pending = source.search_foods("oats");
+    let token = pending.cancel_token();
+    let canceller = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        token.cancel();
+    });
+    let started = Instant::now();
+    assert_eq!(pending.wait().unwrap_err(), LookupError::Cancelled);
+    assert!(started.elapsed() < Duration::from_secs(2));
+    canceller.join().unwrap();
+
+    assert_eq!(source.get_food_by_id("broken").wait().unwrap_err(), LookupError::SourceFailed);
+}
@@ -0,0 +1,63 @@
+//! Integration tests for the audit log of executed and undone commands
+
+// yada-core/tests/audit_log.rs
+mod common;
+
+use std::fs;
+
+use chrono::NaiveDate;
+
+use common::{TempDir, basic_food};
+use yada_core::commands::food_commands::AddFoodCommand;
+use yada_core::models::command::CommandType;
+use yada_core::models::command_manager::{AuditEvent, CommandManager};
+use yada_core::models::food::Macros;
+use yada_core::repositories::audit_log::{self, AuditLog};
+use yada_core::repositories::food_repository::FoodRepository;
+
+#[test]
+fn executed_and_undone_commands_are_appended_and_failed_ones_are_not() {
+    let dir = TempDir::new("audit-log");
+    let path = dir.file("users/alice/audit.log");
+    let mut foods = FoodRepository::new(&dir.file("foods.txt")).unwrap();
+    let mut manager = CommandManager::new(10).with_audit_sink(AuditLog::new(&path));
+
+    let egg = AddFoodCommand::new(&mut foods, basic_food("egg", 70.0, Macros::default(), 50.0));
+    manager.execute_command(Box::new(egg)).unwrap();
+    let duplicate = AddFoodCommand::new(&mut foods, basic_food("egg", 80.0, Macros::default(), 50.0));
+    assert!(manager.execute_command(Box::new(duplicate)).is_err());
+    manager.undo_last_command().unwrap();
+    assert!(manager.take_audit_error().is_none());
+
+    let text = fs::read_to_string(&path).unwrap();
+    let events: Vec<Vec<&str>> = text.lines().map(|line| line.splitn(4, '|').collect()).collect();
+    assert_eq!(events.len(), 2, "{}", text);
+    assert_eq!(events[0][1..3], ["execute", "add_food"]);
+    assert_eq!(events[1][1..3], ["undo", "add_food"]);
+    assert_eq!(events[0][3], events[1][3]);
+    assert!(events[0][3].contains("egg"), "{}", text);
+    assert!(chrono::NaiveDateTime::parse_from_str(events[0][0], "%Y-%m-%dT%H:%M:%S").is_ok());
+
+    // A later manager on the same file adds to it
+    let bread = AddFoodCommand::new(&mut foods, basic_food("bread", 80.0, Macros::default(), 30.0));
+    let mut later = CommandManager::new(10).with_audit_sink(AuditLog::new(&path));
+    later.execute_command(Box::new(bread)).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 3);
+}
+
+#[test]
+fn lines_keep_descriptions_on_one_line_and_failures_are_kept_for_the_caller() {
+    let time = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap().and_hms_opt(8, 15, 2).unwrap();
+    let line = audit_log::format_line(time, AuditEvent::Undo, &CommandType::SetDayNote, "Note: tired\nslept badly | 5h");
+    assert_eq!(line, "2024-03-04T08:15:02|undo|set_day_note|Note: tired slept badly | 5h\n");
+
+    // The log's path is a directory, so it cannot be written, but the command still runs
+    let dir = TempDir::new("audit-log-error");
+    let mut foods = FoodRepository::new(&dir.file("foods.txt")).unwrap();
+    let mut manager = CommandManager::new(10).with_audit_sink(AuditLog::new(dir.file("")));
+    let egg = AddFoodCommand::new(&mut foods, basic_food("egg", 70.0, Macros::default(), 50.0));
+    manager.execute_command(Box::new(egg)).unwrap();
+    assert!(foods.get_food("egg").is_some());
+    assert!(manager.take_audit_error().is_some());
+    assert!(manager.take_audit_error().is_none());
+}
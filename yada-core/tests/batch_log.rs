@@ -0,0 +1,55 @@
+//! Integration tests for parsing and checking batch log lines
+
+// yada-core/tests/batch_log.rs
+mod common;
+
+use std::collections::HashMap;
+
+use common::{assert_close, basic_food};
+use yada_core::models::batch_log::{self, BatchItem};
+use yada_core::models::food::{Food, Macros};
+use yada_core::models::quantity::Quantity;
+use yada_core::models::serving::ServingUnit;
+
+fn food_db() -> HashMap<String, Food> {
+    [
+        basic_food("apple", 95.0, Macros::default(), 180.0),
+        basic_food("bread", 80.0, Macros::default(), 30.0).with_default_servings(Some(2.0)),
+        Food::new_basic("milk".into(), "Milk".into(), common::keywords(&["dairy"]), 120.0),
+    ]
+    .into_iter()
+    .map(|food| (food.id.clone(), food))
+    .collect()
+}
+
+#[test]
+fn batch_lines_parse_into_items_with_optional_amounts() {
+    let items = batch_log::parse_batch(" apple:1, bread ,chicken: 150 g,, ").unwrap();
+    assert_eq!(items, vec![
+        BatchItem { food_id: "apple".into(), quantity: Some(Quantity::servings(1.0)) },
+        BatchItem { food_id: "bread".into(), quantity: None },
+        BatchItem { food_id: "chicken".into(), quantity: Some(Quantity::in_unit(150.0, ServingUnit::Gram)) },
+    ]);
+
+    assert!(batch_log::parse_batch(" , ").is_err());
+    assert!(batch_log::parse_batch(":2").unwrap_err().contains("food ID"));
+    assert!(batch_log::parse_batch("green apple:1").is_err());
+    assert!(batch_log::parse_batch("apple:-1").unwrap_err().starts_with("apple: "));
+}
+
+#[test]
+fn resolving_uses_default_servings_and_reports_every_problem() {
+    let foods = food_db();
+    let items = batch_log::parse_batch("apple:90 g, bread").unwrap();
+    let resolved = batch_log::resolve(&items, &foods).unwrap();
+    assert_close(resolved[0].servings, 0.5);
+    assert_eq!(resolved[1].quantity, Quantity::servings(2.0));
+    assert_close(resolved[1].servings, 2.0);
+
+    // Milk has no serving weight, so grams cannot be converted
+    let items = batch_log::parse_batch("pizza:1, milk:100 g, apple").unwrap();
+    let problems = batch_log::resolve(&items, &foods).unwrap_err();
+    assert_eq!(problems.len(), 2);
+    assert_eq!(problems[0], "Food with ID 'pizza' doesn't exist.");
+    assert!(problems[1].starts_with("milk: "));
+}
@@ -0,0 +1,81 @@
+//! Integration tests for exporting and restoring data bundles
+
+// yada-core/tests/bundle.rs
+mod common;
+
+use std::fs;
+use std::io::Write;
+
+use common::TempDir;
+use yada_core::repositories::bundle::{self, BundleEntry};
+use yada_core::repositories::format::FORMAT_VERSION;
+use zip::write::SimpleFileOptions;
+
+/// Bundle entries for the foods and logs files in a directory
+fn entries(dir: &TempDir) -> Vec<BundleEntry> {
+    vec![
+        BundleEntry::new("foods.txt", "foods", &dir.file("foods.txt")),
+        BundleEntry::new("logs.txt", "logs", &dir.file("logs.txt")),
+        BundleEntry::new("exercise.txt", "exercise", &dir.file("exercise.txt")),
+    ]
+}
+
+/// Writes a zip archive with the given files
+fn write_zip(path: &str, files: &[(&str, &str)]) {
+    let mut zip = zip::ZipWriter::new(fs::File::create(path).unwrap());
+    for (name, contents) in files {
+        zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+        zip.write_all(contents.as_bytes()).unwrap();
+    }
+    zip.finish().unwrap();
+}
+
+#[test]
+fn exported_bundles_restore_the_files_elsewhere() {
+    let source = TempDir::new("bundle-source");
+    let foods = "#yada:foods:v1\nB|oats|Rolled Oats|grain|150\n";
+    let logs = "#yada:logs:v1\n2024-03-05|oats|2|2024-03-05T08:00:00\n";
+    fs::write(source.file("foods.txt"), foods).unwrap();
+    fs::write(source.file("logs.txt"), logs).unwrap();
+
+    let bundle_path = source.file("export.zip");
+    let exported = bundle::export_bundle(&bundle_path, &entries(&source)).unwrap();
+    assert_eq!(exported, ["foods.txt", "logs.txt"], "missing files are left out");
+
+    let read = bundle::read_bundle(&bundle_path).unwrap();
+    assert_eq!(read.names(), ["foods.txt", "logs.txt"]);
+    assert!(read.created.is_some());
+
+    // The target already has logs, which are replaced and backed up
+    let target = TempDir::new("bundle-target");
+    fs::write(target.file("logs.txt"), "#yada:logs:v1\n").unwrap();
+    let restored = bundle::restore_bundle(&read, &entries(&target)).unwrap();
+    assert_eq!(restored, ["foods.txt", "logs.txt"]);
+    assert_eq!(fs::read_to_string(target.file("foods.txt")).unwrap(), foods);
+    assert_eq!(fs::read_to_string(target.file("logs.txt")).unwrap(), logs);
+    assert_eq!(fs::read_to_string(target.file("logs.txt.bak.1")).unwrap(), "#yada:logs:v1\n");
+    assert!(!target.path().join("exercise.txt").exists());
+}
+
+#[test]
+fn bundles_are_checked_before_anything_is_restored() {
+    let dir = TempDir::new("bundle-checks");
+    let path = dir.file("bundle.zip");
+
+    fs::write(&path, "not a zip archive").unwrap();
+    assert!(bundle::read_bundle(&path).unwrap_err().contains("not a YADA data bundle"));
+
+    write_zip(&path, &[("foods.txt", "#yada:foods:v1\n")]);
+    assert!(bundle::read_bundle(&path).unwrap_err().contains("no manifest.txt"));
+
+    let manifest = "#yada:bundle:v1\nFILE|logs.txt|logs\n";
+    write_zip(&path, &[("manifest.txt", manifest), ("logs.txt", "#yada:foods:v1\n")]);
+    assert!(bundle::read_bundle(&path).unwrap_err().contains("holds foods data, expected logs"));
+
+    let newer = format!("#yada:logs:v{}\n", FORMAT_VERSION + 1);
+    write_zip(&path, &[("manifest.txt", manifest), ("logs.txt", &newer)]);
+    assert!(bundle::read_bundle(&path).unwrap_err().contains("newer YADA"));
+
+    write_zip(&path, &[("manifest.txt", manifest)]);
+    assert!(bundle::read_bundle(&path).unwrap_err().contains("logs.txt in"));
+}
@@ -0,0 +1,232 @@
+//! Integration tests for the calorie calculation strategies
+
+// yada-core/tests/calorie_calculator.rs
+mod common;
+
+use std::collections::BTreeMap;
+
+use chrono::{Duration, NaiveDate};
+
+use common::assert_close;
+use yada_core::models::profile::{
+    ActivityLevel, DEFAULT_AGE_YEARS, DailyProfile, EstimatedField, Gender, UserProfile, default_height_cm,
+};
+use yada_core::models::diet_preset::DietPreset;
+use yada_core::models::energy_balance::KCAL_PER_KG;
+use yada_core::strategies::calorie_calculator::{ADAPTIVE_METHOD, CalorieCalculatorFactory, SCHOFIELD_METHOD};
+use yada_core::strategies::health_metrics::{BmiCategory, HealthMetrics};
+
+/// 30-year-old, 180 cm, 80 kg, sedentary on the measured date
+fn profile(gender: Gender) -> (UserProfile, NaiveDate) {
+    let date = NaiveDate::from_ymd_opt(2024, 6, 1).unwrap();
+    let mut profile = UserProfile::new(gender, 180.0, NaiveDate::from_ymd_opt(1994, 1, 1).unwrap());
+    profile.add_or_update_daily_profile(DailyProfile {
+        date,
+        weight: 80.0,
+        activity_level: ActivityLevel::Sedentary,
+        carried_from: None,
+    });
+    (profile, date)
+}
+
+#[test]
+fn factory_provides_both_methods() {
+    let factory = CalorieCalculatorFactory::new();
+    assert!(factory.get_calculator("harris_benedict").is_some());
+    assert!(factory.get_calculator("mifflin_st_jeor").is_some());
+    assert!(factory.get_calculator(ADAPTIVE_METHOD).is_some());
+    assert!(factory.get_calculator("unknown").is_none());
+    assert_eq!(factory.get_all_calculators(), [ADAPTIVE_METHOD, "harris_benedict", "mifflin_st_jeor", SCHOFIELD_METHOD]);
+}
+
+#[test]
+fn mifflin_st_jeor_matches_reference_values() {
+    let factory = CalorieCalculatorFactory::new();
+    let calculator = factory.get_calculator("mifflin_st_jeor").unwrap();
+
+    let (male, date) = profile(Gender::Male);
+    assert_close(calculator.calculate_tdee(&male, date), 1780.0 * 1.2);
+
+    let (female, date) = profile(Gender::Female);
+    assert_close(calculator.calculate_tdee(&female, date), 1614.0 * 1.2);
+}
+
+#[test]
+fn harris_benedict_matches_reference_values() {
+    let factory = CalorieCalculatorFactory::new();
+    let calculator = factory.get_calculator("harris_benedict").unwrap();
+
+    let (male, date) = profile(Gender::Male);
+    let bmr = 88.362 + 13.397 * 80.0 + 4.799 * 180.0 - 5.677 * 30.0;
+    assert_close(calculator.calculate_tdee(&male, date), bmr * 1.2);
+}
+
+#[test]
+fn schofield_uses_the_age_band_and_suits_teens() {
+    let factory = CalorieCalculatorFactory::new();
+    let schofield = factory.get_calculator(SCHOFIELD_METHOD).unwrap();
+
+    // 30 years old: the adult band from 30 to 60
+    let (male, date) = profile(Gender::Male);
+    assert_close(schofield.calculate_bmr(&male, date), 11.472 * 80.0 + 873.1);
+    assert!(schofield.explain(&male, date).is_none());
+
+    // 14 years old, 50 kg
+    let mut teen = UserProfile::new(Gender::Female, 160.0, NaiveDate::from_ymd_opt(2010, 1, 1).unwrap());
+    teen.add_or_update_daily_profile(DailyProfile {
+        date,
+        weight: 50.0,
+        activity_level: ActivityLevel::Sedentary,
+        carried_from: None,
+    });
+    assert_close(schofield.calculate_bmr(&teen, date), 13.384 * 50.0 + 692.6);
+
+    // The adult formulas point teens to Schofield
+    let mifflin = factory.get_calculator("mifflin_st_jeor").unwrap();
+    assert!(mifflin.explain(&teen, date).unwrap().contains(SCHOFIELD_METHOD));
+    assert!(mifflin.explain(&male, date).is_none());
+}
+
+#[test]
+fn missing_daily_profile_yields_zero() {
+    let factory = CalorieCalculatorFactory::new();
+    let calculator = factory.get_calculator("mifflin_st_jeor").unwrap();
+    let (profile, date) = profile(Gender::Male);
+
+    assert_close(calculator.calculate_tdee(&profile, date.pred_opt().unwrap()), 0.0);
+}
+
+#[test]
+fn days_without_a_weigh_in_use_the_latest_earlier_one() {
+    let factory = CalorieCalculatorFactory::new();
+    let (mut profile, date) = profile(Gender::Male);
+    let later = date + Duration::days(3);
+
+    for name in ["mifflin_st_jeor", "harris_benedict"] {
+        let calculator = factory.get_calculator(name).unwrap();
+        let target = calculator.calculate_tdee(&profile, date);
+        assert!(target > 0.0);
+        assert_close(calculator.calculate_tdee(&profile, later), target);
+    }
+
+    // A later weigh-in takes over from its own day on
+    profile.add_or_update_daily_profile(DailyProfile {
+        date: later,
+        weight: 70.0,
+        activity_level: ActivityLevel::Sedentary,
+        carried_from: None,
+    });
+    let calculator = factory.get_calculator("mifflin_st_jeor").unwrap();
+    assert_close(
+        calculator.calculate_tdee(&profile, later + Duration::days(1)),
+        calculator.calculate_tdee(&profile, later),
+    );
+    assert!(calculator.calculate_tdee(&profile, later) < calculator.calculate_tdee(&profile, date));
+}
+
+#[test]
+fn unknown_height_and_age_fall_back_to_population_defaults() {
+    let factory = CalorieCalculatorFactory::new();
+    let calculator = factory.get_calculator("mifflin_st_jeor").unwrap();
+    let (mut male, date) = profile(Gender::Male);
+    assert!(male.body_metrics(date).estimate_notice().is_none());
+
+    male.height = None;
+    male.birth_date = None;
+    let metrics = male.body_metrics(date);
+    assert_eq!(metrics.estimated, vec![EstimatedField::Height, EstimatedField::Age]);
+    assert!(metrics.estimate_notice().unwrap().starts_with("Estimate only"));
+
+    let bmr = 10.0 * 80.0 + 6.25 * default_height_cm(&Gender::Male) - 5.0 * DEFAULT_AGE_YEARS as f64 + 5.0;
+    assert_close(calculator.calculate_tdee(&male, date), bmr * 1.2);
+
+    // A birth date after the calculated day is not a usable age
+    male.birth_date = Some(date.succ_opt().unwrap());
+    assert_eq!(male.age(date), None);
+    assert_eq!(male.body_metrics(date).age_years, DEFAULT_AGE_YEARS);
+}
+
+#[test]
+fn the_target_calculation_lists_its_components() {
+    let factory = CalorieCalculatorFactory::new();
+    let calculator = factory.get_calculator("mifflin_st_jeor").unwrap();
+    let (mut profile, date) = profile(Gender::Male);
+    profile.diet_preset = Some(DietPreset::Bulking);
+
+    let calculation = calculator.calculate_target_calories(&profile, date);
+    assert_close(calculation.bmr, 1780.0);
+    assert_close(calculation.activity_multiplier, 1.2);
+    assert_close(calculation.tdee, 1780.0 * 1.2);
+    assert_eq!(calculation.adjustments.len(), 1);
+    assert_eq!(calculation.adjustments[0].label, "Bulking Preset Adjustment");
+    assert_close(calculation.adjustments[0].change, 300.0);
+    assert_close(calculation.target, 1780.0 * 1.2 + 300.0);
+    assert_eq!(calculation.explanation, "Mifflin-St Jeor Equation BMR times the Sedentary activity multiplier");
+    assert_close(calculator.daily_target_calories(&profile, date), calculation.target);
+
+    // Before the first weigh-in every component is 0
+    let calculation = calculator.calculate_target_calories(&profile, date.pred_opt().unwrap());
+    assert_close(calculation.bmr, 0.0);
+    assert_close(calculation.target, 0.0);
+    assert!(calculation.adjustments.is_empty());
+}
+
+#[test]
+fn health_metrics_break_the_target_down() {
+    let factory = CalorieCalculatorFactory::new();
+    let calculator = factory.get_calculator("mifflin_st_jeor").unwrap();
+    let (mut profile, date) = profile(Gender::Male);
+
+    let metrics = HealthMetrics::calculate(&profile, calculator, date).unwrap();
+    assert_close(metrics.bmi.unwrap(), 80.0 / (1.8 * 1.8));
+    assert_eq!(metrics.bmi_category(), Some(BmiCategory::Normal));
+    assert_close(metrics.bmr, 1780.0);
+    assert_close(metrics.activity_multiplier, 1.2);
+    assert_close(metrics.tdee, metrics.bmr * metrics.activity_multiplier);
+    assert_close(metrics.target, metrics.tdee);
+
+    // No BMI without a height, and no metrics before the first weigh-in
+    profile.height = None;
+    assert!(HealthMetrics::calculate(&profile, calculator, date).unwrap().bmi.is_none());
+    assert!(HealthMetrics::calculate(&profile, calculator, date.pred_opt().unwrap()).is_none());
+
+    assert_eq!(BmiCategory::from_bmi(18.4), BmiCategory::Underweight);
+    assert_eq!(BmiCategory::from_bmi(25.0), BmiCategory::Overweight);
+    assert_eq!(BmiCategory::from_bmi(30.0), BmiCategory::Obese);
+}
+
+#[test]
+fn the_adaptive_method_measures_the_tdee_once_four_weeks_have_data() {
+    let (mut profile, start) = profile(Gender::Male);
+    // 2500 kcal a day for four weeks while gaining 0.25 kg a week
+    for week in 1..=4 {
+        profile.add_or_update_daily_profile(DailyProfile {
+            date: start + Duration::weeks(week),
+            weight: 80.0 + 0.25 * week as f64,
+            activity_level: ActivityLevel::Sedentary,
+            carried_from: None,
+        });
+    }
+    let intake: BTreeMap<NaiveDate, f64> = (0..28).map(|offset| (start + Duration::days(offset), 2500.0)).collect();
+    let date = start + Duration::days(28);
+
+    let factory = CalorieCalculatorFactory::with_intake(intake);
+    let adaptive = factory.get_calculator(ADAPTIVE_METHOD).unwrap();
+    let mifflin = factory.get_calculator("mifflin_st_jeor").unwrap();
+    assert_close(adaptive.calculate_tdee(&profile, date), 2500.0 - 0.25 / 7.0 * KCAL_PER_KG);
+    assert_close(adaptive.calculate_bmr(&profile, date), mifflin.calculate_bmr(&profile, date));
+    assert!(adaptive.explain(&profile, date).unwrap().contains("28 logged day(s)"));
+    assert!(mifflin.explain(&profile, date).is_none());
+
+    // Days whose window has too little data use Mifflin-St Jeor
+    let early = start + Duration::days(5);
+    assert_close(adaptive.calculate_tdee(&profile, early), mifflin.calculate_tdee(&profile, early));
+    // Without any logged intake the factory's default adaptive method falls back too
+    let plain = CalorieCalculatorFactory::new();
+    assert_close(
+        plain.get_calculator(ADAPTIVE_METHOD).unwrap().calculate_tdee(&profile, date),
+        mifflin.calculate_tdee(&profile, date),
+    );
+    // No target before the first weigh-in
+    assert_eq!(adaptive.calculate_tdee(&profile, start.pred_opt().unwrap()), 0.0);
+}
@@ -0,0 +1,69 @@
+//! Integration tests for top foods and calorie shares by category
+
+// yada-core/tests/calorie_sources.rs
+mod common;
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::NaiveDate;
+
+use common::{assert_close, basic_food};
+use yada_core::models::calorie_sources::CalorieSources;
+use yada_core::models::category::UNCATEGORIZED;
+use yada_core::models::food::{Food, Macros};
+use yada_core::models::log::DailyLog;
+
+fn food(id: &str, calories: f64, category: Option<&str>) -> (String, Food) {
+    let food = basic_food(id, calories, Macros::default(), 100.0)
+        .with_metadata(category.map(str::to_string), HashSet::new());
+    (id.to_string(), food)
+}
+
+#[test]
+fn foods_are_ranked_by_frequency_and_by_calories_with_category_shares() {
+    let foods: HashMap<String, Food> = [
+        food("milk", 100.0, Some("dairy")),
+        food("cheese", 400.0, Some("Dairy")),
+        food("oats", 150.0, Some("Grain")),
+        food("bar", 50.0, None),
+    ].into_iter().collect();
+
+    let mut monday = DailyLog::new(NaiveDate::from_ymd_opt(2024, 3, 4).unwrap());
+    monday.add_entry("milk".into(), 1.0);
+    monday.add_entry("oats".into(), 2.0);
+    monday.add_entry("deleted".into(), 3.0);
+    let mut tuesday = DailyLog::new(NaiveDate::from_ymd_opt(2024, 3, 5).unwrap());
+    tuesday.add_entry("milk".into(), 1.0);
+    tuesday.add_entry("milk".into(), 1.0);
+    tuesday.add_entry("cheese".into(), 1.0);
+    tuesday.add_entry("bar".into(), 1.0);
+
+    let sources = CalorieSources::summarize([&monday, &tuesday], &foods);
+    // Entries of unknown foods are left out, as in the daily totals
+    assert_eq!(sources.total_entries, 6);
+    assert_close(sources.total_calories, 1050.0);
+
+    let frequent: Vec<&str> = sources.top_by_frequency(2).iter().map(|food| food.food_id.as_str()).collect();
+    assert_eq!(frequent, ["milk", "cheese"]);
+    // Milk and oats both contributed 300 kcal; milk was logged more often
+    let biggest: Vec<&str> = sources.top_by_calories(10).iter().map(|food| food.food_id.as_str()).collect();
+    assert_eq!(biggest, ["cheese", "milk", "oats", "bar"]);
+    assert_close(sources.percent_of_calories(&sources.foods[0]), 400.0 / 1050.0 * 100.0);
+
+    // Category spellings are normalized, so both dairy foods share one category
+    let categories: Vec<(&str, f64)> = sources.categories.iter()
+        .map(|share| (share.category.as_str(), share.calories))
+        .collect();
+    assert_eq!(categories, [("Dairy", 700.0), ("Grain", 300.0), (UNCATEGORIZED, 50.0)]);
+    assert_close(sources.categories.iter().map(|share| share.percent).sum(), 100.0);
+}
+
+#[test]
+fn an_empty_period_has_no_sources() {
+    let foods: HashMap<String, Food> = [food("milk", 100.0, None)].into_iter().collect();
+    let sources = CalorieSources::summarize(Vec::<&DailyLog>::new(), &foods);
+
+    assert!(sources.is_empty());
+    assert!(sources.categories.is_empty());
+    assert!(sources.top_by_frequency(5).is_empty());
+}
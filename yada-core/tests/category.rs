@@ -0,0 +1,71 @@
+//! Integration tests for food categories
+
+// yada-core/tests/category.rs
+mod common;
+
+use common::{TempDir, keywords};
+use yada_core::ingest::{self, ConflictPolicy};
+use yada_core::ingest::records::FoodRecord;
+use yada_core::models::category;
+use yada_core::models::food::Food;
+use yada_core::repositories::food_repository::FoodRepository;
+
+#[test]
+fn standard_categories_are_recognized_and_inferred() {
+    assert_eq!(category::normalize("  dairy "), Some("Dairy".to_string()));
+    assert_eq!(category::normalize("NUTS & SEEDS"), Some("Nuts & Seeds".to_string()));
+    assert_eq!(category::normalize("Baby food"), Some("Baby food".to_string()));
+    assert_eq!(category::normalize("  "), None);
+
+    let infer = |name: &str, words: &[&str]| category::infer(name, &keywords(words));
+    assert_eq!(infer("Whole Milk (1 cup)", &["drink"]), Some("Dairy"));
+    assert_eq!(infer("Peanut Butter", &["spread"]), Some("Nuts & Seeds"));
+    assert_eq!(infer("Peanut Butter Sandwich", &["lunch"]), Some("Prepared Meal"));
+    assert_eq!(infer("Blueberries", &[]), Some("Fruit"));
+    assert_eq!(infer("Soda", &["sugar"]), Some("Beverage"));
+    assert_eq!(infer("Mystery Item", &["misc"]), None);
+
+    // Typed categories get the standard spelling
+    let food = Food::new_basic("milk".into(), "Milk".into(), keywords(&["milk"]), 90.0)
+        .with_metadata(Some("dairy".into()), keywords(&[]));
+    assert_eq!(food.category.as_deref(), Some("Dairy"));
+    assert!(category::matches(&food, "DAIRY"));
+}
+
+#[test]
+fn imported_foods_get_a_category_and_can_be_browsed() {
+    let dir = TempDir::new("category-import");
+    let mut repo = FoodRepository::new(&dir.file("foods.txt")).unwrap();
+    let record = |id: &str, name: &str, category: Option<&str>| FoodRecord {
+        id: id.to_string(),
+        name: name.to_string(),
+        calories: 100.0,
+        category: category.map(str::to_string),
+        ..FoodRecord::default()
+    };
+    let report = ingest::import_foods(&mut repo, &[
+        record("cheddar", "Cheddar Cheese", None),
+        record("kefir", "Kefir", Some("dairy")),
+        record("lentils", "Red Lentils", None),
+        record("widget", "Widget", None),
+        record("crisps", "Crisps", Some("Snack")),
+    ], ConflictPolicy::Reject);
+    assert_eq!(report.added, 5);
+
+    // Explicit categories win over guesses; unknown foods stay uncategorized
+    assert_eq!(repo.get_food("cheddar").unwrap().category.as_deref(), Some("Dairy"));
+    assert_eq!(repo.get_food("kefir").unwrap().category.as_deref(), Some("Dairy"));
+    assert_eq!(repo.get_food("lentils").unwrap().category.as_deref(), Some("Legume"));
+    assert_eq!(repo.get_food("widget").unwrap().category, None);
+
+    assert_eq!(repo.categories(), vec![
+        ("Dairy".to_string(), 2),
+        ("Legume".to_string(), 1),
+        ("Snack".to_string(), 1),
+    ]);
+
+    // The category survives a save and reload
+    repo.save().unwrap();
+    repo.load().unwrap();
+    assert_eq!(repo.get_food("lentils").unwrap().category.as_deref(), Some("Legume"));
+}
@@ -0,0 +1,258 @@
+//! Integration tests for undoable commands
+
+// yada-core/tests/commands.rs
+mod common;
+
+use chrono::{NaiveDate, NaiveTime};
+
+use std::collections::HashSet;
+
+use common::{TempDir, assert_close, basic_food, keywords};
+use yada_core::commands::food_commands::{AddFoodCommand, EditKeywordsCommand, UpdateFoodCommand};
+use yada_core::commands::log_commands::{AddLogEntryCommand, RemoveLogEntryCommand, UpdateLogEntryCommand};
+use yada_core::models::command::{Command, CommandType};
+use yada_core::models::command_manager::{CommandManager, UndoPolicies, UndoPolicy};
+use yada_core::models::food::{Food, Macros};
+use yada_core::models::log;
+use yada_core::models::meal::MealSlot;
+use yada_core::models::quantity::Quantity;
+use yada_core::repositories::food_repository::FoodRepository;
+use yada_core::repositories::log_repository::LogRepository;
+
+#[test]
+fn add_food_can_be_undone() {
+    let dir = TempDir::new("cmd-food");
+    let mut foods = FoodRepository::new(&dir.file("foods.txt")).unwrap();
+    let mut manager = CommandManager::new(10);
+
+    let command = AddFoodCommand::new(&mut foods, basic_food("egg", 70.0, Macros::default(), 50.0));
+    manager.execute_command(Box::new(command)).unwrap();
+    assert!(foods.get_food("egg").is_some());
+    assert_eq!(manager.get_undo_stack_size(), 1);
+
+    manager.undo_last_command().unwrap();
+    assert!(foods.get_food("egg").is_none());
+    assert!(!manager.has_commands_to_undo());
+}
+
+#[test]
+fn failed_commands_are_not_added_to_the_undo_stack() {
+    let dir = TempDir::new("cmd-failed");
+    let mut foods = FoodRepository::new(&dir.file("foods.txt")).unwrap();
+    foods.add_food(basic_food("egg", 70.0, Macros::default(), 50.0)).unwrap();
+    let mut manager = CommandManager::new(10);
+
+    let duplicate = AddFoodCommand::new(&mut foods, basic_food("egg", 80.0, Macros::default(), 50.0));
+    assert!(manager.execute_command(Box::new(duplicate)).is_err());
+    assert_eq!(manager.get_undo_stack_size(), 0);
+    assert!(manager.undo_last_command().is_err());
+}
+
+#[test]
+fn updating_a_food_recalculates_recipes_built_from_it_and_undo_restores_them() {
+    let dir = TempDir::new("cmd-update-dependents");
+    let mut foods = FoodRepository::new(&dir.file("foods.txt")).unwrap();
+    foods.add_food(basic_food("bread", 80.0, Macros::new(3.0, 15.0, 1.0), 30.0)).unwrap();
+    foods.add_food(basic_food("egg", 70.0, Macros::new(6.0, 0.0, 5.0), 50.0)).unwrap();
+    // A recipe made from another recipe must be calculated after it
+    foods.add_food(Food::new_composite("lunch".into(), "Lunch".into(), keywords(&[]),
+                                       vec![("toast".into(), 1.0), ("egg".into(), 1.0)])).unwrap();
+    foods.add_food(Food::new_composite("toast".into(), "Egg Toast".into(), keywords(&[]),
+                                       vec![("bread".into(), 2.0), ("egg".into(), 1.0)])).unwrap();
+    foods.recalculate_composites();
+    assert_close(foods.get_food("lunch").unwrap().calories_per_serving, 300.0);
+    assert_eq!(foods.dependency_graph().dependents("bread"), ["toast", "lunch"]);
+    assert_eq!(foods.dependency_graph().dependents("egg"), ["toast", "lunch"]);
+    assert!(foods.dependency_graph().dependents("lunch").is_empty());
+
+    let mut logs = LogRepository::new(&dir.file("logs.txt")).unwrap();
+    let day = NaiveDate::from_ymd_opt(2024, 3, 5).unwrap();
+    logs.get_log_mut(day).add_entry("lunch".to_string(), 1.0);
+    logs.get_log_mut(day.succ_opt().unwrap()).add_entry("egg".to_string(), 1.0);
+    let mut manager = CommandManager::new(10);
+
+    let lighter = basic_food("bread", 60.0, Macros::new(3.0, 11.0, 1.0), 30.0);
+    let mut command = UpdateFoodCommand::new(&mut foods, lighter);
+    command.execute().unwrap();
+    let recalculated: Vec<&str> = command.recalculated().iter().map(|food| food.id.as_str()).collect();
+    assert_eq!(recalculated, ["toast", "lunch"]);
+    assert_eq!(command.description(), "Update food: bread (recalculated 2 recipe(s))");
+    command.undo().unwrap();
+    manager.execute_command(Box::new(command)).unwrap();
+
+    assert_close(foods.get_food("toast").unwrap().calories_per_serving, 190.0);
+    assert_close(foods.get_food("lunch").unwrap().calories_per_serving, 260.0);
+    assert_close(foods.get_food("lunch").unwrap().macros.carbs, 22.0);
+    let changed: HashSet<String> = ["bread", "toast", "lunch"].map(String::from).into();
+    assert_eq!(logs.dates_with_foods(&changed), [day]);
+    assert_close(logs.get_log(day).unwrap().total_calories(foods.get_foods()), 260.0);
+
+    manager.undo_last_command().unwrap();
+    assert_close(foods.get_food("toast").unwrap().calories_per_serving, 230.0);
+    assert_close(foods.get_food("lunch").unwrap().calories_per_serving, 300.0);
+    assert_close(foods.get_food("lunch").unwrap().macros.carbs, 30.0);
+}
+
+#[test]
+fn keywords_are_edited_on_several_foods_and_undone_together() {
+    let dir = TempDir::new("cmd-keywords");
+    let mut foods = FoodRepository::new(&dir.file("foods.txt")).unwrap();
+    for id in ["tofu", "oats", "milk"] {
+        foods.add_food(basic_food(id, 100.0, Macros::default(), 100.0)).unwrap();
+    }
+    assert_eq!(foods.set_keyword("milk", " Vegan ", true), Ok(false));
+    let mut manager = CommandManager::new(10);
+
+    let ids = vec!["tofu".to_string(), "oats".to_string(), "milk".to_string()];
+    let command = EditKeywordsCommand::new(&mut foods, ids.clone(), vec!["Vegan".into(), "plant".into()], true);
+    manager.execute_command(Box::new(command)).unwrap();
+    assert!(ids.iter().all(|id| foods.get_food(id).unwrap().has_keyword("vegan")));
+    let vegan: Vec<String> = foods.search_foods(&common::keywords(&["plant"]), false, &[]).iter().map(|food| food.id.clone()).collect();
+    assert_eq!(vegan.len(), 3, "the search index follows keyword changes");
+
+    // Only the keywords the command added are removed again
+    manager.undo_last_command().unwrap();
+    assert!(!foods.get_food("tofu").unwrap().has_keyword("vegan"));
+    assert!(foods.get_food("milk").unwrap().has_keyword("vegan"));
+    assert!(foods.search_foods(&common::keywords(&["plant"]), false, &[]).is_empty());
+
+    let remove = EditKeywordsCommand::new(&mut foods, vec!["milk".into()], vec!["vegan".into()], false);
+    manager.execute_command(Box::new(remove)).unwrap();
+    assert!(!foods.get_food("milk").unwrap().has_keyword("vegan"));
+    assert_eq!(manager.get_command_history().last().unwrap(), "Remove keyword(s) vegan from 1 food(s): milk");
+
+    let unknown = EditKeywordsCommand::new(&mut foods, vec!["tofu".into(), "nope".into()], vec!["soy".into()], true);
+    assert!(manager.execute_command(Box::new(unknown)).is_err());
+    assert!(!foods.get_food("tofu").unwrap().has_keyword("soy"));
+    assert!(foods.set_keyword("tofu", " ", true).is_err());
+}
+
+#[test]
+fn log_commands_undo_in_reverse_order() {
+    let dir = TempDir::new("cmd-log");
+    let mut logs = LogRepository::new(&dir.file("logs.txt")).unwrap();
+    let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+    let mut manager = CommandManager::new(10);
+
+    let add_egg = AddLogEntryCommand::new(&mut logs, date, "egg".into(), 2.0);
+    manager.execute_command(Box::new(add_egg)).unwrap();
+    let add_toast = AddLogEntryCommand::new(&mut logs, date, "toast".into(), 1.0);
+    manager.execute_command(Box::new(add_toast)).unwrap();
+    let egg_id = logs.get_log(date).unwrap().entries[0].entry_id;
+    let remove_egg = RemoveLogEntryCommand::new(&mut logs, date, egg_id);
+    manager.execute_command(Box::new(remove_egg)).unwrap();
+    assert_eq!(logs.get_log(date).unwrap().entries.len(), 1);
+
+    manager.undo_last_command().unwrap();
+    let entries: Vec<_> = logs.get_log(date).unwrap().entries.iter().map(|e| e.food_id.clone()).collect();
+    assert_eq!(entries, ["egg", "toast"]);
+
+    manager.undo_last_command().unwrap();
+    manager.undo_last_command().unwrap();
+    assert!(logs.get_log(date).unwrap().entries.is_empty());
+}
+
+#[test]
+fn undoing_a_log_entry_removes_exactly_that_entry() {
+    let dir = TempDir::new("cmd-log-exact");
+    let mut logs = LogRepository::new(&dir.file("logs.txt")).unwrap();
+    let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+    let at = |hour| log::local_timestamp(date, NaiveTime::from_hms_opt(hour, 0, 0).unwrap()).unwrap();
+    let mut manager = CommandManager::new(10);
+
+    let lunch = AddLogEntryCommand::new(&mut logs, date, "egg".into(), 2.0).with_timestamp(at(12));
+    manager.execute_command(Box::new(lunch)).unwrap();
+    let breakfast = AddLogEntryCommand::new(&mut logs, date, "egg".into(), 1.0).with_timestamp(at(8));
+    manager.execute_command(Box::new(breakfast)).unwrap();
+
+    // The breakfast egg sorts first, but it is the one undone
+    manager.undo_last_command().unwrap();
+    let entries = &logs.get_log(date).unwrap().entries;
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].servings, 2.0);
+    assert_eq!(entries[0].timestamp, at(12));
+}
+
+#[test]
+fn update_log_entry_keeps_time_and_meal_and_can_be_undone() {
+    let dir = TempDir::new("cmd-update-log");
+    let mut logs = LogRepository::new(&dir.file("logs.txt")).unwrap();
+    let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+    let mut manager = CommandManager::new(10);
+
+    let add = AddLogEntryCommand::new(&mut logs, date, "egg".into(), 2.0).with_meal(MealSlot::Breakfast);
+    manager.execute_command(Box::new(add)).unwrap();
+    let before = logs.get_log(date).unwrap().entries[0].clone();
+
+    let grams = Quantity::parse("60 g").unwrap();
+    let update = UpdateLogEntryCommand::new(&mut logs, date, before.entry_id, "toast".into(), grams, 1.5);
+    manager.execute_command(Box::new(update)).unwrap();
+    let entry = &logs.get_log(date).unwrap().entries[0];
+    assert_eq!(entry.food_id, "toast");
+    assert_eq!(entry.quantity, grams);
+    assert_eq!(entry.servings, 1.5);
+    assert_eq!(entry.timestamp, before.timestamp);
+    assert_eq!(entry.meal, Some(MealSlot::Breakfast));
+
+    manager.undo_last_command().unwrap();
+    let entry = &logs.get_log(date).unwrap().entries[0];
+    assert_eq!(entry.food_id, "egg");
+    assert_eq!(entry.quantity, before.quantity);
+    assert_eq!(entry.servings, 2.0);
+
+    let missing = UpdateLogEntryCommand::new(&mut logs, date, 0, "toast".into(), Quantity::servings(1.0), 1.0);
+    assert!(manager.execute_command(Box::new(missing)).is_err());
+}
+
+#[test]
+fn undo_stack_is_bounded() {
+    let dir = TempDir::new("cmd-bounded");
+    let mut logs = LogRepository::new(&dir.file("logs.txt")).unwrap();
+    let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+    let mut manager = CommandManager::new(2);
+
+    for _ in 0..3 {
+        let command = AddLogEntryCommand::new(&mut logs, date, "egg".into(), 1.0);
+        manager.execute_command(Box::new(command)).unwrap();
+    }
+    assert_eq!(manager.get_undo_stack_size(), 2);
+}
+
+#[test]
+fn undo_policies_apply_per_command_type() {
+    let dir = TempDir::new("cmd-policy");
+    let mut logs = LogRepository::new(&dir.file("logs.txt")).unwrap();
+    let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+
+    let defaults = UndoPolicies::default();
+    assert_eq!(defaults.policy_for(&CommandType::Import), UndoPolicy::Skip);
+    assert_eq!(defaults.policy_for(&CommandType::UpdateProfile), UndoPolicy::Confirm);
+    assert_eq!(defaults.policy_for(&CommandType::Other("Anything".into())), UndoPolicy::Allow);
+
+    let policies = UndoPolicies::parse("default=confirm, add_log=skip, UPDATE_PROFILE=allow").unwrap();
+    assert_eq!(policies.policy_for(&CommandType::AddExercise), UndoPolicy::Confirm);
+    assert_eq!(policies.policy_for(&CommandType::UpdateProfile), UndoPolicy::Allow);
+    assert_eq!(policies.policy_for(&CommandType::Import), UndoPolicy::Skip);
+    assert!(UndoPolicies::parse("add_log").is_err());
+    assert!(UndoPolicies::parse("add_log=maybe").is_err());
+    assert!(UndoPolicies::parse("water=skip").is_err());
+    for kind in CommandType::ALL {
+        assert_eq!(CommandType::from_key(kind.key()), Some(kind));
+    }
+
+    // A skipped command is not kept and ends the undo history before it
+    let mut manager = CommandManager::new(10).with_policies(policies);
+    let remove = RemoveLogEntryCommand::new(&mut logs, date, 0);
+    assert!(manager.execute_command(Box::new(remove)).is_err());
+    logs.get_log_mut(date).add_entry("egg".into(), 1.0);
+    let egg_id = logs.get_log(date).unwrap().entries[0].entry_id;
+    let remove = RemoveLogEntryCommand::new(&mut logs, date, egg_id);
+    manager.execute_command(Box::new(remove)).unwrap();
+    assert_eq!(manager.next_undo_policy(), Some(UndoPolicy::Confirm));
+
+    let add = AddLogEntryCommand::new(&mut logs, date, "toast".into(), 1.0);
+    manager.execute_command(Box::new(add)).unwrap();
+    assert_eq!(logs.get_log(date).unwrap().entries.len(), 1);
+    assert!(!manager.has_commands_to_undo());
+    assert_eq!(manager.next_undo_policy(), None);
+}
@@ -0,0 +1,70 @@
+//! Shared helpers for the yada-core integration tests
+
+// yada-core/tests/common/mod.rs
+#![allow(dead_code)]
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use yada_core::models::food::{Food, Macros};
+
+/// Counter making temporary directory names unique within a test binary
+static NEXT_DIR: AtomicUsize = AtomicUsize::new(0);
+
+/// Temporary directory that is removed when dropped
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    /// Creates a fresh, empty directory under the system temp directory
+    pub fn new(name: &str) -> Self {
+        let unique = NEXT_DIR.fetch_add(1, Ordering::SeqCst);
+        let path = std::env::temp_dir()
+            .join(format!("yada-core-{}-{}-{}", name, process::id(), unique));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).expect("create temp dir");
+        TempDir { path }
+    }
+
+    /// Returns the path of the directory itself
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the path of a file inside the directory as a string
+    pub fn file(&self, file_name: &str) -> String {
+        self.path.join(file_name).to_string_lossy().into_owned()
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Builds a keyword set from string slices
+pub fn keywords(words: &[&str]) -> HashSet<String> {
+    words.iter().map(|w| w.to_string()).collect()
+}
+
+/// Builds a basic food with macros and a serving weight
+pub fn basic_food(id: &str, calories: f64, macros: Macros, grams: f64) -> Food {
+    Food::new_basic(id.to_string(), id.to_string(), keywords(&[id]), calories)
+        .with_macros(macros)
+        .with_serving_details(Some(grams), None)
+}
+
+/// Asserts that two floating point values are equal within a small tolerance
+pub fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-6,
+        "expected {}, got {}",
+        expected,
+        actual
+    );
+}
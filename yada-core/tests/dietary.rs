@@ -0,0 +1,102 @@
+//! Integration tests for dietary restrictions and the food tags that conflict with them
+
+// yada-core/tests/dietary.rs
+mod common;
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+
+use common::{TempDir, keywords};
+use yada_core::models::dietary::{self, DietaryRestriction};
+use yada_core::models::food::Food;
+use yada_core::models::profile::{Gender, UserProfile};
+use yada_core::repositories::profile_repository::ProfileRepository;
+
+fn tagged(id: &str, name: &str, tags: &[&str]) -> Food {
+    Food::new_basic(id.to_string(), name.to_string(), keywords(&[]), 100.0)
+        .with_metadata(None, keywords(tags))
+}
+
+fn food_map(foods: Vec<Food>) -> HashMap<String, Food> {
+    foods.into_iter().map(|food| (food.id.clone(), food)).collect()
+}
+
+#[test]
+fn flagged_foods_conflict_only_with_the_restrictions_ruling_them_out() {
+    let bread = tagged("bread", "Wheat Bread", &["contains-gluten", "vegan"]);
+    let cheese = tagged("cheese", "Cheddar", &["contains-dairy", "vegetarian"]);
+    let foods = food_map(vec![bread.clone(), cheese.clone()]);
+
+    assert!(dietary::conflicts(&bread, &foods, &[]).is_empty());
+    assert!(dietary::conflicts(&bread, &foods, &[DietaryRestriction::Vegan]).is_empty());
+    assert!(dietary::conflicts(&cheese, &foods, &[DietaryRestriction::Vegetarian]).is_empty());
+
+    let conflicts = dietary::conflicts(&cheese, &foods, &[DietaryRestriction::Vegan]);
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].tag, "contains-dairy");
+    assert_eq!(
+        conflicts[0].to_string(),
+        "Cheddar is tagged contains-dairy, which conflicts with your vegan restriction"
+    );
+
+    // A flag shared by two restrictions is reported once
+    let conflicts = dietary::conflicts(&cheese, &foods, &[DietaryRestriction::DairyFree, DietaryRestriction::Vegan]);
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].restriction, DietaryRestriction::DairyFree);
+}
+
+#[test]
+fn composites_conflict_through_their_flagged_components() {
+    let bread = tagged("bread", "Wheat Bread", &["contains-gluten"]);
+    let butter = tagged("butter", "Peanut Butter", &["contains-nuts"]);
+    let sandwich = Food::new_composite("sandwich".into(), "Sandwich".into(), keywords(&[]),
+                                       vec![("bread".into(), 2.0), ("butter".into(), 1.0)]);
+    // A lunch made with the sandwich and more bread reports the bread once
+    let lunch = Food::new_composite("lunch".into(), "Lunch".into(), keywords(&[]),
+                                    vec![("sandwich".into(), 1.0), ("bread".into(), 1.0)]);
+    let foods = food_map(vec![bread, butter, sandwich.clone(), lunch.clone()]);
+    let restrictions = [DietaryRestriction::GlutenFree, DietaryRestriction::NutFree];
+
+    let conflicts = dietary::conflicts(&sandwich, &foods, &restrictions);
+    let described: Vec<String> = conflicts.iter().map(ToString::to_string).collect();
+    assert_eq!(described, [
+        "Wheat Bread (in Sandwich) is tagged contains-gluten, which conflicts with your gluten-free restriction",
+        "Peanut Butter (in Sandwich) is tagged contains-nuts, which conflicts with your nut-free restriction",
+    ]);
+
+    let conflicts = dietary::conflicts(&lunch, &foods, &restrictions);
+    assert_eq!(conflicts.len(), 2);
+    assert!(conflicts.iter().all(|conflict| conflict.within.as_deref() == Some("Lunch")));
+}
+
+#[test]
+fn restrictions_are_parsed_and_survive_a_round_trip() {
+    assert_eq!(
+        dietary::parse_restrictions("Vegan, gluten-free, vegan").unwrap(),
+        [DietaryRestriction::GlutenFree, DietaryRestriction::Vegan]
+    );
+    assert_eq!(
+        dietary::parse_restrictions("paleo").unwrap_err(),
+        "'paleo' is not a known restriction"
+    );
+
+    let dir = TempDir::new("dietary-restrictions");
+    let path = dir.file("profile.txt");
+    let mut repo = ProfileRepository::new(&path).unwrap();
+    let mut profile = UserProfile::new(Gender::Female, 165.0, NaiveDate::from_ymd_opt(1990, 1, 1).unwrap());
+    profile.restrictions = vec![DietaryRestriction::NutFree, DietaryRestriction::Vegetarian];
+    repo.set_profile(profile);
+    repo.save().unwrap();
+    assert!(std::fs::read_to_string(&path).unwrap().contains("RESTRICTIONS|nut-free,vegetarian\n"));
+
+    let reloaded = ProfileRepository::new(&path).unwrap();
+    assert_eq!(reloaded.get_profile().unwrap().restrictions, [DietaryRestriction::NutFree, DietaryRestriction::Vegetarian]);
+    assert_eq!(reloaded.skipped_line_count(), 0);
+
+    // Unknown restrictions are skipped, not guessed
+    std::fs::write(&path, "PROFILE|F|165|1990-01-01|mifflin_st_jeor\nRESTRICTIONS|vegan,paleo\n").unwrap();
+    let reloaded = ProfileRepository::new(&path).unwrap();
+    assert!(reloaded.get_profile().unwrap().restrictions.is_empty());
+    assert_eq!(reloaded.skipped_line_count(), 1);
+}
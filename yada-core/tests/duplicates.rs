@@ -0,0 +1,96 @@
+//! Integration tests for duplicate food detection and merging
+
+// yada-core/tests/duplicates.rs
+mod common;
+
+use chrono::NaiveDate;
+
+use common::{TempDir, assert_close, keywords};
+use yada_core::commands::food_commands::MergeFoodsCommand;
+use yada_core::models::command_manager::CommandManager;
+use yada_core::models::duplicates::{self, DuplicatePair};
+use yada_core::models::food::Food;
+use yada_core::models::meal::{MealPlan, MealSlot};
+use yada_core::repositories::food_repository::FoodRepository;
+use yada_core::repositories::log_repository::LogRepository;
+use yada_core::repositories::meal_plan_repository::MealPlanRepository;
+
+fn food(id: &str, name: &str, calories: f64) -> Food {
+    Food::new_basic(id.into(), name.into(), keywords(&[id]), calories)
+}
+
+#[test]
+fn similar_names_with_similar_calories_are_reported() {
+    let dir = TempDir::new("dup-find");
+    let mut repo = FoodRepository::new(&dir.file("foods.txt")).unwrap();
+    for food in [
+        food("yogurt", "Greek Yogurt", 100.0),
+        food("yoghurt", "greek yoghurt", 105.0),
+        food("oats", "Rolled Oats", 150.0),
+        food("oats2", "Rolled oats!", 300.0),
+        food("egg", "Egg", 70.0),
+        food("eggs", "Eggs", 72.0),
+    ] {
+        repo.add_food(food).unwrap();
+    }
+    repo.add_food(Food::new_composite("bowl".into(), "Greek Yogurt".into(), keywords(&[]), vec![("yogurt".into(), 1.0)])).unwrap();
+
+    // Calories too far apart, short names need an exact match, and composites only pair with composites
+    assert_eq!(duplicates::find_duplicates(repo.get_foods()), vec![DuplicatePair {
+        first: "yoghurt".into(),
+        second: "yogurt".into(),
+        name_distance: 1,
+        calorie_difference: 5.0,
+    }]);
+
+    assert!(duplicates::similar_calories(500.0, 549.0));
+    assert!(!duplicates::similar_calories(20.0, 31.0));
+    assert_eq!(duplicates::normalized_name("  Oats, Rolled (dry) "), "oats rolled dry");
+}
+
+#[test]
+fn merging_repoints_recipes_logs_and_plans_until_undone() {
+    let dir = TempDir::new("dup-merge");
+    let mut foods = FoodRepository::new(&dir.file("foods.txt")).unwrap();
+    let mut logs = LogRepository::new(&dir.file("logs.txt")).unwrap();
+    let mut plans = MealPlanRepository::new(&dir.file("meal_plans.txt")).unwrap();
+    let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+
+    foods.add_food(food("oats", "Rolled Oats", 150.0)).unwrap();
+    foods.add_food(food("oats2", "rolled oats", 100.0).with_serving_details(Some(40.0), None)).unwrap();
+    foods.add_food(Food::new_composite("porridge".into(), "Porridge".into(), keywords(&[]),
+        vec![("oats".into(), 1.0), ("oats2".into(), 2.0)])).unwrap();
+    foods.recalculate_composites();
+    logs.get_log_mut(date).add_entry("oats2".into(), 1.0);
+    logs.get_log_mut(date).add_entry("oats".into(), 1.0);
+    let mut plan = MealPlan::new(date);
+    plan.add_item(MealSlot::Breakfast, "oats2".into(), 1.0);
+    plans.set_plan(date, Some(plan));
+
+    let mut manager = CommandManager::new(10);
+    let merge = MergeFoodsCommand::new(&mut foods, &mut logs, &mut plans, "oats".into(), "oats2".into());
+    manager.execute_command(Box::new(merge)).unwrap();
+
+    assert!(foods.get_food("oats2").is_none());
+    let oats = foods.get_food("oats").unwrap();
+    assert!(oats.has_keyword("oats2"));
+    assert_eq!(oats.grams_per_serving, Some(40.0));
+    let porridge = foods.get_food("porridge").unwrap();
+    assert_eq!(porridge.components, vec![("oats".to_string(), 3.0)]);
+    assert_close(porridge.calories_per_serving, 450.0);
+    let ids: Vec<&str> = logs.get_log(date).unwrap().entries.iter().map(|e| e.food_id.as_str()).collect();
+    assert_eq!(ids, ["oats", "oats"]);
+    assert_eq!(plans.get_plan(date).unwrap().items[0].food_id, "oats");
+
+    manager.undo_last_command().unwrap();
+    assert_close(foods.get_food("oats2").unwrap().calories_per_serving, 100.0);
+    assert!(!foods.get_food("oats").unwrap().has_keyword("oats2"));
+    assert_close(foods.get_food("porridge").unwrap().calories_per_serving, 350.0);
+    let ids: Vec<&str> = logs.get_log(date).unwrap().entries.iter().map(|e| e.food_id.as_str()).collect();
+    assert_eq!(ids, ["oats2", "oats"]);
+    assert_eq!(plans.get_plan(date).unwrap().items[0].food_id, "oats2");
+
+    // A recipe cannot be merged with one of its own ingredients
+    assert!(foods.merge_food("porridge", "oats").is_err());
+    assert!(foods.merge_food("oats", "oats").is_err());
+}
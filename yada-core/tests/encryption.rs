@@ -0,0 +1,75 @@
+//! Integration tests for encrypted data files
+
+// yada-core/tests/encryption.rs
+mod common;
+
+use std::fs;
+use std::io;
+
+use common::{TempDir, basic_food};
+use yada_core::models::food::Macros;
+use yada_core::repositories::encryption;
+use yada_core::repositories::food_repository::FoodRepository;
+
+/// Saves a food database with one food while `password` (if any) is unlocked
+fn save_foods(path: &str, password: Option<&str>) {
+    match password {
+        Some(password) => encryption::unlock(password, true),
+        None => encryption::lock(),
+    }
+    let mut repo = FoodRepository::new(path).unwrap();
+    repo.add_food(basic_food("secret_snack", 250.0, Macros::default(), 50.0)).unwrap();
+    repo.save().unwrap();
+}
+
+#[test]
+fn encrypted_files_hide_their_contents_and_load_with_the_password() {
+    let dir = TempDir::new("encrypt-round-trip");
+    let path = dir.file("foods.txt");
+    save_foods(&path, Some("correct horse"));
+
+    let data = fs::read(&path).unwrap();
+    assert!(encryption::is_encrypted(&data));
+    assert!(!String::from_utf8_lossy(&data).contains("secret_snack"));
+    assert!(encryption::check_password(&data, "correct horse").unwrap());
+    assert!(!encryption::check_password(&data, "wrong").unwrap());
+
+    // A new session with the same password derives the key from the file's salt
+    encryption::unlock("correct horse", true);
+    assert!(FoodRepository::new(&path).unwrap().get_food("secret_snack").is_some());
+}
+
+#[test]
+fn encrypted_files_need_the_right_password() {
+    let dir = TempDir::new("encrypt-password");
+    let path = dir.file("foods.txt");
+    save_foods(&path, Some("correct horse"));
+
+    encryption::unlock("battery staple", true);
+    assert_eq!(FoodRepository::new(&path).err().map(|e| e.kind()), Some(io::ErrorKind::InvalidData));
+    encryption::lock();
+    assert_eq!(FoodRepository::new(&path).err().map(|e| e.kind()), Some(io::ErrorKind::PermissionDenied));
+}
+
+#[test]
+fn turning_encryption_on_and_off_converts_files_on_save() {
+    let dir = TempDir::new("encrypt-convert");
+    let path = dir.file("foods.txt");
+    save_foods(&path, None);
+    assert!(fs::read_to_string(&path).unwrap().starts_with("#yada:foods:v1"));
+
+    // Plain files load while encryption is on and are encrypted when saved
+    encryption::unlock("pw", true);
+    let mut repo = FoodRepository::new(&path).unwrap();
+    repo.save().unwrap();
+    assert!(encryption::is_encrypted(&fs::read(&path).unwrap()));
+    let backup = fs::read(format!("{}.bak.1", path)).unwrap();
+    assert!(!encryption::is_encrypted(&backup), "the previous version is kept as it was");
+
+    // With saves unencrypted, the password still opens the file
+    encryption::unlock("pw", false);
+    let mut repo = FoodRepository::new(&path).unwrap();
+    repo.save().unwrap();
+    assert!(fs::read_to_string(&path).unwrap().contains("secret_snack"));
+    encryption::lock();
+}
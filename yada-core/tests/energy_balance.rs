@@ -0,0 +1,81 @@
+//! Integration tests for weekly intake, weight change, and the adaptive TDEE estimate
+
+// yada-core/tests/energy_balance.rs
+mod common;
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{Duration, NaiveDate};
+
+use common::{assert_close, basic_food};
+use yada_core::models::energy_balance::{self, KCAL_PER_KG};
+use yada_core::models::food::Macros;
+use yada_core::models::log::DailyLog;
+use yada_core::models::week::WeekStart;
+
+fn date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 3, day).unwrap()
+}
+
+/// 2000 kcal on each of March 1 to 21 and a weigh-in every Friday, losing 0.5 kg a week
+fn three_weeks() -> (BTreeMap<NaiveDate, f64>, Vec<(NaiveDate, f64)>) {
+    let calories = (0..21).map(|offset| (date(1) + Duration::days(offset), 2000.0)).collect();
+    let weights = vec![(date(1), 80.0), (date(8), 79.5), (date(15), 79.0), (date(22), 78.5)];
+    (calories, weights)
+}
+
+#[test]
+fn the_adaptive_tdee_adds_the_energy_of_the_weight_lost_to_the_intake() {
+    let (calories, weights) = three_weeks();
+
+    let estimate = energy_balance::estimate(&calories, &weights, date(1), date(22)).unwrap();
+    assert_eq!(estimate.days_logged, 21);
+    assert_close(estimate.average_calories, 2000.0);
+    assert_close(estimate.weight_trend_per_week, -0.5);
+    assert_close(estimate.tdee, 2000.0 + 0.5 / 7.0 * KCAL_PER_KG);
+}
+
+#[test]
+fn an_estimate_needs_enough_logged_days_and_weigh_ins_far_enough_apart() {
+    let (calories, weights) = three_weeks();
+
+    // Six logged days
+    assert!(energy_balance::estimate(&calories, &weights, date(1), date(6)).is_none());
+    // The weigh-ins of March 8 and 15 are exactly one week apart
+    assert!(energy_balance::estimate(&calories, &weights, date(2), date(15)).is_some());
+    // A single weigh-in has no trend
+    assert!(energy_balance::estimate(&calories, &weights, date(2), date(12)).is_none());
+}
+
+#[test]
+fn weeks_pair_average_intake_with_the_change_since_the_week_before() {
+    let (calories, weights) = three_weeks();
+
+    // March 1, 2024 is a Friday, so the first Monday week starts on February 26
+    let weeks = energy_balance::weekly_balance(&calories, &weights, date(1), date(25), WeekStart::Monday);
+    assert_eq!(weeks.len(), 5);
+    assert_eq!(weeks[0].start, NaiveDate::from_ymd_opt(2024, 2, 26).unwrap());
+    assert_eq!(weeks[0].days_logged, 3);
+    assert_eq!(weeks[0].weight, Some(80.0));
+    // A single weigh-in without an earlier one has no change
+    assert_eq!(weeks[0].weight_change, None);
+    assert_close(weeks[1].weight_change.unwrap(), -0.5);
+    assert_close(weeks[1].average_calories.unwrap(), 2000.0);
+    assert_eq!(weeks[3].days_logged, 4);
+    assert_close(weeks[3].weight_change.unwrap(), -0.5);
+    // Weeks without data are listed too
+    assert_eq!(weeks[4].days_logged, 0);
+    assert_eq!((weeks[4].average_calories, weeks[4].weight, weeks[4].weight_change), (None, None, None));
+}
+
+#[test]
+fn daily_calories_leave_out_days_without_entries() {
+    let foods = HashMap::from([("oats".to_string(), basic_food("oats", 150.0, Macros::default(), 40.0))]);
+    let mut logged = DailyLog::new(date(4));
+    logged.add_entry("oats".into(), 2.0);
+    let empty = DailyLog::new(date(5));
+
+    let calories = energy_balance::daily_calories([&logged, &empty], &foods);
+    assert_eq!(calories.len(), 1);
+    assert_close(calories[&date(4)], 300.0);
+}
@@ -0,0 +1,112 @@
+//! Round-trip tests for delimiter characters in every repository's text fields
+
+// yada-core/tests/escaping.rs
+mod common;
+
+use chrono::NaiveDate;
+use proptest::prelude::*;
+
+use common::{TempDir, assert_close, keywords};
+use yada_core::models::food::Food;
+use yada_core::models::meal::{MealPlan, MealSlot};
+use yada_core::models::profile::{Gender, UserProfile};
+use yada_core::repositories::food_repository::FoodRepository;
+use yada_core::repositories::format;
+use yada_core::repositories::log_repository::LogRepository;
+use yada_core::repositories::meal_plan_repository::MealPlanRepository;
+use yada_core::repositories::profile_repository::ProfileRepository;
+
+/// An ID using every delimiter of the food, log, and meal plan formats
+const ID: &str = "mac|cheese,v2:big=yes\\";
+
+fn date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 3, day).unwrap()
+}
+
+#[test]
+fn foods_with_delimiters_in_every_text_field_round_trip() {
+    let dir = TempDir::new("escape-foods");
+    let path = dir.file("foods.txt");
+    let mut repo = FoodRepository::new(&path).unwrap();
+    let tags = keywords(&["low,fat", "a=b"]);
+    let mac = Food::new_basic(ID.into(), "Mac | Cheese, extra: cheddar = yes \\ no".into(), keywords(&["pasta|cheese", "x:y"]), 350.0)
+        .with_metadata(Some("Pasta|Dishes".into()), tags.clone());
+    let mut dinner = Food::new_composite("dinner".into(), "Dinner: Mac, Salad".into(), keywords(&["meal"]), vec![(ID.into(), 2.0)]);
+    dinner.calculate_from_components(&[(ID.to_string(), mac.clone())].into_iter().collect());
+    repo.add_food(mac.clone()).unwrap();
+    repo.add_food(dinner).unwrap();
+    repo.save().unwrap();
+
+    let reloaded = FoodRepository::new(&path).unwrap();
+    assert!(reloaded.load_report().is_clean());
+    let loaded = reloaded.get_food(ID).unwrap();
+    assert_eq!(loaded.name, mac.name);
+    assert!(loaded.keywords.is_superset(&mac.keywords));
+    assert_eq!(loaded.category, mac.category);
+    assert_eq!(loaded.tags, mac.tags);
+    let dinner = reloaded.get_food("dinner").unwrap();
+    assert_eq!(dinner.name, "Dinner: Mac, Salad");
+    assert_eq!(dinner.components, [(ID.to_string(), 2.0)]);
+    assert_close(dinner.calories_per_serving, 700.0);
+}
+
+#[test]
+fn log_and_meal_plan_food_ids_round_trip() {
+    let dir = TempDir::new("escape-logs");
+    let logs_path = dir.file("logs.txt");
+    let plans_path = dir.file("meal_plans.txt");
+
+    let mut logs = LogRepository::new(&logs_path).unwrap();
+    logs.get_log_mut(date(4)).add_entry(ID.into(), 1.5);
+    logs.save().unwrap();
+    let mut plans = MealPlanRepository::new(&plans_path).unwrap();
+    let mut plan = MealPlan::new(date(4));
+    plan.add_item(MealSlot::Dinner, ID.into(), 2.0);
+    plans.set_plan(date(4), Some(plan));
+    plans.save().unwrap();
+
+    let logs = LogRepository::new(&logs_path).unwrap();
+    let entry = &logs.get_log(date(4)).unwrap().entries[0];
+    assert_eq!(entry.food_id, ID);
+    assert_close(entry.servings, 1.5);
+    let plans = MealPlanRepository::new(&plans_path).unwrap();
+    assert_eq!(plans.get_plan(date(4)).unwrap().items[0].food_id, ID);
+}
+
+#[test]
+fn profile_calculation_method_round_trips() {
+    let dir = TempDir::new("escape-profile");
+    let path = dir.file("profile.txt");
+    let mut profile = UserProfile::new(Gender::Female, 165.0, NaiveDate::from_ymd_opt(1990, 1, 1).unwrap());
+    profile.calculation_method = "custom|method:v2".into();
+    let mut repo = ProfileRepository::new(&path).unwrap();
+    repo.set_profile(profile);
+    repo.save().unwrap();
+
+    let reloaded = ProfileRepository::new(&path).unwrap();
+    assert!(reloaded.load_report().is_clean());
+    assert_eq!(reloaded.get_profile().unwrap().calculation_method, "custom|method:v2");
+}
+
+#[test]
+fn text_that_cannot_be_stored_is_rejected() {
+    let dir = TempDir::new("escape-reject");
+    let mut repo = FoodRepository::new(&dir.file("foods.txt")).unwrap();
+    let food = |id: &str, name: &str| Food::new_basic(id.into(), name.into(), keywords(&["x"]), 1.0);
+
+    assert!(repo.add_food(food("tab", "Mac\tCheese")).unwrap_err().contains("Food name"));
+    assert!(repo.add_food(food("  ", "Blank")).is_err());
+    repo.add_food(food("ok", "Fine")).unwrap();
+    assert!(repo.update_food(food("ok", "Line\nbreak")).is_err());
+    assert_eq!(repo.get_food("ok").unwrap().name, "Fine");
+    assert!(format::validate_text("Name", "Mac | Cheese \\ ok").is_ok());
+}
+
+proptest! {
+    #[test]
+    fn escaped_fields_split_back_into_the_original_text(fields in prop::collection::vec("[^\\p{Cc}]*", 1..5)) {
+        let line = fields.iter().map(|field| format::escape(field)).collect::<Vec<_>>().join("|");
+        let parts: Vec<String> = format::split_escaped(&line, '|').into_iter().map(format::unescape).collect();
+        prop_assert_eq!(parts, fields);
+    }
+}
@@ -0,0 +1,89 @@
+//! Integration tests for exercise logging and calorie estimates
+
+// yada-core/tests/exercise.rs
+mod common;
+
+use chrono::NaiveDate;
+
+use common::{TempDir, assert_close};
+use yada_core::commands::exercise_commands::{AddExerciseCommand, RemoveExerciseCommand};
+use yada_core::models::command_manager::CommandManager;
+use yada_core::models::exercise::{self, ExerciseEntry};
+use yada_core::models::profile::{ActivityLevel, DailyProfile, Gender, UserProfile};
+use yada_core::repositories::exercise_repository::ExerciseRepository;
+
+fn date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 3, day).unwrap()
+}
+
+#[test]
+fn calories_are_estimated_from_met_weight_and_duration() {
+    let running = exercise::find_activity("running").unwrap();
+    assert_close(exercise::estimate_calories(running.met, 70.0, 30.0), 343.0);
+
+    let entry = ExerciseEntry::new("Yoga".into(), 2.5, 90.0, 60.0);
+    assert_close(entry.calories_burned, 225.0);
+    assert!(exercise::find_activity("juggling").is_none());
+}
+
+#[test]
+fn entries_survive_a_round_trip() {
+    let dir = TempDir::new("exercise-round-trip");
+    let path = dir.file("exercise.txt");
+    let mut repo = ExerciseRepository::new(&path).unwrap();
+    repo.add_entry(date(5), ExerciseEntry::new("Cycling".into(), 7.5, 40.0, 80.0));
+    repo.add_entry(date(5), ExerciseEntry::new("Rock | climbing".into(), 8.0, 60.0, 80.0));
+    repo.add_entry(date(6), ExerciseEntry::new("Walking".into(), 3.5, 20.0, 80.0));
+    repo.save().unwrap();
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert!(contents.starts_with("#yada:exercise:v1\n"));
+
+    let reloaded = ExerciseRepository::new(&path).unwrap();
+    let entries = reloaded.get_entries(date(5));
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[1].activity, "Rock | climbing");
+    assert_close(reloaded.calories_burned(date(5)), 400.0 + 640.0);
+    assert_close(reloaded.calories_burned(date(6)), 3.5 * 80.0 / 3.0);
+    assert_eq!(reloaded.skipped_line_count(), 0);
+    assert!(reloaded.get_entries(date(7)).is_empty());
+}
+
+#[test]
+fn adding_and_removing_exercise_can_be_undone() {
+    let dir = TempDir::new("exercise-undo");
+    let mut repo = ExerciseRepository::new(&dir.file("exercise.txt")).unwrap();
+    let mut manager = CommandManager::new(10);
+
+    for minutes in [30.0, 45.0] {
+        let entry = ExerciseEntry::new("Swimming".into(), 6.0, minutes, 70.0);
+        manager.execute_command(Box::new(AddExerciseCommand::new(&mut repo, date(5), entry))).unwrap();
+    }
+    manager.execute_command(Box::new(RemoveExerciseCommand::new(&mut repo, date(5), 0))).unwrap();
+    assert_eq!(repo.get_entries(date(5)).len(), 1);
+
+    // The removed entry comes back at its old position
+    manager.undo_last_command().unwrap();
+    assert_close(repo.get_entries(date(5))[0].minutes, 30.0);
+
+    manager.undo_last_command().unwrap();
+    assert_eq!(repo.get_entries(date(5)).len(), 1);
+    assert!(manager.execute_command(Box::new(RemoveExerciseCommand::new(&mut repo, date(5), 3))).is_err());
+}
+
+#[test]
+fn the_latest_weight_on_or_before_a_date_is_used() {
+    let mut profile = UserProfile::new(Gender::Female, 165.0, NaiveDate::from_ymd_opt(1990, 1, 1).unwrap());
+    for (day, weight) in [(1, 70.0), (10, 68.0)] {
+        profile.add_or_update_daily_profile(DailyProfile {
+            date: date(day),
+            weight,
+            activity_level: ActivityLevel::Sedentary,
+            carried_from: None,
+        });
+    }
+
+    assert_close(profile.latest_daily_profile(date(5)).unwrap().weight, 70.0);
+    assert_close(profile.latest_daily_profile(date(20)).unwrap().weight, 68.0);
+    assert!(profile.latest_daily_profile(NaiveDate::from_ymd_opt(2024, 2, 1).unwrap()).is_none());
+}
@@ -0,0 +1,62 @@
+//! Integration tests for food cost and calorie density
+
+// yada-core/tests/food_cost.rs
+mod common;
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+
+use common::{assert_close, basic_food};
+use yada_core::models::food::{Food, Macros};
+use yada_core::models::log::DailyLog;
+use yada_core::models::week::{self, WeekStart};
+
+fn foods() -> HashMap<String, Food> {
+    [
+        basic_food("oats", 150.0, Macros::new(5.0, 27.0, 3.0), 40.0).with_serving_details(Some(40.0), Some(0.25)),
+        basic_food("chicken", 165.0, Macros::new(31.0, 0.0, 4.0), 100.0).with_serving_details(Some(100.0), Some(1.5)),
+        basic_food("apple", 95.0, Macros::default(), 180.0),
+        basic_food("free", 10.0, Macros::default(), 0.0).with_serving_details(None, Some(0.0)),
+    ]
+    .into_iter()
+    .map(|food| (food.id.clone(), food))
+    .collect()
+}
+
+#[test]
+fn foods_know_their_calorie_density_and_value_for_money() {
+    let foods = foods();
+    assert_close(foods["oats"].calorie_density().unwrap(), 375.0);
+    assert_close(foods["oats"].per_unit_cost(foods["oats"].calories_per_serving).unwrap(), 600.0);
+    assert_close(foods["chicken"].per_unit_cost(foods["chicken"].macros.protein).unwrap(), 31.0 / 1.5);
+    assert!(foods["apple"].per_unit_cost(95.0).is_none(), "no cost");
+    assert!(foods["free"].per_unit_cost(10.0).is_none() && foods["free"].calorie_density().is_none());
+}
+
+#[test]
+fn daily_cost_counts_only_priced_entries() {
+    let foods = foods();
+    let date = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+    let mut log = DailyLog::new(date);
+    log.add_entry("oats".into(), 2.0);
+    log.add_entry("chicken".into(), 1.5);
+    log.add_entry("apple".into(), 1.0);
+    log.add_entry("deleted".into(), 1.0);
+
+    let cost = log.total_cost(&foods);
+    assert_close(cost.cost, 0.5 + 2.25);
+    assert_eq!((cost.priced_entries, cost.unpriced_entries), (2, 1));
+    assert_close(cost.calories_per_unit_cost().unwrap(), (300.0 + 247.5) / 2.75);
+    assert_close(cost.protein_per_unit_cost().unwrap(), (10.0 + 46.5) / 2.75);
+
+    // 300 + 247.5 + 95 kcal in 80 + 150 + 180 g
+    assert_close(log.calorie_density(&foods).unwrap(), 642.5 * 100.0 / 410.0);
+    assert!(DailyLog::new(date).calorie_density(&foods).is_none());
+    assert!(DailyLog::new(date).total_cost(&foods).calories_per_unit_cost().is_none());
+
+    let mut next = DailyLog::new(date.succ_opt().unwrap());
+    next.add_entry("oats".into(), 1.0);
+    let totals = week::weekly_totals([&log, &next], &foods, WeekStart::Monday);
+    assert_close(totals[0].cost, 3.0);
+}
@@ -0,0 +1,95 @@
+//! Integration tests for installing and uninstalling food packs
+
+// yada-core/tests/food_pack.rs
+mod common;
+
+use std::collections::HashSet;
+
+use common::{TempDir, assert_close};
+use yada_core::models::food::Food;
+use yada_core::repositories::food_pack::{self, FoodPack};
+use yada_core::repositories::food_repository::FoodRepository;
+
+const PACK: &str = "#yada:pack:v1
+# Prices and menus vary by country
+PACK|mcd|McDonald's|2024-05
+B|bigmac|Big Mac|burger,mcdonalds|563|protein=26|carbs=45|fat=33|grams=219|category=Fast Food
+B|fries_medium|Medium Fries|fries,mcdonalds|320|protein=5|carbs=43|fat=15
+B|mcd_coke|Coca-Cola (medium)|drink|210|carbs=58|last_used=2024-01-01
+C|bigmac_meal|Big Mac Meal|meal,mcdonalds|bigmac:1,fries_medium:1,mcd_coke:1
+";
+
+fn ids(foods: &[Food]) -> Vec<&str> {
+    foods.iter().map(|food| food.id.as_str()).collect()
+}
+
+#[test]
+fn packs_are_parsed_with_namespaced_ids() {
+    let pack = FoodPack::parse(PACK).unwrap();
+    assert_eq!((pack.name.as_str(), pack.title.as_str(), pack.version.as_deref()), ("mcd", "McDonald's", Some("2024-05")));
+    assert_eq!(ids(&pack.foods), vec!["mcd_bigmac", "mcd_fries_medium", "mcd_coke", "mcd_bigmac_meal"]);
+    let meal = &pack.foods[3];
+    let components: Vec<&str> = meal.components.iter().map(|(id, _)| id.as_str()).collect();
+    assert_eq!(components, vec!["mcd_bigmac", "mcd_fries_medium", "mcd_coke"]);
+    assert!(pack.foods.iter().all(|food| food.pack.as_deref() == Some("mcd") && food.last_used.is_none()));
+    assert_eq!(pack.foods[0].category.as_deref(), Some("Fast Food"));
+
+    let errors = [
+        ("#yada:foods:v1\nPACK|mcd|x\n", "not a food pack"),
+        ("#yada:pack:v1\nB|a|A|a|1\n", "PACK line"),
+        ("#yada:pack:v1\nPACK|McD|x\n", "invalid pack name"),
+        ("#yada:pack:v1\nPACK|mcd|x\nB|a|A|a\n", "basic food without calories"),
+        ("#yada:pack:v1\nPACK|mcd|x\nC|meal|Meal|m|apple:1\n", "'apple', which is not in the pack"),
+    ];
+    for (text, expected) in errors {
+        let error = FoodPack::parse(text).unwrap_err();
+        assert!(error.contains(expected), "{:?}: {}", text, error);
+    }
+}
+
+#[test]
+fn installing_adds_and_updates_the_packs_foods_and_keeps_them_across_saves() {
+    let dir = TempDir::new("pack-install");
+    let mut repo = FoodRepository::new(&dir.file("foods.txt")).unwrap();
+    repo.add_food(Food::new_basic("mcd_fries_medium".to_string(), "My Fries".to_string(), HashSet::new(), 300.0)).unwrap();
+
+    let pack = FoodPack::parse(PACK).unwrap();
+    let report = pack.install(&mut repo);
+    assert_eq!((report.added, report.updated), (3, 0));
+    assert_eq!(report.conflicts, vec!["mcd_fries_medium"], "the user's own food is not replaced");
+    assert_eq!(repo.get_food("mcd_fries_medium").unwrap().name, "My Fries");
+    assert_close(repo.get_food("mcd_bigmac_meal").unwrap().calories_per_serving, 563.0 + 300.0 + 210.0);
+
+    repo.save().unwrap();
+    let mut repo = FoodRepository::new(&dir.file("foods.txt")).unwrap();
+    assert_eq!(repo.get_food("mcd_bigmac").unwrap().pack.as_deref(), Some("mcd"));
+    assert_eq!(food_pack::installed_packs(&repo), vec![("mcd".to_string(), 3)]);
+
+    let newer = FoodPack::parse(&PACK.replace("|563|", "|550|")).unwrap();
+    let report = newer.install(&mut repo);
+    assert_eq!((report.added, report.updated), (0, 3));
+    assert_close(repo.get_food("mcd_bigmac").unwrap().calories_per_serving, 550.0);
+}
+
+#[test]
+fn uninstalling_keeps_foods_that_are_still_used() {
+    let dir = TempDir::new("pack-uninstall");
+    let mut repo = FoodRepository::new(&dir.file("foods.txt")).unwrap();
+    FoodPack::parse(PACK).unwrap().install(&mut repo);
+    let lunch = Food::new_composite("lunch".to_string(), "Lunch".to_string(), HashSet::new(), vec![("mcd_coke".to_string(), 1.0)]);
+    repo.add_food(lunch).unwrap();
+
+    // The logged meal keeps its components; the user's recipe keeps the coke
+    let in_use = HashSet::from(["mcd_bigmac_meal".to_string()]);
+    let report = food_pack::uninstall(&mut repo, "mcd", &in_use).unwrap();
+    assert!(report.removed.is_empty());
+    assert_eq!(report.kept, vec!["mcd_bigmac", "mcd_bigmac_meal", "mcd_coke", "mcd_fries_medium"]);
+
+    let report = food_pack::uninstall(&mut repo, "mcd", &HashSet::new()).unwrap();
+    assert_eq!(ids(&report.removed), vec!["mcd_bigmac", "mcd_bigmac_meal", "mcd_fries_medium"]);
+    assert_eq!(report.kept, vec!["mcd_coke"]);
+    assert!(repo.get_food("lunch").is_some());
+
+    let error = food_pack::uninstall(&mut repo, "bk", &HashSet::new()).unwrap_err();
+    assert_eq!(error, "No food pack named 'bk' is installed.");
+}
@@ -0,0 +1,68 @@
+//! Integration tests for reading foods described in plain words
+
+// yada-core/tests/food_phrase.rs
+mod common;
+
+use std::collections::HashSet;
+
+use common::{TempDir, assert_close, basic_food, keywords};
+use yada_core::models::food::{Food, Macros};
+use yada_core::models::food_phrase;
+use yada_core::models::quantity::Quantity;
+use yada_core::models::serving::ServingUnit;
+use yada_core::repositories::food_repository::FoodRepository;
+
+fn terms(words: &[&str]) -> HashSet<String> {
+    words.iter().map(|word| word.to_string()).collect()
+}
+
+#[test]
+fn sentences_split_into_amounts_units_and_food_words() {
+    let phrases = food_phrase::parse("2 Eggs and a slice of wheat bread, 150g chicken & half a cup of blueberries").unwrap();
+    assert_eq!(phrases.len(), 4);
+    assert_eq!(phrases[0].quantity, Some(Quantity::servings(2.0)));
+    assert_eq!(phrases[0].terms, terms(&["egg"]));
+    assert_eq!(phrases[1].text, "a slice of wheat bread");
+    assert_eq!(phrases[1].quantity, Some(Quantity::servings(1.0)));
+    assert_eq!(phrases[1].terms, terms(&["wheat", "bread"]));
+    assert_eq!(phrases[2].quantity, Some(Quantity::in_unit(150.0, ServingUnit::Gram)));
+    assert_eq!(phrases[3].quantity, Some(Quantity::in_unit(0.5, ServingUnit::Cup)));
+    assert_eq!(phrases[3].terms, terms(&["blueberry"]));
+
+    let phrases = food_phrase::parse("hummus with 1/2 pita").unwrap();
+    assert_eq!(phrases[0].quantity, None);
+    // The stem of a word that only looks plural still finds it by prefix
+    assert_eq!(phrases[0].terms, terms(&["hummu"]));
+    assert_eq!(phrases[1].quantity, Some(Quantity::servings(0.5)));
+
+    assert!(food_phrase::parse(" and , ").is_err());
+    assert_eq!(food_phrase::parse("toast and 2 of the").unwrap_err(), "'2 of the' does not say which food");
+}
+
+#[test]
+fn phrases_find_foods_by_search_and_convert_amounts() {
+    let dir = TempDir::new("food-phrase");
+    let mut repo = FoodRepository::new(&dir.file("foods.txt")).unwrap();
+    repo.add_food(basic_food("egg", 78.0, Macros::default(), 50.0).with_default_servings(Some(2.0))).unwrap();
+    repo.add_food(Food::new_basic("bread_wheat".into(), "Wheat Bread".into(), keywords(&["bread"]), 80.0)).unwrap();
+    repo.add_food(Food::new_basic("bread_white".into(), "White Bread".into(), keywords(&["bread"]), 75.0)).unwrap();
+
+    let phrases = food_phrase::parse("eggs, a piece of wheat bread, 100 g white bread").unwrap();
+    let best = |index: usize| repo.search_ranked(&phrases[index].terms, true)[0].food;
+
+    let egg = best(0);
+    assert_eq!(egg.id, "egg");
+    let (quantity, servings) = phrases[0].to_servings(egg).unwrap();
+    assert_eq!(quantity, Quantity::servings(2.0));
+    assert_close(servings, 2.0);
+
+    // Pieces of a food not measured in pieces count as servings
+    let bread = best(1);
+    assert_eq!(bread.id, "bread_wheat");
+    assert_close(phrases[1].to_servings(bread).unwrap().1, 1.0);
+
+    // Grams need a serving weight
+    let bread = best(2);
+    assert_eq!(bread.id, "bread_white");
+    assert!(phrases[2].to_servings(bread).is_err());
+}
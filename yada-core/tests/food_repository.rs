@@ -0,0 +1,185 @@
+//! Integration tests for the food database
+
+// yada-core/tests/food_repository.rs
+mod common;
+
+use std::fs;
+
+use common::{TempDir, assert_close, basic_food, keywords};
+use yada_core::models::derived_keywords::KeywordDerivation;
+use yada_core::models::food::{Food, FoodType, Macros};
+use yada_core::repositories::food_repository::{FoodRepository, FoodSort};
+
+#[test]
+fn add_food_rejects_duplicate_ids() {
+    let dir = TempDir::new("food-duplicate");
+    let mut repo = FoodRepository::new(&dir.file("foods.txt")).unwrap();
+
+    repo.add_food(basic_food("egg", 70.0, Macros::default(), 50.0)).unwrap();
+    assert!(repo.add_food(basic_food("egg", 80.0, Macros::default(), 50.0)).is_err());
+    assert_close(repo.get_food("egg").unwrap().calories_per_serving, 70.0);
+}
+
+#[test]
+fn search_supports_and_or_matching() {
+    let dir = TempDir::new("food-search");
+    let mut repo = FoodRepository::new(&dir.file("foods.txt")).unwrap();
+    repo.add_food(Food::new_basic("apple".into(), "Apple".into(), keywords(&["fruit", "red"]), 95.0)).unwrap();
+    repo.add_food(Food::new_basic("banana".into(), "Banana".into(), keywords(&["fruit", "yellow"]), 105.0)).unwrap();
+
+    assert_eq!(repo.search_foods(&keywords(&["fruit", "red"]), true, &[]).len(), 1);
+    assert_eq!(repo.search_foods(&keywords(&["red", "yellow"]), false, &[]).len(), 2);
+    assert!(repo.search_foods(&keywords(&["vegetable"]), false, &[]).is_empty());
+}
+
+#[test]
+fn save_and_load_round_trip_preserves_attributes() {
+    let dir = TempDir::new("food-round-trip");
+    let path = dir.file("foods.txt");
+
+    let mut repo = FoodRepository::new(&path).unwrap();
+    let egg = basic_food("egg", 70.0, Macros::new(6.0, 0.6, 5.0), 50.0)
+        .with_serving_details(Some(50.0), Some(0.25));
+    repo.add_food(egg).unwrap();
+    repo.add_food(basic_food("toast", 80.0, Macros::new(3.0, 15.0, 1.0), 30.0)).unwrap();
+    let mut breakfast = Food::new_composite(
+        "breakfast".into(),
+        "Breakfast".into(),
+        keywords(&["meal"]),
+        vec![("egg".into(), 2.0), ("toast".into(), 1.0)],
+    );
+    breakfast.calculate_from_components(repo.get_foods());
+    repo.add_food(breakfast).unwrap();
+    assert!(repo.is_dirty());
+
+    repo.save().unwrap();
+    assert!(!repo.is_dirty());
+
+    let reloaded = FoodRepository::new(&path).unwrap();
+    let egg = reloaded.get_food("egg").unwrap();
+    assert_eq!(egg.macros, Macros::new(6.0, 0.6, 5.0));
+    assert_eq!(egg.grams_per_serving, Some(50.0));
+    assert_eq!(egg.cost_per_serving, Some(0.25));
+
+    let breakfast = reloaded.get_food("breakfast").unwrap();
+    assert_eq!(breakfast.food_type, FoodType::Composite);
+    assert_eq!(breakfast.components.len(), 2);
+    assert_close(breakfast.calories_per_serving, 220.0);
+    assert_close(breakfast.macros.protein, 15.0);
+}
+
+#[test]
+fn fiber_is_stored_and_summed_for_composites() {
+    let dir = TempDir::new("food-fiber");
+    let path = dir.file("foods.txt");
+
+    let mut repo = FoodRepository::new(&path).unwrap();
+    repo.add_food(basic_food("oats", 150.0, Macros::new(5.0, 27.0, 3.0).with_fiber(4.0), 40.0)).unwrap();
+    repo.add_food(basic_food("berries", 50.0, Macros::new(1.0, 12.0, 0.5).with_fiber(3.0), 150.0)).unwrap();
+    let mut bowl = Food::new_composite(
+        "bowl".into(),
+        "Bowl".into(),
+        keywords(&["breakfast"]),
+        vec![("oats".into(), 1.0), ("berries".into(), 0.5)],
+    );
+    bowl.calculate_from_components(repo.get_foods());
+    repo.add_food(bowl).unwrap();
+    repo.save().unwrap();
+
+    assert!(std::fs::read_to_string(&path).unwrap().contains("|fiber=4"));
+    let reloaded = FoodRepository::new(&path).unwrap();
+    assert_close(reloaded.get_food("oats").unwrap().macros.fiber, 4.0);
+    assert_close(reloaded.get_food("bowl").unwrap().macros.fiber, 5.5);
+}
+
+#[test]
+fn default_servings_survive_a_round_trip() {
+    let dir = TempDir::new("food-default-servings");
+    let path = dir.file("foods.txt");
+
+    let mut repo = FoodRepository::new(&path).unwrap();
+    repo.add_food(basic_food("egg", 70.0, Macros::new(6.0, 0.6, 5.0), 50.0).with_default_servings(Some(2.0))).unwrap();
+    let mut omelette = Food::new_composite(
+        "omelette".into(),
+        "Omelette".into(),
+        keywords(&["breakfast"]),
+        vec![("egg".into(), 3.0)],
+    );
+    omelette.calculate_from_components(repo.get_foods());
+    repo.add_food(omelette.with_default_servings(Some(0.5))).unwrap();
+    repo.save().unwrap();
+
+    let contents = fs::read_to_string(&path).unwrap();
+    assert!(contents.contains("|default_servings=2"));
+    assert!(contents.contains("|default_servings=0.5"));
+    let reloaded = FoodRepository::new(&path).unwrap();
+    assert_close(reloaded.get_food("egg").unwrap().servings_to_log(), 2.0);
+    assert_close(reloaded.get_food("omelette").unwrap().servings_to_log(), 0.5);
+
+    // One serving is the default anyway, and amounts that are not positive are ignored
+    let toast = basic_food("toast", 80.0, Macros::new(3.0, 15.0, 1.0), 30.0);
+    assert_eq!(toast.clone().with_default_servings(Some(1.0)).default_servings, None);
+    assert_eq!(toast.with_default_servings(Some(-2.0)).servings_to_log(), 1.0);
+}
+
+#[test]
+fn load_accepts_lines_without_attributes_and_skips_malformed_ones() {
+    let dir = TempDir::new("food-legacy");
+    let path = dir.file("foods.txt");
+    fs::write(&path, "B|apple|Apple|fruit,red|95\nnot a food line\n").unwrap();
+
+    let repo = FoodRepository::new(&path).unwrap();
+    assert_eq!(repo.get_all_foods().len(), 1);
+    let apple = repo.get_food("apple").unwrap();
+    assert_close(apple.calories_per_serving, 95.0);
+    assert!(apple.macros.is_empty());
+    assert_eq!(apple.grams_per_serving, None);
+}
+
+#[test]
+fn category_and_tags_become_searchable_keywords() {
+    let dir = TempDir::new("food-derived");
+    let path = dir.file("foods.txt");
+    let mut repo = FoodRepository::new(&path).unwrap();
+    repo.set_keyword_derivation(KeywordDerivation::default());
+    let bread = Food::new_basic("bread".into(), "Bread".into(), keywords(&["bread"]), 80.0)
+        .with_metadata(Some("Whole Grain".into()), keywords(&["Contains-Gluten"]));
+    repo.add_food(bread).unwrap();
+
+    assert_eq!(repo.search_foods(&keywords(&["whole-grain"]), true, &[]).len(), 1);
+    assert_eq!(repo.search_foods(&keywords(&["grain", "gluten"]), true, &[]).len(), 1);
+
+    // Derived keywords are recomputed on load, not stored
+    repo.save().unwrap();
+    let contents = fs::read_to_string(&path).unwrap();
+    assert!(contents.contains("|category=Whole Grain|tags=contains-gluten"));
+    let mut reloaded = FoodRepository::new(&path).unwrap();
+    reloaded.set_keyword_derivation(KeywordDerivation::default());
+    assert_eq!(reloaded.get_food("bread").unwrap().keywords, keywords(&["bread"]));
+    assert_eq!(reloaded.search_foods(&keywords(&["gluten"]), false, &[]).len(), 1);
+
+    reloaded.set_keyword_derivation(KeywordDerivation::parse("category").unwrap());
+    assert!(reloaded.search_foods(&keywords(&["gluten"]), false, &[]).is_empty());
+    assert_eq!(reloaded.search_foods(&keywords(&["grain"]), false, &[]).len(), 1);
+}
+
+#[test]
+fn foods_are_listed_sorted_without_archived_ones() {
+    let dir = TempDir::new("food-sorted");
+    let mut repo = FoodRepository::new(&dir.file("foods.txt")).unwrap();
+    for (id, name, calories) in [("b1", "banana", 105.0), ("a2", "Apple", 95.0), ("c3", "cola", 140.0), ("a1", "apple", 95.0)] {
+        repo.add_food(Food::new_basic(id.into(), name.into(), keywords(&[id]), calories)).unwrap();
+    }
+    let mut archived = Food::new_basic("z9".into(), "Aardvark chow".into(), keywords(&["z9"]), 1.0);
+    archived.archived = true;
+    repo.add_food(archived).unwrap();
+
+    let ids = |repo: &FoodRepository, sort| repo.get_foods_sorted(sort).iter().map(|food| food.id.clone()).collect::<Vec<_>>();
+    // Names compare without case; ties fall back to the ID
+    assert_eq!(ids(&repo, FoodSort::Name), vec!["a1", "a2", "b1", "c3"]);
+    assert_eq!(ids(&repo, FoodSort::Calories), vec!["a1", "a2", "b1", "c3"]);
+    assert_eq!(ids(&repo, FoodSort::Id), vec!["a1", "a2", "b1", "c3"]);
+
+    repo.get_foods_mut().get_mut("c3").unwrap().calories_per_serving = 0.0;
+    assert_eq!(ids(&repo, FoodSort::Calories), vec!["c3", "a1", "a2", "b1"]);
+}
@@ -0,0 +1,109 @@
+//! Integration tests for caching food sources
+
+// yada-core/tests/food_source_cache.rs
+mod common;
+
+use std::fs;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use chrono::Duration;
+
+use common::{TempDir, assert_close, basic_food};
+use yada_core::factories::caching_food_source::{CachingFoodSource, DEFAULT_TTL};
+use yada_core::factories::food_source_factory::{FoodSource, FoodSourceFactory};
+use yada_core::models::food::{Food, Macros};
+
+/// Online source stand-in that counts requests and can go offline
+struct FakeOnlineSource {
+    online: Arc<AtomicBool>,
+    requests: Arc<AtomicUsize>,
+}
+
+impl FakeOnlineSource {
+    fn new() -> (Self, Arc<AtomicBool>, Arc<AtomicUsize>) {
+        let online = Arc::new(AtomicBool::new(true));
+        let requests = Arc::new(AtomicUsize::new(0));
+        (FakeOnlineSource { online: online.clone(), requests: requests.clone() }, online, requests)
+    }
+
+    fn foods(&self) -> Vec<Food> {
+        self.requests.fetch_add(1, Ordering::SeqCst);
+        if !self.online.load(Ordering::SeqCst) {
+            return Vec::new();
+        }
+        vec![
+            basic_food("usda_oats", 150.0, Macros::new(5.0, 27.0, 3.0), 40.0),
+            basic_food("usda_oat_milk", 120.0, Macros::new(3.0, 16.0, 5.0), 240.0),
+        ]
+    }
+}
+
+impl FoodSource for FakeOnlineSource {
+    fn get_food_by_id(&self, id: &str) -> Option<Food> {
+        self.foods().into_iter().find(|food| food.id == id)
+    }
+
+    fn search_foods(&self, query: &str) -> Vec<Food> {
+        self.foods().into_iter().filter(|food| food.id.contains(&query.trim().to_lowercase())).collect()
+    }
+
+    fn name(&self) -> &'static str {
+        "usda"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fake USDA database"
+    }
+}
+
+#[test]
+fn fresh_results_are_answered_from_the_cache() {
+    let dir = TempDir::new("cache-fresh");
+    let (source, _, requests) = FakeOnlineSource::new();
+    let cache = CachingFoodSource::new(Box::new(source), dir.path(), DEFAULT_TTL).unwrap();
+
+    assert_eq!(cache.search_foods("Oat").len(), 2);
+    assert_eq!(cache.search_foods(" oat ").len(), 2, "queries are normalized");
+    assert!(cache.get_food_by_id("usda_oats").is_some(), "foods found by a search are cached");
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+    assert_eq!(cache.name(), "usda");
+
+    assert!(cache.get_food_by_id("usda_rice").is_none());
+    assert_eq!(requests.load(Ordering::SeqCst), 2, "unknown foods are asked for");
+}
+
+#[test]
+fn cached_foods_survive_restarts_and_work_offline() {
+    let dir = TempDir::new("cache-offline");
+    let (source, _, _) = FakeOnlineSource::new();
+    let cache = CachingFoodSource::new(Box::new(source), dir.path(), DEFAULT_TTL).unwrap();
+    cache.search_foods("oat");
+    let times = fs::read_to_string(dir.path().join("usda_cache.txt")).unwrap();
+    assert!(times.starts_with("#yada:food_cache:v1\nFOOD|usda_oat_milk|"), "{}", times);
+    assert!(times.contains("QUERY|oat|"), "{}", times);
+
+    // A new session offline, with everything expired: stale results beat none
+    let (source, online, requests) = FakeOnlineSource::new();
+    online.store(false, Ordering::SeqCst);
+    let cache = CachingFoodSource::new(Box::new(source), dir.path(), Duration::zero()).unwrap();
+    assert_eq!(cache.cached_food_count(), 2);
+    let oats = cache.get_food_by_id("usda_oats").unwrap();
+    assert_close(oats.macros.protein, 5.0);
+    assert_eq!(cache.search_foods("oat").len(), 2);
+    assert_eq!(requests.load(Ordering::SeqCst), 2, "stale entries are fetched again first");
+}
+
+#[test]
+fn the_factory_registers_cached_sources_under_their_own_name() {
+    let dir = TempDir::new("cache-factory");
+    let (source, _, requests) = FakeOnlineSource::new();
+    let mut factory = FoodSourceFactory::new();
+    factory.register_cached_source(Box::new(source), &dir.path().join("cache"), DEFAULT_TTL).unwrap();
+
+    let usda = factory.get_source("usda").unwrap();
+    usda.search_foods("oat");
+    usda.search_foods("oat");
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+    assert!(dir.path().join("cache").join("usda_foods.txt").exists());
+}
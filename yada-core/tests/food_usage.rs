@@ -0,0 +1,174 @@
+//! Integration tests for food usage tracking, popularity, and archiving
+
+// yada-core/tests/food_usage.rs
+mod common;
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+
+use common::{TempDir, basic_food, keywords};
+use yada_core::commands::food_commands::ArchiveFoodsCommand;
+use yada_core::models::command_manager::CommandManager;
+use yada_core::models::food::{Food, Macros};
+use yada_core::models::log::DailyLog;
+use yada_core::models::usage::{self, ARCHIVE_AFTER_DAYS};
+use yada_core::repositories::food_repository::FoodRepository;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+fn log(day: NaiveDate, food_ids: &[&str]) -> DailyLog {
+    let mut log = DailyLog::new(day);
+    for id in food_ids {
+        log.add_entry(id.to_string(), 1.0);
+    }
+    log
+}
+
+fn food_db(foods: Vec<Food>) -> HashMap<String, Food> {
+    foods.into_iter().map(|food| (food.id.clone(), food)).collect()
+}
+
+fn sandwich() -> Food {
+    Food::new_composite(
+        "sandwich".into(),
+        "Sandwich".into(),
+        keywords(&["lunch"]),
+        vec![("bread".into(), 2.0), ("ham".into(), 1.0)],
+    )
+}
+
+#[test]
+fn recent_uses_outweigh_many_old_ones() {
+    let today = date(2024, 6, 1);
+    let foods = food_db(vec![
+        basic_food("oats", 150.0, Macros::default(), 40.0),
+        basic_food("toast", 80.0, Macros::default(), 30.0),
+    ]);
+    let mut logs: Vec<DailyLog> = (1..=10).map(|day| log(date(2023, 1, day), &["oats"])).collect();
+    logs.push(log(date(2024, 5, 30), &["toast"]));
+    logs.push(log(date(2024, 5, 31), &["toast"]));
+
+    let found = usage::collect_usage(&logs, &foods, today);
+    assert_eq!(found["oats"].uses, 10);
+    assert_eq!(found["oats"].last_used, Some(date(2023, 1, 10)));
+    assert!(found["toast"].popularity > found["oats"].popularity);
+
+    let mut picker: Vec<&Food> = foods.values().collect();
+    usage::sort_by_popularity(&mut picker, &found);
+    assert_eq!(picker[0].id, "toast");
+}
+
+#[test]
+fn logging_a_composite_counts_as_using_its_components() {
+    let today = date(2024, 6, 1);
+    let foods = food_db(vec![
+        basic_food("bread", 80.0, Macros::default(), 30.0),
+        basic_food("ham", 40.0, Macros::default(), 20.0),
+        sandwich(),
+    ]);
+    let logs = vec![log(date(2024, 5, 20), &["sandwich"])];
+
+    let found = usage::collect_usage(&logs, &foods, today);
+    assert_eq!(found["bread"].last_used, Some(date(2024, 5, 20)));
+    assert_eq!(found["bread"].uses, 0);
+    assert_eq!(found["sandwich"].uses, 1);
+}
+
+#[test]
+fn archive_candidates_skip_recent_foods_and_live_recipe_ingredients() {
+    let today = date(2024, 6, 1);
+    let old = date(2023, 1, 1);
+    let mut bread = basic_food("bread", 80.0, Macros::default(), 30.0);
+    bread.last_used = Some(old);
+    let mut ham = basic_food("ham", 40.0, Macros::default(), 20.0);
+    ham.last_used = Some(old);
+    let mut lard = basic_food("lard", 900.0, Macros::default(), 100.0);
+    lard.last_used = Some(date(2022, 12, 1));
+    let mut kale = basic_food("kale", 30.0, Macros::default(), 50.0);
+    kale.last_used = Some(date(2024, 5, 1));
+    let mut old_archived = basic_food("tang", 90.0, Macros::default(), 20.0);
+    old_archived.last_used = Some(old);
+    old_archived.archived = true;
+    let unknown = basic_food("mystery", 10.0, Macros::default(), 10.0);
+    // Added recently, so its ingredients must stay visible
+    let mut recipe = sandwich();
+    recipe.last_used = Some(date(2024, 5, 28));
+
+    let foods = food_db(vec![bread, ham, lard, kale, old_archived, unknown, recipe]);
+    let candidates = usage::archive_candidates(&foods, today, ARCHIVE_AFTER_DAYS);
+    assert_eq!(candidates, vec!["lard".to_string()]);
+}
+
+#[test]
+fn usage_and_archive_flag_survive_a_round_trip() {
+    let dir = TempDir::new("usage-round-trip");
+    let path = dir.file("foods.txt");
+    let mut repo = FoodRepository::new(&path).unwrap();
+    repo.add_food(basic_food("bread", 80.0, Macros::default(), 30.0)).unwrap();
+    repo.add_food(basic_food("ham", 40.0, Macros::default(), 20.0)).unwrap();
+    repo.add_food(sandwich()).unwrap();
+    repo.record_usage("sandwich", date(2024, 3, 5));
+    repo.set_archived("ham", true).unwrap();
+    repo.save().unwrap();
+
+    let reloaded = FoodRepository::new(&path).unwrap();
+    assert_eq!(reloaded.get_food("bread").unwrap().last_used, Some(date(2024, 3, 5)));
+    assert_eq!(reloaded.get_food("sandwich").unwrap().last_used, Some(date(2024, 3, 5)));
+    assert!(reloaded.get_food("ham").unwrap().archived);
+    assert_eq!(reloaded.get_active_foods().len(), 2);
+}
+
+#[test]
+fn logging_an_archived_food_restores_it_and_dates_never_move_back() {
+    let dir = TempDir::new("usage-restore");
+    let mut repo = FoodRepository::new(&dir.file("foods.txt")).unwrap();
+    repo.add_food(basic_food("kale", 30.0, Macros::default(), 50.0)).unwrap();
+    repo.set_archived("kale", true).unwrap();
+
+    repo.record_usage("kale", date(2024, 5, 1));
+    repo.record_usage("kale", date(2024, 1, 1));
+    let kale = repo.get_food("kale").unwrap();
+    assert!(!kale.archived);
+    assert_eq!(kale.last_used, Some(date(2024, 5, 1)));
+}
+
+#[test]
+fn sync_stamps_unknown_foods_with_today() {
+    let dir = TempDir::new("usage-sync");
+    let mut repo = FoodRepository::new(&dir.file("foods.txt")).unwrap();
+    repo.add_food(basic_food("oats", 150.0, Macros::default(), 40.0)).unwrap();
+    repo.add_food(basic_food("kale", 30.0, Macros::default(), 50.0)).unwrap();
+    let today = date(2024, 6, 1);
+    let logs = vec![log(date(2024, 2, 1), &["oats"])];
+
+    let found = usage::collect_usage(&logs, repo.get_foods(), today);
+    repo.sync_usage(&found, today);
+    assert_eq!(repo.get_food("oats").unwrap().last_used, Some(date(2024, 2, 1)));
+    assert_eq!(repo.get_food("kale").unwrap().last_used, Some(today));
+}
+
+#[test]
+fn archive_command_can_be_undone() {
+    let dir = TempDir::new("usage-archive-undo");
+    let mut repo = FoodRepository::new(&dir.file("foods.txt")).unwrap();
+    repo.add_food(basic_food("oats", 150.0, Macros::default(), 40.0)).unwrap();
+    repo.add_food(basic_food("kale", 30.0, Macros::default(), 50.0)).unwrap();
+    repo.set_archived("kale", true).unwrap();
+    let mut manager = CommandManager::new(10);
+
+    let command = ArchiveFoodsCommand::new(&mut repo, vec!["oats".into(), "kale".into()], true);
+    manager.execute_command(Box::new(command)).unwrap();
+    assert!(repo.get_active_foods().is_empty());
+
+    // Only the food archived by the command comes back
+    manager.undo_last_command().unwrap();
+    assert!(!repo.get_food("oats").unwrap().archived);
+    assert!(repo.get_food("kale").unwrap().archived);
+
+    let unknown = ArchiveFoodsCommand::new(&mut repo, vec!["oats".into(), "nope".into()], true);
+    assert!(manager.execute_command(Box::new(unknown)).is_err());
+    assert!(!repo.get_food("oats").unwrap().archived);
+}
@@ -0,0 +1,161 @@
+//! Integration tests for importing other apps' diary exports
+
+// yada-core/tests/importers.rs
+mod common;
+
+use chrono::NaiveDate;
+
+use common::{TempDir, assert_close};
+use yada_core::importers::{Importer, ImporterFactory};
+use yada_core::importers::cronometer::CronometerImporter;
+use yada_core::importers::loseit::LoseItImporter;
+use yada_core::importers::mfp::MfpImporter;
+use yada_core::models::meal::MealSlot;
+use yada_core::repositories::food_repository::FoodRepository;
+use yada_core::repositories::log_repository::LogRepository;
+
+fn date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 3, day).unwrap()
+}
+
+fn repos(dir: &TempDir) -> (FoodRepository, LogRepository) {
+    (
+        FoodRepository::new(&dir.file("foods.txt")).unwrap(),
+        LogRepository::new(&dir.file("logs.txt")).unwrap(),
+    )
+}
+
+const PER_FOOD: &str = "\u{feff}Date,Meal,Food,Calories,Fat (g),Carbohydrates (g),Protein (g)\n\
+2024-03-05,Breakfast,\"Greek Yogurt, Plain\",150,4,8,20\n\
+2024-03-05,Breakfast,Blueberries,40,0,10,0.5\n\
+03/06/2024,Snacks,\"Greek Yogurt, Plain\",\"300\",8,16,40\n\
+2024-03-06,Meal 5,Blueberries,20,0,5,0.3\n";
+
+#[test]
+fn mfp_per_food_exports_create_foods_and_log_servings() {
+    let dir = TempDir::new("mfp-per-food");
+    let (mut food_repo, mut log_repo) = repos(&dir);
+
+    let report = MfpImporter.import(&mut food_repo, &mut log_repo, PER_FOOD).unwrap();
+    assert_eq!(report.rows, 4);
+    assert_eq!(report.new_foods, ["Greek Yogurt, Plain", "Blueberries"]);
+    assert_eq!(report.entries.added, 4);
+    assert_eq!(report.dates, Some((date(5), date(6))));
+
+    let yogurt = food_repo.get_food("mfp_greek_yogurt_plain").unwrap();
+    assert_close(yogurt.calories_per_serving, 150.0);
+    assert_close(yogurt.macros.protein, 20.0);
+    assert!(!yogurt.archived);
+
+    let day = log_repo.get_log(date(6)).unwrap();
+    assert_eq!(day.entries.len(), 2);
+    let yogurt_entry = day.entries.iter().find(|entry| entry.food_id == "mfp_greek_yogurt_plain").unwrap();
+    assert_close(yogurt_entry.servings, 2.0);
+    assert_eq!(yogurt_entry.meal, Some(MealSlot::Snack));
+    let berries = day.entries.iter().find(|entry| entry.food_id == "mfp_blueberries").unwrap();
+    assert_close(berries.servings, 0.5);
+    assert_eq!(berries.meal, None, "custom meals have no slot");
+}
+
+#[test]
+fn mfp_per_meal_exports_log_archived_meal_totals() {
+    let dir = TempDir::new("mfp-per-meal");
+    let (mut food_repo, mut log_repo) = repos(&dir);
+    let text = "Date,Meal,Calories,Fat (g),Carbohydrates (g),Protein (g),Fiber\n\
+        2024-03-05,Breakfast,\"1,050\",30,120,45,8\n\
+        2024-03-05,Lunch,0,0,0,0,0\n\
+        2024-03-05,Dinner,700,25,60,50,6\n";
+
+    let report = MfpImporter.import(&mut food_repo, &mut log_repo, text).unwrap();
+    assert_eq!(report.new_foods, ["MyFitnessPal Breakfast 2024-03-05", "MyFitnessPal Dinner 2024-03-05"]);
+    assert_eq!(report.entries.added, 2, "empty meals are left out");
+
+    let breakfast = food_repo.get_food("mfp_20240305_breakfast").unwrap();
+    assert_close(breakfast.calories_per_serving, 1050.0);
+    assert_close(breakfast.macros.fiber, 8.0);
+    assert!(breakfast.archived, "meal totals stay out of search");
+    assert_close(log_repo.get_log(date(5)).unwrap().entries[0].servings, 1.0);
+}
+
+#[test]
+fn importing_the_same_export_again_skips_existing_entries() {
+    let dir = TempDir::new("mfp-repeat");
+    let (mut food_repo, mut log_repo) = repos(&dir);
+    MfpImporter.import(&mut food_repo, &mut log_repo, PER_FOOD).unwrap();
+    food_repo.save().unwrap();
+    log_repo.save().unwrap();
+
+    let (mut food_repo, mut log_repo) = repos(&dir);
+    let report = MfpImporter.import(&mut food_repo, &mut log_repo, PER_FOOD).unwrap();
+    assert!(report.new_foods.is_empty());
+    assert_eq!(report.entries.added, 0);
+    assert_eq!(report.entries.skipped, 4);
+    assert_eq!(log_repo.get_log(date(5)).unwrap().entries.len(), 2);
+}
+
+#[test]
+fn files_that_are_not_exports_are_rejected() {
+    let dir = TempDir::new("mfp-invalid");
+    let (mut food_repo, mut log_repo) = repos(&dir);
+
+    let error = MfpImporter.import(&mut food_repo, &mut log_repo, "id,name\napple,Apple\n").unwrap_err();
+    assert!(error.contains("Not a MyFitnessPal export"));
+    let error = MfpImporter.import(&mut food_repo, &mut log_repo, "Date,Food,Calories\nyesterday,Apple,95\n").unwrap_err();
+    assert!(error.contains("row 2"));
+    assert!(food_repo.get_foods().is_empty());
+}
+
+#[test]
+fn cronometer_servings_keep_their_time_and_group() {
+    let dir = TempDir::new("cronometer");
+    let (mut food_repo, mut log_repo) = repos(&dir);
+    let text = "Day,Time,Group,Food Name,Amount,Energy (kcal),Protein (g),Carbs (g),Fat (g),Fiber (g)\n\
+        2024-03-05,08:10,Breakfast,\"Oats, Rolled\",1.00 cup,307.2,10.7,54.8,5.3,8.2\n\
+        2024-03-05,,Uncategorized,Coffee,1 cup,2.4,0.3,0,0,0\n\
+        2024-03-05,8:10 PM,Snacks,\"Oats, Rolled\",0.50 cup,153.6,5.35,27.4,2.65,4.1\n";
+
+    let report = CronometerImporter.import(&mut food_repo, &mut log_repo, text).unwrap();
+    assert_eq!(report.new_foods, ["Oats, Rolled", "Coffee"]);
+    assert_eq!(report.entries.added, 3);
+    assert_close(food_repo.get_food("cronometer_oats_rolled").unwrap().macros.fiber, 8.2);
+
+    let entries = &log_repo.get_log(date(5)).unwrap().entries;
+    let times: Vec<String> = entries.iter().map(|entry| entry.timestamp.format("%H:%M").to_string()).collect();
+    assert!(times.contains(&"08:10".to_string()) && times.contains(&"20:10".to_string()), "{:?}", times);
+    let coffee = entries.iter().find(|entry| entry.food_id == "cronometer_coffee").unwrap();
+    assert_eq!(coffee.meal, None);
+    assert_eq!(coffee.timestamp.format("%H:%M").to_string(), "15:30");
+    let snack = entries.iter().find(|entry| entry.meal == Some(MealSlot::Snack)).unwrap();
+    assert_close(snack.servings, 0.5);
+}
+
+#[test]
+fn loseit_exports_leave_out_exercise() {
+    let dir = TempDir::new("loseit");
+    let (mut food_repo, mut log_repo) = repos(&dir);
+    let text = "Date,Name,Icon,Meal,Quantity,Units,Calories,Fat (g),Protein (g),Carbohydrates (g),Fiber (g)\n\
+        03/05/2024,Banana,Banana,Breakfast,1,Each,105,0.4,1.3,27,3.1\n\
+        03/05/2024,Running,Running,Exercise,30,Minutes,-300,,,,\n\
+        03/06/2024,Banana,Banana,Lunch,2,Each,210,0.8,2.6,54,6.2\n";
+
+    let report = LoseItImporter.import(&mut food_repo, &mut log_repo, text).unwrap();
+    assert_eq!(report.rows, 2);
+    assert_eq!(report.new_foods, ["Banana"]);
+    assert_eq!(report.dates, Some((date(5), date(6))));
+    assert_close(log_repo.get_log(date(6)).unwrap().entries[0].servings, 2.0);
+    assert!(food_repo.get_food("loseit_running").is_none());
+}
+
+#[test]
+fn the_factory_lists_every_importer() {
+    let factory = ImporterFactory::new();
+    let names: Vec<&str> = factory.get_all_importers().iter().map(|importer| importer.name()).collect();
+    assert_eq!(names, ["cronometer", "loseit", "mfp"], "ordered by app name");
+    assert_eq!(factory.get_importer("MFP").unwrap().app_name(), "MyFitnessPal");
+    assert!(factory.get_importer("fitbit").is_none());
+
+    let dir = TempDir::new("importer-mismatch");
+    let (mut food_repo, mut log_repo) = repos(&dir);
+    let error = CronometerImporter.import(&mut food_repo, &mut log_repo, PER_FOOD).unwrap_err();
+    assert!(error.contains("Not a Cronometer export"), "{}", error);
+}
@@ -0,0 +1,176 @@
+//! Integration tests for the bulk ingest API
+
+// yada-core/tests/ingest.rs
+mod common;
+
+use chrono::NaiveDate;
+
+use common::{TempDir, assert_close, basic_food, keywords};
+use yada_core::ingest::{self, ConflictPolicy, csv, json};
+use yada_core::ingest::records::{FoodRecord, LogEntryRecord};
+use yada_core::models::food::{Food, Macros};
+use yada_core::models::meal::MealSlot;
+use yada_core::models::quantity::Quantity;
+use yada_core::repositories::food_repository::FoodRepository;
+use yada_core::repositories::log_repository::LogRepository;
+
+fn date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 3, day).unwrap()
+}
+
+fn food_record(id: &str, calories: f64) -> FoodRecord {
+    FoodRecord {
+        id: id.to_string(),
+        name: id.to_string(),
+        calories,
+        ..FoodRecord::default()
+    }
+}
+
+fn repo_with_toast(dir: &TempDir) -> FoodRepository {
+    let mut repo = FoodRepository::new(&dir.file("foods.txt")).unwrap();
+    repo.add_food(basic_food("toast", 80.0, Macros::new(3.0, 15.0, 1.0), 30.0)).unwrap();
+    let mut breakfast = Food::new_composite("breakfast".into(), "Breakfast".into(), keywords(&["meal"]), vec![("toast".into(), 2.0)]);
+    breakfast.calculate_from_components(repo.get_foods());
+    repo.add_food(breakfast).unwrap();
+    repo
+}
+
+#[test]
+fn foods_are_validated_and_conflicts_follow_the_policy() {
+    let dir = TempDir::new("ingest-foods");
+    let mut repo = repo_with_toast(&dir);
+    let records = vec![
+        food_record("apple", 95.0),
+        food_record("toast", 100.0),
+        food_record("bad id", 10.0),
+        food_record("negative", -5.0),
+        food_record("breakfast", 300.0),
+    ];
+
+    let report = ingest::import_foods(&mut repo, &records, ConflictPolicy::Skip);
+    assert_eq!((report.added, report.replaced, report.skipped), (1, 0, 2));
+    let failed: Vec<usize> = report.errors.iter().map(|error| error.index).collect();
+    assert_eq!(failed, vec![2, 3]);
+    assert_close(repo.get_food("toast").unwrap().calories_per_serving, 80.0);
+
+    let report = ingest::import_foods(&mut repo, &records[..2], ConflictPolicy::Reject);
+    assert_eq!(report.errors.len(), 2);
+
+    // Replacing a basic food updates the recipes built from it; composites are never replaced
+    let report = ingest::import_foods(&mut repo, &records, ConflictPolicy::Replace);
+    assert_eq!(report.replaced, 2);
+    assert_eq!(report.errors.len(), 3);
+    assert_close(repo.get_food("breakfast").unwrap().calories_per_serving, 200.0);
+    assert!(repo.is_dirty());
+}
+
+#[test]
+fn log_entries_need_known_foods_and_deduplicate_by_timestamp() {
+    let dir = TempDir::new("ingest-log");
+    let foods = repo_with_toast(&dir);
+    let mut log = LogRepository::new(&dir.file("logs.txt")).unwrap();
+    let time = date(5).and_hms_opt(8, 15, 0).unwrap();
+
+    let mut stamped = LogEntryRecord::new(date(5), "toast", Quantity::servings(2.0));
+    stamped.timestamp = Some(time);
+    stamped.meal = Some(MealSlot::Breakfast);
+    let records = vec![
+        stamped.clone(),
+        LogEntryRecord::new(date(5), "toast", Quantity::servings(1.0)),
+        LogEntryRecord::new(date(5), "cake", Quantity::servings(1.0)),
+        LogEntryRecord::new(date(5), "toast", Quantity::servings(0.0)),
+    ];
+    let report = ingest::import_log_entries(&mut log, &foods, &records, ConflictPolicy::Skip);
+    assert_eq!(report.added, 2);
+    assert_eq!(report.errors.len(), 2);
+
+    // Pushing the same stamped entry again is a conflict, not a duplicate
+    stamped.quantity = Quantity::servings(3.0);
+    let report = ingest::import_log_entries(&mut log, &foods, std::slice::from_ref(&stamped), ConflictPolicy::Skip);
+    assert_eq!(report.skipped, 1);
+    let report = ingest::import_log_entries(&mut log, &foods, &[stamped], ConflictPolicy::Replace);
+    assert_eq!(report.replaced, 1);
+
+    let entries = &log.get_log(date(5)).unwrap().entries;
+    assert_eq!(entries.len(), 2);
+    assert_close(entries[0].servings, 3.0);
+    assert_eq!(entries[0].meal, Some(MealSlot::Breakfast));
+    assert_eq!(entries[0].timestamp.naive_local(), time);
+}
+
+#[test]
+fn records_are_read_from_csv() {
+    let foods = csv::food_records(
+        "id,name,calories,keywords,protein,grams\n\
+         apple,\"Apple, medium\",95,fruit;Apple,0.5,182\r\n\
+         \n\
+         kale,Kale,33,,2.9,\n",
+    ).unwrap();
+    assert_eq!(foods.len(), 2);
+    assert_eq!(foods[0].name, "Apple, medium");
+    assert_eq!(foods[0].keywords, vec!["fruit".to_string(), "Apple".to_string()]);
+    assert_eq!(foods[0].grams, Some(182.0));
+    assert_eq!(foods[1].grams, None);
+    assert!(foods[1].keywords.is_empty());
+
+    let entries = csv::log_entry_records(
+        "date,food_id,amount,meal,timestamp\n\
+         2024-03-05,apple,,snack,\n\
+         2024-03-05,chicken,85 g,,2024-03-05 12:30:00\n",
+    ).unwrap();
+    assert_eq!(entries[0].quantity, Quantity::servings(1.0));
+    assert_eq!(entries[0].meal, Some(MealSlot::Snack));
+    assert_eq!(entries[1].timestamp, Some(date(5).and_hms_opt(12, 30, 0).unwrap()));
+
+    assert!(csv::food_records("id,name\napple,Apple\n").unwrap_err().contains("calories"));
+    assert!(csv::food_records("id,name,calories\napple,Apple,lots\n").unwrap_err().starts_with("row 2"));
+    assert!(csv::log_entry_records("date,food_id\n\"2024-03-05,apple\n").is_err());
+}
+
+#[test]
+fn records_are_read_from_json() {
+    let foods = json::food_records(r#"[
+        {"id": "apple", "name": "Apple, medium", "calories": 95, "keywords": ["fruit", " Apple", ""], "protein": 0.5, "grams": 182},
+        {"id": "kale", "name": "Kale", "calories": 33, "grams": null, "source": "garden"}
+    ]"#).unwrap();
+    assert_eq!(foods.len(), 2);
+    assert_eq!(foods[0].name, "Apple, medium");
+    assert_eq!(foods[0].keywords, vec!["fruit".to_string(), "Apple".to_string()]);
+    assert_eq!(foods[0].grams, Some(182.0));
+    assert_eq!(foods[1].grams, None);
+    assert!(foods[1].keywords.is_empty());
+
+    let entries = json::log_entry_records(r#"[
+        {"date": "2024-03-05", "food_id": "apple", "meal": "snack"},
+        {"date": "2024-03-05", "food_id": "chicken", "amount": "85 g", "timestamp": "2024-03-05 12:30:00"},
+        {"date": "2024-03-06", "food_id": "apple", "amount": 1.5}
+    ]"#).unwrap();
+    assert_eq!(entries[0].quantity, Quantity::servings(1.0));
+    assert_eq!(entries[0].meal, Some(MealSlot::Snack));
+    assert_eq!(entries[1].timestamp, Some(date(5).and_hms_opt(12, 30, 0).unwrap()));
+    assert_eq!(entries[2].quantity, Quantity::servings(1.5));
+
+    assert!(json::food_records(r#"[{"id": "apple", "name": "Apple"}]"#).unwrap_err().contains("calories"));
+    assert!(json::food_records(r#"[{"id": "a", "name": "A", "calories": 1}, {"id": "b", "name": "B", "calories": "lots"}]"#)
+        .unwrap_err().starts_with("record 2"));
+    assert!(json::food_records(r#"{"id": "apple"}"#).is_err());
+    assert!(json::log_entry_records(r#"[{"date": "05.03.2024", "food_id": "apple"}]"#).unwrap_err().contains("YYYY-MM-DD"));
+    assert!(json::log_entry_records("[{").unwrap_err().starts_with("invalid JSON"));
+}
+
+#[test]
+fn json_records_import_like_csv_records() {
+    let dir = TempDir::new("ingest-json");
+    let mut food_repo = FoodRepository::new(&dir.file("foods.txt")).unwrap();
+    let mut log_repo = LogRepository::new(&dir.file("logs.txt")).unwrap();
+
+    let foods = json::food_records(r#"[{"id": "apple", "name": "Apple", "calories": 95, "grams": 182}]"#).unwrap();
+    assert_eq!(ingest::import_foods(&mut food_repo, &foods, ConflictPolicy::Skip).added, 1);
+    let entries = json::log_entry_records(r#"[{"date": "2024-03-05", "food_id": "apple", "amount": "91 g"}]"#).unwrap();
+    let report = ingest::import_log_entries(&mut log_repo, &food_repo, &entries, ConflictPolicy::Skip);
+
+    assert!(report.is_clean(), "{:?}", report);
+    let log = log_repo.get_log(date(5)).unwrap();
+    assert_close(log.entries[0].servings, 0.5);
+}
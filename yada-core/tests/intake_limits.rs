@@ -0,0 +1,60 @@
+//! Integration tests for alcohol and caffeine tracking and daily limits
+
+// yada-core/tests/intake_limits.rs
+mod common;
+
+use std::collections::HashSet;
+
+use chrono::NaiveDate;
+
+use common::{TempDir, assert_close};
+use yada_core::models::food::{Food, Macros};
+use yada_core::models::intake_limits::{self, IntakeLimits, DEFAULT_CAFFEINE_LIMIT};
+use yada_core::models::log::DailyLog;
+use yada_core::repositories::food_repository::FoodRepository;
+
+#[test]
+fn alcohol_and_caffeine_add_up_in_recipes_and_logs_and_are_saved() {
+    let dir = TempDir::new("intake-limits");
+    let mut repo = FoodRepository::new(&dir.file("foods.txt")).unwrap();
+    let beer = Food::new_basic("beer".into(), "Beer".into(), HashSet::new(), 150.0)
+        .with_macros(Macros::new(1.5, 13.0, 0.0).with_alcohol_and_caffeine(14.0, 0.0));
+    let cola = Food::new_basic("cola".into(), "Cola".into(), HashSet::new(), 140.0)
+        .with_macros(Macros::default().with_alcohol_and_caffeine(0.0, 34.0));
+    repo.add_food(beer).unwrap();
+    repo.add_food(cola).unwrap();
+    repo.add_food(Food::new_composite("mix".into(), "Beer and Cola".into(), HashSet::new(),
+        vec![("beer".into(), 0.5), ("cola".into(), 0.5)])).unwrap();
+    repo.recalculate_composites();
+    assert_close(repo.get_food("mix").unwrap().macros.alcohol, 7.0);
+    assert_close(repo.get_food("mix").unwrap().macros.caffeine, 17.0);
+
+    repo.save().unwrap();
+    let text = std::fs::read_to_string(dir.file("foods.txt")).unwrap();
+    assert!(text.contains("B|cola|Cola||140|caffeine=34\n"), "{}", text);
+    let repo = FoodRepository::new(&dir.file("foods.txt")).unwrap();
+    assert_close(repo.get_food("beer").unwrap().macros.alcohol, 14.0);
+
+    let mut log = DailyLog::new(NaiveDate::from_ymd_opt(2024, 3, 4).unwrap());
+    log.add_entry("beer".into(), 2.0);
+    log.add_entry("mix".into(), 2.0);
+    let totals = log.total_macros(repo.get_foods());
+    assert_close(totals.alcohol, 42.0);
+    assert_close(totals.caffeine, 34.0);
+
+    let warnings = IntakeLimits::default().exceeded(&totals);
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].to_string(), "Alcohol: 42 g is over your daily limit of 28 g");
+    let off = IntakeLimits { alcohol: None, caffeine: Some(30.0) };
+    assert_eq!(off.exceeded(&totals)[0].substance, "Caffeine");
+}
+
+#[test]
+fn limits_can_be_changed_or_turned_off() {
+    assert_eq!(intake_limits::parse_limit(None, DEFAULT_CAFFEINE_LIMIT), Some(400.0));
+    assert_eq!(intake_limits::parse_limit(Some(" 200 "), DEFAULT_CAFFEINE_LIMIT), Some(200.0));
+    assert_eq!(intake_limits::parse_limit(Some("OFF"), DEFAULT_CAFFEINE_LIMIT), None);
+    assert_eq!(intake_limits::parse_limit(Some("0"), DEFAULT_CAFFEINE_LIMIT), None);
+    assert_eq!(intake_limits::parse_limit(Some("-5"), DEFAULT_CAFFEINE_LIMIT), Some(400.0));
+    assert_eq!(intake_limits::parse_limit(Some("lots"), DEFAULT_CAFFEINE_LIMIT), Some(400.0));
+}
@@ -0,0 +1,75 @@
+//! Integration tests for reference checks and repairs
+
+// yada-core/tests/integrity.rs
+mod common;
+
+use chrono::NaiveDate;
+
+use common::{TempDir, assert_close, keywords};
+use yada_core::models::food::Food;
+use yada_core::models::profile::{ActivityLevel, DailyProfile, Gender, UserProfile};
+use yada_core::repositories::food_repository::FoodRepository;
+use yada_core::repositories::integrity::{self, Problem, Repair};
+use yada_core::repositories::log_repository::LogRepository;
+use yada_core::repositories::profile_repository::ProfileRepository;
+
+#[test]
+fn dangling_references_are_found_and_repaired() {
+    let dir = TempDir::new("integrity");
+    let mut foods = FoodRepository::new(&dir.file("foods.txt")).unwrap();
+    let mut logs = LogRepository::new(&dir.file("logs.txt")).unwrap();
+    let mut profiles = ProfileRepository::new(&dir.file("profile.txt")).unwrap();
+    let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+
+    foods.add_food(Food::new_basic("apple".into(), "Apple".into(), keywords(&["fruit"]), 95.0)).unwrap();
+    foods.add_food(Food::new_composite("pie".into(), "Pie".into(), keywords(&[]),
+        vec![("apple".into(), 1.0), ("flour".into(), 2.0)])).unwrap();
+    for id in ["ghost", "apple", "ghost"] {
+        logs.get_log_mut(date).add_entry(id.into(), 1.0);
+    }
+    let mut profile = UserProfile::new(Gender::Female, 165.0, NaiveDate::from_ymd_opt(1990, 1, 1).unwrap());
+    for (day, weight) in [(1, -3.0), (2, 700.0), (3, 60.0)] {
+        profile.add_or_update_daily_profile(DailyProfile {
+            date: NaiveDate::from_ymd_opt(2024, 3, day).unwrap(),
+            weight,
+            activity_level: ActivityLevel::Sedentary,
+            carried_from: None,
+        });
+    }
+    profiles.set_profile(profile);
+
+    let problems = integrity::check(&foods, &logs, &profiles);
+    assert_eq!(problems.len(), 5);
+    assert_eq!(problems[0], Problem::MissingLoggedFood { date, index: 0, food_id: "ghost".into() });
+    assert_eq!(problems[2], Problem::MissingComponent { food_id: "pie".into(), component: "flour".into() });
+    assert_eq!(problems[2].to_string(), "Composite food 'pie' uses unknown food 'flour'");
+
+    // Invalid repairs are rejected before anything changes
+    let invalid = [
+        vec![(problems[0].clone(), Repair::Replace("nothing".into()))],
+        vec![(problems[0].clone(), Repair::SetWeight(70.0))],
+        vec![(problems[2].clone(), Repair::Replace("pie".into()))],
+        vec![(problems[3].clone(), Repair::SetWeight(0.0))],
+    ];
+    for repairs in invalid {
+        assert!(integrity::apply(&repairs, &mut foods, &mut logs, &mut profiles).is_err());
+    }
+    assert_eq!(integrity::check(&foods, &logs, &profiles), problems);
+
+    let repairs = vec![
+        (problems[0].clone(), Repair::Remove),
+        (problems[1].clone(), Repair::Replace("apple".into())),
+        (problems[2].clone(), Repair::Remove),
+        (problems[3].clone(), Repair::SetWeight(62.5)),
+        (problems[4].clone(), Repair::Remove),
+    ];
+    assert_eq!(integrity::apply(&repairs, &mut foods, &mut logs, &mut profiles).unwrap(), 5);
+    assert!(integrity::check(&foods, &logs, &profiles).is_empty());
+
+    let ids: Vec<&str> = logs.get_log(date).unwrap().entries.iter().map(|e| e.food_id.as_str()).collect();
+    assert_eq!(ids, ["apple", "apple"]);
+    assert_close(foods.get_food("pie").unwrap().calories_per_serving, 95.0);
+    let dailies = &profiles.get_profile().unwrap().daily_profiles;
+    assert_eq!(dailies.len(), 2);
+    assert!(dailies.iter().any(|daily| daily.weight == 62.5));
+}
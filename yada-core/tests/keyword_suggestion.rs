@@ -0,0 +1,49 @@
+//! Integration tests for keyword suggestions for new foods
+
+// yada-core/tests/keyword_suggestion.rs
+mod common;
+
+use common::{TempDir, keywords};
+use yada_core::models::food::Food;
+use yada_core::models::keyword_suggestion::{self, MAX_SUGGESTIONS};
+use yada_core::repositories::food_repository::FoodRepository;
+
+fn foods() -> Vec<Food> {
+    [
+        ("yogurt", "Plain Yogurt", &["yogurt", "dairy", "protein"][..]),
+        ("skyr", "Vanilla Skyr Yogurt", &["dairy", "protein", "sweet"][..]),
+        ("blueberry", "Blueberry", &["blueberry", "fruit"][..]),
+        ("tomato", "Tomato", &["tomato", "vegetable"][..]),
+        ("oj", "Orange Juice", &["drink", "fruit"][..]),
+    ]
+    .into_iter()
+    .map(|(id, name, words)| Food::new_basic(id.into(), name.into(), keywords(words), 50.0))
+    .collect()
+}
+
+#[test]
+fn name_words_and_related_foods_suggest_existing_keywords() {
+    let foods = foods();
+    assert_eq!(keyword_suggestion::suggest("Greek Yogurt", &foods), vec!["yogurt", "dairy", "protein", "sweet"]);
+    assert_eq!(keyword_suggestion::suggest("Frozen Blueberries", &foods), vec!["blueberry", "fruit"]);
+    assert_eq!(keyword_suggestion::suggest("Cherry Tomatoes", &foods), vec!["tomato", "vegetable"]);
+    assert_eq!(keyword_suggestion::suggest("Fruit Tea", &foods), vec!["fruit"], "keywords match name words too");
+    assert!(keyword_suggestion::suggest("Rye Bread", &foods).is_empty());
+    assert!(keyword_suggestion::suggest("Greek Yogurt", &[]).is_empty());
+
+    let vocabulary = keyword_suggestion::vocabulary(&foods);
+    assert_eq!((vocabulary["dairy"], vocabulary["fruit"], vocabulary["sweet"]), (2, 2, 1));
+}
+
+#[test]
+fn suggestions_come_from_the_repository_and_are_limited() {
+    let dir = TempDir::new("keyword-suggestion");
+    let mut repo = FoodRepository::new(&dir.file("foods.txt")).unwrap();
+    let many: Vec<String> = (0..12).map(|n| format!("kw{:02}", n)).collect();
+    let many: Vec<&str> = many.iter().map(String::as_str).collect();
+    repo.add_food(Food::new_basic("soup".into(), "Lentil Soup".into(), keywords(&many), 200.0)).unwrap();
+
+    let suggested = repo.suggest_keywords("Tomato Soup");
+    assert_eq!(suggested.len(), MAX_SUGGESTIONS);
+    assert_eq!(suggested[0], "kw00");
+}
@@ -0,0 +1,105 @@
+//! Integration tests for load reports and strict loading
+
+// yada-core/tests/load_report.rs
+mod common;
+
+use std::fs;
+use std::io;
+
+use common::TempDir;
+use yada_core::repositories::food_repository::FoodRepository;
+use yada_core::repositories::load_report::{IssueKind, LoadMode, LoadReport};
+use yada_core::repositories::log_repository::LogRepository;
+use yada_core::repositories::profile_repository::ProfileRepository;
+
+#[test]
+fn food_load_reports_skipped_lines_and_defaulted_numbers_by_line() {
+    let dir = TempDir::new("load-report-foods");
+    let path = dir.file("foods.txt");
+    fs::write(&path, "#yada:foods:v1\n\
+        B|oats|Oats|grain|150|protein=5\n\
+        B|rice|Rice|grain|lots|protein=x\n\
+        X|odd|Odd|thing|1\n\
+        B|short\n").unwrap();
+
+    let repo = FoodRepository::new(&path).unwrap();
+    let report = repo.load_report();
+
+    assert_eq!(repo.get_food("rice").unwrap().calories_per_serving, 0.0);
+    assert_eq!(report.skipped_count(), 2);
+    assert_eq!(report.defaulted_count(), 2);
+    assert_eq!(repo.skipped_line_count(), 2);
+    let lines: Vec<(usize, IssueKind)> = report.issues().iter().map(|issue| (issue.line, issue.kind)).collect();
+    assert_eq!(lines, vec![
+        (3, IssueKind::Defaulted),
+        (3, IssueKind::Defaulted),
+        (4, IssueKind::Skipped),
+        (5, IssueKind::Skipped),
+    ]);
+    assert_eq!(report.issues()[0].to_string(), "Line 3: calories 'lots' is not a number; using 0");
+    assert_eq!(report.issues()[2].to_string(), "Line 4: skipped (unknown record type 'X')");
+    assert_eq!(report.summary(), "2 lines skipped, 2 values defaulted");
+}
+
+#[test]
+fn log_and_profile_loads_report_their_problems() {
+    let dir = TempDir::new("load-report-logs");
+    let logs = dir.file("logs.txt");
+    fs::write(&logs, "#yada:logs:v1\n\
+        2024-03-05|oats|two|2024-03-05T08:00:00\n\
+        yesterday|oats|1|2024-03-05T08:00:00\n").unwrap();
+    let profile = dir.file("profile.txt");
+    fs::write(&profile, "#yada:profile:v1\n\
+        PROFILE|M|180|1990-01-01|mifflin_st_jeor\n\
+        DAILY|2024-03-05|80|Q\n").unwrap();
+
+    let log_report = LogRepository::new(&logs).unwrap().load_report().clone();
+    assert_eq!(log_report.summary(), "1 line skipped, 1 value defaulted");
+    assert_eq!(log_report.issues()[1].to_string(), "Line 3: skipped (invalid date 'yesterday')");
+
+    let profile_report = ProfileRepository::new(&profile).unwrap().load_report().clone();
+    assert_eq!(profile_report.issues()[0].to_string(), "Line 3: activity level 'Q' is unknown; using sedentary");
+}
+
+#[test]
+fn clean_files_have_clean_reports() {
+    let dir = TempDir::new("load-report-clean");
+    let path = dir.file("foods.txt");
+    fs::write(&path, "#yada:foods:v1\nB|oats|Oats|grain|150\n").unwrap();
+
+    let repo = FoodRepository::new(&path).unwrap();
+    assert!(repo.load_report().is_clean());
+    assert_eq!(repo.load_report().summary(), "no problems");
+    assert!(LoadMode::Strict.check(&path, repo.load_report()).is_ok());
+}
+
+#[test]
+fn strict_mode_turns_any_problem_into_an_error() {
+    let mut report = LoadReport::new();
+    report.skip(7, "too few fields");
+
+    assert!(LoadMode::Lenient.check("foods.txt", &report).is_ok());
+    let error = LoadMode::Strict.check("foods.txt", &report).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert!(error.to_string().starts_with("foods.txt is damaged (1 line skipped)"));
+    assert!(error.to_string().contains("Line 7: skipped (too few fields)"));
+}
+
+#[test]
+fn long_reports_list_the_first_problems_and_count_the_rest() {
+    let mut report = LoadReport::new();
+    for line in 1..=8 {
+        report.skip(line, "too few fields");
+    }
+
+    let described = report.describe();
+    assert_eq!(described.len(), 6);
+    assert_eq!(described[5], "... and 3 more");
+}
+
+#[test]
+fn load_modes_parse_on_and_off() {
+    assert_eq!(LoadMode::parse("on"), Some(LoadMode::Strict));
+    assert_eq!(LoadMode::parse(" OFF "), Some(LoadMode::Lenient));
+    assert_eq!(LoadMode::parse("maybe"), None);
+}
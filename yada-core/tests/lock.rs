@@ -0,0 +1,45 @@
+//! Integration tests for the data directory lock
+
+// yada-core/tests/lock.rs
+mod common;
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process;
+
+use common::TempDir;
+use yada_core::repositories::lock::{self, DataLock, LOCK_FILE};
+
+#[test]
+fn a_second_lock_is_refused_until_the_first_is_dropped() {
+    let dir = TempDir::new("lock-held");
+    let data_dir = dir.path();
+
+    let first = DataLock::acquire(data_dir).unwrap();
+    assert_eq!(lock::holder(data_dir), Some(process::id()));
+    let error = DataLock::acquire(data_dir).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::ResourceBusy);
+    assert!(error.to_string().contains(&format!("process {}", process::id())));
+
+    let lock_path = first.path().to_path_buf();
+    let files: Vec<_> = fs::read_dir(data_dir).unwrap().map(|entry| entry.unwrap().file_name()).collect();
+    assert_eq!(files, [LOCK_FILE]);
+    drop(first);
+    assert!(!lock_path.exists());
+    assert!(DataLock::acquire(data_dir).is_ok());
+}
+
+#[test]
+fn locks_left_by_finished_processes_are_taken_over() {
+    if !Path::new("/proc/self").exists() {
+        return;
+    }
+    let dir = TempDir::new("lock-stale");
+    let data_dir = dir.path();
+    // Far above any PID the kernel hands out by default
+    fs::write(data_dir.join(LOCK_FILE), "4194305\n").unwrap();
+
+    let _lock = DataLock::acquire(data_dir).unwrap();
+    assert_eq!(lock::holder(data_dir), Some(process::id()));
+}
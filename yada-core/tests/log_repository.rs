@@ -0,0 +1,243 @@
+//! Integration tests for daily logs and their totals
+
+// yada-core/tests/log_repository.rs
+mod common;
+
+use chrono::{NaiveDate, NaiveTime, Timelike};
+
+use common::{TempDir, assert_close, basic_food};
+use yada_core::models::food::Macros;
+use yada_core::models::log::{self, DailyLog, DayNote, FoodEntry};
+use yada_core::models::quantity::Quantity;
+use yada_core::models::serving::ServingUnit;
+use yada_core::repositories::food_repository::FoodRepository;
+use yada_core::repositories::log_repository::LogRepository;
+
+fn date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 3, day).unwrap()
+}
+
+#[test]
+fn totals_scale_with_servings_and_ignore_unknown_foods() {
+    let dir = TempDir::new("log-totals");
+    let mut foods = FoodRepository::new(&dir.file("foods.txt")).unwrap();
+    foods.add_food(basic_food("egg", 70.0, Macros::new(6.0, 0.5, 5.0), 50.0)).unwrap();
+
+    let mut log = DailyLog::new(date(1));
+    log.add_entry("egg".into(), 2.5);
+    log.add_entry("missing".into(), 1.0);
+
+    assert_close(log.total_calories(foods.get_foods()), 175.0);
+    let macros = log.total_macros(foods.get_foods());
+    assert_close(macros.protein, 15.0);
+    assert_close(macros.carbs, 1.25);
+    assert_close(macros.fat, 12.5);
+}
+
+#[test]
+fn save_and_load_round_trip_keeps_entries_per_date() {
+    let dir = TempDir::new("log-round-trip");
+    let path = dir.file("logs.txt");
+
+    let mut repo = LogRepository::new(&path).unwrap();
+    repo.get_log_mut(date(1)).add_entry("egg".into(), 2.0);
+    repo.get_log_mut(date(2)).add_entry("toast".into(), 1.0);
+    repo.get_log_mut(date(2)).add_entry("egg".into(), 0.5);
+    assert!(repo.is_dirty());
+    repo.save().unwrap();
+    assert!(!repo.is_dirty());
+
+    let reloaded = LogRepository::new(&path).unwrap();
+    assert_eq!(reloaded.get_log(date(1)).unwrap().entries.len(), 1);
+    let second = reloaded.get_log(date(2)).unwrap();
+    assert_eq!(second.entries.len(), 2);
+    assert_eq!(second.entries[1].food_id, "egg");
+    assert_close(second.entries[1].servings, 0.5);
+    assert!(reloaded.get_log(date(3)).is_none());
+}
+
+#[test]
+fn logs_between_two_dates_include_both_ends_in_date_order() {
+    let dir = TempDir::new("log-range");
+    let mut repo = LogRepository::new(&dir.file("logs.txt")).unwrap();
+    for day in [9, 1, 5, 3, 7] {
+        repo.get_log_mut(date(day)).add_entry("egg".into(), 1.0);
+    }
+
+    let dates = |start: u32, end: u32| repo.get_logs_between(date(start), date(end))
+        .iter()
+        .map(|log| log.date)
+        .collect::<Vec<_>>();
+    assert_eq!(dates(3, 7), [date(3), date(5), date(7)]);
+    assert_eq!(dates(2, 2), []);
+    assert_eq!(dates(9, 9), [date(9)]);
+    assert!(dates(7, 3).is_empty());
+}
+
+#[test]
+fn analytics_queries_cover_ranges_and_single_foods() {
+    let dir = TempDir::new("log-analytics");
+    let mut foods = FoodRepository::new(&dir.file("foods.txt")).unwrap();
+    foods.add_food(basic_food("egg", 70.0, Macros::new(6.0, 0.5, 5.0), 50.0)).unwrap();
+    foods.add_food(basic_food("toast", 80.0, Macros::new(3.0, 15.0, 1.0), 30.0)).unwrap();
+
+    let mut repo = LogRepository::new(&dir.file("logs.txt")).unwrap();
+    repo.get_log_mut(date(1)).add_entry("egg".into(), 2.0);
+    repo.get_log_mut(date(2)).add_entry("toast".into(), 1.0);
+    repo.get_log_mut(date(2)).add_entry("egg".into(), 1.0);
+    repo.get_log_mut(date(3)).note = DayNote { text: Some("Fasting".into()), ..DayNote::default() };
+    repo.get_log_mut(date(4)).add_entry("egg".into(), 0.5);
+
+    assert_eq!(repo.days_logged_in(date(1), date(3)), [date(1), date(2)]);
+    assert_close(repo.total_calories_between(date(2), date(4), foods.get_foods()), 185.0);
+    assert_close(repo.total_calories_between(date(5), date(9), foods.get_foods()), 0.0);
+
+    let eggs = repo.entries_for_food("egg");
+    assert_eq!(eggs.iter().map(|(date, _)| *date).collect::<Vec<_>>(), [date(1), date(2), date(4)]);
+    assert_close(eggs[2].1.servings, 0.5);
+    assert!(repo.entries_for_food("milk").is_empty());
+}
+
+#[test]
+fn entry_ids_survive_a_round_trip_and_missing_or_repeated_ids_are_replaced() {
+    let dir = TempDir::new("log-entry-ids");
+    let path = dir.file("logs.txt");
+    std::fs::write(&path, "#yada:logs:v1\n\
+        2024-03-01|egg|1|2024-03-01T07:00:00|id=500\n\
+        2024-03-01|toast|1|2024-03-01T07:05:00|id=500\n\
+        2024-03-02|egg|1|2024-03-02T07:00:00\n").unwrap();
+
+    let mut repo = LogRepository::new(&path).unwrap();
+    let first = repo.get_log(date(1)).unwrap().entries[0].entry_id;
+    let repeated = repo.get_log(date(1)).unwrap().entries[1].entry_id;
+    let missing = repo.get_log(date(2)).unwrap().entries[0].entry_id;
+    assert_eq!(first, 500);
+    assert!(repeated > 500 && missing > 500 && repeated != missing);
+
+    // New entries never reuse a saved ID
+    repo.get_log_mut(date(3)).add_entry("egg".into(), 1.0);
+    let added = repo.get_log(date(3)).unwrap().entries[0].entry_id;
+    assert!(![first, repeated, missing].contains(&added));
+
+    repo.save().unwrap();
+    let reloaded = LogRepository::new(&path).unwrap();
+    let ids: Vec<u64> = [date(1), date(2), date(3)].iter()
+        .flat_map(|day| reloaded.get_log(*day).unwrap().entries.iter().map(|entry| entry.entry_id))
+        .collect();
+    assert_eq!(ids, [first, repeated, missing, added]);
+}
+
+#[test]
+fn entries_are_kept_in_time_order() {
+    let dir = TempDir::new("log-time-order");
+    let path = dir.file("logs.txt");
+    std::fs::write(&path, "#yada:logs:v1\n\
+        2024-03-01|lunch|1|2024-03-01T12:30:00\n\
+        2024-03-01|coffee|1|2024-03-01T07:15:00\n\
+        2024-03-01|toast|1|2024-03-01T07:15:00\n").unwrap();
+
+    let mut repo = LogRepository::new(&path).unwrap();
+    let ids = |repo: &LogRepository| -> Vec<String> {
+        repo.get_log(date(1)).unwrap().entries.iter().map(|entry| entry.food_id.clone()).collect()
+    };
+    assert_eq!(ids(&repo), ["coffee", "toast", "lunch"]);
+    // Times are read back in local time, as they were written
+    assert_eq!(repo.get_log(date(1)).unwrap().entries[0].timestamp.hour(), 7);
+
+    let snack_time = NaiveTime::from_hms_opt(10, 0, 0).unwrap();
+    let mut snack = FoodEntry::new("apple".into(), Quantity::servings(1.0), 1.0);
+    snack.timestamp = log::local_timestamp(date(1), snack_time).unwrap();
+    let index = repo.get_log_mut(date(1)).insert_entry(snack);
+    assert_eq!(index, 2);
+    assert_eq!(ids(&repo), ["coffee", "toast", "apple", "lunch"]);
+}
+
+#[test]
+fn quick_log_ranks_foods_by_uses_and_by_latest_entry() {
+    let dir = TempDir::new("log-quick");
+    let mut repo = LogRepository::new(&dir.file("logs.txt")).unwrap();
+    for day in 1..=3 {
+        repo.get_log_mut(date(day)).add_entry("oats".into(), 1.0);
+    }
+    repo.get_log_mut(date(2)).add_entry("apple".into(), 1.0);
+    repo.get_log_mut(date(4)).add_entry("chicken".into(), 0.85);
+    repo.get_log_mut(date(4)).entries[0].quantity = Quantity::in_unit(85.0, ServingUnit::Gram);
+    repo.get_log_mut(date(1)).add_entry("apple".into(), 2.0);
+    repo.get_log_mut(date(5)).add_entry("tea".into(), 1.0);
+
+    let most_used = repo.most_used_foods(3);
+    let ids: Vec<&str> = most_used.iter().map(|food| food.food_id.as_str()).collect();
+    assert_eq!(ids, vec!["oats", "apple", "tea"]);
+    assert_eq!(most_used[1].uses, 2);
+    assert_eq!(most_used[1].last_date, date(2));
+    assert_eq!(most_used[1].last_quantity, Quantity::servings(1.0));
+
+    let recent = repo.recent_foods(10);
+    let ids: Vec<&str> = recent.iter().map(|food| food.food_id.as_str()).collect();
+    assert_eq!(ids, vec!["tea", "chicken", "oats", "apple"]);
+    assert_eq!(recent[1].last_quantity, Quantity::in_unit(85.0, ServingUnit::Gram));
+}
+
+#[test]
+fn calorie_snapshots_keep_past_totals_when_a_food_changes() {
+    let dir = TempDir::new("log-calorie-snapshots");
+    let path = dir.file("logs.txt");
+    let mut foods = FoodRepository::new(&dir.file("foods.txt")).unwrap();
+    foods.add_food(basic_food("egg", 70.0, Macros::new(6.0, 0.5, 5.0), 50.0)).unwrap();
+
+    // One entry from before snapshots existed, one logged with its calories
+    std::fs::write(&path, "#yada:logs:v1\n\
+        2024-03-01|egg|2|2024-03-01T07:00:00|id=1\n\
+        2024-03-02|egg|1|2024-03-02T07:00:00|kcal=75|id=2\n").unwrap();
+    let mut repo = LogRepository::new(&path).unwrap();
+    assert_eq!(repo.get_log(date(1)).unwrap().entries[0].calories, None);
+    assert_close(repo.get_log(date(2)).unwrap().total_calories(foods.get_foods()), 75.0);
+
+    assert_eq!(repo.backfill_calories(foods.get_foods()), 1);
+    assert!(repo.is_dirty());
+    assert_eq!(repo.backfill_calories(foods.get_foods()), 0);
+    repo.save().unwrap();
+    assert!(std::fs::read_to_string(&path).unwrap().contains("|kcal=140|id=1\n"));
+
+    // Editing the food changes neither day
+    foods.update_food(basic_food("egg", 90.0, Macros::new(6.0, 0.5, 5.0), 50.0)).unwrap();
+    let reloaded = LogRepository::new(&path).unwrap();
+    assert_close(reloaded.get_log(date(1)).unwrap().total_calories(foods.get_foods()), 140.0);
+    assert_close(reloaded.get_log(date(2)).unwrap().total_calories(foods.get_foods()), 75.0);
+    let entry = &reloaded.get_log(date(2)).unwrap().entries[0];
+    assert!(entry.calories_changed(foods.get_food("egg")));
+
+    // Nor does deleting it
+    foods.remove_food("egg").unwrap();
+    assert_close(reloaded.get_log(date(1)).unwrap().total_calories(foods.get_foods()), 140.0);
+    assert_close(entry.logged_calories(None), 75.0);
+    assert!(entry.calories_changed(None));
+}
+
+#[test]
+fn day_notes_survive_a_round_trip_without_counting_as_logged_food() {
+    let dir = TempDir::new("log-day-notes");
+    let path = dir.file("logs.txt");
+
+    let mut repo = LogRepository::new(&path).unwrap();
+    repo.get_log_mut(date(1)).add_entry("egg".into(), 1.0);
+    repo.get_log_mut(date(1)).note = DayNote { text: Some("Tired | hungry, late=yes".into()), mood: Some(2), energy: Some(1) };
+    repo.get_log_mut(date(2)).note = DayNote { text: None, mood: Some(5), energy: None };
+    repo.save().unwrap();
+
+    let reloaded = LogRepository::new(&path).unwrap();
+    assert_eq!(reloaded.get_log(date(1)).unwrap().note.text.as_deref(), Some("Tired | hungry, late=yes"));
+    assert_eq!(reloaded.get_log(date(1)).unwrap().note.energy, Some(1));
+    let second = reloaded.get_log(date(2)).unwrap();
+    assert!(second.entries.is_empty());
+    assert_eq!(second.note, DayNote { text: None, mood: Some(5), energy: None });
+
+    // Ratings out of range are dropped; a note without a date is skipped
+    std::fs::write(&path, "#yada:logs:v1\nNOTE|2024-03-01|mood=9|energy=3\nNOTE|soon|mood=1\n").unwrap();
+    let reloaded = LogRepository::new(&path).unwrap();
+    assert_eq!(reloaded.get_log(date(1)).unwrap().note, DayNote { text: None, mood: None, energy: Some(3) });
+    assert_eq!(reloaded.skipped_line_count(), 1);
+
+    assert_eq!(log::parse_rating(" 4 "), Ok(4));
+    assert!(log::parse_rating("0").is_err());
+}
@@ -0,0 +1,114 @@
+//! Integration tests for meal slots, meal plans, and plan compliance
+
+// yada-core/tests/meal_plan.rs
+mod common;
+
+use std::collections::HashMap;
+
+use chrono::{NaiveDate, NaiveTime};
+
+use common::{TempDir, assert_close, basic_food};
+use yada_core::commands::log_commands::AddLogEntryCommand;
+use yada_core::commands::meal_plan_commands::SetMealPlanCommand;
+use yada_core::models::command_manager::CommandManager;
+use yada_core::models::food::{Food, Macros};
+use yada_core::models::meal::{self, MealPlan, MealSlot};
+use yada_core::repositories::log_repository::LogRepository;
+use yada_core::repositories::meal_plan_repository::MealPlanRepository;
+
+fn date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 3, day).unwrap()
+}
+
+fn food_db() -> HashMap<String, Food> {
+    [basic_food("oats", 150.0, Macros::new(5.0, 27.0, 3.0), 40.0),
+     basic_food("pasta", 400.0, Macros::new(14.0, 80.0, 2.0), 100.0)]
+        .into_iter()
+        .map(|food| (food.id.clone(), food))
+        .collect()
+}
+
+#[test]
+fn slots_parse_and_follow_the_time_of_day() {
+    assert_eq!(MealSlot::parse("Lunch"), Some(MealSlot::Lunch));
+    assert_eq!(MealSlot::parse("brunch"), None);
+    assert_eq!(MealSlot::for_time(NaiveTime::from_hms_opt(7, 30, 0).unwrap()), MealSlot::Breakfast);
+    assert_eq!(MealSlot::for_time(NaiveTime::from_hms_opt(19, 0, 0).unwrap()), MealSlot::Dinner);
+    assert_eq!(MealSlot::for_time(NaiveTime::from_hms_opt(23, 0, 0).unwrap()), MealSlot::Snack);
+}
+
+#[test]
+fn compliance_compares_planned_and_logged_calories_per_slot() {
+    let foods = food_db();
+    let dir = TempDir::new("meal-compliance");
+    let mut log_repo = LogRepository::new(&dir.file("logs.txt")).unwrap();
+    let mut manager = CommandManager::new(10);
+    for (food, servings, slot) in [("oats", 1.0, Some(MealSlot::Breakfast)), ("pasta", 2.0, Some(MealSlot::Dinner)), ("oats", 0.5, None)] {
+        let mut command = AddLogEntryCommand::new(&mut log_repo, date(5), food.into(), servings);
+        if let Some(slot) = slot {
+            command = command.with_meal(slot);
+        }
+        manager.execute_command(Box::new(command)).unwrap();
+    }
+
+    let mut plan = MealPlan::new(date(5));
+    plan.add_item(MealSlot::Breakfast, "oats".into(), 1.0);
+    plan.add_item(MealSlot::Lunch, "pasta".into(), 1.0);
+    plan.add_item(MealSlot::Dinner, "pasta".into(), 1.0);
+
+    let rows = meal::meal_compliance(&plan, log_repo.get_log(date(5)), &foods);
+    let slots: Vec<Option<MealSlot>> = rows.iter().map(|row| row.slot).collect();
+    assert_eq!(slots, vec![Some(MealSlot::Breakfast), Some(MealSlot::Lunch), Some(MealSlot::Dinner), None]);
+    assert_close(rows[0].difference(), 0.0);
+    assert_close(rows[1].difference(), -400.0);
+    assert_close(rows[2].difference(), 400.0);
+    assert_close(rows[3].logged, 75.0);
+}
+
+#[test]
+fn meals_and_plans_survive_a_round_trip() {
+    let dir = TempDir::new("meal-round-trip");
+    let log_path = dir.file("logs.txt");
+    let mut log_repo = LogRepository::new(&log_path).unwrap();
+    log_repo.get_log_mut(date(5)).add_entry("oats".into(), 1.0);
+    log_repo.get_log_mut(date(5)).entries[0].meal = Some(MealSlot::Breakfast);
+    log_repo.get_log_mut(date(5)).add_entry("pasta".into(), 1.0);
+    log_repo.save().unwrap();
+
+    let reloaded = LogRepository::new(&log_path).unwrap();
+    let entries = &reloaded.get_log(date(5)).unwrap().entries;
+    assert_eq!(entries[0].meal, Some(MealSlot::Breakfast));
+    assert_eq!(entries[1].meal, None);
+
+    let plan_path = dir.file("meal_plans.txt");
+    let mut plan_repo = MealPlanRepository::new(&plan_path).unwrap();
+    let mut plan = MealPlan::new(date(5));
+    plan.add_item(MealSlot::Snack, "trail|mix".into(), 0.5);
+    plan_repo.set_plan(date(5), Some(plan.clone()));
+    plan_repo.save().unwrap();
+
+    let contents = std::fs::read_to_string(&plan_path).unwrap();
+    assert!(contents.starts_with("#yada:meal_plans:v1\n"));
+    let reloaded = MealPlanRepository::new(&plan_path).unwrap();
+    assert_eq!(reloaded.get_plan(date(5)), Some(&plan));
+    assert_eq!(reloaded.skipped_line_count(), 0);
+}
+
+#[test]
+fn replacing_a_plan_can_be_undone() {
+    let dir = TempDir::new("meal-plan-undo");
+    let mut repo = MealPlanRepository::new(&dir.file("meal_plans.txt")).unwrap();
+    let mut manager = CommandManager::new(10);
+
+    let mut plan = MealPlan::new(date(5));
+    plan.add_item(MealSlot::Lunch, "pasta".into(), 1.0);
+    manager.execute_command(Box::new(SetMealPlanCommand::new(&mut repo, date(5), Some(plan.clone())))).unwrap();
+    manager.execute_command(Box::new(SetMealPlanCommand::new(&mut repo, date(5), None))).unwrap();
+    assert!(repo.get_plan(date(5)).is_none());
+
+    manager.undo_last_command().unwrap();
+    assert_eq!(repo.get_plan(date(5)), Some(&plan));
+    manager.undo_last_command().unwrap();
+    assert!(repo.get_plan(date(5)).is_none());
+    assert!(repo.is_dirty());
+}
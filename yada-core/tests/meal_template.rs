@@ -0,0 +1,109 @@
+//! Integration tests for meal templates and logging them as one command
+
+// yada-core/tests/meal_template.rs
+mod common;
+
+use chrono::NaiveDate;
+
+use common::{TempDir, assert_close, basic_food};
+use yada_core::commands::log_commands::AddLogEntryCommand;
+use yada_core::commands::macro_command::MacroCommand;
+use yada_core::commands::profile_commands::UpdateDailyProfileCommand;
+use yada_core::models::command::CommandType;
+use yada_core::models::command_manager::CommandManager;
+use yada_core::models::food::Macros;
+use yada_core::models::meal::MealSlot;
+use yada_core::models::meal_template::MealTemplate;
+use yada_core::models::profile::{ActivityLevel, DailyProfile};
+use yada_core::repositories::log_repository::LogRepository;
+use yada_core::repositories::meal_template_repository::MealTemplateRepository;
+use yada_core::repositories::profile_repository::ProfileRepository;
+
+fn breakfast() -> MealTemplate {
+    let mut template = MealTemplate::new("Monday breakfast".to_string(), Some(MealSlot::Breakfast));
+    template.add_item("oatmeal".to_string(), 1.0);
+    template.add_item("milk".to_string(), 0.5);
+    template.add_item("banana".to_string(), 1.0);
+    template
+}
+
+#[test]
+fn templates_survive_a_round_trip_sorted_by_name() {
+    let dir = TempDir::new("meal-templates");
+    let path = dir.file("meal_templates.txt");
+
+    let mut repo = MealTemplateRepository::new(&path).unwrap();
+    assert!(repo.save_template(breakfast()).unwrap().is_none());
+    let mut snack = MealTemplate::new("after | gym".to_string(), None);
+    snack.add_item("shake".to_string(), 2.0);
+    repo.save_template(snack).unwrap();
+    assert_eq!(repo.save_template(MealTemplate::new("Empty".to_string(), None)).unwrap_err(),
+               "A meal template needs at least one food");
+    assert_eq!(repo.templates().iter().map(|template| template.name.as_str()).collect::<Vec<_>>(),
+               ["after | gym", "Monday breakfast"]);
+
+    let mut foods = std::collections::HashMap::new();
+    for (id, calories) in [("oatmeal", 150.0), ("milk", 120.0), ("banana", 105.0)] {
+        foods.insert(id.to_string(), basic_food(id, calories, Macros::default(), 100.0));
+    }
+    assert_close(repo.get_template("monday BREAKFAST").unwrap().total_calories(&foods), 315.0);
+
+    repo.save().unwrap();
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert!(contents.contains("after \\| gym|-|shake|2\nMonday breakfast|breakfast|oatmeal|1\n"));
+    let mut reloaded = MealTemplateRepository::new(&path).unwrap();
+    assert_eq!(reloaded.templates(), repo.templates());
+    assert_eq!(reloaded.skipped_line_count(), 0);
+    assert!(reloaded.food_ids().contains("shake"));
+
+    // Saving under an existing name replaces the template
+    let mut lighter = MealTemplate::new("MONDAY breakfast".to_string(), None);
+    lighter.add_item("banana".to_string(), 1.0);
+    assert_eq!(reloaded.save_template(lighter).unwrap().unwrap(), breakfast());
+    assert_eq!(reloaded.templates().len(), 2);
+    assert!(reloaded.remove_template("after | gym").is_some());
+    assert!(reloaded.remove_template("after | gym").is_none());
+
+    std::fs::write(&path, format!("{}Bad|brunch|toast|1\nBad|-|toast|0\n", contents)).unwrap();
+    assert_eq!(MealTemplateRepository::new(&path).unwrap().skipped_line_count(), 2);
+}
+
+#[test]
+fn a_logged_template_is_undone_as_one_command() {
+    let dir = TempDir::new("meal-template-log");
+    let mut logs = LogRepository::new(&dir.file("logs.txt")).unwrap();
+    let mut manager = CommandManager::new(10);
+    let date = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+
+    let mut command = MacroCommand::new(CommandType::AddLog, "Log meal template 'Monday breakfast'".to_string());
+    for item in breakfast().items {
+        command = command.with_command(Box::new(
+            AddLogEntryCommand::new(&mut logs, date, item.food_id, item.servings).with_meal(MealSlot::Breakfast),
+        ));
+    }
+    assert_eq!(command.len(), 3);
+    manager.execute_command(Box::new(command)).unwrap();
+    assert_eq!(logs.get_log(date).unwrap().entries.len(), 3);
+    assert_eq!(manager.get_undo_stack_size(), 1);
+
+    manager.undo_last_command().unwrap();
+    assert!(logs.get_log(date).unwrap().entries.is_empty());
+}
+
+#[test]
+fn a_failing_part_rolls_back_the_parts_before_it() {
+    let dir = TempDir::new("macro-rollback");
+    let mut logs = LogRepository::new(&dir.file("logs.txt")).unwrap();
+    let mut profiles = ProfileRepository::new(&dir.file("profile.txt")).unwrap();
+    let date = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+
+    // Without a profile there is no daily profile to update, so the second part fails
+    let weigh_in = DailyProfile { date, weight: 80.0, activity_level: ActivityLevel::Sedentary, carried_from: None };
+    let command = MacroCommand::new(CommandType::Other("Test".to_string()), "Log and weigh in".to_string())
+        .with_command(Box::new(AddLogEntryCommand::new(&mut logs, date, "banana".to_string(), 1.0)))
+        .with_command(Box::new(UpdateDailyProfileCommand::new(&mut profiles, weigh_in)));
+    let mut manager = CommandManager::new(10);
+    assert_eq!(manager.execute_command(Box::new(command)).unwrap_err(), "No user profile exists");
+    assert!(logs.get_log(date).is_none_or(|log| log.entries.is_empty()));
+    assert!(!manager.has_commands_to_undo());
+}
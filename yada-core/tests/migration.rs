@@ -0,0 +1,114 @@
+//! Integration tests for the canonical file format and legacy migration
+
+// yada-core/tests/migration.rs
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use common::TempDir;
+use yada_core::repositories::food_repository::FoodRepository;
+use yada_core::repositories::{format, schema};
+use yada_core::repositories::migration::{MigrationStatus, file_format_version, migrate_data_files};
+
+/// Writes legacy (headerless, unsorted) data files and returns their paths
+fn legacy_files(dir: &TempDir) -> (String, String, String) {
+    let foods = dir.file("foods.txt");
+    let logs = dir.file("logs.txt");
+    let profile = dir.file("profile.txt");
+    fs::write(&foods, "B|milk|Milk|milk,dairy|150\nnot a food\nB|apple|Apple|fruit|95\n").unwrap();
+    fs::write(&logs, "2024-03-02|apple|1|2024-03-02T08:00:00\n2024-03-01|ghost|2|2024-03-01T08:00:00\n").unwrap();
+    fs::write(&profile, "PROFILE|F|165|1990-05-01|mifflin_st_jeor\nDAILY|2024-03-02|60|S\nDAILY|2024-03-01|61|L\n").unwrap();
+    (foods, logs, profile)
+}
+
+#[test]
+fn escaping_round_trips_delimiters() {
+    let name = "Mac | Cheese, extra: cheddar = yes \\ no";
+    let line = format!("B|{}|rest", format::escape(name));
+    let parts = format::split_escaped(&line, '|');
+    assert_eq!(parts.len(), 3);
+    assert_eq!(format::unescape(parts[1]), name);
+    assert_eq!(format::parse_header(&format::header("foods")), Some(("foods".to_string(), format::FORMAT_VERSION)));
+}
+
+#[test]
+fn dry_run_reports_without_writing() {
+    let dir = TempDir::new("migrate-dry-run");
+    let (foods, logs, profile) = legacy_files(&dir);
+    let before = fs::read_to_string(&foods).unwrap();
+
+    let report = migrate_data_files(&foods, &logs, &profile, true, false).unwrap();
+    assert!(report.files.iter().all(|f| f.status == MigrationStatus::WouldMigrate));
+    assert_eq!(report.files[0].records, 2);
+    assert_eq!(report.files[0].skipped_lines, 1);
+    assert_eq!(fs::read_to_string(&foods).unwrap(), before);
+    assert!(!Path::new(&format!("{}.pre-migration", foods)).exists());
+}
+
+#[test]
+fn migration_backs_up_and_normalizes_once() {
+    let dir = TempDir::new("migrate-run");
+    let (foods, logs, profile) = legacy_files(&dir);
+    let original = fs::read_to_string(&foods).unwrap();
+
+    let report = migrate_data_files(&foods, &logs, &profile, false, false).unwrap();
+    assert!(report.files.iter().all(|f| f.status == MigrationStatus::Migrated));
+    assert_eq!(report.files[1].warnings, ["2024-03-01: entry for unknown food 'ghost'"]);
+
+    let backup = report.files[0].backup_path.clone().unwrap();
+    assert_eq!(fs::read_to_string(backup).unwrap(), original);
+    assert_eq!(file_format_version(&foods).unwrap(), Some(format::FORMAT_VERSION));
+    assert_eq!(
+        fs::read_to_string(&foods).unwrap(),
+        "#yada:foods:v1\nB|apple|Apple|fruit|95\nB|milk|Milk|dairy,milk|150\n"
+    );
+    let daily_lines: Vec<String> = fs::read_to_string(&profile).unwrap()
+        .lines()
+        .filter(|line| line.starts_with("DAILY"))
+        .map(String::from)
+        .collect();
+    assert_eq!(daily_lines, ["DAILY|2024-03-01|61|L", "DAILY|2024-03-02|60|S"]);
+
+    let again = migrate_data_files(&foods, &logs, &profile, false, false).unwrap();
+    assert!(again.files.iter().all(|f| f.status == MigrationStatus::UpToDate));
+    assert!(!again.changed_anything());
+}
+
+#[test]
+fn newer_files_are_left_alone() {
+    let dir = TempDir::new("migrate-newer");
+    let (foods, logs, profile) = legacy_files(&dir);
+    fs::write(&foods, "#yada:foods:v99\nB|apple|Apple|fruit|95\n").unwrap();
+
+    let report = migrate_data_files(&foods, &logs, &profile, false, true).unwrap();
+    assert_eq!(report.files[0].status, MigrationStatus::Unsupported(99));
+    assert!(fs::read_to_string(&foods).unwrap().starts_with("#yada:foods:v99"));
+}
+
+#[test]
+fn upgrade_steps_run_in_order_up_to_the_current_version() {
+    assert!(schema::UPGRADES.windows(2).all(|pair| pair[0].from < pair[1].from));
+    assert!(schema::UPGRADES.iter().all(|step| step.from < format::FORMAT_VERSION));
+    assert!(schema::steps_from("foods", format::FORMAT_VERSION).is_empty());
+    assert_eq!(schema::steps_from("logs", 0).len(), schema::UPGRADES.len());
+
+    let lines = "\n#yada:foods:v1\nB|a\\|b|x|1\n";
+    let records: Vec<(usize, String)> = schema::records("foods", lines.as_bytes()).map(Result::unwrap).collect();
+    assert_eq!(records, [(3, "B|a\\|b|x|1".to_string())]);
+    assert_eq!(schema::upgrade_record("foods", 0, "B|a\\b|x|1"), "B|a\\\\b|x|1");
+}
+
+#[test]
+fn legacy_backslashes_survive_loading_and_migration() {
+    let dir = TempDir::new("migrate-backslash");
+    let (foods, logs, profile) = legacy_files(&dir);
+    fs::write(&foods, "B|soup|Soup \\ Stew|soup|120\n").unwrap();
+    let name = |path: &str| FoodRepository::new(path).unwrap().get_food("soup").unwrap().name.clone();
+    assert_eq!(name(&foods), "Soup \\ Stew");
+
+    let report = migrate_data_files(&foods, &logs, &profile, false, false).unwrap();
+    assert_eq!(report.files[0].upgrades, [schema::UPGRADES[0].description]);
+    assert_eq!(name(&foods), "Soup \\ Stew");
+    assert!(fs::read_to_string(&foods).unwrap().contains("Soup \\\\ Stew"));
+}
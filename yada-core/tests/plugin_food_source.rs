@@ -0,0 +1,103 @@
+//! Integration tests for food sources provided by plugin executables
+#![cfg(unix)]
+
+// yada-core/tests/plugin_food_source.rs
+mod common;
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use common::{TempDir, assert_close};
+use yada_core::factories::async_food_source::LookupOptions;
+use yada_core::factories::food_source_factory::{FoodSource, FoodSourceFactory};
+use yada_core::factories::plugin_food_source::{self, PluginFoodSource};
+
+/// Shell script answering the plugin protocol for a small burger menu
+const BURGER_PLUGIN: &str = r#"#!/bin/sh
+read -r request
+case "$request" in
+  *describe*) echo '{"name": "burgerdb", "description": "Burger chain menus"}' ;;
+  *'"search"'*) echo '{"foods": [
+      {"id": "bb_cheese", "name": "Cheeseburger", "calories": 300, "protein": 15, "carbs": 32, "fat": 13, "grams": 119, "category": "Fast Food", "extra": true},
+      {"id": "bb_fries", "name": "Small Fries", "calories": 230, "keywords": ["Fries", "potato"]},
+      {"name": "No ID", "calories": 1}
+    ]}' ;;
+  *bb_cheese*) echo '{"food": {"id": "bb_cheese", "name": "Cheeseburger", "calories": 300}}' ;;
+  *) echo '{"food": null}' ;;
+esac
+"#;
+
+fn write_plugin(dir: &Path, file_name: &str, script: &str) {
+    let path = dir.join(file_name);
+    fs::write(&path, script).unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+#[test]
+fn plugins_answer_searches_and_lookups_over_json() {
+    let dir = TempDir::new("plugin-protocol");
+    write_plugin(dir.path(), "burgers", BURGER_PLUGIN);
+
+    let plugin = PluginFoodSource::load(&dir.path().join("burgers")).unwrap();
+    assert_eq!(plugin.name(), "burgerdb");
+    assert_eq!(plugin.description(), "Burger chain menus");
+
+    let foods = plugin.search_foods("burger");
+    assert_eq!(foods.len(), 2, "foods without an ID are skipped");
+    let burger = &foods[0];
+    assert_eq!(burger.name, "Cheeseburger");
+    assert_close(burger.calories_per_serving, 300.0);
+    assert_close(burger.macros.carbs, 32.0);
+    assert_eq!(burger.grams_per_serving, Some(119.0));
+    assert!(burger.has_keyword("cheeseburger"), "keywords default to the name's words");
+    assert!(foods[1].has_keyword("fries") && foods[1].has_keyword("potato"));
+
+    assert!(plugin.get_food_by_id("bb_cheese").is_some());
+    assert!(plugin.get_food_by_id("bb_shake").is_none());
+}
+
+#[test]
+fn broken_plugins_are_reported_and_others_registered() {
+    let dir = TempDir::new("plugin-discover");
+    let plugins = dir.path().join("plugins");
+    fs::create_dir_all(&plugins).unwrap();
+    write_plugin(&plugins, "a_burgers", BURGER_PLUGIN);
+    write_plugin(&plugins, "b_copy", BURGER_PLUGIN);
+    write_plugin(&plugins, "c_failing", "#!/bin/sh\nexit 3\n");
+    write_plugin(&plugins, "d_bad_name", "#!/bin/sh\necho '{\"name\": \"two words\"}'\n");
+    fs::write(plugins.join("README.txt"), "not executable").unwrap();
+
+    let (found, problems) = plugin_food_source::discover(&plugins, LookupOptions::default());
+    assert_eq!(found.len(), 1);
+    assert_eq!(problems.len(), 3, "{:?}", problems);
+    assert!(problems[0].starts_with("b_copy: another plugin"), "{:?}", problems);
+    assert!(problems[1].starts_with("c_failing: exited with"), "{:?}", problems);
+    assert!(problems[2].contains("invalid source name"), "{:?}", problems);
+
+    let mut factory = FoodSourceFactory::new();
+    let problems = factory.register_plugins(&plugins, &dir.path().join("cache"), LookupOptions::default());
+    assert_eq!(problems.len(), 3);
+    let burgers = factory.get_source("burgerdb").unwrap();
+    assert_eq!(burgers.search_foods("fries").len(), 2);
+    assert!(dir.path().join("cache").join("burgerdb_foods.txt").exists(), "plugin results are cached");
+
+    let (found, problems) = plugin_food_source::discover(&dir.path().join("missing"), LookupOptions::default());
+    assert!(found.is_empty() && problems.is_empty());
+}
+
+#[test]
+fn plugins_that_do_not_describe_themselves_in_time_are_skipped() {
+    let dir = TempDir::new("plugin-hung");
+    write_plugin(dir.path(), "a_burgers", BURGER_PLUGIN);
+    write_plugin(dir.path(), "b_hung", "#!/bin/sh\nsleep 5\n");
+    let options = LookupOptions { timeout: Duration::from_millis(300), ..LookupOptions::default() };
+
+    let started = Instant::now();
+    let (found, problems) = plugin_food_source::discover(dir.path(), options);
+    assert!(started.elapsed() < Duration::from_secs(3), "took {:?}", started.elapsed());
+    assert_eq!(found.len(), 1);
+    assert_eq!(problems.len(), 1, "{:?}", problems);
+    assert!(problems[0].starts_with("b_hung: did not answer within"), "{:?}", problems);
+}
@@ -0,0 +1,108 @@
+//! Integration tests for the preferences in the settings file
+
+// yada-core/tests/preferences.rs
+mod common;
+
+use std::time::Duration;
+
+use chrono::NaiveDate;
+
+use common::{TempDir, assert_close};
+use yada_core::models::preferences::{self, ConfirmPolicy, DateFormat, UnitSystem};
+use yada_core::models::search::SearchMode;
+use yada_core::repositories::autosave::AutosavePolicy;
+use yada_core::repositories::settings_repository::{Preferences, SettingsRepository};
+
+#[test]
+fn units_and_date_formats_convert_both_ways() {
+    let imperial = UnitSystem::Imperial;
+    assert_eq!(imperial.format_weight(80.0), "176.4 lb");
+    assert_close(imperial.weight_to_kg(imperial.weight_from_kg(80.0)), 80.0);
+    assert_eq!(imperial.format_height(180.0), "70.9 in");
+    assert_eq!(UnitSystem::Metric.format_weight(80.0), "80.0 kg");
+
+    let date = NaiveDate::from_ymd_opt(2024, 3, 5).unwrap();
+    assert_eq!(DateFormat::DayFirst.format(date), "05.03.2024");
+    assert_eq!(DateFormat::MonthFirst.format(date), "03/05/2024");
+    // Every format is accepted whatever the preference
+    for typed in ["2024-03-05", "05.03.2024", "03/05/2024"] {
+        assert_eq!(preferences::parse_date(typed), Some(date));
+    }
+    assert_eq!(preferences::parse_date("2024-13-05"), None);
+}
+
+#[test]
+fn expert_mode_asks_only_before_changes_that_cannot_be_undone() {
+    assert!(ConfirmPolicy::Safe.requires_confirmation(true));
+    assert!(ConfirmPolicy::Safe.requires_confirmation(false));
+    assert!(!ConfirmPolicy::Expert.requires_confirmation(true));
+    assert!(ConfirmPolicy::Expert.requires_confirmation(false));
+    assert_eq!(ConfirmPolicy::parse(" EXPERT"), Some(ConfirmPolicy::Expert));
+    assert_eq!(ConfirmPolicy::parse("yolo"), None);
+}
+
+#[test]
+fn preferences_survive_a_round_trip_and_bad_values_use_the_default() {
+    let dir = TempDir::new("preferences");
+    let path = dir.file("settings.txt");
+
+    let mut repo = SettingsRepository::new(&path).unwrap();
+    assert_eq!(repo.preferences(), &Preferences::default());
+    repo.set_preferences(Preferences::default());
+    assert!(!repo.is_dirty());
+
+    let changed = Preferences {
+        units: UnitSystem::Imperial,
+        autosave: AutosavePolicy::Interval(Duration::from_secs(300)),
+        search_mode: SearchMode::All,
+        date_format: DateFormat::MonthFirst,
+        confirm: ConfirmPolicy::Expert,
+    };
+    repo.set_preferences(changed.clone());
+    repo.save().unwrap();
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert!(contents.contains("PREF|units=imperial\nPREF|autosave=5\nPREF|search=all\nPREF|dates=month-first\nPREF|confirm=expert\n"));
+    let reloaded = SettingsRepository::new(&path).unwrap();
+    assert_eq!(reloaded.preferences(), &changed);
+    assert_eq!(reloaded.skipped_line_count(), 0);
+
+    std::fs::write(&path, "#yada:settings:v1\nPREF|units=furlongs\nPREF|search=and\nPREF|theme=dark\nPREF|dates\n").unwrap();
+    let reloaded = SettingsRepository::new(&path).unwrap();
+    assert_eq!(reloaded.preferences().units, UnitSystem::Metric);
+    assert_eq!(reloaded.preferences().search_mode, SearchMode::All);
+    assert_eq!(reloaded.load_report().defaulted_count(), 1);
+    assert_eq!(reloaded.skipped_line_count(), 2);
+}
+
+#[test]
+fn saved_searches_survive_a_round_trip_and_names_are_unique() {
+    let dir = TempDir::new("saved-searches");
+    let path = dir.file("settings.txt");
+
+    let mut repo = SettingsRepository::new(&path).unwrap();
+    let terms = |words: &[&str]| words.iter().map(|word| word.to_string()).collect::<Vec<_>>();
+    assert!(!repo.save_search("Snacks", &terms(&["snack", "Protein"]), SearchMode::All).unwrap());
+    assert!(repo.save_search("Dairy, cheap", &terms(&["category:dairy"]), SearchMode::Any).is_err());
+    assert_eq!(repo.save_search("Empty", &terms(&[" "]), SearchMode::Any).unwrap_err(), "A saved search needs at least one keyword");
+    assert!(!repo.save_search("In dairy | cheap", &terms(&["category:dairy", "cheap"]), SearchMode::Any).unwrap());
+    // Names match case-insensitively, so this replaces the first search
+    assert!(repo.save_search("SNACKS", &terms(&["snack"]), SearchMode::Any).unwrap());
+    assert_eq!(repo.saved_searches().len(), 2);
+    assert_eq!(repo.find_search("snacks").unwrap().terms, ["snack"]);
+
+    repo.save().unwrap();
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert!(contents.contains("SEARCH|mode=any|terms=category\\:dairy,cheap|name=In dairy \\| cheap\n"));
+    let reloaded = SettingsRepository::new(&path).unwrap();
+    assert_eq!(reloaded.saved_searches(), repo.saved_searches());
+    assert_eq!(reloaded.skipped_line_count(), 0);
+
+    let mut repo = reloaded;
+    assert_eq!(repo.remove_search("in dairy | cheap").unwrap().terms, ["category:dairy", "cheap"]);
+    assert!(repo.remove_search("in dairy | cheap").is_none());
+
+    std::fs::write(&path, format!("{}SEARCH|mode=all|name=No terms\nSEARCH|terms=snack|name=snacks\n", contents)).unwrap();
+    let reloaded = SettingsRepository::new(&path).unwrap();
+    assert_eq!(reloaded.saved_searches().len(), 2);
+    assert_eq!(reloaded.skipped_line_count(), 2);
+}
@@ -0,0 +1,337 @@
+//! Integration tests for daily profiles, carrying them forward, weekday targets,
+//! diet presets, life stages, macro targets, and profile history
+
+// yada-core/tests/profile.rs
+mod common;
+
+use chrono::{Duration, NaiveDate, Weekday};
+
+use common::{TempDir, assert_close};
+use yada_core::models::profile::{ActivityLevel, DailyProfile, Gender, ProfileVersion, UserProfile};
+use yada_core::models::diet_preset::DietPreset;
+use yada_core::models::food::Macros;
+use yada_core::models::life_stage::LifeStage;
+use yada_core::models::macro_target::{MacroRatios, MacroTargets};
+use yada_core::models::weekday_target::TargetOverride;
+use yada_core::repositories::profile_repository::ProfileRepository;
+use yada_core::strategies::calorie_calculator::CalorieCalculatorFactory;
+
+fn date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 3, day).unwrap()
+}
+
+fn profile_with_weight_on(day: u32) -> UserProfile {
+    let mut profile = UserProfile::new(Gender::Male, 180.0, NaiveDate::from_ymd_opt(1990, 1, 1).unwrap());
+    profile.add_or_update_daily_profile(DailyProfile {
+        date: date(day),
+        weight: 80.0,
+        activity_level: ActivityLevel::LightlyActive,
+        carried_from: None,
+    });
+    profile
+}
+
+#[test]
+fn a_new_day_inherits_the_latest_entered_values() {
+    let mut profile = profile_with_weight_on(5);
+
+    let carried = profile.carry_forward(date(6)).unwrap();
+    assert_close(carried.weight, 80.0);
+    assert_eq!(carried.activity_level, ActivityLevel::LightlyActive);
+    assert_eq!(carried.carried_from, Some(date(5)));
+
+    // Chained days still point at the day the values were entered
+    assert_eq!(profile.carry_forward(date(7)).unwrap().carried_from, Some(date(5)));
+
+    // Days with data, or without earlier data, are left alone
+    assert!(profile.carry_forward(date(5)).is_none());
+    assert!(profile.carry_forward(date(1)).is_none());
+    assert_eq!(profile.daily_profiles.len(), 3);
+}
+
+#[test]
+fn the_carried_forward_mark_survives_a_round_trip() {
+    let dir = TempDir::new("profile-carried");
+    let path = dir.file("profile.txt");
+    let mut repo = ProfileRepository::new(&path).unwrap();
+    let mut profile = profile_with_weight_on(5);
+    profile.carry_forward(date(6));
+    repo.set_profile(profile);
+    repo.save().unwrap();
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert!(contents.contains("DAILY|2024-03-05|80|L\n"));
+    assert!(contents.contains("DAILY|2024-03-06|80|L|carried_from=2024-03-05\n"));
+
+    let reloaded = ProfileRepository::new(&path).unwrap();
+    let profile = reloaded.get_profile().unwrap();
+    assert_eq!(profile.get_daily_profile(date(5)).unwrap().carried_from, None);
+    assert_eq!(profile.get_daily_profile(date(6)).unwrap().carried_from, Some(date(5)));
+    assert_eq!(reloaded.skipped_line_count(), 0);
+}
+
+#[test]
+fn unusable_height_and_birth_date_load_as_unknown() {
+    let dir = TempDir::new("profile-low-data");
+    let path = dir.file("profile.txt");
+    std::fs::write(&path, "PROFILE|F|0|not a date|mifflin_st_jeor\nDAILY|2024-03-05|60|S\n").unwrap();
+
+    let mut repo = ProfileRepository::new(&path).unwrap();
+    let profile = repo.get_profile().unwrap();
+    assert_eq!(profile.height, None);
+    assert_eq!(profile.birth_date, None);
+    assert!(profile.body_metrics(date(5)).estimate_notice().is_some());
+
+    // Unknown values are saved as empty fields and stay unknown
+    repo.save().unwrap();
+    assert!(std::fs::read_to_string(&path).unwrap().contains("PROFILE|F|||mifflin_st_jeor\n"));
+    let reloaded = ProfileRepository::new(&path).unwrap();
+    assert_eq!(reloaded.get_profile().unwrap().height, None);
+    assert_eq!(reloaded.skipped_line_count(), 0);
+}
+
+#[test]
+fn weekday_overrides_fix_or_adjust_the_calculated_target() {
+    let mut profile = profile_with_weight_on(4);
+    profile.add_or_update_daily_profile(DailyProfile {
+        date: date(5),
+        weight: 80.0,
+        activity_level: ActivityLevel::LightlyActive,
+        carried_from: None,
+    });
+    profile.weekday_targets.set(Weekday::Mon, Some(TargetOverride::parse("+300").unwrap()));
+    profile.weekday_targets.set(Weekday::Tue, Some(TargetOverride::parse("1500").unwrap()));
+
+    let calculator = CalorieCalculatorFactory::new();
+    let calculator = calculator.get_calculator("mifflin_st_jeor").unwrap();
+    // 2024-03-04 is a Monday, 2024-03-05 a Tuesday
+    let monday = calculator.calculate_tdee(&profile, date(4));
+    assert_close(calculator.daily_target_calories(&profile, date(4)), monday + 300.0);
+    assert_close(calculator.daily_target_calories(&profile, date(5)), 1500.0);
+
+    // Days before the first daily profile have no target to override
+    profile.weekday_targets.set(Weekday::Sun, Some(TargetOverride::Fixed(2000.0)));
+    assert_close(calculator.daily_target_calories(&profile, date(3)), 0.0);
+
+    // Adjustments never go below zero
+    assert_close(TargetOverride::Adjust(-5000.0).apply(1800.0), 0.0);
+    assert!(TargetOverride::parse("0").is_err());
+    assert!(TargetOverride::parse("lots").is_err());
+}
+
+#[test]
+fn weekday_targets_survive_a_round_trip() {
+    let dir = TempDir::new("profile-weekday-targets");
+    let path = dir.file("profile.txt");
+    let mut repo = ProfileRepository::new(&path).unwrap();
+    let mut profile = profile_with_weight_on(5);
+    profile.weekday_targets.set(Weekday::Sat, Some(TargetOverride::Fixed(2500.0)));
+    profile.weekday_targets.set(Weekday::Mon, Some(TargetOverride::Adjust(-200.0)));
+    repo.set_profile(profile);
+    repo.save().unwrap();
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert!(contents.ends_with("TARGET|Mon|-200\nTARGET|Sat|2500\n"));
+
+    let reloaded = ProfileRepository::new(&path).unwrap();
+    let targets = &reloaded.get_profile().unwrap().weekday_targets;
+    assert_eq!(targets.get(Weekday::Mon), Some(TargetOverride::Adjust(-200.0)));
+    assert_eq!(targets.get(Weekday::Sat), Some(TargetOverride::Fixed(2500.0)));
+    assert_eq!(targets.get(Weekday::Sun), None);
+    assert_eq!(reloaded.skipped_line_count(), 0);
+}
+
+#[test]
+fn a_diet_preset_adjusts_the_target_before_weekday_overrides() {
+    let mut profile = profile_with_weight_on(4);
+    let calculator = CalorieCalculatorFactory::new();
+    let calculator = calculator.get_calculator("mifflin_st_jeor").unwrap();
+    let calculated = calculator.calculate_tdee(&profile, date(4));
+
+    profile.diet_preset = Some(DietPreset::Cutting);
+    assert_close(calculator.daily_target_calories(&profile, date(4)), calculated - 500.0);
+
+    // A weekday adjustment adds to the preset's target; days before any weigh-in have no target
+    profile.weekday_targets.set(Weekday::Mon, Some(TargetOverride::Adjust(200.0)));
+    assert_close(calculator.daily_target_calories(&profile, date(4)), calculated - 300.0);
+    assert_close(calculator.daily_target_calories(&profile, date(3)), 0.0);
+
+    // 35% of 2000 kcal from protein at 4 kcal/g, 25% from fat at 9 kcal/g
+    let grams = DietPreset::Cutting.ratios().grams(2000.0);
+    assert_close(grams.protein, 175.0);
+    assert_close(grams.carbs, 200.0);
+    assert_close(grams.fat, 500.0 / 9.0);
+}
+
+#[test]
+fn the_diet_preset_survives_a_round_trip() {
+    let dir = TempDir::new("profile-diet-preset");
+    let path = dir.file("profile.txt");
+    let mut repo = ProfileRepository::new(&path).unwrap();
+    let mut profile = profile_with_weight_on(5);
+    profile.diet_preset = Some(DietPreset::HighProtein);
+    repo.set_profile(profile);
+    repo.save().unwrap();
+    assert!(std::fs::read_to_string(&path).unwrap().ends_with("PRESET|high_protein\n"));
+
+    let reloaded = ProfileRepository::new(&path).unwrap();
+    assert_eq!(reloaded.get_profile().unwrap().diet_preset, Some(DietPreset::HighProtein));
+    assert_eq!(reloaded.skipped_line_count(), 0);
+
+    // Unknown presets are skipped, not guessed
+    std::fs::write(&path, "PROFILE|M|180|1990-01-01|mifflin_st_jeor\nPRESET|paleo\n").unwrap();
+    let reloaded = ProfileRepository::new(&path).unwrap();
+    assert_eq!(reloaded.get_profile().unwrap().diet_preset, None);
+    assert_eq!(reloaded.skipped_line_count(), 1);
+}
+
+#[test]
+fn pregnancy_adds_calories_by_trimester() {
+    let due_date = NaiveDate::from_ymd_opt(2024, 10, 1).unwrap();
+    let stage = LifeStage::Pregnant { due_date };
+    let week = |week: i64| due_date - Duration::days(280) + Duration::weeks(week - 1);
+    assert_eq!(stage.pregnancy_week(week(1)), Some(1));
+    assert_eq!(stage.pregnancy_week(week(1) - Duration::days(1)), None);
+    assert_close(stage.calorie_addition(week(13)), 0.0);
+    assert_close(stage.calorie_addition(week(14)), 340.0);
+    assert_close(stage.calorie_addition(week(28)), 452.0);
+    assert_eq!(stage.describe(week(20)), "Pregnancy, week 20 (2nd trimester)");
+
+    // Breastfeeding: more after the first six months, nothing before the birth
+    let since = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+    let stage = LifeStage::Breastfeeding { since };
+    assert_close(stage.calorie_addition(since - Duration::days(1)), 0.0);
+    assert_close(stage.calorie_addition(since), 330.0);
+    assert_close(stage.calorie_addition(NaiveDate::from_ymd_opt(2024, 7, 15).unwrap()), 400.0);
+}
+
+#[test]
+fn the_life_stage_adds_to_the_target_and_survives_a_round_trip() {
+    let dir = TempDir::new("profile-life-stage");
+    let path = dir.file("profile.txt");
+    let mut repo = ProfileRepository::new(&path).unwrap();
+    let mut profile = profile_with_weight_on(5);
+    let calculator = CalorieCalculatorFactory::new();
+    let calculator = calculator.get_calculator("mifflin_st_jeor").unwrap();
+    let calculated = calculator.calculate_tdee(&profile, date(5));
+
+    profile.life_stage = Some(LifeStage::Breastfeeding { since: date(1) });
+    profile.diet_preset = Some(DietPreset::Cutting);
+    assert_close(calculator.daily_target_calories(&profile, date(5)), calculated + 330.0 - 500.0);
+    assert_close(calculator.daily_target_calories(&profile, date(4)), 0.0);
+
+    repo.set_profile(profile);
+    repo.save().unwrap();
+    assert!(std::fs::read_to_string(&path).unwrap().contains("LIFESTAGE|breastfeeding|2024-03-01\n"));
+    let reloaded = ProfileRepository::new(&path).unwrap();
+    assert_eq!(reloaded.get_profile().unwrap().life_stage, Some(LifeStage::Breastfeeding { since: date(1) }));
+    assert_eq!(reloaded.skipped_line_count(), 0);
+}
+
+#[test]
+fn macro_targets_override_the_preset_and_survive_a_round_trip() {
+    let dir = TempDir::new("profile-macro-targets");
+    let path = dir.file("profile.txt");
+    let mut repo = ProfileRepository::new(&path).unwrap();
+    let mut profile = profile_with_weight_on(5);
+    profile.diet_preset = Some(DietPreset::Keto);
+    assert_eq!(profile.active_macro_targets(), Some(MacroTargets::Ratios(DietPreset::Keto.ratios())));
+
+    let ratios = MacroRatios::new(30.0, 40.0, 30.0).unwrap();
+    profile.macro_targets = Some(MacroTargets::Ratios(ratios));
+    assert_eq!(profile.active_macro_targets(), Some(MacroTargets::Ratios(ratios)));
+    repo.set_profile(profile);
+    repo.save().unwrap();
+    assert!(std::fs::read_to_string(&path).unwrap().contains("MACROS|percent|30|40|30\n"));
+
+    let reloaded = ProfileRepository::new(&path).unwrap();
+    assert_eq!(reloaded.get_profile().unwrap().macro_targets, Some(MacroTargets::Ratios(ratios)));
+    assert_eq!(reloaded.skipped_line_count(), 0);
+
+    // Percentages must add up to 100; grams do not depend on the calorie target
+    assert!(MacroRatios::new(30.0, 40.0, 20.0).is_err());
+    assert!(MacroRatios::new(-10.0, 80.0, 30.0).is_err());
+    let grams = MacroTargets::Grams(Macros::new(150.0, 200.0, 60.0));
+    assert_eq!(grams.grams(0.0).map(|macros| macros.protein), Some(150.0));
+    assert!(MacroTargets::Ratios(ratios).grams(0.0).is_none());
+    assert_close(MacroTargets::Ratios(ratios).grams(2000.0).unwrap().fat, 600.0 / 9.0);
+}
+
+#[test]
+fn past_targets_use_the_height_and_method_of_the_time() {
+    let mut profile = profile_with_weight_on(4);
+    profile.carry_forward(date(12));
+    let factory = CalorieCalculatorFactory::new();
+    let before = factory.calculator_for(&profile, date(4)).unwrap().daily_target_calories(&profile, date(4));
+
+    // Changed on the 10th: earlier days keep the old values
+    let previous = profile.clone();
+    profile.height = Some(170.0);
+    profile.calculation_method = "mifflin_st_jeor".to_string();
+    profile.record_basic_change(&previous, date(10));
+    assert_eq!(profile.height_on(date(9)), Some(180.0));
+    assert_eq!(profile.height_on(date(10)), Some(170.0));
+    assert_eq!(profile.calculation_method_on(date(9)), previous.calculation_method);
+    assert_eq!(profile.calculation_method_on(date(10)), "mifflin_st_jeor");
+    assert!(profile.uses_method("mifflin_st_jeor") && profile.uses_method(&previous.calculation_method));
+    assert!(!profile.uses_method("adaptive_tdee"));
+
+    let calculator = factory.calculator_for(&profile, date(4)).unwrap();
+    assert_close(calculator.daily_target_calories(&profile, date(4)), before);
+    assert_eq!(factory.calculator_for(&profile, date(12)).unwrap().name(), "mifflin_st_jeor");
+
+    // A second change on the same day keeps the values from before the first
+    let previous = profile.clone();
+    profile.gender = Gender::Female;
+    profile.record_basic_change(&previous, date(10));
+    assert_eq!(profile.history.len(), 1);
+    assert_eq!(profile.gender_on(date(9)), &Gender::Male);
+
+    // A change dated earlier replaces the history after it
+    let previous = profile.clone();
+    profile.height = Some(175.0);
+    profile.record_basic_change(&previous, date(7));
+    assert_eq!(profile.history.len(), 1);
+    assert_eq!(profile.history[0].until, date(7));
+    assert_eq!(profile.height_on(date(8)), Some(175.0));
+
+    // Unchanged values add nothing
+    let previous = profile.clone();
+    profile.record_basic_change(&previous, date(12));
+    assert_eq!(profile.history.len(), 1);
+}
+
+#[test]
+fn profile_history_survives_a_round_trip() {
+    let dir = TempDir::new("profile-history");
+    let path = dir.file("profile.txt");
+    let mut repo = ProfileRepository::new(&path).unwrap();
+    let mut profile = profile_with_weight_on(5);
+    profile.history = vec![
+        ProfileVersion {
+            until: date(2),
+            gender: Gender::Other,
+            height: None,
+            calculation_method: "mifflin_st_jeor".to_string(),
+        },
+        ProfileVersion {
+            until: date(4),
+            gender: Gender::Male,
+            height: Some(182.5),
+            calculation_method: "harris_benedict".to_string(),
+        },
+    ];
+    repo.set_profile(profile.clone());
+    repo.save().unwrap();
+    assert!(std::fs::read_to_string(&path).unwrap().contains("HISTORY|2024-03-02|O||mifflin_st_jeor\n"));
+
+    let reloaded = ProfileRepository::new(&path).unwrap();
+    assert_eq!(reloaded.get_profile().unwrap().history, profile.history);
+    assert_eq!(reloaded.skipped_line_count(), 0);
+
+    // Lines without a date are skipped
+    std::fs::write(&path, "PROFILE|M|180|1990-01-01|mifflin_st_jeor\nHISTORY|soon|M|180|harris_benedict\n").unwrap();
+    let reloaded = ProfileRepository::new(&path).unwrap();
+    assert!(reloaded.get_profile().unwrap().history.is_empty());
+    assert_eq!(reloaded.skipped_line_count(), 1);
+}
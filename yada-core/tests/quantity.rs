@@ -0,0 +1,93 @@
+//! Integration tests for logging by weight and the quantity model
+
+// yada-core/tests/quantity.rs
+mod common;
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+
+use common::{TempDir, assert_close, keywords};
+use yada_core::commands::log_commands::AddLogEntryCommand;
+use yada_core::models::command::Command;
+use yada_core::models::food::{Food, Macros};
+use yada_core::models::log::DailyLog;
+use yada_core::models::quantity::Quantity;
+use yada_core::models::serving::ServingUnit;
+use yada_core::repositories::log_repository::LogRepository;
+
+fn date() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 3, 5).unwrap()
+}
+
+fn chicken() -> Food {
+    Food::new_basic_per_100g("chicken".into(), "Chicken Breast".into(), keywords(&["meat"]), 165.0)
+        .with_macros(Macros::new(31.0, 0.0, 3.6))
+}
+
+fn food_db(foods: Vec<Food>) -> HashMap<String, Food> {
+    foods.into_iter().map(|food| (food.id.clone(), food)).collect()
+}
+
+#[test]
+fn per_100g_foods_are_logged_by_weight() {
+    let foods = food_db(vec![chicken()]);
+    let quantity = Quantity::parse("85 g").unwrap();
+    assert_eq!(quantity, Quantity::in_unit(85.0, ServingUnit::Gram));
+    assert_close(quantity.calories(&foods["chicken"]).unwrap(), 140.25);
+
+    let mut log = DailyLog::new(date());
+    log.add_quantity_entry("chicken".into(), quantity, quantity.to_servings(&foods["chicken"]).unwrap());
+    log.add_entry("chicken".into(), 1.0);
+    assert_close(log.total_calories(&foods), 140.25 + 165.0);
+    assert_close(log.total_macros(&foods).protein, 31.0 * 1.85);
+}
+
+#[test]
+fn weights_are_converted_with_the_current_food_definition() {
+    let mut foods = food_db(vec![chicken()]);
+    let mut log = DailyLog::new(date());
+    log.add_quantity_entry("chicken".into(), Quantity::in_unit(50.0, ServingUnit::Gram), 0.5);
+
+    // Correcting the label to per-serving values with a 200 g serving keeps 50 g accurate
+    let corrected = Food::new_basic("chicken".into(), "Chicken Breast".into(), keywords(&["meat"]), 330.0)
+        .with_serving_details(Some(200.0), None);
+    foods.insert("chicken".into(), corrected);
+    assert_close(log.total_calories(&foods), 82.5);
+
+    // Without any size information the servings recorded at logging time are used
+    let without_size = Food::new_basic("chicken".into(), "Chicken Breast".into(), keywords(&["meat"]), 200.0);
+    foods.insert("chicken".into(), without_size);
+    assert_close(log.total_calories(&foods), 100.0);
+}
+
+#[test]
+fn quantities_survive_a_round_trip_and_servings_lines_are_unchanged() {
+    let dir = TempDir::new("quantity-round-trip");
+    let path = dir.file("logs.txt");
+    let mut repo = LogRepository::new(&path).unwrap();
+    let mut command = AddLogEntryCommand::new(&mut repo, date(), "chicken".into(), 0.85)
+        .with_quantity(Quantity::in_unit(85.0, ServingUnit::Gram));
+    command.execute().unwrap();
+    assert_eq!(command.description(), "Add log entry: 85 g of chicken on 2024-03-05");
+    repo.get_log_mut(date()).add_entry("rice".into(), 2.0);
+    repo.save().unwrap();
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert!(contents.contains("|chicken|0.85|"));
+    assert!(contents.contains("|amount=85|unit=g"));
+    assert_eq!(contents.matches("amount=").count(), 1);
+
+    let reloaded = LogRepository::new(&path).unwrap();
+    let entries = &reloaded.get_log(date()).unwrap().entries;
+    assert_eq!(entries[0].quantity, Quantity::in_unit(85.0, ServingUnit::Gram));
+    assert_close(entries[0].servings, 0.85);
+    assert_eq!(entries[1].quantity, Quantity::servings(2.0));
+}
+
+#[test]
+fn quantities_display_as_entered() {
+    assert_eq!(Quantity::servings(1.0).to_string(), "1 serving");
+    assert_eq!(Quantity::servings(2.5).to_string(), "2.5 servings");
+    assert_eq!(Quantity::in_unit(250.0, ServingUnit::Milliliter).to_string(), "250 ml");
+}
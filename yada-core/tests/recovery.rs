@@ -0,0 +1,78 @@
+//! Integration tests for recovering data files that fail to load
+
+// yada-core/tests/recovery.rs
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use common::{TempDir, basic_food};
+use yada_core::models::food::Macros;
+use yada_core::repositories::food_repository::FoodRepository;
+use yada_core::repositories::recovery;
+use yada_core::repositories::storage;
+
+/// Appends a line that is not valid UTF-8, which makes loading fail
+fn corrupt(path: &str) {
+    let mut bytes = fs::read(path).unwrap();
+    bytes.extend_from_slice(b"B|bad|\xff\xfe|x|1\n");
+    fs::write(path, bytes).unwrap();
+}
+
+#[test]
+fn restoring_a_backup_keeps_the_broken_file() {
+    let dir = TempDir::new("recovery-restore");
+    let path = dir.file("foods.txt");
+    let mut repo = FoodRepository::new(&path).unwrap();
+    repo.add_food(basic_food("oats", 150.0, Macros::default(), 40.0)).unwrap();
+    repo.save().unwrap();
+    repo.add_food(basic_food("kale", 30.0, Macros::default(), 50.0)).unwrap();
+    repo.save().unwrap();
+    corrupt(&path);
+    assert!(FoodRepository::new(&path).is_err());
+
+    let backups = recovery::list_backups(&path);
+    assert_eq!(backups.len(), 1);
+    assert_eq!(backups[0].path, storage::backup_path(&path, 1));
+
+    let broken = recovery::restore_backup(&path, &backups[0]).unwrap();
+    assert_eq!(broken, format!("{}.broken", path));
+    assert!(Path::new(&broken).exists());
+    assert!(Path::new(&backups[0].path).exists());
+
+    let restored = FoodRepository::new(&path).unwrap();
+    assert!(restored.get_food("oats").is_some());
+    assert!(restored.get_food("kale").is_none());
+}
+
+#[test]
+fn skipping_moves_the_file_to_a_free_name() {
+    let dir = TempDir::new("recovery-skip");
+    let path = dir.file("logs.txt");
+    fs::write(&path, "first").unwrap();
+    assert_eq!(recovery::set_aside(&path).unwrap(), format!("{}.broken", path));
+    fs::write(&path, "second").unwrap();
+    assert_eq!(recovery::set_aside(&path).unwrap(), format!("{}.broken.2", path));
+
+    assert!(!Path::new(&path).exists());
+    assert_eq!(fs::read_to_string(format!("{}.broken", path)).unwrap(), "first");
+    assert!(recovery::list_backups(&path).is_empty());
+}
+
+#[test]
+fn check_reports_bad_lines_and_newer_formats() {
+    let dir = TempDir::new("recovery-check");
+    let path = dir.file("foods.txt");
+    fs::write(&path, "#yada:foods:v9\nB|oats|Oats|oats|150\n").unwrap();
+    corrupt(&path);
+
+    let report = recovery::check_file(&path);
+    assert_eq!(report.lines, 3);
+    assert_eq!(report.version, Some(9));
+    assert_eq!(report.problems.len(), 2);
+    assert!(report.problems[0].starts_with("Line 1: written by a newer version"));
+    assert!(report.problems[1].starts_with("Line 3: not valid UTF-8"));
+
+    let missing = recovery::check_file(&dir.file("missing.txt"));
+    assert!(missing.problems[0].starts_with("The file cannot be read"));
+}
@@ -0,0 +1,104 @@
+//! Integration tests for reminders and the settings file storing them
+
+// yada-core/tests/reminder.rs
+mod common;
+
+use chrono::{NaiveDate, NaiveTime, Weekday};
+
+use common::TempDir;
+use yada_core::models::log::DailyLog;
+use yada_core::models::meal::MealSlot;
+use yada_core::models::profile::{ActivityLevel, DailyProfile, Gender, UserProfile};
+use yada_core::models::reminder::{self, ReminderCheck};
+use yada_core::repositories::settings_repository::SettingsRepository;
+
+fn time(hour: u32, minute: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+}
+
+#[test]
+fn day_lists_are_parsed_in_week_order() {
+    assert!(reminder::parse_days("daily").unwrap().is_empty());
+    assert_eq!(reminder::parse_days("Weekends").unwrap(), [Weekday::Sat, Weekday::Sun]);
+    assert_eq!(reminder::parse_days("fri, mon,fri").unwrap(), [Weekday::Mon, Weekday::Fri]);
+    // All seven days are stored as every day
+    assert!(reminder::parse_days("sun,mon,tue,wed,thu,fri,sat").unwrap().is_empty());
+    assert_eq!(reminder::parse_days("mon,funday").unwrap_err(), "'funday' is not a day of the week");
+    assert_eq!(reminder::parse_time("7:05").unwrap(), time(7, 5));
+    assert!(reminder::parse_time("25:00").is_err());
+}
+
+#[test]
+fn reminders_are_due_once_a_day_until_their_check_is_done() {
+    let dir = TempDir::new("reminders-due");
+    let mut repo = SettingsRepository::new(&dir.file("settings.txt")).unwrap();
+    repo.add_reminder(time(13, 0), Vec::new(), Some(ReminderCheck::Meal(MealSlot::Lunch)), "Log lunch").unwrap();
+    repo.add_reminder(time(7, 0), vec![Weekday::Mon], Some(ReminderCheck::WeighIn), "Weigh in").unwrap();
+    assert_eq!(repo.add_reminder(time(8, 0), Vec::new(), None, "  ").unwrap_err(), "A reminder needs a message");
+
+    // 2024-03-04 is a Monday
+    let monday = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+    let due = |repo: &SettingsRepository, at: NaiveTime, log: Option<&DailyLog>, profile: Option<&UserProfile>| {
+        reminder::due_reminders(repo.reminders(), monday.and_time(at), log, profile)
+            .iter()
+            .map(|due| due.id)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(due(&repo, time(6, 59), None, None), Vec::<u32>::new());
+    assert_eq!(due(&repo, time(13, 30), None, None), [2, 1]);
+    // Not due on a Tuesday
+    assert!(reminder::due_reminders(repo.reminders(), monday.succ_opt().unwrap().and_time(time(8, 0)), None, None).is_empty());
+
+    // Logging lunch and entering a weight count, a carried-forward weight does not
+    let mut log = DailyLog::new(monday);
+    log.add_entry("apple".to_string(), 1.0);
+    log.entries[0].meal = Some(MealSlot::Lunch);
+    let mut profile = UserProfile::new(Gender::Male, 180.0, NaiveDate::from_ymd_opt(1990, 1, 1).unwrap());
+    profile.add_or_update_daily_profile(DailyProfile {
+        date: monday,
+        weight: 80.0,
+        activity_level: ActivityLevel::Sedentary,
+        carried_from: Some(NaiveDate::from_ymd_opt(2024, 3, 3).unwrap()),
+    });
+    assert_eq!(due(&repo, time(13, 30), Some(&log), Some(&profile)), [2]);
+    profile.daily_profiles[0].carried_from = None;
+    assert!(due(&repo, time(13, 30), Some(&log), Some(&profile)).is_empty());
+
+    // A reminder shown today is not due again until tomorrow
+    repo.mark_shown(2, monday);
+    assert_eq!(due(&repo, time(13, 30), None, None), [1]);
+}
+
+#[test]
+fn reminders_survive_a_round_trip_and_bad_lines_are_skipped() {
+    let dir = TempDir::new("reminders-round-trip");
+    let path = dir.file("settings.txt");
+    let shown = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+
+    let mut repo = SettingsRepository::new(&path).unwrap();
+    repo.add_reminder(time(13, 0), vec![Weekday::Mon, Weekday::Wed], Some(ReminderCheck::Meal(MealSlot::Lunch)), "Log lunch | soup").unwrap();
+    repo.add_reminder(time(7, 30), Vec::new(), None, "Drink water").unwrap();
+    repo.mark_shown(1, shown);
+    assert!(repo.is_dirty());
+    repo.save().unwrap();
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert!(contents.contains("REMINDER|id=1|time=13:00|days=mon,wed|check=lunch|shown=2024-03-04|message=Log lunch \\| soup\n"));
+
+    let reloaded = SettingsRepository::new(&path).unwrap();
+    assert_eq!(reloaded.reminders(), repo.reminders());
+    assert_eq!(reloaded.skipped_line_count(), 0);
+
+    // New reminders get the ID after the highest one left
+    let mut repo = reloaded;
+    assert_eq!(repo.remove_reminder(1).unwrap().message, "Log lunch | soup");
+    assert!(repo.remove_reminder(1).is_none());
+    assert_eq!(repo.add_reminder(time(9, 0), Vec::new(), None, "Stretch").unwrap().id, 3);
+
+    std::fs::write(&path, format!(
+        "{}REMINDER|id=2|time=noon|message=Bad time\nREMINDER|id=1|time=08:00|message=Same ID\nTHEME|dark\n",
+        contents
+    )).unwrap();
+    let reloaded = SettingsRepository::new(&path).unwrap();
+    assert_eq!(reloaded.reminders().len(), 2);
+    assert_eq!(reloaded.skipped_line_count(), 3);
+}
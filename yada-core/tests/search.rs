@@ -0,0 +1,163 @@
+//! Integration tests for ranked food search
+
+// yada-core/tests/search.rs
+mod common;
+
+use common::{TempDir, keywords};
+use yada_core::models::food::{Food, Macros};
+use yada_core::models::nutrient_filter::{self, Comparison, Nutrient, NutrientFilter};
+use yada_core::models::search::{self, MatchQuality};
+use yada_core::repositories::search_index::SearchIndex;
+use yada_core::repositories::food_repository::FoodRepository;
+
+fn repo(dir: &TempDir) -> FoodRepository {
+    let mut repo = FoodRepository::new(&dir.file("foods.txt")).unwrap();
+    for (id, name, words) in [
+        ("chicken", "Chicken Breast (4 oz)", &["meat", "protein"][..]),
+        ("chickpeas", "Chickpeas", &["legume"][..]),
+        ("pb", "Peanut Butter", &["spread"][..]),
+        ("broccoli", "Broccoli", &["vegetable", "green"][..]),
+        ("banana", "Banana", &["fruit"][..]),
+    ] {
+        repo.add_food(Food::new_basic(id.into(), name.into(), keywords(words), 100.0)).unwrap();
+    }
+    repo
+}
+
+fn ids(repo: &FoodRepository, terms: &[&str], match_all: bool) -> Vec<String> {
+    repo.search_ranked(&keywords(terms), match_all).iter().map(|hit| hit.food.id.clone()).collect()
+}
+
+#[test]
+fn partial_words_and_typos_match_name_and_keywords() {
+    let dir = TempDir::new("search-fuzzy");
+    let repo = repo(&dir);
+
+    assert_eq!(ids(&repo, &["chick"], false), vec!["chicken", "chickpeas"]);
+    assert_eq!(ids(&repo, &["butter"], false), vec!["pb"]);
+    assert_eq!(ids(&repo, &["nut"], false), vec!["pb"]);
+    assert_eq!(ids(&repo, &["peanut butter"], false), vec!["pb"]);
+    assert_eq!(ids(&repo, &["brocoli"], false), vec!["broccoli"]);
+    assert_eq!(ids(&repo, &["bnaana"], false), vec!["banana"]);
+    assert_eq!(ids(&repo, &["vegetabel"], false), vec!["broccoli"]);
+
+    // Short terms are never matched fuzzily
+    assert!(ids(&repo, &["pba"], false).is_empty());
+}
+
+#[test]
+fn results_are_ranked_by_match_quality() {
+    let dir = TempDir::new("search-rank");
+    let repo = repo(&dir);
+
+    // Matching more terms, and matching them exactly, ranks higher
+    let hits = repo.search_ranked(&keywords(&["chick", "legume"]), false);
+    assert_eq!(hits[0].food.id, "chickpeas");
+    assert_eq!(hits[0].best, MatchQuality::Exact);
+    assert_eq!((hits[1].food.id.as_str(), hits[1].best), ("chicken", MatchQuality::Prefix));
+    assert!(hits[0].score > hits[1].score);
+
+    // With AND logic every term must match, however loosely
+    assert_eq!(ids(&repo, &["chiken", "protien"], true), vec!["chicken"]);
+    assert!(ids(&repo, &["chicken", "fruit"], true).is_empty());
+
+    assert_eq!(search::edit_distance("kitten", "sitting"), 3);
+    assert_eq!(search::edit_distance("ab", "ba"), 1);
+    assert_eq!(search::max_distance("egg"), 0);
+}
+
+#[test]
+fn index_follows_added_updated_and_removed_foods() {
+    let dir = TempDir::new("search-index");
+    let mut repo = repo(&dir);
+    repo.save().unwrap();
+
+    let mut banana = repo.get_food("banana").unwrap().clone();
+    banana.keywords = keywords(&["snack"]);
+    repo.update_food(banana).unwrap();
+    assert!(repo.search_foods(&keywords(&["fruit"]), false, &[]).is_empty());
+    assert_eq!(ids(&repo, &["snack"], false), vec!["banana"]);
+
+    repo.add_food(Food::new_basic("kiwi".into(), "Kiwi".into(), keywords(&["fruit"]), 42.0)).unwrap();
+    assert_eq!(ids(&repo, &["fruit"], false), vec!["kiwi"]);
+    assert!(repo.remove_food("kiwi").is_some());
+    assert!(repo.search_foods(&keywords(&["fruit"]), false, &[]).is_empty());
+
+    // Changes made directly to the map are picked up by the next search
+    repo.get_foods_mut().get_mut("pb").unwrap().keywords.insert("fruit".into());
+    assert_eq!(repo.search_foods(&keywords(&["fruit", "spread"]), true, &[]).len(), 1);
+
+    // Reloading rebuilds the index from the file
+    repo.load().unwrap();
+    assert_eq!(ids(&repo, &["fruit"], false), vec!["banana"]);
+
+    // The index is built by the first search after a load, so it includes
+    // foods added before that search
+    repo.load().unwrap();
+    repo.add_food(Food::new_basic("kiwi".into(), "Kiwi".into(), keywords(&["fruit"]), 42.0)).unwrap();
+    assert_eq!(repo.search_foods(&keywords(&["fruit"]), false, &[]).len(), 2);
+
+    // Candidates never miss a food the ranking would match
+    let index = SearchIndex::build(repo.get_foods().values());
+    for term in ["chick", "nut", "brocoli", "bnaana", "egg", "breast"] {
+        let candidates = index.candidates(term).unwrap();
+        for hit in search::rank(repo.get_foods().values(), &keywords(&[term]), false) {
+            assert!(candidates.contains(&hit.food.id), "{} should offer {}", term, hit.food.id);
+        }
+    }
+    assert!(index.candidates("peanut butter").is_none());
+}
+
+#[test]
+fn similar_foods_are_suggested_for_searches_that_find_nothing() {
+    let dir = TempDir::new("search-similar");
+    let repo = repo(&dir);
+    let foods = || repo.get_foods().values();
+
+    let terms = keywords(&["chikn"]);
+    assert!(search::rank(foods(), &terms, false).is_empty(), "too many edits for a fuzzy match");
+    let names: Vec<&str> = search::similar(foods(), &terms, 5).iter().map(|food| food.name.as_str()).collect();
+    assert_eq!(names, vec!["Chicken Breast (4 oz)"], "chickpeas is too different");
+    assert!(search::similar(foods(), &terms, 0).is_empty());
+
+    assert_eq!(search::similar(foods(), &keywords(&["peanut buter"]), 5)[0].id, "pb", "whole names are compared too");
+    assert!(search::similar(foods(), &keywords(&["xyz"]), 5).is_empty());
+    assert!(search::similar(foods(), &keywords(&[]), 5).is_empty());
+    assert!((search::similarity("chikn", "chicken") - 5.0 / 7.0).abs() < 1e-9);
+}
+
+#[test]
+fn nutrient_filters_narrow_keyword_searches() {
+    let filters = nutrient_filter::parse_filters("calories<=150, Protein > 10g,carbohydrates = 0").unwrap();
+    assert_eq!(filters, [
+        NutrientFilter::new(Nutrient::Calories, Comparison::AtMost, 150.0),
+        NutrientFilter::new(Nutrient::Protein, Comparison::Greater, 10.0),
+        NutrientFilter::new(Nutrient::Carbs, Comparison::Equal, 0.0),
+    ]);
+    assert_eq!(filters[1].to_string(), "protein > 10 g");
+    assert!(nutrient_filter::parse_filters("  ").unwrap().is_empty());
+    assert_eq!(nutrient_filter::parse_filters("sugar < 5").unwrap_err(),
+               "'sugar' is not a nutrient; use calories, protein, carbs, fat, fiber, alcohol, caffeine");
+    assert_eq!(nutrient_filter::parse_filters("caffeine < 5 g").unwrap_err(), "caffeine is measured in mg, not g");
+    assert!(nutrient_filter::parse_filters("fat < lots").is_err());
+
+    let dir = TempDir::new("nutrient-filters");
+    let mut repo = repo(&dir);
+    repo.add_food(Food::new_basic("tofu".into(), "Tofu".into(), keywords(&["protein", "vegan"]), 80.0)
+        .with_macros(Macros::new(9.0, 2.0, 5.0))).unwrap();
+    repo.add_food(Food::new_basic("shake".into(), "Protein Shake".into(), keywords(&["protein"]), 160.0)
+        .with_macros(Macros::new(25.0, 8.0, 3.0))).unwrap();
+    let found = |keywords: &[&str], match_all: bool, filters: &str| {
+        let mut ids: Vec<String> = repo.search_foods(&common::keywords(keywords), match_all, &nutrient_filter::parse_filters(filters).unwrap())
+            .iter()
+            .map(|food| food.id.clone())
+            .collect();
+        ids.sort();
+        ids
+    };
+    assert_eq!(found(&["protein"], false, ""), ["chicken", "shake", "tofu"]);
+    assert_eq!(found(&["protein"], false, "calories < 150"), ["chicken", "tofu"]);
+    assert_eq!(found(&["protein"], false, "calories < 200, protein > 10 g"), ["shake"]);
+    // Without keywords, AND logic filters every food
+    assert_eq!(found(&[], true, "calories <= 80"), ["tofu"]);
+}
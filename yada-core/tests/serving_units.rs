@@ -0,0 +1,80 @@
+//! Integration tests for serving sizes and unit conversion
+
+// yada-core/tests/serving_units.rs
+mod common;
+
+use common::{TempDir, assert_close, basic_food, keywords};
+use yada_core::models::food::{Food, Macros};
+use yada_core::models::serving::{ServingSize, ServingUnit, parse_quantity};
+use yada_core::repositories::food_repository::FoodRepository;
+
+fn milk() -> Food {
+    Food::new_basic("milk".into(), "Milk".into(), keywords(&["dairy"]), 150.0)
+        .with_serving_size(Some(ServingSize::new(1.0, ServingUnit::Cup)))
+}
+
+#[test]
+fn quantities_parse_with_and_without_units() {
+    assert_eq!(parse_quantity("2"), Ok((2.0, None)));
+    assert_eq!(parse_quantity("1.5 servings"), Ok((1.5, None)));
+    assert_eq!(parse_quantity("85g"), Ok((85.0, Some(ServingUnit::Gram))));
+    assert_eq!(parse_quantity(" 2 Cups "), Ok((2.0, Some(ServingUnit::Cup))));
+    assert!(parse_quantity("0 g").is_err());
+    assert!(parse_quantity("3 spoons").is_err());
+    assert!(parse_quantity("g").is_err());
+}
+
+#[test]
+fn units_convert_only_within_their_kind() {
+    assert_close(ServingUnit::Ounce.convert(1.0, ServingUnit::Gram).unwrap(), 28.3495);
+    assert_close(ServingUnit::Milliliter.convert(473.176, ServingUnit::Cup).unwrap(), 2.0);
+    assert_eq!(ServingUnit::Gram.convert(100.0, ServingUnit::Milliliter), None);
+    assert_eq!(ServingUnit::Piece.convert(1.0, ServingUnit::Gram), None);
+}
+
+#[test]
+fn amounts_convert_to_servings_through_the_serving_size() {
+    let milk = milk();
+    assert_close(milk.servings_for(2.0, None).unwrap(), 2.0);
+    assert_close(milk.servings_for(0.5, Some(ServingUnit::Cup)).unwrap(), 0.5);
+    assert_close(milk.servings_for(473.176, Some(ServingUnit::Milliliter)).unwrap(), 2.0);
+    assert!(milk.servings_for(100.0, Some(ServingUnit::Gram)).is_err());
+}
+
+#[test]
+fn masses_convert_through_the_serving_weight() {
+    let chicken = basic_food("chicken", 165.0, Macros::default(), 100.0);
+    assert_close(chicken.servings_for(85.0, Some(ServingUnit::Gram)).unwrap(), 0.85);
+    assert_close(chicken.servings_for(1.0, Some(ServingUnit::Ounce)).unwrap(), 0.283495);
+    assert!(chicken.servings_for(1.0, Some(ServingUnit::Piece)).is_err());
+
+    // A serving size given as a mass also defines the serving weight
+    let oats = Food::new_basic("oats".into(), "Oats".into(), keywords(&["oats"]), 150.0)
+        .with_serving_size(Some(ServingSize::new(40.0, ServingUnit::Gram)));
+    assert_eq!(oats.grams_per_serving, Some(40.0));
+}
+
+#[test]
+fn composite_components_can_be_measured_in_units() {
+    let mut foods = std::collections::HashMap::new();
+    foods.insert("milk".to_string(), milk());
+    let servings = foods["milk"].servings_for(120.0, Some(ServingUnit::Milliliter)).unwrap();
+
+    let mut latte = Food::new_composite("latte".into(), "Latte".into(), keywords(&["coffee"]), vec![("milk".into(), servings)]);
+    latte.calculate_from_components(&foods);
+    assert_close(latte.calories_per_serving, 150.0 * 120.0 / 236.588);
+}
+
+#[test]
+fn serving_size_survives_a_round_trip() {
+    let dir = TempDir::new("serving-round-trip");
+    let path = dir.file("foods.txt");
+    let mut repo = FoodRepository::new(&path).unwrap();
+    repo.add_food(milk()).unwrap();
+    repo.save().unwrap();
+
+    let reloaded = FoodRepository::new(&path).unwrap();
+    let milk = reloaded.get_food("milk").unwrap();
+    assert_eq!(milk.serving_size, Some(ServingSize::new(1.0, ServingUnit::Cup)));
+    assert_eq!(milk.grams_per_serving, None);
+}
@@ -0,0 +1,101 @@
+//! Integration tests for atomic writes, backups and autosave policies
+
+// yada-core/tests/storage.rs
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use common::TempDir;
+use yada_core::repositories::autosave::{AutosavePolicy, AutosaveTrigger, Autosaver};
+use yada_core::repositories::storage::{backup_path, write_atomic, write_data_file};
+
+#[test]
+fn write_atomic_rotates_backups() {
+    let dir = TempDir::new("storage-rotate");
+    let path = dir.file("foods.txt");
+
+    for version in 1..=4 {
+        write_atomic(&path, format!("v{}", version).as_bytes(), 2).unwrap();
+    }
+
+    assert_eq!(fs::read_to_string(&path).unwrap(), "v4");
+    assert_eq!(fs::read_to_string(backup_path(&path, 1)).unwrap(), "v3");
+    assert_eq!(fs::read_to_string(backup_path(&path, 2)).unwrap(), "v2");
+    assert!(!Path::new(&backup_path(&path, 3)).exists());
+    assert!(!Path::new(&format!("{}.tmp", path)).exists());
+}
+
+#[test]
+fn backups_keep_the_modification_time_of_their_save() {
+    let dir = TempDir::new("storage-backup-times");
+    let path = dir.file("foods.txt");
+    let saved_at = |days: u64| SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000 + days * 86_400);
+    let set_modified = |time| fs::File::options().write(true).open(&path).unwrap().set_modified(time).unwrap();
+    let modified = |path: String| fs::metadata(path).unwrap().modified().unwrap();
+
+    write_atomic(&path, b"v1", 3).unwrap();
+    set_modified(saved_at(1));
+    write_atomic(&path, b"v2", 3).unwrap();
+    set_modified(saved_at(2));
+    write_atomic(&path, b"v3", 3).unwrap();
+
+    assert_eq!(modified(backup_path(&path, 1)), saved_at(2));
+    assert_eq!(modified(backup_path(&path, 2)), saved_at(1));
+}
+
+#[test]
+fn write_atomic_without_backups_only_replaces_the_file() {
+    let dir = TempDir::new("storage-no-backups");
+    let path = dir.file("logs.txt");
+
+    write_atomic(&path, b"old", 0).unwrap();
+    write_atomic(&path, b"new", 0).unwrap();
+
+    assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+    assert!(!Path::new(&backup_path(&path, 1)).exists());
+}
+
+#[test]
+fn saving_unchanged_contents_keeps_the_backups() {
+    let dir = TempDir::new("storage-unchanged");
+    let path = dir.file("foods.txt");
+
+    write_atomic(&path, b"v1", 3).unwrap();
+    write_atomic(&path, b"v2", 3).unwrap();
+    for _ in 0..5 {
+        write_atomic(&path, b"v2", 3).unwrap();
+        write_data_file(&path, b"v2").unwrap();
+    }
+
+    assert_eq!(fs::read_to_string(&path).unwrap(), "v2");
+    assert_eq!(fs::read_to_string(backup_path(&path, 1)).unwrap(), "v1");
+    assert!(!Path::new(&backup_path(&path, 2)).exists());
+    assert!(!Path::new(&format!("{}.tmp", path)).exists());
+}
+
+#[test]
+fn autosave_policy_parsing() {
+    assert_eq!(AutosavePolicy::parse("command"), Some(AutosavePolicy::AfterEveryCommand));
+    assert_eq!(AutosavePolicy::parse(" OFF "), Some(AutosavePolicy::Disabled));
+    assert_eq!(
+        AutosavePolicy::parse("5"),
+        Some(AutosavePolicy::Interval(Duration::from_secs(300)))
+    );
+    assert_eq!(AutosavePolicy::parse("0"), None);
+    assert_eq!(AutosavePolicy::parse("sometimes"), None);
+}
+
+#[test]
+fn autosaver_respects_policy_and_trigger() {
+    let every_command = Autosaver::new(AutosavePolicy::AfterEveryCommand);
+    assert!(every_command.is_due(AutosaveTrigger::Command));
+    assert!(!every_command.is_due(AutosaveTrigger::Tick));
+
+    let disabled = Autosaver::new(AutosavePolicy::Disabled);
+    assert!(!disabled.is_due(AutosaveTrigger::Command));
+
+    let interval = Autosaver::new(AutosavePolicy::Interval(Duration::from_secs(3600)));
+    assert!(!interval.is_due(AutosaveTrigger::Tick));
+}
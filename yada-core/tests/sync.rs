@@ -0,0 +1,120 @@
+//! Integration tests for syncing data files with a backend
+
+// yada-core/tests/sync.rs
+mod common;
+
+use std::fs::{self, File};
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use common::TempDir;
+use yada_core::sync::local::FolderBackend;
+use yada_core::sync::{self, Side, SyncAction, SyncBackend};
+
+/// Writes a file with a last-modified time `age` seconds in the past
+fn write_aged(path: &Path, contents: &str, age: u64) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, contents).unwrap();
+    let file = File::options().write(true).open(path).unwrap();
+    file.set_modified(SystemTime::now() - Duration::from_secs(age)).unwrap();
+}
+
+/// Syncs `names` and returns the action for each file
+fn sync_actions(data: &TempDir, backend: &FolderBackend, names: &[&str], prefer: Option<Side>) -> Vec<SyncAction> {
+    let names: Vec<String> = names.iter().map(|name| name.to_string()).collect();
+    let report = sync::sync_files(data.path(), &names, backend, prefer, false).unwrap();
+    report.files.iter().map(|file| file.action).collect()
+}
+
+#[test]
+fn files_changed_on_one_side_are_copied_to_the_other() {
+    let data = TempDir::new("sync-data");
+    let remote = TempDir::new("sync-remote");
+    let backend = FolderBackend::new(remote.path());
+    write_aged(&data.path().join("foods.txt"), "foods v1", 100);
+    write_aged(&remote.path().join("users/sam/logs.txt"), "logs v1", 100);
+
+    let names = ["foods.txt", "users/sam/logs.txt", "exercise.txt"];
+    let actions = sync_actions(&data, &backend, &names, None);
+    assert_eq!(actions, [SyncAction::Uploaded, SyncAction::Downloaded, SyncAction::Missing]);
+    assert_eq!(fs::read_to_string(remote.path().join("foods.txt")).unwrap(), "foods v1");
+    assert_eq!(fs::read_to_string(data.path().join("users/sam/logs.txt")).unwrap(), "logs v1");
+
+    let actions = sync_actions(&data, &backend, &names, None);
+    assert_eq!(actions, [SyncAction::UpToDate, SyncAction::UpToDate, SyncAction::Missing]);
+
+    // A remote change comes down; the replaced local file is backed up
+    write_aged(&remote.path().join("foods.txt"), "foods v2", 10);
+    let actions = sync_actions(&data, &backend, &names, None);
+    assert_eq!(actions, [SyncAction::Downloaded, SyncAction::UpToDate, SyncAction::Missing]);
+    assert_eq!(fs::read_to_string(data.path().join("foods.txt")).unwrap(), "foods v2");
+    assert_eq!(fs::read_to_string(data.path().join("foods.txt.bak.1")).unwrap(), "foods v1");
+}
+
+#[test]
+fn files_changed_on_both_sides_are_conflicts_until_a_side_is_preferred() {
+    let data = TempDir::new("sync-conflict-data");
+    let remote = TempDir::new("sync-conflict-remote");
+    let backend = FolderBackend::new(remote.path());
+    let local_file = data.path().join("logs.txt");
+    let remote_file = remote.path().join("logs.txt");
+    write_aged(&local_file, "base", 100);
+    assert_eq!(sync_actions(&data, &backend, &["logs.txt"], None), [SyncAction::Uploaded]);
+
+    write_aged(&local_file, "laptop", 20);
+    write_aged(&remote_file, "desktop", 10);
+    assert_eq!(sync_actions(&data, &backend, &["logs.txt"], None), [SyncAction::Conflict]);
+    assert_eq!(fs::read_to_string(&local_file).unwrap(), "laptop");
+    assert_eq!(fs::read_to_string(&remote_file).unwrap(), "desktop");
+
+    // The conflict stays until resolved, even though the newer copy is known
+    assert_eq!(sync_actions(&data, &backend, &["logs.txt"], None), [SyncAction::Conflict]);
+    assert_eq!(sync_actions(&data, &backend, &["logs.txt"], Some(Side::Local)), [SyncAction::Uploaded]);
+    assert_eq!(fs::read_to_string(&remote_file).unwrap(), "laptop");
+    assert_eq!(sync_actions(&data, &backend, &["logs.txt"], None), [SyncAction::UpToDate]);
+}
+
+#[test]
+fn identical_copies_are_not_conflicts_on_the_first_sync() {
+    let data = TempDir::new("sync-identical-data");
+    let remote = TempDir::new("sync-identical-remote");
+    let backend = FolderBackend::new(remote.path());
+    write_aged(&data.path().join("profile.txt"), "same", 50);
+    write_aged(&remote.path().join("profile.txt"), "same", 20);
+    write_aged(&data.path().join("logs.txt"), "mine", 50);
+    write_aged(&remote.path().join("logs.txt"), "theirs", 20);
+
+    let actions = sync_actions(&data, &backend, &["profile.txt", "logs.txt"], None);
+    assert_eq!(actions, [SyncAction::UpToDate, SyncAction::Conflict]);
+    let actions = sync_actions(&data, &backend, &["logs.txt"], Some(Side::Remote));
+    assert_eq!(actions, [SyncAction::Downloaded]);
+    assert_eq!(fs::read_to_string(data.path().join("logs.txt")).unwrap(), "theirs");
+}
+
+#[test]
+fn dry_runs_report_without_copying_or_recording_state() {
+    let data = TempDir::new("sync-dry-data");
+    let remote = TempDir::new("sync-dry-remote");
+    let backend = FolderBackend::new(remote.path());
+    write_aged(&data.path().join("foods.txt"), "foods", 100);
+
+    let names = vec!["foods.txt".to_string()];
+    let report = sync::sync_files(data.path(), &names, &backend, None, true).unwrap();
+    assert_eq!(report.files[0].action, SyncAction::Uploaded);
+    assert!(report.dry_run);
+    assert_eq!(backend.modified("foods.txt").unwrap(), None);
+    assert!(!data.path().join(sync::STATE_FILE).exists());
+}
+
+#[test]
+fn backend_settings_are_parsed() {
+    let backend = sync::backend_from_spec("folder:/mnt/share/yada").unwrap();
+    assert_eq!(backend.describe(), "folder /mnt/share/yada");
+    let backend = sync::backend_from_spec("webdav:https://cloud.example.com/dav/yada/").unwrap();
+    assert_eq!(backend.describe(), "WebDAV https://cloud.example.com/dav/yada");
+
+    assert!(sync::backend_from_spec("webdav:cloud.example.com").is_err());
+    assert!(sync::backend_from_spec("ftp:example.com").is_err());
+    assert!(sync::backend_from_spec("/mnt/share").is_err());
+    assert!(sync::backend_from_spec("folder:").is_err());
+}
@@ -0,0 +1,96 @@
+//! Integration tests for the target modifier pipeline
+
+// yada-core/tests/target_modifier.rs
+mod common;
+
+use chrono::{NaiveDate, Weekday};
+
+use common::{TempDir, assert_close};
+use yada_core::models::diet_preset::DietPreset;
+use yada_core::models::exercise::ExerciseEntry;
+use yada_core::models::life_stage::LifeStage;
+use yada_core::models::profile::{ActivityLevel, DailyProfile, Gender, UserProfile};
+use yada_core::models::weekday_target::TargetOverride;
+use yada_core::repositories::exercise_repository::ExerciseRepository;
+use yada_core::strategies::calorie_calculator::CalorieCalculatorFactory;
+use yada_core::strategies::target_modifier::{ExerciseCredit, TargetModifier, TargetPipeline};
+
+/// Monday, 4 March 2024
+fn monday() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 3, 4).unwrap()
+}
+
+fn profile() -> UserProfile {
+    let mut profile = UserProfile::new(Gender::Female, 165.0, NaiveDate::from_ymd_opt(1990, 1, 1).unwrap());
+    profile.add_or_update_daily_profile(DailyProfile {
+        date: monday(),
+        weight: 60.0,
+        activity_level: ActivityLevel::Sedentary,
+        carried_from: None,
+    });
+    profile
+}
+
+#[test]
+fn the_standard_pipeline_records_each_step_in_order() {
+    let mut profile = profile();
+    profile.life_stage = Some(LifeStage::Breastfeeding { since: monday() });
+    profile.diet_preset = Some(DietPreset::Cutting);
+    profile.weekday_targets.set(Weekday::Mon, Some(TargetOverride::Adjust(200.0)));
+
+    let breakdown = TargetPipeline::standard().apply(&profile, monday(), 2000.0);
+    let steps: Vec<(&str, f64)> = breakdown.adjustments.iter()
+        .map(|adjustment| (adjustment.label.as_str(), adjustment.change))
+        .collect();
+    assert_eq!(steps, [
+        ("Breastfeeding since 2024-03-04", 330.0),
+        ("Cutting Preset Adjustment", -500.0),
+        ("Mon Target Override", 200.0),
+    ]);
+    assert_close(breakdown.base, 2000.0);
+    assert_close(breakdown.target, 2030.0);
+
+    // The calculators' own target is the standard pipeline's
+    let factory = CalorieCalculatorFactory::new();
+    let calculator = factory.get_calculator("mifflin_st_jeor").unwrap();
+    let tdee = calculator.calculate_tdee(&profile, monday());
+    let expected = TargetPipeline::standard().apply(&profile, monday(), tdee);
+    let calculation = calculator.calculate_target_calories(&profile, monday());
+    assert_eq!(calculation.adjustments, expected.adjustments);
+    assert_close(calculation.target, expected.target);
+}
+
+#[test]
+fn steps_that_change_nothing_and_days_without_a_target_are_left_out() {
+    let mut profile = profile();
+    profile.diet_preset = Some(DietPreset::Keto);
+    let breakdown = TargetPipeline::standard().apply(&profile, monday(), 2000.0);
+    assert!(breakdown.adjustments.is_empty());
+    assert_close(breakdown.target, 2000.0);
+
+    profile.weekday_targets.set(Weekday::Mon, Some(TargetOverride::Fixed(2500.0)));
+    let breakdown = TargetPipeline::standard().apply(&profile, monday(), 0.0);
+    assert!(breakdown.adjustments.is_empty());
+    assert_close(breakdown.target, 0.0);
+}
+
+#[test]
+fn exercise_credit_and_custom_modifiers_extend_the_pipeline() {
+    let dir = TempDir::new("target-modifier-exercise");
+    let mut exercises = ExerciseRepository::new(&dir.file("exercise.txt")).unwrap();
+    exercises.add_entry(monday(), ExerciseEntry::new("Cycling".into(), 7.5, 40.0, 60.0));
+
+    struct Floor;
+    impl TargetModifier for Floor {
+        fn modify(&self, _profile: &UserProfile, _date: NaiveDate, target: f64) -> Option<(f64, String)> {
+            Some((target.max(1200.0), "Minimum Target".to_string()))
+        }
+    }
+
+    let pipeline = TargetPipeline::standard().with(ExerciseCredit::new(&exercises)).with(Floor);
+    let breakdown = pipeline.apply(&profile(), monday(), 800.0);
+    assert_eq!(breakdown.adjustments[0].label, "Exercise Credit");
+    assert_close(breakdown.adjustments[0].change, 300.0);
+    assert_eq!(breakdown.adjustments[1].label, "Minimum Target");
+    assert_close(breakdown.target, 1200.0);
+}
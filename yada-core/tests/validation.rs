@@ -0,0 +1,103 @@
+//! Integration tests for the value ranges and food warnings, and their use when loading and importing
+
+// yada-core/tests/validation.rs
+mod common;
+
+use chrono::{Local, NaiveDate};
+
+use common::{TempDir, basic_food, keywords};
+use yada_core::ingest::{self, ConflictPolicy};
+use yada_core::ingest::records::FoodRecord;
+use yada_core::models::food::{Food, Macros};
+use yada_core::models::serving::{ServingSize, ServingUnit};
+use yada_core::models::validation;
+use yada_core::repositories::food_repository::FoodRepository;
+use yada_core::repositories::profile_repository::ProfileRepository;
+
+#[test]
+fn heights_weights_and_birth_dates_have_ranges() {
+    assert_eq!(validation::height_cm(165.0), Ok(165.0));
+    assert!(validation::height_cm(1.65).is_err());
+    assert!(validation::height_cm(272.5).is_err());
+    assert!(validation::height_cm(f64::NAN).is_err());
+
+    assert_eq!(validation::weight_kg(20.0), Ok(20.0));
+    assert!(validation::weight_kg(0.0).is_err());
+    assert_eq!(validation::weight_kg(615.0), Err("Weight must be between 20 and 500 kg.".to_string()));
+
+    let today = NaiveDate::from_ymd_opt(2024, 3, 5).unwrap();
+    assert!(validation::birth_date(today, today).is_ok());
+    assert_eq!(
+        validation::birth_date(today.succ_opt().unwrap(), today),
+        Err("Birth date cannot be in the future.".to_string())
+    );
+    assert!(validation::birth_date(NaiveDate::from_ymd_opt(1894, 3, 6).unwrap(), today).is_ok());
+    assert!(validation::birth_date(NaiveDate::from_ymd_opt(1894, 3, 5).unwrap(), today).is_err());
+}
+
+#[test]
+fn loading_leaves_out_values_out_of_range() {
+    let dir = TempDir::new("validation-load");
+    let path = dir.file("profile.txt");
+    let next_year = Local::now().date_naive().format("%Y").to_string().parse::<i32>().unwrap() + 1;
+    std::fs::write(&path, format!("#yada:profile:v1\n\
+        PROFILE|M|1.8|{}-01-01|mifflin_st_jeor\n\
+        DAILY|2024-03-04|80|S\n\
+        DAILY|2024-03-05|800|S\n\
+        DAILY|2024-03-06|heavy|S\n\
+        DAILY|someday|80|S\n", next_year)).unwrap();
+
+    let repo = ProfileRepository::new(&path).unwrap();
+    let profile = repo.get_profile().unwrap();
+    assert_eq!(profile.height, None);
+    assert_eq!(profile.birth_date, None);
+    let dates: Vec<NaiveDate> = profile.daily_profiles.iter().map(|daily| daily.date).collect();
+    assert_eq!(dates, [NaiveDate::from_ymd_opt(2024, 3, 4).unwrap()]);
+
+    let report = repo.load_report();
+    assert_eq!(report.summary(), "3 lines skipped, 2 values defaulted");
+    assert_eq!(report.issues()[0].to_string(), "Line 2: height '1.8': Height must be between 50 and 272 cm. Left unknown");
+    assert_eq!(report.issues()[2].to_string(), "Line 4: skipped (weight '800': Weight must be between 20 and 500 kg.)");
+}
+
+#[test]
+fn foods_with_suspicious_values_are_pointed_out() {
+    assert!(validation::food_warnings(&basic_food("toast", 80.0, Macros::new(3.0, 15.0, 1.0), 30.0)).is_empty());
+    // Labels round, and macros need not be given at all
+    assert!(validation::food_warnings(&basic_food("egg", 70.0, Macros::new(6.0, 0.0, 5.0), 50.0)).is_empty());
+    assert!(validation::food_warnings(&basic_food("bread", 250.0, Macros::default(), 100.0)).is_empty());
+
+    let warnings = validation::food_warnings(&basic_food("cracker", 0.0, Macros::default(), 10.0));
+    assert_eq!(warnings, ["The food has 0 kcal per serving, which is rare for solid foods."]);
+    let water = basic_food("water", 0.0, Macros::default(), 250.0)
+        .with_serving_size(Some(ServingSize::new(250.0, ServingUnit::Milliliter)));
+    assert!(validation::food_warnings(&water).is_empty());
+
+    let warnings = validation::food_warnings(&basic_food("pizza", 2200.0, Macros::new(90.0, 250.0, 95.0), 900.0));
+    assert_eq!(warnings, ["The food has 2200 kcal per serving, more than 900. Is the serving a whole package?"]);
+
+    let warnings = validation::food_warnings(&basic_food("oats", 150.0, Macros::new(50.0, 27.0, 3.0), 40.0));
+    assert_eq!(warnings, ["The macros add up to 335 kcal, but the food has 150 kcal per serving."]);
+    let wine = basic_food("wine", 125.0, Macros::new(0.0, 4.0, 0.0).with_alcohol_and_caffeine(16.0, 0.0), 150.0);
+    assert!(validation::food_warnings(&wine).is_empty());
+
+    let composite = Food::new_composite("meal".into(), "Meal".into(), keywords(&["meal"]), vec![("cracker".into(), 1.0)]);
+    assert!(validation::food_warnings(&composite).is_empty());
+}
+
+#[test]
+fn imports_keep_suspicious_foods_and_list_the_warnings() {
+    let dir = TempDir::new("validation-import");
+    let mut repo = FoodRepository::new(&dir.file("foods.txt")).unwrap();
+    let records = [
+        FoodRecord { id: "rice".into(), name: "Rice".into(), calories: 130.0, ..FoodRecord::default() },
+        FoodRecord { id: "cake".into(), name: "Cake".into(), calories: 3500.0, ..FoodRecord::default() },
+    ];
+
+    let report = ingest::import_foods(&mut repo, &records, ConflictPolicy::Replace);
+    assert_eq!(report.added, 2);
+    assert!(report.is_clean());
+    assert_eq!(report.warnings.len(), 1);
+    assert_eq!(report.warnings[0].index, 1);
+    assert_eq!(report.warnings[0].message, "cake: The food has 3500 kcal per serving, more than 900. Is the serving a whole package?");
+}
@@ -0,0 +1,60 @@
+//! Integration tests for week boundaries and weekly totals
+
+// yada-core/tests/week.rs
+mod common;
+
+use std::collections::HashMap;
+
+use chrono::{NaiveDate, Weekday};
+
+use common::{assert_close, basic_food};
+use yada_core::models::food::Macros;
+use yada_core::models::log::DailyLog;
+use yada_core::models::week::{self, WeekStart};
+
+fn date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 3, day).unwrap()
+}
+
+fn log(day: u32, servings: f64) -> DailyLog {
+    let mut log = DailyLog::new(date(day));
+    log.add_entry("oats".into(), servings);
+    log
+}
+
+#[test]
+fn weeks_start_on_the_configured_day() {
+    // March 3, 2024 is a Sunday
+    assert_eq!(WeekStart::Monday.week_of(date(3)), NaiveDate::from_ymd_opt(2024, 2, 26).unwrap());
+    assert_eq!(WeekStart::Sunday.week_of(date(3)), date(3));
+    assert_eq!(WeekStart::Monday.week_of(date(4)), date(4));
+    assert_eq!(WeekStart::Sunday.week_of(date(9)), date(3));
+
+    assert_eq!(WeekStart::Sunday.weekdays()[..2], [Weekday::Sun, Weekday::Mon]);
+    assert_eq!(WeekStart::Monday.weekdays()[6], Weekday::Sun);
+    assert_eq!(WeekStart::parse(" SUN "), Some(WeekStart::Sunday));
+    assert_eq!(WeekStart::parse("friday"), None);
+}
+
+#[test]
+fn weekly_totals_group_logged_days_by_week() {
+    let foods: HashMap<_, _> = [basic_food("oats", 150.0, Macros::new(5.0, 27.0, 3.0), 40.0)]
+        .into_iter()
+        .map(|food| (food.id.clone(), food))
+        .collect();
+    let logs = vec![log(2, 1.0), log(3, 2.0), log(4, 1.0), DailyLog::new(date(5)), log(20, 1.0)];
+
+    let monday = week::weekly_totals(&logs, &foods, WeekStart::Monday);
+    let starts: Vec<NaiveDate> = monday.iter().map(|totals| totals.start).collect();
+    assert_eq!(starts, vec![NaiveDate::from_ymd_opt(2024, 2, 26).unwrap(), date(4), date(18)]);
+    assert_eq!(monday[0].days_logged, 2);
+    assert_close(monday[0].calories, 450.0);
+    assert_close(monday[0].average_calories(), 225.0);
+    assert_close(monday[0].macros.protein, 15.0);
+
+    // Starting on Sunday moves March 3 into the week of March 4
+    let sunday = week::weekly_totals(&logs, &foods, WeekStart::Sunday);
+    assert_eq!(sunday.len(), 3);
+    assert_eq!((sunday[1].start, sunday[1].end()), (date(3), date(9)));
+    assert_close(sunday[1].calories, 450.0);
+}